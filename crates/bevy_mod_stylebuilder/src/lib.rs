@@ -7,6 +7,7 @@ mod builder_layout;
 mod builder_outline;
 mod builder_visibility;
 mod builder_z_index;
+mod provenance;
 mod style_builder;
 mod style_commands;
 mod style_params;
@@ -30,6 +31,7 @@ pub use builder_layout::StyleBuilderLayout;
 pub use builder_outline::StyleBuilderOutline;
 pub use builder_visibility::StyleBuilderVisibility;
 pub use builder_z_index::StyleBuilderZIndex;
+pub use provenance::{StyleProvenance, StyleSource};
 pub use style_builder::StyleBuilder;
 pub use style_commands::StyleCommands;
 pub use style_params::*;
@@ -61,7 +63,7 @@ impl StyleTuple for () {
 
 impl<F: Fn(&mut StyleBuilder) + Send + Sync + 'static> StyleTuple for F {
     fn apply(&self, ctx: &mut StyleBuilder) {
-        (self)(ctx);
+        ctx.apply_traced(std::any::type_name::<F>(), self);
     }
 
     fn into_handle(self) -> StyleHandle {
@@ -0,0 +1,207 @@
+//! Tracking of which style function set each style property, for debugging.
+
+use std::{fmt, panic::Location};
+
+use bevy::{prelude::*, utils::HashMap};
+
+#[cfg(feature = "provenance")]
+use crate::{InheritableFont, InheritableFontColor, InheritableFontSize, StyleBuilder};
+
+/// Identifies the style function which last set a style property.
+#[derive(Clone, Copy, Debug)]
+pub struct StyleSource {
+    /// Full type name of the style function, such as `my_app::style_aside`.
+    pub style: &'static str,
+
+    /// Source location where the style was applied to the entity, if known.
+    pub location: Option<&'static Location<'static>>,
+}
+
+impl StyleSource {
+    /// The style function name without its module path, e.g. `style_aside`. For closures,
+    /// this is the name of the enclosing function.
+    pub fn short_name(&self) -> &'static str {
+        // Strip generic arguments before splitting the path.
+        let path = self.style.split('<').next().unwrap_or(self.style);
+        let mut segments = path.rsplit("::").filter(|s| !s.is_empty());
+        match segments.next() {
+            Some("{{closure}}") => segments.find(|s| *s != "{{closure}}").unwrap_or(path),
+            Some(name) => name,
+            None => path,
+        }
+    }
+}
+
+impl fmt::Display for StyleSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.style.contains("{{closure}}") {
+            write!(f, "closure in {}", self.short_name())?;
+        } else {
+            write!(f, "{}", self.short_name())?;
+        }
+        if let Some(location) = self.location {
+            write!(f, " in {}:{}", location.file(), location.line())?;
+        }
+        Ok(())
+    }
+}
+
+/// Records, for each style property of an entity, which style function set it last. This is
+/// only populated when the `provenance` feature is enabled; it is intended for debugging tools
+/// such as the UI debugger.
+#[derive(Component, Default, Clone, Debug)]
+pub struct StyleProvenance(pub HashMap<&'static str, StyleSource>);
+
+impl StyleProvenance {
+    /// Return the source of the given property, e.g. `"width"`.
+    pub fn get(&self, property: &str) -> Option<&StyleSource> {
+        self.0.get(property)
+    }
+
+    /// Return all recorded properties, sorted by property name.
+    pub fn sorted(&self) -> Vec<(&'static str, StyleSource)> {
+        let mut entries: Vec<_> = self.0.iter().map(|(k, v)| (*k, *v)).collect();
+        entries.sort_by_key(|(k, _)| *k);
+        entries
+    }
+}
+
+/// Copy of all of the style properties of an entity, used to work out which properties a style
+/// function changed.
+#[cfg(feature = "provenance")]
+pub(crate) struct StyleSnapshot {
+    node: Node,
+    background_color: Option<BackgroundColor>,
+    border_color: Option<BorderColor>,
+    border_radius: Option<BorderRadius>,
+    outline: Option<Outline>,
+    visibility: Option<Visibility>,
+    z_index: Option<ZIndex>,
+    font: Option<InheritableFont>,
+    font_size: Option<InheritableFontSize>,
+    font_color: Option<InheritableFontColor>,
+}
+
+#[cfg(feature = "provenance")]
+macro_rules! changed_fields {
+    ($before: expr, $after: expr, $out: expr, $( $field: ident ),* $(,)?) => {
+        $(
+            if $before.$field != $after.$field {
+                $out.push(stringify!($field));
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "provenance")]
+impl StyleSnapshot {
+    pub(crate) fn take(sb: &StyleBuilder) -> Self {
+        let target = &sb.target;
+        Self {
+            node: sb.style.clone(),
+            background_color: target.get::<BackgroundColor>().cloned(),
+            border_color: target.get::<BorderColor>().cloned(),
+            border_radius: target.get::<BorderRadius>().cloned(),
+            outline: target.get::<Outline>().cloned(),
+            visibility: target.get::<Visibility>().cloned(),
+            z_index: target.get::<ZIndex>().cloned(),
+            font: target.get::<InheritableFont>().cloned(),
+            font_size: target.get::<InheritableFontSize>().cloned(),
+            font_color: target.get::<InheritableFontColor>().cloned(),
+        }
+    }
+
+    /// Names of the properties which differ between the two snapshots.
+    pub(crate) fn changed(&self, after: &Self) -> Vec<&'static str> {
+        let mut result = Vec::new();
+        changed_fields!(
+            self.node,
+            after.node,
+            result,
+            display,
+            position_type,
+            overflow,
+            left,
+            right,
+            top,
+            bottom,
+            width,
+            height,
+            min_width,
+            min_height,
+            max_width,
+            max_height,
+            aspect_ratio,
+            align_items,
+            justify_items,
+            align_self,
+            justify_self,
+            align_content,
+            justify_content,
+            margin,
+            padding,
+            border,
+            flex_direction,
+            flex_wrap,
+            flex_grow,
+            flex_shrink,
+            flex_basis,
+            row_gap,
+            column_gap,
+            grid_auto_flow,
+            grid_template_rows,
+            grid_template_columns,
+            grid_auto_rows,
+            grid_auto_columns,
+            grid_row,
+            grid_column,
+        );
+        changed_fields!(
+            self,
+            after,
+            result,
+            background_color,
+            border_color,
+            border_radius,
+            outline,
+            visibility,
+            z_index,
+            font,
+            font_size,
+            font_color,
+        );
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(style: &'static str) -> StyleSource {
+        StyleSource {
+            style,
+            location: None,
+        }
+    }
+
+    #[test]
+    fn test_short_name() {
+        assert_eq!(source("app::ui::style_aside").short_name(), "style_aside");
+        assert_eq!(source("style_aside").short_name(), "style_aside");
+        assert_eq!(source("app::ui::build::{{closure}}").short_name(), "build");
+        assert_eq!(
+            source("app::ui::build::{{closure}}::{{closure}}").short_name(),
+            "build"
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(source("app::style_aside").to_string(), "style_aside");
+        assert_eq!(
+            source("app::build::{{closure}}").to_string(),
+            "closure in build"
+        );
+    }
+}
@@ -1,6 +1,8 @@
 #![allow(missing_docs)]
 //! Defines fluent builder for styles.
 
+use std::panic::Location;
+
 use bevy::{asset::AssetPath, prelude::*, ui};
 
 /// An object that provides a fluent interface for defining styles for bevy_ui nodes.
@@ -10,6 +12,8 @@ pub struct StyleBuilder<'a, 'w> {
     pub target: &'a mut EntityWorldMut<'w>,
     pub(crate) style: ui::Node,
     pub(crate) style_changed: bool,
+    #[cfg(feature = "provenance")]
+    pub(crate) location: Option<&'static Location<'static>>,
 }
 
 impl<'a, 'w> StyleBuilder<'a, 'w> {
@@ -19,6 +23,50 @@ impl<'a, 'w> StyleBuilder<'a, 'w> {
             target,
             style,
             style_changed: false,
+            #[cfg(feature = "provenance")]
+            location: None,
+        }
+    }
+
+    /// Set the source location the styles are being applied from. This is recorded in the
+    /// [`StyleProvenance`](crate::StyleProvenance) of the target when the `provenance` feature
+    /// is enabled, and ignored otherwise.
+    pub fn set_location(&mut self, location: &'static Location<'static>) {
+        #[cfg(feature = "provenance")]
+        {
+            self.location = Some(location);
+        }
+        #[cfg(not(feature = "provenance"))]
+        let _ = location;
+    }
+
+    /// Run a style function. When the `provenance` feature is enabled, `name` is recorded as
+    /// the source of every style property which the function changed.
+    pub fn apply_traced(&mut self, name: &'static str, style_fn: impl FnOnce(&mut Self)) {
+        #[cfg(feature = "provenance")]
+        {
+            let before = crate::provenance::StyleSnapshot::take(self);
+            style_fn(self);
+            let changed = before.changed(&crate::provenance::StyleSnapshot::take(self));
+            if changed.is_empty() {
+                return;
+            }
+            let source = crate::StyleSource {
+                style: name,
+                location: self.location,
+            };
+            if !self.target.contains::<crate::StyleProvenance>() {
+                self.target.insert(crate::StyleProvenance::default());
+            }
+            let mut provenance = self.target.get_mut::<crate::StyleProvenance>().unwrap();
+            for property in changed {
+                provenance.0.insert(property, source);
+            }
+        }
+        #[cfg(not(feature = "provenance"))]
+        {
+            let _ = name;
+            style_fn(self);
         }
     }
 
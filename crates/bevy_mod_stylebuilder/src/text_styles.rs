@@ -1,17 +1,17 @@
 #![allow(missing_docs)]
 
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashSet};
 
 /// Path to the font asset.
-#[derive(Component, Default, Clone, Debug)]
+#[derive(Component, Default, Clone, Debug, PartialEq)]
 pub struct InheritableFont(pub Handle<Font>);
 
 /// Inherited size of the font.
-#[derive(Component, Default, Clone, Debug)]
+#[derive(Component, Default, Clone, Debug, PartialEq)]
 pub struct InheritableFontSize(pub f32);
 
 /// Inherited text color.
-#[derive(Component, Default, Clone, Debug)]
+#[derive(Component, Default, Clone, Debug, PartialEq)]
 pub struct InheritableFontColor(pub Color);
 
 /// Struct that holds the properties for text rendering, which can be inherited. This allows
@@ -44,20 +44,62 @@ impl ComputedFontStyles {
 #[derive(Component)]
 pub struct UseInheritedTextStyles;
 
+/// Recompute the text styles of text nodes whose inherited styles may have changed. A change
+/// to an inheritable style property, the removal of one, or the re-parenting of an entity
+/// affects every text node beneath that entity, so the descendants of each such entity are
+/// walked and their styles re-resolved.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn update_text_styles(
-    mut query: Query<(Entity, &mut Text), With<UseInheritedTextStyles>>,
+    q_text: Query<(), With<UseInheritedTextStyles>>,
+    q_changed_text: Query<Entity, (With<UseInheritedTextStyles>, Changed<Text>)>,
+    q_changed_styles: Query<
+        Entity,
+        Or<(
+            Changed<InheritableFont>,
+            Changed<InheritableFontColor>,
+            Changed<InheritableFontSize>,
+            Changed<Parent>,
+        )>,
+    >,
+    mut removed_font: RemovedComponents<InheritableFont>,
+    mut removed_color: RemovedComponents<InheritableFontColor>,
+    mut removed_size: RemovedComponents<InheritableFontSize>,
+    mut removed_parent: RemovedComponents<Parent>,
     q_inherited_font: Query<Ref<InheritableFont>, ()>,
     q_inherited_color: Query<Ref<InheritableFontColor>, ()>,
     q_inherited_size: Query<Ref<InheritableFontSize>, ()>,
+    q_children: Query<&Children>,
     parents: Query<&Parent>,
     mut commands: Commands,
 ) {
-    let inherited_changed = q_inherited_font.iter().any(|cmp| cmp.is_changed())
-        || q_inherited_color.iter().any(|cmp| cmp.is_changed())
-        || q_inherited_size.iter().any(|cmp| cmp.is_changed());
-    for (entity, text) in query.iter_mut() {
-        if text.is_changed() || inherited_changed {
-            commands.entity(entity).insert(compute_inherited_style(
+    let mut dirty: HashSet<Entity> = q_changed_text.iter().collect();
+    let roots = q_changed_styles
+        .iter()
+        .chain(removed_font.read())
+        .chain(removed_color.read())
+        .chain(removed_size.read())
+        .chain(removed_parent.read());
+    let mut visited: HashSet<Entity> = HashSet::new();
+    for root in roots {
+        let mut stack = vec![root];
+        while let Some(entity) = stack.pop() {
+            if !visited.insert(entity) {
+                // Already visited as a descendant of another root, along with its subtree.
+                continue;
+            }
+            if q_text.contains(entity) {
+                dirty.insert(entity);
+            }
+            if let Ok(children) = q_children.get(entity) {
+                stack.extend(children.iter());
+            }
+        }
+    }
+
+    for entity in dirty {
+        // The entity may have been despawned since its components were removed.
+        if let Some(mut cmd) = commands.get_entity(entity) {
+            cmd.insert(compute_inherited_style(
                 entity,
                 &q_inherited_font,
                 &q_inherited_color,
@@ -132,3 +174,117 @@ fn compute_inherited_style(
     };
     (style, color)
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::SystemId;
+
+    use super::*;
+
+    fn setup() -> (World, SystemId) {
+        let mut world = World::default();
+        let system = world.register_system(update_text_styles);
+        (world, system)
+    }
+
+    fn spawn_chain(world: &mut World, root: Entity, depth: usize) -> Entity {
+        let mut parent = root;
+        for _ in 0..depth {
+            parent = world.spawn_empty().set_parent(parent).id();
+        }
+        parent
+    }
+
+    fn font_size(world: &World, entity: Entity) -> f32 {
+        world.get::<TextFont>(entity).unwrap().font_size
+    }
+
+    #[test]
+    fn test_deep_propagation() {
+        let (mut world, system) = setup();
+        let root = world.spawn(InheritableFontSize(20.)).id();
+        let parent = spawn_chain(&mut world, root, 8);
+        let text = world
+            .spawn((Text::new("text"), UseInheritedTextStyles))
+            .set_parent(parent)
+            .id();
+        world.run_system(system).unwrap();
+        assert_eq!(font_size(&world, text), 20.);
+
+        world.get_mut::<InheritableFontSize>(root).unwrap().0 = 30.;
+        world.run_system(system).unwrap();
+        assert_eq!(font_size(&world, text), 30.);
+    }
+
+    #[test]
+    fn test_nearest_ancestor_wins() {
+        let (mut world, system) = setup();
+        let root = world.spawn(InheritableFontSize(20.)).id();
+        let middle = spawn_chain(&mut world, root, 3);
+        world.entity_mut(middle).insert(InheritableFontSize(10.));
+        let parent = spawn_chain(&mut world, middle, 3);
+        let text = world
+            .spawn((Text::new("text"), UseInheritedTextStyles))
+            .set_parent(parent)
+            .id();
+        world.run_system(system).unwrap();
+        assert_eq!(font_size(&world, text), 10.);
+
+        // A change above the override doesn't affect the text.
+        world.get_mut::<InheritableFontSize>(root).unwrap().0 = 30.;
+        world.run_system(system).unwrap();
+        assert_eq!(font_size(&world, text), 10.);
+
+        // Removing the override exposes the root's size.
+        world.entity_mut(middle).remove::<InheritableFontSize>();
+        world.run_system(system).unwrap();
+        assert_eq!(font_size(&world, text), 30.);
+    }
+
+    #[test]
+    fn test_changed_text_root_with_spans() {
+        let (mut world, system) = setup();
+        let root = world
+            .spawn((
+                Text::new("text"),
+                UseInheritedTextStyles,
+                InheritableFontSize(20.),
+            ))
+            .id();
+        let span = world
+            .spawn((TextSpan::new("span"), UseInheritedTextStyles))
+            .set_parent(root)
+            .id();
+        world.run_system(system).unwrap();
+        assert_eq!(font_size(&world, span), 20.);
+
+        // Changing the text of the root in the same frame must not hide the style change
+        // from its spans.
+        world.get_mut::<Text>(root).unwrap().0 = "changed".to_string();
+        world.get_mut::<InheritableFontSize>(root).unwrap().0 = 30.;
+        world.run_system(system).unwrap();
+        assert_eq!(font_size(&world, root), 30.);
+        assert_eq!(font_size(&world, span), 30.);
+    }
+
+    #[test]
+    fn test_reparent() {
+        let (mut world, system) = setup();
+        let root_a = world.spawn(InheritableFontColor(Color::BLACK)).id();
+        let root_b = world.spawn(InheritableFontColor(Color::WHITE)).id();
+        let parent_a = spawn_chain(&mut world, root_a, 4);
+        let parent_b = spawn_chain(&mut world, root_b, 4);
+        let subtree = world.spawn_empty().set_parent(parent_a).id();
+        let text = world
+            .spawn((Text::new("text"), UseInheritedTextStyles))
+            .set_parent(subtree)
+            .id();
+        world.run_system(system).unwrap();
+        assert_eq!(world.get::<TextColor>(text).unwrap().0, Color::BLACK);
+
+        // Moving an ancestor of the text to another tree re-resolves its styles.
+        world.entity_mut(subtree).set_parent(parent_b);
+        world.run_system(system).unwrap();
+        assert_eq!(world.get::<TextColor>(text).unwrap().0, Color::WHITE);
+    }
+}
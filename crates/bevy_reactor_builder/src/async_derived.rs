@@ -0,0 +1,103 @@
+//! Signals whose value is computed by a future.
+//!
+//! Futures are spawned on bevy's [`AsyncComputeTaskPool`], which runs them on a background
+//! thread on native platforms, and on the browser's event loop (via `spawn_local`) on wasm,
+//! where threads are not available. Either way, the result is never written to the world from
+//! inside the future: it is sent back over a channel and applied at the start of the next
+//! reaction pass.
+//!
+//! # Re-entrancy
+//!
+//! * The `compute` function runs synchronously inside a reaction during the `Update` schedule.
+//!   It can read signals, which become its dependencies, but the future it returns cannot
+//!   access the world, since it may run on another thread or after the frame has ended.
+//! * Results are applied before reactions run, so a resolving future never triggers a reaction
+//!   re-entrantly. Anything which depends on the signal reacts in the same pass in which the
+//!   result is applied.
+//! * When the dependencies change while a future is still pending, a new future is spawned and
+//!   the result of the old one is discarded. The old future is not cancelled, so it should not
+//!   have side effects.
+
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    mpsc::{channel, Receiver},
+    Arc,
+};
+
+use bevy::tasks::{AsyncComputeTaskPool, ConditionalSendFuture};
+use bevy_reactor_signals::{ChannelClosed, ChannelReceiver, Ecx, Signal};
+
+use crate::{Loadable, UiBuilder};
+
+pub trait AsyncDerivedBuilder {
+    /// Create a signal whose value is produced by a future. `compute` is called to create the
+    /// future, and again whenever any of the signals it reads change. The signal is
+    /// [`Loadable::Loading`] until the most recent future resolves. This is typically
+    /// displayed using `suspense`.
+    fn create_async_derived<T, Fut, F>(&mut self, compute: F) -> Signal<Loadable<T>>
+    where
+        T: PartialEq + Send + Sync + 'static,
+        Fut: ConditionalSendFuture<Output = T> + 'static,
+        F: Fn(&mut Ecx) -> Fut + Send + Sync + 'static;
+}
+
+impl<'w> AsyncDerivedBuilder for UiBuilder<'w> {
+    fn create_async_derived<T, Fut, F>(&mut self, compute: F) -> Signal<Loadable<T>>
+    where
+        T: PartialEq + Send + Sync + 'static,
+        Fut: ConditionalSendFuture<Output = T> + 'static,
+        F: Fn(&mut Ecx) -> Fut + Send + Sync + 'static,
+    {
+        let (sender, receiver) = channel::<(u64, T)>();
+        let generation = Arc::new(AtomicU64::new(0));
+        let signal = self.signal_from_channel(
+            LatestResult {
+                receiver,
+                generation: generation.clone(),
+                seen: 0,
+            },
+            Loadable::Loading,
+        );
+        self.create_effect(move |ecx| {
+            let future = compute(ecx);
+            let current = generation.fetch_add(1, Ordering::AcqRel) + 1;
+            let sender = sender.clone();
+            AsyncComputeTaskPool::get()
+                .spawn(async move {
+                    // The receiver is gone if the signal has been despawned.
+                    let _ = sender.send((current, future.await));
+                })
+                .detach();
+        });
+        signal
+    }
+}
+
+/// Channel adapter which reports `Loading` when a new future is started, and discards the
+/// results of futures which have been superseded.
+struct LatestResult<T> {
+    receiver: Receiver<(u64, T)>,
+    /// Generation of the most recently spawned future.
+    generation: Arc<AtomicU64>,
+    /// Generation which the signal has been updated for.
+    seen: u64,
+}
+
+impl<T: Send + 'static> ChannelReceiver<Loadable<T>> for LatestResult<T> {
+    fn try_receive(&mut self) -> Result<Option<Loadable<T>>, ChannelClosed> {
+        let current = self.generation.load(Ordering::Acquire);
+        if current != self.seen {
+            self.seen = current;
+            return Ok(Some(Loadable::Loading));
+        }
+        loop {
+            match self.receiver.try_receive()? {
+                Some((generation, value)) if generation == current => {
+                    return Ok(Some(Loadable::Ready(value)));
+                }
+                Some(_) => continue,
+                None => return Ok(None),
+            }
+        }
+    }
+}
@@ -3,8 +3,9 @@ use bevy::prelude::*;
 use bevy::ui::experimental::GhostNode;
 use bevy_reactor_signals::{Rcx, Reaction, ReactionCell, TrackingScope};
 
+use crate::memo::rebuild_children;
 use crate::test_condition::TestCondition;
-use crate::{CreateChilden, UiBuilder};
+use crate::UiBuilder;
 
 /// The state of the conditional branch, which is initially "unset".
 #[derive(PartialEq)]
@@ -139,8 +140,7 @@ impl<
         owner: Entity,
         world: &mut World,
     ) {
-        world.entity_mut(owner).despawn_descendants();
-        world.entity_mut(owner).create_children_mut(branch);
+        rebuild_children(world, owner, |builder| branch(builder));
     }
 }
 
@@ -1,9 +1,9 @@
 use bevy::{
     core::Name,
-    prelude::{BuildChildren, Entity, EntityWorldMut, World},
+    prelude::{BuildChildren, Component, Entity, EntityWorldMut, Mut, World},
     ui::experimental::GhostNode,
 };
-use bevy_reactor_signals::{Rcx, Reaction, ReactionCell, TrackingScope};
+use bevy_reactor_signals::{Ecx, Rcx, Reaction, ReactionCell, TrackingScope};
 
 pub trait EntityEffectBuilder {
     fn effect<
@@ -15,6 +15,26 @@ pub trait EntityEffectBuilder {
         deps_fn: VF,
         style: SF,
     ) -> &mut Self;
+
+    /// Mutate the component `C` on this entity whenever the dependencies change. Does nothing
+    /// if the entity does not have the component.
+    fn component_effect<
+        C: Component,
+        D: 'static,
+        VF: Fn(&Rcx) -> D + Send + Sync + 'static,
+        SF: Fn(D, Mut<C>) + Send + Sync + 'static,
+    >(
+        &mut self,
+        deps_fn: VF,
+        effect_fn: SF,
+    ) -> &mut Self;
+
+    /// Create a reactive effect which is passed the id of this entity, so that it can
+    /// modify the entity's components directly.
+    fn entity_effect<F: FnMut(&mut Ecx, Entity) + Send + Sync + 'static>(
+        &mut self,
+        effect: F,
+    ) -> &mut Self;
 }
 
 impl<'w> EntityEffectBuilder for EntityWorldMut<'w> {
@@ -46,6 +66,64 @@ impl<'w> EntityEffectBuilder for EntityWorldMut<'w> {
         });
         self
     }
+
+    fn component_effect<
+        C: Component,
+        D: 'static,
+        VF: Fn(&Rcx) -> D + Send + Sync + 'static,
+        SF: Fn(D, Mut<C>) + Send + Sync + 'static,
+    >(
+        &mut self,
+        deps_fn: VF,
+        effect_fn: SF,
+    ) -> &mut Self {
+        self.effect(deps_fn, move |deps, ent| {
+            if let Some(component) = ent.get_mut::<C>() {
+                effect_fn(deps, component);
+            }
+        })
+    }
+
+    fn entity_effect<F: FnMut(&mut Ecx, Entity) + Send + Sync + 'static>(
+        &mut self,
+        effect: F,
+    ) -> &mut Self {
+        let target = self.id();
+        self.world_scope(|world| {
+            spawn_entity_effect(world, target, effect);
+        });
+        self
+    }
+}
+
+/// Spawn an effect, owned by `target`, which is passed the target entity id.
+pub(crate) fn spawn_entity_effect<F: FnMut(&mut Ecx, Entity) + Send + Sync + 'static>(
+    world: &mut World,
+    target: Entity,
+    effect: F,
+) {
+    let mut scope = TrackingScope::new(world.last_change_tick());
+    let mut reaction = EntityEffectReaction { target, effect };
+    let effect_owner = world.spawn(Name::new("Effect")).set_parent(target).id();
+    reaction.react(effect_owner, world, &mut scope);
+    world.entity_mut(effect_owner).insert((
+        scope,
+        ReactionCell::new(reaction),
+        GhostNode::default(),
+    ));
+}
+
+/// Effect reaction which is passed the id of the target entity.
+struct EntityEffectReaction<F: FnMut(&mut Ecx, Entity)> {
+    target: Entity,
+    effect: F,
+}
+
+impl<F: FnMut(&mut Ecx, Entity)> Reaction for EntityEffectReaction<F> {
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let mut ecx = Ecx::new(world, owner, tracking);
+        (self.effect)(&mut ecx, self.target);
+    }
 }
 
 struct TargetedEffectReaction<D, VF: Fn(&Rcx) -> D, SF: Fn(D, &mut EntityWorldMut)> {
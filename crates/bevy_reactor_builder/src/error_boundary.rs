@@ -0,0 +1,147 @@
+use std::{
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::Arc,
+};
+
+use bevy::core::Name;
+use bevy::ecs::{component::Component, world::World};
+use bevy::prelude::{Commands, DespawnRecursiveExt, Entity, In};
+use bevy::ui::experimental::GhostNode;
+use bevy_reactor_signals::{panic_message, Callback, ReactionPanicHandler};
+
+use crate::{CreateChilden, UiBuilder};
+
+/// Build functions and retry callback for an error boundary.
+#[derive(Component, Clone)]
+#[allow(clippy::type_complexity)]
+struct ErrorBoundaryState {
+    content: Arc<dyn Fn(&mut UiBuilder) + Send + Sync>,
+    fallback: Arc<dyn Fn(&mut UiBuilder, &str, Callback) + Send + Sync>,
+    retry: Callback,
+}
+
+pub trait ErrorBoundaryBuilder {
+    /// Build a node which catches panics, either while building `content` or in the
+    /// reactions of its descendants. When a panic is caught, the content is despawned and
+    /// replaced by `fallback`, which is passed the panic message and a callback which
+    /// rebuilds the content when run.
+    fn error_boundary<
+        ContentFn: Send + Sync + Fn(&mut UiBuilder) + 'static,
+        FallbackFn: Send + Sync + Fn(&mut UiBuilder, &str, Callback) + 'static,
+    >(
+        &mut self,
+        content: ContentFn,
+        fallback: FallbackFn,
+    ) -> &mut Self;
+}
+
+impl<'w> ErrorBoundaryBuilder for UiBuilder<'w> {
+    fn error_boundary<
+        ContentFn: Send + Sync + Fn(&mut UiBuilder) + 'static,
+        FallbackFn: Send + Sync + Fn(&mut UiBuilder, &str, Callback) + 'static,
+    >(
+        &mut self,
+        content: ContentFn,
+        fallback: FallbackFn,
+    ) -> &mut Self {
+        let owner = self
+            .spawn((Name::new("ErrorBoundary"), GhostNode::default()))
+            .id();
+        let world = self.world_mut();
+        let retry = UiBuilder::new(world, owner).create_callback(
+            move |_: In<()>, mut commands: Commands| {
+                commands.queue(move |world: &mut World| build_content(world, owner));
+            },
+        );
+        world.entity_mut(owner).insert((
+            ErrorBoundaryState {
+                content: Arc::new(content),
+                fallback: Arc::new(fallback),
+                retry,
+            },
+            ReactionPanicHandler::new(|world, owner, message| {
+                build_fallback(world, owner, &message);
+            }),
+        ));
+        build_content(world, owner);
+        self
+    }
+}
+
+/// Replace the children of the boundary with the content, or with the fallback if building
+/// the content panics.
+fn build_content(world: &mut World, owner: Entity) {
+    let Some(state) = world.get::<ErrorBoundaryState>(owner).cloned() else {
+        return;
+    };
+    world.entity_mut(owner).despawn_descendants();
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        world
+            .entity_mut(owner)
+            .create_children(|builder| (state.content)(builder));
+    }));
+    if let Err(payload) = result {
+        build_fallback(world, owner, &panic_message(payload.as_ref()));
+    }
+}
+
+/// Replace the children of the boundary with the fallback.
+fn build_fallback(world: &mut World, owner: Entity, message: &str) {
+    let Some(state) = world.get::<ErrorBoundaryState>(owner).cloned() else {
+        return;
+    };
+    world.entity_mut(owner).despawn_descendants();
+    world
+        .entity_mut(owner)
+        .create_children(|builder| (state.fallback)(builder, message, state.retry));
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy_reactor_signals::{CreateMutable, RunCallback, SignalsPlugin};
+
+    use super::*;
+
+    #[derive(Resource)]
+    struct Retry(Callback);
+
+    fn texts(world: &mut World) -> Vec<String> {
+        world
+            .query::<&Text>()
+            .iter(world)
+            .map(|text| text.0.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_fallback_and_retry() {
+        let mut app = App::new();
+        app.add_plugins(SignalsPlugin);
+        let world = app.world_mut();
+        let fail = world.create_mutable(true);
+        let root = world.spawn_empty().id();
+        world.entity_mut(root).create_children(|builder| {
+            builder.error_boundary(
+                move |builder| {
+                    if fail.get(builder.world()) {
+                        panic!("content failed");
+                    }
+                    builder.spawn(Text::new("content"));
+                },
+                |builder, message, retry| {
+                    builder.spawn(Text::new(message));
+                    builder.world_mut().insert_resource(Retry(retry));
+                },
+            );
+        });
+        assert_eq!(texts(world), vec!["content failed"]);
+
+        // Retrying once the cause of the panic is fixed replaces the fallback with the content.
+        fail.set(world, false);
+        let retry = world.resource::<Retry>().0;
+        world.run_callback(retry, ());
+        world.flush();
+        assert_eq!(texts(world), vec!["content"]);
+    }
+}
@@ -0,0 +1,134 @@
+use std::{sync::Arc, time::Duration};
+
+use bevy::prelude::*;
+use bevy::ui::experimental::GhostNode;
+use bevy::utils::Instant;
+use bevy_reactor_signals::{Reaction, ReactionCell, TrackingScope};
+
+use crate::{CreateChilden, UiBuilder, UiTemplate};
+
+type BuildFn = Arc<dyn Fn(&mut UiBuilder) + Send + Sync + 'static>;
+
+/// Builds a list of children a few at a time, spreading the work over multiple frames so that
+/// building a large view doesn't cause a hitch. Each frame, children are built until the time
+/// budget is used up, and the rest are left until the next frame. While there are children
+/// left to build, a placeholder (such as a spinner) is displayed after the ones which have
+/// been built.
+#[derive(Clone)]
+pub struct Incremental {
+    /// Time to spend building children each frame.
+    pub budget: Duration,
+
+    /// Displayed in place of the children which have not been built yet.
+    pub placeholder: Option<BuildFn>,
+
+    /// Build functions for each child, in order.
+    pub steps: Vec<BuildFn>,
+}
+
+impl Default for Incremental {
+    fn default() -> Self {
+        Self {
+            budget: Duration::from_millis(4),
+            placeholder: None,
+            steps: Vec::new(),
+        }
+    }
+}
+
+impl Incremental {
+    /// Create a new incremental builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the time to spend building children each frame.
+    pub fn budget(mut self, budget: Duration) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Set the placeholder which is shown until all of the children are built.
+    pub fn placeholder<V: 'static + Send + Sync + Fn(&mut UiBuilder)>(
+        mut self,
+        placeholder: V,
+    ) -> Self {
+        self.placeholder = Some(Arc::new(placeholder));
+        self
+    }
+
+    /// Add a child to be built. Each child is built all at once, so large views should be
+    /// split into several children.
+    pub fn child<V: 'static + Send + Sync + Fn(&mut UiBuilder)>(mut self, child: V) -> Self {
+        self.steps.push(Arc::new(child));
+        self
+    }
+}
+
+impl UiTemplate for Incremental {
+    fn build(&self, builder: &mut UiBuilder) {
+        let owner = builder
+            .spawn((Name::new("Incremental"), GhostNode::default()))
+            .id();
+        let world = builder.world_mut();
+        let content = world
+            .spawn((Name::new("Incremental::Content"), GhostNode::default()))
+            .set_parent(owner)
+            .id();
+        let placeholder = self.placeholder.as_ref().map(|placeholder| {
+            let placeholder = placeholder.clone();
+            world
+                .spawn((Name::new("Incremental::Placeholder"), GhostNode::default()))
+                .set_parent(owner)
+                .create_children(|builder| placeholder(builder))
+                .id()
+        });
+
+        let mut tracking = TrackingScope::new(world.last_change_tick());
+        let mut reaction = IncrementalReaction {
+            budget: self.budget,
+            steps: self.steps.clone(),
+            next: 0,
+            content,
+            placeholder,
+        };
+        reaction.react(owner, world, &mut tracking);
+        world
+            .entity_mut(owner)
+            .insert((tracking, ReactionCell::new(reaction)));
+    }
+}
+
+/// Reaction which builds the next batch of children each frame.
+struct IncrementalReaction {
+    budget: Duration,
+    steps: Vec<BuildFn>,
+    next: usize,
+    content: Entity,
+    placeholder: Option<Entity>,
+}
+
+impl Reaction for IncrementalReaction {
+    fn react(&mut self, _owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let start = Instant::now();
+        // Always build at least one child, so that we make progress even if the budget is
+        // smaller than the time it takes to build a single child.
+        while self.next < self.steps.len() {
+            let step = self.steps[self.next].clone();
+            world
+                .entity_mut(self.content)
+                .create_children(|builder| step(builder));
+            self.next += 1;
+            if start.elapsed() >= self.budget {
+                break;
+            }
+        }
+
+        if self.next < self.steps.len() {
+            // Run again next frame.
+            tracking.set_deferred_change();
+        } else if let Some(placeholder) = self.placeholder.take() {
+            world.entity_mut(placeholder).despawn_recursive();
+        }
+    }
+}
@@ -11,6 +11,13 @@ pub trait InsertComponentBuilder {
         condition: T,
         factory: F,
     ) -> &mut Self;
+
+    /// Add a computed bundle to the element. The bundle is re-inserted whenever the
+    /// dependencies of the computation change.
+    fn insert_computed<B: Bundle, F: Fn(&Rcx) -> B + Send + Sync + 'static>(
+        &mut self,
+        compute: F,
+    ) -> &mut Self;
 }
 
 impl<'w> InsertComponentBuilder for EntityWorldMut<'w> {
@@ -39,6 +46,32 @@ impl<'w> InsertComponentBuilder for EntityWorldMut<'w> {
         });
         self
     }
+
+    fn insert_computed<B: Bundle, F: Fn(&Rcx) -> B + Send + Sync + 'static>(
+        &mut self,
+        compute: F,
+    ) -> &mut Self {
+        let mut scope = TrackingScope::new(self.world().last_change_tick());
+        let mut reaction = ComputedInsertReaction {
+            target: self.id(),
+            compute,
+        };
+        let owner = self.id();
+        self.world_scope(|world| {
+            // Spawn a new reaction entity to contain the effect.
+            let effect_owner = world
+                .spawn(Name::new("InsertComputed"))
+                .set_parent(owner)
+                .id();
+            reaction.react(effect_owner, world, &mut scope);
+            world.entity_mut(effect_owner).insert((
+                scope,
+                ReactionCell::new(reaction),
+                GhostNode::default(),
+            ));
+        });
+        self
+    }
 }
 
 pub struct ConditionalInsertComponentReaction<
@@ -69,3 +102,16 @@ impl<C: Component, T: TestCondition, F: Fn() -> C + Send + Sync> Reaction
         }
     }
 }
+
+pub struct ComputedInsertReaction<B: Bundle, F: Fn(&Rcx) -> B + Send + Sync> {
+    target: Entity,
+    compute: F,
+}
+
+impl<B: Bundle, F: Fn(&Rcx) -> B + Send + Sync> Reaction for ComputedInsertReaction<B, F> {
+    fn react(&mut self, _owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let rcx = Rcx::new(world, self.target, tracking);
+        let bundle = (self.compute)(&rcx);
+        world.entity_mut(self.target).insert(bundle);
+    }
+}
@@ -1,23 +1,39 @@
+mod async_derived;
 mod cond;
 mod effect;
+mod error_boundary;
 mod for_each;
 mod for_index;
+mod incremental;
 mod insert;
 mod lcs;
+mod memo;
+mod option;
+mod state_scoped;
 mod style;
+mod suspense;
 mod switch;
 mod test_condition;
 mod text;
 mod ui_builder;
 mod ui_template;
+mod view_root;
 
+pub use async_derived::AsyncDerivedBuilder;
 pub use cond::CondBuilder;
 pub use effect::EntityEffectBuilder;
+pub use error_boundary::ErrorBoundaryBuilder;
 pub use for_each::ForEachBuilder;
 pub use for_index::ForIndexBuilder;
+pub use incremental::Incremental;
 pub use insert::InsertComponentBuilder;
+pub use memo::Memo;
+pub use option::{CondSomeBuilder, OptionSignal, ResultSignal};
+pub use state_scoped::{StateScopedViewExt, StateScopedViewRoot};
 pub use style::EntityStyleBuilder;
+pub use suspense::{Loadable, SuspenseBuilder};
 pub use switch::SwitchBuilder;
-pub use text::TextBuilder;
+pub use text::{NumberFormatCache, TextBuilder, TextSource};
 pub use ui_builder::{CreateChilden, UiBuilder};
-pub use ui_template::{InvokeUiTemplate, UiTemplate};
+pub use ui_template::{InvokeUiTemplate, SlotFn, Slots, SlottedTemplate, UiTemplate};
+pub use view_root::{SpawnReactiveChildren, ViewRoot};
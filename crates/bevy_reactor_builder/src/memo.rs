@@ -0,0 +1,247 @@
+use std::{
+    any::{Any, TypeId},
+    sync::Arc,
+};
+
+use bevy::prelude::{BuildChildren, Children, Component, DespawnRecursiveExt, Entity, Resource};
+use bevy::ui::experimental::GhostNode;
+use bevy::utils::HashMap;
+use bevy::{core::Name, ecs::world::World};
+use bevy_reactor_signals::{Rcx, Reaction, ReactionCell, TrackingScope};
+
+use crate::{CreateChilden, UiBuilder, UiTemplate};
+
+/// A template whose props are computed reactively, and which is only rebuilt when the props
+/// change. The props are the template itself: whenever the dependencies of `props` change,
+/// a new template is computed and compared with the previous one, and if they are equal the
+/// existing subtree is kept as-is.
+///
+/// The subtree is also kept when an enclosing `cond`, `switch` or similar node is rebuilt, if
+/// the new props are equal to the old ones. Memos are matched with their previous instance by
+/// template type and by the order in which they are built, so a memo inside a `for_each` item
+/// is only kept as long as its position among the memos of the same type doesn't change.
+///
+/// Example:
+/// ```ignore
+/// builder.invoke(Memo::new(move |rcx| Label { text: name.get_clone(rcx) }));
+/// ```
+pub struct Memo<T: UiTemplate + PartialEq + Send + Sync + 'static> {
+    /// Function which computes the template to build.
+    pub props: Arc<dyn Fn(&Rcx) -> T + Send + Sync>,
+}
+
+impl<T: UiTemplate + PartialEq + Send + Sync + 'static> Clone for Memo<T> {
+    fn clone(&self) -> Self {
+        Self {
+            props: self.props.clone(),
+        }
+    }
+}
+
+impl<T: UiTemplate + PartialEq + Send + Sync + 'static> Memo<T> {
+    /// Create a new memoized template from a function which computes its props.
+    pub fn new<F: Fn(&Rcx) -> T + Send + Sync + 'static>(props: F) -> Self {
+        Self {
+            props: Arc::new(props),
+        }
+    }
+}
+
+impl<T: UiTemplate + PartialEq + Send + Sync + 'static> UiTemplate for Memo<T> {
+    fn build(&self, builder: &mut UiBuilder) {
+        let parent = builder.parent();
+        let world = builder.world_mut();
+
+        // If an enclosing node is being rebuilt, re-use the memo from before the rebuild.
+        let type_id = TypeId::of::<T>();
+        let detached = world
+            .get_resource_mut::<MemoStash>()
+            .and_then(|mut stash| {
+                let frame = stash.0.last_mut()?;
+                let ordinal = frame.built.entry(type_id).or_default();
+                let key = (type_id, *ordinal);
+                *ordinal += 1;
+                frame.detached.remove(&key)
+            })
+            .filter(|owner| world.get_entity(*owner).is_ok());
+        let owner = match detached {
+            Some(owner) => world.entity_mut(owner).set_parent(parent).id(),
+            None => world
+                .spawn((Name::new("Memo"), GhostNode::default()))
+                .set_parent(parent)
+                .id(),
+        };
+
+        // The reaction is replaced even when the memo is re-used, since the new props function
+        // may depend on different signals. If the props are unchanged, nothing is rebuilt.
+        let mut tracking = TrackingScope::new(world.last_change_tick());
+        let mut reaction = MemoTemplateReaction {
+            props: self.props.clone(),
+        };
+        reaction.react(owner, world, &mut tracking);
+        world
+            .entity_mut(owner)
+            .insert((tracking, ReactionCell::new(reaction)));
+    }
+}
+
+/// Component on the owner of a [`Memo`], holding the template it was last built with.
+#[derive(Component)]
+struct MemoState {
+    type_id: TypeId,
+    template: Box<dyn Any + Send + Sync>,
+}
+
+/// A reaction which rebuilds the template when its props change.
+struct MemoTemplateReaction<T: UiTemplate + PartialEq + Send + Sync + 'static> {
+    props: Arc<dyn Fn(&Rcx) -> T + Send + Sync>,
+}
+
+impl<T: UiTemplate + PartialEq + Send + Sync + 'static> Reaction for MemoTemplateReaction<T> {
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let rcx = Rcx::new(world, owner, tracking);
+        let template = (self.props)(&rcx);
+        let current = world
+            .get::<MemoState>(owner)
+            .and_then(|state| state.template.downcast_ref::<T>());
+        if current == Some(&template) {
+            return;
+        }
+
+        rebuild_children(world, owner, |builder| template.build(builder));
+        world.entity_mut(owner).insert(MemoState {
+            type_id: TypeId::of::<T>(),
+            template: Box::new(template),
+        });
+    }
+}
+
+/// Memos which were detached from an enclosing node while it is being rebuilt. Since rebuilds
+/// can be nested, this is a stack with one frame per rebuild in progress.
+#[derive(Resource, Default)]
+struct MemoStash(Vec<StashFrame>);
+
+#[derive(Default)]
+struct StashFrame {
+    /// Owners of the detached memos, by template type and the order they were built in.
+    detached: HashMap<(TypeId, usize), Entity>,
+    /// The number of memos of each template type built so far during the rebuild.
+    built: HashMap<TypeId, usize>,
+}
+
+/// Despawn the children of `owner`, and build new ones. Memos among the old descendants are
+/// detached first, so that the memos in the new children can re-use them if their props are
+/// unchanged; any which aren't re-used are despawned afterwards.
+pub(crate) fn rebuild_children(
+    world: &mut World,
+    owner: Entity,
+    build: impl FnOnce(&mut UiBuilder),
+) {
+    let mut frame = StashFrame::default();
+    detach_memos(world, owner, &mut frame);
+    frame.built.clear();
+    world.entity_mut(owner).despawn_descendants();
+
+    world.init_resource::<MemoStash>();
+    let depth = world.resource::<MemoStash>().0.len();
+    world.resource_mut::<MemoStash>().0.push(frame);
+    world.entity_mut(owner).create_children(build);
+
+    // Also drops frames left behind by nested rebuilds which panicked.
+    let frames = world.resource_mut::<MemoStash>().0.split_off(depth);
+    for frame in frames {
+        for memo in frame.detached.into_values() {
+            if let Ok(entity) = world.get_entity_mut(memo) {
+                entity.despawn_recursive();
+            }
+        }
+    }
+}
+
+/// Detach the outermost memos below `entity`, in the order they were built.
+fn detach_memos(world: &mut World, entity: Entity, frame: &mut StashFrame) {
+    let Some(children) = world.get::<Children>(entity) else {
+        return;
+    };
+    for child in children.to_vec() {
+        if let Some(state) = world.get::<MemoState>(child) {
+            let ordinal = frame.built.entry(state.type_id).or_default();
+            frame.detached.insert((state.type_id, *ordinal), child);
+            *ordinal += 1;
+            world.entity_mut(child).remove_parent();
+        } else {
+            detach_memos(world, child, frame);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy_reactor_signals::{CreateMutable, Mutable, SignalsPlugin};
+
+    use super::*;
+    use crate::{CondBuilder, InvokeUiTemplate, ViewRoot};
+
+    #[derive(Clone, PartialEq)]
+    struct Label(i32);
+
+    impl UiTemplate for Label {
+        fn build(&self, builder: &mut UiBuilder) {
+            builder.spawn(Text::new(self.0.to_string()));
+        }
+    }
+
+    struct Panel {
+        flag: Mutable<bool>,
+        value: Mutable<i32>,
+    }
+
+    impl UiTemplate for Panel {
+        fn build(&self, builder: &mut UiBuilder) {
+            let flag = self.flag;
+            let value = self.value;
+            builder.cond(
+                move |rcx: &Rcx| flag.get(rcx),
+                move |builder| {
+                    builder.invoke(Memo::new(move |rcx| Label(value.get(rcx))));
+                },
+                move |builder| {
+                    builder.invoke(Memo::new(move |rcx| Label(value.get(rcx))));
+                },
+            );
+        }
+    }
+
+    fn labels(world: &mut World) -> Vec<Entity> {
+        world
+            .query_filtered::<Entity, With<Text>>()
+            .iter(world)
+            .collect()
+    }
+
+    #[test]
+    fn test_memo_kept_across_parent_rebuild() {
+        let mut app = App::new();
+        app.add_plugins(SignalsPlugin);
+        let world = app.world_mut();
+        let flag = world.create_mutable(true);
+        let value = world.create_mutable(1);
+        let root = world.spawn_empty().id();
+        ViewRoot::under(world, root, &Panel { flag, value });
+        let before = labels(world);
+        assert_eq!(before.len(), 1);
+
+        // The parent is rebuilt, but the memo's props are unchanged.
+        flag.set(app.world_mut(), false);
+        app.update();
+        assert_eq!(labels(app.world_mut()), before);
+
+        // The props change, so the memo is rebuilt.
+        value.set(app.world_mut(), 2);
+        app.update();
+        let after = labels(app.world_mut());
+        assert_eq!(after.len(), 1);
+        assert_ne!(after, before);
+    }
+}
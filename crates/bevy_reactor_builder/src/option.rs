@@ -0,0 +1,154 @@
+use bevy::prelude::Entity;
+use bevy::ui::experimental::GhostNode;
+use bevy::{core::Name, ecs::world::World};
+use bevy_reactor_signals::{Rcx, Reaction, ReactionCell, Signal, TrackingScope};
+
+use crate::memo::rebuild_children;
+use crate::UiBuilder;
+
+/// Combinators for signals of `Option` type. The resulting signals are owned by the builder's
+/// parent entity.
+pub trait OptionSignal<T> {
+    /// Returns a signal which applies `f` to the value, if there is one.
+    fn map_some<U: Send + Sync + 'static, F: Send + Sync + 'static + Fn(&T) -> U>(
+        &self,
+        builder: &mut UiBuilder,
+        f: F,
+    ) -> Signal<Option<U>>;
+
+    /// Returns a signal which contains the value, or `default` if there is none.
+    fn unwrap_or(&self, builder: &mut UiBuilder, default: T) -> Signal<T>;
+
+    /// Returns a signal which is true if there is a value. Readers are only notified when this
+    /// changes, not when the value changes.
+    fn is_some_signal(&self, builder: &mut UiBuilder) -> Signal<bool>;
+}
+
+impl<T: Clone + Send + Sync + 'static> OptionSignal<T> for Signal<Option<T>> {
+    fn map_some<U: Send + Sync + 'static, F: Send + Sync + 'static + Fn(&T) -> U>(
+        &self,
+        builder: &mut UiBuilder,
+        f: F,
+    ) -> Signal<Option<U>> {
+        let signal = self.clone();
+        builder.create_derived(move |rcx| signal.map(rcx, |value| value.as_ref().map(&f)))
+    }
+
+    fn unwrap_or(&self, builder: &mut UiBuilder, default: T) -> Signal<T> {
+        let signal = self.clone();
+        builder.create_derived(move |rcx| {
+            signal.map(rcx, |value| {
+                value.clone().unwrap_or_else(|| default.clone())
+            })
+        })
+    }
+
+    fn is_some_signal(&self, builder: &mut UiBuilder) -> Signal<bool> {
+        let signal = self.clone();
+        builder.create_derived_eq(move |rcx| signal.map(rcx, Option::is_some))
+    }
+}
+
+/// Combinators for signals of `Result` type. The resulting signals are owned by the builder's
+/// parent entity.
+pub trait ResultSignal<T, E> {
+    /// Returns a signal which contains the success value, if there is one.
+    fn ok_signal(&self, builder: &mut UiBuilder) -> Signal<Option<T>>;
+
+    /// Returns a signal which contains the error, if there is one.
+    fn err_signal(&self, builder: &mut UiBuilder) -> Signal<Option<E>>;
+
+    /// Returns a signal which is true if the result is a success. Readers are only notified
+    /// when this changes.
+    fn is_ok_signal(&self, builder: &mut UiBuilder) -> Signal<bool>;
+}
+
+impl<T: Clone + Send + Sync + 'static, E: Clone + Send + Sync + 'static> ResultSignal<T, E>
+    for Signal<Result<T, E>>
+{
+    fn ok_signal(&self, builder: &mut UiBuilder) -> Signal<Option<T>> {
+        let signal = self.clone();
+        builder.create_derived(move |rcx| signal.map(rcx, |value| value.as_ref().ok().cloned()))
+    }
+
+    fn err_signal(&self, builder: &mut UiBuilder) -> Signal<Option<E>> {
+        let signal = self.clone();
+        builder.create_derived(move |rcx| signal.map(rcx, |value| value.as_ref().err().cloned()))
+    }
+
+    fn is_ok_signal(&self, builder: &mut UiBuilder) -> Signal<bool> {
+        let signal = self.clone();
+        builder.create_derived_eq(move |rcx| signal.map(rcx, Result::is_ok))
+    }
+}
+
+pub trait CondSomeBuilder {
+    /// Build a reactive node which renders `some` with the unwrapped value when the signal
+    /// contains a value, and nothing otherwise. The child is rebuilt whenever the value changes.
+    fn cond_some<
+        T: Clone + PartialEq + Send + Sync + 'static,
+        SomeFn: Send + Sync + Fn(&mut UiBuilder, T) + 'static,
+    >(
+        &mut self,
+        signal: Signal<Option<T>>,
+        some: SomeFn,
+    ) -> &mut Self;
+}
+
+impl<'w> CondSomeBuilder for UiBuilder<'w> {
+    fn cond_some<
+        T: Clone + PartialEq + Send + Sync + 'static,
+        SomeFn: Send + Sync + Fn(&mut UiBuilder, T) + 'static,
+    >(
+        &mut self,
+        signal: Signal<Option<T>>,
+        some: SomeFn,
+    ) -> &mut Self {
+        let owner = self
+            .spawn((Name::new("CondSome"), GhostNode::default()))
+            .id();
+
+        let world = self.world_mut();
+        let mut tracking = TrackingScope::new(world.last_change_tick());
+        let mut reaction = CondSomeReaction {
+            signal,
+            some,
+            shown: None,
+        };
+        reaction.react(owner, world, &mut tracking);
+        world
+            .entity_mut(owner)
+            .insert((tracking, ReactionCell::new(reaction)));
+        self
+    }
+}
+
+/// A reaction which rebuilds the child when the value changes.
+struct CondSomeReaction<T, SomeFn>
+where
+    Self: Send + Sync,
+{
+    signal: Signal<Option<T>>,
+    some: SomeFn,
+    /// The value which is currently displayed, or `None` before the first reaction.
+    shown: Option<Option<T>>,
+}
+
+impl<T: Clone + PartialEq + Send + Sync + 'static, SomeFn: Send + Sync + Fn(&mut UiBuilder, T)>
+    Reaction for CondSomeReaction<T, SomeFn>
+{
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let rcx = Rcx::new(world, owner, tracking);
+        let value = self.signal.get_clone(&rcx);
+        if self.shown.as_ref() == Some(&value) {
+            return;
+        }
+
+        rebuild_children(world, owner, |builder| {
+            if let Some(value) = value.clone() {
+                (self.some)(builder, value);
+            }
+        });
+        self.shown = Some(value);
+    }
+}
@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+use bevy::ui::experimental::GhostNode;
+
+use crate::{CreateChilden, UiTemplate};
+
+/// Component which marks the root of a view that exists only while the app is in a given
+/// state. Added by [`StateScopedViewExt::add_state_view`].
+#[derive(Component, Clone, Debug)]
+pub struct StateScopedViewRoot<S: States>(pub S);
+
+pub trait StateScopedViewExt {
+    /// Build `template` as a new view root whenever the app enters `state`, and despawn it,
+    /// along with everything it owns, when the app leaves `state`.
+    fn add_state_view<S: States, T: UiTemplate + Send + Sync + 'static>(
+        &mut self,
+        state: S,
+        template: T,
+    ) -> &mut Self;
+}
+
+impl StateScopedViewExt for App {
+    fn add_state_view<S: States, T: UiTemplate + Send + Sync + 'static>(
+        &mut self,
+        state: S,
+        template: T,
+    ) -> &mut Self {
+        let enter_state = state.clone();
+        let exit_state = state.clone();
+        self.add_systems(OnEnter(state.clone()), move |world: &mut World| {
+            world
+                .spawn((
+                    Name::new(format!("StateView::{:?}", enter_state)),
+                    GhostNode::default(),
+                    StateScopedViewRoot(enter_state.clone()),
+                ))
+                .create_children(|builder| template.build(builder));
+        })
+        .add_systems(
+            OnExit(state),
+            move |mut commands: Commands, q_roots: Query<(Entity, &StateScopedViewRoot<S>)>| {
+                for (root, root_state) in q_roots.iter() {
+                    if root_state.0 == exit_state {
+                        commands.entity(root).despawn_recursive();
+                    }
+                }
+            },
+        )
+    }
+}
@@ -1,3 +1,5 @@
+use std::{any::type_name, panic::Location};
+
 use bevy::{
     prelude::{BuildChildren, Entity, EntityWorldMut, World},
     ui::{self, experimental::GhostNode},
@@ -20,28 +22,33 @@ pub trait EntityStyleBuilder {
 }
 
 impl<'w> EntityStyleBuilder for EntityWorldMut<'w> {
+    #[track_caller]
     fn style<S: FnOnce(&mut StyleBuilder)>(&mut self, style_fn: S) -> &mut Self {
         let mut style = ui::Node::default();
         if let Some(s) = self.get::<ui::Node>() {
             style.clone_from(s);
         }
         let mut sb = StyleBuilder::new(self, style);
-        style_fn(&mut sb);
+        sb.set_location(Location::caller());
+        sb.apply_traced(type_name::<S>(), style_fn);
         sb.finish();
         self
     }
 
+    #[track_caller]
     fn styles(&mut self, styles: impl StyleTuple) -> &mut Self {
         let mut style = ui::Node::default();
         if let Some(s) = self.get::<ui::Node>() {
             style.clone_from(s);
         }
         let mut sb = StyleBuilder::new(self, style);
+        sb.set_location(Location::caller());
         styles.apply(&mut sb);
         sb.finish();
         self
     }
 
+    #[track_caller]
     fn style_dyn<
         D: 'static,
         VF: Fn(&Rcx) -> D + Send + Sync + 'static,
@@ -56,6 +63,7 @@ impl<'w> EntityStyleBuilder for EntityWorldMut<'w> {
             target: self.id(),
             deps_fn,
             style_fn,
+            location: Location::caller(),
         };
         let owner = self.id();
         self.world_scope(|world| {
@@ -76,6 +84,7 @@ struct DynamicStyleReaction<D, VF: Fn(&Rcx) -> D, SF: Fn(D, &mut StyleBuilder)>
     target: Entity,
     deps_fn: VF,
     style_fn: SF,
+    location: &'static Location<'static>,
 }
 
 impl<
@@ -94,7 +103,8 @@ impl<
             style.clone_from(s);
         }
         let mut sb = StyleBuilder::new(&mut target, style);
-        (self.style_fn)(val, &mut sb);
+        sb.set_location(self.location);
+        sb.apply_traced(type_name::<SF>(), |sb| (self.style_fn)(val, sb));
         sb.finish();
     }
 }
@@ -0,0 +1,113 @@
+use bevy::prelude::Entity;
+use bevy::ui::experimental::GhostNode;
+use bevy::{core::Name, ecs::world::World};
+use bevy_reactor_signals::{Rcx, Reaction, ReactionCell, Signal, TrackingScope};
+
+use crate::memo::rebuild_children;
+use crate::UiBuilder;
+
+/// The state of a value which is produced asynchronously, such as an asset or the result of
+/// a background task.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Loadable<T> {
+    /// The value is not available yet.
+    #[default]
+    Loading,
+    /// The value is available.
+    Ready(T),
+}
+
+impl<T> Loadable<T> {
+    /// Returns true if the value is available.
+    pub fn is_ready(&self) -> bool {
+        matches!(self, Loadable::Ready(_))
+    }
+
+    /// Returns a reference to the value, if it is available.
+    pub fn ready(&self) -> Option<&T> {
+        match self {
+            Loadable::Loading => None,
+            Loadable::Ready(value) => Some(value),
+        }
+    }
+}
+
+pub trait SuspenseBuilder {
+    /// Build a reactive node which displays `fallback` (typically a skeleton or spinner) while
+    /// the signal is [`Loadable::Loading`], and `content` once it is [`Loadable::Ready`]. The
+    /// content is rebuilt whenever the ready value changes.
+    fn suspense<
+        T: Clone + PartialEq + Send + Sync + 'static,
+        FallbackFn: Send + Sync + Fn(&mut UiBuilder) + 'static,
+        ContentFn: Send + Sync + Fn(&mut UiBuilder, T) + 'static,
+    >(
+        &mut self,
+        state: Signal<Loadable<T>>,
+        fallback: FallbackFn,
+        content: ContentFn,
+    ) -> &mut Self;
+}
+
+impl<'w> SuspenseBuilder for UiBuilder<'w> {
+    fn suspense<
+        T: Clone + PartialEq + Send + Sync + 'static,
+        FallbackFn: Send + Sync + Fn(&mut UiBuilder) + 'static,
+        ContentFn: Send + Sync + Fn(&mut UiBuilder, T) + 'static,
+    >(
+        &mut self,
+        state: Signal<Loadable<T>>,
+        fallback: FallbackFn,
+        content: ContentFn,
+    ) -> &mut Self {
+        let owner = self
+            .spawn((Name::new("Suspense"), GhostNode::default()))
+            .id();
+
+        let world = self.world_mut();
+        let mut tracking = TrackingScope::new(world.last_change_tick());
+        let mut reaction = SuspenseReaction {
+            state,
+            fallback,
+            content,
+            shown: None,
+        };
+        reaction.react(owner, world, &mut tracking);
+        world
+            .entity_mut(owner)
+            .insert((tracking, ReactionCell::new(reaction)));
+        self
+    }
+}
+
+/// A reaction that swaps between the fallback and the content.
+struct SuspenseReaction<T, FallbackFn, ContentFn>
+where
+    Self: Send + Sync,
+{
+    state: Signal<Loadable<T>>,
+    fallback: FallbackFn,
+    content: ContentFn,
+    /// The state which is currently displayed, or `None` before the first reaction.
+    shown: Option<Loadable<T>>,
+}
+
+impl<
+        T: Clone + PartialEq + Send + Sync + 'static,
+        FallbackFn: Send + Sync + Fn(&mut UiBuilder),
+        ContentFn: Send + Sync + Fn(&mut UiBuilder, T),
+    > Reaction for SuspenseReaction<T, FallbackFn, ContentFn>
+{
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let rcx = Rcx::new(world, owner, tracking);
+        let state = self.state.get_clone(&rcx);
+        if self.shown.as_ref() == Some(&state) {
+            return;
+        }
+
+        rebuild_children(world, owner, |builder| match &state {
+            Loadable::Loading => (self.fallback)(builder),
+            Loadable::Ready(value) => (self.content)(builder, value.clone()),
+        });
+        self.shown = Some(state);
+    }
+}
@@ -1,11 +1,12 @@
 #![allow(clippy::type_complexity)]
 
-use bevy::prelude::{BuildChildren, DespawnRecursiveExt, Entity};
+use bevy::prelude::{BuildChildren, Entity};
 use bevy::ui::experimental::GhostNode;
 use bevy::{core::Name, ecs::world::World};
 use bevy_reactor_signals::{Rcx, Reaction, ReactionCell, Signal, TrackingScope};
 
-use crate::{CreateChilden, UiBuilder};
+use crate::memo::rebuild_children;
+use crate::UiBuilder;
 
 /// Trait that abstracts over the switch test value that controls the If. We use this trait
 /// to allow boolean signals to be passed directly as conditions.
@@ -53,7 +54,8 @@ impl<'w> SwitchBuilder for UiBuilder<'w> {
         value_fn: VF,
         cases_fn: CF,
     ) -> &mut Self {
-        let mut cases: Vec<(Value, Box<dyn Fn(&mut UiBuilder) + Send + Sync>)> = Vec::new();
+        let mut cases: Vec<(CaseTest<Value>, Box<dyn Fn(&mut UiBuilder) + Send + Sync>)> =
+            Vec::new();
         let mut fallback: Option<Box<dyn Fn(&mut UiBuilder) + Send + Sync>> = None;
 
         let mut case_builder = CaseBuilder {
@@ -61,7 +63,6 @@ impl<'w> SwitchBuilder for UiBuilder<'w> {
             fallback: &mut fallback,
         };
         cases_fn(&mut case_builder);
-        // TODO: Populate cases
         let mut reaction = SwitchReaction {
             cases,
             fallback,
@@ -89,8 +90,27 @@ impl<'w> SwitchBuilder for UiBuilder<'w> {
     }
 }
 
+/// How a switch case is matched against the switch value.
+enum CaseTest<Value> {
+    /// Matches a single value.
+    Value(Value),
+    /// Matches any value for which the predicate returns true.
+    When(Box<dyn Fn(&Value) -> bool + Send + Sync>),
+}
+
+impl<Value: PartialEq> CaseTest<Value> {
+    fn matches(&self, value: &Value) -> bool {
+        match self {
+            CaseTest::Value(case_value) => case_value == value,
+            CaseTest::When(predicate) => predicate(value),
+        }
+    }
+}
+
+/// Builder for the cases of a switch. Cases are tested in the order they were added, and the
+/// first matching case is built; if no case matches, the fallback is built instead.
 pub struct CaseBuilder<'a, Value: Send + Sync> {
-    cases: &'a mut Vec<(Value, Box<dyn Fn(&mut UiBuilder) + Send + Sync>)>,
+    cases: &'a mut Vec<(CaseTest<Value>, Box<dyn Fn(&mut UiBuilder) + Send + Sync>)>,
     fallback: &'a mut Option<Box<dyn Fn(&mut UiBuilder) + Send + Sync>>,
 }
 
@@ -100,7 +120,22 @@ impl<'a, Value: Send + Sync> CaseBuilder<'a, Value> {
         value: Value,
         case_fn: CF,
     ) -> &mut Self {
-        self.cases.push((value, Box::new(case_fn)));
+        self.cases.push((CaseTest::Value(value), Box::new(case_fn)));
+        self
+    }
+
+    /// Add a case which matches any value for which `predicate` returns true. This is useful
+    /// for grouping several states, or for values which can't be enumerated.
+    pub fn case_when<
+        PF: Send + Sync + 'static + Fn(&Value) -> bool,
+        CF: Send + Sync + 'static + Fn(&mut UiBuilder),
+    >(
+        &mut self,
+        predicate: PF,
+        case_fn: CF,
+    ) -> &mut Self {
+        self.cases
+            .push((CaseTest::When(Box::new(predicate)), Box::new(case_fn)));
         self
     }
 
@@ -120,7 +155,7 @@ where
 {
     test_value: F,
     switch_index: usize,
-    cases: Vec<(Value, Box<dyn Fn(&mut UiBuilder) + Send + Sync>)>,
+    cases: Vec<(CaseTest<Value>, Box<dyn Fn(&mut UiBuilder) + Send + Sync>)>,
     fallback: Option<Box<dyn Fn(&mut UiBuilder) + Send + Sync>>,
 }
 
@@ -135,19 +170,18 @@ impl<Value: Send + Sync + PartialEq, F: SwitchTestValue<Value>> Reaction
             .cases
             .iter()
             .enumerate()
-            .find_map(|(i, f)| if f.0 == value { Some(i) } else { None })
+            .find_map(|(i, f)| if f.0.matches(&value) { Some(i) } else { None })
             .unwrap_or(usize::MAX);
 
         if index != self.switch_index {
             self.switch_index = index;
-            world.entity_mut(owner).despawn_descendants();
-            if index < self.cases.len() {
-                world
-                    .entity_mut(owner)
-                    .create_children_mut(self.cases[index].1.as_mut());
-            } else if let Some(ref fallback) = self.fallback {
-                world.entity_mut(owner).create_children(fallback);
-            };
+            rebuild_children(world, owner, |builder| {
+                if index < self.cases.len() {
+                    (self.cases[index].1)(builder);
+                } else if let Some(ref fallback) = self.fallback {
+                    fallback(builder);
+                }
+            });
         }
     }
 }
@@ -1,6 +1,10 @@
+use std::fmt::Write;
+
 use bevy::prelude::*;
 use bevy_mod_stylebuilder::UseInheritedTextStyles;
-use bevy_reactor_signals::{Rcx, Reaction, ReactionCell, TrackingScope};
+use bevy_reactor_signals::{
+    Derived, Mutable, Rcx, Reaction, ReactionCell, ReadDerived, ReadMutable, Signal, TrackingScope,
+};
 
 use crate::UiBuilder;
 
@@ -107,6 +111,123 @@ impl<F: FnMut(&Rcx) -> String + Send + Sync + 'static> Reaction for TextComputed
     fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
         let re = Rcx::new(world, owner, tracking);
         let text = (self.text_fn)(&re);
-        world.entity_mut(self.node).get_mut::<Text>().unwrap().0 = text;
+        // Only write the text if it changed, to avoid re-layout of unchanged labels.
+        let mut entt = world.entity_mut(self.node);
+        let mut current = entt.get_mut::<Text>().unwrap();
+        if current.0 != text {
+            current.0 = text;
+        }
+    }
+}
+
+/// Caches the formatted text of a number, so that frequently recomputed labels, such as drag
+/// values and frame counters, skip formatting when the number hasn't changed. Typically owned
+/// by the closure passed to [`TextBuilder::text_computed`]; the closure still returns a copy of
+/// the text, which is only written to the text node if it differs from the current text.
+#[derive(Default, Clone, Debug)]
+pub struct NumberFormatCache {
+    key: Option<(u64, usize)>,
+    text: String,
+}
+
+impl NumberFormatCache {
+    /// Create a new, empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Format `value` with `precision` decimal places, reusing the previous result if neither
+    /// has changed.
+    pub fn format(&mut self, value: impl Into<f64>, precision: usize) -> &str {
+        let value = value.into();
+        let key = (value.to_bits(), precision);
+        if self.key != Some(key) {
+            self.key = Some(key);
+            self.text.clear();
+            let _ = write!(self.text, "{:.*}", precision, value);
+        }
+        &self.text
+    }
+}
+
+/// A reactive value which can be interpolated into text by [`text_fmt!`](crate::text_fmt).
+pub trait TextSource: Send + Sync + 'static {
+    /// The type of value produced.
+    type Value;
+
+    /// Read the current value, tracking it as a dependency.
+    fn read(&self, rcx: &Rcx) -> Self::Value;
+}
+
+impl<T: Clone + Send + Sync + 'static> TextSource for Signal<T> {
+    type Value = T;
+
+    fn read(&self, rcx: &Rcx) -> T {
+        self.get_clone(rcx)
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> TextSource for Mutable<T> {
+    type Value = T;
+
+    fn read(&self, rcx: &Rcx) -> T {
+        rcx.read_mutable_clone(self)
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> TextSource for Derived<T> {
+    type Value = T;
+
+    fn read(&self, rcx: &Rcx) -> T {
+        rcx.read_derived_clone(self)
+    }
+}
+
+/// Create a computed text entity from a format string, where each argument is a signal
+/// ([`Signal`], [`Mutable`] or [`Derived`]). The text is updated whenever any of the signals
+/// changes. Only the signals themselves are captured, so the arguments must be positional.
+///
+/// ```ignore
+/// text_fmt!(builder, "{} of {} selected", selected_count, total);
+/// ```
+///
+/// is equivalent to:
+///
+/// ```ignore
+/// builder.text_computed(move |rcx| {
+///     format!("{} of {} selected", selected_count.get(rcx), total.get(rcx))
+/// });
+/// ```
+#[macro_export]
+macro_rules! text_fmt {
+    ($builder: expr, $fmt: literal $(, $arg: expr)* $(,)?) => {
+        $crate::text_fmt!(@bind $builder, $fmt, []; $($arg,)*)
+    };
+
+    // Bind each argument to a local, so that the closure captures only the signals.
+    (@bind $builder: expr, $fmt: literal, [$($bound: ident)*]; $arg: expr, $($rest: expr,)*) => {{
+        let arg = $arg;
+        $crate::text_fmt!(@bind $builder, $fmt, [$($bound)* arg]; $($rest,)*)
+    }};
+
+    (@bind $builder: expr, $fmt: literal, [$($bound: ident)*];) => {
+        $builder.text_computed(move |rcx| {
+            format!($fmt, $( $crate::TextSource::read(&$bound, rcx) ),*)
+        })
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_number_format_cache() {
+        let mut cache = NumberFormatCache::new();
+        assert_eq!(cache.format(1.5f32, 2), "1.50");
+        assert_eq!(cache.format(1.5f32, 2), "1.50");
+        assert_eq!(cache.format(2.75f32, 0), "3");
+        assert_eq!(cache.format(-0.125, 1), "-0.1");
+        assert_eq!(cache.format(3u8, 1), "3.0");
     }
 }
@@ -1,16 +1,21 @@
 use bevy::{
+    asset::Handle,
     core::Name,
     prelude::{
-        BuildChildren, Bundle, Component, DespawnRecursiveExt, Entity, EntityWorldMut, In,
-        IntoSystem, Parent, World,
+        BuildChildren, Bundle, Component, Entity, EntityWorldMut, In, IntoSystem, Parent, World,
     },
+    scene::{DynamicScene, DynamicSceneRoot, Scene, SceneRoot},
     ui::experimental::GhostNode,
 };
 use bevy_reactor_signals::{
-    create_derived, create_mutable, Callback, CallbackOwner, Ecx, Mutable, Rcx, Reaction,
-    ReactionCell, Signal, TrackingScope, WriteMutable,
+    create_derived, create_derived_eq, create_derived_with_eq, create_mutable, signal_from_channel,
+    Callback, CallbackOwner, ChannelReceiver, Ecx, Mutable, Rcx, Reaction, ReactionCell,
+    ReactionPriority, Signal, TrackingScope, WriteMutable,
 };
 
+use crate::effect::spawn_entity_effect;
+use crate::memo::rebuild_children;
+
 pub struct UiBuilder<'w> {
     /// Bevy World
     world: &'w mut World,
@@ -58,6 +63,21 @@ impl<'w> UiBuilder<'w> {
         ent
     }
 
+    /// Spawn a [`Scene`] as a child of the parent entity. The entities of the scene instance
+    /// are parented to the returned root entity, so they will be despawned along with the
+    /// parent (for example, when a condition changes).
+    pub fn spawn_scene(&mut self, scene: Handle<Scene>) -> EntityWorldMut {
+        self.spawn((Name::new("Scene"), SceneRoot(scene)))
+    }
+
+    /// Spawn a [`DynamicScene`] as a child of the parent entity. As with [`spawn_scene`], the
+    /// scene instance is despawned along with the parent.
+    ///
+    /// [`spawn_scene`]: Self::spawn_scene
+    pub fn spawn_dynamic_scene(&mut self, scene: Handle<DynamicScene>) -> EntityWorldMut {
+        self.spawn((Name::new("DynamicScene"), DynamicSceneRoot(scene)))
+    }
+
     /// Return an `EntityWorldMut` for the given entity.
     pub fn entity_mut(&mut self, entity: Entity) -> EntityWorldMut<'_> {
         self.world.entity_mut(entity)
@@ -92,6 +112,17 @@ impl<'w> UiBuilder<'w> {
         create_mutable(self.world, self.parent, init)
     }
 
+    /// Create a [`Signal`] in this context whose value is fed from a channel, such as the
+    /// receiving end of a channel written by a background thread. The signal holds `init`
+    /// until the first value arrives.
+    pub fn signal_from_channel<T, R>(&mut self, receiver: R, init: T) -> Signal<T>
+    where
+        T: PartialEq + Send + Sync + 'static,
+        R: ChannelReceiver<T>,
+    {
+        signal_from_channel(self.world, self.parent, receiver, init)
+    }
+
     /// Create a new [`Derived`] in this context. This represents a readable signal which
     /// is computed from other signals. The result is not memoized, but is recomputed whenever
     /// the dependencies change.
@@ -108,6 +139,41 @@ impl<'w> UiBuilder<'w> {
         Signal::Derived(derived)
     }
 
+    /// Create a new derived in this context, whose readers are only notified when the output
+    /// changes, as determined by `PartialEq`. Unlike [`UiBuilder::create_memo`], the value is
+    /// computed lazily, when it is read.
+    ///
+    /// Arguments:
+    /// * `compute` - The function that computes the output. This will be called with a single
+    ///    parameter, which is an [`Rcx`] object.
+    pub fn create_derived_eq<
+        R: PartialEq + Clone + Send + Sync + 'static,
+        F: Send + Sync + 'static + Fn(&mut Rcx) -> R,
+    >(
+        &mut self,
+        compute: F,
+    ) -> Signal<R> {
+        let derived = create_derived_eq(self.world, compute);
+        self.world.entity_mut(self.parent).add_child(derived.id());
+        Signal::Derived(derived)
+    }
+
+    /// Like [`UiBuilder::create_derived_eq`], but with a custom comparison function, which
+    /// should return true if the two values are equivalent.
+    pub fn create_derived_with_eq<
+        R: Clone + Send + Sync + 'static,
+        F: Send + Sync + 'static + Fn(&mut Rcx) -> R,
+        C: Send + Sync + 'static + Fn(&R, &R) -> bool,
+    >(
+        &mut self,
+        compute: F,
+        eq: C,
+    ) -> Signal<R> {
+        let derived = create_derived_with_eq(self.world, compute, eq);
+        self.world.entity_mut(self.parent).add_child(derived.id());
+        Signal::Derived(derived)
+    }
+
     /// Create a new memoized computation in this context. This represents a readable signal which
     /// is computed from other signals. The result is memoized, which means that downstream
     /// dependants will not be notified unless the output changes.
@@ -155,6 +221,39 @@ impl<'w> UiBuilder<'w> {
         self
     }
 
+    /// Create a reactive effect with the given priority. Expensive effects can be given
+    /// [`ReactionPriority::Low`], so that they run after other reactions and can be postponed
+    /// to a later frame when the reaction budget is exceeded.
+    pub fn create_effect_with_priority<F: Send + Sync + 'static + FnMut(&mut Ecx)>(
+        &mut self,
+        priority: ReactionPriority,
+        effect: F,
+    ) -> &mut Self {
+        let mut scope = TrackingScope::new(self.world().last_change_tick());
+        let mut reaction = EffectReaction { effect };
+        let owner = self.parent;
+        let effect_owner = self.world.spawn_empty().set_parent(owner).id();
+        reaction.react(effect_owner, self.world, &mut scope);
+        self.world.entity_mut(effect_owner).insert((
+            scope,
+            ReactionCell::new(reaction),
+            priority,
+            GhostNode::default(),
+        ));
+        self
+    }
+
+    /// Create a reactive effect which is owned by the `target` entity, and which is passed
+    /// the target entity id. The effect is despawned along with the target.
+    pub fn create_entity_effect<F: Send + Sync + 'static + FnMut(&mut Ecx, Entity)>(
+        &mut self,
+        target: Entity,
+        effect: F,
+    ) -> &mut Self {
+        spawn_entity_effect(self.world, target, effect);
+        self
+    }
+
     /// Return a reference to the Component `C` on the owner entity of the current
     /// context, or one of it's ancestors. This searches up the entity tree until it finds
     /// a component of the given type.
@@ -280,8 +379,6 @@ impl<D, F: Send + Sync + Fn(&Rcx) -> D, B: Send + Sync + Fn(D, &mut UiBuilder)>
         // Create a reactive context and call the test condition.
         let re = Rcx::new(world, owner, tracking);
         let deps: D = (self.compute)(&re);
-        world.entity_mut(owner).despawn_descendants();
-        let mut builder = UiBuilder::new(world, owner);
-        (self.build)(deps, &mut builder);
+        rebuild_children(world, owner, |builder| (self.build)(deps, builder));
     }
 }
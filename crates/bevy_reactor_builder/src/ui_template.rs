@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use crate::UiBuilder;
 
 pub trait UiTemplate {
@@ -14,3 +16,76 @@ impl<'w> InvokeUiTemplate for UiBuilder<'w> {
         self
     }
 }
+
+/// A builder function for one region of a template's content.
+pub type SlotFn = Arc<dyn Fn(&mut UiBuilder) + Send + Sync + 'static>;
+
+/// The content supplied for each named slot of a template.
+#[derive(Clone, Default)]
+pub struct Slots(Vec<(&'static str, SlotFn)>);
+
+impl Slots {
+    /// Set the content for a slot, replacing any previous content.
+    pub fn insert<F: Fn(&mut UiBuilder) + Send + Sync + 'static>(
+        &mut self,
+        name: &'static str,
+        content: F,
+    ) {
+        let content: SlotFn = Arc::new(content);
+        match self.0.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, slot)) => *slot = content,
+            None => self.0.push((name, content)),
+        }
+    }
+
+    /// Returns the content for a slot, if the caller supplied any.
+    pub fn get(&self, name: &str) -> Option<&SlotFn> {
+        self.0
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, slot)| slot)
+    }
+
+    /// Returns true if the caller supplied content for a slot.
+    pub fn has(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Build the content of a slot, if any. Returns true if the slot was filled.
+    pub fn build(&self, name: &str, builder: &mut UiBuilder) -> bool {
+        match self.get(name) {
+            Some(slot) => {
+                (slot.as_ref())(builder);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// A template which accepts content for named regions, such as the header, body and footer of
+/// a dialog, rather than a single flat list of children.
+pub trait SlottedTemplate: UiTemplate + Sized {
+    /// The names of the slots which this template accepts.
+    const SLOTS: &'static [&'static str];
+
+    /// The slot content supplied so far.
+    fn slots_mut(&mut self) -> &mut Slots;
+
+    /// Supply the content for a named slot.
+    fn slot<F: Fn(&mut UiBuilder) + Send + Sync + 'static>(
+        mut self,
+        name: &'static str,
+        content: F,
+    ) -> Self {
+        debug_assert!(
+            Self::SLOTS.contains(&name),
+            "{} has no slot named {:?}; expected one of {:?}",
+            std::any::type_name::<Self>(),
+            name,
+            Self::SLOTS
+        );
+        self.slots_mut().insert(name, content);
+        self
+    }
+}
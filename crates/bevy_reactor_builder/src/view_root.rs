@@ -0,0 +1,56 @@
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+use bevy::ui::experimental::GhostNode;
+
+use crate::{CreateChilden, UiTemplate};
+
+/// Component which marks the root of a reactive view that was mounted under an existing
+/// entity, such as a HUD node created by another plugin. The root is a [`GhostNode`], so the
+/// children built by the template are laid out as children of the existing entity.
+///
+/// Despawning the root removes the view, along with everything it owns; despawning the
+/// existing entity recursively does the same.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct ViewRoot;
+
+impl ViewRoot {
+    /// Build `template` as a new view root under `parent`, and return the root.
+    pub fn under<T: UiTemplate>(world: &mut World, parent: Entity, template: &T) -> Entity {
+        let mut root = world.spawn((Name::new("ViewRoot"), GhostNode::default(), ViewRoot));
+        root.set_parent(parent);
+        root.create_children(|builder| template.build(builder));
+        root.id()
+    }
+}
+
+pub trait SpawnReactiveChildren {
+    /// Build `template` as a reactive view whose children are attached to this entity. The
+    /// children stay reactive for as long as the view root exists.
+    fn spawn_reactive_children<T: UiTemplate + Send + 'static>(&mut self, template: T)
+        -> &mut Self;
+}
+
+impl SpawnReactiveChildren for EntityCommands<'_> {
+    fn spawn_reactive_children<T: UiTemplate + Send + 'static>(
+        &mut self,
+        template: T,
+    ) -> &mut Self {
+        self.queue(move |parent: Entity, world: &mut World| {
+            ViewRoot::under(world, parent, &template);
+        });
+        self
+    }
+}
+
+impl SpawnReactiveChildren for EntityWorldMut<'_> {
+    fn spawn_reactive_children<T: UiTemplate + Send + 'static>(
+        &mut self,
+        template: T,
+    ) -> &mut Self {
+        let parent = self.id();
+        self.world_scope(|world| {
+            ViewRoot::under(world, parent, &template);
+        });
+        self
+    }
+}
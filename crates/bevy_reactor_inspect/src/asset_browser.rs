@@ -0,0 +1,592 @@
+use std::path::{Path, PathBuf};
+
+use bevy::{
+    asset::io::file::FileAssetReader,
+    core::Name,
+    ecs::world::DeferredWorld,
+    prelude::{Children, Click, Component, In, Local, Pointer, Time, Trigger, UVec2, Vec2},
+    ui::{self, Node},
+};
+use bevy_mod_stylebuilder::{
+    StyleBuilder, StyleBuilderBackground, StyleBuilderBorderColor, StyleBuilderBorderRadius,
+    StyleBuilderLayout, StyleHandle, StyleTuple,
+};
+use bevy_reactor_builder::{
+    CondBuilder, CreateChilden, EntityStyleBuilder, ForEachBuilder, InvokeUiTemplate, TextBuilder,
+    UiBuilder, UiTemplate,
+};
+use bevy_reactor_obsidian::{
+    colors,
+    prelude::{DisclosureToggle, Icon, ScrollView, TextInput, Thumbnail},
+    scrolling::CreateScrollSignals,
+    typography,
+};
+use bevy_reactor_signals::{Callback, IntoSignal, Mutable, RunCallback, Signal};
+
+/// Maximum interval between two clicks for them to count as a double-click, in seconds.
+const DOUBLE_CLICK_TIME: f32 = 0.35;
+
+/// Height reserved below each thumbnail for the file name.
+const LABEL_HEIGHT: f32 = 16.;
+
+/// Padding around each cell in the thumbnail grid.
+const CELL_PADDING: f32 = 4.;
+
+/// File extensions which are displayed as image thumbnails.
+const IMAGE_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "bmp", "tga", "webp", "gif", "dds", "ktx2", "hdr", "exr",
+];
+
+/// Component attached to the draggable entries of an [`AssetBrowser`]. When an entry is
+/// dropped, the drop target receives a `Pointer<DragDrop>` event whose `dropped` entity
+/// carries this component, from which it can read the asset path.
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct AssetDragPayload {
+    /// Path of the dragged asset, relative to the asset source.
+    pub path: String,
+}
+
+fn style_browser(sb: &mut StyleBuilder) {
+    sb.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Stretch)
+        .column_gap(4)
+        .min_height(0);
+}
+
+fn style_folder_panel(sb: &mut StyleBuilder) {
+    sb.width(160).flex_shrink(0.).background_color(colors::U1);
+}
+
+fn style_folder_list(sb: &mut StyleBuilder) {
+    sb.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .color(colors::FOREGROUND)
+        .padding(2);
+}
+
+fn style_folder_row(sb: &mut StyleBuilder) {
+    sb.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .min_height(20)
+        .border_radius(3.);
+}
+
+fn style_folder_children(sb: &mut StyleBuilder) {
+    sb.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .padding_left(12);
+}
+
+fn style_files_panel(sb: &mut StyleBuilder) {
+    sb.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch)
+        .flex_grow(1.)
+        .row_gap(4);
+}
+
+fn style_file_grid(sb: &mut StyleBuilder) {
+    sb.flex_grow(1.).background_color(colors::U1);
+}
+
+fn style_file_cell(sb: &mut StyleBuilder) {
+    sb.position(ui::PositionType::Absolute)
+        .display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Center)
+        .padding(CELL_PADDING)
+        .border(1)
+        .border_radius(4.)
+        .color(colors::FOREGROUND);
+}
+
+fn style_file_label(sb: &mut StyleBuilder) {
+    sb.height(LABEL_HEIGHT)
+        .overflow(ui::OverflowAxis::Clip)
+        .align_self(ui::AlignSelf::Stretch)
+        .justify_content(ui::JustifyContent::Center);
+}
+
+/// A panel for browsing the files of an asset source. The left side shows the folder
+/// hierarchy, and the right side shows the contents of the current folder as a grid of
+/// thumbnails, filtered by an optional search string.
+///
+/// Only the rows of the grid which are scrolled into view are built, so large folders are
+/// cheap to display. Entries can be dragged out of the browser; see [`AssetDragPayload`].
+pub struct AssetBrowser {
+    /// Filesystem directory which contains the assets.
+    pub root: PathBuf,
+
+    /// Size of the thumbnails in pixels.
+    pub thumbnail_size: f32,
+
+    /// Path of the selected asset.
+    pub selected: Signal<Option<String>>,
+
+    /// Callback called with the path of an asset when it is clicked.
+    pub on_select: Option<Callback<String>>,
+
+    /// Callback called with the path of an asset when it is double-clicked.
+    pub on_open: Option<Callback<String>>,
+
+    /// Additional styles to be applied to the browser.
+    pub style: StyleHandle,
+}
+
+impl Default for AssetBrowser {
+    fn default() -> Self {
+        Self {
+            root: FileAssetReader::get_base_path().join("assets"),
+            thumbnail_size: 64.,
+            selected: Signal::Constant(None),
+            on_select: None,
+            on_open: None,
+            style: StyleHandle::default(),
+        }
+    }
+}
+
+impl AssetBrowser {
+    /// Create a new asset browser for the default `assets` directory.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the filesystem directory which contains the assets.
+    pub fn root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.root = root.into();
+        self
+    }
+
+    /// Set the size of the thumbnails.
+    pub fn thumbnail_size(mut self, size: f32) -> Self {
+        self.thumbnail_size = size;
+        self
+    }
+
+    /// Set the path of the selected asset.
+    pub fn selected(mut self, selected: impl IntoSignal<Option<String>>) -> Self {
+        self.selected = selected.into_signal();
+        self
+    }
+
+    /// Set the callback called when an asset is clicked.
+    pub fn on_select(mut self, callback: Callback<String>) -> Self {
+        self.on_select = Some(callback);
+        self
+    }
+
+    /// Set the callback called when an asset is double-clicked.
+    pub fn on_open(mut self, callback: Callback<String>) -> Self {
+        self.on_open = Some(callback);
+        self
+    }
+
+    /// Set additional styles to be applied to the browser.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl UiTemplate for AssetBrowser {
+    fn build(&self, builder: &mut UiBuilder) {
+        let root = self.root.clone();
+        let current_folder = builder.create_mutable::<PathBuf>(PathBuf::new());
+        let search = builder.create_mutable::<String>(String::new());
+        let on_search =
+            builder.create_callback(move |text: In<String>, mut world: DeferredWorld| {
+                search.set_clone(&mut world, text.clone());
+            });
+
+        // Paths of the files to display, relative to the root.
+        let files = builder.create_derived({
+            let root = root.clone();
+            move |rcx| {
+                let folder = current_folder.get_clone(rcx);
+                let search = search.get_clone(rcx).to_lowercase();
+                let mut files = Vec::new();
+                collect_files(&root, &folder, !search.is_empty(), &mut files);
+                files.retain(|path| file_name(path).to_lowercase().contains(&search));
+                files.sort();
+                files
+            }
+        });
+
+        let grid = FileGrid {
+            files,
+            cell_size: Vec2::new(
+                self.thumbnail_size + CELL_PADDING * 2.,
+                self.thumbnail_size + LABEL_HEIGHT + CELL_PADDING * 2.,
+            ),
+            thumbnail_size: self.thumbnail_size,
+            selected: self.selected.clone(),
+            on_select: self.on_select,
+            on_open: self.on_open,
+        };
+
+        builder
+            .spawn((Node::default(), Name::new("AssetBrowser")))
+            .styles((typography::text_default, style_browser, self.style.clone()))
+            .create_children(|builder| {
+                builder.invoke(
+                    ScrollView::new()
+                        .style(style_folder_panel)
+                        .content_style(style_folder_list)
+                        .scroll_enable_y(true)
+                        .children(move |builder| {
+                            builder.invoke(FolderTreeNode {
+                                root: root.clone(),
+                                folder: PathBuf::new(),
+                                current_folder,
+                                initially_expanded: true,
+                            });
+                        }),
+                );
+                builder
+                    .spawn((Node::default(), Name::new("AssetBrowser::Files")))
+                    .style(style_files_panel)
+                    .create_children(|builder| {
+                        builder.invoke(
+                            TextInput::new()
+                                .value(search)
+                                .placeholder("Search...")
+                                .on_change(on_search),
+                        );
+                        builder.invoke(grid);
+                    });
+            });
+    }
+}
+
+/// A folder in the folder tree, along with its subfolders.
+struct FolderTreeNode {
+    root: PathBuf,
+    folder: PathBuf,
+    current_folder: Mutable<PathBuf>,
+    initially_expanded: bool,
+}
+
+impl UiTemplate for FolderTreeNode {
+    fn build(&self, builder: &mut UiBuilder) {
+        let folder = self.folder.clone();
+        let current_folder = self.current_folder;
+        let subfolders = list_subfolders(&self.root, &folder);
+        let expanded = builder.create_mutable(self.initially_expanded);
+        let on_expand =
+            builder.create_callback(move |value: In<bool>, mut world: DeferredWorld| {
+                expanded.set(&mut world, *value);
+            });
+        let name = match folder.file_name() {
+            Some(name) => name.to_string_lossy().to_string(),
+            None => "assets".to_string(),
+        };
+
+        builder
+            .spawn((Node::default(), Name::new("AssetBrowser::Folder")))
+            .style(style_folder_row)
+            .style_dyn(
+                {
+                    let folder = folder.clone();
+                    move |rcx| {
+                        current_folder
+                            .signal()
+                            .map(rcx, |current| *current == folder)
+                    }
+                },
+                |is_current, sb| {
+                    sb.background_color(if is_current {
+                        colors::U3
+                    } else {
+                        colors::TRANSPARENT
+                    });
+                },
+            )
+            .observe({
+                let folder = folder.clone();
+                move |mut trigger: Trigger<Pointer<Click>>, mut world: DeferredWorld| {
+                    trigger.propagate(false);
+                    current_folder.set_clone(&mut world, folder.clone());
+                }
+            })
+            .create_children(|builder| {
+                if subfolders.is_empty() {
+                    builder
+                        .spawn(Node::default())
+                        .style(|sb: &mut StyleBuilder| {
+                            sb.width(18);
+                        });
+                } else {
+                    builder.invoke(
+                        DisclosureToggle::new()
+                            .expanded(expanded)
+                            .on_change(on_expand),
+                    );
+                }
+                builder.text(name);
+            });
+
+        if subfolders.is_empty() {
+            return;
+        }
+        let root = self.root.clone();
+        builder.cond(
+            expanded.signal(),
+            move |builder| {
+                builder
+                    .spawn(Node::default())
+                    .style(style_folder_children)
+                    .create_children(|builder| {
+                        for subfolder in subfolders.iter() {
+                            builder.invoke(FolderTreeNode {
+                                root: root.clone(),
+                                folder: subfolder.clone(),
+                                current_folder,
+                                initially_expanded: false,
+                            });
+                        }
+                    });
+            },
+            |_| {},
+        );
+    }
+}
+
+/// The virtualized grid of file thumbnails.
+struct FileGrid {
+    files: Signal<Vec<String>>,
+    cell_size: Vec2,
+    thumbnail_size: f32,
+    selected: Signal<Option<String>>,
+    on_select: Option<Callback<String>>,
+    on_open: Option<Callback<String>>,
+}
+
+impl UiTemplate for FileGrid {
+    fn build(&self, builder: &mut UiBuilder) {
+        let files = self.files.clone();
+        let cell_size = self.cell_size;
+        let view_id = builder.spawn(Node::default()).id();
+
+        // The portion of the grid which is scrolled into view, as (scroll_top, visible_size).
+        let scroll = builder.create_scroll_signals(view_id);
+        let viewport = builder
+            .create_derived(move |rcx| (scroll.offset.get(rcx).y, scroll.visible_size.get(rcx)));
+        let columns = builder.create_derived(move |rcx| {
+            let (_, visible) = viewport.get(rcx);
+            ((visible.x / cell_size.x).floor() as usize).max(1)
+        });
+
+        let selected = self.selected.clone();
+        let on_select = self.on_select;
+        let on_open = self.on_open;
+        let thumbnail_size = self.thumbnail_size;
+
+        builder.invoke(
+            ScrollView::new()
+                .entity(view_id)
+                .style(style_file_grid)
+                .scroll_enable_y(true)
+                .children(move |builder| {
+                    let files = files.clone();
+                    let selected = selected.clone();
+                    builder
+                        .spawn((Node::default(), Name::new("AssetBrowser::Grid")))
+                        .style_dyn(
+                            {
+                                let files = files.clone();
+                                move |rcx| {
+                                    let count = files.map(rcx, Vec::len);
+                                    count.div_ceil(columns.get(rcx))
+                                }
+                            },
+                            move |rows, sb| {
+                                sb.width(ui::Val::Percent(100.))
+                                    .height(rows as f32 * cell_size.y);
+                            },
+                        )
+                        .create_children(|builder| {
+                            builder.for_each(
+                                move |rcx| {
+                                    let columns = columns.get(rcx);
+                                    let (scroll_top, visible) = viewport.get(rcx);
+                                    let first_row = (scroll_top / cell_size.y).floor() as usize;
+                                    let last_row =
+                                        ((scroll_top + visible.y) / cell_size.y).ceil() as usize;
+                                    files
+                                        .get_clone(rcx)
+                                        .into_iter()
+                                        .enumerate()
+                                        .skip(first_row * columns)
+                                        .take((last_row + 1 - first_row) * columns)
+                                        .map(move |(index, path)| {
+                                            (
+                                                path,
+                                                UVec2::new(
+                                                    (index % columns) as u32,
+                                                    (index / columns) as u32,
+                                                ),
+                                            )
+                                        })
+                                },
+                                move |(path, cell), builder| {
+                                    builder.invoke(FileCell {
+                                        path: path.clone(),
+                                        position: cell.as_vec2() * cell_size,
+                                        thumbnail_size,
+                                        selected: selected.clone(),
+                                        on_select,
+                                        on_open,
+                                    });
+                                },
+                                |_| {},
+                            );
+                        });
+                }),
+        );
+    }
+}
+
+/// A single entry in the thumbnail grid.
+struct FileCell {
+    path: String,
+    position: Vec2,
+    thumbnail_size: f32,
+    selected: Signal<Option<String>>,
+    on_select: Option<Callback<String>>,
+    on_open: Option<Callback<String>>,
+}
+
+impl UiTemplate for FileCell {
+    fn build(&self, builder: &mut UiBuilder) {
+        let path = self.path.clone();
+        let position = self.position;
+        let selected = self.selected.clone();
+        let on_select = self.on_select;
+        let on_open = self.on_open;
+        let is_image = Path::new(&path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| IMAGE_EXTENSIONS.contains(&ext.to_lowercase().as_str()));
+
+        builder
+            .spawn((
+                Node::default(),
+                Name::new("AssetBrowser::File"),
+                AssetDragPayload { path: path.clone() },
+            ))
+            .styles((style_file_cell, move |sb: &mut StyleBuilder| {
+                sb.left(position.x).top(position.y);
+            }))
+            .style_dyn(
+                {
+                    let path = path.clone();
+                    move |rcx| selected.map(rcx, |selected| selected.as_ref() == Some(&path))
+                },
+                |is_selected, sb| {
+                    if is_selected {
+                        sb.background_color(colors::U3).border_color(colors::FOCUS);
+                    } else {
+                        sb.background_color(colors::TRANSPARENT)
+                            .border_color(colors::TRANSPARENT);
+                    }
+                },
+            )
+            .observe({
+                let path = path.clone();
+                move |mut trigger: Trigger<Pointer<Click>>,
+                      mut last_click: Local<f32>,
+                      mut world: DeferredWorld| {
+                    trigger.propagate(false);
+                    let now = world.resource::<Time>().elapsed_secs();
+                    let is_double_click = now - *last_click < DOUBLE_CLICK_TIME;
+                    *last_click = now;
+                    if let Some(on_select) = on_select {
+                        world.run_callback(on_select, path.clone());
+                    }
+                    if is_double_click {
+                        if let Some(on_open) = on_open {
+                            world.run_callback(on_open, path.clone());
+                        }
+                    }
+                }
+            })
+            .create_children(|builder| {
+                let size = Vec2::splat(self.thumbnail_size);
+                if is_image {
+                    builder.invoke(Thumbnail::new(path.as_str()).size(size));
+                } else {
+                    builder
+                        .spawn(Node::default())
+                        .style(move |sb: &mut StyleBuilder| {
+                            sb.width(size.x)
+                                .height(size.y)
+                                .display(ui::Display::Flex)
+                                .justify_content(ui::JustifyContent::Center)
+                                .align_items(ui::AlignItems::Center);
+                        })
+                        .create_children(|builder| {
+                            builder.invoke(
+                                Icon::new("embedded://bevy_reactor_obsidian/assets/icons/disc.png")
+                                    .size(size * 0.5),
+                            );
+                        });
+                }
+                builder
+                    .spawn(Node::default())
+                    .style(style_file_label)
+                    .create_children(|builder| {
+                        builder.text(file_name(&path));
+                    });
+            });
+    }
+}
+
+/// Return the file name portion of an asset path.
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// Convert a relative filesystem path into an asset path, which always uses `/` as the
+/// separator.
+fn to_asset_path(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Return the subfolders of `folder`, relative to `root`, sorted by name.
+fn list_subfolders(root: &Path, folder: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(root.join(folder)) else {
+        return Vec::new();
+    };
+    let mut folders: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|ft| ft.is_dir()))
+        .map(|entry| folder.join(entry.file_name()))
+        .collect();
+    folders.sort();
+    folders
+}
+
+/// Collect the asset paths of the files in `folder`, optionally including subfolders.
+fn collect_files(root: &Path, folder: &Path, recursive: bool, out: &mut Vec<String>) {
+    let Ok(entries) = std::fs::read_dir(root.join(folder)) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = folder.join(entry.file_name());
+        match entry.file_type() {
+            Ok(ft) if ft.is_dir() => {
+                if recursive {
+                    collect_files(root, &path, recursive, out);
+                }
+            }
+            Ok(_) => out.push(to_asset_path(&path)),
+            Err(_) => {}
+        }
+    }
+}
@@ -0,0 +1,309 @@
+use std::{
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::{
+    prelude::*,
+    render::view::screenshot::{Screenshot, ScreenshotCaptured},
+    tasks::IoTaskPool,
+    window::PrimaryWindow,
+};
+use bevy_reactor_obsidian::{
+    input_dispatch::CapturesPointer,
+    prelude::{Clipboard, UiLayer},
+};
+
+/// Where a capture is written.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CaptureTarget {
+    /// Save the capture as an image file. The format is chosen from the extension.
+    File(PathBuf),
+    /// Copy the capture to the system clipboard.
+    Clipboard,
+}
+
+/// Event which requests a capture of the primary window, or of a region of it. The capture is
+/// taken from the rendered image, so the system mouse cursor is never included.
+#[derive(Event, Clone, Debug)]
+pub struct CaptureRequest {
+    /// Where to write the capture.
+    pub target: CaptureTarget,
+
+    /// The region to capture, in logical pixels, or `None` for the whole window.
+    pub region: Option<Rect>,
+
+    /// How long to wait before capturing, in seconds, for example to open a menu first. The
+    /// delay uses real time, so it isn't affected by pausing the game.
+    pub delay: f32,
+}
+
+impl CaptureRequest {
+    /// Request a capture of the whole window, without a delay.
+    pub fn new(target: CaptureTarget) -> Self {
+        Self {
+            target,
+            region: None,
+            delay: 0.,
+        }
+    }
+
+    /// Capture only the given region, in logical pixels.
+    pub fn region(mut self, region: Rect) -> Self {
+        self.region = Some(region);
+        self
+    }
+
+    /// Wait for the given number of seconds before capturing.
+    pub fn delay(mut self, delay: f32) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+/// Settings used by the "Capture Window" and "Capture Region" palette commands.
+#[derive(Resource, Clone, Debug)]
+pub struct UiCapture {
+    /// Directory which captures are saved to. Each capture gets a file name based on the time.
+    pub directory: PathBuf,
+
+    /// Copy captures to the clipboard instead of saving them.
+    pub to_clipboard: bool,
+
+    /// Delay before capturing, in seconds.
+    pub delay: f32,
+}
+
+impl Default for UiCapture {
+    fn default() -> Self {
+        Self {
+            directory: PathBuf::from("captures"),
+            to_clipboard: false,
+            delay: 0.,
+        }
+    }
+}
+
+impl UiCapture {
+    /// A request for a capture of the whole window, using these settings.
+    pub fn request(&self) -> CaptureRequest {
+        let target = if self.to_clipboard {
+            CaptureTarget::Clipboard
+        } else {
+            let millis = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|time| time.as_millis())
+                .unwrap_or_default();
+            CaptureTarget::File(self.directory.join(format!("capture-{}.png", millis)))
+        };
+        CaptureRequest::new(target).delay(self.delay)
+    }
+}
+
+/// Captures which are waiting for their delay to expire.
+#[derive(Resource, Default)]
+pub(crate) struct PendingCaptures(Vec<(Timer, CaptureRequest)>);
+
+/// Start the delay timers of new capture requests.
+pub(crate) fn queue_capture_requests(
+    mut requests: EventReader<CaptureRequest>,
+    mut pending: ResMut<PendingCaptures>,
+) {
+    for request in requests.read() {
+        let timer = Timer::from_seconds(request.delay.max(0.), TimerMode::Once);
+        pending.0.push((timer, request.clone()));
+    }
+}
+
+/// Take the screenshots for captures whose delay has expired.
+pub(crate) fn run_pending_captures(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut pending: ResMut<PendingCaptures>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+) {
+    let scale_factor = windows
+        .get_single()
+        .map(|window| window.scale_factor())
+        .unwrap_or(1.);
+    let mut index = 0;
+    while index < pending.0.len() {
+        if !pending.0[index].0.tick(time.delta()).finished() {
+            index += 1;
+            continue;
+        }
+        let (_, request) = pending.0.swap_remove(index);
+        commands.spawn(Screenshot::primary_window()).observe(
+            move |trigger: Trigger<ScreenshotCaptured>, mut clipboard: ResMut<Clipboard>| {
+                write_capture(&trigger.event().0, &request, scale_factor, &mut clipboard);
+            },
+        );
+    }
+}
+
+/// Crop a captured image to the requested region, and write it to the requested target.
+fn write_capture(
+    image: &Image,
+    request: &CaptureRequest,
+    scale_factor: f32,
+    clipboard: &mut Clipboard,
+) {
+    let mut image = match image.clone().try_into_dynamic() {
+        Ok(image) => image,
+        Err(err) => {
+            warn!("Unable to convert capture: {}", err);
+            return;
+        }
+    };
+    if let Some(region) = request.region {
+        let min = (region.min * scale_factor).max(Vec2::ZERO).as_uvec2();
+        let max = (region.max * scale_factor)
+            .as_uvec2()
+            .min(UVec2::new(image.width(), image.height()));
+        if max.x <= min.x || max.y <= min.y {
+            warn!("Capture region is outside of the window");
+            return;
+        }
+        let size = max - min;
+        image = image.crop_imm(min.x, min.y, size.x, size.y);
+    }
+
+    match &request.target {
+        CaptureTarget::Clipboard => {
+            let rgba = image.to_rgba8();
+            let (width, height) = rgba.dimensions();
+            clipboard.set_image(width as usize, height as usize, rgba.into_raw());
+        }
+        CaptureTarget::File(path) => {
+            let path = path.clone();
+            IoTaskPool::get()
+                .spawn(async move {
+                    if let Some(parent) = path.parent() {
+                        if let Err(err) = std::fs::create_dir_all(parent) {
+                            warn!("Unable to create {}: {}", parent.display(), err);
+                            return;
+                        }
+                    }
+                    match image.to_rgb8().save(&path) {
+                        Ok(()) => info!("Saved capture to {}", path.display()),
+                        Err(err) => warn!("Unable to save capture to {}: {}", path.display(), err),
+                    }
+                })
+                .detach();
+        }
+    }
+}
+
+/// The overlay shown while selecting a region to capture, with the corners of the selection.
+#[derive(Component, Default)]
+pub(crate) struct RegionSelect {
+    start: Vec2,
+    end: Vec2,
+}
+
+impl RegionSelect {
+    fn rect(&self) -> Rect {
+        Rect::from_corners(self.start, self.end)
+    }
+}
+
+/// Show an overlay over the whole window, in which dragging out a rectangle captures that
+/// region. The overlay is removed before the capture is taken; Escape cancels.
+pub(crate) fn begin_region_capture(
+    _: In<()>,
+    mut commands: Commands,
+    settings: Res<UiCapture>,
+    q_overlays: Query<(), With<RegionSelect>>,
+) {
+    if !q_overlays.is_empty() {
+        return;
+    }
+    let request = settings.request();
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(0.),
+                top: Val::Px(0.),
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                ..default()
+            },
+            BackgroundColor(Color::BLACK.with_alpha(0.2)),
+            Name::new("CaptureRegionSelect"),
+            RegionSelect::default(),
+            UiLayer::DragGhost,
+            CapturesPointer,
+        ))
+        .with_child((
+            Node {
+                position_type: PositionType::Absolute,
+                display: Display::None,
+                border: UiRect::all(Val::Px(1.)),
+                ..default()
+            },
+            BorderColor(Color::WHITE),
+            BackgroundColor(Color::WHITE.with_alpha(0.1)),
+        ))
+        .observe(
+            |mut trigger: Trigger<Pointer<DragStart>>, mut q_select: Query<&mut RegionSelect>| {
+                trigger.propagate(false);
+                if let Ok(mut select) = q_select.get_mut(trigger.entity()) {
+                    select.start = trigger.pointer_location.position;
+                    select.end = select.start;
+                }
+            },
+        )
+        .observe(
+            |mut trigger: Trigger<Pointer<Drag>>,
+             mut q_select: Query<(&mut RegionSelect, &Children)>,
+             mut q_node: Query<&mut Node>| {
+                trigger.propagate(false);
+                let Ok((mut select, children)) = q_select.get_mut(trigger.entity()) else {
+                    return;
+                };
+                select.end = trigger.pointer_location.position;
+                let rect = select.rect();
+                if let Some(mut node) = children
+                    .first()
+                    .and_then(|child| q_node.get_mut(*child).ok())
+                {
+                    node.display = Display::Flex;
+                    node.left = Val::Px(rect.min.x);
+                    node.top = Val::Px(rect.min.y);
+                    node.width = Val::Px(rect.width());
+                    node.height = Val::Px(rect.height());
+                }
+            },
+        )
+        .observe(
+            move |mut trigger: Trigger<Pointer<DragEnd>>,
+                  q_select: Query<&RegionSelect>,
+                  mut commands: Commands,
+                  mut requests: EventWriter<CaptureRequest>| {
+                trigger.propagate(false);
+                let Ok(select) = q_select.get(trigger.entity()) else {
+                    return;
+                };
+                let rect = select.rect();
+                commands.entity(trigger.entity()).despawn_recursive();
+                // Ignore clicks which didn't drag out a region.
+                if rect.width() >= 4. && rect.height() >= 4. {
+                    requests.send(request.clone().region(rect));
+                }
+            },
+        );
+}
+
+/// Cancel region selection when Escape is pressed.
+pub(crate) fn cancel_region_capture(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    q_overlays: Query<Entity, With<RegionSelect>>,
+) {
+    if keys.just_pressed(KeyCode::Escape) {
+        for overlay in q_overlays.iter() {
+            commands.entity(overlay).despawn_recursive();
+        }
+    }
+}
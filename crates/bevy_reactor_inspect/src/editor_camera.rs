@@ -0,0 +1,296 @@
+use bevy::{
+    input::mouse::{AccumulatedMouseMotion, AccumulatedMouseScroll, MouseScrollUnit},
+    picking::{focus::HoverMap, pointer::PointerId},
+    prelude::*,
+    render::primitives::Aabb,
+    ui::ComputedNode,
+};
+use bevy_reactor_obsidian::{
+    input_dispatch::KeyboardFocus,
+    prelude::{key_name, Shortcut},
+};
+
+use crate::InspectedEntity;
+
+/// Closest the orbit camera can get to its focus point.
+const MIN_DISTANCE: f32 = 0.05;
+
+/// Keeps the camera from flipping over the poles.
+const MAX_PITCH: f32 = std::f32::consts::FRAC_PI_2 - 0.01;
+
+/// A camera which is driven by the editor camera controller. The camera orbits around a focus
+/// point at a given distance; in fly mode, it rotates in place and moves with the keyboard.
+///
+/// Cameras which have this component have their `Transform` overwritten each frame.
+#[derive(Component, Clone, Debug)]
+pub struct EditorCamera {
+    /// The point the camera orbits around.
+    pub focus: Vec3,
+    /// Distance from the focus point to the camera.
+    pub distance: f32,
+    /// Rotation around the vertical axis, in radians.
+    pub yaw: f32,
+    /// Rotation around the horizontal axis, in radians. Positive values look upwards.
+    pub pitch: f32,
+}
+
+impl EditorCamera {
+    /// Create an editor camera positioned at `eye`, looking at `focus`.
+    pub fn looking_at(eye: Vec3, focus: Vec3) -> Self {
+        let offset = eye - focus;
+        let distance = offset.length().max(MIN_DISTANCE);
+        Self {
+            focus,
+            distance,
+            yaw: offset.x.atan2(offset.z),
+            pitch: -(offset.y / distance).clamp(-1., 1.).asin(),
+        }
+    }
+
+    /// Create an editor camera which matches an existing camera transform, orbiting around a
+    /// point `distance` units in front of it.
+    pub fn from_transform(transform: &Transform, distance: f32) -> Self {
+        Self::looking_at(
+            transform.translation,
+            transform.translation + transform.forward() * distance,
+        )
+    }
+
+    fn rotation(&self) -> Quat {
+        Quat::from_euler(EulerRot::YXZ, self.yaw, self.pitch, 0.)
+    }
+
+    fn eye(&self) -> Vec3 {
+        self.focus + self.rotation() * Vec3::new(0., 0., self.distance)
+    }
+
+    /// Rotate the camera around its focus point.
+    fn orbit(&mut self, delta: Vec2) {
+        self.yaw -= delta.x;
+        self.pitch = (self.pitch - delta.y).clamp(-MAX_PITCH, MAX_PITCH);
+    }
+
+    /// Rotate the camera in place, moving the focus point instead of the eye.
+    fn look(&mut self, delta: Vec2) {
+        let eye = self.eye();
+        self.orbit(delta);
+        self.focus = eye - self.rotation() * Vec3::new(0., 0., self.distance);
+    }
+}
+
+/// Mouse buttons and keys used by the editor camera controller, along with its speeds.
+///
+/// * Orbit: drag with the orbit button.
+/// * Pan: drag with the orbit button while holding the pan modifier.
+/// * Zoom: mouse wheel.
+/// * Fly: hold the fly button to look around with the mouse, and move with the movement keys.
+/// * Frame the inspected entity with the frame key.
+#[derive(Resource, Clone, Debug)]
+pub struct EditorCameraBindings {
+    /// Mouse button which orbits (or pans) the camera.
+    pub orbit: MouseButton,
+    /// Key which switches orbiting to panning while held.
+    pub pan_modifier: KeyCode,
+    /// Mouse button which enables fly mode while held.
+    pub fly: MouseButton,
+    /// Key which moves forward in fly mode.
+    pub forward: KeyCode,
+    /// Key which moves backward in fly mode.
+    pub back: KeyCode,
+    /// Key which moves left in fly mode.
+    pub left: KeyCode,
+    /// Key which moves right in fly mode.
+    pub right: KeyCode,
+    /// Key which moves up in fly mode.
+    pub up: KeyCode,
+    /// Key which moves down in fly mode.
+    pub down: KeyCode,
+    /// Key which moves the focus point to the inspected entity.
+    pub frame_selected: KeyCode,
+    /// Rotation in radians per pixel of mouse movement.
+    pub rotate_speed: f32,
+    /// Fraction of the distance to zoom per wheel line.
+    pub zoom_speed: f32,
+    /// Fly mode movement speed, in units per second.
+    pub fly_speed: f32,
+}
+
+impl Default for EditorCameraBindings {
+    fn default() -> Self {
+        Self {
+            orbit: MouseButton::Middle,
+            pan_modifier: KeyCode::ShiftLeft,
+            fly: MouseButton::Right,
+            forward: KeyCode::KeyW,
+            back: KeyCode::KeyS,
+            left: KeyCode::KeyA,
+            right: KeyCode::KeyD,
+            up: KeyCode::KeyE,
+            down: KeyCode::KeyQ,
+            frame_selected: KeyCode::KeyF,
+            rotate_speed: 0.005,
+            zoom_speed: 0.1,
+            fly_speed: 5.,
+        }
+    }
+}
+
+/// Describe the editor camera's shortcuts for the shortcut help overlay.
+pub(crate) fn editor_camera_shortcuts(world: &World) -> Vec<Shortcut> {
+    let Some(bindings) = world.get_resource::<EditorCameraBindings>() else {
+        return Vec::new();
+    };
+    let shortcut =
+        |keys: String, description: &str| Shortcut::new("Viewport", "Viewport", keys, description);
+    vec![
+        shortcut(format!("{:?} drag", bindings.orbit), "Orbit"),
+        shortcut(
+            format!(
+                "{}+{:?} drag",
+                key_name(bindings.pan_modifier),
+                bindings.orbit
+            ),
+            "Pan",
+        ),
+        shortcut("Wheel".to_string(), "Zoom"),
+        shortcut(format!("Hold {:?}", bindings.fly), "Fly"),
+        shortcut(
+            [
+                bindings.forward,
+                bindings.left,
+                bindings.back,
+                bindings.right,
+                bindings.down,
+                bindings.up,
+            ]
+            .map(key_name)
+            .join(" "),
+            "Move while flying",
+        ),
+        shortcut(
+            key_name(bindings.frame_selected),
+            "Frame the inspected entity",
+        ),
+    ]
+}
+
+/// Give an [`EditorCamera`] to each newly added 3d camera, so that the controller works
+/// without any changes to the game.
+pub(crate) fn attach_editor_camera(
+    mut commands: Commands,
+    q_cameras: Query<(Entity, &Transform), (Added<Camera3d>, Without<EditorCamera>)>,
+) {
+    for (entity, transform) in q_cameras.iter() {
+        commands
+            .entity(entity)
+            .insert(EditorCamera::from_transform(transform, 10.));
+    }
+}
+
+/// Whether the mouse is over a UI node, in which case the viewport should not react to it.
+fn pointer_over_ui(hover_map: &HoverMap, q_nodes: &Query<(), With<ComputedNode>>) -> bool {
+    hover_map
+        .get(&PointerId::Mouse)
+        .is_some_and(|hits| hits.keys().any(|entity| q_nodes.contains(*entity)))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn editor_camera_controller(
+    bindings: Res<EditorCameraBindings>,
+    time: Res<Time>,
+    keys: Res<ButtonInput<KeyCode>>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    motion: Res<AccumulatedMouseMotion>,
+    scroll: Res<AccumulatedMouseScroll>,
+    hover_map: Res<HoverMap>,
+    focus: Option<Res<KeyboardFocus>>,
+    q_nodes: Query<(), With<ComputedNode>>,
+    mut dragging: Local<bool>,
+    mut q_cameras: Query<(&mut EditorCamera, &mut Transform)>,
+) {
+    let over_ui = pointer_over_ui(&hover_map, &q_nodes);
+    // Drags only start in the viewport, but continue if the pointer moves over the UI.
+    if buttons.any_just_pressed([bindings.orbit, bindings.fly]) {
+        *dragging = !over_ui;
+    } else if !buttons.any_pressed([bindings.orbit, bindings.fly]) {
+        *dragging = false;
+    }
+    let flying = *dragging && buttons.pressed(bindings.fly);
+    let orbiting = *dragging && !flying && buttons.pressed(bindings.orbit);
+    let panning = orbiting && keys.pressed(bindings.pan_modifier);
+    // Movement keys are only read in fly mode, and never while a widget has keyboard focus.
+    let keyboard = flying && !focus.is_some_and(|focus| focus.0.is_some());
+
+    let zoom = if over_ui {
+        0.
+    } else {
+        match scroll.unit {
+            MouseScrollUnit::Line => scroll.delta.y,
+            MouseScrollUnit::Pixel => scroll.delta.y * 0.05,
+        }
+    };
+
+    for (mut camera, mut transform) in q_cameras.iter_mut() {
+        let rotate = motion.delta * bindings.rotate_speed;
+        if flying {
+            camera.look(rotate);
+        } else if panning {
+            let rotation = camera.rotation();
+            // Move the focus by the same distance on screen regardless of how far away it is.
+            let scale = camera.distance * bindings.rotate_speed;
+            camera.focus += rotation * Vec3::new(-motion.delta.x, motion.delta.y, 0.) * scale;
+        } else if orbiting {
+            camera.orbit(rotate);
+        }
+
+        if zoom != 0. {
+            camera.distance =
+                (camera.distance * (1. - zoom * bindings.zoom_speed)).max(MIN_DISTANCE);
+        }
+
+        if keyboard {
+            let axis = |positive: KeyCode, negative: KeyCode| {
+                keys.pressed(positive) as i32 as f32 - keys.pressed(negative) as i32 as f32
+            };
+            let direction = Vec3::new(
+                axis(bindings.right, bindings.left),
+                axis(bindings.up, bindings.down),
+                axis(bindings.back, bindings.forward),
+            );
+            let rotation = camera.rotation();
+            camera.focus += rotation * direction * bindings.fly_speed * time.delta_secs();
+        }
+
+        transform.rotation = camera.rotation();
+        transform.translation = camera.eye();
+    }
+}
+
+/// Move the focus point of the editor cameras to the inspected entity, and zoom to fit its
+/// bounds if it has any.
+pub(crate) fn frame_inspected_entity(
+    bindings: Res<EditorCameraBindings>,
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Option<Res<KeyboardFocus>>,
+    inspected: Res<InspectedEntity>,
+    q_targets: Query<(&GlobalTransform, Option<&Aabb>)>,
+    mut q_cameras: Query<&mut EditorCamera>,
+) {
+    if !keys.just_pressed(bindings.frame_selected) || focus.is_some_and(|focus| focus.0.is_some()) {
+        return;
+    }
+    let Some((transform, aabb)) = inspected.0.and_then(|entity| q_targets.get(entity).ok()) else {
+        return;
+    };
+    let (center, radius) = match aabb {
+        Some(aabb) => (
+            transform.transform_point(aabb.center.into()),
+            (transform.compute_transform().scale * Vec3::from(aabb.half_extents)).length(),
+        ),
+        None => (transform.translation(), 1.),
+    };
+    for mut camera in q_cameras.iter_mut() {
+        camera.focus = center;
+        camera.distance = (radius * 2.5).max(MIN_DISTANCE);
+    }
+}
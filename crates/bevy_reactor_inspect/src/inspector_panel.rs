@@ -3,10 +3,10 @@ use bevy::{
     ecs::{observer::ObserverState, system::SystemIdMarker, world::DeferredWorld},
     pbr::{DirectionalLight, PointLight},
     prelude::{
-        Camera2d, Camera3d, Children, Click, Component, Entity, In, Mesh3d, Parent, Pointer, Query,
-        ResMut, Resource, Trigger, Without, World,
+        Camera2d, Camera3d, Children, Click, Component, Entity, GlobalTransform, HierarchyQueryExt,
+        In, Local, Mesh3d, Parent, Pointer, Query, Res, ResMut, Resource, Trigger, Without, World,
     },
-    ui::{self, experimental::GhostNode, Node},
+    ui::{self, experimental::GhostNode, ComputedNode, Node},
     window::{Monitor, Window},
 };
 use bevy_mod_stylebuilder::{
@@ -19,11 +19,14 @@ use bevy_reactor_builder::{
 };
 use bevy_reactor_obsidian::{
     colors,
-    prelude::{DisclosureToggle, ScrollView},
+    prelude::{DisclosureToggle, ScrollView, TextInput},
+    scrolling::ScrollArea,
     typography,
 };
 use bevy_reactor_signals::ReactionCell;
 
+use crate::world_search::{SearchHit, WorldSearch};
+
 fn style_panel(sb: &mut StyleBuilder) {
     sb.position(ui::PositionType::Absolute)
         .display(ui::Display::Flex)
@@ -51,7 +54,37 @@ pub(crate) fn create_inspector_panel(world: &mut World) {
         ))
         .styles((typography::text_default, style_panel))
         .create_children(|builder| {
-            builder.invoke(TopLevelItemList);
+            let query = builder.create_mutable(String::new());
+            let on_query =
+                builder.create_callback(move |text: In<String>, mut world: DeferredWorld| {
+                    query.set_clone(&mut world, text.clone());
+                });
+            // Selecting a search result inspects it and returns to the tree, which expands
+            // and scrolls to reveal the selected entity.
+            let on_select =
+                builder.create_callback(move |hit: In<SearchHit>, mut world: DeferredWorld| {
+                    if let Some(entity) = hit.entity(&world) {
+                        world.resource_mut::<InspectedEntity>().0 = Some(entity);
+                        query.set_clone(&mut world, String::new());
+                    }
+                });
+            let is_searching =
+                builder.create_derived(move |rcx| !query.get_clone(rcx).trim().is_empty());
+            builder.invoke(
+                TextInput::new()
+                    .value(query)
+                    .placeholder("Search world...")
+                    .on_change(on_query),
+            );
+            builder.cond(
+                is_searching,
+                move |builder| {
+                    builder.invoke(WorldSearch::new(query).on_select(on_select));
+                },
+                |builder| {
+                    builder.invoke(TopLevelItemList);
+                },
+            );
         });
 }
 
@@ -98,6 +131,10 @@ impl UiTemplate for TopLevelItemList {
 #[derive(Resource, Default)]
 pub(crate) struct TopLevelEntities(Vec<Entity>);
 
+/// The entity selected in the inspector, if any. The inspector tree expands to reveal it.
+#[derive(Resource, Default, Clone, Copy, PartialEq)]
+pub struct InspectedEntity(pub Option<Entity>);
+
 #[allow(clippy::type_complexity)]
 pub fn copy_top_level_entities(
     q_entities: Query<
@@ -129,6 +166,53 @@ fn style_tree_node_label(sb: &mut StyleBuilder) {
         .flex_direction(ui::FlexDirection::Row);
 }
 
+fn style_tree_node_label_selected(sb: &mut StyleBuilder) {
+    sb.background_color(colors::TEXT_SELECT);
+}
+
+/// Marker on the label row of a node in the entity tree, recording which entity it shows.
+#[derive(Component)]
+pub(crate) struct EntityTreeLabel(Entity);
+
+/// Scrolls the entity tree so that the label of the inspected entity is visible. The tree
+/// expands over several frames to reveal the entity, so the request stays pending until the
+/// label has been laid out.
+pub(crate) fn scroll_to_inspected_entity(
+    r_inspected: Res<InspectedEntity>,
+    mut pending: Local<bool>,
+    q_labels: Query<(Entity, &EntityTreeLabel, &ComputedNode, &GlobalTransform)>,
+    q_parents: Query<&Parent>,
+    mut q_areas: Query<(&mut ScrollArea, &ComputedNode, &GlobalTransform)>,
+) {
+    if r_inspected.is_changed() {
+        *pending = r_inspected.0.is_some();
+    }
+    let Some(target) = r_inspected.0.filter(|_| *pending) else {
+        return;
+    };
+    let Some((label, _, node, transform)) = q_labels
+        .iter()
+        .find(|(_, label, node, _)| label.0 == target && node.size().y > 0.)
+    else {
+        return;
+    };
+
+    for ancestor in q_parents.iter_ancestors(label) {
+        if let Ok((mut area, area_node, area_transform)) = q_areas.get_mut(ancestor) {
+            let height = node.size().y;
+            let offset = (transform.translation().y - height * 0.5)
+                - (area_transform.translation().y - area_node.size().y * 0.5);
+            if offset < 0. {
+                area.scroll_by(0., offset);
+            } else if offset + height > area.visible_size.y {
+                area.scroll_by(0., offset + height - area.visible_size.y);
+            }
+            *pending = false;
+            return;
+        }
+    }
+}
+
 fn style_tree_node_children(sb: &mut StyleBuilder) {
     sb.display(ui::Display::Flex)
         .flex_direction(ui::FlexDirection::Column)
@@ -152,14 +236,40 @@ impl UiTemplate for EntityTreeNode {
                     builder.create_callback(move |value: In<bool>, mut world: DeferredWorld| {
                         expanded.set(&mut world, *value);
                     });
+                // Expand to reveal the inspected entity, if it is a descendant of this one.
+                builder.create_effect(move |ecx| {
+                    let Some(inspected) = ecx.read_resource::<InspectedEntity>().0 else {
+                        return;
+                    };
+                    let world = ecx.world();
+                    let mut ancestor = world.get::<Parent>(inspected).map(|p| p.get());
+                    while let Some(entity) = ancestor {
+                        if entity == entid {
+                            expanded.set(ecx.world_mut(), true);
+                            return;
+                        }
+                        ancestor = world.get::<Parent>(entity).map(|p| p.get());
+                    }
+                });
                 builder
-                    .spawn(Node::default())
+                    .spawn((Node::default(), EntityTreeLabel(entid)))
                     .style(style_tree_node_label)
+                    .style_dyn(
+                        move |rcx| rcx.read_resource::<InspectedEntity>().0 == Some(entid),
+                        |selected, sb| {
+                            if selected {
+                                style_tree_node_label_selected(sb);
+                            } else {
+                                sb.background_color(colors::TRANSPARENT);
+                            }
+                        },
+                    )
                     .observe(
                         move |mut trigger: Trigger<Pointer<Click>>, mut world: DeferredWorld| {
                             trigger.propagate(false);
                             let value = expanded.get(&world);
                             expanded.set(&mut world, !value);
+                            world.resource_mut::<InspectedEntity>().0 = Some(entid);
                         },
                     )
                     .create_children(|builder| {
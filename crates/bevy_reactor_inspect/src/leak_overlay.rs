@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+use bevy_reactor_signals::LeakDetector;
+
+/// Shows the reactive cell counts found by the [`LeakDetector`] in the corner of the window.
+/// The overlay is only shown while the detector is enabled, and is updated after each check.
+#[derive(Resource, Default)]
+pub struct LeakOverlay {
+    /// Whether the overlay is shown.
+    pub enabled: bool,
+}
+
+/// Marker for the text node which displays the cell counts.
+#[derive(Component)]
+pub(crate) struct LeakOverlayText;
+
+/// Spawn, update or remove the overlay to match the [`LeakOverlay`] settings.
+pub(crate) fn update_leak_overlay(
+    mut commands: Commands,
+    settings: Res<LeakOverlay>,
+    detector: Option<Res<LeakDetector>>,
+    mut q_overlay: Query<(Entity, &mut Text), With<LeakOverlayText>>,
+) {
+    let overlay = q_overlay.get_single_mut().ok();
+    let detector = detector.filter(|_| settings.enabled);
+    match (overlay, detector) {
+        (Some((_, mut text)), Some(detector)) => {
+            if detector.is_changed() {
+                text.0 = overlay_text(&detector);
+            }
+        }
+        (None, Some(detector)) => {
+            commands.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    right: Val::Px(4.),
+                    bottom: Val::Px(4.),
+                    ..default()
+                },
+                Text(overlay_text(&detector)),
+                TextFont::from_font_size(12.),
+                BackgroundColor(Color::srgba(0., 0., 0., 0.6)),
+                GlobalZIndex(i32::MAX),
+                PickingBehavior::IGNORE,
+                LeakOverlayText,
+                Name::new("LeakOverlay"),
+            ));
+        }
+        (Some((overlay, _)), None) => {
+            commands.entity(overlay).despawn_recursive();
+        }
+        (None, None) => {}
+    }
+}
+
+fn overlay_text(detector: &LeakDetector) -> String {
+    let stats = &detector.stats;
+    format!(
+        "M {}  D {}  R {}  C {}  orphans {}",
+        stats.total.mutables,
+        stats.total.deriveds,
+        stats.total.reactions,
+        stats.total.callbacks,
+        stats.orphans.len()
+    )
+}
@@ -1,18 +1,162 @@
-use bevy::app::{Plugin, Startup, Update};
+use bevy::app::{First, Plugin, Startup, Update};
+use bevy::pbr::wireframe::WireframePlugin;
+use bevy::prelude::{EventWriter, In, IntoSystemConfigs, Res, ResMut};
+use bevy::time::TimeSystem;
 use bevy_mod_stylebuilder::StyleBuilderPlugin;
-use bevy_reactor_obsidian::ObsidianUiPlugin;
+use bevy_reactor_obsidian::{
+    prelude::{RegisterPaletteCommand, RegisterShortcuts},
+    ObsidianUiPlugin,
+};
 use bevy_reactor_signals::SignalsPlugin;
-use inspector_panel::{copy_top_level_entities, create_inspector_panel, TopLevelEntities};
+use capture::{
+    begin_region_capture, cancel_region_capture, queue_capture_requests, run_pending_captures,
+    PendingCaptures,
+};
+use editor_camera::{
+    attach_editor_camera, editor_camera_controller, editor_camera_shortcuts, frame_inspected_entity,
+};
+use inspector_panel::{
+    copy_top_level_entities, create_inspector_panel, scroll_to_inspected_entity, TopLevelEntities,
+};
+use leak_overlay::update_leak_overlay;
+use playback::apply_playback;
+use snapping::{snapping_shortcuts, update_snap_toggle};
+use ui_debugger::{toggle_ui_debugger, ui_debugger_shortcuts, update_ui_debugger};
+use viewport_overlays::{draw_viewport_grid, sync_viewport_overlays};
 
+mod asset_browser;
+mod capture;
+mod editor_camera;
 mod inspector_panel;
+mod leak_overlay;
+mod playback;
+mod snapping;
+mod ui_debugger;
+mod viewport_overlays;
+mod world_search;
+
+pub use asset_browser::{AssetBrowser, AssetDragPayload};
+pub use capture::{CaptureRequest, CaptureTarget, UiCapture};
+pub use editor_camera::{EditorCamera, EditorCameraBindings};
+pub use inspector_panel::InspectedEntity;
+pub use leak_overlay::LeakOverlay;
+pub use playback::{game_running, Playback, PlaybackControls};
+pub use snapping::{SnapSettings, SnapSettingsButton, SnapSettingsPanel};
+pub use ui_debugger::UiDebugger;
+pub use viewport_overlays::ViewportOverlays;
+pub use world_search::{SearchHit, WorldSearch};
 
 pub struct WorldInspector;
 
 impl Plugin for WorldInspector {
     fn build(&self, app: &mut bevy::prelude::App) {
         app.init_resource::<TopLevelEntities>()
+            .init_resource::<InspectedEntity>()
+            .init_resource::<UiDebugger>()
+            .init_resource::<UiCapture>()
+            .init_resource::<PendingCaptures>()
+            .init_resource::<LeakOverlay>()
+            .add_event::<CaptureRequest>()
             .add_plugins((SignalsPlugin, StyleBuilderPlugin, ObsidianUiPlugin))
             .add_systems(Startup, create_inspector_panel)
-            .add_systems(Update, copy_top_level_entities);
+            .add_systems(
+                Update,
+                (
+                    copy_top_level_entities,
+                    scroll_to_inspected_entity,
+                    (toggle_ui_debugger, update_ui_debugger).chain(),
+                    (queue_capture_requests, run_pending_captures).chain(),
+                    cancel_region_capture,
+                    update_leak_overlay,
+                ),
+            )
+            .register_shortcuts(ui_debugger_shortcuts)
+            .register_palette_command(
+                "Capture Window",
+                |_: In<()>, settings: Res<UiCapture>, mut requests: EventWriter<CaptureRequest>| {
+                    requests.send(settings.request());
+                },
+            )
+            .register_palette_command("Capture Region", begin_region_capture)
+            .register_palette_command(
+                "Toggle Leak Overlay",
+                |_: In<()>, mut overlay: ResMut<LeakOverlay>| {
+                    overlay.enabled = !overlay.enabled;
+                },
+            );
+    }
+}
+
+/// Optional companion to the [`WorldInspector`] which provides basic scene navigation: an
+/// editor camera controller for every 3d camera (see [`EditorCameraBindings`]), and viewport
+/// overlays (see [`ViewportOverlays`]) which can be toggled from the command palette, the
+/// shared [`SnapSettings`] consulted by editing tools, and [`Playback`] control of the game's
+/// time.
+pub struct EditorViewPlugin;
+
+impl Plugin for EditorViewPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        if !app.is_plugin_added::<WireframePlugin>() {
+            app.add_plugins(WireframePlugin);
+        }
+        app.init_resource::<InspectedEntity>()
+            .init_resource::<EditorCameraBindings>()
+            .init_resource::<ViewportOverlays>()
+            .init_resource::<SnapSettings>()
+            .init_resource::<Playback>()
+            .add_systems(First, apply_playback.after(TimeSystem))
+            .add_systems(
+                Update,
+                (
+                    (
+                        attach_editor_camera,
+                        frame_inspected_entity,
+                        editor_camera_controller,
+                    )
+                        .chain(),
+                    sync_viewport_overlays,
+                    draw_viewport_grid,
+                    update_snap_toggle,
+                ),
+            )
+            .register_shortcuts(editor_camera_shortcuts)
+            .register_shortcuts(snapping_shortcuts)
+            .register_palette_command(
+                "Toggle Grid",
+                |_: In<()>, mut overlays: ResMut<ViewportOverlays>| {
+                    overlays.grid = !overlays.grid;
+                },
+            )
+            .register_palette_command(
+                "Toggle Wireframe",
+                |_: In<()>, mut overlays: ResMut<ViewportOverlays>| {
+                    overlays.wireframe = !overlays.wireframe;
+                },
+            )
+            .register_palette_command(
+                "Toggle Bounding Boxes",
+                |_: In<()>, mut overlays: ResMut<ViewportOverlays>| {
+                    overlays.aabbs = !overlays.aabbs;
+                },
+            )
+            .register_palette_command(
+                "Pause / Resume",
+                |_: In<()>, mut playback: ResMut<Playback>| {
+                    playback.paused = !playback.paused;
+                },
+            )
+            .register_palette_command("Step Frame", |_: In<()>, mut playback: ResMut<Playback>| {
+                playback.step();
+            })
+            .register_palette_command(
+                "Toggle Snapping",
+                |_: In<()>, mut snap: ResMut<SnapSettings>| {
+                    let enable =
+                        !(snap.translate_enabled || snap.rotate_enabled || snap.scale_enabled);
+                    snap.translate_enabled = enable;
+                    snap.rotate_enabled = enable;
+                    snap.scale_enabled = enable;
+                },
+            );
     }
 }
@@ -0,0 +1,175 @@
+use std::time::Duration;
+
+use bevy::{
+    ecs::world::DeferredWorld,
+    prelude::*,
+    ui::{self, Node},
+};
+use bevy_mod_stylebuilder::{StyleBuilder, StyleBuilderLayout};
+use bevy_reactor_builder::{
+    CreateChilden, EntityStyleBuilder, InvokeUiTemplate, TextBuilder, UiBuilder, UiTemplate,
+};
+use bevy_reactor_obsidian::prelude::{Button, ButtonVariant, Slider};
+
+/// Controls whether the game is running. Pausing stops virtual time, so that `Time` reports a
+/// zero delta to every system, while the UI (which runs on real time) keeps working. Game
+/// systems which should not run at all while paused can use the [`game_running`] run
+/// condition.
+#[derive(Resource, Clone, Debug)]
+pub struct Playback {
+    /// Whether the game is paused.
+    pub paused: bool,
+
+    /// Speed of virtual time relative to real time.
+    pub time_scale: f32,
+
+    /// How far time advances when stepping a single frame.
+    pub step_duration: Duration,
+
+    /// Number of frames waiting to be stepped while paused.
+    pending_steps: u32,
+
+    /// Whether the current frame is being stepped.
+    stepping: bool,
+}
+
+impl Default for Playback {
+    fn default() -> Self {
+        Self {
+            paused: false,
+            time_scale: 1.,
+            step_duration: Duration::from_secs_f64(1. / 60.),
+            pending_steps: 0,
+            stepping: false,
+        }
+    }
+}
+
+impl Playback {
+    /// Advance a paused game by one frame. Has no effect while the game is running.
+    pub fn step(&mut self) {
+        if self.paused {
+            self.pending_steps += 1;
+        }
+    }
+
+    /// Whether game logic should run this frame: either the game is running, or a single
+    /// frame is being stepped.
+    pub fn is_running(&self) -> bool {
+        !self.paused || self.stepping
+    }
+}
+
+/// Run condition for game systems which should not run while the game is paused, other than
+/// when stepping a single frame.
+pub fn game_running(playback: Res<Playback>) -> bool {
+    playback.is_running()
+}
+
+/// Apply the playback state to virtual time. Runs after Bevy has updated the clocks, so that
+/// a stepped frame sees exactly one step of elapsed time.
+pub(crate) fn apply_playback(
+    mut playback: ResMut<Playback>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut time: ResMut<Time>,
+) {
+    if virtual_time.relative_speed() != playback.time_scale {
+        virtual_time.set_relative_speed(playback.time_scale.max(0.));
+    }
+    if playback.paused != virtual_time.is_paused() {
+        if playback.paused {
+            virtual_time.pause();
+        } else {
+            virtual_time.unpause();
+        }
+    }
+
+    let stepping = playback.paused && playback.pending_steps > 0;
+    if stepping {
+        playback.pending_steps -= 1;
+        let step = playback.step_duration;
+        virtual_time.advance_by(step);
+        *time = virtual_time.as_generic();
+    } else if !playback.paused {
+        playback.pending_steps = 0;
+    }
+    if playback.stepping != stepping {
+        playback.stepping = stepping;
+    }
+}
+
+fn style_playback_controls(sb: &mut StyleBuilder) {
+    sb.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .column_gap(4);
+}
+
+fn style_time_scale(sb: &mut StyleBuilder) {
+    sb.width(120);
+}
+
+/// Toolbar widget for the [`Playback`] resource: play/pause and step buttons, and a slider
+/// for the time scale.
+#[derive(Default)]
+pub struct PlaybackControls;
+
+impl UiTemplate for PlaybackControls {
+    fn build(&self, builder: &mut UiBuilder) {
+        let paused = builder.create_derived(|rcx| rcx.read_resource::<Playback>().paused);
+        let time_scale = builder.create_derived(|rcx| rcx.read_resource::<Playback>().time_scale);
+
+        let toggle_pause = builder.create_callback(|_: In<()>, mut world: DeferredWorld| {
+            let mut playback = world.resource_mut::<Playback>();
+            playback.paused = !playback.paused;
+        });
+        let step = builder.create_callback(|_: In<()>, mut world: DeferredWorld| {
+            world.resource_mut::<Playback>().step();
+        });
+        let set_time_scale = builder.create_callback(|value: In<f32>, mut world: DeferredWorld| {
+            world.resource_mut::<Playback>().time_scale = value.max(0.);
+        });
+        let variant = builder.create_derived(move |rcx| match paused.get(rcx) {
+            true => ButtonVariant::Selected,
+            false => ButtonVariant::Default,
+        });
+        let running = builder.create_derived(move |rcx| !paused.get(rcx));
+
+        builder
+            .spawn((Node::default(), Name::new("PlaybackControls")))
+            .style(style_playback_controls)
+            .create_children(|builder| {
+                builder.invoke(
+                    Button::new()
+                        .variant(variant)
+                        .on_click(toggle_pause)
+                        .children(move |builder| {
+                            builder.text_computed(move |rcx| {
+                                match paused.get(rcx) {
+                                    true => "Play",
+                                    false => "Pause",
+                                }
+                                .to_string()
+                            });
+                        }),
+                );
+                builder.invoke(
+                    Button::new()
+                        .labeled("Step")
+                        .disabled(running)
+                        .on_click(step),
+                );
+                builder.invoke(
+                    Slider::new()
+                        .label("Speed")
+                        .value(time_scale)
+                        .min(0.)
+                        .max(4.)
+                        .step(0.25)
+                        .precision(2)
+                        .style(style_time_scale)
+                        .on_change(set_time_scale),
+                );
+            });
+    }
+}
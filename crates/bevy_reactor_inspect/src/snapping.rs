@@ -0,0 +1,310 @@
+use bevy::{
+    ecs::world::DeferredWorld,
+    prelude::*,
+    ui::{self, Node},
+};
+use bevy_mod_stylebuilder::{StyleBuilder, StyleBuilderLayout};
+use bevy_reactor_builder::{
+    CreateChilden, EntityStyleBuilder, InvokeUiTemplate, TextBuilder, UiBuilder, UiTemplate,
+};
+use bevy_reactor_obsidian::{
+    input_dispatch::KeyboardFocus,
+    prelude::{key_name, Button, Checkbox, Popover, Shortcut, SpinBox},
+};
+use bevy_reactor_signals::Callback;
+
+/// Settings for snapping transforms while they are being edited, for example by a transform
+/// gizmo or when dragging nodes in a graph. Each kind of snapping can be enabled separately;
+/// holding the toggle key temporarily inverts all of them.
+///
+/// Editing tools should not read the `*_enabled` fields directly, but instead pass values
+/// through [`snap_translation`](Self::snap_translation) and the other `snap_` methods, which
+/// take the toggle key into account.
+#[derive(Resource, Clone, Debug)]
+pub struct SnapSettings {
+    /// Snap translations to a grid.
+    pub translate_enabled: bool,
+
+    /// Size of the translation grid.
+    pub translate_step: f32,
+
+    /// Snap rotations to an increment.
+    pub rotate_enabled: bool,
+
+    /// Rotation increment, in degrees.
+    pub rotate_step_degrees: f32,
+
+    /// Snap scales to an increment.
+    pub scale_enabled: bool,
+
+    /// Scale increment.
+    pub scale_step: f32,
+
+    /// Key which inverts snapping while held.
+    pub toggle_key: KeyCode,
+
+    /// Whether the toggle key is currently held.
+    toggled: bool,
+}
+
+impl Default for SnapSettings {
+    fn default() -> Self {
+        Self {
+            translate_enabled: false,
+            translate_step: 0.5,
+            rotate_enabled: false,
+            rotate_step_degrees: 15.,
+            scale_enabled: false,
+            scale_step: 0.1,
+            toggle_key: KeyCode::ControlLeft,
+            toggled: false,
+        }
+    }
+}
+
+impl SnapSettings {
+    /// Whether translations are currently snapped.
+    pub fn translate_active(&self) -> bool {
+        self.translate_enabled != self.toggled && self.translate_step > 0.
+    }
+
+    /// Whether rotations are currently snapped.
+    pub fn rotate_active(&self) -> bool {
+        self.rotate_enabled != self.toggled && self.rotate_step_degrees > 0.
+    }
+
+    /// Whether scales are currently snapped.
+    pub fn scale_active(&self) -> bool {
+        self.scale_enabled != self.toggled && self.scale_step > 0.
+    }
+
+    /// Snap a translation to the grid, if translation snapping is active.
+    pub fn snap_translation(&self, translation: Vec3) -> Vec3 {
+        if self.translate_active() {
+            (translation / self.translate_step).round() * self.translate_step
+        } else {
+            translation
+        }
+    }
+
+    /// Snap a 2d position to the grid, if translation snapping is active. Used for editors
+    /// which position things on a plane, such as node graphs; `scale` converts the grid size
+    /// into the editor's units.
+    pub fn snap_point(&self, point: Vec2, scale: f32) -> Vec2 {
+        let step = self.translate_step * scale;
+        if self.translate_active() && step > 0. {
+            (point / step).round() * step
+        } else {
+            point
+        }
+    }
+
+    /// Snap an angle, in radians, to the rotation increment, if rotation snapping is active.
+    pub fn snap_angle(&self, angle: f32) -> f32 {
+        if self.rotate_active() {
+            let step = self.rotate_step_degrees.to_radians();
+            (angle / step).round() * step
+        } else {
+            angle
+        }
+    }
+
+    /// Snap each of the euler angles of a rotation to the rotation increment, if rotation
+    /// snapping is active.
+    pub fn snap_rotation(&self, rotation: Quat) -> Quat {
+        if self.rotate_active() {
+            let (y, x, z) = rotation.to_euler(EulerRot::YXZ);
+            Quat::from_euler(
+                EulerRot::YXZ,
+                self.snap_angle(y),
+                self.snap_angle(x),
+                self.snap_angle(z),
+            )
+        } else {
+            rotation
+        }
+    }
+
+    /// Snap a scale to the scale increment, if scale snapping is active. Scales are never
+    /// snapped to zero.
+    pub fn snap_scale(&self, scale: Vec3) -> Vec3 {
+        if self.scale_active() {
+            let snapped = (scale / self.scale_step).round() * self.scale_step;
+            Vec3::select(
+                snapped.cmpeq(Vec3::ZERO),
+                scale.signum() * self.scale_step,
+                snapped,
+            )
+        } else {
+            scale
+        }
+    }
+}
+
+/// Track whether the snapping toggle key is held. The key is ignored while a widget has
+/// keyboard focus, so that it doesn't interfere with typing.
+pub(crate) fn update_snap_toggle(
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Option<Res<KeyboardFocus>>,
+    mut settings: ResMut<SnapSettings>,
+) {
+    let toggled =
+        keys.pressed(settings.toggle_key) && !focus.is_some_and(|focus| focus.0.is_some());
+    if settings.toggled != toggled {
+        settings.toggled = toggled;
+    }
+}
+
+/// Describe the snapping shortcuts for the shortcut help overlay.
+pub(crate) fn snapping_shortcuts(world: &World) -> Vec<Shortcut> {
+    let Some(settings) = world.get_resource::<SnapSettings>() else {
+        return Vec::new();
+    };
+    vec![Shortcut::new(
+        "Viewport",
+        "While editing",
+        format!("Hold {}", key_name(settings.toggle_key)),
+        "Invert snapping",
+    )]
+}
+
+fn style_snap_grid(sb: &mut StyleBuilder) {
+    sb.display(ui::Display::Grid)
+        .grid_template_columns(vec![
+            ui::RepeatedGridTrack::auto(1),
+            ui::RepeatedGridTrack::px(1, 80.),
+        ])
+        .align_items(ui::AlignItems::Center)
+        .column_gap(8)
+        .row_gap(4);
+}
+
+/// One of the kinds of snapping in [`SnapSettings`].
+#[derive(Clone, Copy)]
+enum SnapKind {
+    Translate,
+    Rotate,
+    Scale,
+}
+
+impl SnapKind {
+    fn label(self) -> &'static str {
+        match self {
+            SnapKind::Translate => "Translate",
+            SnapKind::Rotate => "Rotate",
+            SnapKind::Scale => "Scale",
+        }
+    }
+
+    /// The enable toggle and increment for this kind of snapping.
+    fn fields(self, settings: &mut SnapSettings) -> (&mut bool, &mut f32) {
+        match self {
+            SnapKind::Translate => (
+                &mut settings.translate_enabled,
+                &mut settings.translate_step,
+            ),
+            SnapKind::Rotate => (
+                &mut settings.rotate_enabled,
+                &mut settings.rotate_step_degrees,
+            ),
+            SnapKind::Scale => (&mut settings.scale_enabled, &mut settings.scale_step),
+        }
+    }
+
+    fn get(self, settings: &SnapSettings) -> (bool, f32) {
+        match self {
+            SnapKind::Translate => (settings.translate_enabled, settings.translate_step),
+            SnapKind::Rotate => (settings.rotate_enabled, settings.rotate_step_degrees),
+            SnapKind::Scale => (settings.scale_enabled, settings.scale_step),
+        }
+    }
+
+    /// Smallest allowed increment, and the precision to display it with.
+    fn range(self) -> (f32, usize) {
+        match self {
+            SnapKind::Rotate => (1., 0),
+            SnapKind::Translate | SnapKind::Scale => (0.01, 2),
+        }
+    }
+}
+
+/// Editor for the [`SnapSettings`] resource: a checkbox and increment for each kind of
+/// snapping.
+#[derive(Default)]
+pub struct SnapSettingsPanel;
+
+impl UiTemplate for SnapSettingsPanel {
+    fn build(&self, builder: &mut UiBuilder) {
+        builder
+            .spawn((Node::default(), Name::new("SnapSettingsPanel")))
+            .style(style_snap_grid)
+            .create_children(|builder| {
+                for kind in [SnapKind::Translate, SnapKind::Rotate, SnapKind::Scale] {
+                    snap_row(builder, kind);
+                }
+            });
+    }
+}
+
+/// Build the checkbox and increment editor for one kind of snapping.
+fn snap_row(builder: &mut UiBuilder, kind: SnapKind) {
+    let (min, precision) = kind.range();
+    let enabled =
+        builder.create_derived(move |rcx| kind.get(rcx.read_resource::<SnapSettings>()).0);
+    let step = builder.create_derived(move |rcx| kind.get(rcx.read_resource::<SnapSettings>()).1);
+    let on_toggle = builder.create_callback(move |value: In<bool>, mut world: DeferredWorld| {
+        *kind.fields(&mut world.resource_mut::<SnapSettings>()).0 = *value;
+    });
+    let on_step = builder.create_callback(move |value: In<f32>, mut world: DeferredWorld| {
+        *kind.fields(&mut world.resource_mut::<SnapSettings>()).1 = value.max(min);
+    });
+    builder.invoke(
+        Checkbox::new()
+            .labeled(kind.label())
+            .checked(enabled)
+            .on_change(on_toggle),
+    );
+    builder.invoke(
+        SpinBox::new()
+            .value(step)
+            .min(min)
+            .step(if precision == 0 { 1. } else { 0.1 })
+            .precision(precision)
+            .on_change(on_step),
+    );
+}
+
+/// A button which opens the [`SnapSettingsPanel`] in a popover.
+#[derive(Default)]
+pub struct SnapSettingsButton;
+
+impl UiTemplate for SnapSettingsButton {
+    fn build(&self, builder: &mut UiBuilder) {
+        let open = builder.create_mutable(false);
+        let on_click: Callback =
+            builder.create_callback(move |_: In<()>, mut world: DeferredWorld| {
+                let value = open.get(&world);
+                open.set(&mut world, !value);
+            });
+        let on_close: Callback =
+            builder.create_callback(move |_: In<()>, mut world: DeferredWorld| {
+                open.set(&mut world, false);
+            });
+        builder
+            .spawn((Node::default(), Name::new("SnapSettingsButton")))
+            .create_children(|builder| {
+                let anchor = builder.parent();
+                builder.invoke(Button::new().labeled("Snap").on_click(on_click));
+                builder.invoke(
+                    Popover::new()
+                        .open(open)
+                        .anchor(anchor)
+                        .on_close(on_close)
+                        .children(|builder| {
+                            builder.text("Snapping");
+                            builder.invoke(SnapSettingsPanel);
+                        }),
+                );
+            });
+    }
+}
@@ -0,0 +1,498 @@
+use std::fmt::Write;
+
+use bevy::{
+    color::Srgba,
+    picking::{focus::HoverMap, pointer::PointerId},
+    prelude::*,
+    ui::{self, experimental::GhostNode, ComputedNode},
+};
+use bevy_mod_stylebuilder::StyleProvenance;
+use bevy_reactor_obsidian::{
+    colors,
+    prelude::{key_name, Shortcut},
+};
+
+use crate::InspectedEntity;
+
+const OUTLINE_COLOR: Srgba = Srgba::new(0.0, 0.8, 1.0, 0.35);
+const GHOST_CHILD_COLOR: Srgba = Srgba::new(1.0, 0.0, 1.0, 0.6);
+const HOVER_COLOR: Srgba = Srgba::new(1.0, 0.9, 0.2, 1.0);
+const SELECTED_COLOR: Srgba = Srgba::new(1.0, 0.4, 0.1, 1.0);
+const MARGIN_FILL: Srgba = Srgba::new(0.97, 0.6, 0.3, 0.3);
+const PADDING_FILL: Srgba = Srgba::new(0.5, 0.8, 0.4, 0.3);
+const GAP_FILL: Srgba = Srgba::new(0.7, 0.4, 0.9, 0.3);
+
+/// Settings and state for the UI debug overlay. When enabled, every UI node is outlined, and
+/// the node under the pointer is highlighted along with its margin, padding and gaps, and a
+/// tooltip describing it. Alt-clicking a node selects it in the world inspector.
+///
+/// With the `provenance` feature enabled, the tooltip also lists which style function set each
+/// of the node's style properties, and where it was applied.
+#[derive(Resource)]
+pub struct UiDebugger {
+    /// Whether the overlay is shown.
+    pub enabled: bool,
+
+    /// Key which toggles the overlay.
+    pub hotkey: KeyCode,
+
+    /// Whether to outline every node, or only the hovered and selected ones.
+    pub outline_all: bool,
+
+    /// The node currently under the pointer.
+    pub hovered: Option<Entity>,
+}
+
+impl Default for UiDebugger {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            hotkey: KeyCode::F12,
+            outline_all: true,
+            hovered: None,
+        }
+    }
+}
+
+/// Marks the entities which make up the overlay, so that they are not themselves outlined.
+#[derive(Component)]
+pub(crate) struct DebugOverlayPart;
+
+/// Entities of the overlay, created when it is first enabled.
+#[derive(Default)]
+pub(crate) struct DebugOverlay {
+    root: Option<Entity>,
+    tooltip: Option<Entity>,
+    boxes: Vec<Entity>,
+}
+
+/// One rectangle to draw, in logical pixels.
+struct DebugBox {
+    rect: Rect,
+    border: Srgba,
+    fill: Srgba,
+}
+
+/// Describe the UI debugger's shortcuts for the shortcut help overlay.
+pub(crate) fn ui_debugger_shortcuts(world: &World) -> Vec<Shortcut> {
+    let Some(debugger) = world.get_resource::<UiDebugger>() else {
+        return Vec::new();
+    };
+    vec![
+        Shortcut::new(
+            "Debugging",
+            "Global",
+            key_name(debugger.hotkey),
+            "Toggle the UI debugger",
+        ),
+        Shortcut::new(
+            "Debugging",
+            "UI debugger",
+            "Alt+Click",
+            "Inspect the node under the pointer",
+        ),
+    ]
+}
+
+pub(crate) fn toggle_ui_debugger(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut debugger: ResMut<UiDebugger>,
+) {
+    if keys.just_pressed(debugger.hotkey) {
+        debugger.enabled = !debugger.enabled;
+    }
+}
+
+/// Returns the bounds of a node, in logical pixels.
+fn node_rect(node: &ComputedNode, transform: &GlobalTransform) -> Rect {
+    let scale = node.inverse_scale_factor();
+    Rect::from_center_size(
+        transform.translation().truncate() * scale,
+        node.size() * scale,
+    )
+}
+
+/// Returns the four strips between `outer` and `outer` inset by the given amounts.
+fn frame_strips(outer: Rect, left: f32, right: f32, top: f32, bottom: f32) -> [Rect; 4] {
+    let inner = Rect::new(
+        outer.min.x + left,
+        outer.min.y + top,
+        outer.max.x - right,
+        outer.max.y - bottom,
+    );
+    [
+        Rect::new(outer.min.x, outer.min.y, outer.max.x, inner.min.y),
+        Rect::new(outer.min.x, inner.max.y, outer.max.x, outer.max.y),
+        Rect::new(outer.min.x, inner.min.y, inner.min.x, inner.max.y),
+        Rect::new(inner.max.x, inner.min.y, outer.max.x, inner.max.y),
+    ]
+}
+
+/// Returns a length in logical pixels, if it is given in pixels.
+fn px(val: ui::Val) -> f32 {
+    match val {
+        ui::Val::Px(px) => px,
+        _ => 0.,
+    }
+}
+
+/// Returns the name of the nearest named ancestor, which is usually the root node of the
+/// template which built the entity.
+fn owner_name(
+    entity: Entity,
+    q_names: &Query<&Name>,
+    q_parents: &Query<&Parent>,
+) -> Option<String> {
+    q_parents
+        .iter_ancestors(entity)
+        .find_map(|ancestor| q_names.get(ancestor).ok())
+        .map(|name| name.to_string())
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub(crate) fn update_ui_debugger(
+    mut commands: Commands,
+    mut overlay: Local<DebugOverlay>,
+    mut debugger: ResMut<UiDebugger>,
+    mut inspected: ResMut<InspectedEntity>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    hover_map: Option<Res<HoverMap>>,
+    q_nodes: Query<
+        (
+            Entity,
+            &Node,
+            &ComputedNode,
+            &GlobalTransform,
+            &InheritedVisibility,
+        ),
+        Without<DebugOverlayPart>,
+    >,
+    q_children: Query<&Children>,
+    q_parents: Query<&Parent>,
+    q_names: Query<&Name>,
+    q_ghosts: Query<(), With<GhostNode>>,
+    q_provenance: Query<&StyleProvenance>,
+    mut q_parts: Query<
+        (&mut Node, &mut BorderColor, &mut BackgroundColor),
+        (With<DebugOverlayPart>, Without<Text>),
+    >,
+    mut q_text: Query<&mut Text, With<DebugOverlayPart>>,
+) {
+    let Some(root) = overlay.root else {
+        if debugger.enabled {
+            let root = commands
+                .spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        width: Val::Percent(100.),
+                        height: Val::Percent(100.),
+                        ..default()
+                    },
+                    Name::new("UiDebugOverlay"),
+                    BorderColor(Color::NONE),
+                    BackgroundColor(Color::NONE),
+                    GlobalZIndex(i32::MAX),
+                    PickingBehavior::IGNORE,
+                    DebugOverlayPart,
+                ))
+                .id();
+            let tooltip = commands
+                .spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        padding: UiRect::all(Val::Px(4.)),
+                        display: Display::None,
+                        ..default()
+                    },
+                    BackgroundColor(colors::U1.with_alpha(0.9).into()),
+                    BorderColor(Color::NONE),
+                    PickingBehavior::IGNORE,
+                    DebugOverlayPart,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::default(),
+                        TextFont::from_font_size(12.),
+                        TextColor(colors::FOREGROUND.into()),
+                        PickingBehavior::IGNORE,
+                        DebugOverlayPart,
+                    ));
+                })
+                .set_parent(root)
+                .id();
+            overlay.root = Some(root);
+            overlay.tooltip = Some(tooltip);
+        }
+        return;
+    };
+
+    if let Ok((mut node, _, _)) = q_parts.get_mut(root) {
+        let display = if debugger.enabled {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        if node.display != display {
+            node.display = display;
+        }
+    }
+    if !debugger.enabled {
+        if debugger.hovered.is_some() {
+            debugger.hovered = None;
+        }
+        return;
+    }
+
+    // The topmost node under the mouse.
+    let hovered = hover_map.as_ref().and_then(|hover_map| {
+        hover_map.get(&PointerId::Mouse).and_then(|hits| {
+            hits.iter()
+                .filter(|(entity, _)| q_nodes.contains(**entity))
+                .min_by(|a, b| a.1.depth.total_cmp(&b.1.depth))
+                .map(|(entity, _)| *entity)
+        })
+    });
+    if debugger.hovered != hovered {
+        debugger.hovered = hovered;
+    }
+    let alt = keys.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]);
+    if alt && mouse.just_pressed(MouseButton::Left) && hovered.is_some() {
+        inspected.0 = hovered;
+    }
+
+    let mut boxes: Vec<DebugBox> = Vec::new();
+    if debugger.outline_all {
+        for (entity, _, computed, transform, visibility) in q_nodes.iter() {
+            if !visibility.get() || computed.size() == Vec2::ZERO {
+                continue;
+            }
+            let under_ghost = q_parents
+                .get(entity)
+                .is_ok_and(|parent| q_ghosts.contains(parent.get()));
+            boxes.push(DebugBox {
+                rect: node_rect(computed, transform),
+                border: if under_ghost {
+                    GHOST_CHILD_COLOR
+                } else {
+                    OUTLINE_COLOR
+                },
+                fill: Srgba::NONE,
+            });
+        }
+    }
+
+    if let Some((_, _, computed, transform, _)) = inspected.0.and_then(|e| q_nodes.get(e).ok()) {
+        boxes.push(DebugBox {
+            rect: node_rect(computed, transform),
+            border: SELECTED_COLOR,
+            fill: Srgba::NONE,
+        });
+    }
+
+    let mut tooltip_text = String::new();
+    let mut tooltip_pos = Vec2::ZERO;
+    if let Some((entity, node, computed, transform, _)) = hovered.and_then(|e| q_nodes.get(e).ok())
+    {
+        let rect = node_rect(computed, transform);
+        let scale = computed.inverse_scale_factor();
+
+        // Margin, outside the node.
+        let margin = Rect::new(
+            rect.min.x - px(node.margin.left),
+            rect.min.y - px(node.margin.top),
+            rect.max.x + px(node.margin.right),
+            rect.max.y + px(node.margin.bottom),
+        );
+        for strip in frame_strips(
+            margin,
+            px(node.margin.left),
+            px(node.margin.right),
+            px(node.margin.top),
+            px(node.margin.bottom),
+        ) {
+            boxes.push(DebugBox {
+                rect: strip,
+                border: Srgba::NONE,
+                fill: MARGIN_FILL,
+            });
+        }
+
+        // Padding, inside the border.
+        let border = computed.border();
+        let padding = computed.padding();
+        let padding_box = Rect::new(
+            rect.min.x + border.left * scale,
+            rect.min.y + border.top * scale,
+            rect.max.x - border.right * scale,
+            rect.max.y - border.bottom * scale,
+        );
+        for strip in frame_strips(
+            padding_box,
+            padding.left * scale,
+            padding.right * scale,
+            padding.top * scale,
+            padding.bottom * scale,
+        ) {
+            boxes.push(DebugBox {
+                rect: strip,
+                border: Srgba::NONE,
+                fill: PADDING_FILL,
+            });
+        }
+
+        // Gaps between consecutive in-flow children of a flex container.
+        if node.display == Display::Flex {
+            let row = matches!(
+                node.flex_direction,
+                FlexDirection::Row | FlexDirection::RowReverse
+            );
+            let mut child_rects: Vec<Rect> = q_children
+                .get(entity)
+                .map(|children| {
+                    children
+                        .iter()
+                        .filter_map(|child| q_nodes.get(*child).ok())
+                        .filter(|(_, n, c, _, _)| {
+                            n.position_type == PositionType::Relative && c.size() != Vec2::ZERO
+                        })
+                        .map(|(_, _, c, t, _)| node_rect(c, t))
+                        .collect()
+                })
+                .unwrap_or_default();
+            if row {
+                child_rects.sort_by(|a, b| a.min.x.total_cmp(&b.min.x));
+            } else {
+                child_rects.sort_by(|a, b| a.min.y.total_cmp(&b.min.y));
+            }
+            for pair in child_rects.windows(2) {
+                let gap = if row {
+                    Rect::new(pair[0].max.x, rect.min.y, pair[1].min.x, rect.max.y)
+                } else {
+                    Rect::new(rect.min.x, pair[0].max.y, rect.max.x, pair[1].min.y)
+                };
+                if gap.width() > 0. && gap.height() > 0. {
+                    boxes.push(DebugBox {
+                        rect: gap,
+                        border: Srgba::NONE,
+                        fill: GAP_FILL,
+                    });
+                }
+            }
+        }
+
+        boxes.push(DebugBox {
+            rect,
+            border: HOVER_COLOR,
+            fill: Srgba::NONE,
+        });
+
+        let size = rect.size();
+        let name = q_names.get(entity).map_or_else(
+            |_| format!("{}", entity),
+            |name| format!("{} ({})", name, entity),
+        );
+        let _ = writeln!(tooltip_text, "{}", name);
+        let _ = writeln!(tooltip_text, "{:.1} x {:.1}", size.x, size.y);
+        let _ = write!(tooltip_text, "{:?}", node.display);
+        match node.display {
+            Display::Flex => {
+                let _ = write!(
+                    tooltip_text,
+                    " {:?}, gap {:?} / {:?}",
+                    node.flex_direction, node.column_gap, node.row_gap
+                );
+            }
+            Display::Grid => {
+                let _ = write!(
+                    tooltip_text,
+                    ", gap {:?} / {:?}",
+                    node.column_gap, node.row_gap
+                );
+            }
+            _ => {}
+        }
+        if let Some(owner) = owner_name(entity, &q_names, &q_parents) {
+            let _ = write!(tooltip_text, "\nin {}", owner);
+        }
+        if q_parents
+            .get(entity)
+            .is_ok_and(|parent| q_ghosts.contains(parent.get()))
+        {
+            tooltip_text.push_str("\nchild of ghost node");
+        }
+        // Only present when the `provenance` feature is enabled.
+        if let Ok(provenance) = q_provenance.get(entity) {
+            for (property, source) in provenance.sorted() {
+                let _ = write!(tooltip_text, "\n{} set by {}", property, source);
+            }
+        }
+        tooltip_pos = Vec2::new(rect.min.x, rect.max.y + 4.);
+    }
+
+    // Spawn more boxes if needed; they are positioned on the next frame.
+    while overlay.boxes.len() < boxes.len() {
+        let entity = commands
+            .spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    border: UiRect::all(Val::Px(1.)),
+                    ..default()
+                },
+                BorderColor(Color::NONE),
+                BackgroundColor(Color::NONE),
+                PickingBehavior::IGNORE,
+                DebugOverlayPart,
+            ))
+            .set_parent(root)
+            .id();
+        overlay.boxes.push(entity);
+    }
+
+    for (index, entity) in overlay.boxes.iter().enumerate() {
+        let Ok((mut node, mut border, mut background)) = q_parts.get_mut(*entity) else {
+            continue;
+        };
+        match boxes.get(index) {
+            Some(b) => {
+                node.display = Display::Flex;
+                node.left = Val::Px(b.rect.min.x);
+                node.top = Val::Px(b.rect.min.y);
+                node.width = Val::Px(b.rect.width());
+                node.height = Val::Px(b.rect.height());
+                border.0 = b.border.into();
+                background.0 = b.fill.into();
+            }
+            None => {
+                if node.display != Display::None {
+                    node.display = Display::None;
+                }
+            }
+        }
+    }
+
+    if let Some(tooltip) = overlay.tooltip {
+        if let Ok((mut node, _, _)) = q_parts.get_mut(tooltip) {
+            if tooltip_text.is_empty() {
+                if node.display != Display::None {
+                    node.display = Display::None;
+                }
+            } else {
+                node.display = Display::Flex;
+                node.left = Val::Px(tooltip_pos.x);
+                node.top = Val::Px(tooltip_pos.y);
+            }
+        }
+        if let Some(text_entity) = q_children
+            .get(tooltip)
+            .ok()
+            .and_then(|children| children.first().copied())
+        {
+            if let Ok(mut text) = q_text.get_mut(text_entity) {
+                if text.0 != tooltip_text {
+                    text.0 = tooltip_text;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::{color::Alpha, pbr::wireframe::WireframeConfig, prelude::*};
+use bevy_reactor_obsidian::colors;
+
+/// Toggles for the overlays drawn in the editor viewport.
+#[derive(Resource, Clone, Debug)]
+pub struct ViewportOverlays {
+    /// Draw a reference grid on the ground plane.
+    pub grid: bool,
+
+    /// Draw every mesh as a wireframe.
+    pub wireframe: bool,
+
+    /// Draw the bounding box of every mesh.
+    pub aabbs: bool,
+
+    /// Number of grid cells along each axis.
+    pub grid_cells: u32,
+
+    /// Distance between grid lines.
+    pub grid_spacing: f32,
+}
+
+impl Default for ViewportOverlays {
+    fn default() -> Self {
+        Self {
+            grid: true,
+            wireframe: false,
+            aabbs: false,
+            grid_cells: 20,
+            grid_spacing: 1.,
+        }
+    }
+}
+
+pub(crate) fn draw_viewport_grid(overlays: Res<ViewportOverlays>, mut gizmos: Gizmos) {
+    if !overlays.grid {
+        return;
+    }
+    gizmos
+        .grid(
+            Isometry3d::from_rotation(Quat::from_rotation_x(FRAC_PI_2)),
+            UVec2::splat(overlays.grid_cells),
+            Vec2::splat(overlays.grid_spacing),
+            colors::U4.with_alpha(0.5),
+        )
+        .outer_edges();
+}
+
+/// Apply the wireframe and bounding box toggles to Bevy's global settings for them.
+pub(crate) fn sync_viewport_overlays(
+    overlays: Res<ViewportOverlays>,
+    wireframe: Option<ResMut<WireframeConfig>>,
+    mut gizmo_config: ResMut<GizmoConfigStore>,
+) {
+    if !overlays.is_changed() {
+        return;
+    }
+    if let Some(mut wireframe) = wireframe {
+        wireframe.global = overlays.wireframe;
+    }
+    gizmo_config.config_mut::<AabbGizmoConfigGroup>().1.draw_all = overlays.aabbs;
+}
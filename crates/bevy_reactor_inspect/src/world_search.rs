@@ -0,0 +1,367 @@
+use std::ops::Range;
+
+use bevy::{
+    core::Name,
+    ecs::{component::ComponentId, world::DeferredWorld},
+    prelude::{Children, Click, Entity, Parent, Pointer, Trigger, Vec2, World},
+    ui::{self, Node},
+};
+use bevy_mod_stylebuilder::{
+    StyleBuilder, StyleBuilderBackground, StyleBuilderFont, StyleBuilderLayout, StyleHandle,
+    StyleTuple,
+};
+use bevy_reactor_builder::{
+    CreateChilden, EntityStyleBuilder, ForEachBuilder, InvokeUiTemplate, TextBuilder, UiBuilder,
+    UiTemplate,
+};
+use bevy_reactor_obsidian::{
+    colors,
+    fuzzy::{fuzzy_filter, FuzzyMatch},
+    prelude::{HighlightedText, ScrollView},
+    scrolling::CreateScrollSignals,
+    typography,
+};
+use bevy_reactor_signals::{Callback, IntoSignal, RunCallback, Signal};
+
+use crate::inspector_panel::InspectorPanelRoot;
+
+/// Height of each row in the result list.
+const ROW_HEIGHT: f32 = 20.;
+
+/// Maximum number of results shown in each group.
+const MAX_GROUP_RESULTS: usize = 200;
+
+/// A result from a [`WorldSearch`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchHit {
+    /// An entity whose `Name` matched the query.
+    Entity(Entity),
+    /// A component type whose name matched the query.
+    Component(ComponentId),
+    /// A resource type whose name matched the query.
+    Resource(ComponentId),
+}
+
+impl SearchHit {
+    /// The entity to select for this result: the entity itself, or for a component, the
+    /// first entity which has that component. Resources have no entity.
+    pub fn entity(&self, world: &World) -> Option<Entity> {
+        match self {
+            SearchHit::Entity(entity) => Some(*entity),
+            SearchHit::Component(id) => world
+                .archetypes()
+                .iter()
+                .find(|archetype| archetype.contains(*id) && !archetype.is_empty())
+                .and_then(|archetype| archetype.entities().first())
+                .map(|entity| entity.id()),
+            SearchHit::Resource(_) => None,
+        }
+    }
+}
+
+/// A row in the flattened list of results.
+#[derive(Clone, PartialEq)]
+enum SearchRow {
+    Group {
+        title: &'static str,
+        count: usize,
+    },
+    Hit {
+        hit: SearchHit,
+        label: String,
+        detail: String,
+        ranges: Vec<Range<usize>>,
+    },
+}
+
+fn style_results(sb: &mut StyleBuilder) {
+    sb.flex_grow(1.).background_color(colors::U1);
+}
+
+fn style_row(sb: &mut StyleBuilder) {
+    sb.position(ui::PositionType::Absolute)
+        .display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .column_gap(6)
+        .left(0)
+        .right(0)
+        .height(ROW_HEIGHT)
+        .padding((4, 0))
+        .overflow(ui::OverflowAxis::Clip);
+}
+
+fn style_group_row(sb: &mut StyleBuilder) {
+    sb.color(colors::DIM).font_size(12);
+}
+
+fn style_hit_row(sb: &mut StyleBuilder) {
+    sb.color(colors::FOREGROUND).padding_left(12);
+}
+
+fn style_hit_detail(sb: &mut StyleBuilder) {
+    sb.color(colors::DIM)
+        .font_size(12)
+        .margin_left(ui::Val::Auto);
+}
+
+/// Searches the world for entities by `Name`, and for component and resource types by type
+/// name, using fuzzy matching. Results are grouped by kind and shown in a list where only the
+/// rows which are scrolled into view are built, so broad queries on large worlds stay cheap.
+///
+/// Results are recomputed whenever the query changes; an empty query shows no results.
+pub struct WorldSearch {
+    /// The search query.
+    pub query: Signal<String>,
+
+    /// Callback called when a result is clicked.
+    pub on_select: Option<Callback<SearchHit>>,
+
+    /// Additional styles to be applied to the result list.
+    pub style: StyleHandle,
+}
+
+impl WorldSearch {
+    /// Create a new world search for the given query.
+    pub fn new(query: impl IntoSignal<String>) -> Self {
+        Self {
+            query: query.into_signal(),
+            on_select: None,
+            style: StyleHandle::default(),
+        }
+    }
+
+    /// Set the callback called when a result is clicked.
+    pub fn on_select(mut self, callback: Callback<SearchHit>) -> Self {
+        self.on_select = Some(callback);
+        self
+    }
+
+    /// Set additional styles to be applied to the result list.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl UiTemplate for WorldSearch {
+    fn build(&self, builder: &mut UiBuilder) {
+        let query = self.query.clone();
+        let rows = builder.create_derived(move |rcx| {
+            let query = query.get_clone(rcx);
+            search_world(rcx.world(), &query)
+        });
+
+        let view_id = builder.spawn(Node::default()).id();
+        // The portion of the list which is scrolled into view, as (scroll_top, visible_size).
+        let scroll = builder.create_scroll_signals(view_id);
+        let viewport = builder
+            .create_derived(move |rcx| (scroll.offset.get(rcx).y, scroll.visible_size.get(rcx)));
+
+        let on_select = self.on_select;
+        builder.invoke(
+            ScrollView::new()
+                .entity(view_id)
+                .style((style_results, self.style.clone()))
+                .content_style(typography::text_default)
+                .scroll_enable_y(true)
+                .children(move |builder| {
+                    let rows = rows.clone();
+                    builder
+                        .spawn((Node::default(), Name::new("WorldSearch::Rows")))
+                        .style_dyn(
+                            {
+                                let rows = rows.clone();
+                                move |rcx| rows.map(rcx, Vec::len)
+                            },
+                            |count, sb| {
+                                sb.width(ui::Val::Percent(100.))
+                                    .height(count as f32 * ROW_HEIGHT);
+                            },
+                        )
+                        .create_children(|builder| {
+                            builder.for_each(
+                                move |rcx| {
+                                    let (scroll_top, visible) = viewport.get(rcx);
+                                    let first = (scroll_top / ROW_HEIGHT).floor() as usize;
+                                    let last =
+                                        ((scroll_top + visible.y) / ROW_HEIGHT).ceil() as usize;
+                                    rows.get_clone(rcx)
+                                        .into_iter()
+                                        .enumerate()
+                                        .skip(first)
+                                        .take(last + 1 - first)
+                                },
+                                move |(index, row), builder| {
+                                    build_row(builder, *index, row, on_select);
+                                },
+                                |_| {},
+                            );
+                        });
+                }),
+        );
+    }
+}
+
+fn build_row(
+    builder: &mut UiBuilder,
+    index: usize,
+    row: &SearchRow,
+    on_select: Option<Callback<SearchHit>>,
+) {
+    let top = index as f32 * ROW_HEIGHT;
+    match row {
+        SearchRow::Group { title, count } => {
+            let title = format!("{} ({})", title, count);
+            builder
+                .spawn((Node::default(), Name::new("WorldSearch::Group")))
+                .styles((style_row, style_group_row, move |sb: &mut StyleBuilder| {
+                    sb.top(top);
+                }))
+                .create_children(|builder| {
+                    builder.text(title);
+                });
+        }
+        SearchRow::Hit {
+            hit,
+            label,
+            detail,
+            ranges,
+        } => {
+            let hit = *hit;
+            builder
+                .spawn((Node::default(), Name::new("WorldSearch::Hit")))
+                .styles((style_row, style_hit_row, move |sb: &mut StyleBuilder| {
+                    sb.top(top);
+                }))
+                .observe(
+                    move |mut trigger: Trigger<Pointer<Click>>, mut world: DeferredWorld| {
+                        trigger.propagate(false);
+                        if let Some(on_select) = on_select {
+                            world.run_callback(on_select, hit);
+                        }
+                    },
+                )
+                .create_children(|builder| {
+                    builder.invoke(HighlightedText::new(label.clone()).ranges(ranges.clone()));
+                    builder
+                        .spawn(Node::default())
+                        .style(style_hit_detail)
+                        .create_children(|builder| {
+                            builder.text(detail.clone());
+                        });
+                });
+        }
+    }
+}
+
+/// Match the query against the world, returning the grouped rows to display.
+fn search_world(world: &World, query: &str) -> Vec<SearchRow> {
+    let mut rows = Vec::new();
+    if query.trim().is_empty() {
+        return rows;
+    }
+
+    let entities: Vec<(Entity, String)> = world
+        .iter_entities()
+        .filter(|entity| !is_inspector_ui(world, entity.id()))
+        .filter_map(|entity| Some((entity.id(), entity.get::<Name>()?.to_string())))
+        .collect();
+    push_group(
+        &mut rows,
+        "Entities",
+        fuzzy_filter(query, entities, |(_, name)| name),
+        |(entity, name)| (SearchHit::Entity(entity), name, entity.to_string()),
+    );
+
+    let components: Vec<(ComponentId, String, usize)> = world
+        .components()
+        .iter()
+        .filter_map(|info| {
+            let count: usize = world
+                .archetypes()
+                .iter()
+                .filter(|archetype| archetype.contains(info.id()))
+                .map(|archetype| archetype.len())
+                .sum();
+            (count > 0).then(|| (info.id(), short_type_name(info.name()), count))
+        })
+        .collect();
+    push_group(
+        &mut rows,
+        "Components",
+        fuzzy_filter(query, components, |(_, name, _)| name),
+        |(id, name, count)| {
+            let detail = match count {
+                1 => "1 entity".to_string(),
+                n => format!("{} entities", n),
+            };
+            (SearchHit::Component(id), name, detail)
+        },
+    );
+
+    let resources: Vec<(ComponentId, String)> = world
+        .iter_resources()
+        .map(|(info, _)| (info.id(), short_type_name(info.name())))
+        .collect();
+    push_group(
+        &mut rows,
+        "Resources",
+        fuzzy_filter(query, resources, |(_, name)| name),
+        |(id, name)| (SearchHit::Resource(id), name, String::new()),
+    );
+
+    rows
+}
+
+/// Append a group header followed by its best matches.
+fn push_group<T>(
+    rows: &mut Vec<SearchRow>,
+    title: &'static str,
+    matches: Vec<(T, FuzzyMatch)>,
+    hit: impl Fn(T) -> (SearchHit, String, String),
+) {
+    if matches.is_empty() {
+        return;
+    }
+    rows.push(SearchRow::Group {
+        title,
+        count: matches.len(),
+    });
+    for (item, m) in matches.into_iter().take(MAX_GROUP_RESULTS) {
+        let (hit, label, detail) = hit(item);
+        rows.push(SearchRow::Hit {
+            hit,
+            label,
+            detail,
+            ranges: m.ranges,
+        });
+    }
+}
+
+/// Whether the entity is part of the inspector's own UI, which is excluded from results.
+fn is_inspector_ui(world: &World, entity: Entity) -> bool {
+    let mut current = Some(entity);
+    while let Some(entity) = current {
+        if world.get::<InspectorPanelRoot>(entity).is_some() {
+            return true;
+        }
+        current = world.get::<Parent>(entity).map(|parent| parent.get());
+    }
+    false
+}
+
+/// Strip module paths from a type name, including from any generic parameters.
+fn short_type_name(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut start = 0;
+    for (index, c) in name.char_indices() {
+        if matches!(c, '<' | '>' | ',' | ' ' | '(' | ')' | '[' | ']' | ';' | '&') {
+            result.push_str(name[start..index].rsplit("::").next().unwrap_or_default());
+            result.push(c);
+            start = index + c.len_utf8();
+        }
+    }
+    result.push_str(name[start..].rsplit("::").next().unwrap_or_default());
+    result
+}
@@ -0,0 +1,115 @@
+use bevy::{prelude::*, ui::ComputedNode};
+
+use super::{AnimatedPxHeight, AnimatedPxWidth, AnimatedTransition};
+
+/// Which dimensions of a node are animated by [`AnimateSize`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SizeAxes {
+    /// Animate the width only.
+    Width,
+
+    /// Animate the height only.
+    #[default]
+    Height,
+
+    /// Animate both width and height.
+    Both,
+}
+
+impl SizeAxes {
+    fn width(self) -> bool {
+        matches!(self, SizeAxes::Width | SizeAxes::Both)
+    }
+
+    fn height(self) -> bool {
+        matches!(self, SizeAxes::Height | SizeAxes::Both)
+    }
+}
+
+/// ECS component which animates the size of a container node to match the size of its first
+/// child, the content. Whenever the measured size of the content changes, the container's
+/// width and / or height is tweened to the new size. The container should clip its overflow,
+/// and the content should not shrink to fit the container, otherwise the content would be
+/// measured at the container's size.
+#[derive(Component, Clone, Debug)]
+pub struct AnimateSize {
+    /// Which dimensions to animate.
+    pub axes: SizeAxes,
+
+    /// Duration of the transition, in seconds.
+    pub duration: f32,
+
+    /// The last measured size of the content.
+    target: Option<Vec2>,
+}
+
+impl AnimateSize {
+    /// Create a new size animation.
+    pub fn new(axes: SizeAxes, duration: f32) -> Self {
+        Self {
+            axes,
+            duration,
+            target: None,
+        }
+    }
+}
+
+/// Measures the content of each [`AnimateSize`] container after layout, and starts a transition
+/// if the content size changed.
+pub(crate) fn animate_sizes(
+    mut commands: Commands,
+    mut q_containers: Query<(Entity, &mut AnimateSize, &ComputedNode, &Children)>,
+    q_content: Query<&ComputedNode>,
+) {
+    for (entity, mut anim, node, children) in q_containers.iter_mut() {
+        let Some(content) = children.first().and_then(|c| q_content.get(*c).ok()) else {
+            continue;
+        };
+        let target = content.size() * content.inverse_scale_factor();
+        let current = node.size() * node.inverse_scale_factor();
+        let axes = anim.axes;
+        let duration = anim.duration;
+        match anim.target {
+            // On the first layout, adopt the content size without animating.
+            None => {
+                anim.target = Some(target);
+                commands
+                    .entity(entity)
+                    .queue(move |mut entt: EntityWorldMut| {
+                        if let Some(mut node) = entt.get_mut::<Node>() {
+                            if axes.width() {
+                                node.width = Val::Px(target.x);
+                            }
+                            if axes.height() {
+                                node.height = Val::Px(target.y);
+                            }
+                        }
+                    });
+            }
+            Some(prev) if (prev - target).abs().max_element() > 0.5 => {
+                anim.target = Some(target);
+                commands
+                    .entity(entity)
+                    .queue(move |mut entt: EntityWorldMut| {
+                        if axes.width() {
+                            AnimatedTransition::<AnimatedPxWidth>::start(
+                                &mut entt,
+                                target.x,
+                                Some(current.x),
+                                duration,
+                            );
+                        }
+                        if axes.height() {
+                            AnimatedTransition::<AnimatedPxHeight>::start(
+                                &mut entt,
+                                target.y,
+                                Some(current.y),
+                                duration,
+                            );
+                        }
+                    });
+            }
+            _ => {}
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use bevy::{prelude::*, ui::experimental::GhostNode};
-use bevy_reactor_builder::UiBuilder;
+use bevy_reactor_builder::{CondBuilder, UiBuilder};
 use bevy_reactor_signals::Signal;
 
 /// Plugin that runs the timers for bistable transitions.
@@ -111,6 +111,56 @@ impl<'w> CreateBistableTransition for UiBuilder<'w> {
     }
 }
 
+/// Trait which adds conditional rendering with exit animations to [`UiBuilder`]. Unlike
+/// [`cond`](CondBuilder::cond), which despawns its children as soon as the condition becomes
+/// false, these keep the content mounted while the exit transition plays.
+pub trait AnimatedCondBuilder {
+    /// Render `content` while `open` is true, and for `duration` seconds afterwards, so that the
+    /// content can animate its exit. The content is passed the transition state, which it can
+    /// use to drive its enter and exit animations; it is despawned once the state reaches
+    /// [`BistableTransitionState::Exited`].
+    fn animated_cond<
+        F: Send + Sync + Fn(Signal<BistableTransitionState>, &mut UiBuilder) + 'static,
+    >(
+        &mut self,
+        open: Signal<bool>,
+        duration: f32,
+        content: F,
+    ) -> &mut Self;
+
+    /// Like [`animated_cond`](Self::animated_cond), but driven by an existing transition
+    /// created with [`create_bistable_transition`](CreateBistableTransition::create_bistable_transition).
+    fn presence<F: Send + Sync + Fn(Signal<BistableTransitionState>, &mut UiBuilder) + 'static>(
+        &mut self,
+        state: Signal<BistableTransitionState>,
+        content: F,
+    ) -> &mut Self;
+}
+
+impl<'w> AnimatedCondBuilder for UiBuilder<'w> {
+    fn animated_cond<
+        F: Send + Sync + Fn(Signal<BistableTransitionState>, &mut UiBuilder) + 'static,
+    >(
+        &mut self,
+        open: Signal<bool>,
+        duration: f32,
+        content: F,
+    ) -> &mut Self {
+        let state = self.create_bistable_transition(open, duration);
+        self.presence(state, content)
+    }
+
+    fn presence<F: Send + Sync + Fn(Signal<BistableTransitionState>, &mut UiBuilder) + 'static>(
+        &mut self,
+        state: Signal<BistableTransitionState>,
+        content: F,
+    ) -> &mut Self {
+        let is_mounted =
+            self.create_derived(move |rcx| state.get(rcx) != BistableTransitionState::Exited);
+        self.cond(is_mounted, move |builder| content(state, builder), |_| {})
+    }
+}
+
 pub fn enter_exit_state_machine(
     mut query: Query<(&mut BistableTransitionStateMachine, &mut TransitionTimer)>,
     time: Res<Time>,
@@ -6,8 +6,10 @@ use bevy::{
     ui::{self, BackgroundColor, BorderColor, Node},
 };
 
+mod animate_size;
 mod bistable_transition;
 
+pub use animate_size::{AnimateSize, SizeAxes};
 pub use bistable_transition::*;
 
 /// Trait that represents a property that can be animated, such as background color,
@@ -261,6 +263,10 @@ impl Plugin for AnimatedTransitionPlugin {
                 AnimatedTransition::<AnimatedRotation>::run_animations,
                 AnimatedTransition::<AnimatedTranslation>::run_animations,
             ),
+        )
+        .add_systems(
+            PostUpdate,
+            animate_size::animate_sizes.after(ui::UiSystem::Layout),
         );
     }
 }
@@ -0,0 +1,31 @@
+// This shader draws a background with a regular grid of dots, such as for a pan/zoom canvas.
+#import bevy_ui::ui_vertex_output::UiVertexOutput
+
+@group(1) @binding(0)
+var<uniform> color_bg: vec4<f32>;
+
+@group(1) @binding(1)
+var<uniform> color_fg: vec4<f32>;
+
+// Position of the grid origin, in logical pixels from the top-left of the node.
+@group(1) @binding(2)
+var<uniform> offset: vec2<f32>;
+
+// Distance between dots, in logical pixels.
+@group(1) @binding(3)
+var<uniform> spacing: f32;
+
+// Number of physical pixels per logical pixel.
+@group(1) @binding(4)
+var<uniform> scale: f32;
+
+@fragment
+fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
+    let pos = in.uv * in.size / max(scale, 0.001) - offset;
+    let cell = max(spacing, 1.0);
+    let nearest = pos - round(pos / cell) * cell;
+    // Dots are 1.5 logical pixels in radius, antialiased over one physical pixel.
+    let dist = (length(nearest) - 1.5) * scale;
+    let dot = smoothstep(0.5, -0.5, dist);
+    return mix(color_bg, color_fg, dot * color_fg.w);
+}
@@ -17,7 +17,10 @@ var<uniform> radius: vec4<f32>;
 fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
     let uv = in.uv - 0.5;
     let size = vec2<f32>(in.size.x, in.size.y);
-    let color = select(color_lo, color_hi, in.uv.x <= value.x);
+    // value.x is the end of the filled segment, value.y is the start, and value.z is non-zero
+    // for vertical sliders, which fill from the bottom.
+    let t = select(in.uv.x, 1.0 - in.uv.y, value.z > 0.5);
+    let color = select(color_lo, color_hi, t >= value.y && t <= value.x);
     let external_distance = sd_rounded_box((in.uv - 0.5) * size, size, vec4<f32>(radius));
     let alpha = smoothstep(0.5, -0.5, external_distance);
 
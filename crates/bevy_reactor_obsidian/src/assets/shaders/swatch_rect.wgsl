@@ -1,4 +1,5 @@
-// This shader draws a rounded rect with a given input color
+// This shader draws a rounded rect with a given input color. Transparent colors are drawn
+// over a checkerboard pattern.
 #import bevy_ui::ui_vertex_output::UiVertexOutput
 
 @group(1) @binding(0)
@@ -7,12 +8,26 @@ var<uniform> color: vec4<f32>;
 @group(1) @binding(1)
 var<uniform> radius: vec4<f32>;
 
+@group(1) @binding(2)
+var<uniform> checker_light: vec4<f32>;
+
+@group(1) @binding(3)
+var<uniform> checker_dark: vec4<f32>;
+
+@group(1) @binding(4)
+var<uniform> checker_size: f32;
+
 @fragment
 fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
-    let uv = (in.uv - vec2<f32>(0.5, 0.5)) * in.size / 8.;
-    let check = select(0.0, 1.0, (fract(uv.x) < 0.5) != (fract(uv.y) < 0.5));
-    let bg = mix(vec3<f32>(0.4, 0.4, 0.4), vec3<f32>(0.6, 0.6, 0.6), check);
-    let c = srgb_to_linear(mix(bg, color.rgb, color.w));
+    var c = color.rgb;
+    if (color.w < 1.0) {
+        // Each period of the pattern is two cells wide.
+        let uv = (in.uv - vec2<f32>(0.5, 0.5)) * in.size / (2.0 * max(checker_size, 1.0));
+        let check = select(0.0, 1.0, (fract(uv.x) < 0.5) != (fract(uv.y) < 0.5));
+        let bg = mix(checker_dark.rgb, checker_light.rgb, check);
+        c = mix(bg, color.rgb, color.w);
+    }
+    c = srgb_to_linear(c);
 
     let size = vec2<f32>(in.size.x, in.size.y);
     let external_distance = sd_rounded_box((in.uv - 0.5) * size, size, radius);
@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+
+/// Text clipboard shared by the controls. On desktop platforms, text is also written to and
+/// read from the system clipboard; elsewhere, the clipboard only works within the app.
+#[derive(Resource, Default)]
+pub struct Clipboard {
+    /// The most recently copied text, used when the system clipboard is unavailable.
+    text: String,
+}
+
+impl Clipboard {
+    /// Place text on the clipboard.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Err(err) =
+            arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(&self.text))
+        {
+            warn!("Unable to write to the system clipboard: {}", err);
+        }
+    }
+
+    /// Place an image, in 8-bit RGBA format, on the system clipboard. Returns false if the
+    /// image couldn't be copied; there is no in-app fallback for images.
+    pub fn set_image(&mut self, width: usize, height: usize, rgba: Vec<u8>) -> bool {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let image = arboard::ImageData {
+                width,
+                height,
+                bytes: rgba.into(),
+            };
+            match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_image(image)) {
+                Ok(()) => true,
+                Err(err) => {
+                    warn!("Unable to write image to the system clipboard: {}", err);
+                    false
+                }
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            let _ = (width, height, rgba);
+            warn!("Copying images to the clipboard is not supported on this platform");
+            false
+        }
+    }
+
+    /// Read the text on the clipboard.
+    pub fn text(&self) -> String {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Ok(text) = arboard::Clipboard::new().and_then(|mut clipboard| clipboard.get_text()) {
+            return text;
+        }
+        self.text.clone()
+    }
+}
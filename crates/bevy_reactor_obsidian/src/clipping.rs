@@ -0,0 +1,111 @@
+use bevy::{prelude::*, ui};
+use bevy_mod_stylebuilder::{StyleBuilder, StyleBuilderLayout};
+
+use crate::{materials::CornerMaskMaterial, rounded_corners::RoundedCorners};
+
+/// Component which clips the children of a UI node to its rounded corners. Bevy's overflow
+/// clipping is rectangular, so content scrolled into a corner would otherwise be drawn past
+/// the rounded edge. The corners are hidden by a mask drawn on top of the children, in the
+/// background color of the nearest ancestor which has one. The shape of the corners is taken
+/// from the node's [`BorderRadius`], so the clipping always matches the node's outline.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq)]
+pub struct RoundedClip;
+
+/// Marker for the mask entity created by [`RoundedClip`].
+#[derive(Component)]
+pub(crate) struct RoundedClipMask;
+
+#[allow(missing_docs)]
+pub trait StyleBuilderClip {
+    /// Clip the children of this node to its bounds, with rounded corners of the given radius.
+    fn clip_children(&mut self, radius: f32) -> &mut Self;
+}
+
+impl<'a, 'w> StyleBuilderClip for StyleBuilder<'a, 'w> {
+    fn clip_children(&mut self, radius: f32) -> &mut Self {
+        self.overflow(ui::OverflowAxis::Clip);
+        self.target
+            .insert((BorderRadius::all(Val::Px(radius)), RoundedClip));
+        self
+    }
+}
+
+/// Creates and updates the corner masks for nodes with a [`RoundedClip`].
+pub(crate) fn update_rounded_clips(
+    mut commands: Commands,
+    q_clips: Query<
+        (
+            Entity,
+            &ComputedNode,
+            Option<&BorderRadius>,
+            Option<&Children>,
+        ),
+        With<RoundedClip>,
+    >,
+    q_masks: Query<(Entity, &MaterialNode<CornerMaskMaterial>, &Parent), With<RoundedClipMask>>,
+    q_parents: Query<&Parent>,
+    q_backgrounds: Query<&BackgroundColor>,
+    mut r_materials: ResMut<Assets<CornerMaskMaterial>>,
+) {
+    for (entity, node, radius, children) in q_clips.iter() {
+        // The corners should blend into whatever is behind this node.
+        let color = q_parents
+            .iter_ancestors(entity)
+            .filter_map(|e| q_backgrounds.get(e).ok())
+            .find(|bg| bg.0.alpha() > 0.)
+            .map_or(Color::NONE, |bg| bg.0);
+        let color = LinearRgba::from(color).to_vec4();
+        let border_radius = radius.map_or(Vec4::ZERO, |radius| {
+            RoundedCorners::from(*radius).resolve(0., node.size() * node.inverse_scale_factor())
+        });
+
+        let mask = children.and_then(|children| {
+            children
+                .iter()
+                .find_map(|child| q_masks.get(*child).ok().map(|(_, mask, _)| mask))
+        });
+        match mask {
+            Some(mask) => {
+                let Some(material) = r_materials.get(mask.id()) else {
+                    continue;
+                };
+                if material.color != color || material.border_radius != border_radius {
+                    let material = r_materials.get_mut(mask.id()).unwrap();
+                    material.color = color;
+                    material.border_radius = border_radius;
+                }
+            }
+            None => {
+                let material = r_materials.add(CornerMaskMaterial {
+                    color,
+                    border_radius,
+                });
+                let mask = commands
+                    .spawn((
+                        MaterialNode(material),
+                        Node {
+                            position_type: PositionType::Absolute,
+                            left: Val::Px(0.),
+                            top: Val::Px(0.),
+                            right: Val::Px(0.),
+                            bottom: Val::Px(0.),
+                            ..default()
+                        },
+                        ZIndex(i32::MAX),
+                        PickingBehavior::IGNORE,
+                        RoundedClipMask,
+                        Name::new("RoundedClipMask"),
+                    ))
+                    .id();
+                commands.entity(entity).add_child(mask);
+            }
+        }
+    }
+
+    // Remove masks from nodes which are no longer clipped.
+    for (mask, _, parent) in q_masks.iter() {
+        if !q_clips.contains(parent.get()) {
+            commands.entity(mask).despawn_recursive();
+        }
+    }
+}
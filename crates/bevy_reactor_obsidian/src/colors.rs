@@ -27,3 +27,70 @@ pub const DESTRUCTIVE_ACC: Srgba = Srgba::new(0.733, 0.475, 0.612, 1.0);
 pub const TRANSPARENT: Srgba = Srgba::new(0.0, 0.0, 0.0, 0.0);
 pub const FOCUS: Srgba = Srgba::new(0.055, 0.647, 0.914, 0.15);
 pub const TEXT_SELECT: Srgba = Srgba::new(0.055, 0.647, 0.914, 0.5);
+
+/// WCAG contrast ratio required for body text.
+pub const CONTRAST_TEXT: f32 = 4.5;
+
+/// WCAG contrast ratio required for large text and for the boundaries of controls.
+pub const CONTRAST_LARGE: f32 = 3.0;
+
+/// Relative luminance of a color, as defined by WCAG. Alpha is ignored.
+pub fn relative_luminance(color: Srgba) -> f32 {
+    fn channel(c: f32) -> f32 {
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    }
+    0.2126 * channel(color.red) + 0.7152 * channel(color.green) + 0.0722 * channel(color.blue)
+}
+
+/// WCAG contrast ratio between two colors, from 1 (identical) to 21 (black on white). The
+/// order of the arguments doesn't matter.
+pub fn contrast_ratio(a: Srgba, b: Srgba) -> f32 {
+    let la = relative_luminance(a);
+    let lb = relative_luminance(b);
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32, tolerance: f32) {
+        assert!(
+            (actual - expected).abs() < tolerance,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_relative_luminance() {
+        assert_close(relative_luminance(Srgba::WHITE), 1., 1e-5);
+        assert_close(relative_luminance(Srgba::BLACK), 0., 1e-5);
+        assert_close(relative_luminance(Srgba::rgb(1., 0., 0.)), 0.2126, 1e-4);
+        assert_close(relative_luminance(Srgba::rgb(0., 1., 0.)), 0.7152, 1e-4);
+        assert_close(relative_luminance(Srgba::rgb(0., 0., 1.)), 0.0722, 1e-4);
+        // #777777, from the WCAG examples.
+        assert_close(
+            relative_luminance(Srgba::hex("777777").unwrap()),
+            0.1845,
+            1e-3,
+        );
+    }
+
+    #[test]
+    fn test_contrast_ratio() {
+        assert_close(contrast_ratio(Srgba::BLACK, Srgba::WHITE), 21., 1e-3);
+        assert_close(contrast_ratio(Srgba::WHITE, Srgba::BLACK), 21., 1e-3);
+        assert_close(contrast_ratio(Srgba::WHITE, Srgba::WHITE), 1., 1e-5);
+        // #777777 on white is just below the 4.5:1 text threshold.
+        let gray = Srgba::hex("777777").unwrap();
+        assert_close(contrast_ratio(gray, Srgba::WHITE), 4.48, 1e-2);
+        assert!(contrast_ratio(gray, Srgba::WHITE) < CONTRAST_TEXT);
+        assert!(contrast_ratio(gray, Srgba::WHITE) > CONTRAST_LARGE);
+    }
+}
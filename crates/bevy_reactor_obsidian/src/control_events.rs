@@ -0,0 +1,65 @@
+use bevy::{ecs::world::DeferredWorld, prelude::*};
+use bevy_reactor_signals::{Callback, RunCallback};
+
+/// Marker trait for semantic events emitted by controls. Control events bubble up the entity
+/// hierarchy, so a composite widget can listen for the events of all of its parts in one place
+/// using [`ControlEventObserver::on_control_event`], rather than passing a callback to each
+/// part. Custom widgets can define their own control events, which should use
+/// `&'static Parent` as their traversal and set `AUTO_PROPAGATE`.
+pub trait ControlEvent: Event + Clone {}
+
+/// Emitted when the user changes the value of a control, such as a slider or checkbox. Unlike
+/// the raw `ValueChange` emitted by `CoreSlider`, the value has already been rounded and
+/// clamped by the control.
+#[derive(Clone, Debug)]
+pub struct ValueChanged<T>(pub T);
+
+impl<T: Send + Sync + 'static> Event for ValueChanged<T> {
+    type Traversal = &'static Parent;
+
+    const AUTO_PROPAGATE: bool = true;
+}
+
+impl<T: Clone + Send + Sync + 'static> ControlEvent for ValueChanged<T> {}
+
+/// Emitted when the user activates a control, such as clicking a button.
+#[derive(Clone, Debug)]
+pub struct Activated;
+
+impl Event for Activated {
+    type Traversal = &'static Parent;
+
+    const AUTO_PROPAGATE: bool = true;
+}
+
+impl ControlEvent for Activated {}
+
+/// Emitted when the user dismisses a control, such as clicking outside of a dialog or
+/// pressing escape.
+#[derive(Clone, Debug)]
+pub struct Dismissed;
+
+impl Event for Dismissed {
+    type Traversal = &'static Parent;
+
+    const AUTO_PROPAGATE: bool = true;
+}
+
+impl ControlEvent for Dismissed {}
+
+#[allow(missing_docs)]
+pub trait ControlEventObserver {
+    /// Listen for control events of type `E` emitted by this entity or its descendants. The
+    /// event stops propagating once it has been handled.
+    fn on_control_event<E: ControlEvent>(&mut self, callback: Callback<E>) -> &mut Self;
+}
+
+impl<'w> ControlEventObserver for EntityWorldMut<'w> {
+    fn on_control_event<E: ControlEvent>(&mut self, callback: Callback<E>) -> &mut Self {
+        self.observe(move |mut trigger: Trigger<E>, mut world: DeferredWorld| {
+            trigger.propagate(false);
+            world.run_callback(callback, trigger.event().clone());
+        });
+        self
+    }
+}
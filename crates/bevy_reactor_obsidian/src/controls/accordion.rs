@@ -0,0 +1,221 @@
+use std::sync::Arc;
+
+use accesskit::{self, Role};
+use bevy::{
+    a11y::AccessibilityNode, ecs::world::DeferredWorld, prelude::*, ui, window::SystemCursorIcon,
+    winit::cursor::CursorIcon,
+};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{
+    CondBuilder, CreateChilden, EntityStyleBuilder, InvokeUiTemplate, TextBuilder, UiBuilder,
+    UiTemplate,
+};
+use bevy_reactor_signals::{Callback, RunCallback};
+
+use crate::{colors, cursor::StyleBuilderCursor, size::Size, typography};
+
+use super::{AnimatedSize, DisclosureToggle};
+
+fn style_accordion(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch);
+}
+
+fn style_section_header(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .min_height(24)
+        .padding((4, 2))
+        .gap(2)
+        .background_color(colors::U3)
+        .color(colors::FOREGROUND)
+        .cursor(CursorIcon::System(SystemCursorIcon::Pointer));
+}
+
+fn style_section_body(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch)
+        .padding(4);
+}
+
+/// Determines how many sections of an [`Accordion`] can be expanded at once.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AccordionPolicy {
+    /// Expanding a section collapses all other sections.
+    #[default]
+    Single,
+
+    /// Any number of sections can be expanded at the same time.
+    Multiple,
+}
+
+/// A single collapsible section within an [`Accordion`].
+#[derive(Clone)]
+struct AccordionSection {
+    title: String,
+    content: Arc<dyn Fn(&mut UiBuilder) + Send + Sync + 'static>,
+}
+
+/// A vertical stack of collapsible sections, each with a clickable header.
+#[derive(Default)]
+pub struct Accordion {
+    /// The sections of the accordion, in display order.
+    sections: Vec<AccordionSection>,
+
+    /// Whether one or several sections can be expanded at once.
+    pub policy: AccordionPolicy,
+
+    /// Indices of the sections which are initially expanded.
+    pub initially_expanded: Vec<usize>,
+
+    /// Size of the disclosure toggles.
+    pub size: Size,
+
+    /// Additional styles to be applied to the accordion.
+    pub style: StyleHandle,
+
+    /// Callback called with the indices of the expanded sections whenever they change.
+    pub on_change: Option<Callback<Vec<usize>>>,
+}
+
+impl Accordion {
+    /// Construct a new `Accordion`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a section with the given title and content.
+    pub fn section<V: 'static + Send + Sync + Fn(&mut UiBuilder)>(
+        mut self,
+        title: impl Into<String>,
+        content: V,
+    ) -> Self {
+        self.sections.push(AccordionSection {
+            title: title.into(),
+            content: Arc::new(content),
+        });
+        self
+    }
+
+    /// Set whether one or several sections can be expanded at once.
+    pub fn policy(mut self, policy: AccordionPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Set which sections are initially expanded.
+    pub fn initially_expanded(mut self, expanded: impl Into<Vec<usize>>) -> Self {
+        self.initially_expanded = expanded.into();
+        self
+    }
+
+    /// Set the size of the disclosure toggles.
+    pub fn size(mut self, size: Size) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the additional styles for the accordion.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+
+    /// Set the callback called when the set of expanded sections changes.
+    pub fn on_change(mut self, callback: Callback<Vec<usize>>) -> Self {
+        self.on_change = Some(callback);
+        self
+    }
+}
+
+impl UiTemplate for Accordion {
+    fn build(&self, builder: &mut UiBuilder) {
+        let policy = self.policy;
+        let on_change = self.on_change;
+        let size = self.size;
+        let mut initial = self.initially_expanded.clone();
+        if policy == AccordionPolicy::Single {
+            initial.truncate(1);
+        }
+        let expanded = builder.create_mutable::<Vec<usize>>(initial);
+
+        builder
+            .spawn((Node::default(), Name::new("Accordion")))
+            .styles((
+                typography::text_default,
+                style_accordion,
+                self.style.clone(),
+            ))
+            .insert(AccessibilityNode::from(accesskit::Node::new(Role::Group)))
+            .create_children(|builder| {
+                for (index, section) in self.sections.iter().enumerate() {
+                    let is_expanded = builder.create_derived(move |rcx| {
+                        expanded.signal().map(rcx, |e| e.contains(&index))
+                    });
+                    let on_toggle = builder.create_callback(
+                        move |value: In<bool>, mut world: DeferredWorld| {
+                            let mut sections = expanded.get_clone(&mut world);
+                            match (policy, *value) {
+                                (AccordionPolicy::Single, true) => {
+                                    sections.clear();
+                                    sections.push(index);
+                                }
+                                (AccordionPolicy::Multiple, true) => {
+                                    if !sections.contains(&index) {
+                                        sections.push(index);
+                                        sections.sort_unstable();
+                                    }
+                                }
+                                (_, false) => sections.retain(|i| *i != index),
+                            }
+                            expanded.set_clone(&mut world, sections.clone());
+                            if let Some(on_change) = on_change {
+                                world.run_callback(on_change, sections);
+                            }
+                        },
+                    );
+
+                    builder
+                        .spawn((Node::default(), Name::new("Accordion::Header")))
+                        .style(style_section_header)
+                        .observe(
+                            move |mut trigger: Trigger<Pointer<Click>>,
+                                  mut world: DeferredWorld| {
+                                trigger.propagate(false);
+                                let value = is_expanded.get(&world);
+                                world.run_callback(on_toggle, !value);
+                            },
+                        )
+                        .create_children(|builder| {
+                            builder.invoke(
+                                DisclosureToggle::new()
+                                    .size(size)
+                                    .expanded(is_expanded)
+                                    .on_change(on_toggle),
+                            );
+                            builder.text(section.title.clone());
+                        });
+
+                    let content = section.content.clone();
+                    builder.invoke(AnimatedSize::new().children(move |builder| {
+                        let content = content.clone();
+                        builder.cond(
+                            is_expanded,
+                            move |builder| {
+                                builder
+                                    .spawn((Node::default(), Name::new("Accordion::Body")))
+                                    .style(style_section_body)
+                                    .create_children(|builder| {
+                                        (content.as_ref())(builder);
+                                    });
+                            },
+                            |_| {},
+                        );
+                    }));
+                }
+            });
+    }
+}
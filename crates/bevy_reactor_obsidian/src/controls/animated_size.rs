@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use bevy::{prelude::*, ui};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{CreateChilden, EntityStyleBuilder, UiBuilder, UiTemplate};
+
+use crate::animation::{AnimateSize, SizeAxes};
+
+fn style_animated_size(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch)
+        .overflow(ui::OverflowAxis::Clip);
+}
+
+fn style_animated_size_content(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch)
+        .flex_shrink(0.);
+}
+
+/// A container which animates its size to follow the size of its children, rather than
+/// jumping, for example when a [`cond`](bevy_reactor_builder::CondBuilder) expands or collapses
+/// its content. The children are clipped while the transition is running.
+pub struct AnimatedSize {
+    /// Which dimensions to animate.
+    pub axes: SizeAxes,
+
+    /// Duration of the transition, in seconds.
+    pub duration: f32,
+
+    /// Additional styles to be applied to the container.
+    pub style: StyleHandle,
+
+    /// The content of the container.
+    pub children: Arc<dyn Fn(&mut UiBuilder) + Send + Sync>,
+}
+
+impl Default for AnimatedSize {
+    fn default() -> Self {
+        Self {
+            axes: SizeAxes::Height,
+            duration: 0.2,
+            style: StyleHandle::default(),
+            children: Arc::new(|_builder| {}),
+        }
+    }
+}
+
+impl AnimatedSize {
+    /// Construct a new `AnimatedSize` container.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set which dimensions to animate.
+    pub fn axes(mut self, axes: SizeAxes) -> Self {
+        self.axes = axes;
+        self
+    }
+
+    /// Set the duration of the transition, in seconds.
+    pub fn duration(mut self, duration: f32) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Set additional styles to be applied to the container.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+
+    /// Set the content of the container.
+    pub fn children<V: 'static + Send + Sync + Fn(&mut UiBuilder)>(mut self, children: V) -> Self {
+        self.children = Arc::new(children);
+        self
+    }
+}
+
+impl UiTemplate for AnimatedSize {
+    fn build(&self, builder: &mut UiBuilder) {
+        let axes = self.axes;
+        builder
+            .spawn((
+                Node::default(),
+                Name::new("AnimatedSize"),
+                AnimateSize::new(self.axes, self.duration),
+            ))
+            .styles((style_animated_size, self.style.clone()))
+            .create_children(|builder| {
+                builder
+                    .spawn((Node::default(), Name::new("AnimatedSize::Content")))
+                    .styles((style_animated_size_content, move |ss: &mut StyleBuilder| {
+                        // When animating the width, the content must not be stretched to the
+                        // width of the container, or it could never grow.
+                        if axes != SizeAxes::Height {
+                            ss.align_self(ui::AlignSelf::FlexStart);
+                        }
+                    }))
+                    .create_children(|builder| {
+                        (self.children.as_ref())(builder);
+                    });
+            });
+    }
+}
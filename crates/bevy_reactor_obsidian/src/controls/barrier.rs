@@ -1,4 +1,7 @@
-use crate::input_dispatch::{FocusKeyboardInput, SetKeyboardFocus};
+use crate::{
+    control_events::Dismissed,
+    input_dispatch::{DispatchControl, FocusKeyboardInput, SetKeyboardFocus},
+};
 use bevy::{ecs::world::DeferredWorld, input::ButtonState, prelude::*};
 use bevy_reactor_signals::{Callback, RunCallback};
 
@@ -14,12 +17,18 @@ pub(crate) fn barrier_on_key_input(
     q_state: Query<&Barrier>,
     mut world: DeferredWorld,
 ) {
+    if trigger.is_default_prevented() {
+        return;
+    }
     if let Ok(bstate) = q_state.get(trigger.entity()) {
-        let event = &trigger.event().0;
+        let event = &trigger.event().input;
         if event.state == ButtonState::Pressed
             && !event.repeat
             && (event.key_code == KeyCode::Escape)
         {
+            world
+                .commands()
+                .trigger_targets(Dismissed, trigger.entity());
             if let Some(on_close) = bstate.on_close {
                 trigger.propagate(false);
                 world.run_callback(on_close, ());
@@ -37,6 +46,7 @@ pub(crate) fn barrier_on_pointer_down(
         let checkbox_id = trigger.entity();
         world.set_keyboard_focus(checkbox_id);
         trigger.propagate(false);
+        world.commands().trigger_targets(Dismissed, checkbox_id);
         if let Some(on_close) = bstate.on_close {
             world.run_callback(on_close, ());
         }
@@ -1,14 +1,16 @@
-use std::sync::Arc;
+use std::{future::Future, pin::Pin, sync::Arc};
 
 use crate::{
     colors,
+    control_events::Activated,
     cursor::StyleBuilderCursor,
     focus_signal::CreateFocusSignal,
     hover_signal::CreateHoverSignal,
     input_dispatch::{FocusKeyboardInput, KeyboardFocus, KeyboardFocusVisible},
     prelude::RoundedCorners,
-    size::Size,
+    size::{Size, UiSizing},
     tab_navigation::{AutoFocus, TabIndex},
+    theme::{Theme, UseTheme},
     typography,
 };
 
@@ -17,18 +19,27 @@ use accesskit::{self, Role};
 use bevy::{
     a11y::AccessibilityNode,
     color::Luminance,
+    ecs::world::DeferredWorld,
     prelude::*,
+    tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task},
     ui,
     window::SystemCursorIcon,
     winit::cursor::CursorIcon,
 };
 use bevy_mod_stylebuilder::*;
 use bevy_reactor_builder::{
-    CreateChilden, EntityStyleBuilder, InsertComponentBuilder, TextBuilder, UiBuilder, UiTemplate,
+    CondBuilder, CreateChilden, EntityStyleBuilder, InsertComponentBuilder, InvokeUiTemplate,
+    TextBuilder, UiBuilder, UiTemplate,
 };
-use bevy_reactor_signals::{Callback, IntoSignal, RunCallback, Signal};
+use bevy_reactor_signals::{Callback, IntoSignal, Mutable, Rcx, RunCallback, Signal};
 
-use super::{Disabled, IsDisabled};
+use super::{
+    button_group::{ButtonGroupMember, ButtonGroupState},
+    Disabled, IsDisabled, Spinner,
+};
+
+/// An asynchronous action which is run when a button is clicked.
+pub type AsyncAction = Arc<dyn Fn() -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>;
 
 /// The variant determines the button's color scheme
 #[derive(Clone, Copy, PartialEq, Default, Debug)]
@@ -77,6 +88,18 @@ pub(crate) struct ButtonState {
     on_click: Option<Callback>,
 }
 
+/// Marker for the background node of a button.
+#[derive(Component)]
+pub(crate) struct ButtonBackground;
+
+/// Component which holds a running task spawned by [`Button::on_click_async`]. The entity is
+/// a child of the button, and dropping it cancels the task.
+#[derive(Component)]
+pub(crate) struct ButtonAsyncTask {
+    task: Task<()>,
+    loading: Mutable<bool>,
+}
+
 /// Button widget
 pub struct Button {
     /// Color variant - default, primary or danger.
@@ -88,6 +111,10 @@ pub struct Button {
     /// Whether the button is disabled.
     pub disabled: Signal<bool>,
 
+    /// Whether the button is in a loading state. A loading button is not interactive, and
+    /// displays a spinner next to its content.
+    pub loading: Signal<bool>,
+
     /// The content to display inside the button.
     pub children: Arc<dyn Fn(&mut UiBuilder)>,
 
@@ -97,6 +124,9 @@ pub struct Button {
     /// Callback called when clicked
     pub on_click: Option<Callback>,
 
+    /// Asynchronous action run when clicked. Takes precedence over `on_click`.
+    pub on_click_async: Option<AsyncAction>,
+
     /// The tab index of the button (default 0).
     pub tab_index: i32,
 
@@ -152,6 +182,12 @@ impl Button {
         self
     }
 
+    /// Set the button loading state.
+    pub fn loading(mut self, loading: impl IntoSignal<bool>) -> Self {
+        self.loading = loading.into_signal();
+        self
+    }
+
     /// Set the child views for this element.
     pub fn children<V: 'static + Fn(&mut UiBuilder)>(mut self, children: V) -> Self {
         self.children = Arc::new(children);
@@ -180,6 +216,17 @@ impl Button {
         self
     }
 
+    /// Set an asynchronous action to run when clicked. The action is spawned as a task, and
+    /// the button is put into the loading state until the returned future resolves.
+    pub fn on_click_async<F, Fut>(mut self, action: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.on_click_async = Some(Arc::new(move || Box::pin(action())));
+        self
+    }
+
     /// Set the tab index of the button.
     pub fn tab_index(mut self, tab_index: i32) -> Self {
         self.tab_index = tab_index;
@@ -205,9 +252,11 @@ impl Default for Button {
             variant: Signal::default(),
             size: Size::default(),
             disabled: default(),
+            loading: default(),
             children: Arc::new(|_builder| {}),
             style: StyleHandle::none(),
             on_click: None,
+            on_click_async: None,
             tab_index: 0,
             corners: RoundedCorners::default(),
             autofocus: false,
@@ -224,31 +273,61 @@ impl UiTemplate for Button {
         let minimal = self.minimal;
 
         let size = self.size;
-        let on_click = self.on_click;
+        let disabled = self.disabled;
+
+        // Loading state is either set explicitly, or driven by a running async action.
+        let task_loading = builder.create_mutable(false);
+        let loading = self.loading;
+        let loading = builder.create_derived(move |rcx| loading.get(rcx) || task_loading.get(rcx));
 
         let button = builder.spawn((Node::default(), Name::new("Button")));
         let button_id = button.id();
+
+        let on_click = match self.on_click_async.clone() {
+            Some(action) => Some(builder.create_callback(
+                move |_: In<()>, mut world: DeferredWorld| {
+                    task_loading.set(&mut world, true);
+                    let task = AsyncComputeTaskPool::get().spawn(action());
+                    world
+                        .commands()
+                        .spawn((
+                            ButtonAsyncTask {
+                                task,
+                                loading: task_loading,
+                            },
+                            Name::new("Button::AsyncTask"),
+                        ))
+                        .set_parent(button_id);
+                },
+            )),
+            None => self.on_click,
+        };
+
         let hovering = builder.create_hover_signal(button_id);
         let focused = builder.create_focus_visible_signal(button_id);
         let mut button = builder.world_mut().entity_mut(button_id);
 
         button
-            .styles((
-                typography::text_default,
-                style_button,
-                move |ss: &mut StyleBuilder| {
-                    ss.min_height(size.height())
-                        .min_width(size.height().floor())
-                        .font_size(size.font_size());
+            .styles((typography::text_default, style_button))
+            .style_dyn(
+                |rcx| *rcx.read_resource::<UiSizing>(),
+                move |sizing, ss| {
+                    let height = sizing.height(size);
+                    ss.min_height(height)
+                        .min_width(height.floor())
+                        .font_size(sizing.font_size(size));
                     if minimal {
                         ss.padding(0);
                     } else {
-                        ss.padding((size.font_size() * 0.75, 0));
+                        ss.padding((sizing.font_size(size) * 0.75, 0));
                     }
                 },
-                self.style.clone(),
-            ))
-            .insert_if(self.disabled, || Disabled)
+            )
+            .styles(self.style.clone())
+            .insert_if(
+                move |rcx: &Rcx| disabled.get(rcx) || loading.get(rcx),
+                || Disabled,
+            )
             .insert((
                 TabIndex(self.tab_index),
                 ButtonPressed(false),
@@ -258,9 +337,18 @@ impl UiTemplate for Button {
             .insert_if(self.autofocus, || AutoFocus)
             .create_children(|builder| {
                 builder
-                    .spawn((Node::default(), Name::new("Button::Background")))
+                    .spawn((
+                        Node::default(),
+                        Name::new("Button::Background"),
+                        ButtonBackground,
+                    ))
                     .style(style_button_bg)
-                    .insert(corners.to_border_radius(self.size.border_radius()))
+                    .style_dyn(
+                        |rcx| *rcx.read_resource::<UiSizing>(),
+                        move |sizing, sb| {
+                            sb.border_radius(corners.to_border_radius(sizing.border_radius(size)));
+                        },
+                    )
                     .style_dyn(
                         move |rcx| {
                             if minimal {
@@ -269,8 +357,16 @@ impl UiTemplate for Button {
                                 let pressed =
                                     rcx.read_component::<ButtonPressed>(button_id).unwrap();
                                 let disabled = rcx.is_disabled(button_id);
+                                let group_selected = rcx
+                                    .read_component::<ButtonGroupMember>(button_id)
+                                    .map_or(false, |member| member.selected);
                                 button_bg_color(
-                                    variant.get(rcx),
+                                    &rcx.use_theme(),
+                                    if group_selected {
+                                        ButtonVariant::Selected
+                                    } else {
+                                        variant.get(rcx)
+                                    },
                                     disabled,
                                     pressed.0,
                                     hovering.get(rcx),
@@ -282,10 +378,10 @@ impl UiTemplate for Button {
                         },
                     )
                     .style_dyn(
-                        move |rcx| focused.get(rcx),
-                        |is_focused, sb| {
-                            if is_focused {
-                                sb.outline_color(colors::FOCUS)
+                        move |rcx| focused.get(rcx).then(|| rcx.use_theme().focus),
+                        |focus, sb| {
+                            if let Some(focus) = focus {
+                                sb.outline_color(focus)
                                     .outline_width(2)
                                     .outline_offset(2);
                             } else {
@@ -295,6 +391,22 @@ impl UiTemplate for Button {
                             }
                         },
                     );
+                builder.cond(
+                    loading,
+                    move |builder| {
+                        builder.computed(
+                            |rcx| *rcx.read_resource::<UiSizing>(),
+                            move |sizing, builder| {
+                                builder.invoke(Spinner::new().size(sizing.font_size(size)).style(
+                                    |sb: &mut StyleBuilder| {
+                                        sb.margin_right(6);
+                                    },
+                                ));
+                            },
+                        );
+                    },
+                    |_| {},
+                );
                 let children = self.children.as_ref();
                 (children)(builder);
             });
@@ -302,16 +414,17 @@ impl UiTemplate for Button {
 }
 
 pub(crate) fn button_bg_color(
+    theme: &Theme,
     variant: ButtonVariant,
     is_disabled: bool,
     is_pressed: bool,
     is_hovering: bool,
 ) -> Srgba {
     let base_color = match variant {
-        ButtonVariant::Default => colors::U3,
-        ButtonVariant::Primary => colors::PRIMARY,
-        ButtonVariant::Danger => colors::DESTRUCTIVE,
-        ButtonVariant::Selected => colors::U4,
+        ButtonVariant::Default => theme.control,
+        ButtonVariant::Primary => theme.primary,
+        ButtonVariant::Danger => theme.destructive,
+        ButtonVariant::Selected => theme.control_selected,
     };
     // println!("Disabled: {}", is_disabled);
     match (is_disabled, is_pressed, is_hovering) {
@@ -324,36 +437,62 @@ pub(crate) fn button_bg_color(
 
 pub(crate) fn button_on_key_event(
     mut trigger: Trigger<FocusKeyboardInput>,
-    q_state: Query<(&ButtonState, Has<Disabled>)>,
+    q_state: Query<(&ButtonState, Option<&ButtonGroupMember>, Has<Disabled>)>,
+    q_groups: Query<&ButtonGroupState>,
     mut commands: Commands,
 ) {
-    if let Ok((bstate, disabled)) = q_state.get(trigger.entity()) {
+    if let Ok((bstate, member, disabled)) = q_state.get(trigger.entity()) {
         if !disabled {
-            let event = &trigger.event().0;
+            let event = &trigger.event().input;
             if !event.repeat
                 && (event.key_code == KeyCode::Enter || event.key_code == KeyCode::Space)
             {
+                commands.trigger_targets(Activated, trigger.entity());
                 if let Some(on_click) = bstate.on_click {
                     trigger.propagate(false);
                     commands.run_callback(on_click, ());
                 }
+                if let Some(on_change) = group_on_change(member, &q_groups) {
+                    trigger.propagate(false);
+                    commands.run_callback(on_change, member.unwrap().index);
+                }
             }
         }
     }
 }
 
+/// Returns the `on_change` callback of the button group that the button belongs to, if any.
+fn group_on_change(
+    member: Option<&ButtonGroupMember>,
+    q_groups: &Query<&ButtonGroupState>,
+) -> Option<Callback<usize>> {
+    member
+        .and_then(|member| q_groups.get(member.group).ok())
+        .and_then(|group| group.on_change)
+}
+
 pub(crate) fn button_on_pointer_click(
     mut trigger: Trigger<Pointer<Click>>,
-    mut q_state: Query<(&ButtonState, &mut ButtonPressed, Has<Disabled>)>,
+    mut q_state: Query<(
+        &ButtonState,
+        &mut ButtonPressed,
+        Option<&ButtonGroupMember>,
+        Has<Disabled>,
+    )>,
+    q_groups: Query<&ButtonGroupState>,
     mut commands: Commands,
 ) {
-    if let Ok((bstate, pressed, disabled)) = q_state.get_mut(trigger.entity()) {
+    if let Ok((bstate, pressed, member, disabled)) = q_state.get_mut(trigger.entity()) {
         trigger.propagate(false);
         if pressed.0 && !disabled {
             // println!("Click: {}", pressed.0);
+            commands.trigger_targets(Activated, trigger.entity());
             if let Some(on_click) = bstate.on_click {
                 commands.run_callback(on_click, ());
             }
+            if let Some(on_change) = group_on_change(member, &q_groups) {
+                commands.run_callback(on_change, member.unwrap().index);
+            }
         }
     }
 }
@@ -409,3 +548,90 @@ pub(crate) fn button_on_pointer_cancel(
         }
     }
 }
+
+/// Poll the tasks spawned by async button actions, and clear the loading state when they
+/// complete.
+pub(crate) fn poll_button_tasks(world: &mut World) {
+    let mut finished: Vec<(Entity, Mutable<bool>)> = Vec::new();
+    let mut q_tasks = world.query::<(Entity, &mut ButtonAsyncTask)>();
+    for (entity, mut task) in q_tasks.iter_mut(world) {
+        if block_on(future::poll_once(&mut task.task)).is_some() {
+            finished.push((entity, task.loading));
+        }
+    }
+    for (entity, loading) in finished {
+        world.entity_mut(entity).despawn_recursive();
+        if world.get_entity(loading.id()).is_ok() {
+            loading.set(world, false);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use bevy::tasks::TaskPool;
+    use bevy_reactor_signals::{CreateMutable, SignalsPlugin};
+
+    use super::*;
+
+    fn spawn_task(world: &mut World, button: Entity, task: Task<()>) -> Mutable<bool> {
+        let loading = world.create_mutable(true);
+        world
+            .spawn(ButtonAsyncTask { task, loading })
+            .set_parent(button);
+        loading
+    }
+
+    fn task_count(world: &mut World) -> usize {
+        world.query::<&ButtonAsyncTask>().iter(world).count()
+    }
+
+    #[test]
+    fn test_loading_cleared_when_task_completes() {
+        let mut app = App::new();
+        app.add_plugins(SignalsPlugin);
+        let world = app.world_mut();
+        let pool = AsyncComputeTaskPool::get_or_init(TaskPool::default);
+        let button = world.spawn(Node::default()).id();
+
+        let done = Arc::new(AtomicBool::new(false));
+        let task_done = done.clone();
+        let loading = spawn_task(
+            world,
+            button,
+            pool.spawn(async move {
+                while !task_done.load(Ordering::Acquire) {
+                    future::yield_now().await;
+                }
+            }),
+        );
+        poll_button_tasks(world);
+        assert!(loading.get(world));
+
+        done.store(true, Ordering::Release);
+        for _ in 0..100 {
+            poll_button_tasks(world);
+            if !loading.get(world) {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert!(!loading.get(world));
+        assert_eq!(task_count(world), 0);
+    }
+
+    #[test]
+    fn test_task_dropped_with_button() {
+        let mut app = App::new();
+        app.add_plugins(SignalsPlugin);
+        let world = app.world_mut();
+        let pool = AsyncComputeTaskPool::get_or_init(TaskPool::default);
+        let button = world.spawn(Node::default()).id();
+        spawn_task(world, button, pool.spawn(future::pending()));
+
+        world.entity_mut(button).despawn_recursive();
+        assert_eq!(task_count(world), 0);
+    }
+}
@@ -0,0 +1,224 @@
+use std::sync::Arc;
+
+use accesskit::{self, Role};
+use bevy::{a11y::AccessibilityNode, prelude::*, ui, ui::experimental::UiChildren};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{
+    CreateChilden, EntityEffectBuilder, EntityStyleBuilder, UiBuilder, UiTemplate,
+};
+use bevy_reactor_signals::{Callback, IntoSignal, Signal};
+
+use crate::{
+    prelude::RoundedCorners,
+    size::{Size, UiSizing},
+};
+
+use super::button::{ButtonBackground, ButtonState};
+
+/// State of a button group, used to assign corners and selection to its member buttons.
+#[derive(Component, Clone)]
+pub(crate) struct ButtonGroupState {
+    /// Number of buttons per row, or `None` if all buttons are in a single row.
+    pub(crate) columns: Option<usize>,
+    /// Button size, used to determine the corner radius.
+    pub(crate) size: Size,
+    pub(crate) selected: Option<usize>,
+    pub(crate) on_change: Option<Callback<usize>>,
+}
+
+/// Component which is added to buttons that are members of a [`ButtonGroup`].
+#[derive(Component, Clone, Copy, PartialEq, Debug)]
+pub(crate) struct ButtonGroupMember {
+    pub(crate) group: Entity,
+    pub(crate) index: usize,
+    pub(crate) selected: bool,
+}
+
+/// A row (or column) of buttons which are visually joined together. The corners of the
+/// first and last buttons are rounded, and the buttons in between are square. Optionally,
+/// the group can manage an exclusive selection among its buttons.
+pub struct ButtonGroup {
+    /// Button size, used to determine the corner radius.
+    pub size: Size,
+
+    /// If true, lay out the buttons in a column instead of a row.
+    pub vertical: bool,
+
+    /// Index of the selected button, if the group manages selection.
+    pub selected: Signal<Option<usize>>,
+
+    /// Callback called with the index of a button when it is clicked.
+    pub on_change: Option<Callback<usize>>,
+
+    /// The buttons to display.
+    pub children: Arc<dyn Fn(&mut UiBuilder)>,
+
+    /// Additional styles to be applied to the group.
+    pub style: StyleHandle,
+}
+
+impl Default for ButtonGroup {
+    fn default() -> Self {
+        Self {
+            size: Size::default(),
+            vertical: false,
+            selected: Signal::Constant(None),
+            on_change: None,
+            children: Arc::new(|_builder| {}),
+            style: StyleHandle::default(),
+        }
+    }
+}
+
+impl ButtonGroup {
+    /// Create a new button group.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the button size.
+    pub fn size(mut self, size: Size) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set whether to lay out the buttons vertically.
+    pub fn vertical(mut self, vertical: bool) -> Self {
+        self.vertical = vertical;
+        self
+    }
+
+    /// Set the index of the selected button. Setting this enables exclusive selection.
+    pub fn selected(mut self, selected: impl IntoSignal<Option<usize>>) -> Self {
+        self.selected = selected.into_signal();
+        self
+    }
+
+    /// Set the callback called when a button in the group is clicked.
+    pub fn on_change(mut self, callback: Callback<usize>) -> Self {
+        self.on_change = Some(callback);
+        self
+    }
+
+    /// Set the child views for this element.
+    pub fn children<V: 'static + Fn(&mut UiBuilder)>(mut self, children: V) -> Self {
+        self.children = Arc::new(children);
+        self
+    }
+
+    /// Set additional styles to be applied to the group.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl UiTemplate for ButtonGroup {
+    fn build(&self, builder: &mut UiBuilder) {
+        let vertical = self.vertical;
+        let selected = self.selected;
+
+        builder
+            .spawn((Node::default(), Name::new("ButtonGroup")))
+            .styles((
+                move |ss: &mut StyleBuilder| {
+                    ss.display(ui::Display::Flex)
+                        .flex_direction(if vertical {
+                            ui::FlexDirection::Column
+                        } else {
+                            ui::FlexDirection::Row
+                        })
+                        .align_items(ui::AlignItems::Stretch)
+                        .gap(1);
+                },
+                self.style.clone(),
+            ))
+            .insert((
+                ButtonGroupState {
+                    columns: if vertical { Some(1) } else { None },
+                    size: self.size,
+                    selected: None,
+                    on_change: self.on_change,
+                },
+                AccessibilityNode::from(accesskit::Node::new(Role::Group)),
+            ))
+            .component_effect::<ButtonGroupState, _, _, _>(
+                move |rcx| selected.get(rcx),
+                |selected, mut state| {
+                    state.selected = selected;
+                },
+            )
+            .create_children(|builder| {
+                (self.children.as_ref())(builder);
+            });
+    }
+}
+
+/// Assign corners and selection state to the buttons within each button group.
+pub(crate) fn update_button_groups(
+    q_groups: Query<(Entity, &ButtonGroupState)>,
+    q_buttons: Query<(Option<&ButtonGroupMember>, &Children), With<ButtonState>>,
+    mut q_backgrounds: Query<&mut BorderRadius, With<ButtonBackground>>,
+    ui_children: UiChildren,
+    sizing: Res<UiSizing>,
+    mut commands: Commands,
+) {
+    for (group_id, group) in q_groups.iter() {
+        let buttons: Vec<Entity> = ui_children
+            .iter_ui_children(group_id)
+            .filter(|child| q_buttons.contains(*child))
+            .collect();
+        let count = buttons.len();
+        let columns = group.columns.unwrap_or(count).max(1);
+        for (index, button_id) in buttons.iter().enumerate() {
+            let radius = group_corners(index, count, columns)
+                .to_border_radius(sizing.border_radius(group.size));
+
+            let Ok((member, children)) = q_buttons.get(*button_id) else {
+                continue;
+            };
+            for child in children.iter() {
+                if let Ok(mut border_radius) = q_backgrounds.get_mut(*child) {
+                    if *border_radius != radius {
+                        *border_radius = radius;
+                    }
+                }
+            }
+
+            let next = ButtonGroupMember {
+                group: group_id,
+                index,
+                selected: group.selected == Some(index),
+            };
+            if member != Some(&next) {
+                commands.entity(*button_id).insert(next);
+            }
+        }
+    }
+}
+
+/// Determine which corners of a button should be rounded, given its position within a grid
+/// of `count` buttons laid out in rows of `columns`. Only the corners on the outside of the
+/// group are rounded.
+fn group_corners(index: usize, count: usize, columns: usize) -> RoundedCorners {
+    let last = count - 1;
+    let row = index / columns;
+    let col = index % columns;
+    let top = row == 0;
+    // A button is on the bottom edge if there is no button directly below it.
+    let bottom = index + columns > last;
+    let left = col == 0;
+    let right = col == columns - 1 || index == last;
+    match (top, bottom, left, right) {
+        (true, true, true, true) => RoundedCorners::All,
+        (true, true, true, false) => RoundedCorners::Left,
+        (true, true, false, true) => RoundedCorners::Right,
+        (true, false, true, true) => RoundedCorners::Top,
+        (false, true, true, true) => RoundedCorners::Bottom,
+        (true, _, true, _) => RoundedCorners::TopLeft,
+        (true, _, _, true) => RoundedCorners::TopRight,
+        (_, true, true, _) => RoundedCorners::BottomLeft,
+        (_, true, _, true) => RoundedCorners::BottomRight,
+        _ => RoundedCorners::None,
+    }
+}
@@ -0,0 +1,388 @@
+use bevy::{prelude::*, ui};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{
+    CreateChilden, EntityStyleBuilder, ForIndexBuilder, TextBuilder, UiBuilder, UiTemplate,
+};
+use bevy_reactor_signals::{IntoSignal, Signal};
+
+use crate::{
+    colors,
+    materials::{update_ui_material, CreateUiMaterial, DrawPathMaterial, DrawablePath},
+};
+
+fn style_chart(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Stretch);
+}
+
+fn style_sparkline(ss: &mut StyleBuilder) {
+    ss.width(64).height(16);
+}
+
+fn style_bar_chart(ss: &mut StyleBuilder) {
+    ss.width(120).height(48);
+}
+
+fn style_plot(ss: &mut StyleBuilder) {
+    ss.flex_grow(1.);
+}
+
+fn style_plot_axes(ss: &mut StyleBuilder) {
+    ss.border_left(1).border_bottom(1).border_color(colors::U3);
+}
+
+fn style_axis_labels(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .justify_content(ui::JustifyContent::SpaceBetween)
+        .align_items(ui::AlignItems::FlexEnd)
+        .padding_right(4)
+        .font_size(10)
+        .color(colors::DIM);
+}
+
+fn style_bars(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::FlexEnd);
+}
+
+fn style_bar(ss: &mut StyleBuilder) {
+    ss.flex_grow(1.).flex_basis(0);
+}
+
+/// Returns the range of the values, or the fixed range if there is one. The range is never
+/// empty, so that a flat series is drawn through the middle of the chart.
+fn value_range(values: &[f32], fixed: Option<(f32, f32)>) -> (f32, f32) {
+    let (lo, hi) = fixed.unwrap_or_else(|| {
+        values
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), v| {
+                (lo.min(*v), hi.max(*v))
+            })
+    });
+    if !lo.is_finite() || !hi.is_finite() {
+        (0., 1.)
+    } else if hi <= lo {
+        (lo - 1., lo + 1.)
+    } else {
+        (lo, hi)
+    }
+}
+
+/// Displays the minimum and maximum of the range to the left of the plot.
+fn build_axis_labels(builder: &mut UiBuilder, range: Signal<(f32, f32)>) {
+    builder
+        .spawn((Node::default(), Name::new("ChartAxis")))
+        .style(style_axis_labels)
+        .create_children(|builder| {
+            builder.text_computed(move |rcx| format!("{:.1}", range.get(rcx).1));
+            builder.text_computed(move |rcx| format!("{:.1}", range.get(rcx).0));
+        });
+}
+
+/// A tiny line chart, typically fed by a ring buffer of recent samples, such as frame times.
+pub struct Sparkline {
+    /// The values to plot, from oldest to newest.
+    pub values: Signal<Vec<f32>>,
+
+    /// The range of values spanned by the chart. If not set, the chart is scaled to fit the
+    /// values.
+    pub range: Option<(f32, f32)>,
+
+    /// Color of the line.
+    pub color: Srgba,
+
+    /// Width of the line, in pixels.
+    pub stroke_width: f32,
+
+    /// If true, the line is drawn as a smooth curve rather than straight segments.
+    pub smooth: bool,
+
+    /// If true, display axis lines and labels for the range.
+    pub axes: bool,
+
+    /// Additional styles to be applied to the chart.
+    pub style: StyleHandle,
+}
+
+impl Default for Sparkline {
+    fn default() -> Self {
+        Self {
+            values: Signal::Constant(Vec::new()),
+            range: None,
+            color: colors::ACCENT,
+            stroke_width: 1.5,
+            smooth: false,
+            axes: false,
+            style: StyleHandle::default(),
+        }
+    }
+}
+
+impl Sparkline {
+    /// Create a new sparkline.
+    pub fn new(values: impl IntoSignal<Vec<f32>>) -> Self {
+        Self {
+            values: values.into_signal(),
+            ..default()
+        }
+    }
+
+    /// Set a fixed range for the chart.
+    pub fn range(mut self, min: f32, max: f32) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Set the color of the line.
+    pub fn color(mut self, color: impl Into<Srgba>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// Set the width of the line.
+    pub fn stroke_width(mut self, stroke_width: f32) -> Self {
+        self.stroke_width = stroke_width;
+        self
+    }
+
+    /// Set whether to draw the line as a smooth curve.
+    pub fn smooth(mut self, smooth: bool) -> Self {
+        self.smooth = smooth;
+        self
+    }
+
+    /// Set whether to display axes.
+    pub fn axes(mut self, axes: bool) -> Self {
+        self.axes = axes;
+        self
+    }
+
+    /// Set the additional styles for the chart.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+
+    /// Append a sample to a ring buffer of values, discarding the oldest samples so that
+    /// there are at most `capacity`.
+    pub fn push_sample(buffer: &mut Vec<f32>, value: f32, capacity: usize) {
+        buffer.push(value);
+        if buffer.len() > capacity {
+            buffer.drain(..buffer.len() - capacity);
+        }
+    }
+}
+
+impl UiTemplate for Sparkline {
+    fn build(&self, builder: &mut UiBuilder) {
+        let values = self.values.clone();
+        let fixed_range = self.range;
+        let color = self.color;
+        let stroke_width = self.stroke_width;
+        let smooth = self.smooth;
+        let axes = self.axes;
+        let range =
+            builder.create_derived(move |rcx| values.map(rcx, |v| value_range(v, fixed_range)));
+
+        builder
+            .spawn((Node::default(), Name::new("Sparkline")))
+            .style((style_chart, style_sparkline, self.style.clone()))
+            .create_children(|builder| {
+                if axes {
+                    build_axis_labels(builder, range);
+                }
+
+                let material = builder.create_ui_material(DrawPathMaterial::default());
+                let material_id = material.id();
+                let plot = builder
+                    .spawn((Node::default(), Name::new("SparklinePlot")))
+                    .style(style_plot)
+                    .insert(MaterialNode(material))
+                    .id();
+                if axes {
+                    builder.entity_mut(plot).style(style_plot_axes);
+                }
+
+                // Rebuild the path when the values or the size of the plot change.
+                let values = self.values.clone();
+                builder.create_effect(move |ecx| {
+                    let size = ecx
+                        .use_component_map::<ComputedNode, _, _>(plot, |node| node.size())
+                        .unwrap_or_default();
+                    let (lo, hi) = range.get(ecx);
+                    // Inset the line so that the stroke isn't clipped at the edges.
+                    let inset = stroke_width * 0.5 + 1.;
+                    let extent = (size - Vec2::splat(inset * 2.)).max(Vec2::ZERO);
+                    let points: Vec<Vec2> = values.map(ecx, |values| {
+                        let step = extent.x / (values.len().max(2) - 1) as f32;
+                        values
+                            .iter()
+                            .enumerate()
+                            .map(|(i, v)| {
+                                let t = ((v - lo) / (hi - lo)).clamp(0., 1.);
+                                Vec2::new(inset + i as f32 * step, inset + (1. - t) * extent.y)
+                            })
+                            .collect()
+                    });
+
+                    let mut path = DrawablePath::new(color, stroke_width);
+                    path.set_hairline(true);
+                    if let Some((first, rest)) = points.split_first() {
+                        path.move_to(*first);
+                        if smooth && rest.len() > 1 {
+                            // Curve through the midpoints, using each sample as the control.
+                            for pair in rest.windows(2) {
+                                path.quadratic_to(pair[0], (pair[0] + pair[1]) * 0.5);
+                            }
+                            path.line_to(*rest.last().unwrap());
+                        } else {
+                            for point in rest {
+                                path.line_to(*point);
+                            }
+                        }
+                    }
+                    update_ui_material(ecx.world_mut(), material_id, |material| {
+                        material.update(&path, Vec2::ZERO);
+                    });
+                });
+            });
+    }
+}
+
+/// A simple bar chart, with one bar per value. Bars start from zero, so negative values are
+/// drawn as empty bars.
+pub struct BarChart {
+    /// The values to plot.
+    pub values: Signal<Vec<f32>>,
+
+    /// The value at the top of the chart. If not set, the chart is scaled to fit the values.
+    pub max: Option<f32>,
+
+    /// Color of the bars.
+    pub color: Srgba,
+
+    /// Gap between bars, in pixels.
+    pub gap: f32,
+
+    /// If true, display axis lines and labels for the range.
+    pub axes: bool,
+
+    /// Additional styles to be applied to the chart.
+    pub style: StyleHandle,
+}
+
+impl Default for BarChart {
+    fn default() -> Self {
+        Self {
+            values: Signal::Constant(Vec::new()),
+            max: None,
+            color: colors::ACCENT,
+            gap: 1.,
+            axes: false,
+            style: StyleHandle::default(),
+        }
+    }
+}
+
+impl BarChart {
+    /// Create a new bar chart.
+    pub fn new(values: impl IntoSignal<Vec<f32>>) -> Self {
+        Self {
+            values: values.into_signal(),
+            ..default()
+        }
+    }
+
+    /// Set the value at the top of the chart.
+    pub fn max(mut self, max: f32) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Set the color of the bars.
+    pub fn color(mut self, color: impl Into<Srgba>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// Set the gap between bars.
+    pub fn gap(mut self, gap: f32) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Set whether to display axes.
+    pub fn axes(mut self, axes: bool) -> Self {
+        self.axes = axes;
+        self
+    }
+
+    /// Set the additional styles for the chart.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl UiTemplate for BarChart {
+    fn build(&self, builder: &mut UiBuilder) {
+        let values = self.values.clone();
+        let max = self.max;
+        let color = self.color;
+        let gap = self.gap;
+        let axes = self.axes;
+        let range = builder.create_derived(move |rcx| {
+            values.map(rcx, |v| value_range(v, max.map(|max| (0., max))))
+        });
+
+        builder
+            .spawn((Node::default(), Name::new("BarChart")))
+            .style((style_chart, style_bar_chart, self.style.clone()))
+            .create_children(|builder| {
+                if axes {
+                    build_axis_labels(builder, range);
+                }
+
+                let values = self.values.clone();
+                let bars = builder
+                    .spawn((Node::default(), Name::new("BarChartBars")))
+                    .style((style_plot, style_bars, move |ss: &mut StyleBuilder| {
+                        ss.column_gap(gap);
+                    }))
+                    .id();
+                if axes {
+                    builder.entity_mut(bars).style(style_plot_axes);
+                }
+                builder.entity_mut(bars).create_children(|builder| {
+                    builder.for_index(
+                        move |rcx| {
+                            // Bars are rebuilt only when their height changes.
+                            let hi = range.get(rcx).1.max(0.);
+                            values
+                                .map(rcx, |values| {
+                                    values
+                                        .iter()
+                                        .map(|v| if hi > 0. { (v / hi).clamp(0., 1.) } else { 0. })
+                                        .collect::<Vec<f32>>()
+                                })
+                                .into_iter()
+                        },
+                        move |height, _, builder| {
+                            let height = *height;
+                            builder.spawn((Node::default(), Name::new("Bar"))).style((
+                                style_bar,
+                                move |ss: &mut StyleBuilder| {
+                                    ss.height(ui::Val::Percent(height * 100.))
+                                        .background_color(color);
+                                },
+                            ));
+                        },
+                        |_| {},
+                    );
+                });
+            });
+    }
+}
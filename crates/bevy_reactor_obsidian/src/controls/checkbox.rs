@@ -18,7 +18,9 @@ use crate::{
     colors,
     cursor::StyleBuilderCursor,
     hover_signal::CreateHoverSignal,
+    labelling::{LabelFor, LabelledBy},
     prelude::{CreateFocusSignal, TabIndex},
+    theme::UseTheme,
     typography,
 };
 
@@ -80,6 +82,9 @@ pub struct Checkbox {
 
     /// The tab index of the checkbox (default 0).
     pub tab_index: i32,
+
+    /// An external entity which labels the checkbox, set by [`Checkbox::label_for`].
+    pub label_entity: Option<Entity>,
 }
 
 impl Default for Checkbox {
@@ -91,6 +96,7 @@ impl Default for Checkbox {
             style: Default::default(),
             on_change: Default::default(),
             tab_index: Default::default(),
+            label_entity: None,
         }
     }
 }
@@ -123,6 +129,13 @@ impl Checkbox {
         self
     }
 
+    /// Use an existing entity, elsewhere in the UI, as the label of the checkbox. Clicking the
+    /// label toggles the checkbox, and the label is reported as the checkbox's accessible name.
+    pub fn label_for(mut self, label: Entity) -> Self {
+        self.label_entity = Some(label);
+        self
+    }
+
     /// Set the style of the checkbox.
     pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
         self.style = style.into_handle();
@@ -151,6 +164,7 @@ impl UiTemplate for Checkbox {
 
         let checked = self.checked;
         let disabled = self.disabled;
+        let mut label = Entity::PLACEHOLDER;
 
         builder
             .world_mut()
@@ -174,13 +188,14 @@ impl UiTemplate for Checkbox {
                             let is_checked = checked.get(rcx);
                             let is_disabled = disabled.get(rcx);
                             let is_hovering = hovering.get(rcx);
+                            let theme = rcx.use_theme();
                             match (is_checked, is_disabled, is_hovering) {
-                                (true, true, _) => colors::ACCENT.with_alpha(0.2),
-                                (true, false, true) => colors::ACCENT.darker(0.15),
-                                (true, _, _) => colors::ACCENT.darker(0.2),
-                                (false, true, _) => colors::U1.with_alpha(0.7),
-                                (false, false, true) => colors::U1.lighter(0.002),
-                                (false, false, false) => colors::U1,
+                                (true, true, _) => theme.accent.with_alpha(0.2),
+                                (true, false, true) => theme.accent.darker(0.15),
+                                (true, _, _) => theme.accent.darker(0.2),
+                                (false, true, _) => theme.surface.with_alpha(0.7),
+                                (false, false, true) => theme.surface.lighter(0.002),
+                                (false, false, false) => theme.surface,
                             }
                         },
                         |color, sb| {
@@ -188,10 +203,10 @@ impl UiTemplate for Checkbox {
                         },
                     )
                     .style_dyn(
-                        move |rcx| focused.get(rcx),
-                        |is_focused, sb| {
-                            if is_focused {
-                                sb.outline_color(colors::FOCUS)
+                        move |rcx| focused.get(rcx).then(|| rcx.use_theme().focus),
+                        |focus, sb| {
+                            if let Some(focus) = focus {
+                                sb.outline_color(focus)
                                     .outline_offset(2)
                                     .outline_width(2);
                             } else {
@@ -209,22 +224,34 @@ impl UiTemplate for Checkbox {
                         );
                     });
 
-                builder
+                label = builder
                     .spawn(Node::default())
                     .styles((typography::text_default, style_checkbox_label))
                     .style_dyn(
-                        move |rcx| disabled.get(rcx),
-                        |disabled, sb| {
+                        move |rcx| (disabled.get(rcx), rcx.use_theme().foreground),
+                        |(disabled, foreground), sb| {
                             if disabled {
-                                sb.color(colors::FOREGROUND.with_alpha(0.2));
+                                sb.color(foreground.with_alpha(0.2));
                             } else {
-                                sb.color(colors::FOREGROUND);
+                                sb.color(foreground);
                             }
                         },
                     )
                     .create_children(|builder| {
                         (self.label.as_ref())(builder);
-                    });
+                    })
+                    .id();
             });
+
+        // An external label takes precedence over the checkbox's own label.
+        match self.label_entity {
+            Some(external) => {
+                builder.world_mut().entity_mut(external).insert(LabelFor(id));
+                builder.world_mut().entity_mut(id).insert(LabelledBy(external));
+            }
+            None => {
+                builder.world_mut().entity_mut(id).insert(LabelledBy(label));
+            }
+        }
     }
 }
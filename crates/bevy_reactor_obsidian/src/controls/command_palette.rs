@@ -0,0 +1,489 @@
+use std::ops::Range;
+
+use accesskit::{self, Role};
+use bevy::{
+    a11y::AccessibilityNode, ecs::world::DeferredWorld, input::ButtonState, prelude::*, ui,
+    window::SystemCursorIcon, winit::cursor::CursorIcon,
+};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{
+    CondBuilder, CreateChilden, EntityStyleBuilder, ForEachBuilder, InvokeUiTemplate, TextBuilder,
+    UiBuilder, UiTemplate,
+};
+use bevy_reactor_signals::{Callback, Mutable, Rcx, RunCallback};
+
+use crate::{
+    colors, cursor::StyleBuilderCursor, fuzzy::fuzzy_filter, input_dispatch::FocusKeyboardInput,
+    layering::UiLayer, size::Size, tab_navigation::TabGroup, typography,
+};
+
+use super::{barrier::Barrier, HighlightedText, TextInput};
+
+fn style_palette_barrier(ss: &mut StyleBuilder) {
+    ss.position(PositionType::Absolute)
+        .display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Center)
+        .left(0)
+        .top(0)
+        .width(ui::Val::Vw(100.))
+        .height(ui::Val::Vh(100.))
+        .padding_top(ui::Val::Vh(15.))
+        .background_color(colors::U1.with_alpha(0.5));
+}
+
+fn style_palette(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch)
+        .background_color(colors::U2)
+        .border(1)
+        .border_color(colors::U3)
+        .border_radius(6.0)
+        .padding(6)
+        .gap(6);
+}
+
+fn style_results(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch);
+}
+
+fn style_result(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .justify_content(ui::JustifyContent::SpaceBetween)
+        .align_items(ui::AlignItems::Center)
+        .min_height(24)
+        .padding((8, 2))
+        .border_radius(3.0)
+        .color(colors::FOREGROUND)
+        .cursor(CursorIcon::System(SystemCursorIcon::Pointer));
+}
+
+fn style_shortcut(ss: &mut StyleBuilder) {
+    ss.color(colors::DIM).margin_left(12);
+}
+
+fn style_no_results(ss: &mut StyleBuilder) {
+    ss.color(colors::DIM).padding((8, 4));
+}
+
+/// A named action which can be run from the [`CommandPalette`].
+#[derive(Clone)]
+pub struct PaletteCommand {
+    /// The name displayed in the palette, and matched against the search query.
+    pub name: String,
+
+    /// Optional keyboard shortcut, displayed next to the name.
+    pub shortcut: Option<String>,
+
+    /// Callback which performs the command.
+    pub action: Callback,
+}
+
+/// Resource which holds the list of commands available in the [`CommandPalette`].
+#[derive(Resource, Default)]
+pub struct CommandRegistry {
+    commands: Vec<PaletteCommand>,
+}
+
+impl CommandRegistry {
+    /// Add a command to the registry.
+    pub fn add(&mut self, name: impl Into<String>, action: Callback) -> &mut Self {
+        self.commands.push(PaletteCommand {
+            name: name.into(),
+            shortcut: None,
+            action,
+        });
+        self
+    }
+
+    /// Add a command to the registry, along with a description of its keyboard shortcut.
+    pub fn add_with_shortcut(
+        &mut self,
+        name: impl Into<String>,
+        shortcut: impl Into<String>,
+        action: Callback,
+    ) -> &mut Self {
+        self.commands.push(PaletteCommand {
+            name: name.into(),
+            shortcut: Some(shortcut.into()),
+            action,
+        });
+        self
+    }
+
+    /// Remove all commands with the given name.
+    pub fn remove(&mut self, name: &str) {
+        self.commands.retain(|cmd| cmd.name != name);
+    }
+
+    /// Iterate over all registered commands.
+    pub fn iter(&self) -> impl Iterator<Item = &PaletteCommand> {
+        self.commands.iter()
+    }
+}
+
+/// Extension trait which registers a one-shot system as a palette command.
+pub trait RegisterPaletteCommand {
+    /// Register `system` as a command in the [`CommandRegistry`] under the given name.
+    fn register_palette_command<M, S: IntoSystem<In<()>, (), M> + 'static>(
+        &mut self,
+        name: impl Into<String>,
+        system: S,
+    ) -> &mut Self;
+}
+
+impl RegisterPaletteCommand for App {
+    fn register_palette_command<M, S: IntoSystem<In<()>, (), M> + 'static>(
+        &mut self,
+        name: impl Into<String>,
+        system: S,
+    ) -> &mut Self {
+        let world = self.world_mut();
+        let action = Callback::new(world.register_system(system));
+        world
+            .get_resource_or_insert_with(CommandRegistry::default)
+            .add(name, action);
+        self
+    }
+}
+
+/// Resource which controls whether the [`CommandPalette`] is open.
+#[derive(Resource, Default)]
+pub struct CommandPaletteState {
+    /// Whether the palette is currently shown.
+    pub open: bool,
+}
+
+/// Resource which holds the key combination that toggles the [`CommandPalette`].
+#[derive(Resource, Clone, Copy)]
+pub struct CommandPaletteHotkey {
+    /// The key which opens the palette.
+    pub key: KeyCode,
+
+    /// Whether the Control key must be held.
+    pub ctrl: bool,
+
+    /// Whether the Shift key must be held.
+    pub shift: bool,
+}
+
+impl Default for CommandPaletteHotkey {
+    fn default() -> Self {
+        Self {
+            key: KeyCode::KeyP,
+            ctrl: true,
+            shift: false,
+        }
+    }
+}
+
+/// An entry in the list of search results.
+#[derive(Clone, PartialEq)]
+struct PaletteMatch {
+    /// Position within the result list.
+    rank: usize,
+    /// Index of the command within the registry.
+    command: usize,
+    name: String,
+    /// Byte ranges of the name which match the query.
+    ranges: Vec<Range<usize>>,
+    shortcut: Option<String>,
+}
+
+/// Return the registry entries which match the query, best match first.
+fn filter_commands(
+    registry: &CommandRegistry,
+    query: &str,
+    max_results: usize,
+) -> Vec<PaletteMatch> {
+    fuzzy_filter(query, registry.commands.iter().enumerate(), |(_, cmd)| {
+        cmd.name.as_str()
+    })
+    .into_iter()
+    .take(max_results)
+    .enumerate()
+    .map(|(rank, ((command, cmd), m))| PaletteMatch {
+        rank,
+        command,
+        name: cmd.name.clone(),
+        ranges: m.ranges,
+        shortcut: cmd.shortcut.clone(),
+    })
+    .collect()
+}
+
+/// Toggle the command palette when the hotkey is pressed.
+pub(crate) fn toggle_command_palette(
+    keys: Res<ButtonInput<KeyCode>>,
+    hotkey: Res<CommandPaletteHotkey>,
+    mut state: ResMut<CommandPaletteState>,
+) {
+    let ctrl = keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    if keys.just_pressed(hotkey.key) && ctrl == hotkey.ctrl && shift == hotkey.shift {
+        state.open = !state.open;
+    }
+}
+
+/// A popup overlay which lets the user search the [`CommandRegistry`] by name and run a
+/// command. The palette is shown and hidden via the [`CommandPaletteState`] resource, which is
+/// toggled by the [`CommandPaletteHotkey`] (Ctrl+P by default). Use the arrow keys to choose a
+/// result, Enter to run it, and Escape to dismiss the palette.
+pub struct CommandPalette {
+    /// Width of the palette.
+    pub width: ui::Val,
+
+    /// Text shown in the search field when it is empty.
+    pub placeholder: String,
+
+    /// Maximum number of results to show.
+    pub max_results: usize,
+
+    /// Additional styles to be applied to the palette.
+    pub style: StyleHandle,
+}
+
+impl Default for CommandPalette {
+    fn default() -> Self {
+        Self {
+            width: ui::Val::Px(480.),
+            placeholder: "Type a command...".to_string(),
+            max_results: 12,
+            style: StyleHandle::default(),
+        }
+    }
+}
+
+impl CommandPalette {
+    /// Construct a new `CommandPalette`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the width of the palette.
+    pub fn width(mut self, width: ui::Val) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Set the placeholder text of the search field.
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Set the maximum number of results to show.
+    pub fn max_results(mut self, max_results: usize) -> Self {
+        self.max_results = max_results;
+        self
+    }
+
+    /// Set additional styles to be applied to the palette.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl UiTemplate for CommandPalette {
+    fn build(&self, builder: &mut UiBuilder) {
+        let width = self.width;
+        let max_results = self.max_results;
+        let placeholder = self.placeholder.clone();
+        let style = self.style.clone();
+        let query: Mutable<String> = builder.create_mutable(String::new());
+        let selected: Mutable<usize> = builder.create_mutable(0);
+
+        let on_close = builder.create_callback(move |_: In<()>, mut world: DeferredWorld| {
+            world.resource_mut::<CommandPaletteState>().open = false;
+            query.set_clone(&mut world, String::new());
+            selected.set(&mut world, 0);
+        });
+
+        let on_execute =
+            builder.create_callback(move |command: In<usize>, mut world: DeferredWorld| {
+                world.run_callback(on_close, ());
+                let action = world
+                    .resource::<CommandRegistry>()
+                    .commands
+                    .get(*command)
+                    .map(|cmd| cmd.action);
+                if let Some(action) = action {
+                    world.run_callback(action, ());
+                }
+            });
+
+        let on_query_change =
+            builder.create_callback(move |text: In<String>, mut world: DeferredWorld| {
+                query.set_clone(&mut world, text.0);
+                selected.set(&mut world, 0);
+            });
+
+        let on_submit =
+            builder.create_callback(move |text: In<String>, mut world: DeferredWorld| {
+                let index = selected.get(&world);
+                let command =
+                    filter_commands(world.resource::<CommandRegistry>(), &text, max_results)
+                        .get(index)
+                        .map(|m| m.command);
+                if let Some(command) = command {
+                    world.run_callback(on_execute, command);
+                }
+            });
+
+        builder.cond(
+            |rcx: &Rcx| rcx.read_resource::<CommandPaletteState>().open,
+            move |builder| {
+                let placeholder = placeholder.clone();
+                let style = style.clone();
+                builder
+                    .spawn((Node::default(), Name::new("CommandPalette::Overlay")))
+                    .style(style_palette_barrier)
+                    .insert((
+                        Barrier {
+                            on_close: Some(on_close),
+                        },
+                        UiLayer::Modal,
+                    ))
+                    .create_children(|builder| {
+                        builder
+                            .spawn((Node::default(), Name::new("CommandPalette")))
+                            .insert((
+                                TabGroup {
+                                    order: 0,
+                                    modal: true,
+                                },
+                                AccessibilityNode::from(accesskit::Node::new(Role::Dialog)),
+                            ))
+                            .styles((
+                                typography::text_default,
+                                style_palette,
+                                move |ss: &mut StyleBuilder| {
+                                    ss.width(width);
+                                },
+                                style,
+                            ))
+                            .observe(|mut trigger: Trigger<Pointer<Down>>| {
+                                // Prevent clicks from propagating to the barrier and closing
+                                // the palette.
+                                trigger.propagate(false);
+                            })
+                            .observe(
+                                move |mut trigger: Trigger<FocusKeyboardInput>,
+                                      mut world: DeferredWorld| {
+                                    let event = &trigger.event().input;
+                                    if event.state != ButtonState::Pressed {
+                                        return;
+                                    }
+                                    let count = filter_commands(
+                                        world.resource::<CommandRegistry>(),
+                                        &query.get_clone(&mut world),
+                                        max_results,
+                                    )
+                                    .len();
+                                    let index = selected.get(&world);
+                                    let next = match event.key_code {
+                                        KeyCode::ArrowUp => index.saturating_sub(1),
+                                        KeyCode::ArrowDown => {
+                                            (index + 1).min(count.saturating_sub(1))
+                                        }
+                                        KeyCode::PageUp | KeyCode::Home => 0,
+                                        KeyCode::PageDown | KeyCode::End => count.saturating_sub(1),
+                                        _ => return,
+                                    };
+                                    trigger.propagate(false);
+                                    selected.set(&mut world, next);
+                                },
+                            )
+                            .create_children(|builder| {
+                                builder.invoke(
+                                    TextInput::new()
+                                        .value(query.signal())
+                                        .placeholder(placeholder)
+                                        .size(Size::Md)
+                                        .autofocus(true)
+                                        .on_change(on_query_change)
+                                        .on_submit(on_submit),
+                                );
+                                builder
+                                    .spawn((Node::default(), Name::new("CommandPalette::Results")))
+                                    .style(style_results)
+                                    .create_children(|builder| {
+                                        builder.for_each(
+                                            move |rcx| {
+                                                filter_commands(
+                                                    rcx.read_resource::<CommandRegistry>(),
+                                                    &query.signal().get_clone(rcx),
+                                                    max_results,
+                                                )
+                                                .into_iter()
+                                            },
+                                            move |item, builder| {
+                                                build_result(item, selected, on_execute, builder);
+                                            },
+                                            |builder| {
+                                                builder
+                                                    .spawn(Node::default())
+                                                    .style(style_no_results)
+                                                    .create_children(|builder| {
+                                                        builder.text("No matching commands");
+                                                    });
+                                            },
+                                        );
+                                    });
+                            });
+                    });
+            },
+            |_| {},
+        );
+    }
+}
+
+fn build_result(
+    item: &PaletteMatch,
+    selected: Mutable<usize>,
+    on_execute: Callback<usize>,
+    builder: &mut UiBuilder,
+) {
+    let rank = item.rank;
+    let command = item.command;
+    builder
+        .spawn((Node::default(), Name::new("CommandPalette::Result")))
+        .style(style_result)
+        .style_dyn(
+            move |rcx| selected.get(rcx) == rank,
+            |is_selected, sb| {
+                sb.background_color(if is_selected {
+                    colors::TEXT_SELECT
+                } else {
+                    colors::TRANSPARENT
+                });
+            },
+        )
+        .observe(
+            move |mut trigger: Trigger<Pointer<Click>>, mut world: DeferredWorld| {
+                trigger.propagate(false);
+                world.run_callback(on_execute, command);
+            },
+        )
+        .observe(
+            move |_trigger: Trigger<Pointer<Over>>, mut world: DeferredWorld| {
+                selected.set(&mut world, rank);
+            },
+        )
+        .create_children(|builder| {
+            builder.invoke(HighlightedText::new(item.name.clone()).ranges(item.ranges.clone()));
+            if let Some(shortcut) = &item.shortcut {
+                builder
+                    .spawn(Node::default())
+                    .style(style_shortcut)
+                    .create_children(|builder| {
+                        builder.text(shortcut.clone());
+                    });
+            }
+        });
+}
@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 
-use super::Disabled;
+use crate::input_dispatch::CapturesPointer;
+
+use super::{Disabled, DragScaling};
 
 #[derive(Clone, Debug, Component)]
 pub struct ValueChange<T>(pub T);
@@ -17,17 +19,36 @@ impl<T: Send + Sync + 'static> Event for ValueChange<T> {
 /// before clamping. It is the receiver's responsibility to update the slider's value when
 /// the value change event is received.
 #[derive(Component)]
-#[require(DragState)]
+#[require(DragState, CapturesPointer)]
 pub struct CoreSlider {
     pub value: f32,
     pub min: f32,
     pub max: f32,
+    /// If true, the slider is oriented vertically, with the minimum value at the bottom.
+    pub vertical: bool,
+    /// Step size used when snapping; zero disables snapping.
+    pub step: f32,
+    /// How drag movement is converted into value changes.
+    pub scaling: DragScaling,
 }
 
 impl CoreSlider {
     /// Constructg a new [`CoreSlider`].
     pub fn new(value: f32, min: f32, max: f32) -> Self {
-        Self { value, min, max }
+        Self {
+            value,
+            min,
+            max,
+            vertical: false,
+            step: 0.,
+            scaling: DragScaling::default(),
+        }
+    }
+
+    /// Set whether the slider is oriented vertically.
+    pub fn with_vertical(mut self, vertical: bool) -> Self {
+        self.vertical = vertical;
+        self
     }
 
     /// Get the current value of the slider.
@@ -35,6 +56,18 @@ impl CoreSlider {
         self.value
     }
 
+    /// Set the step size used when snapping.
+    pub fn with_step(mut self, step: f32) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Set how drag movement is converted into value changes.
+    pub fn with_scaling(mut self, scaling: DragScaling) -> Self {
+        self.scaling = scaling;
+        self
+    }
+
     /// Set the value of the slider, clamping it to the min and max values.
     pub fn set_value(&mut self, value: f32) {
         self.value = value.clamp(self.min, self.max);
@@ -46,8 +79,10 @@ impl CoreSlider {
 pub struct DragState {
     /// Whether the slider is currently being dragged.
     dragging: bool,
-    /// The value of the slider when dragging started.
+    /// The accumulated (unclamped) value of the slider during the drag.
     offset: f32,
+    /// The drag distance as of the previous drag event.
+    last_distance: Vec2,
 }
 
 pub(crate) fn slider_on_drag_start(
@@ -59,6 +94,7 @@ pub(crate) fn slider_on_drag_start(
         if !disabled {
             drag.dragging = true;
             drag.offset = slider.value;
+            drag.last_distance = Vec2::ZERO;
         }
     }
 }
@@ -66,17 +102,28 @@ pub(crate) fn slider_on_drag_start(
 pub(crate) fn slider_on_drag(
     mut trigger: Trigger<Pointer<Drag>>,
     mut q_state: Query<(&ComputedNode, &CoreSlider, &mut DragState)>,
+    keys: Res<ButtonInput<KeyCode>>,
     mut commands: Commands,
 ) {
-    if let Ok((node, slider, drag)) = q_state.get_mut(trigger.entity()) {
+    if let Ok((node, slider, mut drag)) = q_state.get_mut(trigger.entity()) {
         trigger.propagate(false);
         if drag.dragging {
+            // Accumulate movement since the previous event, so that changing modifier keys
+            // mid-drag does not cause the value to jump.
             let distance = trigger.event().distance;
-            // Measure node width and slider value.
-            let slider_width = node.size().x;
+            let movement = distance - drag.last_distance;
+            drag.last_distance = distance;
+            // Measure node length and slider value. Vertical sliders increase upwards.
+            let (slider_length, delta) = if slider.vertical {
+                (node.size().y, -movement.y)
+            } else {
+                (node.size().x, movement.x)
+            };
+            let delta = slider.scaling.scale_delta(delta, &keys);
             let range = slider.max - slider.min;
             let new_value = if range > 0. {
-                drag.offset + (distance.x * range) / slider_width
+                drag.offset += (delta * range) / slider_length;
+                slider.scaling.snap(drag.offset, slider.step, &keys)
             } else {
                 slider.min + range * 0.5
             };
@@ -0,0 +1,418 @@
+use bevy::{
+    ecs::world::DeferredWorld,
+    prelude::*,
+    ui::{self, RelativeCursorPosition},
+};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{CreateChilden, EntityStyleBuilder, UiBuilder, UiTemplate};
+use bevy_reactor_signals::{Callback, IntoSignal, RunCallback, Signal};
+
+use crate::{colors, input_dispatch::CapturesPointer};
+
+/// Angle increment used when Shift is held while dragging a dial.
+const DIAL_SNAP_DEGREES: f32 = 15.;
+
+fn style_dial(ss: &mut StyleBuilder) {
+    ss.border(1)
+        .border_color(colors::U3)
+        .background_color(colors::U1)
+        .border_radius(ui::Val::Percent(50.));
+}
+
+fn style_pad(ss: &mut StyleBuilder) {
+    ss.border(1)
+        .border_color(colors::U3)
+        .background_color(colors::U1)
+        .border_radius(3)
+        .overflow(ui::OverflowAxis::Clip);
+}
+
+fn style_crosshair_h(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .left(0)
+        .right(0)
+        .height(1)
+        .background_color(colors::U2);
+}
+
+fn style_crosshair_v(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .top(0)
+        .bottom(0)
+        .width(1)
+        .background_color(colors::U2);
+}
+
+fn style_thumb(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .width(8)
+        .height(8)
+        .margin_left(-4)
+        .margin_top(-4)
+        .border_radius(4)
+        .background_color(colors::FOREGROUND);
+}
+
+/// Returns the pointer position relative to the node, from 0 to 1 on each axis, or `None` if
+/// the position is not known.
+fn cursor_position(world: &DeferredWorld, entity: Entity) -> Option<Vec2> {
+    world
+        .get::<RelativeCursorPosition>(entity)
+        .and_then(|pos| pos.normalized)
+}
+
+fn shift_pressed(world: &DeferredWorld) -> bool {
+    world
+        .resource::<ButtonInput<KeyCode>>()
+        .any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight])
+}
+
+/// A rotary control for editing an angle, in degrees. Dragging anywhere on the dial points it
+/// towards the cursor; holding Shift snaps to 15° increments. Zero points up, and angles
+/// increase clockwise, from 0 up to (but not including) 360.
+pub struct Dial {
+    /// Current angle, in degrees.
+    pub value: Signal<f32>,
+
+    /// Diameter of the dial, in pixels.
+    pub diameter: f32,
+
+    /// Whether the dial is disabled.
+    pub disabled: Signal<bool>,
+
+    /// Additional styles to be applied to the dial.
+    pub style: StyleHandle,
+
+    /// Callback called with the new angle when the dial is dragged.
+    pub on_change: Option<Callback<f32>>,
+}
+
+impl Default for Dial {
+    fn default() -> Self {
+        Self {
+            value: Signal::Constant(0.),
+            diameter: 32.,
+            disabled: Signal::Constant(false),
+            style: StyleHandle::default(),
+            on_change: None,
+        }
+    }
+}
+
+impl Dial {
+    /// Create a new dial.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the current angle, in degrees.
+    pub fn value(mut self, value: impl IntoSignal<f32>) -> Self {
+        self.value = value.into_signal();
+        self
+    }
+
+    /// Set the diameter of the dial.
+    pub fn diameter(mut self, diameter: f32) -> Self {
+        self.diameter = diameter;
+        self
+    }
+
+    /// Set whether the dial is disabled.
+    pub fn disabled(mut self, disabled: impl IntoSignal<bool>) -> Self {
+        self.disabled = disabled.into_signal();
+        self
+    }
+
+    /// Set additional styles to be applied to the dial.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+
+    /// Set the callback called when the angle changes.
+    pub fn on_change(mut self, callback: Callback<f32>) -> Self {
+        self.on_change = Some(callback);
+        self
+    }
+}
+
+impl UiTemplate for Dial {
+    fn build(&self, builder: &mut UiBuilder) {
+        let value = self.value;
+        let disabled = self.disabled;
+        let on_change = self.on_change;
+        let diameter = self.diameter;
+
+        let dial = builder
+            .spawn((
+                Node::default(),
+                Name::new("Dial"),
+                RelativeCursorPosition::default(),
+                CapturesPointer,
+            ))
+            .id();
+
+        // Point the dial towards the cursor.
+        let update = move |world: &mut DeferredWorld| {
+            if disabled.get(&*world) {
+                return;
+            }
+            let Some(pos) = cursor_position(world, dial) else {
+                return;
+            };
+            let offset = pos - Vec2::splat(0.5);
+            if offset.length_squared() < 1e-6 {
+                return;
+            }
+            let mut angle = offset.x.atan2(-offset.y).to_degrees();
+            if shift_pressed(world) {
+                angle = (angle / DIAL_SNAP_DEGREES).round() * DIAL_SNAP_DEGREES;
+            }
+            let angle = angle.rem_euclid(360.);
+            if angle != value.get(&*world) {
+                if let Some(on_change) = on_change {
+                    world.run_callback(on_change, angle);
+                }
+            }
+        };
+
+        builder
+            .entity_mut(dial)
+            .styles((
+                style_dial,
+                move |ss: &mut StyleBuilder| {
+                    ss.width(diameter).height(diameter);
+                },
+                self.style.clone(),
+            ))
+            .observe(
+                move |mut trigger: Trigger<Pointer<Down>>, mut world: DeferredWorld| {
+                    trigger.propagate(false);
+                    if trigger.event().button == PointerButton::Primary {
+                        update(&mut world);
+                    }
+                },
+            )
+            .observe(
+                move |mut trigger: Trigger<Pointer<Drag>>, mut world: DeferredWorld| {
+                    trigger.propagate(false);
+                    if trigger.event().button == PointerButton::Primary {
+                        update(&mut world);
+                    }
+                },
+            )
+            .create_children(|builder| {
+                builder
+                    .spawn((Node::default(), Name::new("Dial::Thumb")))
+                    .style(style_thumb)
+                    .style_dyn(
+                        move |rcx| (value.get(rcx), disabled.get(rcx)),
+                        |(angle, disabled), ss| {
+                            // Place the thumb just inside the rim.
+                            let (sin, cos) = angle.to_radians().sin_cos();
+                            ss.left(ui::Val::Percent(50. + 35. * sin))
+                                .top(ui::Val::Percent(50. - 35. * cos))
+                                .background_color(if disabled {
+                                    colors::U4
+                                } else {
+                                    colors::FOREGROUND
+                                });
+                        },
+                    );
+            });
+    }
+}
+
+/// A square pad for editing a [`Vec2`], such as a texture offset, by dragging a point within it.
+/// The x axis increases to the right, and the y axis increases upwards.
+pub struct PadInput {
+    /// Current value.
+    pub value: Signal<Vec2>,
+
+    /// Value at the bottom-left corner of the pad.
+    pub min: Vec2,
+
+    /// Value at the top-right corner of the pad.
+    pub max: Vec2,
+
+    /// Step size for each axis when Shift is held; zero disables snapping.
+    pub step: Vec2,
+
+    /// Size of the pad, in pixels.
+    pub size: f32,
+
+    /// Whether the pad is disabled.
+    pub disabled: Signal<bool>,
+
+    /// Additional styles to be applied to the pad.
+    pub style: StyleHandle,
+
+    /// Callback called with the new value when the point is dragged.
+    pub on_change: Option<Callback<Vec2>>,
+}
+
+impl Default for PadInput {
+    fn default() -> Self {
+        Self {
+            value: Signal::Constant(Vec2::ZERO),
+            min: Vec2::splat(-1.),
+            max: Vec2::splat(1.),
+            step: Vec2::ZERO,
+            size: 64.,
+            disabled: Signal::Constant(false),
+            style: StyleHandle::default(),
+            on_change: None,
+        }
+    }
+}
+
+impl PadInput {
+    /// Create a new pad input.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the current value.
+    pub fn value(mut self, value: impl IntoSignal<Vec2>) -> Self {
+        self.value = value.into_signal();
+        self
+    }
+
+    /// Set the range of values spanned by the pad.
+    pub fn range(mut self, min: Vec2, max: Vec2) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+
+    /// Set the step size used when Shift is held.
+    pub fn step(mut self, step: Vec2) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Set the size of the pad.
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set whether the pad is disabled.
+    pub fn disabled(mut self, disabled: impl IntoSignal<bool>) -> Self {
+        self.disabled = disabled.into_signal();
+        self
+    }
+
+    /// Set additional styles to be applied to the pad.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+
+    /// Set the callback called when the value changes.
+    pub fn on_change(mut self, callback: Callback<Vec2>) -> Self {
+        self.on_change = Some(callback);
+        self
+    }
+}
+
+impl UiTemplate for PadInput {
+    fn build(&self, builder: &mut UiBuilder) {
+        let value = self.value;
+        let disabled = self.disabled;
+        let on_change = self.on_change;
+        let min = self.min;
+        let max = self.max;
+        let step = self.step;
+        let size = self.size;
+
+        let pad = builder
+            .spawn((
+                Node::default(),
+                Name::new("PadInput"),
+                RelativeCursorPosition::default(),
+                CapturesPointer,
+            ))
+            .id();
+
+        // Move the point to the cursor.
+        let update = move |world: &mut DeferredWorld| {
+            if disabled.get(&*world) {
+                return;
+            }
+            let Some(pos) = cursor_position(world, pad) else {
+                return;
+            };
+            let t = Vec2::new(pos.x, 1. - pos.y).clamp(Vec2::ZERO, Vec2::ONE);
+            let mut new_value = min + (max - min) * t;
+            if shift_pressed(world) {
+                if step.x > 0. {
+                    new_value.x = (new_value.x / step.x).round() * step.x;
+                }
+                if step.y > 0. {
+                    new_value.y = (new_value.y / step.y).round() * step.y;
+                }
+            }
+            let new_value = new_value.clamp(min.min(max), min.max(max));
+            if new_value != value.get(&*world) {
+                if let Some(on_change) = on_change {
+                    world.run_callback(on_change, new_value);
+                }
+            }
+        };
+
+        builder
+            .entity_mut(pad)
+            .styles((
+                style_pad,
+                move |ss: &mut StyleBuilder| {
+                    ss.width(size).height(size);
+                },
+                self.style.clone(),
+            ))
+            .observe(
+                move |mut trigger: Trigger<Pointer<Down>>, mut world: DeferredWorld| {
+                    trigger.propagate(false);
+                    if trigger.event().button == PointerButton::Primary {
+                        update(&mut world);
+                    }
+                },
+            )
+            .observe(
+                move |mut trigger: Trigger<Pointer<Drag>>, mut world: DeferredWorld| {
+                    trigger.propagate(false);
+                    if trigger.event().button == PointerButton::Primary {
+                        update(&mut world);
+                    }
+                },
+            )
+            .create_children(|builder| {
+                builder
+                    .spawn((Node::default(), Name::new("PadInput::Crosshair")))
+                    .styles((style_crosshair_h, |ss: &mut StyleBuilder| {
+                        ss.top(ui::Val::Percent(50.));
+                    }));
+                builder
+                    .spawn((Node::default(), Name::new("PadInput::Crosshair")))
+                    .styles((style_crosshair_v, |ss: &mut StyleBuilder| {
+                        ss.left(ui::Val::Percent(50.));
+                    }));
+                builder
+                    .spawn((Node::default(), Name::new("PadInput::Thumb")))
+                    .style(style_thumb)
+                    .style_dyn(
+                        move |rcx| (value.get(rcx), disabled.get(rcx)),
+                        move |(value, disabled), ss| {
+                            let t = ((value - min) / (max - min)).clamp(Vec2::ZERO, Vec2::ONE);
+                            ss.left(ui::Val::Percent(t.x * 100.))
+                                .top(ui::Val::Percent((1. - t.y) * 100.))
+                                .background_color(if disabled {
+                                    colors::U4
+                                } else {
+                                    colors::FOREGROUND
+                                });
+                        },
+                    );
+            });
+    }
+}
@@ -7,21 +7,24 @@ use bevy::{
 };
 use bevy_mod_stylebuilder::*;
 use bevy_reactor_builder::{
-    CondBuilder, CreateChilden, EntityEffectBuilder, EntityStyleBuilder, UiBuilder, UiTemplate,
+    CreateChilden, EntityEffectBuilder, EntityStyleBuilder, InsertComponentBuilder,
+    InvokeUiTemplate, Slots, SlottedTemplate, UiBuilder, UiTemplate,
 };
 use bevy_reactor_signals::{Callback, RunCallback, Signal};
 
 use crate::{
     animation::{
-        AnimatedBackgroundColor, AnimatedScale, AnimatedTransition, BistableTransitionState,
-        CreateBistableTransition,
+        AnimatedBackgroundColor, AnimatedCondBuilder, AnimatedScale, AnimatedTransition,
+        BistableTransitionState, CreateBistableTransition,
     },
     colors,
+    layering::UiLayer,
     prelude::TabGroup,
+    tab_navigation::InitialFocus,
     typography::text_default,
 };
 
-use super::barrier::Barrier;
+use super::{barrier::Barrier, Portal};
 
 // Dialog background overlay
 fn style_dialog_barrier(ss: &mut StyleBuilder) {
@@ -35,7 +38,6 @@ fn style_dialog_barrier(ss: &mut StyleBuilder) {
         .height(ui::Val::Vh(100.))
         .border(1)
         .border_color(colors::ANIMATION)
-        .z_index(100)
         .background_color(colors::U2.with_alpha(0.0));
 }
 
@@ -63,7 +65,9 @@ fn style_dialog(ss: &mut StyleBuilder) {
 const TRANSITION_DURATION: f32 = 0.3;
 
 /// Displays a modal dialog box. This will display the dialog frame and the backdrop overlay.
-/// Use the dialog header/body/footer controls to get the standard layout.
+/// Use the `"header"`, `"body"` and `"footer"` slots to get the standard layout; these are
+/// wrapped in [`DialogHeader`], [`DialogBody`] and [`DialogFooter`] and placed after any
+/// `children`.
 pub struct Dialog {
     /// The width of the dialog, one of several standard widths.
     pub width: ui::Val,
@@ -75,11 +79,17 @@ pub struct Dialog {
     /// The content of the dialog.
     pub children: Arc<dyn Fn(&mut UiBuilder) + Send + Sync + 'static>,
 
+    /// Content for the header, body and footer slots.
+    pub slots: Slots,
+
     /// Callback called when the dialog's close button is clicked.
     pub on_close: Option<Callback>,
 
     /// Callback called when the dialog has completed it's closing animation.
     pub on_exited: Option<Callback>,
+
+    /// Which element of the dialog receives focus when it opens.
+    pub initial_focus: Option<InitialFocus>,
 }
 
 impl Default for Dialog {
@@ -88,8 +98,10 @@ impl Default for Dialog {
             width: ui::Val::Px(400.0),
             open: Signal::Constant(false),
             children: Arc::new(|_| {}),
+            slots: Slots::default(),
             on_close: None,
             on_exited: None,
+            initial_focus: None,
         }
     }
 }
@@ -129,6 +141,20 @@ impl Dialog {
         self.on_exited = Some(on_exited);
         self
     }
+
+    /// Sets which element of the dialog receives focus when it opens.
+    pub fn initial_focus(mut self, initial_focus: InitialFocus) -> Self {
+        self.initial_focus = Some(initial_focus);
+        self
+    }
+}
+
+impl SlottedTemplate for Dialog {
+    const SLOTS: &'static [&'static str] = &["header", "body", "footer"];
+
+    fn slots_mut(&mut self) -> &mut Slots {
+        &mut self.slots
+    }
 }
 
 impl UiTemplate for Dialog {
@@ -137,6 +163,7 @@ impl UiTemplate for Dialog {
         let on_exited = self.on_exited;
         let state = builder.create_bistable_transition(self.open, TRANSITION_DURATION);
         let width = self.width;
+        let initial_focus = self.initial_focus.clone();
 
         builder.create_effect(move |ve| {
             let state = state.get(ve);
@@ -147,19 +174,20 @@ impl UiTemplate for Dialog {
             }
         });
 
-        let is_shown =
-            builder.create_derived(move |rcx| state.get(rcx) != BistableTransitionState::Exited);
-
         let children = self.children.clone();
-        builder.cond(
-            is_shown,
-            move |builder| {
+        let slots = self.slots.clone();
+        builder.presence(state, move |state, builder| {
+            let children = children.clone();
+            let slots = slots.clone();
+            let initial_focus = initial_focus.clone();
+            builder.invoke(Portal::new().children(move |builder| {
                 let children = children.clone();
-                // Portal::new(
+                let slots = slots.clone();
+                let initial_focus = initial_focus.clone();
                 builder
                     .spawn((Node::default(), Name::new("Dialog::Overlay")))
                     .style(style_dialog_barrier)
-                    .insert(Barrier { on_close })
+                    .insert((Barrier { on_close }, UiLayer::Modal))
                     .effect(
                         move |rcx| {
                             let state = state.get(rcx);
@@ -186,6 +214,10 @@ impl UiTemplate for Dialog {
                                 order: 0,
                                 modal: true,
                             })
+                            .insert_if(initial_focus.is_some(), {
+                                let initial_focus = initial_focus.clone();
+                                move || initial_focus.clone().unwrap()
+                            })
                             .observe(|mut trigger: Trigger<Pointer<Down>>| {
                                 // Prevent clicks from propagating to the barrier and closing
                                 // the dialog.
@@ -215,13 +247,24 @@ impl UiTemplate for Dialog {
                             )
                             .create_children(|builder| {
                                 (children.as_ref())(builder);
+                                build_dialog_slots(builder, &slots);
                             });
-                        // ;
                     });
-                // );
-            },
-            |_| {},
-        );
+            }));
+        });
+    }
+}
+
+/// Build the header, body and footer slots of a dialog, with their standard layout.
+fn build_dialog_slots(builder: &mut UiBuilder, slots: &Slots) {
+    if let Some(header) = slots.get("header").cloned() {
+        builder.invoke(DialogHeader::new().children(move |builder| header(builder)));
+    }
+    if let Some(body) = slots.get("body").cloned() {
+        builder.invoke(DialogBody::new().children(move |builder| body(builder)));
+    }
+    if let Some(footer) = slots.get("footer").cloned() {
+        builder.invoke(DialogFooter::new().children(move |builder| footer(builder)));
     }
 }
 
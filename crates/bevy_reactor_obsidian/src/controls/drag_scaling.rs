@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+
+/// Options which control how pointer drags are converted into value changes for slider-like
+/// controls.
+///
+/// * Holding Shift scales drag movement by `precision_factor`, for fine adjustments.
+/// * Holding Ctrl snaps the value to whole multiples of the control's step, if `ctrl_snap`
+///   is enabled.
+/// * An `exponent` greater than 1 makes scrubbing non-linear: slow movements produce small
+///   changes, while fast movements cover large ranges quickly.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DragScaling {
+    /// Factor applied to drag movement while Shift is held.
+    pub precision_factor: f32,
+
+    /// Whether holding Ctrl snaps the value to whole steps.
+    pub ctrl_snap: bool,
+
+    /// Exponent applied to the per-event drag movement. 1.0 is linear.
+    pub exponent: f32,
+}
+
+impl Default for DragScaling {
+    fn default() -> Self {
+        Self {
+            precision_factor: 0.1,
+            ctrl_snap: true,
+            exponent: 1.0,
+        }
+    }
+}
+
+impl DragScaling {
+    /// Construct a new `DragScaling` with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the factor applied to drag movement while Shift is held.
+    pub fn precision_factor(mut self, factor: f32) -> Self {
+        self.precision_factor = factor;
+        self
+    }
+
+    /// Set whether holding Ctrl snaps the value to whole steps.
+    pub fn ctrl_snap(mut self, ctrl_snap: bool) -> Self {
+        self.ctrl_snap = ctrl_snap;
+        self
+    }
+
+    /// Set the exponent used for non-linear scrubbing.
+    pub fn exponent(mut self, exponent: f32) -> Self {
+        self.exponent = exponent;
+        self
+    }
+
+    /// Scale a drag movement, in pixels, based on the current modifier keys.
+    pub fn scale_delta(&self, delta: f32, keys: &ButtonInput<KeyCode>) -> f32 {
+        let delta = delta.signum() * delta.abs().powf(self.exponent);
+        if keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+            delta * self.precision_factor
+        } else {
+            delta
+        }
+    }
+
+    /// Snap a value to a multiple of `step` if Ctrl is held. A `step` of zero disables snapping.
+    pub fn snap(&self, value: f32, step: f32, keys: &ButtonInput<KeyCode>) -> f32 {
+        if self.ctrl_snap
+            && step > 0.
+            && keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight])
+        {
+            (value / step).round() * step
+        } else {
+            value
+        }
+    }
+}
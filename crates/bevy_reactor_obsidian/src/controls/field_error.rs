@@ -0,0 +1,66 @@
+use bevy::{prelude::*, ui};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{
+    CondBuilder, CreateChilden, EntityStyleBuilder, TextBuilder, UiBuilder, UiTemplate,
+};
+use bevy_reactor_signals::{IntoSignal, Rcx, Signal};
+
+use crate::{colors, typography};
+
+fn style_field_error(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .font_size(12)
+        .color(colors::DESTRUCTIVE_ACC);
+}
+
+/// Displays the validation error message for a form field. Nothing is rendered when there
+/// is no error.
+pub struct FieldError {
+    /// The error message to display.
+    pub error: Signal<Option<String>>,
+
+    /// Additional styles to be applied to the message.
+    pub style: StyleHandle,
+}
+
+impl FieldError {
+    /// Construct a new `FieldError` from an error signal, such as the one returned by
+    /// [`FormField::create_error_signal`](crate::form::FormField::create_error_signal).
+    pub fn new(error: impl IntoSignal<Option<String>>) -> Self {
+        Self {
+            error: error.into_signal(),
+            style: StyleHandle::default(),
+        }
+    }
+
+    /// Set additional styles to be applied to the message.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl UiTemplate for FieldError {
+    fn build(&self, builder: &mut UiBuilder) {
+        let error = self.error.clone();
+        let style = self.style.clone();
+        builder.cond(
+            {
+                let error = error.clone();
+                move |rcx: &Rcx| error.map(rcx, Option::is_some)
+            },
+            move |builder| {
+                let error = error.clone();
+                builder
+                    .spawn((Node::default(), Name::new("FieldError")))
+                    .styles((typography::text_default, style_field_error, style.clone()))
+                    .create_children(|builder| {
+                        builder.text_computed(move |rcx| error.get_clone(rcx).unwrap_or_default());
+                    });
+            },
+            |_| {},
+        );
+    }
+}
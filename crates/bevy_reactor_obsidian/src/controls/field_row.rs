@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use bevy::{prelude::*, ui, window::SystemCursorIcon, winit::cursor::CursorIcon};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{CreateChilden, EntityStyleBuilder, TextBuilder, UiBuilder, UiTemplate};
+
+use crate::{
+    colors,
+    cursor::StyleBuilderCursor,
+    labelling::{DescribedBy, LabelFor, LabelledBy},
+    tab_navigation::TabIndex,
+    typography,
+};
+
+fn style_field_row(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Grid)
+        .grid_template_columns(vec![
+            ui::RepeatedGridTrack::auto(1),
+            ui::RepeatedGridTrack::flex(1, 1.),
+        ])
+        .align_items(ui::AlignItems::Center)
+        .column_gap(8)
+        .row_gap(2);
+}
+
+fn style_field_label(ss: &mut StyleBuilder) {
+    ss.color(colors::DIM)
+        .cursor(CursorIcon::System(SystemCursorIcon::Default));
+}
+
+fn style_field_control(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center);
+}
+
+fn style_field_description(ss: &mut StyleBuilder) {
+    ss.grid_column(ui::GridPlacement::start(2))
+        .font_size(12)
+        .color(colors::DIM);
+}
+
+/// Search the descendants of `root`, depth first, for the first focusable entity.
+fn find_focusable(world: &World, root: Entity) -> Option<Entity> {
+    let children = world.get::<Children>(root)?;
+    for child in children.iter() {
+        if world.get::<TabIndex>(*child).is_some() {
+            return Some(*child);
+        }
+        if let Some(found) = find_focusable(world, *child) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+/// A labelled row in a form: a label, followed by a control, and optionally a line of help
+/// text beneath the control. Clicking the label focuses the control (and toggles it, for
+/// checkboxes), and the label and help text are reported to assistive technology as the
+/// control's name and description. The control is the first focusable entity built by the
+/// `control` function.
+pub struct FieldRow {
+    /// The label text.
+    pub label: String,
+
+    /// Optional help text describing the field.
+    pub description: Option<String>,
+
+    /// Builds the control.
+    pub control: Arc<dyn Fn(&mut UiBuilder) + Send + Sync>,
+
+    /// Additional styles to be applied to the row.
+    pub style: StyleHandle,
+}
+
+impl FieldRow {
+    /// Construct a new `FieldRow` with the given label.
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            description: None,
+            control: Arc::new(|_builder| {}),
+            style: StyleHandle::default(),
+        }
+    }
+
+    /// Set the help text describing the field.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the function which builds the control.
+    pub fn control<V: 'static + Send + Sync + Fn(&mut UiBuilder)>(mut self, control: V) -> Self {
+        self.control = Arc::new(control);
+        self
+    }
+
+    /// Set additional styles to be applied to the row.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl UiTemplate for FieldRow {
+    fn build(&self, builder: &mut UiBuilder) {
+        let mut label = Entity::PLACEHOLDER;
+        let mut control = Entity::PLACEHOLDER;
+        let mut description = None;
+
+        builder
+            .spawn((Node::default(), Name::new("FieldRow")))
+            .styles((
+                typography::text_default,
+                style_field_row,
+                self.style.clone(),
+            ))
+            .create_children(|builder| {
+                label = builder
+                    .spawn((Node::default(), Name::new("FieldRow::Label")))
+                    .style(style_field_label)
+                    .create_children(|builder| {
+                        builder.text(self.label.clone());
+                    })
+                    .id();
+                control = builder
+                    .spawn((Node::default(), Name::new("FieldRow::Control")))
+                    .style(style_field_control)
+                    .create_children(|builder| {
+                        (self.control.as_ref())(builder);
+                    })
+                    .id();
+                if let Some(text) = &self.description {
+                    description = Some(
+                        builder
+                            .spawn((Node::default(), Name::new("FieldRow::Description")))
+                            .style(style_field_description)
+                            .create_children(|builder| {
+                                builder.text(text.clone());
+                            })
+                            .id(),
+                    );
+                }
+            });
+
+        let world = builder.world_mut();
+        let Some(target) = find_focusable(world, control) else {
+            return;
+        };
+        world.entity_mut(label).insert(LabelFor(target));
+        let mut target = world.entity_mut(target);
+        target.insert(LabelledBy(label));
+        if let Some(description) = description {
+            target.insert(DescribedBy(description));
+        }
+    }
+}
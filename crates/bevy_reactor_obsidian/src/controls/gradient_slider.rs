@@ -5,7 +5,7 @@ use bevy_reactor_builder::{
 };
 use bevy_reactor_signals::{Callback, IntoSignal, RunCallback, Signal};
 
-use crate::materials::GradientRectMaterial;
+use crate::materials::{update_ui_material, CreateUiMaterial, GradientRectMaterial};
 
 use super::{core_slider::ValueChange, CoreSlider};
 
@@ -228,29 +228,20 @@ impl UiTemplate for GradientSlider {
             })
         };
 
-        let mut gradient_material_assets = builder
-            .world_mut()
-            .get_resource_mut::<Assets<GradientRectMaterial>>()
-            .unwrap();
-        let gradient_material = gradient_material_assets.add(GradientRectMaterial {
+        let gradient_material = builder.create_ui_material(GradientRectMaterial {
             color_stops: [Srgba::default().to_vec4(); 8],
             num_color_stops: IVec4::new(2, 0, 0, 0),
             cap_size: THUMB_WIDTH * 0.5,
         });
 
         // Effect to update the material handle.
-        builder.create_effect({
-            let material = gradient_material.clone();
-            move |rcx| {
-                let (num_color_stops, color_stops) = color_stops.get(rcx);
-                let mut ui_materials = rcx
-                    .world_mut()
-                    .get_resource_mut::<Assets<GradientRectMaterial>>()
-                    .unwrap();
-                let material = ui_materials.get_mut(material.id()).unwrap();
+        let material_id = gradient_material.id();
+        builder.create_effect(move |rcx| {
+            let (num_color_stops, color_stops) = color_stops.get(rcx);
+            update_ui_material(rcx.world_mut(), material_id, |material| {
                 material.num_color_stops.x = num_color_stops as i32;
                 material.color_stops = color_stops;
-            }
+            });
         });
 
         builder
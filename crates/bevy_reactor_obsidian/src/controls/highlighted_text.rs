@@ -0,0 +1,123 @@
+use std::ops::Range;
+
+use bevy::prelude::*;
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{EntityStyleBuilder, UiBuilder, UiTemplate};
+use bevy_reactor_signals::{IntoSignal, Signal};
+
+use crate::{
+    fuzzy::{fuzzy_match, highlight_segments},
+    typography,
+};
+
+/// Displays a string with some of its characters emphasized, typically the characters which
+/// matched a search query. The text inherits its font from the enclosing element.
+pub struct HighlightedText {
+    /// The text to display.
+    pub text: Signal<String>,
+
+    /// Query which is fuzzy-matched against the text to determine which characters to
+    /// emphasize. Ignored if explicit ranges are given.
+    pub query: Signal<String>,
+
+    /// Explicit byte ranges of the text to emphasize, for example from a previous call to
+    /// [`fuzzy_match`].
+    pub ranges: Option<Vec<Range<usize>>>,
+
+    /// Style applied to the emphasized characters.
+    pub highlight_style: StyleHandle,
+
+    /// Additional styles to be applied to the text.
+    pub style: StyleHandle,
+}
+
+impl Default for HighlightedText {
+    fn default() -> Self {
+        Self {
+            text: Signal::Constant(String::new()),
+            query: Signal::Constant(String::new()),
+            ranges: None,
+            highlight_style: typography::text_highlight.into_handle(),
+            style: StyleHandle::default(),
+        }
+    }
+}
+
+impl HighlightedText {
+    /// Create a new highlighted text element.
+    pub fn new(text: impl IntoSignal<String>) -> Self {
+        Self {
+            text: text.into_signal(),
+            ..default()
+        }
+    }
+
+    /// Set the query to match against the text.
+    pub fn query(mut self, query: impl IntoSignal<String>) -> Self {
+        self.query = query.into_signal();
+        self
+    }
+
+    /// Set explicit ranges to emphasize, instead of matching a query.
+    pub fn ranges(mut self, ranges: Vec<Range<usize>>) -> Self {
+        self.ranges = Some(ranges);
+        self
+    }
+
+    /// Set the style of the emphasized characters.
+    pub fn highlight_style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.highlight_style = style.into_handle();
+        self
+    }
+
+    /// Set the additional styles for the text.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl UiTemplate for HighlightedText {
+    fn build(&self, builder: &mut UiBuilder) {
+        let text = self.text.clone();
+        let query = self.query.clone();
+        let ranges = self.ranges.clone();
+        let highlight_style = self.highlight_style.clone();
+
+        let root = builder
+            .spawn((
+                Name::new("HighlightedText"),
+                Text::default(),
+                TextLayout::default(),
+            ))
+            .styles(self.style.clone())
+            .insert(UseInheritedTextStyles)
+            .id();
+
+        // Text spans must be direct children of the text entity, so rebuild them all whenever
+        // the text or the query changes.
+        builder.create_effect(move |ecx| {
+            let text = text.get_clone(ecx);
+            let ranges = match &ranges {
+                Some(ranges) => ranges.clone(),
+                None => {
+                    let query = query.get_clone(ecx);
+                    fuzzy_match(&query, &text)
+                        .map(|m| m.ranges)
+                        .unwrap_or_default()
+                }
+            };
+            let mut entt = ecx.world_mut().entity_mut(root);
+            entt.despawn_descendants();
+            entt.with_children(|parent| {
+                for (segment, highlighted) in highlight_segments(&text, &ranges) {
+                    let mut span = parent.spawn(TextSpan::new(segment));
+                    if highlighted {
+                        span.styles(highlight_style.clone());
+                    }
+                    span.insert(UseInheritedTextStyles);
+                }
+            });
+        });
+    }
+}
@@ -1,9 +1,11 @@
-use super::{Button, Icon};
+use super::{AsyncAction, Button, Icon};
 use crate::{colors, prelude::RoundedCorners, size::Size};
 use bevy::prelude::*;
 use bevy_mod_stylebuilder::*;
 use bevy_reactor_builder::{InvokeUiTemplate, UiBuilder, UiTemplate};
 use bevy_reactor_signals::{Callback, IntoSignal, Signal};
+use std::future::Future;
+use std::sync::Arc;
 
 /// A widget which displays a button containing an icon.
 #[derive(Default)]
@@ -20,6 +22,9 @@ pub struct IconButton {
     /// Whether the button is disabled.
     pub disabled: Signal<bool>,
 
+    /// Whether the button is in a loading state.
+    pub loading: Signal<bool>,
+
     /// Which corners to render rounded.
     pub corners: RoundedCorners,
 
@@ -29,6 +34,9 @@ pub struct IconButton {
     /// Callback called when clicked
     pub on_click: Option<Callback>,
 
+    /// Asynchronous action run when clicked. Takes precedence over `on_click`.
+    pub on_click_async: Option<AsyncAction>,
+
     /// The tab index of the button (default 0).
     pub tab_index: i32,
 
@@ -72,6 +80,12 @@ impl IconButton {
         self
     }
 
+    /// Set the button loading state.
+    pub fn loading(mut self, loading: impl IntoSignal<bool>) -> Self {
+        self.loading = loading.into_signal();
+        self
+    }
+
     /// Set the additional styles for the button.
     pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
         self.style = style.into_handle();
@@ -90,6 +104,17 @@ impl IconButton {
         self
     }
 
+    /// Set an asynchronous action to run when clicked. The button is put into the loading
+    /// state until the returned future resolves.
+    pub fn on_click_async<F, Fut>(mut self, action: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.on_click_async = Some(Arc::new(move || Box::pin(action())));
+        self
+    }
+
     /// Set the tab index of the button.
     pub fn tab_index(mut self, tab_index: i32) -> Self {
         self.tab_index = tab_index;
@@ -125,7 +150,9 @@ impl UiTemplate for IconButton {
                 },
                 self.style.clone(),
             )),
+            loading: self.loading,
             on_click: self.on_click,
+            on_click_async: self.on_click_async.clone(),
             tab_index: self.tab_index,
             autofocus: self.autofocus,
             minimal: self.minimal,
@@ -0,0 +1,313 @@
+use std::{future::Future, pin::Pin, sync::Arc};
+
+use bevy::{
+    ecs::world::DeferredWorld,
+    prelude::*,
+    tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task},
+    ui,
+};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{
+    CreateChilden, EntityStyleBuilder, ForEachBuilder, InvokeUiTemplate, SwitchBuilder,
+    TextBuilder, UiBuilder, UiTemplate,
+};
+use bevy_reactor_signals::Mutable;
+
+use crate::{colors, scrolling::CreateScrollSignals, typography};
+
+use super::{Button, ScrollView, Spinner};
+
+fn style_infinite_list(ss: &mut StyleBuilder) {
+    ss.background_color(colors::U1)
+        .border_radius(5.0)
+        .padding(3);
+}
+
+fn style_status_row(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .column_gap(6)
+        .left(0)
+        .right(0)
+        .padding((4, 0));
+}
+
+fn style_row(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .left(0)
+        .right(0)
+        .padding((4, 0))
+        .overflow(ui::OverflowAxis::Clip);
+}
+
+/// Asynchronous function which loads the page of items starting at the given offset. An
+/// empty page means that there are no more items.
+pub type PageLoader<T> = Arc<
+    dyn Fn(usize) -> Pin<Box<dyn Future<Output = Result<Vec<T>, String>> + Send>> + Send + Sync,
+>;
+
+/// Loading state of an [`InfiniteList`].
+#[derive(Clone, Debug, Default, PartialEq)]
+enum LoadState {
+    /// Waiting for the user to scroll near the end.
+    #[default]
+    Idle,
+    /// A page is being loaded.
+    Loading,
+    /// The last load failed, with the given message.
+    Failed(String),
+    /// The loader returned an empty page.
+    Exhausted,
+}
+
+/// Component which holds the task loading a page of an [`InfiniteList`]. The task resolves to
+/// a function which applies the page to the list.
+#[derive(Component)]
+pub(crate) struct InfiniteListTask(Task<Box<dyn FnOnce(&mut World) + Send>>);
+
+/// A scrolling list which loads more items when scrolled near the end, for long or remote
+/// collections such as profiler entries or asset registries. Pages are appended to the
+/// `items` mutable, which the caller owns and may also modify directly; a loading row is shown
+/// while a page is in flight, and a retry button if it fails.
+///
+/// Rows all have the same height, and only the rows scrolled into view are built.
+pub struct InfiniteList<T: Clone + PartialEq + Send + Sync + 'static> {
+    /// The items loaded so far.
+    pub items: Mutable<Vec<T>>,
+
+    /// Function which loads a page of items.
+    pub loader: PageLoader<T>,
+
+    /// Function which builds the content of the row for an item.
+    pub row: Arc<dyn Fn(&T, &mut UiBuilder) + Send + Sync>,
+
+    /// Height of each row.
+    pub row_height: f32,
+
+    /// How close to the end of the list, in pixels, scrolling must get to load the next page.
+    pub threshold: f32,
+
+    /// Additional styles to be applied to the list.
+    pub style: StyleHandle,
+}
+
+impl<T: Clone + PartialEq + Send + Sync + 'static> InfiniteList<T> {
+    /// Create a new infinite list, which appends the pages returned by `loader` to `items`.
+    pub fn new<F, Fut>(items: Mutable<Vec<T>>, loader: F) -> Self
+    where
+        F: Fn(usize) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Vec<T>, String>> + Send + 'static,
+    {
+        Self {
+            items,
+            loader: Arc::new(move |offset| Box::pin(loader(offset))),
+            row: Arc::new(|_, _| {}),
+            row_height: 20.,
+            threshold: 100.,
+            style: StyleHandle::default(),
+        }
+    }
+
+    /// Set the function which builds the content of each row.
+    pub fn row<F: Fn(&T, &mut UiBuilder) + Send + Sync + 'static>(mut self, row: F) -> Self {
+        self.row = Arc::new(row);
+        self
+    }
+
+    /// Set the height of each row.
+    pub fn row_height(mut self, height: f32) -> Self {
+        self.row_height = height;
+        self
+    }
+
+    /// Set how close to the end scrolling must get to load the next page.
+    pub fn threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Set additional styles to be applied to the list.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl<T: Clone + PartialEq + Send + Sync + 'static> UiTemplate for InfiniteList<T> {
+    fn build(&self, builder: &mut UiBuilder) {
+        let items = self.items;
+        let loader = self.loader.clone();
+        let row = self.row.clone();
+        let row_height = self.row_height;
+        let threshold = self.threshold;
+        let state = builder.create_mutable(LoadState::Idle);
+
+        let view_id = builder.spawn(Node::default()).id();
+        let scroll = builder.create_scroll_signals(view_id);
+
+        // Load the next page when the end of the list is near. This also loads the first page,
+        // and keeps loading while the items don't fill the view.
+        builder.create_effect(move |ecx| {
+            if state.get_clone(ecx) != LoadState::Idle {
+                return;
+            }
+            let remaining = scroll.content_size.get(ecx).y
+                - scroll.visible_size.get(ecx).y
+                - scroll.offset.get(ecx).y;
+            if remaining > threshold {
+                return;
+            }
+            let offset = items.signal().map(ecx, Vec::len);
+            let page = loader(offset);
+            let task = AsyncComputeTaskPool::get().spawn(async move {
+                let result = page.await;
+                Box::new(move |world: &mut World| apply_page(world, items, state, result))
+                    as Box<dyn FnOnce(&mut World) + Send>
+            });
+            let world = ecx.world_mut();
+            state.set_clone(world, LoadState::Loading);
+            world.spawn((InfiniteListTask(task), Name::new("InfiniteList::Task")));
+        });
+
+        let on_retry = builder.create_callback(move |_: In<()>, mut world: DeferredWorld| {
+            state.set_clone(&mut world, LoadState::Idle);
+        });
+
+        // Rows of the list, followed by a row showing the loading state, if any.
+        let row_count = builder.create_derived(move |rcx| {
+            let status = !matches!(state.get_clone(rcx), LoadState::Idle | LoadState::Exhausted);
+            items.signal().map(rcx, Vec::len) + status as usize
+        });
+
+        builder.invoke(
+            ScrollView::new()
+                .entity(view_id)
+                .style((style_infinite_list, self.style.clone()))
+                .content_style(typography::text_default)
+                .scroll_enable_y(true)
+                .children(move |builder| {
+                    let row = row.clone();
+                    builder
+                        .spawn((Node::default(), Name::new("InfiniteList")))
+                        .style_dyn(
+                            move |rcx| row_count.get(rcx),
+                            move |count, sb| {
+                                sb.width(ui::Val::Percent(100.))
+                                    .height(count as f32 * row_height);
+                            },
+                        )
+                        .create_children(|builder| {
+                            builder.for_each(
+                                move |rcx| {
+                                    let scroll_top = scroll.offset.get(rcx).y;
+                                    let visible = scroll.visible_size.get(rcx).y;
+                                    let first = (scroll_top / row_height).floor() as usize;
+                                    let last =
+                                        ((scroll_top + visible) / row_height).ceil() as usize;
+                                    items
+                                        .signal()
+                                        .get_clone(rcx)
+                                        .into_iter()
+                                        .enumerate()
+                                        .skip(first)
+                                        .take(last + 1 - first)
+                                },
+                                move |(index, item), builder| {
+                                    let top = *index as f32 * row_height;
+                                    builder
+                                        .spawn((Node::default(), Name::new("InfiniteList::Row")))
+                                        .styles((style_row, move |sb: &mut StyleBuilder| {
+                                            sb.top(top).height(row_height);
+                                        }))
+                                        .create_children(|builder| row(item, builder));
+                                },
+                                |_| {},
+                            );
+                            builder
+                                .spawn((Node::default(), Name::new("InfiniteList::Status")))
+                                .style(style_status_row)
+                                .style_dyn(
+                                    move |rcx| items.signal().map(rcx, Vec::len),
+                                    move |count, sb| {
+                                        sb.top(count as f32 * row_height).height(row_height);
+                                    },
+                                )
+                                .create_children(|builder| {
+                                    builder.switch(
+                                        move |rcx| state.signal().get_clone(rcx),
+                                        |cases| {
+                                            cases
+                                                .case(LoadState::Loading, |builder| {
+                                                    builder.invoke(Spinner::new());
+                                                    builder.text("Loading...");
+                                                })
+                                                .case_when(
+                                                    |state| matches!(state, LoadState::Failed(_)),
+                                                    move |builder| {
+                                                        builder.text_computed(
+                                                            move |rcx| match state
+                                                                .signal()
+                                                                .get_clone(rcx)
+                                                            {
+                                                                LoadState::Failed(err) => err,
+                                                                _ => String::new(),
+                                                            },
+                                                        );
+                                                        builder.invoke(
+                                                            Button::new()
+                                                                .labeled("Retry")
+                                                                .on_click(on_retry),
+                                                        );
+                                                    },
+                                                );
+                                        },
+                                    );
+                                });
+                        });
+                }),
+        );
+    }
+}
+
+/// Append a loaded page to the list, or record the failure.
+fn apply_page<T: Clone + Send + Sync + 'static>(
+    world: &mut World,
+    items: Mutable<Vec<T>>,
+    state: Mutable<LoadState>,
+    result: Result<Vec<T>, String>,
+) {
+    // The list may have been despawned while the page was loading.
+    if world.get_entity(state.id()).is_err() || world.get_entity(items.id()).is_err() {
+        return;
+    }
+    match result {
+        Ok(page) if page.is_empty() => state.set_clone(world, LoadState::Exhausted),
+        Ok(page) => {
+            let mut list = items.get_clone(world);
+            list.extend(page);
+            items.set_clone(world, list);
+            state.set_clone(world, LoadState::Idle);
+        }
+        Err(err) => state.set_clone(world, LoadState::Failed(err)),
+    }
+}
+
+/// Poll the tasks loading pages of infinite lists, and apply the pages which have arrived.
+pub(crate) fn poll_infinite_list_tasks(world: &mut World) {
+    let mut finished = Vec::new();
+    let mut q_tasks = world.query::<(Entity, &mut InfiniteListTask)>();
+    for (entity, mut task) in q_tasks.iter_mut(world) {
+        if let Some(apply) = block_on(future::poll_once(&mut task.0)) {
+            finished.push((entity, apply));
+        }
+    }
+    for (entity, apply) in finished {
+        world.despawn(entity);
+        apply(world);
+    }
+}
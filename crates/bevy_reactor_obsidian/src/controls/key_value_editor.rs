@@ -0,0 +1,333 @@
+use bevy::{ecs::world::DeferredWorld, prelude::*, ui, utils::HashSet};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{
+    CreateChilden, EntityStyleBuilder, ForEachBuilder, InvokeUiTemplate, TextBuilder, UiBuilder,
+    UiTemplate,
+};
+use bevy_reactor_signals::{Callback, IntoSignal, Mutable, Rcx, RunCallback, Signal};
+
+use crate::{colors, typography};
+
+use super::{Button, FieldError, IconButton, TextInput};
+
+fn style_key_value_editor(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch)
+        .row_gap(4);
+}
+
+fn style_key_value_grid(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Grid)
+        .grid_template_columns(vec![
+            ui::RepeatedGridTrack::flex(1, 1.),
+            ui::RepeatedGridTrack::flex(1, 2.),
+            ui::RepeatedGridTrack::auto(1),
+        ])
+        .align_items(ui::AlignItems::Center)
+        .column_gap(4)
+        .row_gap(2);
+}
+
+fn style_column_heading(ss: &mut StyleBuilder) {
+    ss.color(colors::DIM).font_size(12);
+}
+
+fn style_key_cell(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .min_width(0)
+        .border_radius(5.0);
+}
+
+fn style_duplicate_key(ss: &mut StyleBuilder) {
+    ss.outline_color(colors::DESTRUCTIVE_ACC).outline_width(1);
+}
+
+fn style_cell_input(ss: &mut StyleBuilder) {
+    ss.flex_grow(1.).min_width(0);
+}
+
+/// One row of the editor. Rows have ids so that they are not rebuilt while they are edited.
+#[derive(Clone, Debug, PartialEq)]
+struct KeyValueRow {
+    id: u64,
+    key: String,
+    value: String,
+}
+
+/// The entries of a set of rows, in order.
+fn row_entries(rows: &[KeyValueRow]) -> Vec<(String, String)> {
+    rows.iter()
+        .map(|row| (row.key.clone(), row.value.clone()))
+        .collect()
+}
+
+/// The keys which are empty or used by more than one row.
+fn invalid_keys(rows: &[KeyValueRow]) -> HashSet<String> {
+    let mut seen = HashSet::new();
+    let mut invalid = HashSet::new();
+    for row in rows {
+        if row.key.is_empty() || !seen.insert(row.key.as_str()) {
+            invalid.insert(row.key.clone());
+        }
+    }
+    invalid
+}
+
+/// Editor for a string table, such as a `HashMap<String, String>` field or custom metadata: a
+/// grid of editable keys and values, with buttons to add and remove rows.
+///
+/// Edits are reported through `on_change` with every entry, in display order, but only while
+/// the table is valid: each key must be non-empty and unique. While it is not, the offending
+/// keys are outlined and an error is shown below the grid.
+pub struct KeyValueEditor {
+    /// The entries to edit.
+    pub entries: Signal<Vec<(String, String)>>,
+
+    /// Label shown above the key column.
+    pub key_label: String,
+
+    /// Label shown above the value column.
+    pub value_label: String,
+
+    /// Callback called with all of the entries when a valid edit is made.
+    pub on_change: Option<Callback<Vec<(String, String)>>>,
+
+    /// Additional styles to be applied to the editor.
+    pub style: StyleHandle,
+}
+
+impl KeyValueEditor {
+    /// Create a new editor for the given entries.
+    pub fn new(entries: impl IntoSignal<Vec<(String, String)>>) -> Self {
+        Self {
+            entries: entries.into_signal(),
+            key_label: "Key".to_string(),
+            value_label: "Value".to_string(),
+            on_change: None,
+            style: StyleHandle::default(),
+        }
+    }
+
+    /// Set the labels shown above the key and value columns.
+    pub fn labels(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.key_label = key.into();
+        self.value_label = value.into();
+        self
+    }
+
+    /// Set the callback called when a valid edit is made.
+    pub fn on_change(mut self, callback: Callback<Vec<(String, String)>>) -> Self {
+        self.on_change = Some(callback);
+        self
+    }
+
+    /// Set additional styles to be applied to the editor.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl UiTemplate for KeyValueEditor {
+    fn build(&self, builder: &mut UiBuilder) {
+        let entries = self.entries.clone();
+        let on_change = self.on_change;
+        let rows = builder.create_mutable::<Vec<KeyValueRow>>(Vec::new());
+        let next_id = builder.create_mutable::<u64>(0);
+
+        // Replace the rows when the entries change from outside, unless they already match,
+        // as they do after an edit has been reported.
+        builder.create_effect(move |ecx| {
+            let entries = entries.get_clone(ecx);
+            if rows.signal().map(ecx, |rows| row_entries(rows) == entries) {
+                return;
+            }
+            let first_id = next_id.get(ecx);
+            let new_rows: Vec<KeyValueRow> = entries
+                .into_iter()
+                .zip(first_id..)
+                .map(|((key, value), id)| KeyValueRow { id, key, value })
+                .collect();
+            let world = ecx.world_mut();
+            next_id.set(world, first_id + new_rows.len() as u64);
+            rows.set_clone(world, new_rows);
+        });
+
+        // Apply an edit to the rows, and report the entries if they are valid.
+        let update = builder.create_callback(
+            move |edit: In<Vec<KeyValueRow>>, mut world: DeferredWorld| {
+                let edit = edit.0;
+                let valid = invalid_keys(&edit).is_empty();
+                let changed = valid.then(|| row_entries(&edit));
+                rows.set_clone(&mut world, edit);
+                if let (Some(on_change), Some(entries)) = (on_change, changed) {
+                    world.run_callback(on_change, entries);
+                }
+            },
+        );
+
+        let on_add = builder.create_callback(move |_: In<()>, mut world: DeferredWorld| {
+            let id = next_id.get(&world);
+            next_id.set(&mut world, id + 1);
+            let mut edit = rows.get_clone(&mut world);
+            edit.push(KeyValueRow {
+                id,
+                key: String::new(),
+                value: String::new(),
+            });
+            world.run_callback(update, edit);
+        });
+
+        let invalid =
+            builder.create_derived(move |rcx| rows.signal().map(rcx, |r| invalid_keys(r)));
+        let error = builder.create_derived({
+            let invalid = invalid.clone();
+            move |rcx| {
+                invalid.map(rcx, |invalid| match invalid.len() {
+                    0 => None,
+                    _ if invalid.contains("") => Some("Keys must not be empty".to_string()),
+                    _ => {
+                        let mut keys: Vec<&str> = invalid.iter().map(String::as_str).collect();
+                        keys.sort();
+                        Some(format!("Duplicate keys: {}", keys.join(", ")))
+                    }
+                })
+            }
+        });
+
+        let key_label = self.key_label.clone();
+        let value_label = self.value_label.clone();
+        builder
+            .spawn((Node::default(), Name::new("KeyValueEditor")))
+            .styles((
+                typography::text_default,
+                style_key_value_editor,
+                self.style.clone(),
+            ))
+            .create_children(|builder| {
+                builder
+                    .spawn((Node::default(), Name::new("KeyValueEditor::Grid")))
+                    .style(style_key_value_grid)
+                    .create_children(|builder| {
+                        builder
+                            .spawn(Node::default())
+                            .style(style_column_heading)
+                            .create_children(|builder| {
+                                builder.text(key_label);
+                            });
+                        builder
+                            .spawn(Node::default())
+                            .style(style_column_heading)
+                            .create_children(|builder| {
+                                builder.text(value_label);
+                            });
+                        builder.spawn(Node::default());
+                        builder.for_each(
+                            move |rcx| {
+                                rows.signal()
+                                    .map(rcx, |rows| {
+                                        rows.iter().map(|row| row.id).collect::<Vec<_>>()
+                                    })
+                                    .into_iter()
+                            },
+                            move |id, builder| {
+                                build_row(builder, *id, rows, invalid.clone(), update);
+                            },
+                            |_| {},
+                        );
+                    });
+                builder.invoke(FieldError::new(error));
+                builder.invoke(Button::new().labeled("Add").on_click(on_add));
+            });
+    }
+}
+
+/// Build the key and value inputs, and the remove button, for one row.
+fn build_row(
+    builder: &mut UiBuilder,
+    id: u64,
+    rows: Mutable<Vec<KeyValueRow>>,
+    invalid: Signal<HashSet<String>>,
+    update: Callback<Vec<KeyValueRow>>,
+) {
+    let field = move |rcx: &mut Rcx, key: bool| {
+        rows.signal().map(rcx, |rows| {
+            rows.iter()
+                .find(|row| row.id == id)
+                .map(|row| {
+                    if key {
+                        row.key.clone()
+                    } else {
+                        row.value.clone()
+                    }
+                })
+                .unwrap_or_default()
+        })
+    };
+    let key = builder.create_derived(move |rcx| field(rcx, true));
+    let value = builder.create_derived(move |rcx| field(rcx, false));
+    let duplicate = builder.create_derived({
+        let key = key.clone();
+        move |rcx| {
+            let key = key.get_clone(rcx);
+            invalid.map(rcx, |invalid| invalid.contains(&key))
+        }
+    });
+
+    let edit = move |world: &mut DeferredWorld, change: &dyn Fn(&mut KeyValueRow)| {
+        let mut edit = rows.get_clone(world);
+        if let Some(row) = edit.iter_mut().find(|row| row.id == id) {
+            change(row);
+        }
+        world.run_callback(update, edit);
+    };
+    let on_key = builder.create_callback(move |text: In<String>, mut world: DeferredWorld| {
+        edit(&mut world, &|row| row.key.clone_from(&text));
+    });
+    let on_value = builder.create_callback(move |text: In<String>, mut world: DeferredWorld| {
+        edit(&mut world, &|row| row.value.clone_from(&text));
+    });
+    let on_remove = builder.create_callback(move |_: In<()>, mut world: DeferredWorld| {
+        let mut edit = rows.get_clone(&mut world);
+        edit.retain(|row| row.id != id);
+        world.run_callback(update, edit);
+    });
+
+    // The key input is wrapped so that it can be outlined while its key is invalid.
+    builder
+        .spawn((Node::default(), Name::new("KeyValueEditor::Key")))
+        .style(style_key_cell)
+        .style_dyn(
+            move |rcx| duplicate.get(rcx),
+            |duplicate, sb| {
+                if duplicate {
+                    style_duplicate_key(sb);
+                } else {
+                    sb.outline_width(0);
+                }
+            },
+        )
+        .create_children(|builder| {
+            builder.invoke(
+                TextInput::new()
+                    .value(key)
+                    .placeholder("key")
+                    .style(style_cell_input)
+                    .on_change(on_key),
+            );
+        });
+    builder.invoke(
+        TextInput::new()
+            .value(value)
+            .placeholder("value")
+            .style(style_cell_input)
+            .on_change(on_value),
+    );
+    builder.invoke(
+        IconButton::new("embedded://bevy_reactor_obsidian/assets/icons/remove.png")
+            .minimal(true)
+            .on_click(on_remove),
+    );
+}
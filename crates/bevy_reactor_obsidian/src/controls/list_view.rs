@@ -0,0 +1,185 @@
+use std::sync::Arc;
+
+use bevy::{ecs::world::DeferredWorld, prelude::*, ui};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{
+    CreateChilden, EntityStyleBuilder, ForEachBuilder, InvokeUiTemplate, UiBuilder, UiTemplate,
+};
+use bevy_reactor_signals::{Callback, IntoSignal, Mutable, RunCallback, Signal};
+
+use crate::{
+    colors,
+    selection::{SelectModifiers, SelectionModel},
+};
+
+use super::ScrollView;
+
+fn style_list_view(ss: &mut StyleBuilder) {
+    ss.background_color(colors::U1)
+        .border_radius(5.0)
+        .padding(3);
+}
+
+fn style_list_view_inner(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch);
+}
+
+fn style_list_row(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .padding((4, 1))
+        .border_radius(3.0)
+        .color(colors::FOREGROUND);
+}
+
+/// A scrollable list of items. Clicking a row selects it; if the selection allows multiple
+/// items, shift-click selects a range and ctrl-click toggles individual rows.
+pub struct ListView<T: Clone + PartialEq + Send + Sync + 'static> {
+    /// The items to display.
+    pub items: Signal<Vec<T>>,
+
+    /// Function which builds the content of the row for an item.
+    pub row: Arc<dyn Fn(&T, &mut UiBuilder) + Send + Sync>,
+
+    /// The selection. If not set, the list keeps its own single selection.
+    pub selection: Option<Mutable<SelectionModel<T>>>,
+
+    /// Callback called with the selected items when the selection changes.
+    pub on_selection_change: Option<Callback<Vec<T>>>,
+
+    /// Additional styles to be applied to the list view.
+    pub style: StyleHandle,
+}
+
+impl<T: Clone + PartialEq + Send + Sync + 'static> ListView<T> {
+    /// Create a new list view.
+    pub fn new(items: impl IntoSignal<Vec<T>>) -> Self {
+        Self {
+            items: items.into_signal(),
+            row: Arc::new(|_, _| {}),
+            selection: None,
+            on_selection_change: None,
+            style: StyleHandle::default(),
+        }
+    }
+
+    /// Set the function which builds the content of each row.
+    pub fn row<F: Fn(&T, &mut UiBuilder) + Send + Sync + 'static>(mut self, row: F) -> Self {
+        self.row = Arc::new(row);
+        self
+    }
+
+    /// Set the selection model to read and update.
+    pub fn selection(mut self, selection: Mutable<SelectionModel<T>>) -> Self {
+        self.selection = Some(selection);
+        self
+    }
+
+    /// Set the callback called when the selection changes.
+    pub fn on_selection_change(mut self, callback: Callback<Vec<T>>) -> Self {
+        self.on_selection_change = Some(callback);
+        self
+    }
+
+    /// Set additional styles to be applied to the list view.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl<T: Clone + PartialEq + Send + Sync + 'static> UiTemplate for ListView<T> {
+    fn build(&self, builder: &mut UiBuilder) {
+        let items = self.items.clone();
+        let row = self.row.clone();
+        let on_selection_change = self.on_selection_change;
+        let selection = self
+            .selection
+            .unwrap_or_else(|| builder.create_mutable(SelectionModel::<T>::single()));
+
+        builder.invoke(
+            ScrollView::new()
+                .children(move |builder| {
+                    let items = items.clone();
+                    let row = row.clone();
+                    builder
+                        .spawn((Node::default(), Name::new("ListView")))
+                        .style(style_list_view_inner)
+                        .create_children(|builder| {
+                            builder.for_each(
+                                {
+                                    let items = items.clone();
+                                    move |rcx| items.get_clone(rcx).into_iter()
+                                },
+                                move |item, builder| {
+                                    build_list_row(
+                                        builder,
+                                        item,
+                                        items.clone(),
+                                        selection,
+                                        on_selection_change,
+                                        row.as_ref(),
+                                    );
+                                },
+                                |_| {},
+                            );
+                        });
+                })
+                .style((style_list_view, self.style.clone()))
+                .scroll_enable_y(true),
+        );
+    }
+}
+
+fn build_list_row<T: Clone + PartialEq + Send + Sync + 'static>(
+    builder: &mut UiBuilder,
+    item: &T,
+    items: Signal<Vec<T>>,
+    selection: Mutable<SelectionModel<T>>,
+    on_selection_change: Option<Callback<Vec<T>>>,
+    row: &(dyn Fn(&T, &mut UiBuilder) + Send + Sync),
+) {
+    let key = item.clone();
+    let selected = builder.create_derived(move |rcx| {
+        selection
+            .signal()
+            .map(rcx, |selection| selection.is_selected(&key))
+    });
+    let key = item.clone();
+    builder
+        .spawn((Node::default(), Name::new("ListRow")))
+        .style(style_list_row)
+        .style_dyn(
+            move |rcx| selected.get(rcx),
+            |selected, ss| {
+                ss.background_color(if selected {
+                    colors::TEXT_SELECT
+                } else {
+                    colors::TRANSPARENT
+                });
+            },
+        )
+        .observe(
+            move |mut trigger: Trigger<Pointer<Click>>, mut world: DeferredWorld| {
+                trigger.propagate(false);
+                if trigger.event().button != PointerButton::Primary {
+                    return;
+                }
+                let modifiers =
+                    SelectModifiers::from_keys(world.resource::<ButtonInput<KeyCode>>());
+                let order = items.get_clone(&world);
+                let mut model = selection.get_clone(&mut world);
+                if model.click(key.clone(), modifiers, &order) {
+                    let selected = model.selected().to_vec();
+                    selection.set_clone(&mut world, model);
+                    if let Some(on_change) = on_selection_change {
+                        world.run_callback(on_change, selected);
+                    }
+                }
+            },
+        )
+        .create_children(|builder| row(item, builder));
+}
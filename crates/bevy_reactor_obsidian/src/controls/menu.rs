@@ -0,0 +1,1206 @@
+use std::sync::Arc;
+
+use accesskit::{self, Role};
+use bevy::{
+    a11y::AccessibilityNode,
+    ecs::world::DeferredWorld,
+    input::ButtonState,
+    picking::{focus::HoverMap, pointer::PointerId},
+    prelude::*,
+    ui,
+};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{
+    CondBuilder, CreateChilden, EntityEffectBuilder, EntityStyleBuilder, InsertComponentBuilder,
+    InvokeUiTemplate, TextBuilder, UiBuilder, UiTemplate,
+};
+use bevy_reactor_signals::{Callback, IntoSignal, Mutable, Rcx, RunCallback, Signal};
+
+use crate::{
+    colors,
+    control_events::Activated,
+    focus_signal::CreateFocusSignal,
+    hover_signal::{CreateHoverSignal, HoverSafeZone},
+    input_dispatch::{FocusKeyboardInput, KeyboardFocus, KeyboardFocusVisible, SetKeyboardFocus},
+    layering::UiLayer,
+    tab_navigation::{TabGroup, TabIndex},
+    typography,
+};
+
+use super::{Disabled, Icon, Portal, Spacer};
+
+/// Builder function for the items of a menu.
+type MenuContent = Arc<dyn Fn(&mut UiBuilder) + Send + Sync + 'static>;
+
+/// How long the pointer must rest over an item before its submenu opens.
+const HOVER_INTENT_DELAY: f32 = 0.15;
+
+fn style_menu_bar(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .background_color(colors::U2)
+        .padding(2)
+        .gap(2);
+}
+
+fn style_menu_title(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .padding((8, 2))
+        .border_radius(3.0)
+        .color(colors::FOREGROUND);
+}
+
+fn style_menu_popup(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch)
+        .min_width(160)
+        .background_color(colors::U2)
+        .border(1)
+        .border_color(colors::U3)
+        .border_radius(4.0)
+        .padding(3);
+}
+
+fn style_menu_item(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .min_height(24)
+        .padding((8, 0))
+        .border_radius(3.0);
+}
+
+fn style_menu_label(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center);
+}
+
+fn style_mnemonic(ss: &mut StyleBuilder) {
+    ss.border_bottom(1).border_color(colors::DIM);
+}
+
+fn style_menu_check(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .justify_content(ui::JustifyContent::FlexStart)
+        .align_items(ui::AlignItems::Center)
+        .flex_shrink(0.);
+}
+
+fn style_menu_radio_dot(ss: &mut StyleBuilder) {
+    ss.width(6)
+        .height(6)
+        .margin_left(3)
+        .border_radius(3.0)
+        .background_color(colors::FOREGROUND);
+}
+
+fn style_menu_shortcut(ss: &mut StyleBuilder) {
+    ss.color(colors::DIM).margin_left(24);
+}
+
+fn style_submenu_icon(ss: &mut StyleBuilder) {
+    ss.margin_left(12).margin_right(-4);
+}
+
+fn style_menu_divider(ss: &mut StyleBuilder) {
+    ss.height(1).background_color(colors::U3).margin((0, 3));
+}
+
+fn style_menu_section_label(ss: &mut StyleBuilder) {
+    ss.color(colors::DIM).font_size(11).padding((8, 2));
+}
+
+/// Split a label such as `"&File"` into the text to display and the byte offset of its
+/// mnemonic character, which is the one following the `&`. A literal ampersand is written as
+/// `&&`.
+fn parse_mnemonic(label: &str) -> (String, Option<usize>) {
+    let mut text = String::with_capacity(label.len());
+    let mut mnemonic = None;
+    let mut chars = label.chars();
+    while let Some(c) = chars.next() {
+        if c != '&' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('&') => text.push('&'),
+            Some(next) => {
+                if mnemonic.is_none() {
+                    mnemonic = Some(text.len());
+                }
+                text.push(next);
+            }
+            None => {}
+        }
+    }
+    (text, mnemonic)
+}
+
+/// The key which selects a mnemonic character. Only letters and digits can be mnemonics.
+fn mnemonic_key(c: char) -> Option<KeyCode> {
+    Some(match c.to_ascii_lowercase() {
+        'a' => KeyCode::KeyA,
+        'b' => KeyCode::KeyB,
+        'c' => KeyCode::KeyC,
+        'd' => KeyCode::KeyD,
+        'e' => KeyCode::KeyE,
+        'f' => KeyCode::KeyF,
+        'g' => KeyCode::KeyG,
+        'h' => KeyCode::KeyH,
+        'i' => KeyCode::KeyI,
+        'j' => KeyCode::KeyJ,
+        'k' => KeyCode::KeyK,
+        'l' => KeyCode::KeyL,
+        'm' => KeyCode::KeyM,
+        'n' => KeyCode::KeyN,
+        'o' => KeyCode::KeyO,
+        'p' => KeyCode::KeyP,
+        'q' => KeyCode::KeyQ,
+        'r' => KeyCode::KeyR,
+        's' => KeyCode::KeyS,
+        't' => KeyCode::KeyT,
+        'u' => KeyCode::KeyU,
+        'v' => KeyCode::KeyV,
+        'w' => KeyCode::KeyW,
+        'x' => KeyCode::KeyX,
+        'y' => KeyCode::KeyY,
+        'z' => KeyCode::KeyZ,
+        '0' => KeyCode::Digit0,
+        '1' => KeyCode::Digit1,
+        '2' => KeyCode::Digit2,
+        '3' => KeyCode::Digit3,
+        '4' => KeyCode::Digit4,
+        '5' => KeyCode::Digit5,
+        '6' => KeyCode::Digit6,
+        '7' => KeyCode::Digit7,
+        '8' => KeyCode::Digit8,
+        '9' => KeyCode::Digit9,
+        _ => return None,
+    })
+}
+
+/// Build the text of a menu label, underlining its mnemonic character. Returns the key which
+/// selects the mnemonic, if there is one.
+fn build_menu_label(builder: &mut UiBuilder, label: &str) -> Option<KeyCode> {
+    let (text, mnemonic) = parse_mnemonic(label);
+    let Some(start) = mnemonic else {
+        builder.text(text);
+        return None;
+    };
+    let c = text[start..].chars().next().unwrap();
+    let end = start + c.len_utf8();
+    builder
+        .spawn((Node::default(), Name::new("MenuLabel")))
+        .style(style_menu_label)
+        .create_children(|builder| {
+            if start > 0 {
+                builder.text(&text[..start]);
+            }
+            builder
+                .spawn((Node::default(), Name::new("MenuLabel::Mnemonic")))
+                .style(style_mnemonic)
+                .create_children(|builder| {
+                    builder.text(c.to_string());
+                });
+            if end < text.len() {
+                builder.text(&text[end..]);
+            }
+        });
+    mnemonic_key(c)
+}
+
+/// State of a [`MenuBar`], placed on the bar entity.
+#[derive(Component)]
+pub(crate) struct MenuBarState {
+    /// Index of the open menu, if any.
+    open: Mutable<Option<usize>>,
+    /// Number of menus in the bar.
+    count: usize,
+    /// The key which opens each menu (along with Alt).
+    mnemonics: Vec<Option<KeyCode>>,
+    /// The entity which had keyboard focus before the menus were opened.
+    restore_focus: Option<Entity>,
+}
+
+/// Which side of its anchor a menu popup is placed on.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PopupSide {
+    Below,
+    Right,
+}
+
+/// Positions a menu popup next to the element which opened it.
+#[derive(Component)]
+pub(crate) struct MenuPopupAnchor {
+    anchor: Entity,
+    side: PopupSide,
+}
+
+/// Placed on a menu popup, so that the items within it can find their menu bar.
+#[derive(Component, Clone, Copy)]
+pub(crate) struct MenuPopup {
+    bar: Entity,
+    /// For submenus, the item which opened the popup, and its open state.
+    parent_item: Option<(Entity, Mutable<bool>)>,
+}
+
+/// State of an item within a menu popup.
+#[derive(Component)]
+pub(crate) struct MenuItemState {
+    /// The popup which contains the item.
+    popup: Entity,
+    on_click: Option<Callback>,
+    /// The key which activates the item while its popup has focus.
+    mnemonic: Option<KeyCode>,
+    /// For submenu items, whether the submenu is open.
+    submenu: Option<Mutable<bool>>,
+    /// For radio items, callback which makes the item's value the selected one.
+    on_select: Option<Callback>,
+}
+
+/// Placed on a menu popup; true if any of its items can be checked, in which case every item
+/// reserves space for a checkmark so that the labels line up.
+#[derive(Component, Default)]
+pub(crate) struct MenuCheckColumn(bool);
+
+/// The kind of check indicator displayed to the left of an item's label.
+#[derive(Clone, Copy)]
+enum MenuCheck {
+    None,
+    Check(Signal<bool>),
+    Radio(Signal<bool>),
+}
+
+/// Open one of the menus of a menu bar, closing any other. `keyboard` indicates whether the
+/// menu was opened with the keyboard, in which case the focused item is highlighted.
+fn open_menu(world: &mut DeferredWorld, bar: Entity, index: usize, keyboard: bool) {
+    let Some(state) = world.get::<MenuBarState>(bar) else {
+        return;
+    };
+    let open = state.open;
+    if open.get(&*world).is_none() {
+        let focus = world.resource::<KeyboardFocus>().0;
+        world.get_mut::<MenuBarState>(bar).unwrap().restore_focus = focus;
+    }
+    world.resource_mut::<KeyboardFocusVisible>().0 = keyboard;
+    open.set(world, Some(index));
+}
+
+/// Open the menu `delta` places to the right of the open one, wrapping around.
+fn step_menu(world: &mut DeferredWorld, bar: Entity, delta: isize) {
+    let Some(state) = world.get::<MenuBarState>(bar) else {
+        return;
+    };
+    let (open, count) = (state.open, state.count);
+    if let Some(index) = open.get(&*world) {
+        let next = (index as isize + delta).rem_euclid(count as isize) as usize;
+        open_menu(world, bar, next, true);
+    }
+}
+
+/// Close all menus of a menu bar, and give keyboard focus back to whatever had it before.
+fn close_menus(world: &mut DeferredWorld, bar: Entity) {
+    let Some(state) = world.get::<MenuBarState>(bar) else {
+        return;
+    };
+    let (open, restore_focus) = (state.open, state.restore_focus);
+    if open.get(&*world).is_none() {
+        return;
+    }
+    open.set(world, None);
+    match restore_focus {
+        Some(entity) if world.entities().contains(entity) => world.set_keyboard_focus(entity),
+        _ => world.clear_keyboard_focus(),
+    }
+}
+
+/// The items of a popup, in tree order. Items in nested submenus are not included.
+fn popup_items(world: &World, popup: Entity) -> Vec<Entity> {
+    let mut items = Vec::new();
+    let mut stack = vec![popup];
+    while let Some(entity) = stack.pop() {
+        if world.get::<MenuItemState>(entity).is_some() {
+            items.push(entity);
+        } else if let Some(children) = world.get::<Children>(entity) {
+            stack.extend(children.iter().rev().copied());
+        }
+    }
+    items
+}
+
+/// The nearest popup which contains `entity`.
+fn find_popup(world: &World, entity: Entity) -> Option<Entity> {
+    let mut entity = Some(entity);
+    while let Some(e) = entity {
+        if world.get::<MenuPopup>(e).is_some() {
+            return Some(e);
+        }
+        entity = world.get::<Parent>(e).map(|p| p.get());
+    }
+    None
+}
+
+fn focus_item(world: &mut DeferredWorld, item: Entity) {
+    world.set_keyboard_focus(item);
+    world.resource_mut::<KeyboardFocusVisible>().0 = true;
+}
+
+/// Activate a menu item: open its submenu if it has one, otherwise close the menus and run
+/// its action.
+fn activate_item(world: &mut DeferredWorld, item: Entity, keyboard: bool) {
+    if world.get::<Disabled>(item).is_some() {
+        return;
+    }
+    let Some(state) = world.get::<MenuItemState>(item) else {
+        return;
+    };
+    let (popup, on_click, on_select, submenu) =
+        (state.popup, state.on_click, state.on_select, state.submenu);
+    if submenu.is_some() {
+        if keyboard {
+            world.resource_mut::<KeyboardFocusVisible>().0 = true;
+        }
+        hover_item(world, popup, item);
+        return;
+    }
+    if let Some(bar) = world.get::<MenuPopup>(popup).map(|p| p.bar) {
+        close_menus(world, bar);
+    }
+    world.commands().trigger_targets(Activated, item);
+    if let Some(on_select) = on_select {
+        world.run_callback(on_select, ());
+    }
+    if let Some(on_click) = on_click {
+        world.run_callback(on_click, ());
+    }
+}
+
+/// Open the submenu of `item`, if it has one, and close any other submenu of the same popup.
+fn hover_item(world: &mut DeferredWorld, popup: Entity, item: Entity) {
+    for other in popup_items(world, popup) {
+        let Some(open) = world.get::<MenuItemState>(other).and_then(|s| s.submenu) else {
+            continue;
+        };
+        let is_open = other == item && world.get::<Disabled>(other).is_none();
+        if open.get(&*world) != is_open {
+            open.set(world, is_open);
+        }
+    }
+}
+
+fn popup_on_key_input(mut trigger: Trigger<FocusKeyboardInput>, mut world: DeferredWorld) {
+    let event = &trigger.event().input;
+    if event.state != ButtonState::Pressed {
+        return;
+    }
+    let (key, repeat) = (event.key_code, event.repeat);
+    let Some(popup) = world.get::<MenuPopup>(trigger.entity()).copied() else {
+        return;
+    };
+    let focus = world.resource::<KeyboardFocus>().0;
+    let items: Vec<Entity> = popup_items(&world, trigger.entity())
+        .into_iter()
+        .filter(|item| world.get::<Disabled>(*item).is_none())
+        .collect();
+    let current = focus.and_then(|focus| items.iter().position(|item| *item == focus));
+    let count = items.len();
+    match key {
+        KeyCode::ArrowDown | KeyCode::ArrowUp | KeyCode::Home | KeyCode::End => {
+            if count > 0 {
+                let next = match (key, current) {
+                    (KeyCode::ArrowDown, Some(index)) => (index + 1) % count,
+                    (KeyCode::ArrowDown, None) | (KeyCode::Home, _) => 0,
+                    (KeyCode::ArrowUp, Some(index)) => (index + count - 1) % count,
+                    _ => count - 1,
+                };
+                focus_item(&mut world, items[next]);
+            }
+        }
+        KeyCode::Enter | KeyCode::Space => {
+            if let (Some(index), false) = (current, repeat) {
+                activate_item(&mut world, items[index], true);
+            }
+        }
+        KeyCode::ArrowRight => {
+            let submenu = current.map(|index| items[index]).filter(|item| {
+                world
+                    .get::<MenuItemState>(*item)
+                    .is_some_and(|s| s.submenu.is_some())
+            });
+            match submenu {
+                Some(item) => activate_item(&mut world, item, true),
+                None => step_menu(&mut world, popup.bar, 1),
+            }
+        }
+        KeyCode::ArrowLeft | KeyCode::Escape => match popup.parent_item {
+            Some((item, open)) => {
+                open.set(&mut world, false);
+                focus_item(&mut world, item);
+            }
+            None if key == KeyCode::Escape => close_menus(&mut world, popup.bar),
+            None => step_menu(&mut world, popup.bar, -1),
+        },
+        _ => {
+            let item = items.iter().copied().find(|item| {
+                world
+                    .get::<MenuItemState>(*item)
+                    .is_some_and(|s| s.mnemonic == Some(key))
+            });
+            match item {
+                Some(item) if !repeat => {
+                    focus_item(&mut world, item);
+                    activate_item(&mut world, item, true);
+                }
+                _ => return,
+            }
+        }
+    }
+    trigger.propagate(false);
+}
+
+/// Build a menu popup containing `items`, positioned next to `anchor`.
+fn build_menu_popup(
+    builder: &mut UiBuilder,
+    popup: MenuPopup,
+    anchor: Entity,
+    side: PopupSide,
+    items: &MenuContent,
+) {
+    let popup_id = builder
+        .spawn((
+            Node::default(),
+            Name::new("MenuPopup"),
+            popup,
+            MenuPopupAnchor { anchor, side },
+            // Hidden until it has been positioned.
+            Visibility::Hidden,
+            UiLayer::Floating,
+            MenuCheckColumn::default(),
+            TabGroup {
+                order: 0,
+                modal: true,
+            },
+            AccessibilityNode::from(accesskit::Node::new(Role::Menu)),
+        ))
+        .id();
+    builder
+        .entity_mut(popup_id)
+        .styles((typography::text_default, style_menu_popup))
+        .observe(popup_on_key_input)
+        .create_children(|builder| {
+            (items.as_ref())(builder);
+        });
+
+    let world = builder.world_mut();
+    if let Some((item, _)) = popup.parent_item {
+        world.entity_mut(item).insert(HoverSafeZone(popup_id));
+    }
+
+    // Focus the first item so that the menu can be navigated with the keyboard. Submenus
+    // opened by hovering leave the focus where it is.
+    if popup.parent_item.is_none() || world.resource::<KeyboardFocusVisible>().0 {
+        let first = popup_items(world, popup_id)
+            .into_iter()
+            .find(|item| world.get::<Disabled>(*item).is_none());
+        if let Some(first) = first {
+            world.set_keyboard_focus(first);
+        }
+    }
+}
+
+/// Spawn the row for a menu item, and return its entity. `state` is inserted on the item once
+/// its mnemonic is known, and `trailing` builds the content which is aligned to the right of
+/// the row.
+fn spawn_menu_item(
+    builder: &mut UiBuilder,
+    name: &'static str,
+    label: &str,
+    disabled: Signal<bool>,
+    check: MenuCheck,
+    mut state: MenuItemState,
+    trailing: impl FnOnce(&mut UiBuilder, Entity),
+) -> Entity {
+    let popup = state.popup;
+    let submenu = state.submenu;
+    let role = match check {
+        MenuCheck::None => Role::MenuItem,
+        MenuCheck::Check(_) => Role::MenuItemCheckBox,
+        MenuCheck::Radio(_) => Role::MenuItemRadio,
+    };
+    if !matches!(check, MenuCheck::None) {
+        if let Some(mut column) = builder.world_mut().get_mut::<MenuCheckColumn>(popup) {
+            column.0 = true;
+        }
+    }
+    let item = builder.spawn((Node::default(), Name::new(name))).id();
+    let hovering = builder.create_hover_signal(item);
+    let focused = builder.create_focus_visible_signal(item);
+    let intent = builder.create_hover_intent_signal(item, HOVER_INTENT_DELAY);
+    builder
+        .entity_mut(item)
+        .style(style_menu_item)
+        .style_dyn(
+            move |rcx| {
+                (
+                    disabled.get(rcx),
+                    hovering.get(rcx) || focused.get(rcx) || submenu.is_some_and(|s| s.get(rcx)),
+                )
+            },
+            |(is_disabled, highlight), sb| {
+                sb.color(if is_disabled {
+                    colors::DIM.with_alpha(0.4)
+                } else {
+                    colors::FOREGROUND
+                })
+                .background_color(if highlight && !is_disabled {
+                    colors::TEXT_SELECT
+                } else {
+                    colors::TRANSPARENT
+                });
+            },
+        )
+        .insert_if(disabled, || Disabled)
+        // Disabled items are skipped when tabbing, as well as by the arrow keys.
+        .effect(
+            move |rcx| disabled.get(rcx),
+            |is_disabled, ent| {
+                ent.insert(TabIndex(if is_disabled { -1 } else { 0 }));
+            },
+        )
+        .insert(AccessibilityNode::from(accesskit::Node::new(role)))
+        .observe(
+            move |mut trigger: Trigger<Pointer<Click>>, mut world: DeferredWorld| {
+                trigger.propagate(false);
+                activate_item(&mut world, item, false);
+            },
+        )
+        .observe(
+            move |_trigger: Trigger<Pointer<Over>>, mut world: DeferredWorld| {
+                // Keyboard navigation continues from the hovered item.
+                if world.get::<Disabled>(item).is_none() {
+                    world.set_keyboard_focus(item);
+                }
+            },
+        )
+        .create_children(|builder| {
+            build_menu_check(builder, popup, check);
+            state.mnemonic = build_menu_label(builder, label);
+            builder.invoke(Spacer);
+            trailing(builder, item);
+        });
+    builder.entity_mut(item).insert(state);
+    builder.create_effect(move |ecx| {
+        if intent.get(ecx) {
+            hover_item(&mut DeferredWorld::from(ecx.world_mut()), popup, item);
+        }
+    });
+    item
+}
+
+/// Build the column to the left of an item's label, which displays its checkmark or radio
+/// indicator. The column is collapsed in popups which have no checkable items.
+fn build_menu_check(builder: &mut UiBuilder, popup: Entity, check: MenuCheck) {
+    builder
+        .spawn((Node::default(), Name::new("MenuItem::Check")))
+        .style(style_menu_check)
+        .style_dyn(
+            move |rcx| {
+                rcx.read_component::<MenuCheckColumn>(popup)
+                    .is_some_and(|column| column.0)
+            },
+            |has_column, sb| {
+                sb.width(if has_column { 18 } else { 0 });
+            },
+        )
+        .create_children(|builder| match check {
+            MenuCheck::None => {}
+            MenuCheck::Check(checked) => {
+                builder.cond(
+                    checked,
+                    |builder| {
+                        builder.invoke(
+                            Icon::new(
+                                "embedded://bevy_reactor_obsidian/assets/icons/checkmark.png",
+                            )
+                            .color(Color::from(colors::FOREGROUND))
+                            .size(Vec2::splat(12.)),
+                        );
+                    },
+                    |_| {},
+                );
+            }
+            MenuCheck::Radio(selected) => {
+                builder.cond(
+                    selected,
+                    |builder| {
+                        builder
+                            .spawn((Node::default(), Name::new("MenuItem::RadioDot")))
+                            .style(style_menu_radio_dot);
+                    },
+                    |_| {},
+                );
+            }
+        });
+}
+
+/// A top-level menu of a [`MenuBar`].
+#[derive(Clone)]
+pub struct Menu {
+    /// The title of the menu. Prefix a character with `&` to make it the mnemonic, which
+    /// opens the menu when pressed along with Alt.
+    pub label: String,
+
+    /// Builder for the items of the menu.
+    pub items: MenuContent,
+}
+
+impl Menu {
+    /// Create a new menu with the given title.
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            items: Arc::new(|_| {}),
+        }
+    }
+
+    /// Set the items of the menu, typically [`MenuItem`], [`SubMenu`] and [`MenuDivider`].
+    pub fn items<F: Fn(&mut UiBuilder) + Send + Sync + 'static>(mut self, items: F) -> Self {
+        self.items = Arc::new(items);
+        self
+    }
+}
+
+/// A horizontal strip of menus, such as an application's File and Edit menus.
+///
+/// Clicking a title opens its menu; while a menu is open, hovering another title switches to
+/// it. Left and right arrows move between menus, up and down move between items, and Enter
+/// activates the focused item. Alt plus a title's mnemonic opens that menu, and while a menu
+/// is open an item's mnemonic activates it. Submenus open when the pointer rests on their
+/// item, or with the right arrow key.
+#[derive(Default)]
+pub struct MenuBar {
+    /// The menus in the bar.
+    pub menus: Vec<Menu>,
+
+    /// Additional styles to be applied to the bar.
+    pub style: StyleHandle,
+}
+
+impl MenuBar {
+    /// Create a new, empty menu bar.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a menu to the bar.
+    pub fn menu(mut self, menu: Menu) -> Self {
+        self.menus.push(menu);
+        self
+    }
+
+    /// Set additional styles to be applied to the bar.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl UiTemplate for MenuBar {
+    fn build(&self, builder: &mut UiBuilder) {
+        let open: Mutable<Option<usize>> = builder.create_mutable(None);
+        let mut mnemonics = Vec::with_capacity(self.menus.len());
+        let bar = builder
+            .spawn((
+                Node::default(),
+                Name::new("MenuBar"),
+                AccessibilityNode::from(accesskit::Node::new(Role::MenuBar)),
+            ))
+            .id();
+        builder
+            .entity_mut(bar)
+            .styles((typography::text_default, style_menu_bar, self.style.clone()))
+            .create_children(|builder| {
+                for (index, menu) in self.menus.iter().enumerate() {
+                    mnemonics.push(build_menu_title(builder, bar, open, index, menu));
+                }
+            });
+        builder.entity_mut(bar).insert(MenuBarState {
+            open,
+            count: self.menus.len(),
+            mnemonics,
+            restore_focus: None,
+        });
+    }
+}
+
+/// Build the title of a menu, and its popup. Returns the menu's mnemonic key.
+fn build_menu_title(
+    builder: &mut UiBuilder,
+    bar: Entity,
+    open: Mutable<Option<usize>>,
+    index: usize,
+    menu: &Menu,
+) -> Option<KeyCode> {
+    let title = builder
+        .spawn((Node::default(), Name::new("MenuBar::Title")))
+        .id();
+    let hovering = builder.create_hover_signal(title);
+    let items = menu.items.clone();
+    let mut mnemonic = None;
+    builder
+        .entity_mut(title)
+        .style(style_menu_title)
+        .style_dyn(
+            move |rcx| open.get(rcx) == Some(index) || hovering.get(rcx),
+            |highlight, sb| {
+                sb.background_color(if highlight {
+                    colors::U3
+                } else {
+                    colors::TRANSPARENT
+                });
+            },
+        )
+        .insert(AccessibilityNode::from(accesskit::Node::new(
+            Role::MenuItem,
+        )))
+        .observe(
+            move |mut trigger: Trigger<Pointer<Down>>, mut world: DeferredWorld| {
+                trigger.propagate(false);
+                if open.get(&world) == Some(index) {
+                    close_menus(&mut world, bar);
+                } else {
+                    open_menu(&mut world, bar, index, false);
+                }
+            },
+        )
+        .observe(
+            move |_trigger: Trigger<Pointer<Over>>, mut world: DeferredWorld| {
+                if open.get(&world).is_some_and(|open| open != index) {
+                    open_menu(&mut world, bar, index, false);
+                }
+            },
+        )
+        .create_children(|builder| {
+            mnemonic = build_menu_label(builder, &menu.label);
+            builder.cond(
+                move |rcx: &Rcx| open.get(rcx) == Some(index),
+                move |builder| {
+                    let items = items.clone();
+                    builder.invoke(Portal::new().children(move |builder| {
+                        let popup = MenuPopup {
+                            bar,
+                            parent_item: None,
+                        };
+                        build_menu_popup(builder, popup, title, PopupSide::Below, &items);
+                    }));
+                },
+                |_| {},
+            );
+        });
+    mnemonic
+}
+
+/// Builder for the radio group membership of a menu item. It returns a signal which is true
+/// when the item is selected, and a callback which selects it.
+type MenuRadio = Arc<dyn Fn(&mut UiBuilder) -> (Signal<bool>, Callback) + Send + Sync + 'static>;
+
+/// An item within a menu, which runs an action when clicked.
+#[derive(Default)]
+pub struct MenuItem {
+    /// The label of the item. Prefix a character with `&` to make it the mnemonic, which
+    /// activates the item when pressed while the menu has focus.
+    pub label: String,
+
+    /// Description of the item's keyboard shortcut, displayed on the right. This is only a
+    /// hint: the shortcut itself must be handled elsewhere.
+    pub shortcut: Option<String>,
+
+    /// Whether the item is disabled. Disabled items are skipped by keyboard navigation.
+    pub disabled: Signal<bool>,
+
+    /// If set, the item is checkable, and displays a checkmark when this is true. Activating
+    /// the item does not change the signal; `on_click` should do that.
+    pub checked: Option<Signal<bool>>,
+
+    /// If set, the item is one of a group of mutually exclusive choices.
+    pub radio: Option<MenuRadio>,
+
+    /// Callback called when the item is activated.
+    pub on_click: Option<Callback>,
+}
+
+impl MenuItem {
+    /// Create a new menu item.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the label of the item.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Set the keyboard shortcut hint displayed on the right of the item.
+    pub fn shortcut(mut self, shortcut: impl Into<String>) -> Self {
+        self.shortcut = Some(shortcut.into());
+        self
+    }
+
+    /// Set whether the item is disabled.
+    pub fn disabled(mut self, disabled: impl IntoSignal<bool>) -> Self {
+        self.disabled = disabled.into_signal();
+        self
+    }
+
+    /// Make the item checkable, displaying a checkmark when `checked` is true.
+    pub fn checked(mut self, checked: impl IntoSignal<bool>) -> Self {
+        self.checked = Some(checked.into_signal());
+        self
+    }
+
+    /// Make the item a member of a radio group. All of the items which share `group` form the
+    /// group: the item is selected when the group's value equals `value`, and activating it
+    /// sets the group's value (before `on_click` is called).
+    pub fn radio<T: PartialEq + Clone + Send + Sync + 'static>(
+        mut self,
+        group: Mutable<T>,
+        value: T,
+    ) -> Self {
+        self.radio = Some(Arc::new(move |builder: &mut UiBuilder| {
+            let test = value.clone();
+            let selected = builder.create_derived(move |rcx| group.get_clone(rcx) == test);
+            let value = value.clone();
+            let select = builder.create_callback(move |_: In<()>, mut world: DeferredWorld| {
+                group.set_clone(&mut world, value.clone());
+            });
+            (selected, select)
+        }));
+        self
+    }
+
+    /// Set the callback called when the item is activated.
+    pub fn on_click(mut self, on_click: Callback) -> Self {
+        self.on_click = Some(on_click);
+        self
+    }
+}
+
+impl UiTemplate for MenuItem {
+    fn build(&self, builder: &mut UiBuilder) {
+        let Some(popup) = find_popup(builder.world(), builder.parent()) else {
+            warn!("MenuItem must be placed within a menu");
+            return;
+        };
+        let (check, on_select) = match (&self.radio, self.checked) {
+            (Some(radio), _) => {
+                let (selected, select) = (radio.as_ref())(builder);
+                (MenuCheck::Radio(selected), Some(select))
+            }
+            (None, Some(checked)) => (MenuCheck::Check(checked), None),
+            (None, None) => (MenuCheck::None, None),
+        };
+        let shortcut = self.shortcut.clone();
+        spawn_menu_item(
+            builder,
+            "MenuItem",
+            &self.label,
+            self.disabled,
+            check,
+            MenuItemState {
+                popup,
+                on_click: self.on_click,
+                mnemonic: None,
+                submenu: None,
+                on_select,
+            },
+            |builder, _| {
+                if let Some(shortcut) = shortcut {
+                    builder
+                        .spawn((Node::default(), Name::new("MenuItem::Shortcut")))
+                        .style(style_menu_shortcut)
+                        .create_children(|builder| {
+                            builder.text(shortcut);
+                        });
+                }
+            },
+        );
+    }
+}
+
+/// An item within a menu which opens a nested menu.
+#[derive(Default)]
+pub struct SubMenu {
+    /// The label of the item. Prefix a character with `&` to make it the mnemonic.
+    pub label: String,
+
+    /// Builder for the items of the nested menu.
+    pub items: Option<MenuContent>,
+
+    /// Whether the item is disabled.
+    pub disabled: Signal<bool>,
+}
+
+impl SubMenu {
+    /// Create a new submenu item.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the label of the item.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Set the items of the nested menu.
+    pub fn items<F: Fn(&mut UiBuilder) + Send + Sync + 'static>(mut self, items: F) -> Self {
+        self.items = Some(Arc::new(items));
+        self
+    }
+
+    /// Set whether the item is disabled.
+    pub fn disabled(mut self, disabled: impl IntoSignal<bool>) -> Self {
+        self.disabled = disabled.into_signal();
+        self
+    }
+}
+
+impl UiTemplate for SubMenu {
+    fn build(&self, builder: &mut UiBuilder) {
+        let Some(popup) = find_popup(builder.world(), builder.parent()) else {
+            warn!("SubMenu must be placed within a menu");
+            return;
+        };
+        let Some(bar) = builder
+            .world()
+            .get::<MenuPopup>(popup)
+            .map(|popup| popup.bar)
+        else {
+            warn!("SubMenu must be placed within a menu");
+            return;
+        };
+        let open = builder.create_mutable(false);
+        let items = self.items.clone().unwrap_or_else(|| Arc::new(|_| {}));
+        spawn_menu_item(
+            builder,
+            "SubMenu",
+            &self.label,
+            self.disabled,
+            MenuCheck::None,
+            MenuItemState {
+                popup,
+                on_click: None,
+                mnemonic: None,
+                submenu: Some(open),
+                on_select: None,
+            },
+            move |builder, item| {
+                builder.invoke(
+                    Icon::new("embedded://bevy_reactor_obsidian/assets/icons/chevron_right.png")
+                        .color(Color::from(colors::DIM))
+                        .size(Vec2::splat(14.))
+                        .style(style_submenu_icon),
+                );
+                builder.cond(
+                    open.signal(),
+                    move |builder| {
+                        let items = items.clone();
+                        builder.invoke(Portal::new().children(move |builder| {
+                            let popup = MenuPopup {
+                                bar,
+                                parent_item: Some((item, open)),
+                            };
+                            build_menu_popup(builder, popup, item, PopupSide::Right, &items);
+                        }));
+                    },
+                    |_| {},
+                );
+            },
+        );
+    }
+}
+
+/// A horizontal line separating groups of items within a menu, optionally followed by a label
+/// for the next group.
+#[derive(Clone, Default)]
+pub struct MenuDivider {
+    /// Label for the section which follows the divider.
+    pub label: Option<String>,
+}
+
+impl MenuDivider {
+    /// Create a new menu divider.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the label for the section which follows the divider.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+}
+
+impl UiTemplate for MenuDivider {
+    fn build(&self, builder: &mut UiBuilder) {
+        builder
+            .spawn((Node::default(), Name::new("MenuDivider")))
+            .style(style_menu_divider);
+        if let Some(label) = &self.label {
+            builder
+                .spawn((Node::default(), Name::new("MenuDivider::Label")))
+                .style(style_menu_section_label)
+                .create_children(|builder| {
+                    builder.text(label.clone());
+                });
+        }
+    }
+}
+
+/// Place each menu popup next to its anchor, flipping it to the other side of the anchor if it
+/// would otherwise extend past the edge of the window.
+pub(crate) fn position_menu_popups(
+    mut q_popups: Query<(
+        &MenuPopupAnchor,
+        &ComputedNode,
+        &Parent,
+        &mut Node,
+        &mut Visibility,
+    )>,
+    q_nodes: Query<(&ComputedNode, &GlobalTransform)>,
+) {
+    for (anchor, computed, parent, mut node, mut visibility) in q_popups.iter_mut() {
+        let Ok((anchor_node, anchor_transform)) = q_nodes.get(anchor.anchor) else {
+            continue;
+        };
+        // The popup's parent is the portal root, which covers the window.
+        let Ok((window_node, _)) = q_nodes.get(parent.get()) else {
+            continue;
+        };
+        let scale = computed.inverse_scale_factor();
+        let rect = Rect::from_center_size(anchor_transform.translation().xy(), anchor_node.size());
+        let (min, max) = (rect.min * scale, rect.max * scale);
+        let size = computed.size() * scale;
+        let bounds = window_node.size() * scale;
+        let position = match anchor.side {
+            PopupSide::Below => Vec2::new(
+                min.x.min(bounds.x - size.x).max(0.),
+                if max.y + size.y > bounds.y && min.y >= size.y {
+                    min.y - size.y
+                } else {
+                    max.y
+                },
+            ),
+            PopupSide::Right => Vec2::new(
+                if max.x + size.x > bounds.x && min.x >= size.x {
+                    min.x - size.x
+                } else {
+                    max.x
+                },
+                min.y.min(bounds.y - size.y).max(0.),
+            ),
+        };
+        let (left, top) = (ui::Val::Px(position.x), ui::Val::Px(position.y));
+        if node.left != left || node.top != top {
+            node.left = left;
+            node.top = top;
+        }
+        if *visibility == Visibility::Hidden && size.x > 0. {
+            *visibility = Visibility::Inherited;
+        }
+    }
+}
+
+/// Open a menu when Alt and its mnemonic key are pressed.
+pub(crate) fn menu_bar_mnemonics(
+    keys: Res<ButtonInput<KeyCode>>,
+    q_bars: Query<(Entity, &MenuBarState)>,
+    mut commands: Commands,
+) {
+    if !keys.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]) {
+        return;
+    }
+    for key in keys.get_just_pressed() {
+        let found = q_bars.iter().find_map(|(bar, state)| {
+            state
+                .mnemonics
+                .iter()
+                .position(|m| *m == Some(*key))
+                .map(|index| (bar, index))
+        });
+        if let Some((bar, index)) = found {
+            commands.queue(move |world: &mut World| {
+                open_menu(&mut DeferredWorld::from(world), bar, index, true);
+            });
+            return;
+        }
+    }
+}
+
+/// Close the open menus when a mouse button is pressed outside of them.
+pub(crate) fn dismiss_menu_bars(
+    mouse: Res<ButtonInput<MouseButton>>,
+    hover_map: Option<Res<HoverMap>>,
+    q_bars: Query<Entity, With<MenuBarState>>,
+    q_menu_parts: Query<(), Or<(With<MenuBarState>, With<MenuPopup>)>>,
+    q_parents: Query<&Parent>,
+    mut commands: Commands,
+) {
+    if mouse.get_just_pressed().next().is_none() || q_bars.is_empty() {
+        return;
+    }
+    let Some(hover_map) = hover_map else { return };
+    let in_menu = hover_map.get(&PointerId::Mouse).is_some_and(|hovers| {
+        hovers.keys().any(|entity| {
+            std::iter::once(*entity)
+                .chain(q_parents.iter_ancestors(*entity))
+                .any(|e| q_menu_parts.contains(e))
+        })
+    });
+    if !in_menu {
+        for bar in q_bars.iter() {
+            commands.queue(move |world: &mut World| {
+                close_menus(&mut DeferredWorld::from(world), bar);
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mnemonic() {
+        assert_eq!(parse_mnemonic("Open"), ("Open".to_string(), None));
+        assert_eq!(parse_mnemonic("&File"), ("File".to_string(), Some(0)));
+        assert_eq!(parse_mnemonic("Save &As"), ("Save As".to_string(), Some(5)));
+        // Only the first mnemonic counts.
+        assert_eq!(
+            parse_mnemonic("&Edit &Me"),
+            ("Edit Me".to_string(), Some(0))
+        );
+    }
+
+    #[test]
+    fn test_parse_mnemonic_escaped_ampersand() {
+        assert_eq!(
+            parse_mnemonic("Fish && Chips"),
+            ("Fish & Chips".to_string(), None)
+        );
+        assert_eq!(parse_mnemonic("&&&Both"), ("&Both".to_string(), Some(1)));
+    }
+
+    #[test]
+    fn test_parse_mnemonic_trailing_ampersand() {
+        assert_eq!(parse_mnemonic("Close&"), ("Close".to_string(), None));
+        assert_eq!(parse_mnemonic("&"), (String::new(), None));
+    }
+}
@@ -0,0 +1,294 @@
+use bevy::{
+    ecs::world::DeferredWorld,
+    prelude::*,
+    render::{
+        camera::RenderTarget,
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
+        view::RenderLayers,
+    },
+    ui,
+};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{EntityStyleBuilder, UiBuilder, UiTemplate};
+use bevy_reactor_signals::{IntoSignal, Mutable, Signal};
+
+use crate::colors;
+
+/// Render layers from this one up are reserved for previews, one layer per preview, so that
+/// each preview camera only sees its own model.
+const FIRST_PREVIEW_LAYER: usize = 24;
+
+/// Allocates render layers for previews.
+#[derive(Resource, Default)]
+pub(crate) struct PreviewLayers {
+    next: usize,
+    free: Vec<usize>,
+}
+
+impl PreviewLayers {
+    fn alloc(&mut self) -> usize {
+        self.free.pop().unwrap_or_else(|| {
+            self.next += 1;
+            FIRST_PREVIEW_LAYER + self.next - 1
+        })
+    }
+
+    fn release(&mut self, layer: usize) {
+        self.free.push(layer);
+    }
+}
+
+/// The position of a preview camera, orbiting around the origin.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Orbit {
+    /// Rotation around the vertical axis, in radians.
+    pub yaw: f32,
+    /// Elevation above the horizontal plane, in radians.
+    pub pitch: f32,
+    /// Distance from the origin.
+    pub distance: f32,
+}
+
+impl Default for Orbit {
+    fn default() -> Self {
+        Self {
+            yaw: 0.6,
+            pitch: 0.4,
+            distance: 3.,
+        }
+    }
+}
+
+impl Orbit {
+    /// The camera transform for this orbit, looking at the origin.
+    pub fn transform(&self) -> Transform {
+        let rotation = Quat::from_euler(EulerRot::YXZ, self.yaw, -self.pitch, 0.);
+        Transform::from_translation(rotation * Vec3::Z * self.distance)
+            .looking_at(Vec3::ZERO, Vec3::Y)
+    }
+
+    /// Return an orbit rotated by a pointer drag of `delta` logical pixels.
+    fn dragged(&self, delta: Vec2) -> Self {
+        const LIMIT: f32 = std::f32::consts::FRAC_PI_2 - 0.01;
+        Self {
+            yaw: self.yaw - delta.x * 0.01,
+            pitch: (self.pitch + delta.y * 0.01).clamp(-LIMIT, LIMIT),
+            distance: self.distance,
+        }
+    }
+}
+
+/// The offscreen camera, light and model used to render a preview. This is placed on the UI
+/// node which displays the preview; the rig is despawned when the component is removed.
+#[derive(Component)]
+pub(crate) struct PreviewRig {
+    pub(crate) root: Entity,
+    pub(crate) camera: Entity,
+    pub(crate) model: Entity,
+    layer: usize,
+}
+
+/// Create an image which can be used as a camera render target.
+pub(crate) fn create_render_target(world: &mut World, size: UVec2) -> Handle<Image> {
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: size.x.max(1),
+            height: size.y.max(1),
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 0],
+        TextureFormat::Bgra8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    image.texture_descriptor.usage =
+        TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST | TextureUsages::RENDER_ATTACHMENT;
+    world.resource_mut::<Assets<Image>>().add(image)
+}
+
+/// Spawn a camera, light and (empty) model which render to `target`, on a render layer of
+/// their own. The rig is a top-level entity, since UI transforms must not propagate to it.
+pub(crate) fn spawn_preview_rig(
+    world: &mut World,
+    target: Handle<Image>,
+    clear_color: Color,
+) -> PreviewRig {
+    let layer = world.resource_mut::<PreviewLayers>().alloc();
+    let layers = RenderLayers::layer(layer);
+    let mut root = world.spawn((
+        Transform::default(),
+        Visibility::default(),
+        Name::new("PreviewRig"),
+    ));
+    let mut camera = Entity::PLACEHOLDER;
+    let mut model = Entity::PLACEHOLDER;
+    root.with_children(|parent| {
+        camera = parent
+            .spawn((
+                Camera3d::default(),
+                Camera {
+                    // Render before the cameras which display the preview.
+                    order: -1,
+                    target: RenderTarget::Image(target),
+                    clear_color: ClearColorConfig::Custom(clear_color),
+                    ..default()
+                },
+                Orbit::default().transform(),
+                layers.clone(),
+            ))
+            .id();
+        parent.spawn((
+            DirectionalLight {
+                illuminance: 4000.,
+                ..default()
+            },
+            Transform::from_xyz(2., 4., 3.).looking_at(Vec3::ZERO, Vec3::Y),
+            layers.clone(),
+        ));
+        model = parent
+            .spawn((Transform::default(), Visibility::default(), layers))
+            .id();
+    });
+    PreviewRig {
+        root: root.id(),
+        camera,
+        model,
+        layer,
+    }
+}
+
+/// Despawn the preview rig when the preview widget is removed.
+pub(crate) fn register_preview_hooks(world: &mut World) {
+    world.register_component_hooks::<PreviewRig>().on_remove(
+        |mut world: DeferredWorld, entity, _component| {
+            let rig = world.get::<PreviewRig>(entity).unwrap();
+            let (root, layer) = (rig.root, rig.layer);
+            world.resource_mut::<PreviewLayers>().release(layer);
+            world.commands().entity(root).try_despawn_recursive();
+        },
+    );
+}
+
+fn style_mesh_preview(ss: &mut StyleBuilder) {
+    ss.border_radius(4);
+}
+
+/// Displays a mesh with a material, rendered offscreen by its own camera and light. Dragging
+/// the preview orbits the camera around the mesh, which should be centered on the origin.
+pub struct MeshPreview {
+    /// The mesh to display.
+    pub mesh: Signal<Handle<Mesh>>,
+
+    /// The material to render the mesh with.
+    pub material: Signal<Handle<StandardMaterial>>,
+
+    /// The camera position. If not set, the preview keeps its own.
+    pub orbit: Option<Mutable<Orbit>>,
+
+    /// Size of the preview, in pixels. This is also the size of the render target.
+    pub size: UVec2,
+
+    /// Additional styles to be applied to the preview.
+    pub style: StyleHandle,
+}
+
+impl Default for MeshPreview {
+    fn default() -> Self {
+        Self {
+            mesh: Signal::Constant(Handle::default()),
+            material: Signal::Constant(Handle::default()),
+            orbit: None,
+            size: UVec2::splat(128),
+            style: StyleHandle::default(),
+        }
+    }
+}
+
+impl MeshPreview {
+    /// Create a new `MeshPreview`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the mesh to display.
+    pub fn mesh(mut self, mesh: impl IntoSignal<Handle<Mesh>>) -> Self {
+        self.mesh = mesh.into_signal();
+        self
+    }
+
+    /// Set the material to render the mesh with.
+    pub fn material(mut self, material: impl IntoSignal<Handle<StandardMaterial>>) -> Self {
+        self.material = material.into_signal();
+        self
+    }
+
+    /// Set the mutable which holds the camera position, so that it can be read or changed
+    /// from outside the preview.
+    pub fn orbit(mut self, orbit: Mutable<Orbit>) -> Self {
+        self.orbit = Some(orbit);
+        self
+    }
+
+    /// Set the size of the preview.
+    pub fn size(mut self, size: UVec2) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set additional styles to be applied to the preview.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl UiTemplate for MeshPreview {
+    fn build(&self, builder: &mut UiBuilder) {
+        let orbit = self
+            .orbit
+            .unwrap_or_else(|| builder.create_mutable(Orbit::default()));
+        let mesh = self.mesh.clone();
+        let material = self.material.clone();
+        let size = self.size;
+
+        let world = builder.world_mut();
+        let target = create_render_target(world, size);
+        let rig = spawn_preview_rig(world, target.clone(), colors::U1.into());
+        let (camera, model) = (rig.camera, rig.model);
+
+        builder
+            .spawn((ImageNode::new(target), Name::new("MeshPreview"), rig))
+            .styles((
+                style_mesh_preview,
+                move |ss: &mut StyleBuilder| {
+                    ss.width(ui::Val::Px(size.x as f32))
+                        .height(ui::Val::Px(size.y as f32));
+                },
+                self.style.clone(),
+            ))
+            .observe(
+                move |mut trigger: Trigger<Pointer<Drag>>, mut world: DeferredWorld| {
+                    if trigger.event().button == PointerButton::Primary {
+                        trigger.propagate(false);
+                        let delta = trigger.event().delta;
+                        let old = orbit.get(&world);
+                        orbit.set(&mut world, old.dragged(delta));
+                    }
+                },
+            );
+
+        builder.create_effect(move |ecx| {
+            let mesh = mesh.get_clone(ecx);
+            let material = material.get_clone(ecx);
+            ecx.world_mut()
+                .entity_mut(model)
+                .insert((Mesh3d(mesh), MeshMaterial3d(material)));
+        });
+
+        builder.create_effect(move |ecx| {
+            let transform = orbit.get(ecx).transform();
+            ecx.world_mut().entity_mut(camera).insert(transform);
+        });
+    }
+}
@@ -1,47 +1,121 @@
+mod accordion;
+mod animated_size;
 mod barrier;
 mod button;
+mod button_group;
+mod chart;
 mod checkbox;
+mod command_palette;
 mod core_slider;
+mod dial;
 mod dialog;
 mod disabled;
 mod disclosure_toggle;
+mod drag_scaling;
+mod field_error;
+mod field_row;
 mod gradient_slider;
+mod highlighted_text;
 mod icon;
 mod icon_button;
+mod infinite_list;
+mod key_value_editor;
+mod list_view;
+mod menu;
+pub(crate) mod mesh_preview;
+mod number_input;
+mod palette_swatches;
+mod popover;
+mod portal;
+mod range_slider;
 mod scrollview;
+mod selectable_text;
+mod shortcut_help;
+mod skeleton;
 mod slider;
 mod spacer;
 mod spinbox;
+mod spinner;
 mod splitter;
 mod swatch;
 mod swatch_grid;
-mod toggle_state;
+mod text_input;
+mod thumbnail;
+mod timeline;
+pub(crate) mod toggle_state;
 mod tool_palette;
+mod window_chrome;
+mod xyz_field;
+mod zoom_canvas;
 
 use bevy::app::Plugin;
-pub use button::{Button, ButtonVariant};
+pub use accordion::{Accordion, AccordionPolicy};
+pub use animated_size::AnimatedSize;
+pub use button::{AsyncAction, Button, ButtonVariant};
+pub use button_group::ButtonGroup;
+pub use chart::{BarChart, Sparkline};
 pub use checkbox::Checkbox;
+pub use command_palette::{
+    CommandPalette, CommandPaletteHotkey, CommandPaletteState, CommandRegistry, PaletteCommand,
+    RegisterPaletteCommand,
+};
 pub use core_slider::CoreSlider;
+pub use dial::{Dial, PadInput};
 pub use dialog::{Dialog, DialogBody, DialogFooter, DialogHeader};
 pub use disabled::{Disabled, IsDisabled};
 pub use disclosure_toggle::DisclosureToggle;
+pub use drag_scaling::DragScaling;
+pub use field_error::FieldError;
+pub use field_row::FieldRow;
 pub use gradient_slider::{ColorGradient, GradientSlider};
+pub use highlighted_text::HighlightedText;
 pub use icon::Icon;
 pub use icon_button::IconButton;
+pub use infinite_list::{InfiniteList, PageLoader};
+pub use key_value_editor::KeyValueEditor;
+pub use list_view::ListView;
+pub use menu::{Menu, MenuBar, MenuDivider, MenuItem, SubMenu};
+pub use mesh_preview::{MeshPreview, Orbit};
+pub use number_input::{NumberInput, Vec3Input};
+pub use palette_swatches::PaletteSwatches;
+pub use popover::Popover;
+pub use portal::Portal;
+pub use range_slider::RangeSlider;
 pub use scrollview::ScrollView;
+pub use selectable_text::{SelectableText, TextSelection};
+pub use shortcut_help::{
+    format_hotkey, key_name, RegisterShortcuts, Shortcut, ShortcutHelp, ShortcutHelpHotkey,
+    ShortcutHelpState, ShortcutRegistry,
+};
+pub use skeleton::Skeleton;
 pub use slider::Slider;
 pub use spacer::Spacer;
 pub use spinbox::SpinBox;
+pub use spinner::Spinner;
 pub use splitter::{Splitter, SplitterDirection};
-pub use swatch::Swatch;
+pub use swatch::{Checkerboard, Swatch};
 pub use swatch_grid::SwatchGrid;
+pub use text_input::TextInput;
+pub use thumbnail::{Thumbnail, ThumbnailFit};
+pub use timeline::{KeyMove, Timeline, TimelineTrack};
 pub use tool_palette::{ToolButton, ToolPalette};
+pub use window_chrome::{TitleBar, WindowResizeGrip, WindowResizeGrips};
+pub use xyz_field::XYZField;
+pub use zoom_canvas::{
+    canvas_to_screen, screen_to_canvas, CanvasTransform, ZoomCanvas, ZoomCanvasViewport,
+};
 
 pub(crate) struct ControlEventsPlugin;
 
 impl Plugin for ControlEventsPlugin {
     fn build(&self, app: &mut bevy::app::App) {
-        app.add_observer(toggle_state::toggle_on_key_input)
+        app.init_resource::<CommandRegistry>()
+            .init_resource::<CommandPaletteState>()
+            .init_resource::<CommandPaletteHotkey>()
+            .init_resource::<ShortcutHelpState>()
+            .init_resource::<ShortcutHelpHotkey>()
+            .init_resource::<mesh_preview::PreviewLayers>()
+            .add_observer(toggle_state::toggle_on_key_input)
             .add_observer(toggle_state::toggle_on_pointer_click)
             .add_observer(button::button_on_key_event)
             .add_observer(button::button_on_pointer_down)
@@ -53,6 +127,32 @@ impl Plugin for ControlEventsPlugin {
             .add_observer(barrier::barrier_on_pointer_down)
             .add_observer(core_slider::slider_on_drag_start)
             .add_observer(core_slider::slider_on_drag_end)
-            .add_observer(core_slider::slider_on_drag);
+            .add_observer(core_slider::slider_on_drag)
+            .add_observer(range_slider::range_slider_on_drag_start)
+            .add_observer(range_slider::range_slider_on_drag_end)
+            .add_observer(range_slider::range_slider_on_drag)
+            .add_observer(range_slider::range_slider_thumb_on_key_input)
+            .add_systems(
+                bevy::app::Update,
+                (
+                    button::poll_button_tasks,
+                    infinite_list::poll_infinite_list_tasks,
+                    button_group::update_button_groups,
+                    spinner::rotate_spinners,
+                    skeleton::shimmer_skeletons,
+                    thumbnail::update_thumbnails,
+                    command_palette::toggle_command_palette,
+                    shortcut_help::toggle_shortcut_help,
+                    menu::position_menu_popups,
+                    menu::menu_bar_mnemonics,
+                    menu::dismiss_menu_bars,
+                    popover::position_popovers,
+                    selectable_text::clear_unfocused_text_selections,
+                    selectable_text::update_text_selection_highlights,
+                ),
+            );
+        app.register_shortcuts(shortcut_help::builtin_shortcuts);
+        portal::register_portal_hooks(app.world_mut());
+        mesh_preview::register_preview_hooks(app.world_mut());
     }
 }
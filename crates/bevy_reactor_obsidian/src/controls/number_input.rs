@@ -0,0 +1,340 @@
+use bevy::{ecs::world::DeferredWorld, prelude::*, ui};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{
+    CreateChilden, EntityStyleBuilder, InvokeUiTemplate, UiBuilder, UiTemplate,
+};
+use bevy_reactor_signals::{Callback, IntoSignal, Rcx, RunCallback, Signal};
+
+use crate::{
+    focus_signal::CreateFocusSignal,
+    size::Size,
+    text_parse::{NumberFormat, ParseError},
+};
+
+use super::TextInput;
+
+fn style_parsed_input(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Stretch);
+}
+
+/// Common properties of fields whose text is parsed into a value.
+struct ParsedInputProps {
+    size: Size,
+    disabled: Signal<bool>,
+    style: StyleHandle,
+}
+
+/// Builds a text field which displays a formatted value. While the field is being edited the
+/// raw text is kept separately; when the user presses Enter or focus leaves the field, the
+/// text is parsed and the result passed to `on_commit`. Text which fails to parse is discarded
+/// and the field reverts to the previous value.
+fn build_parsed_input<T: Send + 'static>(
+    builder: &mut UiBuilder,
+    props: ParsedInputProps,
+    display: impl Fn(&Rcx) -> String + Send + Sync + 'static,
+    parse: impl Fn(&str) -> Result<T, ParseError> + Send + Sync + 'static,
+    mask: impl Fn(char) -> bool + Send + Sync + 'static,
+    on_commit: Option<Callback<T>>,
+) {
+    let editing = builder.create_mutable::<Option<String>>(None);
+    let text = builder.create_derived(move |rcx| match editing.signal().get_clone(rcx) {
+        Some(text) => text,
+        None => display(rcx),
+    });
+
+    let commit = builder.create_callback(move |_: In<()>, mut world: DeferredWorld| {
+        let Some(text) = editing.get_clone(&mut world) else {
+            return;
+        };
+        editing.set_clone(&mut world, None);
+        if let (Ok(value), Some(on_commit)) = (parse(&text), on_commit) {
+            world.run_callback(on_commit, value);
+        }
+    });
+    let on_edit = builder.create_callback(move |text: In<String>, mut world: DeferredWorld| {
+        editing.set_clone(&mut world, Some(text.0));
+    });
+    let on_submit = builder.create_callback(move |_: In<String>, mut world: DeferredWorld| {
+        world.run_callback(commit, ());
+    });
+
+    let id = builder
+        .spawn((Node::default(), Name::new("ParsedInput")))
+        .id();
+    let focused = builder.create_focus_within_signal(id);
+    builder.create_effect(move |ve| {
+        if !focused.get(ve) {
+            ve.run_callback(commit, ());
+        }
+    });
+
+    builder
+        .entity_mut(id)
+        .styles((style_parsed_input, props.style))
+        .create_children(|builder| {
+            builder.invoke(
+                TextInput::new()
+                    .value(text)
+                    .size(props.size)
+                    .disabled(props.disabled)
+                    .mask(mask)
+                    .on_change(on_edit)
+                    .on_submit(on_submit)
+                    .style(|ss: &mut StyleBuilder| {
+                        ss.flex_grow(1.);
+                    }),
+            );
+        });
+}
+
+/// A text field for editing a number. Only numeric characters can be typed; the text may be
+/// an arithmetic expression such as `2+3*4`, which is evaluated when the edit is committed.
+/// The result is clamped to the field's range.
+pub struct NumberInput {
+    /// Current field value.
+    pub value: Signal<f32>,
+
+    /// Minimum field value.
+    pub min: Signal<f32>,
+
+    /// Maximum field value.
+    pub max: Signal<f32>,
+
+    /// Number of decimal places to display.
+    pub precision: usize,
+
+    /// How numbers are written, e.g. which decimal separator to use.
+    pub format: NumberFormat,
+
+    /// Field size.
+    pub size: Size,
+
+    /// Whether the field is disabled.
+    pub disabled: Signal<bool>,
+
+    /// Additional styles to be applied to the field.
+    pub style: StyleHandle,
+
+    /// Callback called with the new value when an edit is committed.
+    pub on_change: Option<Callback<f32>>,
+}
+
+impl Default for NumberInput {
+    fn default() -> Self {
+        Self {
+            value: Signal::Constant(0.),
+            min: Signal::Constant(f32::MIN),
+            max: Signal::Constant(f32::MAX),
+            precision: 2,
+            format: NumberFormat::default(),
+            size: Size::default(),
+            disabled: Signal::Constant(false),
+            style: StyleHandle::default(),
+            on_change: None,
+        }
+    }
+}
+
+impl NumberInput {
+    /// Construct a new `NumberInput`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the current field value.
+    pub fn value(mut self, value: impl IntoSignal<f32>) -> Self {
+        self.value = value.into_signal();
+        self
+    }
+
+    /// Set the minimum field value.
+    pub fn min(mut self, min: impl IntoSignal<f32>) -> Self {
+        self.min = min.into_signal();
+        self
+    }
+
+    /// Set the maximum field value.
+    pub fn max(mut self, max: impl IntoSignal<f32>) -> Self {
+        self.max = max.into_signal();
+        self
+    }
+
+    /// Set the number of decimal places to display.
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Set how numbers are written.
+    pub fn format(mut self, format: NumberFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Set the field size.
+    pub fn size(mut self, size: Size) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the field disabled state.
+    pub fn disabled(mut self, disabled: impl IntoSignal<bool>) -> Self {
+        self.disabled = disabled.into_signal();
+        self
+    }
+
+    /// Set additional styles to be applied to the field.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+
+    /// Set the callback called when an edit is committed.
+    pub fn on_change(mut self, callback: Callback<f32>) -> Self {
+        self.on_change = Some(callback);
+        self
+    }
+}
+
+impl UiTemplate for NumberInput {
+    fn build(&self, builder: &mut UiBuilder) {
+        let value = self.value;
+        let precision = self.precision;
+        let format = self.format;
+        let on_change = self.on_change;
+
+        // Clamp on commit, using the range at the time the edit is committed.
+        let on_commit = on_change.map(|on_change| {
+            let (min, max) = (self.min, self.max);
+            builder.create_callback(move |value: In<f64>, mut world: DeferredWorld| {
+                let min = min.get(&world);
+                let max = max.get(&world);
+                world.run_callback(on_change, (*value as f32).clamp(min, max));
+            })
+        });
+
+        build_parsed_input(
+            builder,
+            ParsedInputProps {
+                size: self.size,
+                disabled: self.disabled,
+                style: self.style.clone(),
+            },
+            move |rcx| format.format(value.get(rcx) as f64, precision),
+            move |text| format.parse(text),
+            move |c| format.is_numeric_char(c),
+            on_commit,
+        );
+    }
+}
+
+/// A text field for editing a [`Vec3`], written as three separated components such as
+/// `1, 2, 3`. Each component may be an arithmetic expression.
+pub struct Vec3Input {
+    /// Current field value.
+    pub value: Signal<Vec3>,
+
+    /// Number of decimal places to display for each component.
+    pub precision: usize,
+
+    /// How numbers are written, e.g. which decimal separator to use.
+    pub format: NumberFormat,
+
+    /// Field size.
+    pub size: Size,
+
+    /// Whether the field is disabled.
+    pub disabled: Signal<bool>,
+
+    /// Additional styles to be applied to the field.
+    pub style: StyleHandle,
+
+    /// Callback called with the new value when an edit is committed.
+    pub on_change: Option<Callback<Vec3>>,
+}
+
+impl Default for Vec3Input {
+    fn default() -> Self {
+        Self {
+            value: Signal::Constant(Vec3::ZERO),
+            precision: 2,
+            format: NumberFormat::default(),
+            size: Size::default(),
+            disabled: Signal::Constant(false),
+            style: StyleHandle::default(),
+            on_change: None,
+        }
+    }
+}
+
+impl Vec3Input {
+    /// Construct a new `Vec3Input`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the current field value.
+    pub fn value(mut self, value: impl IntoSignal<Vec3>) -> Self {
+        self.value = value.into_signal();
+        self
+    }
+
+    /// Set the number of decimal places to display for each component.
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Set how numbers are written.
+    pub fn format(mut self, format: NumberFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Set the field size.
+    pub fn size(mut self, size: Size) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the field disabled state.
+    pub fn disabled(mut self, disabled: impl IntoSignal<bool>) -> Self {
+        self.disabled = disabled.into_signal();
+        self
+    }
+
+    /// Set additional styles to be applied to the field.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+
+    /// Set the callback called when an edit is committed.
+    pub fn on_change(mut self, callback: Callback<Vec3>) -> Self {
+        self.on_change = Some(callback);
+        self
+    }
+}
+
+impl UiTemplate for Vec3Input {
+    fn build(&self, builder: &mut UiBuilder) {
+        let value = self.value;
+        let precision = self.precision;
+        let format = self.format;
+
+        build_parsed_input(
+            builder,
+            ParsedInputProps {
+                size: self.size,
+                disabled: self.disabled,
+                style: self.style.clone(),
+            },
+            move |rcx| format.format_vec3(value.get(rcx), precision),
+            move |text| format.parse_vec3(text),
+            move |c| format.is_vector_char(c),
+            self.on_change,
+        );
+    }
+}
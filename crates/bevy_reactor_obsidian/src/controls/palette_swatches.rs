@@ -0,0 +1,227 @@
+use bevy::{
+    color::Srgba,
+    ecs::world::DeferredWorld,
+    input::ButtonState,
+    prelude::*,
+    ui::{self, RelativeCursorPosition},
+};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{
+    CreateChilden, EntityStyleBuilder, ForEachBuilder, InvokeUiTemplate, TextBuilder, UiBuilder,
+    UiTemplate,
+};
+use bevy_reactor_signals::{Callback, IntoSignal, Signal};
+
+use crate::{
+    colors,
+    input_dispatch::FocusKeyboardInput,
+    palettes::{ColorPalettes, PaletteId},
+};
+
+use super::SwatchGrid;
+
+fn style_palette_swatches(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch)
+        .gap(4);
+}
+
+fn style_palette_section(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .gap(2);
+}
+
+fn style_palette_label(ss: &mut StyleBuilder) {
+    ss.font_size(12).color(colors::DIM);
+}
+
+/// Marks the section which displays a palette, so that swatches dragged out of one palette
+/// can be dropped onto another.
+#[derive(Component, Clone, Copy)]
+struct PaletteDropTarget(PaletteId);
+
+/// The recent, pinned and named palettes from the [`ColorPalettes`] resource, each shown as a
+/// swatch grid, for use within a color picker. Clicking a swatch selects its color. Swatches
+/// can be dragged to reorder them within a palette, or dropped onto another palette to move
+/// them there. When a grid has keyboard focus, the arrow keys change the selection and `P`
+/// pins or unpins the selected color.
+///
+/// The selected color is not added to the recent colors automatically; the picker should call
+/// [`ColorPalettes::add_recent`] when the user commits a color.
+pub struct PaletteSwatches {
+    /// The currently selected color.
+    pub selected: Signal<Srgba>,
+
+    /// Number of swatches per row.
+    pub columns: u32,
+
+    /// Number of rows in each palette.
+    pub rows: u32,
+
+    /// Additional styles to be applied to the palette list.
+    pub style: StyleHandle,
+
+    /// Callback called when a swatch is clicked.
+    pub on_change: Option<Callback<Srgba>>,
+}
+
+impl Default for PaletteSwatches {
+    fn default() -> Self {
+        Self {
+            selected: Signal::Constant(Srgba::default()),
+            columns: 8,
+            rows: 2,
+            style: StyleHandle::default(),
+            on_change: None,
+        }
+    }
+}
+
+impl PaletteSwatches {
+    /// Create a new palette list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set which color is selected.
+    pub fn selected(mut self, selected: impl IntoSignal<Srgba>) -> Self {
+        self.selected = selected.into_signal();
+        self
+    }
+
+    /// Set the number of swatches per row.
+    pub fn columns(mut self, columns: u32) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Set the number of rows in each palette.
+    pub fn rows(mut self, rows: u32) -> Self {
+        self.rows = rows;
+        self
+    }
+
+    /// Set additional styles to be applied to the palette list.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+
+    /// Set the callback called when a swatch is clicked.
+    pub fn on_change(mut self, callback: Callback<Srgba>) -> Self {
+        self.on_change = Some(callback);
+        self
+    }
+}
+
+impl UiTemplate for PaletteSwatches {
+    fn build(&self, builder: &mut UiBuilder) {
+        let selected = self.selected;
+        let on_change = self.on_change;
+        let grid_size = UVec2::new(self.columns, self.rows);
+
+        builder
+            .spawn((Node::default(), Name::new("PaletteSwatches")))
+            .styles((style_palette_swatches, self.style.clone()))
+            .observe(
+                move |mut trigger: Trigger<FocusKeyboardInput>, mut world: DeferredWorld| {
+                    let event = &trigger.event().input;
+                    if event.state == ButtonState::Pressed && event.key_code == KeyCode::KeyP {
+                        trigger.propagate(false);
+                        let color = selected.get(&world);
+                        world.resource_mut::<ColorPalettes>().toggle_pin(color);
+                    }
+                },
+            )
+            .create_children(|builder| {
+                builder.for_each(
+                    |rcx| {
+                        let palettes = rcx.read_resource::<ColorPalettes>();
+                        let mut sections = vec![
+                            (PaletteId::Recent, "Recent".to_string()),
+                            (PaletteId::Pinned, "Pinned".to_string()),
+                        ];
+                        sections.extend(
+                            palettes
+                                .named
+                                .iter()
+                                .enumerate()
+                                .map(|(index, p)| (PaletteId::Named(index), p.name.clone())),
+                        );
+                        sections.into_iter()
+                    },
+                    move |(id, name), builder| {
+                        build_palette_section(builder, *id, name, grid_size, selected, on_change);
+                    },
+                    |_| {},
+                );
+            });
+    }
+}
+
+fn build_palette_section(
+    builder: &mut UiBuilder,
+    id: PaletteId,
+    name: &str,
+    grid_size: UVec2,
+    selected: Signal<Srgba>,
+    on_change: Option<Callback<Srgba>>,
+) {
+    let colors = builder.create_derived(move |rcx| {
+        rcx.read_resource::<ColorPalettes>()
+            .colors(id)
+            .iter()
+            .take((grid_size.x * grid_size.y) as usize)
+            .copied()
+            .collect::<Vec<_>>()
+    });
+    let on_drop_outside = builder.create_callback(
+        move |index: In<usize>,
+              q_targets: Query<(&PaletteDropTarget, &RelativeCursorPosition)>,
+              mut palettes: ResMut<ColorPalettes>| {
+            let target = q_targets
+                .iter()
+                .find(|(target, pos)| target.0 != id && pos.mouse_over());
+            if let Some((target, _)) = target {
+                palettes.move_color(id, *index, target.0);
+            }
+        },
+    );
+
+    let mut grid = SwatchGrid::new(colors)
+        .grid_size(grid_size)
+        .selected(selected)
+        .on_drop_outside(on_drop_outside);
+    // The recent colors are a history, so their order is not editable.
+    if id != PaletteId::Recent {
+        let on_reorder = builder.create_callback(
+            move |indices: In<(usize, usize)>, mut palettes: ResMut<ColorPalettes>| {
+                palettes.reorder(id, indices.0, indices.1);
+            },
+        );
+        grid = grid.on_reorder(on_reorder);
+    }
+    if let Some(on_change) = on_change {
+        grid = grid.on_change(on_change);
+    }
+
+    builder
+        .spawn((
+            Node::default(),
+            Name::new("PaletteSection"),
+            PaletteDropTarget(id),
+            RelativeCursorPosition::default(),
+        ))
+        .style(style_palette_section)
+        .create_children(|builder| {
+            builder
+                .spawn((Node::default(), Name::new("PaletteLabel")))
+                .style(style_palette_label)
+                .create_children(|builder| {
+                    builder.text(name.to_string());
+                });
+            builder.invoke(grid);
+        });
+}
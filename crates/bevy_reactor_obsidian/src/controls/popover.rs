@@ -0,0 +1,199 @@
+use std::sync::Arc;
+
+use bevy::{prelude::*, ui};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{
+    CondBuilder, CreateChilden, EntityStyleBuilder, InsertComponentBuilder, InvokeUiTemplate,
+    UiBuilder, UiTemplate,
+};
+use bevy_reactor_signals::{Callback, IntoSignal, Signal};
+
+use crate::{colors, layering::UiLayer, prelude::TabGroup, typography::text_default};
+
+use super::{barrier::Barrier, Portal};
+
+// Transparent backdrop which closes the popover when clicked.
+fn style_popover_barrier(ss: &mut StyleBuilder) {
+    ss.position(PositionType::Absolute)
+        .left(0)
+        .top(0)
+        .width(ui::Val::Vw(100.))
+        .height(ui::Val::Vh(100.));
+}
+
+fn style_popover(ss: &mut StyleBuilder) {
+    ss.position(PositionType::Absolute)
+        .display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch)
+        .background_color(colors::U2)
+        .border_color(colors::U1)
+        .border(1)
+        .border_radius(4.0)
+        .padding(8)
+        .row_gap(4);
+}
+
+/// Positions a popover below the element it is attached to.
+#[derive(Component)]
+pub(crate) struct PopoverAnchor(Entity);
+
+/// A floating panel attached to another element, such as the button which opened it. Unlike
+/// a [`Dialog`](super::Dialog), a popover does not dim the rest of the window: it sits over
+/// an invisible barrier, and clicking the barrier, or pressing Escape, calls `on_close`.
+///
+/// The popover is placed below its anchor, or above it if there is not enough room below.
+pub struct Popover {
+    /// Signal that controls whether the popover is open.
+    pub open: Signal<bool>,
+
+    /// The element the popover is placed next to.
+    pub anchor: Option<Entity>,
+
+    /// The content of the popover.
+    pub children: Arc<dyn Fn(&mut UiBuilder) + Send + Sync + 'static>,
+
+    /// Additional styles to be applied to the popover.
+    pub style: StyleHandle,
+
+    /// Callback called when the user clicks outside the popover or presses Escape.
+    pub on_close: Option<Callback>,
+}
+
+impl Default for Popover {
+    fn default() -> Self {
+        Self {
+            open: Signal::Constant(false),
+            anchor: None,
+            children: Arc::new(|_| {}),
+            style: StyleHandle::default(),
+            on_close: None,
+        }
+    }
+}
+
+impl Popover {
+    /// Creates a new `Popover`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the signal that controls whether the popover is open.
+    pub fn open(mut self, open: impl IntoSignal<bool>) -> Self {
+        self.open = open.into_signal();
+        self
+    }
+
+    /// Sets the element the popover is placed next to.
+    pub fn anchor(mut self, anchor: Entity) -> Self {
+        self.anchor = Some(anchor);
+        self
+    }
+
+    /// Sets the content of the popover.
+    pub fn children<V: 'static + Send + Sync + Fn(&mut UiBuilder)>(mut self, children: V) -> Self {
+        self.children = Arc::new(children);
+        self
+    }
+
+    /// Sets additional styles to be applied to the popover.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+
+    /// Sets the callback called when the popover should close.
+    pub fn on_close(mut self, on_close: Callback) -> Self {
+        self.on_close = Some(on_close);
+        self
+    }
+}
+
+impl UiTemplate for Popover {
+    fn build(&self, builder: &mut UiBuilder) {
+        let open = self.open.clone();
+        let on_close = self.on_close;
+        let anchor = self.anchor.unwrap_or(builder.parent());
+        let children = self.children.clone();
+        let style = self.style.clone();
+        builder.cond(
+            open,
+            move |builder| {
+                let children = children.clone();
+                let style = style.clone();
+                builder.invoke(Portal::new().children(move |builder| {
+                    let children = children.clone();
+                    let style = style.clone();
+                    builder
+                        .spawn((Node::default(), Name::new("Popover::Barrier")))
+                        .style(style_popover_barrier)
+                        .insert((Barrier { on_close }, UiLayer::Floating))
+                        .create_children(|builder| {
+                            builder
+                                .spawn((
+                                    Node::default(),
+                                    Name::new("Popover"),
+                                    PopoverAnchor(anchor),
+                                    // Hidden until it has been positioned.
+                                    Visibility::Hidden,
+                                    TabGroup {
+                                        order: 0,
+                                        modal: true,
+                                    },
+                                ))
+                                .styles((text_default, style_popover, style))
+                                .observe(|mut trigger: Trigger<Pointer<Down>>| {
+                                    // Prevent clicks from propagating to the barrier and
+                                    // closing the popover.
+                                    trigger.propagate(false);
+                                })
+                                .create_children(|builder| {
+                                    (children.as_ref())(builder);
+                                });
+                        });
+                }));
+            },
+            |_| {},
+        );
+    }
+}
+
+pub(crate) fn position_popovers(
+    mut q_popovers: Query<(
+        &PopoverAnchor,
+        &ComputedNode,
+        &Parent,
+        &mut Node,
+        &mut Visibility,
+    )>,
+    q_nodes: Query<(&ComputedNode, &GlobalTransform)>,
+) {
+    for (anchor, computed, parent, mut node, mut visibility) in q_popovers.iter_mut() {
+        let Ok((anchor_node, anchor_transform)) = q_nodes.get(anchor.0) else {
+            continue;
+        };
+        // The popover's parent is the barrier, which covers the window.
+        let Ok((window_node, _)) = q_nodes.get(parent.get()) else {
+            continue;
+        };
+        let scale = computed.inverse_scale_factor();
+        let rect = Rect::from_center_size(anchor_transform.translation().xy(), anchor_node.size());
+        let (min, max) = (rect.min * scale, rect.max * scale);
+        let size = computed.size() * scale;
+        let bounds = window_node.size() * scale;
+        let left = min.x.min(bounds.x - size.x).max(0.);
+        let top = if max.y + size.y > bounds.y && min.y >= size.y {
+            min.y - size.y
+        } else {
+            max.y
+        };
+        let (left, top) = (ui::Val::Px(left), ui::Val::Px(top));
+        if node.left != left || node.top != top {
+            node.left = left;
+            node.top = top;
+        }
+        if *visibility == Visibility::Hidden && size.x > 0. {
+            *visibility = Visibility::Inherited;
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use bevy::{ecs::world::DeferredWorld, prelude::*};
+use bevy_reactor_builder::{CreateChilden, UiBuilder, UiTemplate};
+
+/// Placed in the tree where the portal was invoked; owns the portal's root node, which is
+/// despawned along with it.
+#[derive(Component)]
+pub(crate) struct PortalRoot(Entity);
+
+/// Displays its children in a new top-level UI node, rather than as children of the node
+/// where the portal is invoked. This means that floating elements such as popups are not
+/// clipped by the overflow settings of their ancestors, and are positioned relative to the
+/// window. The children are still owned by the invoking template, and are despawned with it.
+#[derive(Clone)]
+pub struct Portal {
+    /// The content of the portal.
+    pub children: Arc<dyn Fn(&mut UiBuilder) + Send + Sync + 'static>,
+}
+
+impl Default for Portal {
+    fn default() -> Self {
+        Self {
+            children: Arc::new(|_| {}),
+        }
+    }
+}
+
+impl Portal {
+    /// Create a new portal.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the child views for this element.
+    pub fn children<V: 'static + Send + Sync + Fn(&mut UiBuilder)>(mut self, children: V) -> Self {
+        self.children = Arc::new(children);
+        self
+    }
+}
+
+impl UiTemplate for Portal {
+    fn build(&self, builder: &mut UiBuilder) {
+        // Render to the same camera as the node the portal was invoked from.
+        let parent = builder.parent();
+        let target_camera = {
+            let world = builder.world();
+            let mut entity = Some(parent);
+            let mut camera = None;
+            while let Some(e) = entity {
+                if let Some(target) = world.get::<TargetCamera>(e) {
+                    camera = Some(target.clone());
+                    break;
+                }
+                entity = world.get::<Parent>(e).map(|p| p.get());
+            }
+            camera
+        };
+
+        let mut root = builder.world_mut().spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(0.),
+                top: Val::Px(0.),
+                right: Val::Px(0.),
+                bottom: Val::Px(0.),
+                ..default()
+            },
+            PickingBehavior::IGNORE,
+            Name::new("Portal"),
+        ));
+        if let Some(target_camera) = target_camera {
+            root.insert(target_camera);
+        }
+        let children = self.children.clone();
+        root.create_children(|builder| {
+            (children.as_ref())(builder);
+        });
+        let root = root.id();
+
+        builder.spawn((PortalRoot(root), Name::new("PortalRoot")));
+    }
+}
+
+/// Despawn the portal's root node when the placeholder is removed.
+pub(crate) fn register_portal_hooks(world: &mut World) {
+    world.register_component_hooks::<PortalRoot>().on_remove(
+        |mut world: DeferredWorld, entity, _component| {
+            let root = world.get::<PortalRoot>(entity).unwrap().0;
+            world.commands().entity(root).try_despawn_recursive();
+        },
+    );
+}
@@ -0,0 +1,468 @@
+use bevy::{
+    color::LinearRgba, prelude::*, ui, ui::RelativeCursorPosition, window::SystemCursorIcon,
+    winit::cursor::CursorIcon,
+};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{
+    CreateChilden, EntityEffectBuilder, EntityStyleBuilder, TextBuilder, UiBuilder, UiTemplate,
+};
+use bevy_reactor_signals::{Callback, IntoSignal, RunCallback, Signal};
+
+use crate::{
+    colors,
+    cursor::StyleBuilderCursor,
+    focus_signal::CreateFocusSignal,
+    input_dispatch::{CapturesPointer, FocusKeyboardInput},
+    materials::{update_ui_material, CreateUiMaterial, SliderRectMaterial},
+    prelude::RoundedCorners,
+    tab_navigation::TabIndex,
+    typography,
+};
+
+use super::Disabled;
+
+fn style_range_slider(ss: &mut StyleBuilder) {
+    ss.min_width(64)
+        .height(20)
+        .display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .justify_content(ui::JustifyContent::Center)
+        .font_size(14)
+        .color(colors::FOREGROUND)
+        .cursor(CursorIcon::System(SystemCursorIcon::ColResize));
+}
+
+fn style_range_slider_vertical(ss: &mut StyleBuilder) {
+    ss.min_width(ui::Val::Auto)
+        .min_height(64)
+        .width(20)
+        .height(ui::Val::Auto)
+        .flex_direction(ui::FlexDirection::Column)
+        .cursor(CursorIcon::System(SystemCursorIcon::RowResize));
+}
+
+fn style_thumb(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .background_color(colors::FOREGROUND.with_alpha(0.7))
+        .border_radius(1);
+}
+
+/// Identifies one of the two thumbs of a [`RangeSlider`].
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) enum RangeThumb {
+    Low,
+    High,
+}
+
+/// Component which holds the state of a range slider.
+#[derive(Component)]
+#[require(CapturesPointer)]
+pub(crate) struct RangeSliderState {
+    low: f32,
+    high: f32,
+    min: f32,
+    max: f32,
+    step: f32,
+    precision: usize,
+    vertical: bool,
+    on_change: Option<Callback<(f32, f32)>>,
+    /// The thumb which is currently being dragged.
+    active: Option<RangeThumb>,
+    /// The value of the active thumb when dragging started.
+    offset: f32,
+}
+
+impl RangeSliderState {
+    /// Compute a new (low, high) pair after moving the given thumb, and notify the listener.
+    fn change(&self, thumb: RangeThumb, value: f32, commands: &mut Commands) {
+        let rounding = f32::powi(10., self.precision as i32);
+        let value = ((value * rounding).round() / rounding).clamp(self.min, self.max);
+        let (low, high) = match thumb {
+            RangeThumb::Low => (value.min(self.high), self.high),
+            RangeThumb::High => (self.low, value.max(self.low)),
+        };
+        if low != self.low || high != self.high {
+            if let Some(on_change) = self.on_change {
+                commands.run_callback(on_change, (low, high));
+            }
+        }
+    }
+
+    fn thumb_value(&self, thumb: RangeThumb) -> f32 {
+        match thumb {
+            RangeThumb::Low => self.low,
+            RangeThumb::High => self.high,
+        }
+    }
+}
+
+/// Component which identifies a thumb entity within a range slider.
+#[derive(Component)]
+pub(crate) struct RangeSliderThumb {
+    slider: Entity,
+    thumb: RangeThumb,
+}
+
+/// A slider with two thumbs, used to edit a range of values.
+pub struct RangeSlider {
+    /// Low end of the selected range.
+    pub low: Signal<f32>,
+
+    /// High end of the selected range.
+    pub high: Signal<f32>,
+
+    /// Minimum slider value.
+    pub min: Signal<f32>,
+
+    /// Maximum slider value.
+    pub max: Signal<f32>,
+
+    /// Number of decimal places to round to (0 = integer).
+    pub precision: usize,
+
+    /// Amount to increment when using the keyboard.
+    pub step: f32,
+
+    /// Whether the slider is disabled.
+    pub disabled: Signal<bool>,
+
+    /// If true, the slider is oriented vertically, with the minimum value at the bottom.
+    pub vertical: bool,
+
+    /// Style handle for slider root element.
+    pub style: StyleHandle,
+
+    /// Callback called with the new (low, high) values when either thumb is moved.
+    pub on_change: Option<Callback<(f32, f32)>>,
+}
+
+impl RangeSlider {
+    /// Create a new range slider.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the low end of the selected range.
+    pub fn low(mut self, low: impl IntoSignal<f32>) -> Self {
+        self.low = low.into_signal();
+        self
+    }
+
+    /// Set the high end of the selected range.
+    pub fn high(mut self, high: impl IntoSignal<f32>) -> Self {
+        self.high = high.into_signal();
+        self
+    }
+
+    /// Set the minimum slider value.
+    pub fn min(mut self, min: impl IntoSignal<f32>) -> Self {
+        self.min = min.into_signal();
+        self
+    }
+
+    /// Set the maximum slider value.
+    pub fn max(mut self, max: impl IntoSignal<f32>) -> Self {
+        self.max = max.into_signal();
+        self
+    }
+
+    /// Set the number of decimal places to round to (0 = integer).
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Set the amount to increment when using the keyboard.
+    pub fn step(mut self, step: f32) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Set whether the slider is disabled.
+    pub fn disabled(mut self, disabled: impl IntoSignal<bool>) -> Self {
+        self.disabled = disabled.into_signal();
+        self
+    }
+
+    /// Set whether the slider is oriented vertically.
+    pub fn vertical(mut self, vertical: bool) -> Self {
+        self.vertical = vertical;
+        self
+    }
+
+    /// Set the style handle for the slider root element.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+
+    /// Set the callback called when either thumb is moved.
+    pub fn on_change(mut self, on_change: Callback<(f32, f32)>) -> Self {
+        self.on_change = Some(on_change);
+        self
+    }
+}
+
+impl Default for RangeSlider {
+    fn default() -> Self {
+        Self {
+            low: Signal::Constant(0.),
+            high: Signal::Constant(1.),
+            min: Signal::Constant(0.),
+            max: Signal::Constant(1.),
+            precision: 0,
+            step: 1.,
+            disabled: Signal::Constant(false),
+            vertical: false,
+            style: StyleHandle::default(),
+            on_change: None,
+        }
+    }
+}
+
+/// Convert a value to a position along the slider in the range 0..1.
+fn slider_position(value: f32, min: f32, max: f32) -> f32 {
+    if max > min {
+        ((value - min) / (max - min)).clamp(0., 1.)
+    } else {
+        0.
+    }
+}
+
+impl UiTemplate for RangeSlider {
+    fn build(&self, builder: &mut UiBuilder) {
+        let low = self.low;
+        let high = self.high;
+        let min = self.min;
+        let max = self.max;
+        let precision = self.precision;
+        let vertical = self.vertical;
+        let disabled = self.disabled;
+
+        let material = builder.create_ui_material(SliderRectMaterial {
+            color_lo: LinearRgba::from(colors::U1).to_vec4(),
+            color_hi: LinearRgba::from(colors::U3).to_vec4(),
+            value: Vec4::new(1., 0., if vertical { 1. } else { 0. }, 0.),
+            radius: RoundedCorners::All.to_vec(4.),
+        });
+        let material_id = material.id();
+
+        // Effect to update the material with the filled segment between the thumbs.
+        builder.create_effect(move |ecx| {
+            let min = min.get(ecx);
+            let max = max.get(ecx);
+            let lo_pos = slider_position(low.get(ecx), min, max);
+            let hi_pos = slider_position(high.get(ecx), min, max);
+            update_ui_material(ecx.world_mut(), material_id, |material| {
+                material.value.x = hi_pos;
+                material.value.y = lo_pos;
+            });
+        });
+
+        let slider_id = builder
+            .spawn((
+                MaterialNode(material),
+                Name::new("RangeSlider"),
+                RelativeCursorPosition::default(),
+                RangeSliderState {
+                    low: 0.,
+                    high: 0.,
+                    min: 0.,
+                    max: 0.,
+                    step: self.step,
+                    precision,
+                    vertical,
+                    on_change: self.on_change,
+                    active: None,
+                    offset: 0.,
+                },
+            ))
+            .id();
+
+        builder
+            .entity_mut(slider_id)
+            .styles((
+                typography::text_default,
+                style_range_slider,
+                move |ss: &mut StyleBuilder| {
+                    if vertical {
+                        style_range_slider_vertical(ss);
+                    }
+                },
+                self.style.clone(),
+            ))
+            .component_effect::<RangeSliderState, _, _, _>(
+                move |rcx| (low.get(rcx), high.get(rcx), min.get(rcx), max.get(rcx)),
+                |(low, high, min, max), mut state| {
+                    state.low = low;
+                    state.high = high;
+                    state.min = min;
+                    state.max = max;
+                },
+            )
+            .effect(
+                move |rcx| disabled.get(rcx),
+                |disabled, ent| {
+                    if disabled {
+                        ent.insert(Disabled);
+                    } else {
+                        ent.remove::<Disabled>();
+                    }
+                },
+            )
+            .create_children(|builder| {
+                for (thumb, signal) in [(RangeThumb::Low, low), (RangeThumb::High, high)] {
+                    let thumb_id = builder
+                        .spawn((
+                            Node::default(),
+                            Name::new("RangeSlider::Thumb"),
+                            TabIndex(0),
+                            RangeSliderThumb {
+                                slider: slider_id,
+                                thumb,
+                            },
+                        ))
+                        .id();
+                    let focused = builder.create_focus_visible_signal(thumb_id);
+                    builder
+                        .entity_mut(thumb_id)
+                        .style(style_thumb)
+                        .style_dyn(
+                            move |rcx| slider_position(signal.get(rcx), min.get(rcx), max.get(rcx)),
+                            move |pos, sb| {
+                                if vertical {
+                                    sb.left(0)
+                                        .right(0)
+                                        .height(4)
+                                        .bottom(ui::Val::Percent(pos * 100.))
+                                        .margin_bottom(-2);
+                                } else {
+                                    sb.top(0)
+                                        .bottom(0)
+                                        .width(4)
+                                        .left(ui::Val::Percent(pos * 100.))
+                                        .margin_left(-2);
+                                }
+                            },
+                        )
+                        .style_dyn(
+                            move |rcx| focused.get(rcx),
+                            |is_focused, sb| {
+                                if is_focused {
+                                    sb.outline_color(colors::FOCUS)
+                                        .outline_width(2)
+                                        .outline_offset(1);
+                                } else {
+                                    sb.outline_color(colors::TRANSPARENT)
+                                        .outline_width(0)
+                                        .outline_offset(0);
+                                }
+                            },
+                        );
+                }
+                builder.text_computed(move |rcx| {
+                    format!(
+                        "{:.*} - {:.*}",
+                        precision,
+                        low.get(rcx),
+                        precision,
+                        high.get(rcx)
+                    )
+                });
+            });
+    }
+}
+
+pub(crate) fn range_slider_on_drag_start(
+    mut trigger: Trigger<Pointer<DragStart>>,
+    mut q_state: Query<(
+        &mut RangeSliderState,
+        &RelativeCursorPosition,
+        Has<Disabled>,
+    )>,
+) {
+    if let Ok((mut state, cursor, disabled)) = q_state.get_mut(trigger.entity()) {
+        trigger.propagate(false);
+        if disabled {
+            return;
+        }
+        // Choose the thumb which is nearest to the cursor.
+        let range = state.max - state.min;
+        let value = match cursor.normalized {
+            Some(pos) if state.vertical => state.min + (1. - pos.y) * range,
+            Some(pos) => state.min + pos.x * range,
+            None => state.low,
+        };
+        let thumb = if (value - state.low).abs() < (value - state.high).abs()
+            || (state.low == state.high && value < state.low)
+        {
+            RangeThumb::Low
+        } else {
+            RangeThumb::High
+        };
+        state.active = Some(thumb);
+        state.offset = state.thumb_value(thumb);
+    }
+}
+
+pub(crate) fn range_slider_on_drag(
+    mut trigger: Trigger<Pointer<Drag>>,
+    q_state: Query<(&ComputedNode, &RangeSliderState)>,
+    mut commands: Commands,
+) {
+    if let Ok((node, state)) = q_state.get(trigger.entity()) {
+        trigger.propagate(false);
+        if let Some(thumb) = state.active {
+            let distance = trigger.event().distance;
+            let (slider_length, delta) = if state.vertical {
+                (node.size().y, -distance.y)
+            } else {
+                (node.size().x, distance.x)
+            };
+            let range = state.max - state.min;
+            if range > 0. && slider_length > 0. {
+                let new_value = state.offset + (delta * range) / slider_length;
+                state.change(thumb, new_value, &mut commands);
+            }
+        }
+    }
+}
+
+pub(crate) fn range_slider_on_drag_end(
+    mut trigger: Trigger<Pointer<DragEnd>>,
+    mut q_state: Query<&mut RangeSliderState>,
+) {
+    if let Ok(mut state) = q_state.get_mut(trigger.entity()) {
+        trigger.propagate(false);
+        state.active = None;
+    }
+}
+
+pub(crate) fn range_slider_thumb_on_key_input(
+    mut trigger: Trigger<FocusKeyboardInput>,
+    q_thumb: Query<&RangeSliderThumb>,
+    q_state: Query<(&RangeSliderState, Has<Disabled>)>,
+    mut commands: Commands,
+) {
+    let Ok(thumb) = q_thumb.get(trigger.entity()) else {
+        return;
+    };
+    let Ok((state, disabled)) = q_state.get(thumb.slider) else {
+        return;
+    };
+    let event = &trigger.event().input;
+    if disabled || event.state != bevy::input::ButtonState::Pressed {
+        return;
+    }
+    let value = state.thumb_value(thumb.thumb);
+    let new_value = match event.key_code {
+        KeyCode::ArrowLeft | KeyCode::ArrowDown => value - state.step,
+        KeyCode::ArrowRight | KeyCode::ArrowUp => value + state.step,
+        KeyCode::Home => state.min,
+        KeyCode::End => state.max,
+        _ => return,
+    };
+    trigger.propagate(false);
+    state.change(thumb.thumb, new_value, &mut commands);
+}
@@ -3,7 +3,7 @@ use std::sync::Arc;
 use bevy::{ecs::world::DeferredWorld, prelude::*, ui};
 use bevy_mod_stylebuilder::*;
 use bevy_reactor_builder::{CreateChilden, EntityStyleBuilder, UiBuilder, UiTemplate};
-use bevy_reactor_signals::Mutable;
+use bevy_reactor_signals::{Callback, Mutable, RunCallback};
 
 use crate::scrolling::{ScrollArea, ScrollBar, ScrollBarThumb, ScrollContent, ScrollWheelEvent};
 
@@ -97,6 +97,8 @@ pub struct ScrollView {
     /// Optional entity id to use for the scrolling element. This is useful for querying the
     /// current scroll position.
     pub entity: Option<Entity>,
+    /// Callback called with the new scroll position whenever the view is scrolled.
+    pub on_scroll: Option<Callback<Vec2>>,
 }
 
 impl Default for ScrollView {
@@ -108,6 +110,7 @@ impl Default for ScrollView {
             scroll_enable_x: Default::default(),
             scroll_enable_y: Default::default(),
             entity: Default::default(),
+            on_scroll: Default::default(),
         }
     }
 }
@@ -154,6 +157,14 @@ impl ScrollView {
         self.entity = Some(entity);
         self
     }
+
+    /// Set the callback called when the view is scrolled. Use
+    /// [`create_scroll_signals`](crate::scrolling::CreateScrollSignals::create_scroll_signals)
+    /// to track other aspects of the scroll state.
+    pub fn on_scroll(mut self, on_scroll: Callback<Vec2>) -> Self {
+        self.on_scroll = Some(on_scroll);
+        self
+    }
 }
 
 impl UiTemplate for ScrollView {
@@ -225,6 +236,20 @@ impl UiTemplate for ScrollView {
                             (self.children.as_ref())(builder);
                         });
                 });
+
+            if let Some(on_scroll) = self.on_scroll {
+                // Report changes of position, but not the initial position.
+                let mut last_offset = None;
+                builder.create_effect(move |ecx| {
+                    let offset = ecx.use_component_map(id_scroll_area, ScrollArea::offset);
+                    if let (Some(_), Some(offset)) = (last_offset, offset) {
+                        if last_offset != Some(offset) {
+                            ecx.run_callback(on_scroll, offset);
+                        }
+                    }
+                    last_offset = offset;
+                });
+            }
         });
     }
 }
@@ -0,0 +1,346 @@
+use std::ops::Range;
+
+use bevy::{
+    ecs::world::DeferredWorld,
+    input::{keyboard::Key, ButtonState},
+    prelude::*,
+    text::TextLayoutInfo,
+    ui,
+};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{CreateChilden, EntityStyleBuilder, TextBuilder, UiBuilder, UiTemplate};
+use bevy_reactor_signals::{IntoSignal, Signal};
+
+use crate::{
+    clipboard::Clipboard,
+    colors,
+    input_dispatch::{FocusKeyboardInput, KeyboardFocus, SetKeyboardFocus},
+    selection::SelectModifiers,
+};
+
+fn style_selectable_text(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Relative);
+}
+
+fn style_highlight_layer(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .left(0)
+        .top(0)
+        .right(0)
+        .bottom(0);
+}
+
+/// The selected range of a [`SelectableText`], in glyphs. The anchor is where the selection
+/// started, and the focus is where it currently ends, so the focus may come before the anchor.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TextSelection {
+    /// Glyph index where the selection started.
+    pub anchor: usize,
+    /// Glyph index where the selection ends.
+    pub focus: usize,
+}
+
+impl TextSelection {
+    /// The selected glyphs, in order.
+    pub fn range(&self) -> Range<usize> {
+        self.anchor.min(self.focus)..self.anchor.max(self.focus)
+    }
+
+    /// Whether no glyphs are selected.
+    pub fn is_empty(&self) -> bool {
+        self.anchor == self.focus
+    }
+}
+
+/// Marks the node which holds the selection highlight rectangles.
+#[derive(Component)]
+pub(crate) struct SelectionHighlights;
+
+/// Read-only text which can be selected by dragging over it, and copied with Ctrl+C. Ctrl+A
+/// selects all of the text. Use this instead of plain text for values the user may want to
+/// copy, such as log output or inspector values.
+pub struct SelectableText {
+    /// The text to display.
+    pub text: Signal<String>,
+
+    /// Additional styles to be applied to the text.
+    pub style: StyleHandle,
+}
+
+impl SelectableText {
+    /// Create selectable text with the given content.
+    pub fn new(text: impl IntoSignal<String>) -> Self {
+        Self {
+            text: text.into_signal(),
+            style: StyleHandle::default(),
+        }
+    }
+
+    /// Set additional styles to be applied to the text.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl UiTemplate for SelectableText {
+    fn build(&self, builder: &mut UiBuilder) {
+        let text = self.text.clone();
+        builder
+            .spawn((
+                Node::default(),
+                Name::new("SelectableText"),
+                TextSelection::default(),
+            ))
+            .styles((style_selectable_text, self.style.clone()))
+            .observe(
+                |mut trigger: Trigger<Pointer<Down>>,
+                 q_text: TextLayoutQuery,
+                 keys: Res<ButtonInput<KeyCode>>,
+                 mut q_selection: Query<&mut TextSelection>,
+                 mut commands: Commands| {
+                    trigger.propagate(false);
+                    let id = trigger.entity();
+                    commands.queue(move |world: &mut World| world.set_keyboard_focus(id));
+                    let Some(index) =
+                        q_text.glyph_at(id, trigger.event().pointer_location.position)
+                    else {
+                        return;
+                    };
+                    if let Ok(mut selection) = q_selection.get_mut(id) {
+                        // Shift-click extends the existing selection.
+                        let anchor = match SelectModifiers::from_keys(&keys).shift {
+                            true => selection.anchor,
+                            false => index,
+                        };
+                        selection.set_if_neq(TextSelection {
+                            anchor,
+                            focus: index,
+                        });
+                    }
+                },
+            )
+            .observe(
+                |mut trigger: Trigger<Pointer<Drag>>,
+                 q_text: TextLayoutQuery,
+                 mut q_selection: Query<&mut TextSelection>| {
+                    trigger.propagate(false);
+                    let id = trigger.entity();
+                    let Some(index) =
+                        q_text.glyph_at(id, trigger.event().pointer_location.position)
+                    else {
+                        return;
+                    };
+                    if let Ok(mut selection) = q_selection.get_mut(id) {
+                        if selection.focus != index {
+                            selection.focus = index;
+                        }
+                    }
+                },
+            )
+            .observe(
+                |mut trigger: Trigger<FocusKeyboardInput>, mut world: DeferredWorld| {
+                    let event = trigger.event().input.clone();
+                    if event.state != ButtonState::Pressed {
+                        return;
+                    }
+                    let keys = world.resource::<ButtonInput<KeyCode>>();
+                    if !SelectModifiers::from_keys(keys).ctrl {
+                        return;
+                    }
+                    let id = trigger.entity();
+                    match &event.logical_key {
+                        Key::Character(c) if c.eq_ignore_ascii_case("c") => {
+                            trigger.propagate(false);
+                            if let Some(text) = selected_text(&world, id) {
+                                world.resource_mut::<Clipboard>().set_text(text);
+                            }
+                        }
+                        Key::Character(c) if c.eq_ignore_ascii_case("a") => {
+                            trigger.propagate(false);
+                            let count = text_entity(&world, id)
+                                .and_then(|text| world.get::<TextLayoutInfo>(text))
+                                .map_or(0, |layout| layout.glyphs.len());
+                            if let Some(mut selection) = world.get_mut::<TextSelection>(id) {
+                                selection.set_if_neq(TextSelection {
+                                    anchor: 0,
+                                    focus: count,
+                                });
+                            }
+                        }
+                        _ => {}
+                    }
+                },
+            )
+            .create_children(|builder| {
+                builder
+                    .spawn((
+                        Node::default(),
+                        Name::new("SelectableText::Highlights"),
+                        SelectionHighlights,
+                        PickingBehavior::IGNORE,
+                    ))
+                    .style(style_highlight_layer);
+                builder.text_computed(move |rcx| text.get_clone(rcx));
+            });
+    }
+}
+
+/// Queries needed to find the glyph under the pointer.
+#[derive(bevy::ecs::system::SystemParam)]
+pub(crate) struct TextLayoutQuery<'w, 's> {
+    q_children: Query<'w, 's, &'static Children>,
+    q_layout: Query<
+        'w,
+        's,
+        (
+            &'static TextLayoutInfo,
+            &'static ComputedNode,
+            &'static GlobalTransform,
+        ),
+    >,
+}
+
+impl TextLayoutQuery<'_, '_> {
+    /// Find the caret position nearest to a point in window coordinates, as the index of the
+    /// glyph which follows it.
+    fn glyph_at(&self, selectable: Entity, position: Vec2) -> Option<usize> {
+        let (layout, node, transform) = self
+            .q_children
+            .get(selectable)
+            .ok()?
+            .iter()
+            .find_map(|child| self.q_layout.get(*child).ok())?;
+        // Glyphs are positioned in physical pixels, relative to the top left of the text.
+        let origin = transform.translation().xy() - node.size() * 0.5;
+        let point = position / node.inverse_scale_factor() - origin;
+        Some(caret_index(layout, point))
+    }
+}
+
+/// Index of the caret position nearest to `point`: the first glyph on the closest line whose
+/// center is to the right of the point, or the end of that line.
+fn caret_index(layout: &TextLayoutInfo, point: Vec2) -> usize {
+    let line = layout
+        .glyphs
+        .iter()
+        .min_by(|a, b| {
+            let da = (a.position.y - point.y).abs();
+            let db = (b.position.y - point.y).abs();
+            da.total_cmp(&db)
+        })
+        .map(|glyph| glyph.line_index);
+    let Some(line) = line else {
+        return 0;
+    };
+    let mut last = 0;
+    for (index, glyph) in layout.glyphs.iter().enumerate() {
+        if glyph.line_index != line {
+            continue;
+        }
+        if point.x < glyph.position.x {
+            return index;
+        }
+        last = index + 1;
+    }
+    last
+}
+
+/// The text child of a [`SelectableText`].
+fn text_entity(world: &DeferredWorld, selectable: Entity) -> Option<Entity> {
+    world
+        .get::<Children>(selectable)?
+        .iter()
+        .copied()
+        .find(|child| world.get::<TextLayoutInfo>(*child).is_some())
+}
+
+/// The currently selected portion of the text.
+fn selected_text(world: &DeferredWorld, selectable: Entity) -> Option<String> {
+    let selection = world.get::<TextSelection>(selectable)?;
+    if selection.is_empty() {
+        return None;
+    }
+    let text_id = text_entity(world, selectable)?;
+    let text = &world.get::<Text>(text_id)?.0;
+    let glyphs = &world.get::<TextLayoutInfo>(text_id)?.glyphs;
+    let range = selection.range();
+    let start = glyphs.get(range.start)?.byte_index;
+    let end = glyphs
+        .get(range.end)
+        .map_or(text.len(), |glyph| glyph.byte_index);
+    text.get(start..end).map(str::to_string)
+}
+
+/// Clear the selection of any selectable text which no longer has keyboard focus.
+pub(crate) fn clear_unfocused_text_selections(
+    focus: Res<KeyboardFocus>,
+    mut q_selections: Query<(Entity, &mut TextSelection)>,
+) {
+    for (entity, mut selection) in q_selections.iter_mut() {
+        if focus.0 != Some(entity) && !selection.is_empty() {
+            *selection = TextSelection::default();
+        }
+    }
+}
+
+/// Rebuild the highlight rectangles of selectable text whose selection or layout changed.
+/// Each line of the selection gets one rectangle, placed behind the text.
+pub(crate) fn update_text_selection_highlights(
+    q_selections: Query<(Entity, Ref<TextSelection>, &Children)>,
+    q_highlights: Query<Entity, With<SelectionHighlights>>,
+    q_layout: Query<(Ref<TextLayoutInfo>, &ComputedNode, &GlobalTransform)>,
+    q_nodes: Query<(&ComputedNode, &GlobalTransform)>,
+    mut commands: Commands,
+) {
+    for (entity, selection, children) in q_selections.iter() {
+        let Some(layer) = children.iter().find(|c| q_highlights.contains(**c)) else {
+            continue;
+        };
+        let Some((layout, text_node, text_transform)) =
+            children.iter().find_map(|c| q_layout.get(*c).ok())
+        else {
+            continue;
+        };
+        if !selection.is_changed() && !layout.is_changed() {
+            continue;
+        }
+        let Ok((node, transform)) = q_nodes.get(entity) else {
+            continue;
+        };
+
+        let scale = node.inverse_scale_factor();
+        // Offset of the text within the selectable node, in physical pixels.
+        let offset = (text_transform.translation().xy() - text_node.size() * 0.5)
+            - (transform.translation().xy() - node.size() * 0.5);
+        let mut lines: Vec<(usize, Rect)> = Vec::new();
+        for glyph in layout.glyphs.get(selection.range()).unwrap_or_default() {
+            let rect = Rect::from_center_size(glyph.position + offset, glyph.size);
+            match lines.last_mut() {
+                Some((line, bounds)) if *line == glyph.line_index => {
+                    *bounds = bounds.union(rect);
+                }
+                _ => lines.push((glyph.line_index, rect)),
+            }
+        }
+
+        let mut layer = commands.entity(*layer);
+        layer.despawn_descendants();
+        layer.with_children(|builder| {
+            for (_, rect) in lines {
+                builder.spawn((
+                    Node {
+                        position_type: ui::PositionType::Absolute,
+                        left: ui::Val::Px(rect.min.x * scale),
+                        top: ui::Val::Px(rect.min.y * scale),
+                        width: ui::Val::Px(rect.width() * scale),
+                        height: ui::Val::Px(rect.height() * scale),
+                        ..default()
+                    },
+                    BackgroundColor(colors::TEXT_SELECT.into()),
+                    PickingBehavior::IGNORE,
+                ));
+            }
+        });
+    }
+}
@@ -0,0 +1,484 @@
+use std::{ops::Range, sync::Arc};
+
+use accesskit::{self, Role};
+use bevy::{a11y::AccessibilityNode, ecs::world::DeferredWorld, prelude::*, ui};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{
+    CondBuilder, CreateChilden, EntityStyleBuilder, ForEachBuilder, InvokeUiTemplate, TextBuilder,
+    UiBuilder, UiTemplate,
+};
+use bevy_reactor_signals::{Mutable, Rcx};
+
+use crate::{
+    colors, fuzzy::fuzzy_filter, input_dispatch::KeyboardFocus, layering::UiLayer, size::Size,
+    tab_navigation::TabGroup, typography,
+};
+
+use super::{
+    barrier::Barrier, CommandPaletteHotkey, CommandRegistry, HighlightedText, ScrollView, TextInput,
+};
+
+fn style_help_barrier(ss: &mut StyleBuilder) {
+    ss.position(PositionType::Absolute)
+        .display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Center)
+        .left(0)
+        .top(0)
+        .width(ui::Val::Vw(100.))
+        .height(ui::Val::Vh(100.))
+        .padding_top(ui::Val::Vh(10.))
+        .background_color(colors::U1.with_alpha(0.5));
+}
+
+fn style_help(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch)
+        .width(520)
+        .max_height(ui::Val::Vh(75.))
+        .background_color(colors::U2)
+        .border(1)
+        .border_color(colors::U3)
+        .border_radius(6.0)
+        .padding(6)
+        .gap(6);
+}
+
+fn style_help_list(ss: &mut StyleBuilder) {
+    ss.flex_grow(1.).flex_shrink(1.).min_height(0);
+}
+
+fn style_group(ss: &mut StyleBuilder) {
+    ss.color(colors::DIM)
+        .font_size(12)
+        .padding((8, 2))
+        .margin_top(6);
+}
+
+fn style_row(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .column_gap(8)
+        .min_height(22)
+        .padding((8, 0))
+        .color(colors::FOREGROUND);
+}
+
+fn style_scope(ss: &mut StyleBuilder) {
+    ss.color(colors::DIM).font_size(12);
+}
+
+fn style_keys(ss: &mut StyleBuilder) {
+    ss.margin_left(ui::Val::Auto)
+        .padding((6, 1))
+        .border_radius(3.0)
+        .background_color(colors::U3)
+        .font_size(12);
+}
+
+fn style_no_results(ss: &mut StyleBuilder) {
+    ss.color(colors::DIM).padding((8, 4));
+}
+
+/// A keyboard shortcut listed in the [`ShortcutHelp`] overlay.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Shortcut {
+    /// Heading the shortcut is listed under, such as "Viewport" or "Commands".
+    pub category: String,
+
+    /// Where the shortcut applies, such as "Global" or the name of a panel.
+    pub scope: String,
+
+    /// Description of the key combination, such as "Ctrl+P".
+    pub keys: String,
+
+    /// What the shortcut does.
+    pub description: String,
+}
+
+impl Shortcut {
+    /// Construct a new shortcut description.
+    pub fn new(
+        category: impl Into<String>,
+        scope: impl Into<String>,
+        keys: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        Self {
+            category: category.into(),
+            scope: scope.into(),
+            keys: keys.into(),
+            description: description.into(),
+        }
+    }
+}
+
+type ShortcutSource = Arc<dyn Fn(&World) -> Vec<Shortcut> + Send + Sync>;
+
+/// Resource which collects the keyboard shortcuts shown in the [`ShortcutHelp`] overlay.
+///
+/// Rather than storing a fixed list, the registry holds functions which describe the
+/// shortcuts from the resources that actually define them, so the list is regenerated each
+/// time the overlay opens and reflects any rebinding. Commands in the [`CommandRegistry`]
+/// which have a shortcut are included automatically.
+#[derive(Resource, Default)]
+pub struct ShortcutRegistry {
+    sources: Vec<ShortcutSource>,
+}
+
+impl ShortcutRegistry {
+    /// Add a function which describes a set of shortcuts.
+    pub fn add_source(
+        &mut self,
+        source: impl Fn(&World) -> Vec<Shortcut> + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.sources.push(Arc::new(source));
+        self
+    }
+
+    /// Collect all shortcuts, sorted by category.
+    pub fn collect(&self, world: &World) -> Vec<Shortcut> {
+        let mut shortcuts: Vec<Shortcut> = self
+            .sources
+            .iter()
+            .flat_map(|source| source(world))
+            .collect();
+        if let Some(commands) = world.get_resource::<CommandRegistry>() {
+            shortcuts.extend(commands.iter().filter_map(|cmd| {
+                Some(Shortcut::new(
+                    "Commands",
+                    "Global",
+                    cmd.shortcut.clone()?,
+                    cmd.name.clone(),
+                ))
+            }));
+        }
+        // Stable sort, so that shortcuts within a category keep their registration order.
+        shortcuts.sort_by(|a, b| a.category.cmp(&b.category));
+        shortcuts
+    }
+}
+
+/// Extension trait which adds a source of shortcuts to the [`ShortcutRegistry`].
+pub trait RegisterShortcuts {
+    /// Register a function which describes a set of shortcuts.
+    fn register_shortcuts(
+        &mut self,
+        source: impl Fn(&World) -> Vec<Shortcut> + Send + Sync + 'static,
+    ) -> &mut Self;
+}
+
+impl RegisterShortcuts for App {
+    fn register_shortcuts(
+        &mut self,
+        source: impl Fn(&World) -> Vec<Shortcut> + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_insert_with(ShortcutRegistry::default)
+            .add_source(source);
+        self
+    }
+}
+
+/// Format a key for display, such as "P", "1" or "Esc".
+pub fn key_name(key: KeyCode) -> String {
+    let name = match key {
+        KeyCode::ControlLeft | KeyCode::ControlRight => "Ctrl",
+        KeyCode::ShiftLeft | KeyCode::ShiftRight => "Shift",
+        KeyCode::AltLeft | KeyCode::AltRight => "Alt",
+        KeyCode::SuperLeft | KeyCode::SuperRight => "Cmd",
+        KeyCode::Escape => "Esc",
+        KeyCode::Slash => "/",
+        KeyCode::Backslash => "\\",
+        KeyCode::Comma => ",",
+        KeyCode::Period => ".",
+        KeyCode::Semicolon => ";",
+        KeyCode::Quote => "'",
+        KeyCode::Minus => "-",
+        KeyCode::Equal => "=",
+        KeyCode::BracketLeft => "[",
+        KeyCode::BracketRight => "]",
+        KeyCode::Backquote => "`",
+        KeyCode::ArrowUp => "Up",
+        KeyCode::ArrowDown => "Down",
+        KeyCode::ArrowLeft => "Left",
+        KeyCode::ArrowRight => "Right",
+        _ => {
+            let name = format!("{:?}", key);
+            return name
+                .strip_prefix("Key")
+                .or_else(|| name.strip_prefix("Digit"))
+                .unwrap_or(&name)
+                .to_string();
+        }
+    };
+    name.to_string()
+}
+
+/// Format a key combination for display, such as "Ctrl+Shift+P".
+pub fn format_hotkey(key: KeyCode, ctrl: bool, shift: bool) -> String {
+    let mut result = String::new();
+    if ctrl {
+        result.push_str("Ctrl+");
+    }
+    if shift {
+        result.push_str("Shift+");
+    }
+    result.push_str(&key_name(key));
+    result
+}
+
+/// Resource which controls whether the [`ShortcutHelp`] overlay is open.
+#[derive(Resource, Default)]
+pub struct ShortcutHelpState {
+    /// Whether the overlay is currently shown.
+    pub open: bool,
+}
+
+/// Resource which holds the key combination that toggles the [`ShortcutHelp`] overlay.
+#[derive(Resource, Clone, Copy)]
+pub struct ShortcutHelpHotkey {
+    /// The key which opens the overlay.
+    pub key: KeyCode,
+
+    /// Whether the Shift key must be held.
+    pub shift: bool,
+}
+
+impl Default for ShortcutHelpHotkey {
+    fn default() -> Self {
+        // "?" on most keyboard layouts.
+        Self {
+            key: KeyCode::Slash,
+            shift: true,
+        }
+    }
+}
+
+/// Shortcuts defined by this crate.
+pub(crate) fn builtin_shortcuts(world: &World) -> Vec<Shortcut> {
+    let mut shortcuts = Vec::new();
+    if let Some(hotkey) = world.get_resource::<CommandPaletteHotkey>() {
+        shortcuts.push(Shortcut::new(
+            "General",
+            "Global",
+            format_hotkey(hotkey.key, hotkey.ctrl, hotkey.shift),
+            "Open the command palette",
+        ));
+    }
+    if let Some(hotkey) = world.get_resource::<ShortcutHelpHotkey>() {
+        shortcuts.push(Shortcut::new(
+            "General",
+            "Global",
+            format_hotkey(hotkey.key, false, hotkey.shift),
+            "Show keyboard shortcuts",
+        ));
+    }
+    shortcuts.push(Shortcut::new(
+        "General",
+        "Dialogs and popups",
+        "Esc",
+        "Close",
+    ));
+    shortcuts.push(Shortcut::new(
+        "General",
+        "Global",
+        "Tab",
+        "Move focus to the next control",
+    ));
+    shortcuts
+}
+
+/// Toggle the shortcut help overlay when the hotkey is pressed. The hotkey is ignored while
+/// a text field has focus, since it is usually a printable character.
+pub(crate) fn toggle_shortcut_help(
+    keys: Res<ButtonInput<KeyCode>>,
+    hotkey: Res<ShortcutHelpHotkey>,
+    focus: Res<KeyboardFocus>,
+    q_access: Query<&AccessibilityNode>,
+    mut state: ResMut<ShortcutHelpState>,
+) {
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    if !keys.just_pressed(hotkey.key) || shift != hotkey.shift {
+        return;
+    }
+    let typing = focus
+        .0
+        .and_then(|entity| q_access.get(entity).ok())
+        .is_some_and(|node| node.role() == Role::TextInput);
+    if !typing {
+        state.open = !state.open;
+    }
+}
+
+/// A row in the shortcut list.
+#[derive(Clone, PartialEq)]
+enum HelpRow {
+    Group(String),
+    Shortcut {
+        shortcut: Shortcut,
+        ranges: Vec<Range<usize>>,
+    },
+}
+
+/// Filter the shortcuts by the query, and group them under their categories.
+fn help_rows(shortcuts: Vec<Shortcut>, query: &str) -> Vec<HelpRow> {
+    let mut matches: Vec<(Shortcut, Vec<Range<usize>>)> = if query.trim().is_empty() {
+        shortcuts.into_iter().map(|s| (s, Vec::new())).collect()
+    } else {
+        fuzzy_filter(query, shortcuts, |s| s.description.as_str())
+            .into_iter()
+            .map(|(s, m)| (s, m.ranges))
+            .collect()
+    };
+    matches.sort_by(|(a, _), (b, _)| a.category.cmp(&b.category));
+    let mut rows = Vec::new();
+    let mut category: Option<String> = None;
+    for (shortcut, ranges) in matches {
+        if category.as_ref() != Some(&shortcut.category) {
+            category = Some(shortcut.category.clone());
+            rows.push(HelpRow::Group(shortcut.category.clone()));
+        }
+        rows.push(HelpRow::Shortcut { shortcut, ranges });
+    }
+    rows
+}
+
+/// A modal overlay listing every registered keyboard shortcut, grouped by category, with a
+/// search field to filter them. The overlay is shown and hidden via the
+/// [`ShortcutHelpState`] resource, which is toggled by the [`ShortcutHelpHotkey`] ("?" by
+/// default). Press Escape or click outside the overlay to dismiss it.
+///
+/// The list is generated from the [`ShortcutRegistry`] when the overlay opens.
+#[derive(Default)]
+pub struct ShortcutHelp;
+
+impl UiTemplate for ShortcutHelp {
+    fn build(&self, builder: &mut UiBuilder) {
+        let query: Mutable<String> = builder.create_mutable(String::new());
+
+        let on_close = builder.create_callback(move |_: In<()>, mut world: DeferredWorld| {
+            world.resource_mut::<ShortcutHelpState>().open = false;
+            query.set_clone(&mut world, String::new());
+        });
+        let on_query_change =
+            builder.create_callback(move |text: In<String>, mut world: DeferredWorld| {
+                query.set_clone(&mut world, text.0);
+            });
+
+        builder.cond(
+            |rcx: &Rcx| rcx.read_resource::<ShortcutHelpState>().open,
+            move |builder| {
+                // Generated once each time the overlay opens.
+                let shortcuts = {
+                    let world = builder.world();
+                    world
+                        .get_resource::<ShortcutRegistry>()
+                        .map(|registry| registry.collect(world))
+                        .unwrap_or_default()
+                };
+                builder
+                    .spawn((Node::default(), Name::new("ShortcutHelp::Overlay")))
+                    .style(style_help_barrier)
+                    .insert((
+                        Barrier {
+                            on_close: Some(on_close),
+                        },
+                        UiLayer::Modal,
+                    ))
+                    .create_children(|builder| {
+                        builder
+                            .spawn((Node::default(), Name::new("ShortcutHelp")))
+                            .insert((
+                                TabGroup {
+                                    order: 0,
+                                    modal: true,
+                                },
+                                AccessibilityNode::from(accesskit::Node::new(Role::Dialog)),
+                            ))
+                            .styles((typography::text_default, style_help))
+                            .observe(|mut trigger: Trigger<Pointer<Down>>| {
+                                // Prevent clicks from propagating to the barrier and closing
+                                // the overlay.
+                                trigger.propagate(false);
+                            })
+                            .create_children(|builder| {
+                                builder.invoke(
+                                    TextInput::new()
+                                        .value(query.signal())
+                                        .placeholder("Search shortcuts...")
+                                        .size(Size::Md)
+                                        .autofocus(true)
+                                        .on_change(on_query_change),
+                                );
+                                let shortcuts = shortcuts.clone();
+                                builder.invoke(
+                                    ScrollView::new()
+                                        .style(style_help_list)
+                                        .scroll_enable_y(true)
+                                        .children(move |builder| {
+                                            let shortcuts = shortcuts.clone();
+                                            builder.for_each(
+                                                move |rcx| {
+                                                    help_rows(
+                                                        shortcuts.clone(),
+                                                        &query.get_clone(rcx),
+                                                    )
+                                                    .into_iter()
+                                                },
+                                                |row, builder| build_row(row, builder),
+                                                |builder| {
+                                                    builder
+                                                        .spawn(Node::default())
+                                                        .style(style_no_results)
+                                                        .create_children(|builder| {
+                                                            builder.text("No matching shortcuts");
+                                                        });
+                                                },
+                                            );
+                                        }),
+                                );
+                            });
+                    });
+            },
+            |_| {},
+        );
+    }
+}
+
+fn build_row(row: &HelpRow, builder: &mut UiBuilder) {
+    match row {
+        HelpRow::Group(category) => {
+            builder
+                .spawn((Node::default(), Name::new("ShortcutHelp::Group")))
+                .style(style_group)
+                .create_children(|builder| {
+                    builder.text(category.clone());
+                });
+        }
+        HelpRow::Shortcut { shortcut, ranges } => {
+            builder
+                .spawn((Node::default(), Name::new("ShortcutHelp::Shortcut")))
+                .style(style_row)
+                .create_children(|builder| {
+                    builder.invoke(
+                        HighlightedText::new(shortcut.description.clone()).ranges(ranges.clone()),
+                    );
+                    builder
+                        .spawn(Node::default())
+                        .style(style_scope)
+                        .create_children(|builder| {
+                            builder.text(shortcut.scope.clone());
+                        });
+                    builder
+                        .spawn(Node::default())
+                        .style(style_keys)
+                        .create_children(|builder| {
+                            builder.text(shortcut.keys.clone());
+                        });
+                });
+        }
+    }
+}
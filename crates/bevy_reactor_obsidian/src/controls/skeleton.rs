@@ -0,0 +1,161 @@
+use bevy::{color::Mix, prelude::*, ui};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{CreateChilden, EntityStyleBuilder, UiBuilder, UiTemplate};
+
+use crate::colors;
+
+/// Marker component for skeleton blocks, which are animated with a shimmer.
+#[derive(Component)]
+pub(crate) struct SkeletonShimmer;
+
+/// Duration of one cycle of the shimmer animation, in seconds.
+const SHIMMER_PERIOD: f32 = 1.2;
+
+/// Placeholder shown while content is loading: one or more blocks in the approximate shape
+/// of the content, animated with a shimmer. Typically used as the fallback of a `suspense`.
+#[derive(Clone)]
+pub struct Skeleton {
+    /// Width of each block.
+    pub width: ui::Val,
+
+    /// Height of each block in pixels.
+    pub height: f32,
+
+    /// Radius of the corners of each block.
+    pub radius: f32,
+
+    /// Number of blocks, stacked vertically.
+    pub count: usize,
+
+    /// Space between the blocks, in pixels.
+    pub gap: f32,
+
+    /// Additional styles to apply to the skeleton.
+    pub style: StyleHandle,
+}
+
+impl Default for Skeleton {
+    fn default() -> Self {
+        Self {
+            width: ui::Val::Percent(100.),
+            height: 12.,
+            radius: 3.,
+            count: 1,
+            gap: 6.,
+            style: StyleHandle::default(),
+        }
+    }
+}
+
+impl Skeleton {
+    /// Construct a new `Skeleton`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A single line of text, such as a label.
+    pub fn label() -> Self {
+        Self::default().width(ui::Val::Percent(60.))
+    }
+
+    /// Rows the height of a standard control, such as the entries of a list or the fields
+    /// of an inspector.
+    pub fn rows(count: usize) -> Self {
+        Self::default().height(24.).radius(4.).count(count)
+    }
+
+    /// A circle, such as an avatar or icon.
+    pub fn circle(size: f32) -> Self {
+        Self::default()
+            .width(ui::Val::Px(size))
+            .height(size)
+            .radius(size * 0.5)
+    }
+
+    /// Set the width of each block.
+    pub fn width(mut self, width: ui::Val) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Set the height of each block.
+    pub fn height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Set the radius of the corners of each block.
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Set the number of blocks.
+    pub fn count(mut self, count: usize) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// Set the space between blocks.
+    pub fn gap(mut self, gap: f32) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Set the additional styles for the skeleton.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl UiTemplate for Skeleton {
+    fn build(&self, builder: &mut UiBuilder) {
+        let width = self.width;
+        let height = self.height;
+        let radius = self.radius;
+        let count = self.count;
+        let gap = self.gap;
+        builder
+            .spawn((Node::default(), Name::new("Skeleton")))
+            .styles((
+                move |sb: &mut StyleBuilder| {
+                    sb.display(ui::Display::Flex)
+                        .flex_direction(ui::FlexDirection::Column)
+                        .align_self(ui::AlignSelf::Stretch)
+                        .row_gap(gap);
+                },
+                self.style.clone(),
+            ))
+            .create_children(|builder| {
+                for _ in 0..count {
+                    builder
+                        .spawn((
+                            Node::default(),
+                            Name::new("Skeleton::Block"),
+                            SkeletonShimmer,
+                        ))
+                        .style(move |sb: &mut StyleBuilder| {
+                            sb.width(width)
+                                .height(height)
+                                .flex_shrink(0.)
+                                .border_radius(radius)
+                                .background_color(colors::U3);
+                        });
+                }
+            });
+    }
+}
+
+/// Animate skeleton blocks by pulsing their color.
+pub(crate) fn shimmer_skeletons(
+    mut q_blocks: Query<&mut BackgroundColor, With<SkeletonShimmer>>,
+    time: Res<Time>,
+) {
+    let phase = time.elapsed_secs() * std::f32::consts::TAU / SHIMMER_PERIOD;
+    let t = phase.sin() * 0.5 + 0.5;
+    let color = Color::from(colors::U2.mix(&colors::U3, t));
+    for mut bg in q_blocks.iter_mut() {
+        bg.0 = color;
+    }
+}
@@ -1,28 +1,48 @@
 use bevy::{
-    color::LinearRgba, ecs::world::DeferredWorld, prelude::*, ui, window::SystemCursorIcon,
+    color::LinearRgba,
+    ecs::world::DeferredWorld,
+    input::{keyboard::Key, ButtonState},
+    prelude::*,
+    ui,
+    window::SystemCursorIcon,
     winit::cursor::CursorIcon,
 };
 use bevy_mod_stylebuilder::*;
 use bevy_reactor_builder::{
     CondBuilder, CreateChilden, EntityEffectBuilder, EntityStyleBuilder, InvokeUiTemplate,
-    TextBuilder, UiBuilder, UiTemplate,
+    NumberFormatCache, TextBuilder, UiBuilder, UiTemplate,
 };
 use bevy_reactor_signals::{Callback, IntoSignal, RunCallback, Signal};
 
 use crate::{
-    colors, cursor::StyleBuilderCursor, materials::SliderRectMaterial, prelude::RoundedCorners,
+    colors,
+    control_events::ValueChanged,
+    cursor::StyleBuilderCursor,
+    focus_signal::CreateFocusSignal,
+    input_dispatch::{FocusKeyboardInput, KeyboardFocus, KeyboardFocusVisible},
+    materials::{update_ui_material, CreateUiMaterial, SliderRectMaterial},
+    prelude::RoundedCorners,
+    tab_navigation::TabIndex,
+    theme::UseTheme,
     typography,
 };
 
 use super::{
     core_slider::{CoreSlider, ValueChange},
-    IconButton, Spacer,
+    DragScaling, IconButton, IsDisabled, Spacer,
 };
 
+/// Maximum interval between two clicks for them to count as a double-click, in seconds.
+const DOUBLE_CLICK_TIME: f32 = 0.35;
+
 fn style_slider(ss: &mut StyleBuilder) {
     ss.min_width(64).height(20);
 }
 
+fn style_slider_vertical(ss: &mut StyleBuilder) {
+    ss.min_height(64).width(20);
+}
+
 fn style_overlay(ss: &mut StyleBuilder) {
     ss.display(ui::Display::Flex)
         .flex_direction(ui::FlexDirection::Row)
@@ -35,10 +55,19 @@ fn style_overlay(ss: &mut StyleBuilder) {
         .cursor(CursorIcon::System(SystemCursorIcon::ColResize));
 }
 
+fn style_overlay_vertical(ss: &mut StyleBuilder) {
+    ss.flex_direction(ui::FlexDirection::ColumnReverse)
+        .cursor(CursorIcon::System(SystemCursorIcon::RowResize));
+}
+
 fn style_slider_button(ss: &mut StyleBuilder) {
     ss.height(20.).padding(0).max_width(12).flex_grow(0.2);
 }
 
+fn style_slider_button_vertical(ss: &mut StyleBuilder) {
+    ss.width(20.).padding(0).max_height(12).flex_grow(0.2);
+}
+
 fn style_label(ss: &mut StyleBuilder) {
     ss.flex_grow(1.)
         .display(ui::Display::Flex)
@@ -51,7 +80,17 @@ fn style_label(ss: &mut StyleBuilder) {
         .color(colors::FOREGROUND);
 }
 
-/// Horizontal slider widget
+fn style_label_vertical(ss: &mut StyleBuilder) {
+    ss.height(ui::Val::Auto)
+        .width(ui::Val::Percent(100.))
+        .padding((0, 6));
+}
+
+/// Slider widget, which can be oriented horizontally or vertically.
+///
+/// When focused, the arrow keys change the value by `step`, Page Up / Page Down change it by
+/// ten steps, and Home / End set it to the minimum or maximum. Double-clicking the slider
+/// allows the value to be typed in directly.
 pub struct Slider {
     /// Current slider value.
     pub value: Signal<f32>,
@@ -83,6 +122,12 @@ pub struct Slider {
 
     /// Callback called when value changes
     pub on_change: Option<Callback<f32>>,
+
+    /// If true, the slider is oriented vertically, with the minimum value at the bottom.
+    pub vertical: bool,
+
+    /// How drag movement is converted into value changes.
+    pub drag_scaling: DragScaling,
 }
 
 impl Slider {
@@ -151,6 +196,18 @@ impl Slider {
         self.on_change = Some(on_change);
         self
     }
+
+    /// Set whether the slider is oriented vertically.
+    pub fn vertical(mut self, vertical: bool) -> Self {
+        self.vertical = vertical;
+        self
+    }
+
+    /// Set how drag movement is converted into value changes.
+    pub fn drag_scaling(mut self, drag_scaling: DragScaling) -> Self {
+        self.drag_scaling = drag_scaling;
+        self
+    }
 }
 
 impl Default for Slider {
@@ -166,6 +223,8 @@ impl Default for Slider {
             style: StyleHandle::default(),
             label: None,
             on_change: None,
+            vertical: false,
+            drag_scaling: DragScaling::default(),
         }
     }
 }
@@ -189,15 +248,25 @@ impl UiTemplate for Slider {
         let label = self.label.clone();
         let step = self.step;
         let on_change = self.on_change;
+        let vertical = self.vertical;
+        let drag_scaling = self.drag_scaling;
+
+        // Text being typed, when the slider is in text entry mode.
+        let edit_text = builder.create_mutable::<Option<String>>(None);
+        let focused = builder.create_focus_visible_signal(slider_id);
+        let has_focus = builder.create_focus_signal(slider_id);
+
+        // Leave text entry mode when the slider loses focus.
+        builder.create_effect(move |ecx| {
+            if !has_focus.get(ecx) && edit_text.signal().map(ecx, Option::is_some) {
+                edit_text.set_clone(ecx.world_mut(), None);
+            }
+        });
 
-        let mut ui_materials = builder
-            .world_mut()
-            .get_resource_mut::<Assets<SliderRectMaterial>>()
-            .unwrap();
-        let material = ui_materials.add(SliderRectMaterial {
+        let material = builder.create_ui_material(SliderRectMaterial {
             color_lo: LinearRgba::from(colors::U1).to_vec4(),
             color_hi: LinearRgba::from(colors::U3).to_vec4(),
-            value: Vec4::new(0.5, 0., 0., 0.),
+            value: Vec4::new(0.5, 0., if vertical { 1. } else { 0. }, 0.),
             radius: RoundedCorners::All.to_vec(4.),
         });
         let material_id = material.id();
@@ -213,20 +282,42 @@ impl UiTemplate for Slider {
                 0.
             };
 
-            let mut ui_materials = ecx
-                .world_mut()
-                .get_resource_mut::<Assets<SliderRectMaterial>>()
-                .unwrap();
-            let material = ui_materials.get_mut(material_id).unwrap();
-            material.value.x = pos;
+            update_ui_material(ecx.world_mut(), material_id, |material| {
+                material.value.x = pos;
+            });
         });
 
         builder
             .entity_mut(slider_id)
-            .styles((typography::text_default, style_slider, self.style.clone()))
-            .insert(MaterialNode(material.clone()))
+            .styles((
+                typography::text_default,
+                if vertical {
+                    style_slider_vertical
+                } else {
+                    style_slider
+                },
+                self.style.clone(),
+            ))
+            .insert((MaterialNode(material), TabIndex(0)))
+            .style_dyn(
+                move |rcx| focused.get(rcx).then(|| rcx.use_theme().focus),
+                |focus, sb| {
+                    if let Some(focus) = focus {
+                        sb.outline_color(focus)
+                            .outline_width(2)
+                            .outline_offset(2);
+                    } else {
+                        sb.outline_color(colors::TRANSPARENT)
+                            .outline_width(0)
+                            .outline_offset(0);
+                    }
+                },
+            )
             .effect(move |rcx| {
                 CoreSlider::new(value.get(rcx), min.get(rcx), max.get(rcx))
+                    .with_vertical(vertical)
+                    .with_step(step)
+                    .with_scaling(drag_scaling)
             }, |slider, ent| {
                 ent.insert(slider);
             })
@@ -240,8 +331,105 @@ impl UiTemplate for Slider {
                     if let Some(on_change) = on_change {
                         world.run_callback(on_change, new_value);
                     }
+                    world.commands().trigger_targets(ValueChanged(new_value), slider_id);
                 }
             })
+            .observe(
+                |mut trigger: Trigger<Pointer<Down>>,
+                 mut focus: ResMut<KeyboardFocus>,
+                 mut focus_visible: ResMut<KeyboardFocusVisible>| {
+                    trigger.propagate(false);
+                    focus.0 = Some(trigger.entity());
+                    focus_visible.0 = false;
+                },
+            )
+            .observe(
+                move |mut trigger: Trigger<Pointer<Click>>,
+                      mut last_click: Local<Option<f32>>,
+                      mut world: DeferredWorld| {
+                    trigger.propagate(false);
+                    let now = world.resource::<Time>().elapsed_secs();
+                    let is_double_click =
+                        last_click.is_some_and(|last| now - last < DOUBLE_CLICK_TIME);
+                    *last_click = Some(now);
+                    if is_double_click && !world.is_disabled(trigger.entity()) {
+                        // Enter text entry mode.
+                        let text = format!("{:.*}", precision, value.get(&world));
+                        world.resource_mut::<KeyboardFocus>().0 = Some(trigger.entity());
+                        edit_text.set_clone(&mut world, Some(text));
+                    }
+                },
+            )
+            .observe(
+                move |mut trigger: Trigger<FocusKeyboardInput>, mut world: DeferredWorld| {
+                    let event = trigger.event().input.clone();
+                    if event.state != ButtonState::Pressed || world.is_disabled(trigger.entity())
+                    {
+                        return;
+                    }
+                    let min = min.get(&world);
+                    let max = max.get(&world);
+                    let current = value.get(&world);
+                    let mut handled = true;
+                    let next_value = match edit_text.get_clone(&mut world) {
+                        Some(mut text) => match (event.key_code, &event.logical_key) {
+                            (KeyCode::Enter | KeyCode::NumpadEnter, _) => {
+                                edit_text.set_clone(&mut world, None);
+                                text.trim().parse::<f32>().ok()
+                            }
+                            (KeyCode::Escape, _) => {
+                                edit_text.set_clone(&mut world, None);
+                                None
+                            }
+                            (KeyCode::Backspace, _) => {
+                                text.pop();
+                                edit_text.set_clone(&mut world, Some(text));
+                                None
+                            }
+                            (KeyCode::Tab, _) => {
+                                handled = false;
+                                None
+                            }
+                            (_, Key::Character(chars))
+                                if chars
+                                    .chars()
+                                    .all(|c| c.is_ascii_digit() || c == '.' || c == '-') =>
+                            {
+                                text.push_str(chars);
+                                edit_text.set_clone(&mut world, Some(text));
+                                None
+                            }
+                            _ => None,
+                        },
+                        None => match event.key_code {
+                            KeyCode::ArrowLeft | KeyCode::ArrowDown => Some(current - step),
+                            KeyCode::ArrowRight | KeyCode::ArrowUp => Some(current + step),
+                            KeyCode::PageDown => Some(current - step * 10.),
+                            KeyCode::PageUp => Some(current + step * 10.),
+                            KeyCode::Home => Some(min),
+                            KeyCode::End => Some(max),
+                            _ => {
+                                handled = false;
+                                None
+                            }
+                        },
+                    };
+                    if handled {
+                        trigger.propagate(false);
+                    }
+                    if let Some(next_value) = next_value {
+                        let rounding = f32::powi(10., precision as i32);
+                        let next_value =
+                            ((next_value * rounding).round() / rounding).clamp(min, max);
+                        if next_value != current {
+                            if let Some(on_change) = on_change {
+                                world.run_callback(on_change, next_value);
+                            }
+                            world.commands().trigger_targets(ValueChanged(next_value), slider_id);
+                        }
+                    }
+                },
+            )
             .create_children(|builder| {
                 let dec_disabled =
                     builder.create_derived(move |rcx| value.get(rcx) <= min.get(rcx));
@@ -252,6 +440,7 @@ impl UiTemplate for Slider {
                     if let Some(on_change) = on_change {
                         world.run_callback(on_change, next_value);
                     }
+                    world.commands().trigger_targets(ValueChanged(next_value), slider_id);
                 });
                 let inc_disabled =
                     builder.create_derived(move |rcx| value.get(rcx) >= max.get(rcx));
@@ -262,20 +451,38 @@ impl UiTemplate for Slider {
                     if let Some(on_change) = on_change {
                         world.run_callback(on_change, next_value);
                     }
+                    world.commands().trigger_targets(ValueChanged(next_value), slider_id);
                 });
                 builder
                     .spawn((Node::default(), Name::new("Slider::Overlay")))
-                    .style(style_overlay)
+                    .styles((
+                        style_overlay,
+                        move |ss: &mut StyleBuilder| {
+                            if vertical {
+                                style_overlay_vertical(ss);
+                            }
+                        },
+                    ))
                     .create_children(move |builder| {
                         builder.cond(
                             show_buttons,
                             move |builder| {
                                 builder.invoke(
-                            IconButton::new(
-                                "embedded://bevy_reactor_obsidian/assets/icons/chevron_left.png",
-                            )
-                            .corners(RoundedCorners::Left)
-                            .style(style_slider_button)
+                            IconButton::new(if vertical {
+                                "embedded://bevy_reactor_obsidian/assets/icons/chevron_down.png"
+                            } else {
+                                "embedded://bevy_reactor_obsidian/assets/icons/chevron_left.png"
+                            })
+                            .corners(if vertical {
+                                RoundedCorners::Bottom
+                            } else {
+                                RoundedCorners::Left
+                            })
+                            .style(if vertical {
+                                style_slider_button_vertical
+                            } else {
+                                style_slider_button
+                            })
                             .minimal(true)
                             .disabled(dec_disabled)
                             .on_click(dec_click));
@@ -284,16 +491,27 @@ impl UiTemplate for Slider {
                         );
                         builder
                             .spawn(Node::default())
-                            .style(style_label)
+                            .styles((
+                                style_label,
+                                move |ss: &mut StyleBuilder| {
+                                    if vertical {
+                                        style_label_vertical(ss);
+                                    }
+                                },
+                            ))
                             .create_children(|builder| {
                                 if let Some(label) = label {
                                     builder.text(label);
                                     builder.invoke(Spacer);
                                 }
                                 builder.text_computed({
-                                    move |rcx| {
-                                        let value = value.get(rcx);
-                                        format!("{:.*}", precision, value)
+                                    let mut cache = NumberFormatCache::new();
+                                    move |rcx| match edit_text.signal().get_clone(rcx) {
+                                        Some(text) => format!("{}|", text),
+                                        None => {
+                                            let value = value.get(rcx);
+                                            cache.format(value, precision).to_string()
+                                        }
                                     }
                                 });
                             });
@@ -301,11 +519,21 @@ impl UiTemplate for Slider {
                             show_buttons,
                             move |builder| {
                                 builder.invoke(
-                                IconButton::new(
-                                    "embedded://bevy_reactor_obsidian/assets/icons/chevron_right.png",
-                                )
-                                .corners(RoundedCorners::Right)
-                                .style(style_slider_button)
+                                IconButton::new(if vertical {
+                                    "embedded://bevy_reactor_obsidian/assets/icons/chevron_up.png"
+                                } else {
+                                    "embedded://bevy_reactor_obsidian/assets/icons/chevron_right.png"
+                                })
+                                .corners(if vertical {
+                                    RoundedCorners::Top
+                                } else {
+                                    RoundedCorners::Right
+                                })
+                                .style(if vertical {
+                                    style_slider_button_vertical
+                                } else {
+                                    style_slider_button
+                                })
                                 .minimal(true)
                                 .disabled(inc_disabled)
                                 .on_click(inc_click));
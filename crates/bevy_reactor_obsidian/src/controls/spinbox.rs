@@ -3,14 +3,14 @@ use bevy::{
 };
 use bevy_mod_stylebuilder::*;
 use bevy_reactor_builder::{
-    CondBuilder, CreateChilden, EntityStyleBuilder, InvokeUiTemplate, TextBuilder, UiBuilder,
-    UiTemplate,
+    CondBuilder, CreateChilden, EntityStyleBuilder, InvokeUiTemplate, NumberFormatCache,
+    TextBuilder, UiBuilder, UiTemplate,
 };
 use bevy_reactor_signals::{Callback, IntoSignal, RunCallback, Signal};
 
 use crate::{colors, cursor::StyleBuilderCursor, prelude::RoundedCorners, typography};
 
-use super::IconButton;
+use super::{DragScaling, IconButton};
 
 #[derive(Clone, PartialEq, Default, Copy)]
 enum DragType {
@@ -22,7 +22,10 @@ enum DragType {
 #[derive(Clone, PartialEq, Default, Copy)]
 struct DragState {
     dragging: DragType,
+    /// The accumulated (unrounded) value during the drag.
     offset: f32,
+    /// The drag distance as of the previous drag event.
+    last_distance: Vec2,
     was_dragged: bool,
 }
 
@@ -96,6 +99,9 @@ pub struct SpinBox {
 
     /// Callback called when value changes
     pub on_change: Option<Callback<f32>>,
+
+    /// How drag movement is converted into value changes.
+    pub drag_scaling: DragScaling,
 }
 
 impl SpinBox {
@@ -158,6 +164,12 @@ impl SpinBox {
         self.on_change = Some(on_change);
         self
     }
+
+    /// Set how drag movement is converted into value changes.
+    pub fn drag_scaling(mut self, drag_scaling: DragScaling) -> Self {
+        self.drag_scaling = drag_scaling;
+        self
+    }
 }
 
 impl Default for SpinBox {
@@ -172,6 +184,7 @@ impl Default for SpinBox {
             formatted_value: None,
             style: StyleHandle::default(),
             on_change: None,
+            drag_scaling: DragScaling::default(),
         }
     }
 }
@@ -193,6 +206,7 @@ impl UiTemplate for SpinBox {
         let precision = self.precision;
         let step = self.step;
         let on_change = self.on_change;
+        let drag_scaling = self.drag_scaling;
 
         let dec_disabled = builder.create_derived(move |rcx| value.get(rcx) <= min.get(rcx));
         let dec_click = builder.create_callback(move |_: In<()>, mut world: DeferredWorld| {
@@ -247,6 +261,7 @@ impl UiTemplate for SpinBox {
                                 DragState {
                                     dragging: DragType::Dragging,
                                     offset,
+                                    last_distance: Vec2::ZERO,
                                     was_dragged: false,
                                 },
                             );
@@ -263,6 +278,7 @@ impl UiTemplate for SpinBox {
                                     DragState {
                                         dragging: DragType::None,
                                         offset,
+                                        last_distance: Vec2::ZERO,
                                         was_dragged: false,
                                     },
                                 );
@@ -277,21 +293,26 @@ impl UiTemplate for SpinBox {
                                 let min = min.get(&world);
                                 let max = max.get(&world);
                                 let event = trigger.event();
-                                let new_value = ds.offset
-                                    + ((event.distance.x - event.distance.y) * 0.1 * step);
+                                let keys = world.resource::<ButtonInput<KeyCode>>();
+                                let movement = event.distance - ds.last_distance;
+                                let offset = ds.offset
+                                    + drag_scaling.scale_delta(movement.x - movement.y, keys)
+                                        * 0.1
+                                        * step;
+                                let new_value = drag_scaling.snap(offset, step, keys);
                                 let rounding = f32::powi(10., precision as i32);
                                 let value = value.get(&world);
                                 let new_value = (new_value * rounding).round() / rounding;
+                                drag_state.set(
+                                    &mut world,
+                                    DragState {
+                                        offset,
+                                        last_distance: event.distance,
+                                        was_dragged: ds.was_dragged || value != new_value,
+                                        ..ds
+                                    },
+                                );
                                 if value != new_value {
-                                    if !ds.was_dragged {
-                                        drag_state.set(
-                                            &mut world,
-                                            DragState {
-                                                was_dragged: true,
-                                                ..ds
-                                            },
-                                        );
-                                    }
                                     if let Some(on_change) = on_change {
                                         world.run_callback(on_change, new_value.clamp(min, max));
                                     }
@@ -300,9 +321,10 @@ impl UiTemplate for SpinBox {
                         },
                     )
                     .create_children(|builder| {
+                        let mut cache = NumberFormatCache::new();
                         builder.text_computed(move |rcx| {
                             let value = value.get(rcx);
-                            format!("{:.*}", precision, value)
+                            cache.format(value, precision).to_string()
                         });
                     });
                 builder.cond(
@@ -0,0 +1,103 @@
+use bevy::{prelude::*, ui};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{CreateChilden, EntityStyleBuilder, UiBuilder, UiTemplate};
+
+use crate::colors;
+
+/// Marker component for the rotating part of a spinner.
+#[derive(Component)]
+pub(crate) struct SpinnerRotation;
+
+/// Rate at which spinners rotate, in radians per second.
+const SPINNER_SPEED: f32 = std::f32::consts::TAU;
+
+/// A small, indeterminate progress indicator.
+#[derive(Clone)]
+pub struct Spinner {
+    /// Diameter of the spinner in pixels.
+    pub size: f32,
+
+    /// Color of the spinner.
+    pub color: Srgba,
+
+    /// Additional styles to apply to the spinner.
+    pub style: StyleHandle,
+}
+
+impl Spinner {
+    /// Construct a new `Spinner`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the diameter of the spinner.
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the color of the spinner.
+    pub fn color(mut self, color: Srgba) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Set the additional styles for the spinner.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl Default for Spinner {
+    fn default() -> Self {
+        Self {
+            size: 12.0,
+            color: colors::FOREGROUND,
+            style: StyleHandle::default(),
+        }
+    }
+}
+
+impl UiTemplate for Spinner {
+    fn build(&self, builder: &mut UiBuilder) {
+        let size = self.size;
+        let color = self.color;
+        builder
+            .spawn((Node::default(), Name::new("Spinner"), SpinnerRotation))
+            .styles((
+                move |sb: &mut StyleBuilder| {
+                    sb.width(size)
+                        .height(size)
+                        .flex_shrink(0.)
+                        .border(2)
+                        .border_color(color.with_alpha(0.25))
+                        .border_radius(size * 0.5);
+                },
+                self.style.clone(),
+            ))
+            .create_children(|builder| {
+                builder
+                    .spawn((Node::default(), Name::new("Spinner::Dot")))
+                    .style(move |sb: &mut StyleBuilder| {
+                        sb.position(ui::PositionType::Absolute)
+                            .left(size * 0.5 - 3.)
+                            .top(-2)
+                            .width(2)
+                            .height(2)
+                            .border(0)
+                            .border_radius(1)
+                            .background_color(color);
+                    });
+            });
+    }
+}
+
+pub(crate) fn rotate_spinners(
+    mut q_spinners: Query<&mut Transform, With<SpinnerRotation>>,
+    time: Res<Time>,
+) {
+    for mut transform in q_spinners.iter_mut() {
+        transform.rotate_z(time.delta_secs() * SPINNER_SPEED);
+    }
+}
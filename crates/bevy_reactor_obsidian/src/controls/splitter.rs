@@ -6,7 +6,10 @@ use bevy_mod_stylebuilder::*;
 use bevy_reactor_builder::{CreateChilden, EntityStyleBuilder, UiBuilder, UiTemplate};
 use bevy_reactor_signals::{Callback, IntoSignal, RunCallback, Signal};
 
-use crate::{colors, cursor::StyleBuilderCursor, hover_signal::CreateHoverSignal};
+use crate::{
+    colors, cursor::StyleBuilderCursor, hover_signal::CreateHoverSignal,
+    input_dispatch::CapturesPointer,
+};
 
 /// The direction of the splitter. Represents the direction of the bar, not the items being split.
 #[derive(Clone, PartialEq, Default)]
@@ -142,6 +145,7 @@ impl UiTemplate for Splitter {
         builder
             .entity_mut(id)
             .style(style_splitter)
+            .insert(CapturesPointer)
             .observe(
                 move |mut trigger: Trigger<Pointer<DragStart>>, mut world: DeferredWorld| {
                     // Save initial value to use as drag offset.
@@ -5,7 +5,7 @@ use bevy_reactor_builder::{CondBuilder, CreateChilden, EntityStyleBuilder, UiTem
 use bevy_reactor_signals::{Callback, IntoSignal, RunCallback, Signal};
 // use bevy_tabindex::TabIndex;
 
-use crate::materials::SwatchRectMaterial;
+use crate::materials::{update_ui_material, CreateUiMaterial, SwatchRectMaterial};
 
 use crate::colors;
 
@@ -28,6 +28,29 @@ fn style_selection(ss: &mut StyleBuilder) {
         .flex_grow(1.);
 }
 
+/// Appearance of the checkerboard pattern drawn behind transparent colors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Checkerboard {
+    /// Width and height of each cell, in pixels.
+    pub size: f32,
+
+    /// Color of the lighter cells.
+    pub light: Srgba,
+
+    /// Color of the darker cells.
+    pub dark: Srgba,
+}
+
+impl Default for Checkerboard {
+    fn default() -> Self {
+        Self {
+            size: 4.,
+            light: Srgba::rgb(0.6, 0.6, 0.6),
+            dark: Srgba::rgb(0.4, 0.4, 0.4),
+        }
+    }
+}
+
 /// Color swatch widget. This displays a solid color, and can also display a checkerboard
 /// pattern behind the color if it has an alpha of less than 1.
 #[derive(Default)]
@@ -38,6 +61,9 @@ pub struct Swatch {
     /// For swatch grids, whether this swatch is selected.
     pub selected: Signal<bool>,
 
+    /// Checkerboard pattern shown behind the color when it is transparent.
+    pub checkerboard: Checkerboard,
+
     /// Additional styles to be applied to the widget.
     pub style: StyleHandle,
 
@@ -57,6 +83,12 @@ impl Swatch {
         self
     }
 
+    /// Set the checkerboard pattern shown behind transparent colors.
+    pub fn checkerboard(mut self, checkerboard: Checkerboard) -> Self {
+        self.checkerboard = checkerboard;
+        self
+    }
+
     /// Set additional styles to be applied to the button.
     pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
         self.style = style.into_handle();
@@ -81,33 +113,27 @@ impl UiTemplate for Swatch {
         let color = self.color;
         let selected = self.selected;
         let on_click = self.on_click;
+        let checkerboard = self.checkerboard;
 
-        let mut ui_materials = builder
-            .world_mut()
-            .get_resource_mut::<Assets<SwatchRectMaterial>>()
-            .unwrap();
-        let material = ui_materials.add(SwatchRectMaterial {
+        let material = builder.create_ui_material(SwatchRectMaterial {
             color: LinearRgba::from(colors::U1).to_vec4(),
             border_radius: Vec4::splat(0.),
+            checker_light: checkerboard.light.to_vec4(),
+            checker_dark: checkerboard.dark.to_vec4(),
+            checker_size: checkerboard.size,
         });
 
         // Update material color
-        builder.create_effect({
-            let material = material.clone();
-            move |rcx| {
-                let color = color.get(rcx);
-                // println!("Update color: {}", color.to_hex());
-                let mut ui_materials = rcx
-                    .world_mut()
-                    .get_resource_mut::<Assets<SwatchRectMaterial>>()
-                    .unwrap();
-                let material = ui_materials.get_mut(material.id()).unwrap();
+        let material_id = material.id();
+        builder.create_effect(move |rcx| {
+            let color = color.get(rcx);
+            update_ui_material(rcx.world_mut(), material_id, |material| {
                 material.color = color.to_vec4();
-            }
+            });
         });
 
         builder
-            .spawn((MaterialNode(material.clone()), Name::new("Swatch")))
+            .spawn((MaterialNode(material), Name::new("Swatch")))
             .styles((style_swatch, self.style.clone()))
             .observe(
                 move |mut trigger: Trigger<Pointer<Click>>,
@@ -1,11 +1,22 @@
-use bevy::{color::Srgba, prelude::*, ui};
+use bevy::{
+    color::Srgba,
+    ecs::world::DeferredWorld,
+    input::ButtonState,
+    prelude::*,
+    ui::{self, RelativeCursorPosition},
+};
 use bevy_mod_stylebuilder::*;
 use bevy_reactor_builder::{
     CreateChilden, EntityStyleBuilder, ForEachBuilder, InvokeUiTemplate, UiBuilder, UiTemplate,
 };
 use bevy_reactor_signals::{Callback, IntoSignal, RunCallback, Signal};
 
-use crate::colors;
+use crate::{
+    colors,
+    focus_signal::CreateFocusSignal,
+    input_dispatch::{FocusKeyboardInput, KeyboardFocus, KeyboardFocusVisible},
+    tab_navigation::TabIndex,
+};
 
 use super::Swatch;
 
@@ -24,6 +35,12 @@ fn style_swatch(ss: &mut StyleBuilder) {
     ss.min_width(12).min_height(12);
 }
 
+fn style_drop_target(ss: &mut StyleBuilder) {
+    ss.outline_color(colors::FOCUS)
+        .outline_width(2)
+        .outline_offset(1);
+}
+
 fn style_empty_slot(ss: &mut StyleBuilder) {
     ss.border(1)
         .min_width(16)
@@ -31,8 +48,10 @@ fn style_empty_slot(ss: &mut StyleBuilder) {
         .border_color(colors::U2.lighter(0.01));
 }
 
-/// Color swatch widget. This displays a solid color, and can also display a checkerboard
-/// pattern behind the color if it has an alpha of less than 1.
+/// A grid of color swatches. Clicking a swatch selects it, and when the grid has keyboard
+/// focus the arrow keys move the selection. If an `on_reorder` callback is provided, swatches
+/// can also be dragged to a new position within the grid; if an `on_drop_outside` callback is
+/// provided, they can be dragged out of it.
 pub struct SwatchGrid {
     /// Color to display.
     /// TODO: Should this be `Color` instead? How will we serialize?
@@ -49,6 +68,14 @@ pub struct SwatchGrid {
 
     /// Callback called when a swatch is clicked
     pub on_change: Option<Callback<Srgba>>,
+
+    /// Callback called when a swatch is dragged to a new position, with the old and new
+    /// indices. Dragging is only enabled if this is set.
+    pub on_reorder: Option<Callback<(usize, usize)>>,
+
+    /// Callback called with the index of a swatch which was dragged and released outside the
+    /// grid, for example to move it to another grid. Dragging is enabled if this is set.
+    pub on_drop_outside: Option<Callback<usize>>,
 }
 
 impl SwatchGrid {
@@ -86,6 +113,20 @@ impl SwatchGrid {
         self.on_change = Some(on_click);
         self
     }
+
+    /// Set the callback called when a swatch is dragged to a new position. Setting this
+    /// enables drag-to-reorder.
+    pub fn on_reorder(mut self, on_reorder: Callback<(usize, usize)>) -> Self {
+        self.on_reorder = Some(on_reorder);
+        self
+    }
+
+    /// Set the callback called when a swatch is dragged out of the grid. Setting this enables
+    /// dragging.
+    pub fn on_drop_outside(mut self, on_drop_outside: Callback<usize>) -> Self {
+        self.on_drop_outside = Some(on_drop_outside);
+        self
+    }
 }
 
 impl Default for SwatchGrid {
@@ -96,6 +137,8 @@ impl Default for SwatchGrid {
             selected: Signal::Constant(Srgba::default()),
             style: Default::default(),
             on_change: None,
+            on_reorder: None,
+            on_drop_outside: None,
         }
     }
 }
@@ -107,6 +150,12 @@ impl UiTemplate for SwatchGrid {
         let grid_size = self.grid_size;
         let selected = self.selected;
         let on_change = self.on_change;
+        let on_reorder = self.on_reorder;
+        let on_drop_outside = self.on_drop_outside;
+
+        // Index of the swatch being dragged, and of the cell it would be dropped on.
+        let drag_source = builder.create_mutable::<Option<usize>>(None);
+        let drop_target = builder.create_mutable::<Option<usize>>(None);
 
         let on_click = builder.create_callback(move |color: In<Srgba>, mut commands: Commands| {
             if let Some(on_change) = on_change.as_ref() {
@@ -114,8 +163,18 @@ impl UiTemplate for SwatchGrid {
             }
         });
 
+        let grid_id = builder
+            .spawn((
+                Node::default(),
+                Name::new("SwatchGrid"),
+                RelativeCursorPosition::default(),
+                TabIndex(0),
+            ))
+            .id();
+        let focused = builder.create_focus_visible_signal(grid_id);
+
         builder
-            .spawn((Node::default(), Name::new("SwatchGrid")))
+            .entity_mut(grid_id)
             .styles((
                 style_swatch_grid,
                 move |ss: &mut StyleBuilder| {
@@ -127,27 +186,155 @@ impl UiTemplate for SwatchGrid {
                 },
                 self.style.clone(),
             ))
+            .style_dyn(
+                move |rcx| focused.get(rcx),
+                |is_focused, sb| {
+                    if is_focused {
+                        sb.outline_color(colors::FOCUS)
+                            .outline_width(2)
+                            .outline_offset(2);
+                    } else {
+                        sb.outline_color(colors::TRANSPARENT)
+                            .outline_width(0)
+                            .outline_offset(0);
+                    }
+                },
+            )
+            .observe(
+                |mut trigger: Trigger<Pointer<Down>>,
+                 mut focus: ResMut<KeyboardFocus>,
+                 mut focus_visible: ResMut<KeyboardFocusVisible>| {
+                    trigger.propagate(false);
+                    focus.0 = Some(trigger.entity());
+                    focus_visible.0 = false;
+                },
+            )
+            .observe({
+                let colors = colors.clone();
+                move |mut trigger: Trigger<FocusKeyboardInput>, mut world: DeferredWorld| {
+                    let event = &trigger.event().input;
+                    if event.state != ButtonState::Pressed {
+                        return;
+                    }
+                    let colors = colors.get_clone(&world);
+                    let count = colors.len().min(num_cells);
+                    if count == 0 {
+                        return;
+                    }
+                    let selected_color = selected.get(&world);
+                    let current = colors[..count].iter().position(|c| *c == selected_color);
+                    let columns = grid_size.x as usize;
+                    let next = match (event.key_code, current) {
+                        (KeyCode::ArrowLeft, Some(i)) => i.saturating_sub(1),
+                        (KeyCode::ArrowRight, Some(i)) => (i + 1).min(count - 1),
+                        (KeyCode::ArrowUp, Some(i)) => i.checked_sub(columns).unwrap_or(i),
+                        (KeyCode::ArrowDown, Some(i)) if i + columns < count => i + columns,
+                        (KeyCode::ArrowDown, Some(i)) => i,
+                        (
+                            KeyCode::ArrowLeft
+                            | KeyCode::ArrowRight
+                            | KeyCode::ArrowUp
+                            | KeyCode::ArrowDown
+                            | KeyCode::Home,
+                            _,
+                        ) => 0,
+                        (KeyCode::End, _) => count - 1,
+                        _ => return,
+                    };
+                    trigger.propagate(false);
+                    if Some(next) != current {
+                        if let Some(on_change) = on_change {
+                            world.run_callback(on_change, colors[next]);
+                        }
+                    }
+                }
+            })
+            .observe({
+                let colors = colors.clone();
+                move |mut trigger: Trigger<Pointer<DragStart>>,
+                      q_cursor: Query<&RelativeCursorPosition>,
+                      mut world: DeferredWorld| {
+                    if on_reorder.is_none() && on_drop_outside.is_none() {
+                        return;
+                    }
+                    trigger.propagate(false);
+                    let count = colors.map(&world, |c| c.len().min(num_cells));
+                    let cell = q_cursor
+                        .get(trigger.entity())
+                        .ok()
+                        .and_then(|cursor| cell_at(cursor, grid_size))
+                        .filter(|cell| *cell < count);
+                    drag_source.set(&mut world, cell);
+                    drop_target.set(&mut world, cell);
+                }
+            })
+            .observe({
+                let colors = colors.clone();
+                move |mut trigger: Trigger<Pointer<Drag>>,
+                      q_cursor: Query<&RelativeCursorPosition>,
+                      mut world: DeferredWorld| {
+                    if drag_source.get(&world).is_none() {
+                        return;
+                    }
+                    trigger.propagate(false);
+                    let count = colors.map(&world, |c| c.len().min(num_cells));
+                    let cell = q_cursor
+                        .get(trigger.entity())
+                        .ok()
+                        .and_then(|cursor| cell_at(cursor, grid_size))
+                        .map(|cell| cell.min(count.saturating_sub(1)));
+                    if drop_target.get(&world) != cell {
+                        drop_target.set(&mut world, cell);
+                    }
+                }
+            })
+            .observe(
+                move |mut trigger: Trigger<Pointer<DragEnd>>, mut world: DeferredWorld| {
+                    let Some(from) = drag_source.get(&world) else {
+                        return;
+                    };
+                    trigger.propagate(false);
+                    let to = drop_target.get(&world);
+                    drag_source.set(&mut world, None);
+                    drop_target.set(&mut world, None);
+                    match (to, on_reorder, on_drop_outside) {
+                        (Some(to), Some(on_reorder), _) if from != to => {
+                            world.run_callback(on_reorder, (from, to));
+                        }
+                        (None, _, Some(on_drop_outside)) => {
+                            world.run_callback(on_drop_outside, from);
+                        }
+                        _ => {}
+                    }
+                },
+            )
             .create_children(|builder| {
                 builder.for_each(
                     move |rcx| {
                         let colors = colors.get_clone(rcx);
                         let selected_color = selected.get(rcx);
+                        let drop_target = drop_target.get(rcx);
                         (0..num_cells).map(move |i| {
                             if i < colors.len() {
                                 let color = colors[i];
                                 let is_selected = selected_color == color;
-                                Some((color, is_selected))
+                                Some((color, is_selected, drop_target == Some(i)))
                             } else {
                                 None
                             }
                         })
                     },
                     move |color, builder| match color {
-                        Some((color, selected)) => {
+                        Some((color, selected, is_drop_target)) => {
+                            let is_drop_target = *is_drop_target;
                             builder.invoke(
                                 Swatch::new(*color)
                                     .selected(Signal::Constant(*selected))
-                                    .style(style_swatch)
+                                    .style((style_swatch, move |ss: &mut StyleBuilder| {
+                                        if is_drop_target {
+                                            style_drop_target(ss);
+                                        }
+                                    }))
                                     .on_click(on_click),
                             );
                         }
@@ -160,3 +347,14 @@ impl UiTemplate for SwatchGrid {
             });
     }
 }
+
+/// Return the index of the grid cell under the cursor, if any.
+fn cell_at(cursor: &RelativeCursorPosition, grid_size: UVec2) -> Option<usize> {
+    let pos = cursor.normalized?;
+    if !(0. ..1.).contains(&pos.x) || !(0. ..1.).contains(&pos.y) {
+        return None;
+    }
+    let col = (pos.x * grid_size.x as f32) as usize;
+    let row = (pos.y * grid_size.y as f32) as usize;
+    Some(row * grid_size.x as usize + col)
+}
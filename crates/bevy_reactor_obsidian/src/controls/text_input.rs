@@ -0,0 +1,279 @@
+use std::sync::Arc;
+
+use accesskit::{self, Role};
+use bevy::{
+    a11y::AccessibilityNode,
+    ecs::world::DeferredWorld,
+    input::{keyboard::Key, ButtonState},
+    prelude::*,
+    ui,
+    window::SystemCursorIcon,
+    winit::cursor::CursorIcon,
+};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{
+    CreateChilden, EntityStyleBuilder, InsertComponentBuilder, TextBuilder, UiBuilder, UiTemplate,
+};
+use bevy_reactor_signals::{Callback, IntoSignal, Rcx, RunCallback, Signal};
+
+use crate::{
+    colors,
+    cursor::StyleBuilderCursor,
+    focus_signal::CreateFocusSignal,
+    input_dispatch::{FocusKeyboardInput, KeyboardFocus, KeyboardFocusVisible},
+    size::{Size, UiSizing},
+    tab_navigation::{AutoFocus, TabIndex},
+    typography,
+};
+
+use super::{Disabled, IsDisabled};
+
+fn style_text_input(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .overflow(ui::OverflowAxis::Clip)
+        .background_color(colors::U1)
+        .border(1)
+        .border_color(colors::U3)
+        .color(colors::FOREGROUND)
+        .cursor(CursorIcon::System(SystemCursorIcon::Text));
+}
+
+/// A single-line text entry field. The text is edited at the end of the line: typed
+/// characters are appended, Backspace removes the last character, and Escape clears the field.
+pub struct TextInput {
+    /// The current text.
+    pub value: Signal<String>,
+
+    /// Text shown in a dimmed color when the field is empty.
+    pub placeholder: String,
+
+    /// Field size.
+    pub size: Size,
+
+    /// Whether the field is disabled.
+    pub disabled: Signal<bool>,
+
+    /// Additional styles to be applied to the field.
+    pub style: StyleHandle,
+
+    /// Callback called with the new text whenever it is edited.
+    pub on_change: Option<Callback<String>>,
+
+    /// Callback called with the current text when Enter is pressed.
+    pub on_submit: Option<Callback<String>>,
+
+    /// The tab index of the field (default 0).
+    pub tab_index: i32,
+
+    /// If true, set focus to this field when it's added to the UI.
+    pub autofocus: bool,
+
+    /// Optional input mask: typed characters for which this returns false are ignored.
+    pub mask: Option<Arc<dyn Fn(char) -> bool + Send + Sync>>,
+}
+
+impl Default for TextInput {
+    fn default() -> Self {
+        Self {
+            value: Signal::Constant(String::new()),
+            placeholder: String::new(),
+            size: Size::default(),
+            disabled: Signal::Constant(false),
+            style: StyleHandle::default(),
+            on_change: None,
+            on_submit: None,
+            tab_index: 0,
+            autofocus: false,
+            mask: None,
+        }
+    }
+}
+
+impl TextInput {
+    /// Construct a new `TextInput`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the current text.
+    pub fn value(mut self, value: impl IntoSignal<String>) -> Self {
+        self.value = value.into_signal();
+        self
+    }
+
+    /// Set the placeholder text.
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Set the field size.
+    pub fn size(mut self, size: Size) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the field disabled state.
+    pub fn disabled(mut self, disabled: impl IntoSignal<bool>) -> Self {
+        self.disabled = disabled.into_signal();
+        self
+    }
+
+    /// Set additional styles to be applied to the field.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+
+    /// Set the callback called when the text is edited.
+    pub fn on_change(mut self, callback: Callback<String>) -> Self {
+        self.on_change = Some(callback);
+        self
+    }
+
+    /// Set the callback called when Enter is pressed.
+    pub fn on_submit(mut self, callback: Callback<String>) -> Self {
+        self.on_submit = Some(callback);
+        self
+    }
+
+    /// Set the tab index of the field.
+    pub fn tab_index(mut self, tab_index: i32) -> Self {
+        self.tab_index = tab_index;
+        self
+    }
+
+    /// Set whether to autofocus the field when it's added to the UI.
+    pub fn autofocus(mut self, autofocus: bool) -> Self {
+        self.autofocus = autofocus;
+        self
+    }
+
+    /// Set the input mask, which restricts the characters that can be typed into the field.
+    pub fn mask<F: Fn(char) -> bool + Send + Sync + 'static>(mut self, mask: F) -> Self {
+        self.mask = Some(Arc::new(mask));
+        self
+    }
+}
+
+impl UiTemplate for TextInput {
+    fn build(&self, builder: &mut UiBuilder) {
+        let value = self.value.clone();
+        let size = self.size;
+        let disabled = self.disabled;
+        let on_change = self.on_change;
+        let on_submit = self.on_submit;
+        let placeholder = self.placeholder.clone();
+        let mask = self.mask.clone();
+
+        let input_id = builder
+            .spawn((Node::default(), Name::new("TextInput")))
+            .id();
+        let focused = builder.create_focus_visible_signal(input_id);
+        let has_focus = builder.create_focus_signal(input_id);
+
+        builder
+            .entity_mut(input_id)
+            .styles((typography::text_default, style_text_input))
+            .style_dyn(
+                |rcx| *rcx.read_resource::<UiSizing>(),
+                move |sizing, ss| {
+                    ss.min_height(sizing.height(size))
+                        .font_size(sizing.font_size(size))
+                        .border_radius(sizing.border_radius(size))
+                        .padding((sizing.font_size(size) * 0.5, 0));
+                },
+            )
+            .styles(self.style.clone())
+            .insert((
+                TabIndex(self.tab_index),
+                AccessibilityNode::from(accesskit::Node::new(Role::TextInput)),
+            ))
+            .insert_if(move |rcx: &Rcx| disabled.get(rcx), || Disabled)
+            .insert_if(self.autofocus, || AutoFocus)
+            .style_dyn(
+                move |rcx| focused.get(rcx),
+                |is_focused, sb| {
+                    if is_focused {
+                        sb.outline_color(colors::FOCUS)
+                            .outline_width(2)
+                            .outline_offset(2);
+                    } else {
+                        sb.outline_color(colors::TRANSPARENT)
+                            .outline_width(0)
+                            .outline_offset(0);
+                    }
+                },
+            )
+            .style_dyn(
+                {
+                    let value = value.clone();
+                    move |rcx| value.map(rcx, String::is_empty)
+                },
+                |is_empty, sb| {
+                    sb.color(if is_empty {
+                        colors::DIM
+                    } else {
+                        colors::FOREGROUND
+                    });
+                },
+            )
+            .observe(
+                |mut trigger: Trigger<Pointer<Down>>,
+                 mut focus: ResMut<KeyboardFocus>,
+                 mut focus_visible: ResMut<KeyboardFocusVisible>| {
+                    trigger.propagate(false);
+                    focus.0 = Some(trigger.entity());
+                    focus_visible.0 = false;
+                },
+            )
+            .observe({
+                let value = value.clone();
+                move |mut trigger: Trigger<FocusKeyboardInput>, mut world: DeferredWorld| {
+                    let event = trigger.event().input.clone();
+                    if event.state != ButtonState::Pressed || world.is_disabled(trigger.entity()) {
+                        return;
+                    }
+                    let mut text = value.get_clone(&world);
+                    let accepts = |c: char| mask.as_ref().map_or(true, |mask| mask(c));
+                    match (event.key_code, &event.logical_key) {
+                        (KeyCode::Enter | KeyCode::NumpadEnter, _) => {
+                            trigger.propagate(false);
+                            if let Some(on_submit) = on_submit {
+                                world.run_callback(on_submit, text);
+                            }
+                            return;
+                        }
+                        (KeyCode::Escape, _) if !text.is_empty() => text.clear(),
+                        (KeyCode::Backspace, _) => {
+                            text.pop();
+                        }
+                        (KeyCode::Tab | KeyCode::Escape, _) => return,
+                        (_, Key::Space) if accepts(' ') => text.push(' '),
+                        (_, Key::Character(chars))
+                            if !chars.chars().any(|c| c.is_control() || !accepts(c)) =>
+                        {
+                            text.push_str(chars);
+                        }
+                        _ => return,
+                    }
+                    trigger.propagate(false);
+                    if let Some(on_change) = on_change {
+                        world.run_callback(on_change, text);
+                    }
+                }
+            })
+            .create_children(|builder| {
+                builder.text_computed(move |rcx| {
+                    let text = value.get_clone(rcx);
+                    match (text.is_empty(), has_focus.get(rcx)) {
+                        (true, false) => placeholder.clone(),
+                        (_, true) => format!("{}|", text),
+                        (false, false) => text,
+                    }
+                });
+            });
+    }
+}
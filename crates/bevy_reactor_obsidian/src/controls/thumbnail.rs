@@ -0,0 +1,273 @@
+use bevy::{
+    asset::{AssetLoadFailedEvent, LoadState},
+    prelude::*,
+    ui,
+    utils::HashSet,
+};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{
+    CreateChilden, EntityStyleBuilder, InvokeUiTemplate, SwitchBuilder, UiBuilder, UiTemplate,
+};
+use bevy_reactor_signals::Rcx;
+
+use crate::{colors, prelude::RoundedCorners};
+
+use super::{Icon, Spinner};
+
+fn style_thumbnail(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .justify_content(ui::JustifyContent::Center)
+        .align_items(ui::AlignItems::Center)
+        .overflow(ui::OverflowAxis::Clip)
+        .background_color(colors::U1);
+}
+
+/// How the image is fitted into the bounds of a [`Thumbnail`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ThumbnailFit {
+    /// Scale the image to cover the entire thumbnail, cropping the edges if needed.
+    #[default]
+    Cover,
+
+    /// Scale the image so that it fits entirely within the thumbnail, leaving empty space
+    /// on two sides if the aspect ratios differ.
+    Contain,
+
+    /// Stretch the image to the size of the thumbnail, ignoring its aspect ratio.
+    Fill,
+}
+
+impl ThumbnailFit {
+    /// Compute the displayed size of an image of size `image` within a frame of size `frame`.
+    fn fit(&self, image: UVec2, frame: Vec2) -> Vec2 {
+        if image.x == 0 || image.y == 0 || *self == ThumbnailFit::Fill {
+            return frame;
+        }
+        let image = image.as_vec2();
+        let scale_x = frame.x / image.x;
+        let scale_y = frame.y / image.y;
+        match self {
+            ThumbnailFit::Cover => image * scale_x.max(scale_y),
+            ThumbnailFit::Contain => image * scale_x.min(scale_y),
+            ThumbnailFit::Fill => unreachable!(),
+        }
+    }
+}
+
+/// Loading state of a thumbnail's image.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ThumbnailStatus {
+    Loading,
+    Loaded,
+    Failed,
+}
+
+/// The image displayed by a thumbnail, and its size once loaded.
+#[derive(Component)]
+pub(crate) struct ThumbnailImage {
+    handle: Handle<Image>,
+    size: UVec2,
+}
+
+/// Displays an image with rounded corners, such as an avatar or an asset preview. While the
+/// image is loading a spinner is shown in its place, and if the image fails to load a fallback
+/// icon is shown instead.
+pub struct Thumbnail {
+    /// The image to display.
+    pub image: HandleOrOwnedPath<Image>,
+
+    /// Size of the thumbnail in pixels.
+    pub size: Vec2,
+
+    /// How the image is fitted into the thumbnail.
+    pub fit: ThumbnailFit,
+
+    /// Radius of the rounded corners.
+    pub radius: f32,
+
+    /// Which corners to render rounded.
+    pub corners: RoundedCorners,
+
+    /// Additional styles to be applied to the thumbnail.
+    pub style: StyleHandle,
+}
+
+impl Default for Thumbnail {
+    fn default() -> Self {
+        Self {
+            image: HandleOrOwnedPath::default(),
+            size: Vec2::splat(64.),
+            fit: ThumbnailFit::default(),
+            radius: 4.,
+            corners: RoundedCorners::All,
+            style: StyleHandle::default(),
+        }
+    }
+}
+
+impl Thumbnail {
+    /// Create a new `Thumbnail` from a `&str` or `Handle<Image>`.
+    pub fn new(image: impl Into<HandleOrOwnedPath<Image>>) -> Self {
+        Self {
+            image: image.into(),
+            ..default()
+        }
+    }
+
+    /// Set the size of the thumbnail.
+    pub fn size(mut self, size: Vec2) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set how the image is fitted into the thumbnail.
+    pub fn fit(mut self, fit: ThumbnailFit) -> Self {
+        self.fit = fit;
+        self
+    }
+
+    /// Set the radius of the rounded corners.
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Set which corners to render rounded.
+    pub fn corners(mut self, corners: RoundedCorners) -> Self {
+        self.corners = corners;
+        self
+    }
+
+    /// Set additional styles to be applied to the thumbnail.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl UiTemplate for Thumbnail {
+    fn build(&self, builder: &mut UiBuilder) {
+        let handle = match &self.image {
+            HandleOrOwnedPath::Handle(handle) => handle.clone(),
+            HandleOrOwnedPath::Path(path) => {
+                builder.world().resource::<AssetServer>().load(path.clone())
+            }
+        };
+        let size = self.size;
+        let fit = self.fit;
+        let border_radius = self.corners.to_border_radius(self.radius);
+
+        let id = builder
+            .spawn((
+                Node::default(),
+                Name::new("Thumbnail"),
+                ThumbnailImage {
+                    handle: handle.clone(),
+                    size: UVec2::ZERO,
+                },
+                ThumbnailStatus::Loading,
+            ))
+            .id();
+
+        builder
+            .entity_mut(id)
+            .styles((
+                style_thumbnail,
+                move |ss: &mut StyleBuilder| {
+                    ss.width(size.x).height(size.y);
+                },
+                self.style.clone(),
+            ))
+            .insert(border_radius)
+            .create_children(|builder| {
+                builder.switch(
+                    move |rcx: &Rcx| {
+                        rcx.read_component::<ThumbnailStatus>(id)
+                            .copied()
+                            .unwrap_or(ThumbnailStatus::Loading)
+                    },
+                    move |builder| {
+                        let handle = handle.clone();
+                        builder
+                            .case(ThumbnailStatus::Loading, move |builder| {
+                                builder.invoke(Spinner::new().size(size.min_element() * 0.25));
+                            })
+                            .case(ThumbnailStatus::Loaded, move |builder| {
+                                let handle = handle.clone();
+                                builder
+                                    .spawn((
+                                        Node::default(),
+                                        Name::new("Thumbnail::Image"),
+                                        border_radius,
+                                    ))
+                                    .style(move |ss: &mut StyleBuilder| {
+                                        ss.flex_shrink(0.).background_image(handle.clone());
+                                    })
+                                    .style_dyn(
+                                        move |rcx| {
+                                            rcx.read_component::<ThumbnailImage>(id)
+                                                .map_or(UVec2::ZERO, |image| image.size)
+                                        },
+                                        move |image_size, ss| {
+                                            let display = fit.fit(image_size, size);
+                                            ss.width(display.x).height(display.y);
+                                        },
+                                    );
+                            })
+                            .case(ThumbnailStatus::Failed, move |builder| {
+                                builder.invoke(
+                                    Icon::new(
+                                        "embedded://bevy_reactor_obsidian/assets/icons/close.png",
+                                    )
+                                    .size(Vec2::splat(size.min_element() * 0.25))
+                                    .color(Color::from(colors::DIM)),
+                                );
+                            });
+                    },
+                );
+            });
+    }
+}
+
+/// Update the loading status of thumbnails in response to asset events.
+pub(crate) fn update_thumbnails(
+    mut q_thumbnails: Query<(&mut ThumbnailImage, &mut ThumbnailStatus)>,
+    mut asset_events: EventReader<AssetEvent<Image>>,
+    mut failed_events: EventReader<AssetLoadFailedEvent<Image>>,
+    asset_server: Res<AssetServer>,
+    images: Res<Assets<Image>>,
+) {
+    let changed: HashSet<AssetId<Image>> = asset_events
+        .read()
+        .map(|event| match event {
+            AssetEvent::Added { id }
+            | AssetEvent::Modified { id }
+            | AssetEvent::Removed { id }
+            | AssetEvent::Unused { id }
+            | AssetEvent::LoadedWithDependencies { id } => *id,
+        })
+        .collect();
+    let failed: HashSet<AssetId<Image>> = failed_events.read().map(|event| event.id).collect();
+
+    for (mut thumbnail, mut status) in q_thumbnails.iter_mut() {
+        let id = thumbnail.handle.id();
+        // Newly-added thumbnails need an initial status, since the image may have finished
+        // loading (or failed) before the thumbnail was spawned.
+        if !thumbnail.is_added() && !changed.contains(&id) && !failed.contains(&id) {
+            continue;
+        }
+        let next_status = if let Some(image) = images.get(id) {
+            if thumbnail.size != image.size() {
+                thumbnail.size = image.size();
+            }
+            ThumbnailStatus::Loaded
+        } else if failed.contains(&id)
+            || matches!(asset_server.get_load_state(id), Some(LoadState::Failed(_)))
+        {
+            ThumbnailStatus::Failed
+        } else {
+            ThumbnailStatus::Loading
+        };
+        status.set_if_neq(next_status);
+    }
+}
@@ -0,0 +1,655 @@
+use bevy::{
+    ecs::world::DeferredWorld,
+    input::mouse::MouseScrollUnit,
+    prelude::*,
+    ui::{self, RelativeCursorPosition},
+};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{
+    CreateChilden, EntityStyleBuilder, ForIndexBuilder, TextBuilder, UiBuilder, UiTemplate,
+};
+use bevy_reactor_signals::{Callback, IntoSignal, Mutable, RunCallback, Signal};
+
+use crate::{colors, scrolling::ScrollWheelEvent, typography};
+
+/// Maximum zoom factor, relative to showing the whole duration.
+const MAX_ZOOM: f32 = 1000.;
+
+fn style_timeline(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Stretch)
+        .background_color(colors::U1);
+}
+
+fn style_track_labels(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .width(96)
+        .border_right(1)
+        .border_color(colors::U3);
+}
+
+fn style_track_label(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .align_items(ui::AlignItems::Center)
+        .height(22)
+        .padding_left(6)
+        .color(colors::DIM);
+}
+
+fn style_time_column(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .flex_grow(1.)
+        .overflow(ui::OverflowAxis::Clip);
+}
+
+fn style_ruler(ss: &mut StyleBuilder) {
+    ss.height(22)
+        .background_color(colors::U2)
+        .border_bottom(1)
+        .border_color(colors::U3)
+        .font_size(10)
+        .color(colors::DIM);
+}
+
+fn style_tick(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .top(0)
+        .bottom(0)
+        .padding_left(3)
+        .border_left(1)
+        .border_color(colors::U3);
+}
+
+fn style_lane(ss: &mut StyleBuilder) {
+    ss.height(22).border_bottom(1).border_color(colors::U2);
+}
+
+fn style_keyframe(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .top(6)
+        .width(9)
+        .height(9)
+        .margin_left(-4)
+        .border_radius(2)
+        .background_color(colors::U4);
+}
+
+fn style_selection(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .top(0)
+        .bottom(0)
+        .background_color(colors::ACCENT.with_alpha(0.15));
+}
+
+fn style_playhead(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .top(0)
+        .bottom(0)
+        .width(1)
+        .background_color(colors::ACCENT);
+}
+
+/// A track in a [`Timeline`], with a list of keyframe times.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TimelineTrack {
+    /// Name displayed next to the track.
+    pub name: String,
+    /// Times of the keyframes in the track.
+    pub keys: Vec<f32>,
+}
+
+impl TimelineTrack {
+    /// Create a new track.
+    pub fn new(name: impl Into<String>, keys: Vec<f32>) -> Self {
+        Self {
+            name: name.into(),
+            keys,
+        }
+    }
+}
+
+/// Emitted by a [`Timeline`] when a keyframe is dragged.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KeyMove {
+    /// Index of the track.
+    pub track: usize,
+    /// Index of the keyframe within the track.
+    pub key: usize,
+    /// The new time of the keyframe.
+    pub time: f32,
+}
+
+/// The visible portion of the timeline.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct TimelineView {
+    /// Time at the left edge.
+    start: f32,
+    /// Zoom factor, where 1 shows the whole duration.
+    zoom: f32,
+}
+
+impl Default for TimelineView {
+    fn default() -> Self {
+        Self {
+            start: 0.,
+            zoom: 1.,
+        }
+    }
+}
+
+impl TimelineView {
+    /// Length of time which is visible.
+    fn span(&self, duration: f32) -> f32 {
+        duration.max(f32::EPSILON) / self.zoom
+    }
+
+    /// Position of a time, as a percentage of the width of the timeline.
+    fn percent(&self, time: f32, duration: f32) -> f32 {
+        (time - self.start) / self.span(duration) * 100.
+    }
+
+    /// Keep the visible portion within the duration.
+    fn clamped(mut self, duration: f32) -> Self {
+        self.zoom = self.zoom.clamp(1., MAX_ZOOM);
+        self.start = self
+            .start
+            .clamp(0., (duration - self.span(duration)).max(0.));
+        self
+    }
+}
+
+/// Round a time to the nearest multiple of `snap`, if non-zero, and clamp it to the duration.
+fn snap_time(time: f32, snap: f32, duration: f32) -> f32 {
+    let time = if snap > 0. {
+        (time / snap).round() * snap
+    } else {
+        time
+    };
+    time.clamp(0., duration)
+}
+
+/// Returns the time under the cursor, based on its position within the time column.
+fn cursor_time(world: &DeferredWorld, column: Entity, view: TimelineView, duration: f32) -> f32 {
+    let x = world
+        .get::<RelativeCursorPosition>(column)
+        .and_then(|pos| pos.normalized)
+        .map_or(0., |pos| pos.x);
+    view.start + x * view.span(duration)
+}
+
+/// Choose a tick interval so that there are roughly eight ticks across the visible span.
+fn tick_interval(span: f32) -> f32 {
+    let target = span / 8.;
+    let magnitude = 10f32.powf(target.log10().floor());
+    [1., 2., 5.]
+        .into_iter()
+        .map(|m| m * magnitude)
+        .find(|interval| *interval >= target)
+        .unwrap_or(magnitude * 10.)
+}
+
+/// A timeline editor for animation tooling. Displays a time ruler, a playhead and a row of
+/// draggable keyframe markers for each track. The mouse wheel zooms around the cursor, and
+/// dragging with the middle button pans. Clicking or dragging the ruler scrubs the playhead,
+/// and dragging across the tracks selects a range of time.
+///
+/// The timeline does not modify its inputs; instead, changes are reported through callbacks.
+pub struct Timeline {
+    /// Length of the timeline.
+    pub duration: Signal<f32>,
+
+    /// Position of the playhead.
+    pub playhead: Signal<f32>,
+
+    /// The tracks to display.
+    pub tracks: Signal<Vec<TimelineTrack>>,
+
+    /// The selected range of time, if any.
+    pub selection: Signal<Option<(f32, f32)>>,
+
+    /// If non-zero, times are snapped to multiples of this interval when editing.
+    pub snap: f32,
+
+    /// Callback called when the playhead is scrubbed.
+    pub on_scrub: Option<Callback<f32>>,
+
+    /// Callback called when a keyframe is dragged.
+    pub on_key_move: Option<Callback<KeyMove>>,
+
+    /// Callback called when the selected range changes.
+    pub on_select: Option<Callback<Option<(f32, f32)>>>,
+
+    /// Additional styles to be applied to the timeline.
+    pub style: StyleHandle,
+}
+
+impl Default for Timeline {
+    fn default() -> Self {
+        Self {
+            duration: Signal::Constant(10.),
+            playhead: Signal::Constant(0.),
+            tracks: Signal::Constant(Vec::new()),
+            selection: Signal::Constant(None),
+            snap: 0.,
+            on_scrub: None,
+            on_key_move: None,
+            on_select: None,
+            style: StyleHandle::default(),
+        }
+    }
+}
+
+impl Timeline {
+    /// Create a new timeline.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the length of the timeline.
+    pub fn duration(mut self, duration: impl IntoSignal<f32>) -> Self {
+        self.duration = duration.into_signal();
+        self
+    }
+
+    /// Set the position of the playhead.
+    pub fn playhead(mut self, playhead: impl IntoSignal<f32>) -> Self {
+        self.playhead = playhead.into_signal();
+        self
+    }
+
+    /// Set the tracks to display.
+    pub fn tracks(mut self, tracks: impl IntoSignal<Vec<TimelineTrack>>) -> Self {
+        self.tracks = tracks.into_signal();
+        self
+    }
+
+    /// Set the selected range of time.
+    pub fn selection(mut self, selection: impl IntoSignal<Option<(f32, f32)>>) -> Self {
+        self.selection = selection.into_signal();
+        self
+    }
+
+    /// Set the snapping interval.
+    pub fn snap(mut self, snap: f32) -> Self {
+        self.snap = snap;
+        self
+    }
+
+    /// Set the callback called when the playhead is scrubbed.
+    pub fn on_scrub(mut self, callback: Callback<f32>) -> Self {
+        self.on_scrub = Some(callback);
+        self
+    }
+
+    /// Set the callback called when a keyframe is dragged.
+    pub fn on_key_move(mut self, callback: Callback<KeyMove>) -> Self {
+        self.on_key_move = Some(callback);
+        self
+    }
+
+    /// Set the callback called when the selected range changes.
+    pub fn on_select(mut self, callback: Callback<Option<(f32, f32)>>) -> Self {
+        self.on_select = Some(callback);
+        self
+    }
+
+    /// Set the additional styles for the timeline.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl UiTemplate for Timeline {
+    fn build(&self, builder: &mut UiBuilder) {
+        let duration = self.duration;
+        let playhead = self.playhead;
+        let tracks = self.tracks.clone();
+        let selection = self.selection;
+        let snap = self.snap;
+        let on_scrub = self.on_scrub;
+        let on_key_move = self.on_key_move;
+        let on_select = self.on_select;
+        let view: Mutable<TimelineView> = builder.create_mutable(TimelineView::default());
+        // Time at which the current range selection started.
+        let select_anchor: Mutable<Option<f32>> = builder.create_mutable(None);
+
+        builder
+            .spawn((Node::default(), Name::new("Timeline")))
+            .style((typography::text_default, style_timeline, self.style.clone()))
+            .create_children(|builder| {
+                let tracks_labels = tracks.clone();
+                builder
+                    .spawn((Node::default(), Name::new("Timeline::Labels")))
+                    .style(style_track_labels)
+                    .create_children(|builder| {
+                        builder.spawn(Node::default()).style(style_ruler);
+                        builder.for_index(
+                            move |rcx| {
+                                tracks_labels
+                                    .map(rcx, |tracks| {
+                                        tracks
+                                            .iter()
+                                            .map(|track| track.name.clone())
+                                            .collect::<Vec<_>>()
+                                    })
+                                    .into_iter()
+                            },
+                            |name, _, builder| {
+                                builder
+                                    .spawn(Node::default())
+                                    .style(style_track_label)
+                                    .create_children(|builder| {
+                                        builder.text(name.clone());
+                                    });
+                            },
+                            |_| {},
+                        );
+                    });
+
+                let column = builder
+                    .spawn((
+                        Node::default(),
+                        Name::new("Timeline::Tracks"),
+                        RelativeCursorPosition::default(),
+                    ))
+                    .style(style_time_column)
+                    .id();
+
+                builder
+                    .entity_mut(column)
+                    .observe(
+                        move |mut trigger: Trigger<ScrollWheelEvent>, mut world: DeferredWorld| {
+                            trigger.propagate(false);
+                            let event = &trigger.event().0;
+                            let (dx, dy) = match event.unit {
+                                MouseScrollUnit::Line => (event.x, event.y),
+                                MouseScrollUnit::Pixel => (event.x / 20., event.y / 20.),
+                            };
+                            let duration = duration.get(&world);
+                            let old = view.get(&world);
+                            // Zoom around the time under the cursor, and pan horizontally.
+                            let anchor = cursor_time(&world, column, old, duration);
+                            let mut new = TimelineView {
+                                start: old.start,
+                                zoom: old.zoom * 1.1f32.powf(dy),
+                            };
+                            new.start = anchor
+                                - (anchor - old.start) * old.zoom / new.zoom
+                                - dx * new.span(duration) * 0.05;
+                            view.set(&mut world, new.clamped(duration));
+                        },
+                    )
+                    .observe(
+                        move |mut trigger: Trigger<Pointer<Down>>, mut world: DeferredWorld| {
+                            trigger.propagate(false);
+                            if trigger.event().button == PointerButton::Primary {
+                                if let Some(on_select) = on_select {
+                                    world.run_callback(on_select, None);
+                                }
+                            }
+                        },
+                    )
+                    .observe(
+                        move |mut trigger: Trigger<Pointer<DragStart>>,
+                              mut world: DeferredWorld| {
+                            trigger.propagate(false);
+                            if trigger.event().button == PointerButton::Primary {
+                                let duration = duration.get(&world);
+                                let time = cursor_time(&world, column, view.get(&world), duration);
+                                select_anchor
+                                    .set(&mut world, Some(snap_time(time, snap, duration)));
+                            }
+                        },
+                    )
+                    .observe(
+                        move |mut trigger: Trigger<Pointer<Drag>>, mut world: DeferredWorld| {
+                            trigger.propagate(false);
+                            let duration = duration.get(&world);
+                            let current = view.get(&world);
+                            match trigger.event().button {
+                                PointerButton::Primary => {
+                                    let Some(anchor) = select_anchor.get(&world) else {
+                                        return;
+                                    };
+                                    let time = snap_time(
+                                        cursor_time(&world, column, current, duration),
+                                        snap,
+                                        duration,
+                                    );
+                                    if let Some(on_select) = on_select {
+                                        world.run_callback(
+                                            on_select,
+                                            Some((anchor.min(time), anchor.max(time))),
+                                        );
+                                    }
+                                }
+                                PointerButton::Middle => {
+                                    let Some(width) = world
+                                        .get::<ComputedNode>(column)
+                                        .map(|node| node.size().x * node.inverse_scale_factor())
+                                    else {
+                                        return;
+                                    };
+                                    if width > 0. {
+                                        let delta = trigger.event().delta.x / width
+                                            * current.span(duration);
+                                        let new = TimelineView {
+                                            start: current.start - delta,
+                                            ..current
+                                        };
+                                        view.set(&mut world, new.clamped(duration));
+                                    }
+                                }
+                                _ => {}
+                            }
+                        },
+                    )
+                    .observe(
+                        move |mut trigger: Trigger<Pointer<DragEnd>>, mut world: DeferredWorld| {
+                            trigger.propagate(false);
+                            select_anchor.set(&mut world, None);
+                        },
+                    );
+
+                builder.entity_mut(column).create_children(|builder| {
+                    // Scrubs the playhead to the time under the cursor.
+                    let scrub = move |world: &mut DeferredWorld| {
+                        let duration = duration.get(world);
+                        let time = cursor_time(world, column, view.get(world), duration);
+                        if let Some(on_scrub) = on_scrub {
+                            world.run_callback(on_scrub, snap_time(time, snap, duration));
+                        }
+                    };
+
+                    builder
+                        .spawn((Node::default(), Name::new("Timeline::Ruler")))
+                        .style(style_ruler)
+                        .observe(
+                            move |mut trigger: Trigger<Pointer<Down>>, mut world: DeferredWorld| {
+                                trigger.propagate(false);
+                                if trigger.event().button == PointerButton::Primary {
+                                    scrub(&mut world);
+                                }
+                            },
+                        )
+                        .observe(|mut trigger: Trigger<Pointer<DragStart>>| {
+                            trigger.propagate(false);
+                        })
+                        .observe(
+                            move |mut trigger: Trigger<Pointer<Drag>>, mut world: DeferredWorld| {
+                                trigger.propagate(false);
+                                if trigger.event().button == PointerButton::Primary {
+                                    scrub(&mut world);
+                                }
+                            },
+                        )
+                        .create_children(|builder| {
+                            builder.for_index(
+                                move |rcx| {
+                                    let duration = duration.get(rcx);
+                                    let view = view.get(rcx);
+                                    let span = view.span(duration);
+                                    let interval = tick_interval(span);
+                                    let first = (view.start / interval).ceil() as i64;
+                                    let last = ((view.start + span) / interval).floor() as i64;
+                                    (first..=last).map(move |i| (i as f32 * interval, interval))
+                                },
+                                move |(time, interval), _, builder| {
+                                    let time = *time;
+                                    let precision = (-interval.log10()).ceil().max(0.) as usize;
+                                    builder
+                                        .spawn(Node::default())
+                                        .style(style_tick)
+                                        .style_dyn(
+                                            move |rcx| {
+                                                view.get(rcx).percent(time, duration.get(rcx))
+                                            },
+                                            |left, sb| {
+                                                sb.left(ui::Val::Percent(left));
+                                            },
+                                        )
+                                        .create_children(|builder| {
+                                            builder.text(format!("{:.*}", precision, time));
+                                        });
+                                },
+                                |_| {},
+                            );
+                        });
+
+                    // Lanes are keyed by index, so that keyframe markers are not rebuilt
+                    // while they are being dragged.
+                    let tracks_lanes = tracks.clone();
+                    builder.for_index(
+                        move |rcx| 0..tracks_lanes.map(rcx, Vec::len),
+                        move |track_index, _, builder| {
+                            let track_index = *track_index;
+                            let tracks_keys = tracks.clone();
+                            builder
+                                .spawn((Node::default(), Name::new("Timeline::Lane")))
+                                .style(style_lane)
+                                .create_children(|builder| {
+                                    let tracks_count = tracks_keys.clone();
+                                    builder.for_index(
+                                        move |rcx| {
+                                            0..tracks_count.map(rcx, |tracks| {
+                                                tracks.get(track_index).map_or(0, |t| t.keys.len())
+                                            })
+                                        },
+                                        move |key_index, _, builder| {
+                                            let key_index = *key_index;
+                                            let tracks = tracks_keys.clone();
+                                            builder
+                                                .spawn((
+                                                    Node::default(),
+                                                    Name::new("Timeline::Keyframe"),
+                                                ))
+                                                .style(style_keyframe)
+                                                .style_dyn(
+                                                    move |rcx| {
+                                                        let time = tracks.map(rcx, |tracks| {
+                                                            tracks
+                                                                .get(track_index)
+                                                                .and_then(|t| {
+                                                                    t.keys.get(key_index).copied()
+                                                                })
+                                                                .unwrap_or_default()
+                                                        });
+                                                        view.get(rcx)
+                                                            .percent(time, duration.get(rcx))
+                                                    },
+                                                    |left, sb| {
+                                                        sb.left(ui::Val::Percent(left));
+                                                    },
+                                                )
+                                                .observe(
+                                                    |mut trigger: Trigger<Pointer<Down>>| {
+                                                        trigger.propagate(false);
+                                                    },
+                                                )
+                                                .observe(
+                                                    |mut trigger: Trigger<Pointer<DragStart>>| {
+                                                        trigger.propagate(false);
+                                                    },
+                                                )
+                                                .observe(
+                                                    move |mut trigger: Trigger<Pointer<Drag>>,
+                                                          mut world: DeferredWorld| {
+                                                        trigger.propagate(false);
+                                                        if trigger.event().button
+                                                            != PointerButton::Primary
+                                                        {
+                                                            return;
+                                                        }
+                                                        let duration = duration.get(&world);
+                                                        let time = snap_time(
+                                                            cursor_time(
+                                                                &world,
+                                                                column,
+                                                                view.get(&world),
+                                                                duration,
+                                                            ),
+                                                            snap,
+                                                            duration,
+                                                        );
+                                                        if let Some(on_key_move) = on_key_move {
+                                                            world.run_callback(
+                                                                on_key_move,
+                                                                KeyMove {
+                                                                    track: track_index,
+                                                                    key: key_index,
+                                                                    time,
+                                                                },
+                                                            );
+                                                        }
+                                                    },
+                                                );
+                                        },
+                                        |_| {},
+                                    );
+                                });
+                        },
+                        |_| {},
+                    );
+
+                    builder
+                        .spawn((Node::default(), Name::new("Timeline::Selection")))
+                        .style(style_selection)
+                        .insert(PickingBehavior::IGNORE)
+                        .style_dyn(
+                            move |rcx| {
+                                let duration = duration.get(rcx);
+                                let view = view.get(rcx);
+                                selection.get(rcx).map(|(lo, hi)| {
+                                    (view.percent(lo, duration), view.percent(hi, duration))
+                                })
+                            },
+                            |range, sb| match range {
+                                Some((lo, hi)) => {
+                                    sb.display(ui::Display::Flex)
+                                        .left(ui::Val::Percent(lo))
+                                        .width(ui::Val::Percent(hi - lo));
+                                }
+                                None => {
+                                    sb.display(ui::Display::None);
+                                }
+                            },
+                        );
+
+                    builder
+                        .spawn((Node::default(), Name::new("Timeline::Playhead")))
+                        .style(style_playhead)
+                        .insert(PickingBehavior::IGNORE)
+                        .style_dyn(
+                            move |rcx| view.get(rcx).percent(playhead.get(rcx), duration.get(rcx)),
+                            |left, sb| {
+                                sb.left(ui::Val::Percent(left));
+                            },
+                        );
+                });
+            });
+    }
+}
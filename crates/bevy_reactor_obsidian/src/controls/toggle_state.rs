@@ -1,4 +1,7 @@
-use crate::input_dispatch::{FocusKeyboardInput, SetKeyboardFocus};
+use crate::{
+    control_events::ValueChanged,
+    input_dispatch::{FocusKeyboardInput, SetKeyboardFocus},
+};
 use bevy::{ecs::world::DeferredWorld, input::ButtonState, prelude::*};
 use bevy_reactor_signals::{Callback, RunCallback, Signal};
 
@@ -10,19 +13,43 @@ pub struct ToggleState {
     pub(crate) on_change: Option<Callback<bool>>,
 }
 
+/// Toggle the state of an entity with a [`ToggleState`], as if it had been clicked. Does nothing
+/// if the entity is not a toggle or is disabled.
+pub(crate) fn toggle_entity(world: &mut DeferredWorld, entity: Entity) {
+    if world.get::<Disabled>(entity).is_some() {
+        return;
+    }
+    let Some((checked, on_change)) = world
+        .get::<ToggleState>(entity)
+        .map(|tstate| (tstate.checked, tstate.on_change))
+    else {
+        return;
+    };
+    let is_checked = checked.get(&*world);
+    world
+        .commands()
+        .trigger_targets(ValueChanged(!is_checked), entity);
+    if let Some(on_change) = on_change {
+        world.run_callback(on_change, !is_checked);
+    }
+}
+
 pub(crate) fn toggle_on_key_input(
     mut trigger: Trigger<FocusKeyboardInput>,
     q_state: Query<(&ToggleState, Has<Disabled>)>,
     mut world: DeferredWorld,
 ) {
     if let Ok((tstate, disabled)) = q_state.get(trigger.entity()) {
-        let event = &trigger.event().0;
+        let event = &trigger.event().input;
         if !disabled
             && event.state == ButtonState::Pressed
             && !event.repeat
             && (event.key_code == KeyCode::Enter || event.key_code == KeyCode::Space)
         {
             let is_checked = tstate.checked.get(&world);
+            world
+                .commands()
+                .trigger_targets(ValueChanged(!is_checked), trigger.entity());
             if let Some(on_change) = tstate.on_change {
                 trigger.propagate(false);
                 world.run_callback(on_change, !is_checked);
@@ -40,9 +67,12 @@ pub(crate) fn toggle_on_pointer_click(
         let checkbox_id = trigger.entity();
         world.set_keyboard_focus(checkbox_id);
         trigger.propagate(false);
-        if let Some(on_change) = tstate.on_change {
-            if !disabled {
-                let is_checked = tstate.checked.get(&world);
+        if !disabled {
+            let is_checked = tstate.checked.get(&world);
+            world
+                .commands()
+                .trigger_targets(ValueChanged(!is_checked), checkbox_id);
+            if let Some(on_change) = tstate.on_change {
                 world.run_callback(on_change, !is_checked);
             }
         }
@@ -9,11 +9,12 @@ use bevy::{
 use accesskit::{self, Role};
 use bevy_mod_stylebuilder::*;
 use bevy_reactor_builder::{
-    CreateChilden, EntityStyleBuilder, InvokeUiTemplate, UiBuilder, UiTemplate,
+    CreateChilden, EntityEffectBuilder, EntityStyleBuilder, InvokeUiTemplate, UiBuilder,
+    UiTemplate,
 };
 use bevy_reactor_signals::{Callback, IntoSignal, Signal};
 
-use super::{Button, ButtonVariant};
+use super::{button_group::ButtonGroupState, Button, ButtonVariant};
 
 fn style_tool_palette(ss: &mut StyleBuilder) {
     ss.display(ui::Display::Grid)
@@ -29,7 +30,9 @@ struct ToolPaletteContext {
     size: Size,
 }
 
-/// ToolPalette - a grid of tool buttons
+/// ToolPalette - a grid of tool buttons. The outer corners of the grid are rounded
+/// automatically, and the palette can optionally manage an exclusive selection among its
+/// buttons.
 pub struct ToolPalette {
     /// Button size.
     pub size: Size,
@@ -42,6 +45,12 @@ pub struct ToolPalette {
 
     /// Number of button columns
     pub columns: u16,
+
+    /// Index of the selected button, if the palette manages selection.
+    pub selected: Signal<Option<usize>>,
+
+    /// Callback called with the index of a button when it is clicked.
+    pub on_change: Option<Callback<usize>>,
 }
 
 impl Default for ToolPalette {
@@ -51,6 +60,8 @@ impl Default for ToolPalette {
             children: Arc::new(|_builder| {}),
             style: Default::default(),
             columns: Default::default(),
+            selected: Signal::Constant(None),
+            on_change: None,
         }
     }
 }
@@ -84,11 +95,24 @@ impl ToolPalette {
         self.columns = columns;
         self
     }
+
+    /// Set the index of the selected button. Setting this enables exclusive selection.
+    pub fn selected(mut self, selected: impl IntoSignal<Option<usize>>) -> Self {
+        self.selected = selected.into_signal();
+        self
+    }
+
+    /// Set the callback called when a button in the palette is clicked.
+    pub fn on_change(mut self, callback: Callback<usize>) -> Self {
+        self.on_change = Some(callback);
+        self
+    }
 }
 
 impl UiTemplate for ToolPalette {
     fn build(&self, builder: &mut UiBuilder) {
         let columns = self.columns;
+        let selected = self.selected;
 
         builder
             .spawn((Node::default(), Name::new("ToolPalette")))
@@ -100,7 +124,19 @@ impl UiTemplate for ToolPalette {
                 self.style.clone(),
             ))
             .insert(ToolPaletteContext { size: self.size })
+            .insert(ButtonGroupState {
+                columns: Some(columns as usize),
+                size: self.size,
+                selected: None,
+                on_change: self.on_change,
+            })
             .insert(AccessibilityNode::from(accesskit::Node::new(Role::Group)))
+            .component_effect::<ButtonGroupState, _, _, _>(
+                move |rcx| selected.get(rcx),
+                |selected, mut state| {
+                    state.selected = selected;
+                },
+            )
             .create_children(|builder| {
                 (self.children.as_ref())(builder);
             });
@@ -179,7 +215,8 @@ impl ToolButton {
         self
     }
 
-    /// Set which corners to render rounded.
+    /// Set which corners to render rounded. Inside a [`ToolPalette`], corners are assigned
+    /// automatically based on the button's position in the grid.
     pub fn corners(mut self, corners: RoundedCorners) -> Self {
         self.corners = corners;
         self
@@ -208,11 +245,13 @@ impl Default for ToolButton {
 
 impl UiTemplate for ToolButton {
     fn build(&self, builder: &mut UiBuilder) {
-        let context = builder
+        // Tool buttons may also be used outside of a palette, for example in a `ButtonGroup`.
+        let size = builder
             .use_inherited_component::<ToolPaletteContext>()
-            .unwrap();
+            .map(|context| context.size)
+            .unwrap_or_default();
         let mut btn = Button::new()
-            .size(context.size)
+            .size(size)
             .variant(self.variant)
             .disabled(self.disabled)
             .tab_index(self.tab_index)
@@ -0,0 +1,397 @@
+use std::{sync::Arc, time::Duration};
+
+use bevy::{
+    ecs::world::DeferredWorld,
+    math::CompassOctant,
+    prelude::*,
+    ui,
+    window::{PrimaryWindow, SystemCursorIcon},
+    winit::cursor::CursorIcon,
+};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{
+    CreateChilden, EntityStyleBuilder, InvokeUiTemplate, TextBuilder, UiBuilder, UiTemplate,
+};
+use bevy_reactor_signals::{Callback, IntoSignal, RunCallback, Signal};
+
+use crate::{colors, cursor::StyleBuilderCursor, size::Size, typography::text_default};
+
+use super::IconButton;
+
+/// Two clicks on the title area within this interval toggle maximization.
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
+type WindowQuery<'w, 's> = Query<'w, 's, (Entity, &'static mut Window, Has<PrimaryWindow>)>;
+
+/// Returns the given window, or the primary window if none was given.
+fn target_window<'a>(
+    q_windows: &'a mut WindowQuery,
+    window: Option<Entity>,
+) -> Option<Mut<'a, Window>> {
+    q_windows
+        .iter_mut()
+        .find(|(entity, _, primary)| match window {
+            Some(window) => *entity == window,
+            None => *primary,
+        })
+        .map(|(_, window, _)| window)
+}
+
+fn style_title_bar(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .height(28)
+        .padding_left(8)
+        .gap(4)
+        .background_color(colors::U1);
+}
+
+fn style_title_area(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .justify_content(ui::JustifyContent::Center)
+        .align_self(ui::AlignSelf::Stretch)
+        .flex_grow(1.)
+        .color(colors::DIM);
+}
+
+/// A title bar for a borderless window. Dragging the title area moves the window, and
+/// double-clicking it toggles maximization. Displays buttons for minimizing, maximizing and
+/// closing the window.
+pub struct TitleBar {
+    /// The window to control. Defaults to the primary window.
+    pub window: Option<Entity>,
+
+    /// The title text.
+    pub title: Signal<String>,
+
+    /// Additional content placed before the title, such as menus.
+    pub children: Arc<dyn Fn(&mut UiBuilder) + Send + Sync>,
+
+    /// Callback called when the minimize button is clicked. If not set, the window is
+    /// minimized.
+    pub on_minimize: Option<Callback>,
+
+    /// Callback called when the maximize button is clicked or the title is double-clicked.
+    /// If not set, maximization of the window is toggled.
+    pub on_maximize: Option<Callback>,
+
+    /// Callback called when the close button is clicked. If not set, the window is closed.
+    pub on_close: Option<Callback>,
+
+    /// Additional styles to be applied to the title bar.
+    pub style: StyleHandle,
+}
+
+impl Default for TitleBar {
+    fn default() -> Self {
+        Self {
+            window: None,
+            title: Signal::Constant(String::new()),
+            children: Arc::new(|_| {}),
+            on_minimize: None,
+            on_maximize: None,
+            on_close: None,
+            style: StyleHandle::default(),
+        }
+    }
+}
+
+impl TitleBar {
+    /// Create a new title bar for the primary window.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set which window the title bar controls.
+    pub fn window(mut self, window: Entity) -> Self {
+        self.window = Some(window);
+        self
+    }
+
+    /// Set the title text.
+    pub fn title(mut self, title: impl IntoSignal<String>) -> Self {
+        self.title = title.into_signal();
+        self
+    }
+
+    /// Set the content placed before the title.
+    pub fn children<V: 'static + Fn(&mut UiBuilder) + Send + Sync>(mut self, children: V) -> Self {
+        self.children = Arc::new(children);
+        self
+    }
+
+    /// Set the callback called when the minimize button is clicked.
+    pub fn on_minimize(mut self, callback: Callback) -> Self {
+        self.on_minimize = Some(callback);
+        self
+    }
+
+    /// Set the callback called when the window is maximized or restored.
+    pub fn on_maximize(mut self, callback: Callback) -> Self {
+        self.on_maximize = Some(callback);
+        self
+    }
+
+    /// Set the callback called when the close button is clicked.
+    pub fn on_close(mut self, callback: Callback) -> Self {
+        self.on_close = Some(callback);
+        self
+    }
+
+    /// Set the additional styles for the title bar.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl UiTemplate for TitleBar {
+    fn build(&self, builder: &mut UiBuilder) {
+        let window = self.window;
+        let title = self.title.clone();
+
+        let minimize = self.on_minimize.unwrap_or_else(|| {
+            builder.create_callback(move |_: In<()>, mut q_windows: WindowQuery| {
+                if let Some(mut window) = target_window(&mut q_windows, window) {
+                    window.set_minimized(true);
+                }
+            })
+        });
+        let maximize = self.on_maximize.unwrap_or_else(|| {
+            builder.create_callback(
+                move |_: In<()>, mut maximized: Local<bool>, mut q_windows: WindowQuery| {
+                    if let Some(mut window) = target_window(&mut q_windows, window) {
+                        *maximized = !*maximized;
+                        window.set_maximized(*maximized);
+                    }
+                },
+            )
+        });
+        let close = self.on_close.unwrap_or_else(|| {
+            builder.create_callback(
+                move |_: In<()>,
+                      mut commands: Commands,
+                      q_windows: Query<(Entity, Has<PrimaryWindow>), With<Window>>| {
+                    let target = q_windows.iter().find(|(entity, primary)| match window {
+                        Some(window) => *entity == window,
+                        None => *primary,
+                    });
+                    if let Some((target, _)) = target {
+                        commands.entity(target).despawn_recursive();
+                    }
+                },
+            )
+        });
+
+        builder
+            .spawn((Node::default(), Name::new("TitleBar")))
+            .style((style_title_bar, self.style.clone()))
+            .create_children(|builder| {
+                (self.children.as_ref())(builder);
+
+                let mut last_click: Option<Duration> = None;
+                builder
+                    .spawn((Node::default(), Name::new("TitleArea")))
+                    .style((text_default, style_title_area))
+                    .observe(
+                        move |mut trigger: Trigger<Pointer<DragStart>>,
+                              mut q_windows: WindowQuery| {
+                            trigger.propagate(false);
+                            if trigger.event().button != PointerButton::Primary {
+                                return;
+                            }
+                            if let Some(mut window) = target_window(&mut q_windows, window) {
+                                window.start_drag_move();
+                            }
+                        },
+                    )
+                    .observe(
+                        move |mut trigger: Trigger<Pointer<Click>>, mut world: DeferredWorld| {
+                            trigger.propagate(false);
+                            if trigger.event().button != PointerButton::Primary {
+                                return;
+                            }
+                            let now = world.resource::<Time<Real>>().elapsed();
+                            match last_click {
+                                Some(last) if now - last < DOUBLE_CLICK_INTERVAL => {
+                                    last_click = None;
+                                    world.run_callback(maximize, ());
+                                }
+                                _ => last_click = Some(now),
+                            }
+                        },
+                    )
+                    .create_children(|builder| {
+                        builder.text_computed(move |rcx| title.get_clone(rcx));
+                    });
+
+                builder.invoke(
+                    IconButton::new("embedded://bevy_reactor_obsidian/assets/icons/remove.png")
+                        .size(Size::Sm)
+                        .minimal(true)
+                        .tab_index(-1)
+                        .on_click(minimize),
+                );
+                builder.invoke(
+                    IconButton::new("embedded://bevy_reactor_obsidian/assets/icons/add_box.png")
+                        .size(Size::Sm)
+                        .minimal(true)
+                        .tab_index(-1)
+                        .on_click(maximize),
+                );
+                builder.invoke(
+                    IconButton::new("embedded://bevy_reactor_obsidian/assets/icons/close.png")
+                        .size(Size::Sm)
+                        .minimal(true)
+                        .tab_index(-1)
+                        .on_click(close),
+                );
+            });
+    }
+}
+
+/// An invisible grip along an edge or corner of a borderless window, which resizes the window
+/// when dragged. Should be placed in a root node which covers the whole window.
+#[derive(Clone)]
+pub struct WindowResizeGrip {
+    /// The window to resize. Defaults to the primary window.
+    pub window: Option<Entity>,
+
+    /// Which edge or corner of the window the grip is on.
+    pub direction: CompassOctant,
+
+    /// Thickness of the grip, in pixels.
+    pub thickness: f32,
+}
+
+impl WindowResizeGrip {
+    /// Create a new resize grip on the given edge or corner of the primary window.
+    pub fn new(direction: CompassOctant) -> Self {
+        Self {
+            window: None,
+            direction,
+            thickness: 5.,
+        }
+    }
+
+    /// Set which window the grip resizes.
+    pub fn window(mut self, window: Entity) -> Self {
+        self.window = Some(window);
+        self
+    }
+
+    /// Set the thickness of the grip.
+    pub fn thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+}
+
+impl UiTemplate for WindowResizeGrip {
+    fn build(&self, builder: &mut UiBuilder) {
+        let window = self.window;
+        let direction = self.direction;
+        let thickness = self.thickness;
+        builder
+            .spawn((Node::default(), Name::new("WindowResizeGrip")))
+            .style(move |ss: &mut StyleBuilder| {
+                ss.position(PositionType::Absolute);
+                let (top, bottom, left, right) = match direction {
+                    CompassOctant::North => (true, false, false, false),
+                    CompassOctant::NorthEast => (true, false, false, true),
+                    CompassOctant::East => (false, false, false, true),
+                    CompassOctant::SouthEast => (false, true, false, true),
+                    CompassOctant::South => (false, true, false, false),
+                    CompassOctant::SouthWest => (false, true, true, false),
+                    CompassOctant::West => (false, false, true, false),
+                    CompassOctant::NorthWest => (true, false, true, false),
+                };
+                // Edges span the window between the corner grips.
+                if top || bottom {
+                    ss.height(thickness);
+                } else {
+                    ss.top(thickness).bottom(thickness);
+                }
+                if left || right {
+                    ss.width(thickness);
+                } else {
+                    ss.left(thickness).right(thickness);
+                }
+                if top {
+                    ss.top(0);
+                }
+                if bottom {
+                    ss.bottom(0);
+                }
+                if left {
+                    ss.left(0);
+                }
+                if right {
+                    ss.right(0);
+                }
+                ss.cursor(CursorIcon::System(match direction {
+                    CompassOctant::North | CompassOctant::South => SystemCursorIcon::NsResize,
+                    CompassOctant::East | CompassOctant::West => SystemCursorIcon::EwResize,
+                    CompassOctant::NorthEast | CompassOctant::SouthWest => {
+                        SystemCursorIcon::NeswResize
+                    }
+                    CompassOctant::NorthWest | CompassOctant::SouthEast => {
+                        SystemCursorIcon::NwseResize
+                    }
+                }));
+            })
+            .observe(
+                move |mut trigger: Trigger<Pointer<Down>>, mut q_windows: WindowQuery| {
+                    trigger.propagate(false);
+                    if trigger.event().button != PointerButton::Primary {
+                        return;
+                    }
+                    if let Some(mut window) = target_window(&mut q_windows, window) {
+                        window.start_drag_resize(direction);
+                    }
+                },
+            );
+    }
+}
+
+/// Resize grips on all four edges and corners of a borderless window.
+#[derive(Clone, Default)]
+pub struct WindowResizeGrips {
+    /// The window to resize. Defaults to the primary window.
+    pub window: Option<Entity>,
+}
+
+impl WindowResizeGrips {
+    /// Create resize grips for the primary window.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set which window the grips resize.
+    pub fn window(mut self, window: Entity) -> Self {
+        self.window = Some(window);
+        self
+    }
+}
+
+impl UiTemplate for WindowResizeGrips {
+    fn build(&self, builder: &mut UiBuilder) {
+        for direction in [
+            CompassOctant::North,
+            CompassOctant::NorthEast,
+            CompassOctant::East,
+            CompassOctant::SouthEast,
+            CompassOctant::South,
+            CompassOctant::SouthWest,
+            CompassOctant::West,
+            CompassOctant::NorthWest,
+        ] {
+            let mut grip = WindowResizeGrip::new(direction);
+            grip.window = self.window;
+            builder.invoke(grip);
+        }
+    }
+}
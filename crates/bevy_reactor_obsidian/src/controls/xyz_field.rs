@@ -0,0 +1,271 @@
+use bevy::{ecs::world::DeferredWorld, prelude::*, ui};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{
+    CreateChilden, EntityStyleBuilder, InvokeUiTemplate, UiBuilder, UiTemplate,
+};
+use bevy_reactor_signals::{Callback, IntoSignal, RunCallback, Signal};
+
+use crate::{colors, size::Size};
+
+use super::{Button, ButtonVariant, DragScaling, Icon, SpinBox};
+
+fn style_xyz_field(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .gap(2);
+}
+
+fn style_axis(ss: &mut StyleBuilder) {
+    ss.flex_grow(1.)
+        .flex_basis(0)
+        .border_left(3)
+        .border_radius(5);
+}
+
+fn style_link_button(ss: &mut StyleBuilder) {
+    ss.height(20).padding((2, 0));
+}
+
+/// Applies an edit of one axis to the whole vector. When linked, the other axes are scaled by
+/// the same proportion, or offset by the same amount if the edited axis was zero.
+fn apply_axis_edit(prev: Vec3, axis: usize, value: f32, linked: bool) -> Vec3 {
+    if !linked {
+        let mut result = prev;
+        result[axis] = value;
+        return result;
+    }
+    let old = prev[axis];
+    if old.abs() > f32::EPSILON {
+        prev * (value / old)
+    } else {
+        prev + Vec3::splat(value - old)
+    }
+}
+
+/// Three compact numeric fields for editing a [`Vec3`], one per axis, each accented with the
+/// axis color. Each axis can be dragged to scrub its value. An optional link toggle makes edits
+/// to one axis scale the others proportionally.
+pub struct XYZField {
+    /// Current field value.
+    pub value: Signal<Vec3>,
+
+    /// Minimum value for each axis.
+    pub min: f32,
+
+    /// Maximum value for each axis.
+    pub max: f32,
+
+    /// Number of decimal places to display for each axis.
+    pub precision: usize,
+
+    /// Amount by which each axis changes per step when scrubbing.
+    pub step: f32,
+
+    /// Whether edits are proportional across all axes.
+    pub linked: Signal<bool>,
+
+    /// Whether to show the button which toggles proportional editing.
+    pub show_link: bool,
+
+    /// Whether the field is disabled.
+    pub disabled: Signal<bool>,
+
+    /// Additional styles to be applied to the field.
+    pub style: StyleHandle,
+
+    /// Callback called with the new value when any axis changes.
+    pub on_change: Option<Callback<Vec3>>,
+
+    /// Callback called when the link button is clicked, with the new linked state. If not
+    /// set, the field keeps its own linked state.
+    pub on_link_change: Option<Callback<bool>>,
+
+    /// How drag movement is converted into value changes.
+    pub drag_scaling: DragScaling,
+}
+
+impl Default for XYZField {
+    fn default() -> Self {
+        Self {
+            value: Signal::Constant(Vec3::ZERO),
+            min: f32::MIN,
+            max: f32::MAX,
+            precision: 2,
+            step: 0.1,
+            linked: Signal::Constant(false),
+            show_link: false,
+            disabled: Signal::Constant(false),
+            style: StyleHandle::default(),
+            on_change: None,
+            on_link_change: None,
+            drag_scaling: DragScaling::default(),
+        }
+    }
+}
+
+impl XYZField {
+    /// Construct a new `XYZField`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the current field value.
+    pub fn value(mut self, value: impl IntoSignal<Vec3>) -> Self {
+        self.value = value.into_signal();
+        self
+    }
+
+    /// Set the minimum value for each axis.
+    pub fn min(mut self, min: f32) -> Self {
+        self.min = min;
+        self
+    }
+
+    /// Set the maximum value for each axis.
+    pub fn max(mut self, max: f32) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Set the number of decimal places to display for each axis.
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Set the amount by which each axis changes per step when scrubbing.
+    pub fn step(mut self, step: f32) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Set whether edits are proportional across all axes.
+    pub fn linked(mut self, linked: impl IntoSignal<bool>) -> Self {
+        self.linked = linked.into_signal();
+        self
+    }
+
+    /// Set whether to show the button which toggles proportional editing.
+    pub fn show_link(mut self, show_link: bool) -> Self {
+        self.show_link = show_link;
+        self
+    }
+
+    /// Set the field disabled state.
+    pub fn disabled(mut self, disabled: impl IntoSignal<bool>) -> Self {
+        self.disabled = disabled.into_signal();
+        self
+    }
+
+    /// Set additional styles to be applied to the field.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+
+    /// Set the callback called when any axis changes.
+    pub fn on_change(mut self, callback: Callback<Vec3>) -> Self {
+        self.on_change = Some(callback);
+        self
+    }
+
+    /// Set the callback called when the link button is clicked.
+    pub fn on_link_change(mut self, callback: Callback<bool>) -> Self {
+        self.on_link_change = Some(callback);
+        self
+    }
+
+    /// Set how drag movement is converted into value changes.
+    pub fn drag_scaling(mut self, drag_scaling: DragScaling) -> Self {
+        self.drag_scaling = drag_scaling;
+        self
+    }
+}
+
+impl UiTemplate for XYZField {
+    fn build(&self, builder: &mut UiBuilder) {
+        let value = self.value;
+        let on_change = self.on_change;
+        let on_link_change = self.on_link_change;
+
+        // If nobody is listening for link changes, the field tracks its own linked state,
+        // starting from the given value.
+        let linked_prop = self.linked;
+        let own_linked = builder.create_mutable::<Option<bool>>(None);
+        let linked = builder
+            .create_derived(move |rcx| own_linked.get(rcx).unwrap_or_else(|| linked_prop.get(rcx)));
+
+        let toggle_link = builder.create_callback(move |_: In<()>, mut world: DeferredWorld| {
+            let next = !linked.get(&world);
+            match on_link_change {
+                Some(on_link_change) => world.run_callback(on_link_change, next),
+                None => own_linked.set(&mut world, Some(next)),
+            }
+        });
+
+        builder
+            .spawn((Node::default(), Name::new("XYZField")))
+            .styles((style_xyz_field, self.style.clone()))
+            .create_children(|builder| {
+                for (axis, color) in [colors::X_RED, colors::Y_GREEN, colors::Z_BLUE]
+                    .into_iter()
+                    .enumerate()
+                {
+                    let on_axis_change =
+                        builder.create_callback(move |v: In<f32>, mut world: DeferredWorld| {
+                            let prev = value.get(&world);
+                            let next = apply_axis_edit(prev, axis, *v, linked.get(&world));
+                            if next != prev {
+                                if let Some(on_change) = on_change {
+                                    world.run_callback(on_change, next);
+                                }
+                            }
+                        });
+                    let axis_value = builder.create_derived(move |rcx| value.get(rcx)[axis]);
+                    builder.invoke(
+                        SpinBox::new()
+                            .value(axis_value)
+                            .min(self.min)
+                            .max(self.max)
+                            .precision(self.precision)
+                            .step(self.step)
+                            .disabled(self.disabled)
+                            .drag_scaling(self.drag_scaling)
+                            .style((style_axis, move |ss: &mut StyleBuilder| {
+                                ss.border_color(color);
+                            }))
+                            .on_change(on_axis_change),
+                    );
+                }
+
+                if self.show_link {
+                    let variant = builder.create_derived(move |rcx| {
+                        if linked.get(rcx) {
+                            ButtonVariant::Selected
+                        } else {
+                            ButtonVariant::Default
+                        }
+                    });
+                    builder.invoke(
+                        Button::new()
+                            .variant(variant)
+                            .size(Size::Xs)
+                            .minimal(true)
+                            .style(style_link_button)
+                            .disabled(self.disabled)
+                            .on_click(toggle_link)
+                            .children(|builder| {
+                                builder.invoke(
+                                    Icon::new(
+                                        "embedded://bevy_reactor_obsidian/assets/icons/lock.png",
+                                    )
+                                    .size(Vec2::splat(12.))
+                                    .color(Color::from(colors::DIM)),
+                                );
+                            }),
+                    );
+                }
+            });
+    }
+}
@@ -0,0 +1,309 @@
+use std::sync::Arc;
+
+use bevy::{
+    ecs::world::DeferredWorld,
+    input::mouse::MouseScrollUnit,
+    prelude::*,
+    ui::{self, RelativeCursorPosition},
+};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{
+    CreateChilden, EntityEffectBuilder, EntityStyleBuilder, UiBuilder, UiTemplate,
+};
+use bevy_reactor_signals::Mutable;
+
+use crate::{
+    colors,
+    materials::{update_ui_material, CreateUiMaterial, DotGridMaterial},
+    scrolling::ScrollWheelEvent,
+};
+
+fn style_zoom_canvas(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .overflow(ui::OverflowAxis::Clip)
+        .background_color(colors::U1);
+}
+
+fn style_grid(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .left(0)
+        .top(0)
+        .right(0)
+        .bottom(0);
+}
+
+fn style_content(ss: &mut StyleBuilder) {
+    // The content has no size of its own, so that it is scaled around its top-left corner.
+    ss.position(ui::PositionType::Absolute).width(0).height(0);
+}
+
+/// The pan and zoom of a [`ZoomCanvas`]. A point in canvas coordinates is drawn at
+/// `point * zoom + offset`, in logical pixels relative to the top-left of the canvas widget.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CanvasTransform {
+    /// Position of the canvas origin, relative to the top-left of the widget.
+    pub offset: Vec2,
+    /// Scale factor.
+    pub zoom: f32,
+}
+
+impl Default for CanvasTransform {
+    fn default() -> Self {
+        Self {
+            offset: Vec2::ZERO,
+            zoom: 1.,
+        }
+    }
+}
+
+impl CanvasTransform {
+    /// Convert a position relative to the top-left of the widget into canvas coordinates.
+    pub fn local_to_canvas(&self, local: Vec2) -> Vec2 {
+        (local - self.offset) / self.zoom
+    }
+
+    /// Convert a position in canvas coordinates into a position relative to the top-left of
+    /// the widget.
+    pub fn canvas_to_local(&self, canvas: Vec2) -> Vec2 {
+        canvas * self.zoom + self.offset
+    }
+
+    /// Return a transform with the zoom multiplied by `factor`, clamped to `range`, keeping
+    /// the canvas point under `anchor` (in local coordinates) fixed.
+    pub fn zoom_around(&self, anchor: Vec2, factor: f32, range: (f32, f32)) -> Self {
+        let zoom = (self.zoom * factor).clamp(range.0, range.1);
+        let canvas = self.local_to_canvas(anchor);
+        Self {
+            offset: anchor - canvas * zoom,
+            zoom,
+        }
+    }
+
+    /// Return a transform which is panned by `delta` logical pixels.
+    pub fn panned(&self, delta: Vec2) -> Self {
+        Self {
+            offset: self.offset + delta,
+            zoom: self.zoom,
+        }
+    }
+}
+
+/// Component placed on the viewport of a [`ZoomCanvas`], holding its transform. This is used
+/// by [`screen_to_canvas`] and [`canvas_to_screen`].
+#[derive(Component, Clone, Copy)]
+pub struct ZoomCanvasViewport(pub Mutable<CanvasTransform>);
+
+/// Returns the position of the top-left corner of a UI node, in logical window coordinates.
+fn node_origin(world: &World, entity: Entity) -> Option<Vec2> {
+    let node = world.get::<ComputedNode>(entity)?;
+    let transform = world.get::<GlobalTransform>(entity)?;
+    Some((transform.translation().xy() - node.size() * 0.5) * node.inverse_scale_factor())
+}
+
+/// Convert a window position, such as a pointer location, into canvas coordinates for the
+/// given [`ZoomCanvas`] viewport.
+pub fn screen_to_canvas(world: &World, viewport: Entity, screen: Vec2) -> Option<Vec2> {
+    let canvas = world.get::<ZoomCanvasViewport>(viewport)?.0;
+    let origin = node_origin(world, viewport)?;
+    Some(canvas.get(world).local_to_canvas(screen - origin))
+}
+
+/// Convert a position in canvas coordinates into a window position for the given
+/// [`ZoomCanvas`] viewport.
+pub fn canvas_to_screen(world: &World, viewport: Entity, canvas: Vec2) -> Option<Vec2> {
+    let transform = world.get::<ZoomCanvasViewport>(viewport)?.0;
+    let origin = node_origin(world, viewport)?;
+    Some(transform.get(world).canvas_to_local(canvas) + origin)
+}
+
+/// A container which can be panned and zoomed, for things like node graphs and curve editors.
+/// Children are positioned in canvas coordinates, typically using absolute positioning.
+///
+/// The mouse wheel zooms around the cursor. Dragging with the middle mouse button, or with the
+/// primary button on an empty part of the canvas, pans.
+///
+/// Note that the content is scaled using its `Transform`, which picking does not take into
+/// account: the hit areas of children are centered correctly, but not scaled.
+pub struct ZoomCanvas {
+    /// Views for the canvas content.
+    pub children: Arc<dyn Fn(&mut UiBuilder)>,
+    /// The pan and zoom of the canvas. If not set, the canvas keeps its own.
+    pub transform: Option<Mutable<CanvasTransform>>,
+    /// Minimum and maximum zoom factors.
+    pub zoom_range: (f32, f32),
+    /// Spacing of the background grid, in canvas units. If zero, no grid is drawn.
+    pub grid_spacing: f32,
+    /// Additional styles to be applied to the canvas.
+    pub style: StyleHandle,
+}
+
+impl Default for ZoomCanvas {
+    fn default() -> Self {
+        Self {
+            children: Arc::new(|_| {}),
+            transform: None,
+            zoom_range: (0.1, 10.),
+            grid_spacing: 0.,
+            style: StyleHandle::default(),
+        }
+    }
+}
+
+impl ZoomCanvas {
+    /// Create a new `ZoomCanvas`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the child views for this element.
+    pub fn children<V: 'static + Fn(&mut UiBuilder)>(mut self, children: V) -> Self {
+        self.children = Arc::new(children);
+        self
+    }
+
+    /// Set the mutable which holds the pan and zoom, so that it can be read or changed from
+    /// outside the canvas.
+    pub fn transform(mut self, transform: Mutable<CanvasTransform>) -> Self {
+        self.transform = Some(transform);
+        self
+    }
+
+    /// Set the minimum and maximum zoom factors.
+    pub fn zoom_range(mut self, min: f32, max: f32) -> Self {
+        self.zoom_range = (min, max);
+        self
+    }
+
+    /// Draw a dot grid in the background, with the given spacing in canvas units.
+    pub fn grid(mut self, spacing: f32) -> Self {
+        self.grid_spacing = spacing;
+        self
+    }
+
+    /// Set additional styles to be applied to the canvas.
+    pub fn style<S: StyleTuple + 'static>(mut self, style: S) -> Self {
+        self.style = style.into_handle();
+        self
+    }
+}
+
+impl UiTemplate for ZoomCanvas {
+    fn build(&self, builder: &mut UiBuilder) {
+        let transform = self
+            .transform
+            .unwrap_or_else(|| builder.create_mutable(CanvasTransform::default()));
+        let zoom_range = self.zoom_range;
+        let grid_spacing = self.grid_spacing;
+
+        let viewport = builder
+            .spawn((
+                Node::default(),
+                Name::new("ZoomCanvas"),
+                ZoomCanvasViewport(transform),
+                RelativeCursorPosition::default(),
+            ))
+            .id();
+
+        builder
+            .entity_mut(viewport)
+            .styles((style_zoom_canvas, self.style.clone()))
+            .observe(
+                move |mut trigger: Trigger<ScrollWheelEvent>, mut world: DeferredWorld| {
+                    trigger.propagate(false);
+                    let event = &trigger.event().0;
+                    let dy = match event.unit {
+                        MouseScrollUnit::Line => event.y,
+                        MouseScrollUnit::Pixel => event.y / 20.,
+                    };
+                    let Some((cursor, size)) = world
+                        .get::<RelativeCursorPosition>(viewport)
+                        .and_then(|pos| pos.normalized)
+                        .zip(world.get::<ComputedNode>(viewport))
+                        .map(|(pos, node)| (pos, node.size() * node.inverse_scale_factor()))
+                    else {
+                        return;
+                    };
+                    let old = transform.get(&world);
+                    transform.set(
+                        &mut world,
+                        old.zoom_around(cursor * size, 1.1f32.powf(dy), zoom_range),
+                    );
+                },
+            )
+            .observe(
+                move |mut trigger: Trigger<Pointer<Drag>>, mut world: DeferredWorld| {
+                    let event = trigger.event();
+                    let delta = event.delta;
+                    let pan = match event.button {
+                        PointerButton::Middle => true,
+                        // Only pan with the primary button when dragging the background, so
+                        // that children can handle their own drags.
+                        PointerButton::Primary => event.target == viewport,
+                        PointerButton::Secondary => false,
+                    };
+                    if pan {
+                        trigger.propagate(false);
+                        let old = transform.get(&world);
+                        transform.set(&mut world, old.panned(delta));
+                    }
+                },
+            )
+            .create_children(|builder| {
+                if grid_spacing > 0. {
+                    let material = builder.create_ui_material(DotGridMaterial {
+                        color_bg: LinearRgba::from(colors::U1).to_vec4(),
+                        color_fg: LinearRgba::from(colors::U3).to_vec4(),
+                        offset: Vec2::ZERO,
+                        spacing: grid_spacing,
+                        scale: 1.,
+                    });
+                    let material_id = material.id();
+                    let grid = builder
+                        .spawn((
+                            MaterialNode(material),
+                            Name::new("ZoomCanvas::Grid"),
+                            // Let drags on the background go to the viewport.
+                            PickingBehavior::IGNORE,
+                        ))
+                        .style(style_grid)
+                        .id();
+
+                    // Scroll the grid along with the content.
+                    builder.create_effect(move |ecx| {
+                        let t = transform.get(ecx);
+                        let scale = ecx
+                            .use_component_map::<ComputedNode, _, _>(grid, |node| {
+                                node.inverse_scale_factor().recip()
+                            })
+                            .unwrap_or(1.);
+                        update_ui_material(ecx.world_mut(), material_id, |material| {
+                            material.offset = t.offset;
+                            material.spacing = grid_spacing * t.zoom;
+                            material.scale = scale;
+                        });
+                    });
+                }
+
+                builder
+                    .spawn((Node::default(), Name::new("ZoomCanvas::Content")))
+                    .style(style_content)
+                    .style_dyn(
+                        move |rcx| transform.get(rcx).offset,
+                        |offset, sb| {
+                            sb.left(offset.x).top(offset.y);
+                        },
+                    )
+                    .effect(
+                        move |rcx| transform.get(rcx).zoom,
+                        |zoom, ent| {
+                            if let Some(mut transform) = ent.get_mut::<Transform>() {
+                                transform.scale = Vec3::new(zoom, zoom, 1.);
+                            }
+                        },
+                    )
+                    .create_children(|builder| {
+                        (self.children.as_ref())(builder);
+                    });
+            });
+    }
+}
@@ -0,0 +1,339 @@
+use std::sync::Arc;
+
+use bevy::{ecs::world::DeferredWorld, prelude::*};
+use bevy_reactor_builder::UiBuilder;
+use bevy_reactor_signals::{
+    Callback, Mutable, Rcx, ReadDerived, ReadMutable, RunCallback, Signal, WriteMutable,
+};
+
+type Validator<T> = Arc<dyn Fn(&T) -> Result<(), String> + Send + Sync>;
+
+/// Type-erased interface to a [`FormField`], used by the [`Form`] to validate and reset all
+/// of its fields regardless of their value types.
+trait AnyFormField: Send + Sync {
+    /// Reactively check whether the field's value passes validation.
+    fn is_valid(&self, rcx: &Rcx) -> bool;
+
+    /// Reactively check whether the field's value differs from its initial value.
+    fn is_dirty(&self, rcx: &Rcx) -> bool;
+
+    /// Check whether the field's value passes validation, outside of a reactive context.
+    fn check(&self, world: &DeferredWorld) -> bool;
+
+    /// Restore the field's initial value.
+    fn reset(&self, world: &mut DeferredWorld);
+
+    /// Make the field's current value its new initial value.
+    fn commit(&self, world: &mut DeferredWorld);
+}
+
+/// The list of fields registered with a form. Fields are compared by identity.
+#[derive(Clone, Default)]
+struct FormFields(Vec<Arc<dyn AnyFormField>>);
+
+impl PartialEq for FormFields {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len()
+            && self
+                .0
+                .iter()
+                .zip(other.0.iter())
+                .all(|(a, b)| Arc::ptr_eq(a, b))
+    }
+}
+
+/// A single value within a [`Form`], along with its validation rule and initial value.
+pub struct FormField<T: 'static> {
+    value: Mutable<T>,
+    initial: Mutable<T>,
+    submitted: Mutable<bool>,
+    validator: Validator<T>,
+}
+
+impl<T> Clone for FormField<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value,
+            initial: self.initial,
+            submitted: self.submitted,
+            validator: self.validator.clone(),
+        }
+    }
+}
+
+impl<T: PartialEq + Clone + Send + Sync + 'static> FormField<T> {
+    /// Signal containing the current value of the field.
+    pub fn value(&self) -> Signal<T> {
+        self.value.signal()
+    }
+
+    /// Get the current value of the field.
+    pub fn get<R: ReadMutable + ReadDerived>(&self, cx: &R) -> T {
+        self.value.signal().get_clone(cx)
+    }
+
+    /// Set the current value of the field.
+    pub fn set<W: WriteMutable>(&self, cx: &mut W, value: T) {
+        self.value.set_clone(cx, value);
+    }
+
+    /// Run the validator against the current value of the field.
+    pub fn validate<R: ReadMutable + ReadDerived>(&self, cx: &R) -> Result<(), String> {
+        self.value.signal().map(cx, |value| (self.validator)(value))
+    }
+
+    /// True if the current value of the field differs from its initial value.
+    pub fn is_dirty<R: ReadMutable + ReadDerived>(&self, cx: &R) -> bool {
+        self.value.signal().map(cx, |value| {
+            self.initial.signal().map(cx, |initial| value != initial)
+        })
+    }
+
+    /// The validation error to display for this field, if any. Errors are only reported once
+    /// the field has been edited, or after the user has attempted to submit the form, so that
+    /// a pristine form isn't covered in error messages.
+    pub fn error<R: ReadMutable + ReadDerived>(&self, cx: &R) -> Option<String> {
+        if !self.is_dirty(cx) && !self.submitted.get(cx) {
+            return None;
+        }
+        self.validate(cx).err()
+    }
+
+    /// Create a signal containing the validation error to display for this field.
+    pub fn create_error_signal(&self, builder: &mut UiBuilder) -> Signal<Option<String>> {
+        let field = self.clone();
+        builder.create_derived(move |rcx| field.error(rcx))
+    }
+
+    /// Create a callback which sets the value of this field, suitable for passing to the
+    /// `on_change` property of an input control.
+    pub fn create_setter(&self, builder: &mut UiBuilder) -> Callback<T> {
+        let value = self.value;
+        builder.create_callback(move |new_value: In<T>, mut world: DeferredWorld| {
+            value.set_clone(&mut world, new_value.0);
+        })
+    }
+}
+
+impl<T: PartialEq + Clone + Send + Sync + 'static> AnyFormField for FormField<T> {
+    fn is_valid(&self, rcx: &Rcx) -> bool {
+        self.validate(rcx).is_ok()
+    }
+
+    fn is_dirty(&self, rcx: &Rcx) -> bool {
+        FormField::is_dirty(self, rcx)
+    }
+
+    fn check(&self, world: &DeferredWorld) -> bool {
+        self.validate(world).is_ok()
+    }
+
+    fn reset(&self, world: &mut DeferredWorld) {
+        let initial = self.initial.signal().get_clone(world);
+        self.value.set_clone(world, initial);
+    }
+
+    fn commit(&self, world: &mut DeferredWorld) {
+        let value = self.value.signal().get_clone(world);
+        self.initial.set_clone(world, value);
+    }
+}
+
+/// Groups a set of [`FormField`]s, tracking whether they are valid and whether any of them
+/// have been edited. Submitting the form runs the `on_submit` callback only if every field
+/// passes validation.
+#[derive(Clone, Copy)]
+pub struct Form {
+    fields: Mutable<FormFields>,
+    submitted: Mutable<bool>,
+    on_submit: Option<Callback>,
+}
+
+impl Form {
+    /// Add a new field to the form with the given initial value. The validator returns an
+    /// error message if the value is not acceptable.
+    pub fn field<T, V>(&self, builder: &mut UiBuilder, initial: T, validator: V) -> FormField<T>
+    where
+        T: PartialEq + Clone + Send + Sync + 'static,
+        V: Fn(&T) -> Result<(), String> + Send + Sync + 'static,
+    {
+        let field = FormField {
+            value: builder.create_mutable(initial.clone()),
+            initial: builder.create_mutable(initial),
+            submitted: self.submitted,
+            validator: Arc::new(validator),
+        };
+        let entry: Arc<dyn AnyFormField> = Arc::new(field.clone());
+        self.fields
+            .update(builder.world_mut(), |mut fields| fields.0.push(entry));
+        field
+    }
+
+    /// True if every field in the form passes validation.
+    pub fn is_valid(&self, rcx: &Rcx) -> bool {
+        self.fields
+            .signal()
+            .get_clone(rcx)
+            .0
+            .iter()
+            .all(|field| field.is_valid(rcx))
+    }
+
+    /// True if any field in the form has been changed from its initial value.
+    pub fn is_dirty(&self, rcx: &Rcx) -> bool {
+        self.fields
+            .signal()
+            .get_clone(rcx)
+            .0
+            .iter()
+            .any(|field| field.is_dirty(rcx))
+    }
+
+    /// Attempt to submit the form. If all fields are valid, the `on_submit` callback is run and
+    /// the current values become the new initial values, so the form is pristine again.
+    /// Otherwise, error messages are shown for all invalid fields. Returns whether the form
+    /// was submitted.
+    pub fn submit(&self, world: &mut DeferredWorld) -> bool {
+        let fields = self.fields.signal().get_clone(world);
+        if !fields.0.iter().all(|field| field.check(world)) {
+            self.submitted.set(world, true);
+            return false;
+        }
+        for field in fields.0.iter() {
+            field.commit(world);
+        }
+        self.submitted.set(world, false);
+        if let Some(on_submit) = self.on_submit {
+            world.run_callback(on_submit, ());
+        }
+        true
+    }
+
+    /// Restore all fields to their initial values, and clear any error messages.
+    pub fn reset(&self, world: &mut DeferredWorld) {
+        let fields = self.fields.signal().get_clone(world);
+        for field in fields.0.iter() {
+            field.reset(world);
+        }
+        self.submitted.set(world, false);
+    }
+
+    /// Create a callback which submits the form, suitable for a submit button.
+    pub fn create_submit_callback(&self, builder: &mut UiBuilder) -> Callback {
+        let form = *self;
+        builder.create_callback(move |_: In<()>, mut world: DeferredWorld| {
+            form.submit(&mut world);
+        })
+    }
+
+    /// Create a callback which resets the form, suitable for a cancel or revert button.
+    pub fn create_reset_callback(&self, builder: &mut UiBuilder) -> Callback {
+        let form = *self;
+        builder.create_callback(move |_: In<()>, mut world: DeferredWorld| {
+            form.reset(&mut world);
+        })
+    }
+}
+
+/// Method to create a [`Form`].
+pub trait CreateForm {
+    /// Create a new, empty form. `on_submit` is called when the form is submitted and all of
+    /// its fields are valid.
+    fn create_form(&mut self, on_submit: Option<Callback>) -> Form;
+}
+
+impl<'w> CreateForm for UiBuilder<'w> {
+    fn create_form(&mut self, on_submit: Option<Callback>) -> Form {
+        Form {
+            fields: self.create_mutable(FormFields::default()),
+            submitted: self.create_mutable(false),
+            on_submit,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_reactor_builder::CreateChilden;
+    use bevy_reactor_signals::SignalsPlugin;
+
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct Submissions(usize);
+
+    fn not_empty(value: &String) -> Result<(), String> {
+        if value.is_empty() {
+            Err("Required".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn build_form(world: &mut World) -> (Form, FormField<String>) {
+        world.init_resource::<Submissions>();
+        let mut result = None;
+        world.spawn_empty().create_children(|builder| {
+            let on_submit =
+                builder.create_callback(|_: In<()>, mut submissions: ResMut<Submissions>| {
+                    submissions.0 += 1;
+                });
+            let form = builder.create_form(Some(on_submit));
+            let name = form.field(builder, String::new(), not_empty);
+            result = Some((form, name));
+        });
+        result.unwrap()
+    }
+
+    #[test]
+    fn test_validate_on_submit() {
+        let mut app = App::new();
+        app.add_plugins(SignalsPlugin);
+        let world = app.world_mut();
+        let (form, name) = build_form(world);
+
+        // A pristine field doesn't report its error until the form is submitted.
+        assert_eq!(name.validate(world), Err("Required".to_string()));
+        assert_eq!(name.error(world), None);
+
+        assert!(!form.submit(&mut DeferredWorld::from(&mut *world)));
+        world.flush();
+        assert_eq!(name.error(world), Some("Required".to_string()));
+        assert_eq!(world.resource::<Submissions>().0, 0);
+
+        name.set(world, "Ada".to_string());
+        assert_eq!(name.error(world), None);
+        assert!(form.submit(&mut DeferredWorld::from(&mut *world)));
+        world.flush();
+        assert_eq!(world.resource::<Submissions>().0, 1);
+    }
+
+    #[test]
+    fn test_dirty_tracking() {
+        let mut app = App::new();
+        app.add_plugins(SignalsPlugin);
+        let world = app.world_mut();
+        let (form, name) = build_form(world);
+        assert!(!name.is_dirty(world));
+
+        name.set(world, "Ada".to_string());
+        assert!(name.is_dirty(world));
+
+        // Setting the initial value again makes the field pristine.
+        name.set(world, String::new());
+        assert!(!name.is_dirty(world));
+
+        // Resetting restores the initial value.
+        name.set(world, "Ada".to_string());
+        form.reset(&mut DeferredWorld::from(&mut *world));
+        assert!(!name.is_dirty(world));
+        assert_eq!(name.get(world), "");
+
+        // Submitting makes the submitted values the new initial values.
+        name.set(world, "Grace".to_string());
+        assert!(form.submit(&mut DeferredWorld::from(&mut *world)));
+        world.flush();
+        assert!(!name.is_dirty(world));
+        assert_eq!(name.get(world), "Grace");
+    }
+}
@@ -0,0 +1,174 @@
+use std::ops::Range;
+
+/// Result of matching a query against a piece of text.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FuzzyMatch {
+    /// How well the query matches; higher is better.
+    pub score: i32,
+    /// Byte ranges of the matched characters within the text. Adjacent matches are merged
+    /// into a single range.
+    pub ranges: Vec<Range<usize>>,
+}
+
+/// Match `query` against `text`. Every character of the query must appear in the text in
+/// order, ignoring case and whitespace in the query; consecutive matches and matches at the
+/// start of a word score higher. Returns `None` if the text does not match.
+pub fn fuzzy_match(query: &str, text: &str) -> Option<FuzzyMatch> {
+    let mut result = FuzzyMatch::default();
+    let mut text_chars = text.char_indices();
+    let mut prev_end: Option<usize> = None;
+    let mut prev_char: Option<char> = None;
+    for qc in query.chars().filter(|c| !c.is_whitespace()) {
+        let qc = qc.to_ascii_lowercase();
+        loop {
+            let (index, tc) = text_chars.next()?;
+            let at_word_start = match prev_char {
+                None => true,
+                Some(p) => !p.is_alphanumeric() || (p.is_lowercase() && tc.is_uppercase()),
+            };
+            prev_char = Some(tc);
+            if tc.to_ascii_lowercase() == qc {
+                let end = index + tc.len_utf8();
+                result.score += 1;
+                if at_word_start {
+                    result.score += 3;
+                }
+                match prev_end {
+                    Some(prev) if prev == index => {
+                        result.score += 2;
+                        if let Some(range) = result.ranges.last_mut() {
+                            range.end = end;
+                        }
+                    }
+                    Some(prev) => {
+                        result.score -= ((index - prev) as i32).min(3);
+                        result.ranges.push(index..end);
+                    }
+                    None => {
+                        result.score -= (index as i32).min(3);
+                        result.ranges.push(index..end);
+                    }
+                }
+                prev_end = Some(end);
+                break;
+            }
+        }
+    }
+    Some(result)
+}
+
+/// Score how well `query` matches `text`, as with [`fuzzy_match`].
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    fuzzy_match(query, text).map(|m| m.score)
+}
+
+/// Match `query` against each item, returning the items which match along with their match
+/// results, best match first. Items with equal scores keep their original order.
+pub fn fuzzy_filter<T>(
+    query: &str,
+    items: impl IntoIterator<Item = T>,
+    text: impl Fn(&T) -> &str,
+) -> Vec<(T, FuzzyMatch)> {
+    let mut matches: Vec<(T, FuzzyMatch)> = items
+        .into_iter()
+        .filter_map(|item| {
+            let m = fuzzy_match(query, text(&item))?;
+            Some((item, m))
+        })
+        .collect();
+    matches.sort_by(|a, b| b.1.score.cmp(&a.1.score));
+    matches
+}
+
+/// Split `text` into segments, each flagged with whether it lies within one of the
+/// highlighted `ranges`. The ranges must be sorted and must not overlap.
+pub fn highlight_segments<'a>(text: &'a str, ranges: &[Range<usize>]) -> Vec<(&'a str, bool)> {
+    let mut segments = Vec::with_capacity(ranges.len() * 2 + 1);
+    let mut pos = 0;
+    for range in ranges {
+        let start = range.start.clamp(pos, text.len());
+        let end = range.end.clamp(start, text.len());
+        if start > pos {
+            segments.push((&text[pos..start], false));
+        }
+        if end > start {
+            segments.push((&text[start..end], true));
+        }
+        pos = end;
+    }
+    if pos < text.len() {
+        segments.push((&text[pos..], false));
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_query() {
+        assert_eq!(fuzzy_match("", "anything"), Some(FuzzyMatch::default()));
+        assert_eq!(fuzzy_match(" ", ""), Some(FuzzyMatch::default()));
+
+        // An empty query matches every item, in the original order.
+        let items = fuzzy_filter("", ["b", "a", "c"], |item| *item);
+        let items: Vec<&str> = items.into_iter().map(|(item, _)| item).collect();
+        assert_eq!(items, vec!["b", "a", "c"]);
+    }
+
+    #[test]
+    fn test_match_ranges() {
+        // Consecutive matches are merged into a single range.
+        let m = fuzzy_match("save", "save file").unwrap();
+        assert_eq!(m.ranges, vec![0..4]);
+        assert_eq!(m.score, 13);
+
+        let m = fuzzy_match("sf", "save file").unwrap();
+        assert_eq!(m.ranges, vec![0..1, 5..6]);
+        assert_eq!(m.score, 5);
+
+        // Whitespace in the query is ignored.
+        assert_eq!(fuzzy_match("s f", "save file"), Some(m));
+
+        // Characters must appear in order.
+        assert_eq!(fuzzy_match("fs", "save file"), None);
+        assert_eq!(fuzzy_match("x", "save file"), None);
+
+        // Ranges are byte offsets.
+        assert_eq!(fuzzy_match("e", "héllo e").unwrap().ranges, vec![7..8]);
+    }
+
+    #[test]
+    fn test_case_handling() {
+        assert_eq!(fuzzy_match("SAVE", "save").unwrap().ranges, vec![0..4]);
+        assert_eq!(fuzzy_match("save", "SAVE").unwrap().ranges, vec![0..4]);
+
+        // An uppercase letter after a lowercase one starts a word.
+        let m = fuzzy_match("fb", "fooBar").unwrap();
+        assert_eq!(m.ranges, vec![0..1, 3..4]);
+        assert_eq!(m.score, 6);
+    }
+
+    #[test]
+    fn test_filter_ordering() {
+        let items = ["misfit", "xyz", "sift", "save file"];
+        let matches = fuzzy_filter("sf", items, |item| *item);
+        let order: Vec<(&str, i32)> = matches.iter().map(|(item, m)| (*item, m.score)).collect();
+        assert_eq!(order, vec![("save file", 5), ("sift", 4), ("misfit", 2)]);
+
+        // Equal scores keep their original order.
+        let matches = fuzzy_filter("a", ["ab", "ac"], |item| *item);
+        let order: Vec<&str> = matches.into_iter().map(|(item, _)| item).collect();
+        assert_eq!(order, vec!["ab", "ac"]);
+    }
+
+    #[test]
+    fn test_highlight_segments() {
+        assert_eq!(
+            highlight_segments("save file", &[0..1, 5..6]),
+            vec![("s", true), ("ave ", false), ("f", true), ("ile", false)]
+        );
+        assert_eq!(highlight_segments("abc", &[]), vec![("abc", false)]);
+    }
+}
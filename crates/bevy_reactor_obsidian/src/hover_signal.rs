@@ -1,6 +1,9 @@
 use bevy::{
     hierarchy::Parent,
-    picking::{focus::HoverMap, pointer::PointerId},
+    picking::{
+        focus::HoverMap,
+        pointer::{PointerId, PointerLocation},
+    },
     prelude::*,
 };
 use bevy_reactor_builder::UiBuilder;
@@ -10,6 +13,124 @@ use bevy_reactor_signals::Signal;
 #[derive(Default, Component)]
 pub(crate) struct Hovering(pub bool);
 
+/// Component which tracks whether the pointer has rested over an entity for long enough to
+/// signal an intent to interact with it, rather than just passing over it.
+#[derive(Component)]
+pub(crate) struct HoverIntent {
+    /// How long the pointer must rest over the entity, in seconds.
+    delay: f32,
+    /// How long the pointer has been hovering so far.
+    elapsed: f32,
+    /// Where the pointer was when it left the entity while heading for the safe zone.
+    exit_origin: Option<Vec2>,
+    /// How long the pointer has been travelling towards the safe zone.
+    grace: f32,
+}
+
+/// Whether hover intent is currently active. Kept separate from [`HoverIntent`] so that
+/// signals only react when the state actually changes, not every frame the timer ticks.
+#[derive(Default, Component)]
+pub(crate) struct HoverIntentActive(pub bool);
+
+/// Component which designates a region, such as an open submenu, that the pointer may travel
+/// towards after leaving a hover intent target without the intent being cancelled. So long as
+/// the pointer stays within the triangle formed by its exit point and the near edge of the
+/// safe zone, the target remains active, up to a short timeout.
+#[derive(Component, Clone, Copy)]
+pub struct HoverSafeZone(pub Entity);
+
+/// Maximum time the pointer may spend travelling towards a safe zone.
+const SAFE_ZONE_TIMEOUT: f32 = 0.5;
+
+/// True if the entity or one of its descendants is in the hover set.
+fn is_hovered(hover_map: &HoverMap, entity: Entity, parent_query: &Query<&Parent>) -> bool {
+    match hover_map.get(&PointerId::Mouse) {
+        Some(map) => map
+            .iter()
+            .any(|(ha, _)| *ha == entity || parent_query.iter_ancestors(*ha).any(|e| e == entity)),
+        None => false,
+    }
+}
+
+/// True if `point` lies within the triangle formed by `origin` and the edge of `rect` which
+/// faces it.
+fn in_safe_triangle(point: Vec2, origin: Vec2, rect: Rect) -> bool {
+    let (a, b) = if origin.x < rect.min.x {
+        (rect.min, Vec2::new(rect.min.x, rect.max.y))
+    } else if origin.x > rect.max.x {
+        (Vec2::new(rect.max.x, rect.min.y), rect.max)
+    } else if origin.y < rect.min.y {
+        (rect.min, Vec2::new(rect.max.x, rect.min.y))
+    } else {
+        (Vec2::new(rect.min.x, rect.max.y), rect.max)
+    };
+    let side = |p0: Vec2, p1: Vec2| (p1 - p0).perp_dot(point - p0);
+    let (d0, d1, d2) = (side(origin, a), side(a, b), side(b, origin));
+    let has_neg = d0 < 0. || d1 < 0. || d2 < 0.;
+    let has_pos = d0 > 0. || d1 > 0. || d2 > 0.;
+    !(has_neg && has_pos)
+}
+
+pub(crate) fn update_hover_intents(
+    time: Res<Time>,
+    hover_map: Option<Res<HoverMap>>,
+    parent_query: Query<&Parent>,
+    q_pointers: Query<(&PointerId, &PointerLocation)>,
+    q_nodes: Query<(&ComputedNode, &GlobalTransform)>,
+    mut q_intents: Query<(
+        Entity,
+        &mut HoverIntent,
+        &mut HoverIntentActive,
+        Option<&HoverSafeZone>,
+    )>,
+) {
+    let Some(hover_map) = hover_map else { return };
+    let pointer_position = q_pointers
+        .iter()
+        .find(|(id, _)| **id == PointerId::Mouse)
+        .and_then(|(_, location)| location.location())
+        .map(|location| location.position);
+    let delta = time.delta_secs();
+
+    for (entity, mut intent, mut active, safe_zone) in q_intents.iter_mut() {
+        let intent = &mut *intent;
+        let next = if is_hovered(&hover_map, entity, &parent_query) {
+            intent.exit_origin = None;
+            intent.elapsed += delta;
+            intent.elapsed >= intent.delay
+        } else {
+            intent.elapsed = 0.;
+            let zone_rect =
+                safe_zone
+                    .and_then(|zone| q_nodes.get(zone.0).ok())
+                    .map(|(node, transform)| {
+                        Rect::from_center_size(transform.translation().xy(), node.size())
+                    });
+            match (active.0, pointer_position, zone_rect) {
+                (true, Some(position), Some(rect)) if rect.contains(position) => {
+                    intent.exit_origin = None;
+                    true
+                }
+                (true, Some(position), Some(rect)) => {
+                    let origin = *intent.exit_origin.get_or_insert_with(|| {
+                        intent.grace = 0.;
+                        position
+                    });
+                    intent.grace += delta;
+                    intent.grace < SAFE_ZONE_TIMEOUT && in_safe_triangle(position, origin, rect)
+                }
+                _ => false,
+            }
+        };
+        if !next {
+            intent.exit_origin = None;
+        }
+        if active.0 != next {
+            active.0 = next;
+        }
+    }
+}
+
 // Note: previously this was implemented as a Reaction, however it was reacting every frame
 // because HoverMap is mutated every frame regardless of whether or not it changed.
 pub(crate) fn update_hover_states(
@@ -18,14 +139,8 @@ pub(crate) fn update_hover_states(
     parent_query: Query<&Parent>,
 ) {
     let Some(hover_map) = hover_map else { return };
-    let hover_set = hover_map.get(&PointerId::Mouse);
     for (entity, mut hoverable) in hovers.iter_mut() {
-        let is_hovering = match hover_set {
-            Some(map) => map.iter().any(|(ha, _)| {
-                *ha == entity || parent_query.iter_ancestors(*ha).any(|e| e == entity)
-            }),
-            None => false,
-        };
+        let is_hovering = is_hovered(&hover_map, entity, &parent_query);
         if hoverable.0 != is_hovering {
             hoverable.0 = is_hovering;
         }
@@ -36,6 +151,11 @@ pub(crate) fn update_hover_states(
 pub trait CreateHoverSignal {
     /// Signal that returns true when the mouse is hovering over the given entity or a descendant.
     fn create_hover_signal(&mut self, target: Entity) -> Signal<bool>;
+
+    /// Signal that returns true once the mouse has rested over the given entity or a descendant
+    /// for `delay` seconds, and false as soon as it leaves. If the entity has a
+    /// [`HoverSafeZone`], the signal stays true while the pointer moves towards the safe zone.
+    fn create_hover_intent_signal(&mut self, target: Entity, delay: f32) -> Signal<bool>;
 }
 
 impl<'w> CreateHoverSignal for UiBuilder<'w> {
@@ -48,4 +168,21 @@ impl<'w> CreateHoverSignal for UiBuilder<'w> {
         });
         hovering
     }
+
+    fn create_hover_intent_signal(&mut self, target: Entity, delay: f32) -> Signal<bool> {
+        self.world_mut().entity_mut(target).insert((
+            HoverIntent {
+                delay,
+                elapsed: 0.,
+                exit_origin: None,
+                grace: 0.,
+            },
+            HoverIntentActive(false),
+        ));
+        self.create_derived(move |rcx| {
+            rcx.read_component::<HoverIntentActive>(target)
+                .map(|h| h.0)
+                .unwrap_or(false)
+        })
+    }
 }
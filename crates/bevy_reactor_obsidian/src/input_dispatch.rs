@@ -1,7 +1,115 @@
-use bevy::{a11y::Focus, ecs::world::DeferredWorld, input::keyboard::KeyboardInput, prelude::*};
+//! Routing of keyboard and pointer input to widgets.
+//!
+//! # Event consumption
+//!
+//! Keyboard events are dispatched to the focused entity (or the [`DefaultKeyHandler`]) in
+//! three steps:
+//!
+//! 1. **Capture**: a [`KeyboardCapture`] event is triggered on each entity from the root of
+//!    the hierarchy down to the target, one at a time. This lets a container, such as a
+//!    draggable row or a scroll view, claim a key before its descendants see it.
+//! 2. **Bubble**: a [`FocusKeyboardInput`] event is triggered on the target and propagates up
+//!    through its ancestors. Widgets handle their own keys here.
+//! 3. **Default actions**: handlers which implement a generic behavior on behalf of the
+//!    widgets below them, such as tab navigation or closing a dialog with Escape, check
+//!    [`is_default_prevented`](DispatchControl::is_default_prevented) and do nothing if a
+//!    widget has opted out.
+//!
+//! Observers control the dispatch through [`DispatchControl`]:
+//!
+//! - [`stop_propagation`](DispatchControl::stop_propagation) consumes the event: no entity
+//!   after the current one sees it, in either phase. Stopping during capture skips the bubble
+//!   phase entirely. Other observers on the current entity still run.
+//! - [`prevent_default`](DispatchControl::prevent_default) lets the event continue, but
+//!   suppresses default actions. The flag carries over from the capture phase to the bubble
+//!   phase.
+//!
+//! Calling `trigger.propagate(false)` in the bubble phase is equivalent to `stop_propagation`,
+//! except that later phases can't tell that the event was consumed.
+//!
+//! Pointer events come from `bevy_picking` and only bubble, from the entity under the pointer
+//! up to the root; `trigger.propagate(false)` consumes them. A container which must take over
+//! a gesture from its children, such as a draggable row containing buttons, should add
+//! [`CapturesPointer`], which routes the rest of a drag to the container once it starts.
 
+use bevy::{
+    a11y::Focus,
+    ecs::world::DeferredWorld,
+    input::keyboard::KeyboardInput,
+    picking::{backend::HitData, focus::HoverMap, pointer::PointerId, PickSet},
+    prelude::*,
+    utils::HashMap,
+};
+
+/// How far a dispatched event has got, shared by all of the observers which see it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DispatchState {
+    stopped: bool,
+    default_prevented: bool,
+}
+
+/// An input event which follows the dispatch rules described in the [module
+/// docs](self).
+pub trait DispatchedEvent: Event {
+    /// The dispatch state of this event.
+    fn dispatch_state(&self) -> &DispatchState;
+
+    /// The dispatch state of this event, for modification.
+    fn dispatch_state_mut(&mut self) -> &mut DispatchState;
+}
+
+/// Methods for controlling the dispatch of an event from within an observer.
+pub trait DispatchControl {
+    /// Consume the event, so that it is not delivered to any further entities.
+    fn stop_propagation(&mut self);
+
+    /// Suppress the default actions for this event, without stopping it.
+    fn prevent_default(&mut self);
+
+    /// Whether some observer has consumed the event.
+    fn is_propagation_stopped(&self) -> bool;
+
+    /// Whether some observer has suppressed the default actions for this event.
+    fn is_default_prevented(&self) -> bool;
+}
+
+impl<E: DispatchedEvent, B: Bundle> DispatchControl for Trigger<'_, E, B> {
+    fn stop_propagation(&mut self) {
+        self.event_mut().dispatch_state_mut().stopped = true;
+        self.propagate(false);
+    }
+
+    fn prevent_default(&mut self) {
+        self.event_mut().dispatch_state_mut().default_prevented = true;
+    }
+
+    fn is_propagation_stopped(&self) -> bool {
+        self.event().dispatch_state().stopped
+    }
+
+    fn is_default_prevented(&self) -> bool {
+        self.event().dispatch_state().default_prevented
+    }
+}
+
+/// Keyboard event sent to the focused entity, in the bubble phase. Propagates from the focused
+/// entity up to the root.
 #[derive(Clone, Debug, Component)]
-pub struct FocusKeyboardInput(pub KeyboardInput);
+pub struct FocusKeyboardInput {
+    /// The keyboard input.
+    pub input: KeyboardInput,
+    state: DispatchState,
+}
+
+impl FocusKeyboardInput {
+    /// Create a new keyboard event, for delivering synthetic key presses.
+    pub fn new(input: KeyboardInput) -> Self {
+        Self {
+            input,
+            state: DispatchState::default(),
+        }
+    }
+}
 
 impl Event for FocusKeyboardInput {
     type Traversal = &'static Parent;
@@ -9,6 +117,59 @@ impl Event for FocusKeyboardInput {
     const AUTO_PROPAGATE: bool = true;
 }
 
+impl DispatchedEvent for FocusKeyboardInput {
+    fn dispatch_state(&self) -> &DispatchState {
+        &self.state
+    }
+
+    fn dispatch_state_mut(&mut self) -> &mut DispatchState {
+        &mut self.state
+    }
+}
+
+/// Keyboard event sent to each ancestor of the focused entity, and then the entity itself, in
+/// the capture phase. Doesn't propagate; the dispatcher triggers it on each entity in turn.
+#[derive(Clone, Debug, Event)]
+pub struct KeyboardCapture {
+    /// The keyboard input.
+    pub input: KeyboardInput,
+    state: DispatchState,
+}
+
+impl DispatchedEvent for KeyboardCapture {
+    fn dispatch_state(&self) -> &DispatchState {
+        &self.state
+    }
+
+    fn dispatch_state_mut(&mut self) -> &mut DispatchState {
+        &mut self.state
+    }
+}
+
+/// Deliver a key event to `target`, running the capture phase and then the bubble phase.
+fn dispatch_key_event(world: &mut World, input: KeyboardInput, target: Entity) {
+    let mut path = vec![target];
+    let mut entity = target;
+    while let Some(parent) = world.get::<Parent>(entity) {
+        entity = parent.get();
+        path.push(entity);
+    }
+
+    let mut capture = KeyboardCapture {
+        input,
+        state: DispatchState::default(),
+    };
+    for entity in path.into_iter().rev() {
+        world.trigger_targets_ref(&mut capture, entity);
+        if capture.state.stopped {
+            return;
+        }
+    }
+
+    let KeyboardCapture { input, state } = capture;
+    world.trigger_targets_ref(&mut FocusKeyboardInput { input, state }, target);
+}
+
 #[derive(Clone, Debug, Resource)]
 pub struct KeyboardFocus(pub Option<Entity>);
 
@@ -64,6 +225,270 @@ impl<'w> SetKeyboardFocus for DeferredWorld<'w> {
     }
 }
 
+/// How a keyboard focus change came about, as reported by the [`FocusTrace`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FocusSource {
+    /// Sequential navigation with the Tab key.
+    Tab,
+    /// A pointer press on a widget.
+    Click,
+    /// An [`InitialFocus`](crate::tab_navigation::InitialFocus) policy or
+    /// [`AutoFocus`](crate::tab_navigation::AutoFocus) marker.
+    Initial,
+    /// Any other change, such as a call to [`SetKeyboardFocus::set_keyboard_focus`].
+    Programmatic,
+}
+
+/// Resource which, when enabled, logs every change of keyboard focus along with its cause
+/// and the names of the entities involved. This is useful for tracking down focus bugs.
+#[derive(Clone, Debug, Default, Resource)]
+pub struct FocusTrace {
+    /// Whether focus changes are logged.
+    pub enabled: bool,
+    /// The focus as of the last check.
+    last: Option<Entity>,
+    /// The cause of the focus change made this frame, if known.
+    source: Option<FocusSource>,
+}
+
+impl FocusTrace {
+    /// Record the cause of a focus change made this frame. If there are several, the first
+    /// one wins. Changes with no recorded cause are reported as programmatic.
+    pub fn note(&mut self, source: FocusSource) {
+        if self.enabled && self.source.is_none() {
+            self.source = Some(source);
+        }
+    }
+}
+
+/// Describe an entity for debug output, using its [`Name`] if it has one.
+pub(crate) fn entity_label(entity: Option<Entity>, q_names: &Query<&Name>) -> String {
+    match entity {
+        Some(entity) => match q_names.get(entity) {
+            Ok(name) => format!("{} ({})", name.as_str(), entity),
+            Err(_) => entity.to_string(),
+        },
+        None => "none".to_string(),
+    }
+}
+
+/// Pointer presses are the usual cause of focus changes outside of tab navigation.
+fn note_pointer_focus(_trigger: Trigger<Pointer<Down>>, mut trace: ResMut<FocusTrace>) {
+    trace.note(FocusSource::Click);
+}
+
+/// Log keyboard focus changes, if the [`FocusTrace`] is enabled.
+fn trace_focus_changes(
+    focus: Res<KeyboardFocus>,
+    mut trace: ResMut<FocusTrace>,
+    q_names: Query<&Name>,
+) {
+    let source = trace.source.take();
+    if trace.last == focus.0 {
+        return;
+    }
+    if trace.enabled {
+        info!(
+            "Focus: {} -> {} ({:?})",
+            entity_label(trace.last, &q_names),
+            entity_label(focus.0, &q_names),
+            source.unwrap_or(FocusSource::Programmatic)
+        );
+    }
+    trace.last = focus.0;
+}
+
+/// Resource which locks user input while a long-running operation, such as saving or baking,
+/// is in progress. While locked, pointer and keyboard input are only delivered to the entities
+/// within the allowed subtree (such as a progress dialog), if there is one. Use [`LockInput`]
+/// to lock and unlock input, which also saves and restores the keyboard focus.
+#[derive(Clone, Debug, Default, Resource)]
+pub struct InputLock {
+    locked: bool,
+    allow: Option<Entity>,
+    saved_focus: Option<Entity>,
+}
+
+impl InputLock {
+    /// Returns true if input is currently locked.
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Returns the root of the subtree which still receives input while locked.
+    pub fn allowed(&self) -> Option<Entity> {
+        self.allow
+    }
+}
+
+pub trait LockInput {
+    /// Lock input to everything except the subtree rooted at `allow`. If input is already
+    /// locked, this changes the allowed subtree.
+    fn lock_input(&mut self, allow: Option<Entity>);
+
+    /// Release the input lock, restoring the keyboard focus from before it was locked.
+    fn unlock_input(&mut self);
+}
+
+impl LockInput for World {
+    fn lock_input(&mut self, allow: Option<Entity>) {
+        let focus = self
+            .get_resource::<KeyboardFocus>()
+            .and_then(|focus| focus.0);
+        let mut lock = self.get_resource_or_insert_with(InputLock::default);
+        if !lock.locked {
+            lock.saved_focus = focus;
+        }
+        lock.locked = true;
+        lock.allow = allow;
+
+        // Move focus out of the locked part of the UI.
+        if let Some(focus) = focus {
+            if !allow.is_some_and(|root| is_in_subtree(self, focus, root)) {
+                match allow {
+                    Some(root) => self.set_keyboard_focus(root),
+                    None => self.clear_keyboard_focus(),
+                }
+            }
+        }
+    }
+
+    fn unlock_input(&mut self) {
+        let Some(mut lock) = self.get_resource_mut::<InputLock>() else {
+            return;
+        };
+        if !lock.locked {
+            return;
+        }
+        lock.locked = false;
+        lock.allow = None;
+        match lock.saved_focus.take() {
+            Some(focus) if self.get_entity(focus).is_ok() => self.set_keyboard_focus(focus),
+            _ => self.clear_keyboard_focus(),
+        }
+    }
+}
+
+/// Returns true if `entity` is `root` or one of its descendants.
+fn is_in_subtree(world: &World, entity: Entity, root: Entity) -> bool {
+    let mut current = Some(entity);
+    while let Some(e) = current {
+        if e == root {
+            return true;
+        }
+        current = world.get::<Parent>(e).map(|parent| parent.get());
+    }
+    false
+}
+
+/// Marker component for widgets which capture the pointer while being dragged. Once a drag
+/// starts on this entity or one of its descendants, pointer events are routed to this entity
+/// until the drag ends, even if the pointer moves over other widgets or leaves the window.
+#[derive(Clone, Debug, Default, Component)]
+pub struct CapturesPointer;
+
+/// Resource which tracks which entity, if any, has captured each pointer.
+#[derive(Clone, Debug, Default, Resource)]
+pub struct PointerCapture(HashMap<PointerId, (Entity, Option<HitData>)>);
+
+impl PointerCapture {
+    /// Returns the entity which has captured the given pointer.
+    pub fn get(&self, pointer: PointerId) -> Option<Entity> {
+        self.0.get(&pointer).map(|(entity, _)| *entity)
+    }
+}
+
+pub trait CapturePointer {
+    /// Route all events for `pointer` to `entity`, regardless of hit testing, until the
+    /// capture is released.
+    fn capture_pointer(&mut self, pointer: PointerId, entity: Entity);
+
+    /// Release the capture for `pointer`, if any.
+    fn release_pointer_capture(&mut self, pointer: PointerId);
+}
+
+impl CapturePointer for World {
+    fn capture_pointer(&mut self, pointer: PointerId, entity: Entity) {
+        let mut capture = self.get_resource_or_insert_with(PointerCapture::default);
+        capture.0.insert(pointer, (entity, None));
+    }
+
+    fn release_pointer_capture(&mut self, pointer: PointerId) {
+        if let Some(mut capture) = self.get_resource_mut::<PointerCapture>() {
+            capture.0.remove(&pointer);
+        }
+    }
+}
+
+impl<'w> CapturePointer for DeferredWorld<'w> {
+    fn capture_pointer(&mut self, pointer: PointerId, entity: Entity) {
+        if let Some(mut capture) = self.get_resource_mut::<PointerCapture>() {
+            capture.0.insert(pointer, (entity, None));
+        }
+    }
+
+    fn release_pointer_capture(&mut self, pointer: PointerId) {
+        if let Some(mut capture) = self.get_resource_mut::<PointerCapture>() {
+            capture.0.remove(&pointer);
+        }
+    }
+}
+
+/// When a drag starts, capture the pointer for the nearest ancestor which has
+/// [`CapturesPointer`].
+fn capture_on_drag_start(
+    trigger: Trigger<Pointer<DragStart>>,
+    q_captures: Query<(), With<CapturesPointer>>,
+    q_parents: Query<&Parent>,
+    mut capture: ResMut<PointerCapture>,
+) {
+    let target = std::iter::once(trigger.entity())
+        .chain(q_parents.iter_ancestors(trigger.entity()))
+        .find(|e| q_captures.contains(*e));
+    if let Some(target) = target {
+        capture.0.insert(
+            trigger.pointer_id,
+            (target, Some(trigger.event().hit.clone())),
+        );
+    }
+}
+
+fn release_on_drag_end(trigger: Trigger<Pointer<DragEnd>>, mut capture: ResMut<PointerCapture>) {
+    capture.0.remove(&trigger.pointer_id);
+}
+
+fn release_on_cancel(trigger: Trigger<Pointer<Cancel>>, mut capture: ResMut<PointerCapture>) {
+    capture.0.remove(&trigger.pointer_id);
+}
+
+/// Replace the hovered entities of each captured pointer with the capturing entity, so that
+/// pointer events are routed to it regardless of what is under the pointer.
+fn apply_pointer_capture(
+    mut capture: ResMut<PointerCapture>,
+    mut hover_map: ResMut<HoverMap>,
+    q_entities: Query<()>,
+) {
+    // Drop captures whose entity has been despawned.
+    capture
+        .0
+        .retain(|_, (entity, _)| q_entities.contains(*entity));
+    for (pointer, (entity, last_hit)) in capture.0.iter_mut() {
+        let hovers = hover_map.entry(*pointer).or_default();
+        // Remember the most recent hit on the captured entity, to use when the pointer is
+        // elsewhere.
+        let hit = hovers
+            .get(entity)
+            .or_else(|| hovers.values().next())
+            .cloned()
+            .or_else(|| last_hit.clone());
+        hovers.clear();
+        if let Some(hit) = hit {
+            hovers.insert(*entity, hit.clone());
+            *last_hit = Some(hit);
+        }
+    }
+}
+
 /// Plugin which registers the system for dispatching keyboard events based on focus and
 /// hover state.
 pub struct InputDispatchPlugin;
@@ -72,7 +497,21 @@ impl Plugin for InputDispatchPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(KeyboardFocus(None))
             .insert_resource(KeyboardFocusVisible(false))
-            .add_systems(Update, (dispatch_keyboard_input, sync_a11y_focus));
+            .init_resource::<InputLock>()
+            .init_resource::<PointerCapture>()
+            .init_resource::<FocusTrace>()
+            .add_observer(note_pointer_focus)
+            .add_observer(capture_on_drag_start)
+            .add_observer(release_on_drag_end)
+            .add_observer(release_on_cancel)
+            .add_systems(
+                PreUpdate,
+                (apply_pointer_capture, filter_locked_hovers)
+                    .chain()
+                    .in_set(PickSet::PostHover),
+            )
+            .add_systems(Update, (dispatch_keyboard_input, sync_a11y_focus))
+            .add_systems(PostUpdate, trace_focus_changes);
     }
 }
 
@@ -83,20 +522,58 @@ fn sync_a11y_focus(focus: Res<KeyboardFocus>, mut a11y_focus: ResMut<Focus>) {
     }
 }
 
+/// While input is locked, remove hovered entities outside of the allowed subtree, so that
+/// they don't receive pointer events.
+fn filter_locked_hovers(
+    lock: Res<InputLock>,
+    mut hover_map: ResMut<HoverMap>,
+    q_parents: Query<&Parent>,
+) {
+    if !lock.locked {
+        return;
+    }
+    for hovers in hover_map.values_mut() {
+        hovers.retain(|entity, _| {
+            lock.allow.is_some_and(|root| {
+                *entity == root || q_parents.iter_ancestors(*entity).any(|e| e == root)
+            })
+        });
+    }
+}
+
 fn dispatch_keyboard_input(
     mut key_events: EventReader<KeyboardInput>,
     focus: Res<KeyboardFocus>,
+    lock: Res<InputLock>,
     q_default_handler: Query<Entity, With<DefaultKeyHandler>>,
+    q_parents: Query<&Parent>,
     mut commands: Commands,
 ) {
-    // If an element has keyboard focus, then dispatch the key event to that element.
-    if let Some(focus_elt) = focus.0 {
-        for ev in key_events.read() {
-            commands.trigger_targets(FocusKeyboardInput(ev.clone()), focus_elt);
+    // While input is locked, only the allowed subtree receives key events.
+    if lock.locked {
+        let target = focus.0.filter(|focus| {
+            lock.allow.is_some_and(|root| {
+                *focus == root || q_parents.iter_ancestors(*focus).any(|e| e == root)
+            })
+        });
+        match target {
+            Some(target) => {
+                for ev in key_events.read() {
+                    let ev = ev.clone();
+                    commands.queue(move |world: &mut World| dispatch_key_event(world, ev, target));
+                }
+            }
+            None => key_events.clear(),
         }
-    } else if let Some(ent) = q_default_handler.iter().next() {
+        return;
+    }
+
+    // If an element has keyboard focus, then dispatch the key event to that element, otherwise
+    // to the default handler.
+    if let Some(target) = focus.0.or_else(|| q_default_handler.iter().next()) {
         for ev in key_events.read() {
-            commands.trigger_targets(FocusKeyboardInput(ev.clone()), ent);
+            let ev = ev.clone();
+            commands.queue(move |world: &mut World| dispatch_key_event(world, ev, target));
         }
     } else if !key_events.is_empty() {
         // warn!("No focus entity and no default keyboard handler: try inserting DefaultKeyHandler on your top-level entity");
@@ -0,0 +1,68 @@
+//! Relationships between labels and the controls they describe, used for click-to-focus and
+//! for the accessibility tree.
+
+use accesskit::NodeId;
+use bevy::{a11y::AccessibilityNode, ecs::world::DeferredWorld, prelude::*};
+
+use crate::{controls::toggle_state::toggle_entity, input_dispatch::SetKeyboardFocus};
+
+/// Placed on a label entity to associate it with a control. Clicking the label focuses the
+/// control, and toggles it if it is a toggle such as a checkbox.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct LabelFor(pub Entity);
+
+/// Placed on a control to indicate which entity contains its label. The relationship is
+/// reported to assistive technology via AccessKit.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct LabelledBy(pub Entity);
+
+/// Placed on a control to indicate which entity contains a longer description of it, such as
+/// help text below a form field.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct DescribedBy(pub Entity);
+
+fn node_id(entity: Entity) -> NodeId {
+    NodeId(entity.to_bits())
+}
+
+/// Observer which forwards clicks on a label to its control.
+pub(crate) fn label_on_pointer_click(
+    mut trigger: Trigger<Pointer<Click>>,
+    q_labels: Query<&LabelFor>,
+    mut world: DeferredWorld,
+) {
+    if let Ok(label_for) = q_labels.get(trigger.entity()) {
+        let control = label_for.0;
+        trigger.propagate(false);
+        world.set_keyboard_focus(control);
+        toggle_entity(&mut world, control);
+    }
+}
+
+/// Copies label and description relationships into each control's accessibility node.
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_accessibility_relations(
+    mut q_controls: Query<
+        (
+            &mut AccessibilityNode,
+            Option<&LabelledBy>,
+            Option<&DescribedBy>,
+        ),
+        Or<(
+            Added<AccessibilityNode>,
+            Changed<LabelledBy>,
+            Changed<DescribedBy>,
+        )>,
+    >,
+) {
+    for (mut node, labelled_by, described_by) in q_controls.iter_mut() {
+        match labelled_by {
+            Some(label) => node.set_labelled_by(vec![node_id(label.0)]),
+            None => node.clear_labelled_by(),
+        }
+        match described_by {
+            Some(description) => node.set_described_by(vec![node_id(description.0)]),
+            None => node.clear_described_by(),
+        }
+    }
+}
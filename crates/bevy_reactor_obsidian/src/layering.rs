@@ -0,0 +1,124 @@
+use bevy::{prelude::*, utils::HashMap};
+
+/// Number of z-indices reserved for each layer.
+const BAND_SIZE: i32 = 1000;
+
+/// The stacking layer of a floating UI element. Adding this component to an entity assigns it
+/// a [`GlobalZIndex`] within the band reserved for its layer; elements opened later stack on
+/// top of earlier ones in the same layer. Removing the component (or despawning the entity)
+/// releases the index and restores the entity's previous z-index, if any.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum UiLayer {
+    /// Ordinary page content.
+    Content,
+    /// Menus, popovers and tooltips.
+    Floating,
+    /// Modal dialogs and their backdrops.
+    Modal,
+    /// Transient notifications, shown above modals.
+    Toast,
+    /// The ghost image shown while dragging, which is always on top.
+    DragGhost,
+}
+
+impl UiLayer {
+    /// The lowest z-index in this layer's band.
+    pub fn base(&self) -> i32 {
+        BAND_SIZE
+            * match self {
+                UiLayer::Content => 0,
+                UiLayer::Floating => 1,
+                UiLayer::Modal => 2,
+                UiLayer::Toast => 3,
+                UiLayer::DragGhost => 4,
+            }
+    }
+}
+
+/// Resource which keeps track of the floating elements open in each layer, in stacking order.
+#[derive(Resource, Default)]
+pub struct LayerManager {
+    stacks: HashMap<UiLayer, Vec<Entity>>,
+    /// The `GlobalZIndex` each element had before it was layered.
+    saved: HashMap<Entity, Option<GlobalZIndex>>,
+}
+
+impl LayerManager {
+    /// Add an element to the top of a layer, and return its z-index.
+    fn push(&mut self, layer: UiLayer, entity: Entity, saved: Option<GlobalZIndex>) -> i32 {
+        let stack = self.stacks.entry(layer).or_default();
+        stack.retain(|e| *e != entity);
+        stack.push(entity);
+        self.saved.insert(entity, saved);
+        layer.base() + stack.len() as i32
+    }
+
+    /// Remove an element from its layer. Returns the z-index it had before it was layered.
+    fn remove(&mut self, layer: UiLayer, entity: Entity) -> Option<GlobalZIndex> {
+        if let Some(stack) = self.stacks.get_mut(&layer) {
+            stack.retain(|e| *e != entity);
+        }
+        self.saved.remove(&entity).flatten()
+    }
+
+    /// The elements in a layer, from bottom to top.
+    pub fn stack(&self, layer: UiLayer) -> &[Entity] {
+        self.stacks
+            .get(&layer)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// The topmost element in a layer, if any.
+    pub fn top(&self, layer: UiLayer) -> Option<Entity> {
+        self.stack(layer).last().copied()
+    }
+
+    /// The z-index assigned to an element, if it is layered.
+    pub fn z_index(&self, entity: Entity) -> Option<i32> {
+        self.stacks.iter().find_map(|(layer, stack)| {
+            stack
+                .iter()
+                .position(|e| *e == entity)
+                .map(|index| layer.base() + index as i32 + 1)
+        })
+    }
+}
+
+/// Component hooks which allocate and release z-indices as layered elements come and go.
+pub(crate) fn register_layer_hooks(world: &mut World) {
+    world
+        .register_component_hooks::<UiLayer>()
+        .on_add(|mut world, entity, _component| {
+            let layer = *world.get::<UiLayer>(entity).unwrap();
+            let saved = world.get::<GlobalZIndex>(entity).copied();
+            let z_index = world
+                .resource_mut::<LayerManager>()
+                .push(layer, entity, saved);
+            world
+                .commands()
+                .entity(entity)
+                .insert(GlobalZIndex(z_index));
+        })
+        .on_remove(|mut world, entity, _component| {
+            let layer = *world.get::<UiLayer>(entity).unwrap();
+            let mut manager = world.resource_mut::<LayerManager>();
+            let saved = manager.remove(layer, entity);
+            // Compact the remaining elements in the layer so the band doesn't fill up.
+            let restack: Vec<(Entity, i32)> = manager
+                .stack(layer)
+                .iter()
+                .enumerate()
+                .map(|(index, e)| (*e, layer.base() + index as i32 + 1))
+                .collect();
+            let mut commands = world.commands();
+            for (e, z_index) in restack {
+                commands.entity(e).try_insert(GlobalZIndex(z_index));
+            }
+            // The entity may be in the process of being despawned.
+            match saved {
+                Some(z_index) => commands.entity(entity).try_insert(z_index),
+                None => commands.entity(entity).remove::<GlobalZIndex>(),
+            };
+        });
+}
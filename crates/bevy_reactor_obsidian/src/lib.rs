@@ -1,34 +1,74 @@
-use bevy::{asset::embedded_asset, prelude::*};
+use bevy::{asset::embedded_asset, picking::PickSet, prelude::*};
 
 pub mod animation;
+pub mod clipboard;
+pub mod clipping;
 pub mod colors;
+pub mod control_events;
 pub mod controls;
 pub mod cursor;
 pub mod focus_signal;
+pub mod form;
+pub mod fuzzy;
 pub mod hover_signal;
 pub mod input_dispatch;
+pub mod labelling;
+pub mod layering;
 mod materials;
+pub mod palettes;
+pub mod picking_occlusion;
+pub mod rounded_border;
 pub mod rounded_corners;
 pub mod scrolling;
+pub mod selection;
 pub mod size;
 pub mod tab_navigation;
+pub mod text_parse;
+pub mod theme;
+pub mod thumbnail_service;
 pub mod typography;
 
 pub mod prelude {
+    pub use crate::animation::{AnimateSize, SizeAxes};
+    pub use crate::clipboard::Clipboard;
+    pub use crate::clipping::StyleBuilderClip;
     pub use crate::colors;
+    pub use crate::control_events::{
+        Activated, ControlEvent, ControlEventObserver, Dismissed, ValueChanged,
+    };
     pub use crate::controls::*;
     pub use crate::focus_signal::CreateFocusSignal;
-    pub use crate::hover_signal::CreateHoverSignal;
+    pub use crate::form::{CreateForm, Form, FormField};
+    pub use crate::fuzzy::{fuzzy_filter, fuzzy_match, FuzzyMatch};
+    pub use crate::hover_signal::{CreateHoverSignal, HoverSafeZone};
+    pub use crate::input_dispatch::{DispatchControl, FocusSource, FocusTrace, KeyboardCapture};
+    pub use crate::labelling::{DescribedBy, LabelFor, LabelledBy};
+    pub use crate::layering::{LayerManager, UiLayer};
+    pub use crate::palettes::{ColorPalette, ColorPalettes, PaletteId};
+    pub use crate::picking_occlusion::BlocksPicking;
+    pub use crate::rounded_border::{RoundedBorder, StyleBuilderRoundedBorder};
     pub use crate::rounded_corners::RoundedCorners;
-    pub use crate::size::Size;
-    pub use crate::tab_navigation::{handle_tab_navigation, TabGroup, TabIndex};
-    pub use crate::typography;
+    pub use crate::scrolling::{CreateScrollSignals, ScrollSignals, Sticky};
+    pub use crate::selection::{SelectModifiers, SelectionMode, SelectionModel};
+    pub use crate::size::{Size, UiDensity, UiSizing};
+    pub use crate::tab_navigation::{
+        handle_tab_navigation, FocusDebugPlugin, InitialFocus, TabGroup, TabIndex,
+    };
+    pub use crate::text_parse::NumberFormat;
+    pub use crate::theme::{
+        AccentPalette, ContrastIssue, ProvideTheme, Theme, ThemePatch, UseTheme,
+    };
+    pub use crate::thumbnail_service::{CreateThumbnailSignal, ThumbnailService, ThumbnailSource};
+    pub use crate::typography::{self, StyledTextBuilder, TextVariant};
     pub use crate::ObsidianUiPlugin;
 }
 
 pub struct ObsidianUiPlugin;
 use input_dispatch::InputDispatchPlugin;
-use materials::{GradientRectMaterial, SliderRectMaterial, SwatchRectMaterial};
+use materials::{
+    CornerMaskMaterial, DotGridMaterial, DrawPathMaterial, GradientRectMaterial,
+    RoundedBorderMaterial, SliderRectMaterial, SwatchRectMaterial,
+};
 
 impl Plugin for ObsidianUiPlugin {
     fn build(&self, app: &mut App) {
@@ -54,6 +94,8 @@ impl Plugin for ObsidianUiPlugin {
         embedded_asset!(app, "assets/fonts/Inter/static/Inter_18pt-Medium.ttf");
         embedded_asset!(app, "assets/fonts/Inter/static/Inter_18pt-MediumItalic.ttf");
 
+        embedded_asset!(app, "assets/fonts/DejaVu_Sans_Mono/DejaVuSansMono.ttf");
+
         embedded_asset!(app, "assets/icons/add_box.png");
         embedded_asset!(app, "assets/icons/add.png");
         embedded_asset!(app, "assets/icons/checkmark.png");
@@ -74,10 +116,18 @@ impl Plugin for ObsidianUiPlugin {
         embedded_asset!(app, "assets/shaders/gradient_rect.wgsl");
         embedded_asset!(app, "assets/shaders/swatch_rect.wgsl");
         embedded_asset!(app, "assets/shaders/slider_rect.wgsl");
+        embedded_asset!(app, "assets/shaders/corner_mask.wgsl");
+        embedded_asset!(app, "assets/shaders/rounded_border.wgsl");
+        embedded_asset!(app, "assets/shaders/draw_path.wgsl");
+        embedded_asset!(app, "assets/shaders/dot_grid.wgsl");
         app.add_plugins((
             UiMaterialPlugin::<GradientRectMaterial>::default(),
             UiMaterialPlugin::<SliderRectMaterial>::default(),
             UiMaterialPlugin::<SwatchRectMaterial>::default(),
+            UiMaterialPlugin::<CornerMaskMaterial>::default(),
+            UiMaterialPlugin::<RoundedBorderMaterial>::default(),
+            UiMaterialPlugin::<DrawPathMaterial>::default(),
+            UiMaterialPlugin::<DotGridMaterial>::default(),
             animation::BistableTransitionPlugin,
             animation::AnimatedTransitionPlugin,
             controls::ControlEventsPlugin,
@@ -86,16 +136,35 @@ impl Plugin for ObsidianUiPlugin {
         // .add_plugins((
         //     EventListenerPlugin::<MenuCloseEvent>::default(),
         // ))
+        .init_resource::<clipboard::Clipboard>()
+        .init_resource::<layering::LayerManager>()
+        .init_resource::<size::UiSizing>()
+        .init_resource::<palettes::ColorPalettes>()
+        .init_resource::<thumbnail_service::ThumbnailService>()
+        .init_resource::<theme::Theme>()
+        .add_observer(labelling::label_on_pointer_click)
+        .add_systems(
+            PreUpdate,
+            picking_occlusion::occlude_picking.in_set(PickSet::Backend),
+        )
         .add_systems(
             Update,
             (
                 scrolling::handle_scroll_events,
                 scrolling::update_scroll_positions,
+                scrolling::update_sticky_positions.after(scrolling::update_scroll_positions),
                 hover_signal::update_hover_states,
+                hover_signal::update_hover_intents,
                 cursor::update_cursor,
+                clipping::update_rounded_clips,
+                size::apply_ui_scale,
+                labelling::update_accessibility_relations,
+                thumbnail_service::run_thumbnail_jobs,
             ),
         );
-        // .init_resource::<RecentColors>()
+        #[cfg(debug_assertions)]
+        app.add_systems(Update, theme::warn_low_contrast);
+        layering::register_layer_hooks(app.world_mut());
         // .add_systems(PostUpdate, floating::position_floating);
     }
 }
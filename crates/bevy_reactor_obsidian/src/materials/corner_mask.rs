@@ -0,0 +1,17 @@
+use bevy::prelude::*;
+use bevy::reflect::TypePath;
+use bevy::render::render_resource::*;
+
+#[derive(AsBindGroup, Asset, TypePath, Debug, Clone)]
+pub(crate) struct CornerMaskMaterial {
+    #[uniform(0)]
+    pub(crate) color: Vec4,
+    #[uniform(1)]
+    pub(crate) border_radius: Vec4,
+}
+
+impl UiMaterial for CornerMaskMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "embedded://bevy_reactor_obsidian/assets/shaders/corner_mask.wgsl".into()
+    }
+}
@@ -3,15 +3,21 @@ use bevy::reflect::TypePath;
 use bevy::render::render_resource::*;
 
 #[derive(AsBindGroup, Asset, TypePath, Debug, Clone)]
-pub struct DotGridMaterial {
+pub(crate) struct DotGridMaterial {
     #[uniform(0)]
     pub(crate) color_bg: Vec4,
     #[uniform(1)]
     pub(crate) color_fg: Vec4,
+    #[uniform(2)]
+    pub(crate) offset: Vec2,
+    #[uniform(3)]
+    pub(crate) spacing: f32,
+    #[uniform(4)]
+    pub(crate) scale: f32,
 }
 
 impl UiMaterial for DotGridMaterial {
     fn fragment_shader() -> ShaderRef {
-        "obsidian_ui://shaders/dot_grid.wgsl".into()
+        "embedded://bevy_reactor_obsidian/assets/shaders/dot_grid.wgsl".into()
     }
 }
@@ -18,6 +18,7 @@ pub enum DrawablePathSegment {
 pub struct DrawablePath {
     color: Srgba,
     width: f32,
+    hairline: bool,
     commands: Vec<DrawablePathSegment>,
 }
 
@@ -26,10 +27,18 @@ impl DrawablePath {
         Self {
             color,
             width,
+            hairline: false,
             commands: Vec::new(),
         }
     }
 
+    /// Enable anti-aliased hairline mode. The stroke edges are feathered over one pixel
+    /// instead of a fixed distance, and strokes narrower than a pixel are drawn one pixel
+    /// wide with reduced opacity, so that thin lines don't shimmer as they move.
+    pub fn set_hairline(&mut self, hairline: bool) {
+        self.hairline = hairline;
+    }
+
     pub fn move_to(&mut self, point: Vec2) {
         self.commands.push(DrawablePathSegment::Move(point));
     }
@@ -42,28 +51,6 @@ impl DrawablePath {
         self.commands
             .push(DrawablePathSegment::Quadratic((control, point)));
     }
-
-    pub fn bounds(&self) -> Rect {
-        if self.commands.is_empty() {
-            return Rect::default();
-        }
-        let mut bounds = Rect {
-            min: Vec2::splat(f32::INFINITY),
-            max: Vec2::splat(f32::NEG_INFINITY),
-        };
-        for segment in &self.commands {
-            match segment {
-                DrawablePathSegment::Move(point) | DrawablePathSegment::Line(point) => {
-                    bounds = bounds.union_point(*point);
-                }
-                DrawablePathSegment::Quadratic((control, point)) => {
-                    bounds = bounds.union_point(*control);
-                    bounds = bounds.union_point(*point);
-                }
-            }
-        }
-        bounds.inflate(self.width * 0.5)
-    }
 }
 
 /// Type of drawing operation for each path segment.
@@ -90,39 +77,46 @@ pub struct DrawPathMaterial {
     #[uniform(1)]
     pub(crate) width: f32,
 
+    /// Non-zero if hairline mode is enabled.
+    #[uniform(3)]
+    pub(crate) hairline: u32,
+
     // #[uniform(2)]
     #[storage(2, read_only)]
     pub(crate) commands: Vec<PathCommand>,
 }
 
 impl DrawPathMaterial {
-    pub fn update(&mut self, path: &DrawablePath) {
-        let bounds = path.bounds();
+    /// Update the material from a path. `origin` is the position of the top-left corner of the
+    /// node in path coordinates; for a node which is sized to fit the path, this is the
+    /// minimum of the path's bounds.
+    pub fn update(&mut self, path: &DrawablePath, origin: Vec2) {
         self.color = path.color.to_vec4();
         self.width = path.width;
+        self.hairline = path.hairline as u32;
         self.commands.clear();
         for segment in &path.commands {
             match segment {
                 DrawablePathSegment::Move(point) => {
                     self.commands.push(PathCommand {
                         op: PathCommandType::Move as u32,
-                        point: *point - bounds.min,
+                        point: *point - origin,
                     });
                 }
                 DrawablePathSegment::Line(point) => {
                     self.commands.push(PathCommand {
                         op: PathCommandType::Line as u32,
-                        point: *point - bounds.min,
+                        point: *point - origin,
                     });
                 }
                 DrawablePathSegment::Quadratic((control, point)) => {
                     self.commands.push(PathCommand {
                         op: PathCommandType::Quad1 as u32,
-                        point: *control - bounds.min,
+                        point: *control - origin,
                     });
                     self.commands.push(PathCommand {
                         op: PathCommandType::Quad2 as u32,
-                        point: *point - bounds.min,
+                        point: *point - origin,
                     });
                 }
             }
@@ -132,6 +126,6 @@ impl DrawPathMaterial {
 
 impl UiMaterial for DrawPathMaterial {
     fn fragment_shader() -> ShaderRef {
-        "obsidian_ui://shaders/draw_path.wgsl".into()
+        "embedded://bevy_reactor_obsidian/assets/shaders/draw_path.wgsl".into()
     }
 }
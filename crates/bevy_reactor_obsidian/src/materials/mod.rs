@@ -1,11 +1,46 @@
-// mod dot_grid;
-// mod draw_path;
+mod corner_mask;
+mod dot_grid;
+mod draw_path;
 mod gradient_rect;
+mod rounded_border;
 mod slider_rect;
 mod swatch_rect;
 
-// pub(crate) use dot_grid::DotGridMaterial;
-// pub(crate) use draw_path::*;
+pub(crate) use corner_mask::CornerMaskMaterial;
+pub(crate) use dot_grid::DotGridMaterial;
+pub(crate) use draw_path::*;
 pub(crate) use gradient_rect::GradientRectMaterial;
+pub(crate) use rounded_border::RoundedBorderMaterial;
 pub(crate) use slider_rect::SliderRectMaterial;
 pub(crate) use swatch_rect::SwatchRectMaterial;
+
+use bevy::prelude::*;
+use bevy_reactor_builder::UiBuilder;
+
+/// Extension for creating material assets from a template.
+///
+/// The returned handle should be given to the widget's `MaterialNode`, and effects which
+/// update the material should capture only its `AssetId`. The node is then the sole owner of
+/// the asset, so the asset is freed when the widget is despawned rather than accumulating each
+/// time the widget is rebuilt.
+pub(crate) trait CreateUiMaterial {
+    fn create_ui_material<M: UiMaterial>(&mut self, material: M) -> Handle<M>;
+}
+
+impl<'w> CreateUiMaterial for UiBuilder<'w> {
+    fn create_ui_material<M: UiMaterial>(&mut self, material: M) -> Handle<M> {
+        self.world_mut().resource_mut::<Assets<M>>().add(material)
+    }
+}
+
+/// Modify a material asset in place. Does nothing if the asset has already been freed, which
+/// can happen when an effect runs while its widget is being despawned.
+pub(crate) fn update_ui_material<M: UiMaterial>(
+    world: &mut World,
+    id: AssetId<M>,
+    update: impl FnOnce(&mut M),
+) {
+    if let Some(material) = world.resource_mut::<Assets<M>>().get_mut(id) {
+        update(material);
+    }
+}
@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+use bevy::reflect::TypePath;
+use bevy::render::render_resource::*;
+
+#[derive(AsBindGroup, Asset, TypePath, Debug, Clone)]
+pub(crate) struct RoundedBorderMaterial {
+    #[uniform(0)]
+    pub(crate) color: Vec4,
+    #[uniform(1)]
+    pub(crate) border_color: Vec4,
+    #[uniform(2)]
+    pub(crate) border_radius: Vec4,
+    #[uniform(3)]
+    pub(crate) border_width: f32,
+}
+
+impl UiMaterial for RoundedBorderMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "embedded://bevy_reactor_obsidian/assets/shaders/rounded_border.wgsl".into()
+    }
+}
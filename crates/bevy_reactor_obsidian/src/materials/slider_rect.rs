@@ -9,7 +9,7 @@ pub struct SliderRectMaterial {
     #[uniform(1)]
     pub(crate) color_hi: Vec4,
     #[uniform(2)]
-    pub(crate) value: Vec4,
+    pub(crate) value: Vec4, // End of filled segment, start of filled segment, vertical
     #[uniform(3)]
     pub(crate) radius: Vec4, // TopLeft, TopRight, BottomRight, BottomLeft
 }
@@ -8,6 +8,12 @@ pub(crate) struct SwatchRectMaterial {
     pub(crate) color: Vec4,
     #[uniform(1)]
     pub(crate) border_radius: Vec4,
+    #[uniform(2)]
+    pub(crate) checker_light: Vec4,
+    #[uniform(3)]
+    pub(crate) checker_dark: Vec4,
+    #[uniform(4)]
+    pub(crate) checker_size: f32,
 }
 
 impl UiMaterial for SwatchRectMaterial {
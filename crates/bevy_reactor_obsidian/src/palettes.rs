@@ -0,0 +1,173 @@
+use bevy::{color::Srgba, prelude::*};
+
+/// Identifies one of the color lists in [`ColorPalettes`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PaletteId {
+    /// Recently used colors, most recent first.
+    Recent,
+    /// Colors pinned by the user.
+    Pinned,
+    /// A named palette, by index.
+    Named(usize),
+}
+
+/// A named list of colors.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorPalette {
+    /// Name displayed above the palette.
+    pub name: String,
+    /// The colors in the palette.
+    pub colors: Vec<Srgba>,
+}
+
+impl ColorPalette {
+    /// Create a new palette.
+    pub fn new(name: impl Into<String>, colors: Vec<Srgba>) -> Self {
+        Self {
+            name: name.into(),
+            colors,
+        }
+    }
+}
+
+/// Resource which holds the colors shown in color pickers: a history of recently used colors,
+/// colors pinned by the user, and any number of named palettes. Apps which want the palettes to
+/// persist between sessions should save and restore this resource.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct ColorPalettes {
+    /// Recently used colors, most recent first.
+    pub recent: Vec<Srgba>,
+    /// Maximum number of recent colors to remember.
+    pub max_recent: usize,
+    /// Colors pinned by the user.
+    pub pinned: Vec<Srgba>,
+    /// Named palettes.
+    pub named: Vec<ColorPalette>,
+}
+
+impl Default for ColorPalettes {
+    fn default() -> Self {
+        Self {
+            recent: Vec::new(),
+            max_recent: 16,
+            pinned: Vec::new(),
+            named: Vec::new(),
+        }
+    }
+}
+
+impl ColorPalettes {
+    /// The colors in the given list. Returns an empty slice if there is no such palette.
+    pub fn colors(&self, id: PaletteId) -> &[Srgba] {
+        match id {
+            PaletteId::Recent => &self.recent,
+            PaletteId::Pinned => &self.pinned,
+            PaletteId::Named(index) => self.named.get(index).map_or(&[], |p| &p.colors),
+        }
+    }
+
+    fn colors_mut(&mut self, id: PaletteId) -> Option<&mut Vec<Srgba>> {
+        match id {
+            PaletteId::Recent => Some(&mut self.recent),
+            PaletteId::Pinned => Some(&mut self.pinned),
+            PaletteId::Named(index) => self.named.get_mut(index).map(|p| &mut p.colors),
+        }
+    }
+
+    /// Record that a color was used, moving it to the front of the recent colors.
+    pub fn add_recent(&mut self, color: Srgba) {
+        self.recent.retain(|c| *c != color);
+        self.recent.insert(0, color);
+        self.recent.truncate(self.max_recent);
+    }
+
+    /// Whether the color is pinned.
+    pub fn is_pinned(&self, color: Srgba) -> bool {
+        self.pinned.contains(&color)
+    }
+
+    /// Pin a color, if it is not already pinned.
+    pub fn pin(&mut self, color: Srgba) {
+        if !self.is_pinned(color) {
+            self.pinned.push(color);
+        }
+    }
+
+    /// Unpin a color.
+    pub fn unpin(&mut self, color: Srgba) {
+        self.pinned.retain(|c| *c != color);
+    }
+
+    /// Pin the color if it is not pinned, otherwise unpin it.
+    pub fn toggle_pin(&mut self, color: Srgba) {
+        if self.is_pinned(color) {
+            self.unpin(color);
+        } else {
+            self.pin(color);
+        }
+    }
+
+    /// Add a named palette, returning its id. If a palette with that name already exists, its
+    /// colors are replaced.
+    pub fn add_palette(&mut self, name: impl Into<String>, colors: Vec<Srgba>) -> PaletteId {
+        let name = name.into();
+        match self.named.iter().position(|p| p.name == name) {
+            Some(index) => {
+                self.named[index].colors = colors;
+                PaletteId::Named(index)
+            }
+            None => {
+                self.named.push(ColorPalette::new(name, colors));
+                PaletteId::Named(self.named.len() - 1)
+            }
+        }
+    }
+
+    /// Remove a named palette. Ids of the palettes after it shift down by one.
+    pub fn remove_palette(&mut self, id: PaletteId) {
+        if let PaletteId::Named(index) = id {
+            if index < self.named.len() {
+                self.named.remove(index);
+            }
+        }
+    }
+
+    /// Find a named palette by name.
+    pub fn find_palette(&self, name: &str) -> Option<PaletteId> {
+        self.named
+            .iter()
+            .position(|p| p.name == name)
+            .map(PaletteId::Named)
+    }
+
+    /// Move the color at `index` in one list to the end of another. Colors dragged out of the
+    /// recent list are copied rather than moved, since the list is a history. Does nothing if
+    /// the target already contains the color.
+    pub fn move_color(&mut self, from: PaletteId, index: usize, to: PaletteId) {
+        let Some(&color) = self.colors(from).get(index) else {
+            return;
+        };
+        if from == to || self.colors(to).contains(&color) {
+            return;
+        }
+        let Some(target) = self.colors_mut(to) else {
+            return;
+        };
+        target.push(color);
+        if from != PaletteId::Recent {
+            if let Some(source) = self.colors_mut(from) {
+                source.remove(index);
+            }
+        }
+    }
+
+    /// Move a color to a new position within the same list.
+    pub fn reorder(&mut self, id: PaletteId, from: usize, to: usize) {
+        if let Some(colors) = self.colors_mut(id) {
+            if from < colors.len() && to < colors.len() {
+                let color = colors.remove(from);
+                colors.insert(to, color);
+            }
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use bevy::{
+    picking::{
+        backend::{HitData, PointerHits},
+        pointer::{PointerId, PointerLocation},
+    },
+    prelude::*,
+    window::PrimaryWindow,
+};
+
+/// Marker component for UI nodes, such as panels and toolbars, which should prevent the
+/// pointer from picking 3D objects behind them. Other UI elements within the node can still
+/// be picked as usual.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct BlocksPicking;
+
+/// Picking backend which reports a hit on any [`BlocksPicking`] node under the pointer. The
+/// hit is ordered just above the scene cameras, but below the regular UI hits, so it blocks
+/// raycast and backdrop hits without hiding the UI elements inside the node.
+pub(crate) fn occlude_picking(
+    q_pointers: Query<(&PointerId, &PointerLocation)>,
+    q_cameras: Query<(Entity, &Camera)>,
+    q_primary_window: Query<Entity, With<PrimaryWindow>>,
+    q_blockers: Query<
+        (
+            Entity,
+            &ComputedNode,
+            &GlobalTransform,
+            &InheritedVisibility,
+        ),
+        With<BlocksPicking>,
+    >,
+    mut output_events: EventWriter<PointerHits>,
+) {
+    let primary_window = q_primary_window.get_single().ok();
+    for (pointer_id, pointer_location) in q_pointers.iter() {
+        let Some(location) = pointer_location.location() else {
+            continue;
+        };
+
+        // Find the topmost camera which renders to the same target as the pointer.
+        let Some((camera_id, camera)) = q_cameras
+            .iter()
+            .filter(|(_, camera)| {
+                camera.is_active
+                    && camera.target.normalize(primary_window).as_ref() == Some(&location.target)
+            })
+            .max_by_key(|(_, camera)| camera.order)
+        else {
+            continue;
+        };
+
+        let picks: Vec<(Entity, HitData)> = q_blockers
+            .iter()
+            .filter(|(_, node, transform, visibility)| {
+                visibility.get()
+                    && Rect::from_center_size(transform.translation().xy(), node.size())
+                        .contains(location.position)
+            })
+            .map(|(entity, ..)| (entity, HitData::new(camera_id, 0., None, None)))
+            .collect();
+
+        if !picks.is_empty() {
+            let order = camera.order as f32 + 0.25;
+            output_events.send(PointerHits::new(*pointer_id, picks, order));
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use bevy::prelude::*;
+use bevy_mod_stylebuilder::StyleBuilder;
+
+use crate::{materials::RoundedBorderMaterial, rounded_corners::RoundedCorners};
+
+/// Describes a rounded rectangle with an outline, drawn with a shader so that the outline
+/// follows the curve of the corners.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoundedBorder {
+    /// Color of the interior.
+    pub fill: Color,
+
+    /// Color of the outline.
+    pub color: Color,
+
+    /// Thickness of the outline, in pixels. Zero means no outline.
+    pub width: f32,
+
+    /// Radius of each corner, ordered top left, top right, bottom right, bottom left.
+    pub radius: Vec4,
+}
+
+impl Default for RoundedBorder {
+    fn default() -> Self {
+        Self {
+            fill: Color::NONE,
+            color: Color::NONE,
+            width: 1.,
+            radius: Vec4::ZERO,
+        }
+    }
+}
+
+impl RoundedBorder {
+    /// Construct a new `RoundedBorder`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the color of the interior.
+    pub fn fill(mut self, fill: impl Into<Color>) -> Self {
+        self.fill = fill.into();
+        self
+    }
+
+    /// Set the color of the outline.
+    pub fn color(mut self, color: impl Into<Color>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// Set the thickness of the outline.
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Set the same radius for all corners.
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.radius = Vec4::splat(radius);
+        self
+    }
+
+    /// Round only the given corners.
+    pub fn corners(mut self, corners: RoundedCorners, radius: f32) -> Self {
+        self.radius = corners.to_vec(radius);
+        self
+    }
+
+    /// Set the radius of each corner individually.
+    pub fn corner_radii(
+        mut self,
+        top_left: f32,
+        top_right: f32,
+        bottom_right: f32,
+        bottom_left: f32,
+    ) -> Self {
+        self.radius = Vec4::new(top_left, top_right, bottom_right, bottom_left);
+        self
+    }
+}
+
+#[allow(missing_docs)]
+pub trait StyleBuilderRoundedBorder {
+    /// Draw the node's background and outline as a rounded rectangle. This takes the place of
+    /// `background_color` and `border_color`, which don't follow rounded corners.
+    fn rounded_border(&mut self, border: RoundedBorder) -> &mut Self;
+}
+
+impl<'a, 'w> StyleBuilderRoundedBorder for StyleBuilder<'a, 'w> {
+    fn rounded_border(&mut self, border: RoundedBorder) -> &mut Self {
+        let color = LinearRgba::from(border.fill).to_vec4();
+        let border_color = LinearRgba::from(border.color).to_vec4();
+        let existing = self
+            .target
+            .get::<MaterialNode<RoundedBorderMaterial>>()
+            .map(|node| node.0.clone());
+        let material = self.target.world_scope(|world| {
+            let mut materials = world.resource_mut::<Assets<RoundedBorderMaterial>>();
+            // Reuse the material if the style is being re-applied.
+            if let Some(material) = existing
+                .as_ref()
+                .and_then(|handle| materials.get_mut(handle.id()))
+            {
+                material.color = color;
+                material.border_color = border_color;
+                material.border_radius = border.radius;
+                material.border_width = border.width;
+                return None;
+            }
+            Some(materials.add(RoundedBorderMaterial {
+                color,
+                border_color,
+                border_radius: border.radius,
+                border_width: border.width,
+            }))
+        });
+        if let Some(material) = material {
+            self.target.insert(MaterialNode(material));
+        }
+        self
+    }
+}
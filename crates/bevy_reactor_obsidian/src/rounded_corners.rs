@@ -16,11 +16,39 @@ pub enum RoundedCorners {
     Right,
     Bottom,
     Left,
+    /// A separate radius for each corner. Percentages are relative to the smaller of the
+    /// node's width and height, as with [`BorderRadius`].
+    Custom {
+        top_left: Val,
+        top_right: Val,
+        bottom_right: Val,
+        bottom_left: Val,
+    },
+}
+
+impl From<BorderRadius> for RoundedCorners {
+    fn from(radius: BorderRadius) -> Self {
+        RoundedCorners::Custom {
+            top_left: radius.top_left,
+            top_right: radius.top_right,
+            bottom_right: radius.bottom_right,
+            bottom_left: radius.bottom_left,
+        }
+    }
 }
 
 impl RoundedCorners {
-    /// Convert the `RoundedCorners` to a `Vec4` for use in a shader.
+    /// Convert the `RoundedCorners` to a `Vec4` for use in a shader. `Custom` radii given as
+    /// percentages resolve to zero, since the size of the node is not known; use
+    /// [`RoundedCorners::resolve`] for those.
     pub fn to_vec(&self, radius: f32) -> Vec4 {
+        self.resolve(radius, Vec2::ZERO)
+    }
+
+    /// Convert the `RoundedCorners` to a `Vec4` for use in a shader, resolving any percentages
+    /// against a node of the given size. Radii are limited to half the smaller dimension of the
+    /// node, matching how Bevy draws rounded borders.
+    pub fn resolve(&self, radius: f32, size: Vec2) -> Vec4 {
         match self {
             RoundedCorners::None => Vec4::new(0.0, 0.0, 0.0, 0.0),
             RoundedCorners::All => Vec4::new(radius, radius, radius, radius),
@@ -32,6 +60,30 @@ impl RoundedCorners {
             RoundedCorners::Right => Vec4::new(0.0, radius, radius, 0.0),
             RoundedCorners::Bottom => Vec4::new(0.0, 0.0, radius, radius),
             RoundedCorners::Left => Vec4::new(radius, 0.0, 0.0, radius),
+            RoundedCorners::Custom {
+                top_left,
+                top_right,
+                bottom_right,
+                bottom_left,
+            } => {
+                let min_length = size.min_element();
+                let resolve = |val: &Val| match val {
+                    Val::Px(px) => *px,
+                    Val::Percent(percent) => min_length * percent / 100.,
+                    _ => 0.,
+                };
+                let radii = Vec4::new(
+                    resolve(top_left),
+                    resolve(top_right),
+                    resolve(bottom_right),
+                    resolve(bottom_left),
+                );
+                if min_length > 0. {
+                    radii.clamp(Vec4::ZERO, Vec4::splat(min_length * 0.5))
+                } else {
+                    radii.max(Vec4::ZERO)
+                }
+            }
         }
     }
 
@@ -90,6 +142,17 @@ impl RoundedCorners {
                 bottom_right: zero,
                 bottom_left: radius,
             },
+            RoundedCorners::Custom {
+                top_left,
+                top_right,
+                bottom_right,
+                bottom_left,
+            } => BorderRadius {
+                top_left: *top_left,
+                top_right: *top_right,
+                bottom_right: *bottom_right,
+                bottom_left: *bottom_left,
+            },
         }
     }
 
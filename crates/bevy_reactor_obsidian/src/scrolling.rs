@@ -5,6 +5,8 @@ use bevy::{
     prelude::*,
     ui,
 };
+use bevy_reactor_builder::UiBuilder;
+use bevy_reactor_signals::{Rcx, Signal};
 
 #[derive(Clone, Debug, Component)]
 pub struct ScrollWheelEvent(pub MouseWheel);
@@ -55,6 +57,120 @@ impl ScrollArea {
         self.scroll_left = x.min(self.content_size.x - self.visible_size.x).max(0.);
         self.scroll_top = y.min(self.content_size.y - self.visible_size.y).max(0.);
     }
+
+    /// The current scroll position.
+    pub fn offset(&self) -> Vec2 {
+        Vec2::new(self.scroll_left, self.scroll_top)
+    }
+
+    /// The largest possible scroll position on each axis; zero when the content fits.
+    pub fn scroll_range(&self) -> Vec2 {
+        (self.content_size - self.visible_size).max(Vec2::ZERO)
+    }
+
+    /// The scroll position as a fraction of the scroll range, from 0 to 1 on each axis. Axes
+    /// which can't scroll report 0.
+    pub fn scroll_fraction(&self) -> Vec2 {
+        let range = self.scroll_range();
+        Vec2::new(
+            if range.x > 0. {
+                self.scroll_left / range.x
+            } else {
+                0.
+            },
+            if range.y > 0. {
+                self.scroll_top / range.y
+            } else {
+                0.
+            },
+        )
+    }
+
+    /// Whether the content is scrolled all the way to the top. This is also true when the
+    /// content can't scroll vertically.
+    pub fn at_top(&self) -> bool {
+        self.scroll_top <= SCROLL_EPSILON
+    }
+
+    /// Whether the content is scrolled all the way to the bottom. This is also true when the
+    /// content can't scroll vertically.
+    pub fn at_bottom(&self) -> bool {
+        self.scroll_top >= self.scroll_range().y - SCROLL_EPSILON
+    }
+}
+
+/// Distance from the end of the scroll range, in pixels, which counts as being at the end.
+/// Layout rounding can leave the position slightly short.
+const SCROLL_EPSILON: f32 = 0.5;
+
+/// Reactive signals describing the state of a scroll view, created by
+/// [`create_scroll_signals`](CreateScrollSignals::create_scroll_signals).
+#[derive(Clone, Copy)]
+pub struct ScrollSignals {
+    /// The scroll position, in pixels.
+    pub offset: Signal<Vec2>,
+
+    /// Size of the scrolling content.
+    pub content_size: Signal<Vec2>,
+
+    /// Size of the visible area.
+    pub visible_size: Signal<Vec2>,
+
+    /// The scroll position as a fraction of the scroll range on each axis.
+    pub fraction: Signal<Vec2>,
+
+    /// Whether the content is scrolled to the top.
+    pub at_top: Signal<bool>,
+
+    /// Whether the content is scrolled to the bottom.
+    pub at_bottom: Signal<bool>,
+}
+
+pub trait CreateScrollSignals {
+    /// Create signals which track the scroll state of a scroll view. `scroll_view` is either
+    /// an entity with a [`ScrollArea`], or the entity passed to
+    /// [`ScrollView::entity`](crate::controls::ScrollView::entity). The signals report default
+    /// values until the scroll view has been built and laid out.
+    fn create_scroll_signals(&mut self, scroll_view: Entity) -> ScrollSignals;
+}
+
+impl<'w> CreateScrollSignals for UiBuilder<'w> {
+    fn create_scroll_signals(&mut self, scroll_view: Entity) -> ScrollSignals {
+        ScrollSignals {
+            offset: scroll_signal(self, scroll_view, ScrollArea::offset),
+            content_size: scroll_signal(self, scroll_view, |area| area.content_size),
+            visible_size: scroll_signal(self, scroll_view, |area| area.visible_size),
+            fraction: scroll_signal(self, scroll_view, ScrollArea::scroll_fraction),
+            at_top: scroll_signal(self, scroll_view, ScrollArea::at_top),
+            at_bottom: scroll_signal(self, scroll_view, ScrollArea::at_bottom),
+        }
+    }
+}
+
+/// Create a signal which extracts a value from the [`ScrollArea`] of a scroll view. The
+/// extracted value is compared, so the signal only changes when the value does.
+fn scroll_signal<T: PartialEq + Clone + Default + Send + Sync + 'static>(
+    builder: &mut UiBuilder,
+    scroll_view: Entity,
+    extract: fn(&ScrollArea) -> T,
+) -> Signal<T> {
+    builder.create_derived_eq(move |rcx| {
+        find_scroll_area(rcx, scroll_view)
+            .and_then(|area| rcx.use_component_map(area, extract))
+            .unwrap_or_default()
+    })
+}
+
+/// Find the entity with the [`ScrollArea`]: either `scroll_view` itself or one of its
+/// children.
+fn find_scroll_area(rcx: &Rcx, scroll_view: Entity) -> Option<Entity> {
+    if rcx.world().get::<ScrollArea>(scroll_view).is_some() {
+        return Some(scroll_view);
+    }
+    rcx.read_component::<Children>(scroll_view)?
+        .iter()
+        .copied()
+        .find(|child| rcx.world().get::<ScrollArea>(*child).is_some())
 }
 
 /// Marker component indicating this entity is the scrolling content area.
@@ -78,6 +194,26 @@ pub struct ScrollBar {
 #[derive(Component)]
 pub struct ScrollBarThumb;
 
+/// Makes a descendant of a scroll view's content stick to an edge of the visible area while
+/// the rest of its section scrolls past. The section is the sticky entity's parent: the sticky
+/// entity never leaves the bounds of its parent, so when a list is made of sections which each
+/// start with a sticky header, the header of the next section pushes the previous one out.
+///
+/// Sticky entities are offset with relative positioning, so they should not set `top`
+/// themselves. They are drawn above their siblings, and usually need an opaque background.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+#[require(ZIndex(sticky_z_index))]
+pub enum Sticky {
+    /// Stick to the top of the visible area, such as for section headers.
+    Top,
+    /// Stick to the bottom of the visible area, such as for totals or input rows.
+    Bottom,
+}
+
+fn sticky_z_index() -> ZIndex {
+    ZIndex(1)
+}
+
 #[allow(clippy::type_complexity)]
 pub(crate) fn update_scroll_positions(
     mut query: Query<(&ComputedNode, &mut ScrollArea, &GlobalTransform, &Children)>,
@@ -164,6 +300,71 @@ pub(crate) fn update_scroll_positions(
     }
 }
 
+/// Offset sticky entities so that they stay within the visible area of their scroll view.
+/// Positions are computed relative to the scroll content, so that they agree with the scroll
+/// position set by [`update_scroll_positions`] in the same frame.
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_sticky_positions(
+    mut q_sticky: Query<(&Sticky, &mut Node, &ComputedNode, &GlobalTransform, &Parent)>,
+    q_nodes: Query<(&ComputedNode, &GlobalTransform), Without<Sticky>>,
+    q_content: Query<&Parent, With<ScrollContent>>,
+    q_areas: Query<&ScrollArea>,
+    q_parents: Query<&Parent>,
+) {
+    for (sticky, mut style, node, transform, parent) in q_sticky.iter_mut() {
+        let Some(content) = std::iter::once(parent.get())
+            .chain(q_parents.iter_ancestors(parent.get()))
+            .find(|e| q_content.contains(*e))
+        else {
+            continue;
+        };
+        let Some(area) = q_content
+            .get(content)
+            .ok()
+            .and_then(|p| q_areas.get(p.get()).ok())
+        else {
+            continue;
+        };
+        let (Ok(section), Ok(content_node)) = (q_nodes.get(parent.get()), q_nodes.get(content))
+        else {
+            continue;
+        };
+
+        // Work in logical pixels, relative to the top of the content.
+        let scale = node.inverse_scale_factor();
+        let top_of = |node: &ComputedNode, transform: &GlobalTransform| {
+            (transform.translation().y - node.size().y * 0.5) * scale
+        };
+        let content_top = top_of(content_node.0, content_node.1);
+        let current_shift = match style.top {
+            ui::Val::Px(shift) => shift,
+            _ => 0.,
+        };
+        let height = node.size().y * scale;
+        let natural_top = top_of(node, transform) - content_top - current_shift;
+        let section_top = top_of(section.0, section.1) - content_top;
+        let section_bottom = section_top + section.0.size().y * scale;
+
+        let top = match sticky {
+            Sticky::Top => natural_top
+                .max(area.scroll_top)
+                .min(section_bottom - height)
+                .max(natural_top),
+            Sticky::Bottom => {
+                (natural_top + height)
+                    .min(area.scroll_top + area.visible_size.y)
+                    .max(section_top + height)
+                    .min(natural_top + height)
+                    - height
+            }
+        };
+        let shift = ui::Val::Px(top - natural_top);
+        if style.top != shift {
+            style.top = shift;
+        }
+    }
+}
+
 pub(crate) fn handle_scroll_events(
     mut scroll_evr: EventReader<MouseWheel>,
     hover_map: Res<HoverMap>,
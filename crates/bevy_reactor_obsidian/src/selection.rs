@@ -0,0 +1,290 @@
+use bevy::prelude::*;
+
+/// How many items a [`SelectionModel`] allows to be selected at once.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SelectionMode {
+    /// Items cannot be selected.
+    None,
+    /// At most one item can be selected.
+    #[default]
+    Single,
+    /// Any number of items can be selected, using shift-click to select a range and
+    /// ctrl-click to toggle individual items.
+    Multiple,
+}
+
+/// Modifier keys which affect how a click changes the selection.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SelectModifiers {
+    /// Extend the selection from the anchor to the clicked item.
+    pub shift: bool,
+    /// Toggle the clicked item without affecting the rest of the selection.
+    pub ctrl: bool,
+}
+
+impl SelectModifiers {
+    /// Read the modifier keys which are currently held down. The command key counts as ctrl,
+    /// following the macOS convention.
+    pub fn from_keys(keys: &ButtonInput<KeyCode>) -> Self {
+        Self {
+            shift: keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]),
+            ctrl: keys.any_pressed([
+                KeyCode::ControlLeft,
+                KeyCode::ControlRight,
+                KeyCode::SuperLeft,
+                KeyCode::SuperRight,
+            ]),
+        }
+    }
+}
+
+/// The set of selected items in a list or tree, along with the anchor used for range
+/// selection. Items are identified by value, so they should be cheap to clone, such as ids or
+/// entities. Can be stored in a [`Mutable`](bevy_reactor_signals::Mutable) or used as a
+/// resource.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct SelectionModel<T> {
+    mode: SelectionMode,
+    selected: Vec<T>,
+    anchor: Option<T>,
+}
+
+impl<T> Default for SelectionModel<T> {
+    fn default() -> Self {
+        Self {
+            mode: SelectionMode::Single,
+            selected: Vec::new(),
+            anchor: None,
+        }
+    }
+}
+
+impl<T: Clone + PartialEq> SelectionModel<T> {
+    /// Create a new, empty selection.
+    pub fn new(mode: SelectionMode) -> Self {
+        Self {
+            mode,
+            selected: Vec::new(),
+            anchor: None,
+        }
+    }
+
+    /// Create a new selection which allows at most one item.
+    pub fn single() -> Self {
+        Self::new(SelectionMode::Single)
+    }
+
+    /// Create a new selection which allows any number of items.
+    pub fn multiple() -> Self {
+        Self::new(SelectionMode::Multiple)
+    }
+
+    /// The selection mode.
+    pub fn mode(&self) -> SelectionMode {
+        self.mode
+    }
+
+    /// The selected items, in the order they were selected.
+    pub fn selected(&self) -> &[T] {
+        &self.selected
+    }
+
+    /// The item most recently selected with a plain click or ctrl-click, from which shift-click
+    /// ranges are extended.
+    pub fn anchor(&self) -> Option<&T> {
+        self.anchor.as_ref()
+    }
+
+    /// Whether the item is selected.
+    pub fn is_selected(&self, item: &T) -> bool {
+        self.selected.contains(item)
+    }
+
+    /// Whether no items are selected.
+    pub fn is_empty(&self) -> bool {
+        self.selected.is_empty()
+    }
+
+    /// Deselect all items.
+    pub fn clear(&mut self) {
+        self.selected.clear();
+        self.anchor = None;
+    }
+
+    /// Replace the selection with a single item.
+    pub fn select(&mut self, item: T) {
+        if self.mode == SelectionMode::None {
+            return;
+        }
+        self.selected.clear();
+        self.selected.push(item.clone());
+        self.anchor = Some(item);
+    }
+
+    /// Add the item to the selection if it is not selected, or remove it if it is. In single
+    /// selection mode, this selects the item in place of any other.
+    pub fn toggle(&mut self, item: T) {
+        match self.mode {
+            SelectionMode::None => {}
+            SelectionMode::Single => {
+                if self.is_selected(&item) {
+                    self.clear();
+                } else {
+                    self.select(item);
+                }
+            }
+            SelectionMode::Multiple => {
+                if let Some(index) = self.selected.iter().position(|s| *s == item) {
+                    self.selected.remove(index);
+                } else {
+                    self.selected.push(item.clone());
+                }
+                self.anchor = Some(item);
+            }
+        }
+    }
+
+    /// Select every item in `order` between the anchor and `item`, inclusive, replacing the
+    /// rest of the selection unless `keep` is true. The anchor is not moved, so that repeated
+    /// shift-clicks pivot around the same item. Falls back to selecting just the item if there
+    /// is no anchor, or if either end is missing from `order`.
+    pub fn extend_to(&mut self, item: T, order: &[T], keep: bool) {
+        if self.mode != SelectionMode::Multiple {
+            self.select(item);
+            return;
+        }
+        let anchor = self
+            .anchor
+            .as_ref()
+            .and_then(|anchor| order.iter().position(|o| o == anchor));
+        let target = order.iter().position(|o| *o == item);
+        let (Some(anchor), Some(target)) = (anchor, target) else {
+            self.select(item);
+            return;
+        };
+        if !keep {
+            self.selected.clear();
+        }
+        for o in &order[anchor.min(target)..=anchor.max(target)] {
+            if !self.selected.contains(o) {
+                self.selected.push(o.clone());
+            }
+        }
+    }
+
+    /// Update the selection in response to a click on `item`, following the usual desktop
+    /// conventions: a plain click selects only that item, ctrl-click toggles it, shift-click
+    /// selects the range from the anchor, and ctrl-shift-click adds that range. `order` is the
+    /// list of items as displayed. Returns true if the selection changed.
+    pub fn click(&mut self, item: T, modifiers: SelectModifiers, order: &[T]) -> bool {
+        let prev = self.selected.clone();
+        match (modifiers.shift, modifiers.ctrl) {
+            (true, ctrl) => self.extend_to(item, order, ctrl),
+            (false, true) => self.toggle(item),
+            (false, false) => self.select(item),
+        }
+        self.selected != prev
+    }
+
+    /// Remove any selected items for which the predicate returns false, such as items which
+    /// no longer exist. Returns true if the selection changed.
+    pub fn retain(&mut self, mut keep: impl FnMut(&T) -> bool) -> bool {
+        let len = self.selected.len();
+        self.selected.retain(|item| keep(item));
+        if self.anchor.as_ref().is_some_and(|anchor| !keep(anchor)) {
+            self.anchor = None;
+        }
+        self.selected.len() != len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORDER: [u32; 6] = [0, 1, 2, 3, 4, 5];
+    const PLAIN: SelectModifiers = SelectModifiers {
+        shift: false,
+        ctrl: false,
+    };
+    const SHIFT: SelectModifiers = SelectModifiers {
+        shift: true,
+        ctrl: false,
+    };
+    const CTRL: SelectModifiers = SelectModifiers {
+        shift: false,
+        ctrl: true,
+    };
+    const CTRL_SHIFT: SelectModifiers = SelectModifiers {
+        shift: true,
+        ctrl: true,
+    };
+
+    #[test]
+    fn test_shift_range_from_anchor() {
+        let mut selection = SelectionModel::multiple();
+        assert!(selection.click(2, PLAIN, &ORDER));
+        assert!(selection.click(4, SHIFT, &ORDER));
+        assert_eq!(selection.selected(), &[2, 3, 4]);
+        assert_eq!(selection.anchor(), Some(&2));
+
+        // Shift-clicking again pivots around the same anchor, replacing the range.
+        assert!(selection.click(0, SHIFT, &ORDER));
+        assert_eq!(selection.selected(), &[0, 1, 2]);
+        assert_eq!(selection.anchor(), Some(&2));
+
+        // Ctrl-shift adds the range to the existing selection.
+        assert!(selection.click(5, CTRL_SHIFT, &ORDER));
+        assert_eq!(selection.selected(), &[0, 1, 2, 3, 4, 5]);
+
+        // Without an anchor, shift-click selects just the item.
+        selection.clear();
+        selection.click(3, SHIFT, &ORDER);
+        assert_eq!(selection.selected(), &[3]);
+    }
+
+    #[test]
+    fn test_ctrl_toggle() {
+        let mut selection = SelectionModel::multiple();
+        selection.click(1, PLAIN, &ORDER);
+        assert!(selection.click(3, CTRL, &ORDER));
+        assert_eq!(selection.selected(), &[1, 3]);
+        assert_eq!(selection.anchor(), Some(&3));
+
+        // Ctrl-clicking a selected item deselects it, and moves the anchor to it.
+        assert!(selection.click(1, CTRL, &ORDER));
+        assert_eq!(selection.selected(), &[3]);
+        assert_eq!(selection.anchor(), Some(&1));
+
+        // A plain click on the only selected item changes nothing.
+        assert!(!selection.click(3, PLAIN, &ORDER));
+    }
+
+    #[test]
+    fn test_single_select_mode() {
+        let mut selection = SelectionModel::single();
+        selection.click(1, PLAIN, &ORDER);
+        selection.click(3, SHIFT, &ORDER);
+        assert_eq!(selection.selected(), &[3]);
+
+        // Ctrl-click replaces the selection, or clears it if the item is already selected.
+        selection.click(4, CTRL, &ORDER);
+        assert_eq!(selection.selected(), &[4]);
+        selection.click(4, CTRL, &ORDER);
+        assert!(selection.is_empty());
+
+        let mut selection = SelectionModel::new(SelectionMode::None);
+        assert!(!selection.click(1, PLAIN, &ORDER));
+        assert!(selection.is_empty());
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut selection = SelectionModel::multiple();
+        selection.click(1, PLAIN, &ORDER);
+        selection.click(2, CTRL, &ORDER);
+        assert!(selection.retain(|item| *item != 2));
+        assert_eq!(selection.selected(), &[1]);
+        assert_eq!(selection.anchor(), None);
+    }
+}
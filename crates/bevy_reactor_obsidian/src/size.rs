@@ -1,3 +1,5 @@
+use bevy::prelude::*;
+
 /// Standard sizes for buttons and other widgets that have size variants.
 #[derive(Clone, Copy, PartialEq, Debug, Default)]
 #[allow(missing_docs)]
@@ -65,3 +67,73 @@ impl Size {
         }
     }
 }
+
+/// How tightly widgets are packed.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum UiDensity {
+    /// Shorter widgets, for fitting more controls on screen.
+    Compact,
+    /// The standard widget heights.
+    #[default]
+    Comfortable,
+}
+
+impl UiDensity {
+    /// Multiplier applied to widget heights.
+    pub fn factor(&self) -> f32 {
+        match self {
+            UiDensity::Compact => 0.85,
+            UiDensity::Comfortable => 1.0,
+        }
+    }
+}
+
+/// Global sizing settings for the UI. Widgets read this resource when computing their size,
+/// so changes take effect immediately.
+#[derive(Resource, Clone, Copy, PartialEq, Debug)]
+pub struct UiSizing {
+    /// Widget density.
+    pub density: UiDensity,
+
+    /// Multiplier for the size of the entire UI, for example on high-DPI displays. This is
+    /// applied through Bevy's [`UiScale`].
+    pub scale: f32,
+
+    /// Additional multiplier for the font sizes of widgets, for users who need larger text.
+    pub font_scale: f32,
+}
+
+impl Default for UiSizing {
+    fn default() -> Self {
+        Self {
+            density: UiDensity::default(),
+            scale: 1.0,
+            font_scale: 1.0,
+        }
+    }
+}
+
+impl UiSizing {
+    /// Returns the height of a widget of the given size, in pixels.
+    pub fn height(&self, size: Size) -> f32 {
+        // Text needs to fit, so don't let widgets shrink below the scaled font size.
+        (size.height() * self.density.factor()).max(self.font_size(size) * 1.4)
+    }
+
+    /// Returns the font size for a widget of the given size.
+    pub fn font_size(&self, size: Size) -> f32 {
+        size.font_size() * self.font_scale
+    }
+
+    /// Returns the border radius for a widget of the given size.
+    pub fn border_radius(&self, size: Size) -> f32 {
+        size.border_radius() * self.density.factor()
+    }
+}
+
+/// Copies [`UiSizing::scale`] to Bevy's [`UiScale`].
+pub(crate) fn apply_ui_scale(sizing: Res<UiSizing>, mut ui_scale: ResMut<UiScale>) {
+    if sizing.is_changed() && ui_scale.0 != sizing.scale {
+        ui_scale.0 = sizing.scale;
+    }
+}
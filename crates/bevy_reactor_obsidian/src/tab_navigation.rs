@@ -1,19 +1,27 @@
 use bevy::{
     a11y::Focus,
     app::{App, Plugin, Update},
+    core::Name,
     ecs::{
         component::Component,
         entity::Entity,
-        system::{Query, ResMut, SystemParam},
+        system::{Commands, Local, Query, ResMut, SystemParam},
     },
     hierarchy::{Children, Parent},
+    input::keyboard::KeyCode,
     input::{ButtonInput, ButtonState},
     log::*,
-    prelude::{Added, KeyCode, Res, Trigger, With, Without},
+    prelude::{Added, In, IntoSystemConfigs, Res, Resource, Trigger, With, Without},
     ui::Node,
 };
 
-use crate::input_dispatch::{FocusKeyboardInput, KeyboardFocus, KeyboardFocusVisible};
+use crate::{
+    controls::RegisterPaletteCommand,
+    input_dispatch::{
+        entity_label, DispatchControl, FocusKeyboardInput, FocusSource, FocusTrace, KeyboardFocus,
+        KeyboardFocusVisible,
+    },
+};
 
 /// A component which indicates that an entity wants to participate in tab navigation.
 ///
@@ -34,6 +42,31 @@ pub struct TabIndex(pub i32);
 #[derive(Debug, Default, Component, Copy, Clone)]
 pub struct AutoFocus;
 
+/// Container-level policy which determines which descendant receives focus when the container
+/// is added, for example the first field of a dialog. The policy is resolved once, on the first
+/// update after the container is added, so its children must have been built by then. If the
+/// container is also a modal [`TabGroup`], the policy is applied again when it becomes the
+/// topmost modal group again, such as when a nested dialog or popup on top of it closes, unless
+/// focus is already within it. Focus which has been cleared is left alone.
+///
+/// A descendant with [`AutoFocus`] takes priority over the container's policy.
+#[derive(Debug, Component, Clone, PartialEq)]
+#[require(InitialFocusPending)]
+pub enum InitialFocus {
+    /// Focus the first focusable descendant, in tab order.
+    FirstFocusable,
+
+    /// Focus the first focusable descendant, in tab order, with the given [`Name`].
+    Named(String),
+
+    /// Focus a specific entity.
+    Entity(Entity),
+}
+
+/// Marks a container whose [`InitialFocus`] policy has not been resolved yet.
+#[derive(Debug, Default, Component, Copy, Clone)]
+pub(crate) struct InitialFocusPending;
+
 /// A component used to mark a tree of entities as containing tabbable elements.
 #[derive(Debug, Default, Component, Copy, Clone)]
 pub struct TabGroup {
@@ -169,6 +202,24 @@ impl TabNavigation<'_, '_> {
         focusable.get(next).map(|(e, _)| e).copied()
     }
 
+    /// Return the focusable descendants of a container, in tab order.
+    pub fn focusable_descendants(&self, container: Entity) -> Vec<Entity> {
+        let mut focusable: Vec<(Entity, TabIndex)> = Vec::new();
+        let children = self
+            .tabgroup
+            .get(container)
+            .map(|(_, _, children)| children)
+            .ok()
+            .or_else(|| self.tabindex.get(container).ok().and_then(|(_, _, c)| c));
+        if let Some(children) = children {
+            for child in children.iter() {
+                self.gather_focusable(&mut focusable, *child);
+            }
+        }
+        focusable.sort_by(compare_tab_indices);
+        focusable.into_iter().map(|(e, _)| e).collect()
+    }
+
     /// Gather all focusable entities in tree order.
     fn gather_focusable(&self, out: &mut Vec<(Entity, TabIndex)>, parent: Entity) {
         if let Ok((entity, tabindex, children)) = self.tabindex.get(parent) {
@@ -207,20 +258,128 @@ fn compare_tab_indices(a: &(Entity, TabIndex), b: &(Entity, TabIndex)) -> std::c
 fn handle_auto_focus(
     mut focus: ResMut<KeyboardFocus>,
     mut a11y_focus: ResMut<Focus>,
+    mut trace: ResMut<FocusTrace>,
     query: Query<Entity, (With<TabIndex>, Added<AutoFocus>)>,
 ) {
     if let Some(entity) = query.iter().next() {
+        trace.note(FocusSource::Initial);
         focus.0 = Some(entity);
         a11y_focus.0 = Some(entity);
     }
 }
 
+/// Resolve an [`InitialFocus`] policy to the entity which should receive focus.
+fn resolve_initial_focus(
+    container: Entity,
+    policy: &InitialFocus,
+    nav: &TabNavigation,
+    q_names: &Query<&Name>,
+    q_nodes: &Query<(), With<Node>>,
+) -> Option<Entity> {
+    match policy {
+        InitialFocus::FirstFocusable => nav.focusable_descendants(container).first().copied(),
+        InitialFocus::Named(name) => nav
+            .focusable_descendants(container)
+            .into_iter()
+            .find(|e| q_names.get(*e).is_ok_and(|n| n.as_str() == name)),
+        InitialFocus::Entity(entity) => q_nodes.contains(*entity).then_some(*entity),
+    }
+}
+
+/// True if `entity` is `container` or one of its descendants.
+fn is_in_container(entity: Entity, container: Entity, q_parents: &Query<&Parent>) -> bool {
+    let mut current = Some(entity);
+    while let Some(e) = current {
+        if e == container {
+            return true;
+        }
+        current = q_parents.get(e).ok().map(Parent::get);
+    }
+    false
+}
+
+/// The modal tab groups, in the order they were added, and the one which was topmost on the
+/// previous update.
+#[derive(Default)]
+struct ModalGroups {
+    stack: Vec<Entity>,
+    topmost: Option<Entity>,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_initial_focus(
+    mut commands: Commands,
+    nav: TabNavigation,
+    mut focus: ResMut<KeyboardFocus>,
+    mut a11y_focus: ResMut<Focus>,
+    mut trace: ResMut<FocusTrace>,
+    mut modals: Local<ModalGroups>,
+    q_pending: Query<(Entity, &InitialFocus), With<InitialFocusPending>>,
+    q_policies: Query<&InitialFocus, Without<InitialFocusPending>>,
+    q_added_groups: Query<(Entity, &TabGroup), Added<TabGroup>>,
+    q_groups: Query<&TabGroup>,
+    q_names: Query<&Name>,
+    q_nodes: Query<(), With<Node>>,
+    q_parents: Query<&Parent>,
+) {
+    // Newly-added containers. The policy is only tried once, so that a container whose
+    // target never appears doesn't keep stealing focus.
+    for (container, policy) in q_pending.iter() {
+        if let Some(target) = resolve_initial_focus(container, policy, &nav, &q_names, &q_nodes) {
+            trace.note(FocusSource::Initial);
+            focus.0 = Some(target);
+            a11y_focus.0 = Some(target);
+        }
+        commands.entity(container).remove::<InitialFocusPending>();
+    }
+
+    // Find the topmost modal group. Dialogs, popups and palettes usually share the same order,
+    // so ties are broken by which was added last.
+    for (group, tab_group) in q_added_groups.iter() {
+        if tab_group.modal {
+            modals.stack.push(group);
+        }
+    }
+    modals
+        .stack
+        .retain(|group| q_groups.get(*group).is_ok_and(|tg| tg.modal));
+    let topmost = modals
+        .stack
+        .iter()
+        .enumerate()
+        .max_by_key(|(index, group)| (q_groups.get(**group).map_or(0, |tg| tg.order), *index))
+        .map(|(_, group)| *group);
+    if std::mem::replace(&mut modals.topmost, topmost) == topmost {
+        return;
+    }
+
+    // When a container becomes the topmost modal group again, for example because a nested
+    // dialog on top of it has closed, restore focus within it. Focus which was cleared on
+    // purpose, such as by `clear_keyboard_focus`, is left alone.
+    let (Some(container), Some(focused)) = (topmost, focus.0) else {
+        return;
+    };
+    let Ok(policy) = q_policies.get(container) else {
+        return;
+    };
+    if q_nodes.contains(focused) && is_in_container(focused, container, &q_parents) {
+        return;
+    }
+    let target = resolve_initial_focus(container, policy, &nav, &q_names, &q_nodes);
+    if target.is_some() {
+        trace.note(FocusSource::Initial);
+        focus.0 = target;
+        a11y_focus.0 = target;
+    }
+}
+
 /// Plugin for handling keyboard input.
 pub struct TabNavigationPlugin;
 
 impl Plugin for TabNavigationPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, handle_auto_focus);
+        // Widget-level autofocus runs last so that it takes priority.
+        app.add_systems(Update, (handle_initial_focus, handle_auto_focus).chain());
     }
 }
 
@@ -231,10 +390,14 @@ pub fn handle_tab_navigation(
     mut focus: ResMut<KeyboardFocus>,
     mut a11y_focus: ResMut<Focus>,
     mut visible: ResMut<KeyboardFocusVisible>,
+    mut trace: ResMut<FocusTrace>,
     keys: Res<ButtonInput<KeyCode>>,
 ) {
-    // Tab navigation.
-    let key_event = &trigger.event().0;
+    // Tab navigation is a default action, which widgets can suppress.
+    if trigger.is_default_prevented() {
+        return;
+    }
+    let key_event = &trigger.event().input;
     if key_event.key_code == KeyCode::Tab
         && key_event.state == ButtonState::Pressed
         && !key_event.repeat
@@ -249,9 +412,170 @@ pub fn handle_tab_navigation(
         );
         if next.is_some() {
             trigger.propagate(false);
+            trace.note(FocusSource::Tab);
             focus.0 = next;
             a11y_focus.0 = next;
             visible.0 = true;
         }
     }
 }
+
+/// Log the path from the root to the focused entity, and the tab order of the tab group which
+/// contains it (or of the topmost modal group, or the first group, if nothing is focused).
+pub fn log_focus_report(
+    focus: Res<KeyboardFocus>,
+    nav: TabNavigation,
+    q_groups: Query<(Entity, &TabGroup)>,
+    q_parents: Query<&Parent>,
+    q_names: Query<&Name>,
+) {
+    let mut path = Vec::new();
+    let mut group = None;
+    let mut entity = focus.0;
+    while let Some(e) = entity {
+        path.push(entity_label(Some(e), &q_names));
+        if group.is_none() && q_groups.contains(e) {
+            group = Some(e);
+        }
+        entity = q_parents.get(e).ok().map(|parent| parent.get());
+    }
+    path.reverse();
+    if path.is_empty() {
+        info!("Focus path: none");
+    } else {
+        info!("Focus path: {}", path.join(" > "));
+    }
+
+    let group = group.or_else(|| {
+        q_groups
+            .iter()
+            .filter(|(_, tg)| tg.modal)
+            .max_by_key(|(_, tg)| tg.order)
+            .map(|(e, _)| e)
+            .or_else(|| {
+                q_groups
+                    .iter()
+                    .min_by_key(|(_, tg)| tg.order)
+                    .map(|(e, _)| e)
+            })
+    });
+    let Some(group) = group else {
+        info!("Tab order: no active tab group");
+        return;
+    };
+    info!("Tab order of {}:", entity_label(Some(group), &q_names));
+    for (index, e) in nav.focusable_descendants(group).into_iter().enumerate() {
+        let marker = if Some(e) == focus.0 { "*" } else { " " };
+        info!(
+            "{} {:3}: {}",
+            marker,
+            index,
+            entity_label(Some(e), &q_names)
+        );
+    }
+}
+
+/// The key which logs a focus report, when the [`FocusDebugPlugin`] is installed.
+#[derive(Resource, Clone, Copy)]
+struct FocusDebugHotkey(KeyCode);
+
+fn focus_debug_hotkey(
+    keys: Res<ButtonInput<KeyCode>>,
+    hotkey: Res<FocusDebugHotkey>,
+    mut commands: Commands,
+) {
+    if keys.just_pressed(hotkey.0) {
+        commands.run_system_cached(log_focus_report);
+    }
+}
+
+/// Plugin which helps to debug keyboard focus. It enables the [`FocusTrace`], logs a focus
+/// report (see [`log_focus_report`]) when the hotkey is pressed, and adds "Print Focus Report"
+/// and "Toggle Focus Trace" commands to the command palette.
+pub struct FocusDebugPlugin {
+    /// The key which logs a focus report.
+    pub hotkey: KeyCode,
+}
+
+impl Default for FocusDebugPlugin {
+    fn default() -> Self {
+        Self {
+            hotkey: KeyCode::F8,
+        }
+    }
+}
+
+impl Plugin for FocusDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(FocusDebugHotkey(self.hotkey))
+            .insert_resource(FocusTrace {
+                enabled: true,
+                ..Default::default()
+            })
+            .add_systems(Update, focus_debug_hotkey)
+            .register_palette_command("Print Focus Report", |_: In<()>, mut commands: Commands| {
+                commands.run_system_cached(log_focus_report);
+            })
+            .register_palette_command(
+                "Toggle Focus Trace",
+                |_: In<()>, mut trace: ResMut<FocusTrace>| {
+                    trace.enabled = !trace.enabled;
+                    info!("Focus trace {}", if trace.enabled { "on" } else { "off" });
+                },
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::{App, BuildChildren, ChildBuild, DespawnRecursiveExt};
+
+    use super::*;
+
+    fn spawn_group(app: &mut App, policy: Option<InitialFocus>) -> (Entity, Entity) {
+        let mut field = Entity::PLACEHOLDER;
+        let mut group = app.world_mut().spawn((
+            Node::default(),
+            TabGroup {
+                order: 0,
+                modal: true,
+            },
+        ));
+        if let Some(policy) = policy {
+            group.insert(policy);
+        }
+        group.with_children(|builder| {
+            field = builder.spawn((Node::default(), TabIndex(0))).id();
+        });
+        (group.id(), field)
+    }
+
+    fn focused(app: &App) -> Option<Entity> {
+        app.world().resource::<KeyboardFocus>().0
+    }
+
+    #[test]
+    fn test_initial_focus_yields_to_later_modal() {
+        let mut app = App::new();
+        app.insert_resource(KeyboardFocus(None))
+            .init_resource::<Focus>()
+            .init_resource::<FocusTrace>()
+            .add_systems(Update, handle_initial_focus);
+
+        let (_, dialog_field) = spawn_group(&mut app, Some(InitialFocus::FirstFocusable));
+        app.update();
+        assert_eq!(focused(&app), Some(dialog_field));
+
+        // A popup without a policy, which is not a descendant of the dialog, keeps focus.
+        let (popup, popup_field) = spawn_group(&mut app, None);
+        app.world_mut().resource_mut::<KeyboardFocus>().0 = Some(popup_field);
+        app.update();
+        app.update();
+        assert_eq!(focused(&app), Some(popup_field));
+
+        // Once the popup closes, focus returns to the dialog.
+        app.world_mut().entity_mut(popup).despawn_recursive();
+        app.update();
+        assert_eq!(focused(&app), Some(dialog_field));
+    }
+}
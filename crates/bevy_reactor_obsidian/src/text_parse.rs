@@ -0,0 +1,319 @@
+use std::fmt;
+
+use bevy::math::Vec3;
+
+/// Error produced when text entered into a field cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The text was empty.
+    Empty,
+    /// The text contained a character which was not expected at that position.
+    UnexpectedChar(char),
+    /// The text ended in the middle of an expression.
+    UnexpectedEnd,
+    /// The expression divided by zero.
+    DivisionByZero,
+    /// A vector had the wrong number of components.
+    ComponentCount { expected: usize, found: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "Value is empty"),
+            ParseError::UnexpectedChar(c) => write!(f, "Unexpected character '{}'", c),
+            ParseError::UnexpectedEnd => write!(f, "Incomplete expression"),
+            ParseError::DivisionByZero => write!(f, "Division by zero"),
+            ParseError::ComponentCount { expected, found } => {
+                write!(f, "Expected {} components, found {}", expected, found)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Locale-dependent rules for reading and writing numbers in text fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// The character used as a decimal point. A period is always accepted as well.
+    pub decimal_separator: char,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            decimal_separator: '.',
+        }
+    }
+}
+
+impl NumberFormat {
+    /// Number format which uses a comma as the decimal separator, as in much of Europe.
+    pub const COMMA: NumberFormat = NumberFormat {
+        decimal_separator: ',',
+    };
+
+    /// The character used to separate the components of a vector. This is a comma, unless
+    /// the comma is already used as the decimal separator, in which case it is a semicolon.
+    pub fn component_separator(&self) -> char {
+        if self.decimal_separator == ',' {
+            ';'
+        } else {
+            ','
+        }
+    }
+
+    /// Input mask for numeric fields: returns true if `c` can appear in a number or an
+    /// arithmetic expression.
+    pub fn is_numeric_char(&self, c: char) -> bool {
+        c.is_ascii_digit()
+            || c == '.'
+            || c == self.decimal_separator
+            || matches!(c, '+' | '-' | '*' | '/' | '%' | '^' | '(' | ')' | ' ')
+    }
+
+    /// Input mask for vector fields: like [`NumberFormat::is_numeric_char`], but also accepts
+    /// the component separator.
+    pub fn is_vector_char(&self, c: char) -> bool {
+        self.is_numeric_char(c) || c == self.component_separator()
+    }
+
+    /// Parse a number, which may be written as an arithmetic expression such as `2+3*4`.
+    /// Supports `+`, `-`, `*`, `/`, `%`, `^` and parentheses.
+    pub fn parse(&self, text: &str) -> Result<f64, ParseError> {
+        let chars: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+        if chars.is_empty() {
+            return Err(ParseError::Empty);
+        }
+        let mut parser = ExprParser {
+            chars: &chars,
+            pos: 0,
+            decimal_separator: self.decimal_separator,
+        };
+        let value = parser.expr()?;
+        match parser.peek() {
+            Some(c) => Err(ParseError::UnexpectedChar(c)),
+            None => Ok(value),
+        }
+    }
+
+    /// Parse a vector written as three separated components, such as `1, 2, 3`. Each
+    /// component may be an arithmetic expression.
+    pub fn parse_vec3(&self, text: &str) -> Result<Vec3, ParseError> {
+        let components = text
+            .split(self.component_separator())
+            .map(|part| self.parse(part).map(|v| v as f32))
+            .collect::<Result<Vec<f32>, ParseError>>()?;
+        match components[..] {
+            [x, y, z] => Ok(Vec3::new(x, y, z)),
+            _ => Err(ParseError::ComponentCount {
+                expected: 3,
+                found: components.len(),
+            }),
+        }
+    }
+
+    /// Format a number with the given number of decimal places.
+    pub fn format(&self, value: f64, precision: usize) -> String {
+        let text = format!("{:.*}", precision, value);
+        if self.decimal_separator == '.' {
+            text
+        } else {
+            text.replace('.', &self.decimal_separator.to_string())
+        }
+    }
+
+    /// Format a vector with the given number of decimal places per component.
+    pub fn format_vec3(&self, value: Vec3, precision: usize) -> String {
+        let separator = format!("{} ", self.component_separator());
+        [value.x, value.y, value.z]
+            .iter()
+            .map(|v| self.format(*v as f64, precision))
+            .collect::<Vec<_>>()
+            .join(&separator)
+    }
+}
+
+/// Recursive-descent evaluator for arithmetic expressions.
+struct ExprParser<'a> {
+    chars: &'a [char],
+    pos: usize,
+    decimal_separator: char,
+}
+
+impl<'a> ExprParser<'a> {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.pos += 1;
+        c
+    }
+
+    /// expr := term (('+' | '-') term)*
+    fn expr(&mut self) -> Result<f64, ParseError> {
+        let mut value = self.term()?;
+        while let Some(op @ ('+' | '-')) = self.peek() {
+            self.pos += 1;
+            let rhs = self.term()?;
+            value = if op == '+' { value + rhs } else { value - rhs };
+        }
+        Ok(value)
+    }
+
+    /// term := unary (('*' | '/' | '%') unary)*
+    fn term(&mut self) -> Result<f64, ParseError> {
+        let mut value = self.unary()?;
+        while let Some(op @ ('*' | '/' | '%')) = self.peek() {
+            self.pos += 1;
+            let rhs = self.unary()?;
+            value = match op {
+                '*' => value * rhs,
+                _ if rhs == 0. => return Err(ParseError::DivisionByZero),
+                '/' => value / rhs,
+                _ => value % rhs,
+            };
+        }
+        Ok(value)
+    }
+
+    /// unary := ('-' | '+') unary | power
+    fn unary(&mut self) -> Result<f64, ParseError> {
+        match self.peek() {
+            Some('-') => {
+                self.pos += 1;
+                Ok(-self.unary()?)
+            }
+            Some('+') => {
+                self.pos += 1;
+                self.unary()
+            }
+            _ => self.power(),
+        }
+    }
+
+    /// power := primary ('^' unary)?
+    fn power(&mut self) -> Result<f64, ParseError> {
+        let base = self.primary()?;
+        if self.peek() == Some('^') {
+            self.pos += 1;
+            let exponent = self.unary()?;
+            return Ok(base.powf(exponent));
+        }
+        Ok(base)
+    }
+
+    /// primary := number | '(' expr ')'
+    fn primary(&mut self) -> Result<f64, ParseError> {
+        match self.peek() {
+            Some('(') => {
+                self.pos += 1;
+                let value = self.expr()?;
+                match self.next() {
+                    Some(')') => Ok(value),
+                    Some(c) => Err(ParseError::UnexpectedChar(c)),
+                    None => Err(ParseError::UnexpectedEnd),
+                }
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' || c == self.decimal_separator => {
+                self.number()
+            }
+            Some(c) => Err(ParseError::UnexpectedChar(c)),
+            None => Err(ParseError::UnexpectedEnd),
+        }
+    }
+
+    fn number(&mut self) -> Result<f64, ParseError> {
+        let mut text = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_ascii_digit() {
+                text.push(c);
+            } else if c == '.' || c == self.decimal_separator {
+                if text.contains('.') {
+                    return Err(ParseError::UnexpectedChar(c));
+                }
+                text.push('.');
+            } else {
+                break;
+            }
+            self.pos += 1;
+        }
+        text.parse::<f64>()
+            .map_err(|_| ParseError::UnexpectedChar(self.decimal_separator))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_precedence() {
+        let format = NumberFormat::default();
+        assert_eq!(format.parse("2+3*4"), Ok(14.));
+        assert_eq!(format.parse("(2+3)*4"), Ok(20.));
+        assert_eq!(format.parse("10-4-3"), Ok(3.));
+        assert_eq!(format.parse("8/2/2"), Ok(2.));
+        assert_eq!(format.parse("7%4+1"), Ok(4.));
+        // Exponents are right-associative, and bind tighter than unary minus.
+        assert_eq!(format.parse("2^3^2"), Ok(512.));
+        assert_eq!(format.parse("-2^2"), Ok(-4.));
+        assert_eq!(format.parse("2*-3"), Ok(-6.));
+        assert_eq!(format.parse(" 1 + 2 "), Ok(3.));
+    }
+
+    #[test]
+    fn test_division_by_zero() {
+        let format = NumberFormat::default();
+        assert_eq!(format.parse("1/0"), Err(ParseError::DivisionByZero));
+        assert_eq!(format.parse("5%0"), Err(ParseError::DivisionByZero));
+        assert_eq!(format.parse("1/(2-2)"), Err(ParseError::DivisionByZero));
+    }
+
+    #[test]
+    fn test_malformed() {
+        let format = NumberFormat::default();
+        assert_eq!(format.parse(""), Err(ParseError::Empty));
+        assert_eq!(format.parse("  "), Err(ParseError::Empty));
+        assert_eq!(format.parse("2+"), Err(ParseError::UnexpectedEnd));
+        assert_eq!(format.parse("(1+2"), Err(ParseError::UnexpectedEnd));
+        assert_eq!(format.parse("2*)"), Err(ParseError::UnexpectedChar(')')));
+        assert_eq!(format.parse("1.2.3"), Err(ParseError::UnexpectedChar('.')));
+        assert_eq!(format.parse("abc"), Err(ParseError::UnexpectedChar('a')));
+        assert_eq!(
+            format.parse_vec3("1, 2"),
+            Err(ParseError::ComponentCount {
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_locales() {
+        let period = NumberFormat::default();
+        assert_eq!(period.parse("1.5"), Ok(1.5));
+        assert_eq!(period.parse("1,5"), Err(ParseError::UnexpectedChar(',')));
+        assert_eq!(period.parse_vec3("1, 2.5, 3"), Ok(Vec3::new(1., 2.5, 3.)));
+        assert_eq!(period.format(1.5, 2), "1.50");
+        assert_eq!(
+            period.format_vec3(Vec3::new(1., 2.5, 3.), 1),
+            "1.0, 2.5, 3.0"
+        );
+
+        // A period is accepted as the decimal point even when the locale uses a comma.
+        let comma = NumberFormat::COMMA;
+        assert_eq!(comma.parse("1,5"), Ok(1.5));
+        assert_eq!(comma.parse("1.5"), Ok(1.5));
+        assert_eq!(comma.parse("1,5*2"), Ok(3.));
+        assert_eq!(comma.parse_vec3("1; 2,5; 3"), Ok(Vec3::new(1., 2.5, 3.)));
+        assert_eq!(comma.format(1.5, 2), "1,50");
+        assert_eq!(
+            comma.format_vec3(Vec3::new(1., 2.5, 3.), 1),
+            "1,0; 2,5; 3,0"
+        );
+    }
+}
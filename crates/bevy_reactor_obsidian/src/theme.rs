@@ -0,0 +1,505 @@
+use std::fmt;
+
+use bevy::{color::Srgba, prelude::*, ui::experimental::GhostNode};
+use bevy_reactor_builder::{CreateChilden, UiBuilder};
+use bevy_reactor_signals::{IntoSignal, Rcx, Signal};
+
+use crate::colors::{self, contrast_ratio, relative_luminance, CONTRAST_LARGE, CONTRAST_TEXT};
+
+/// The set of color tokens used by the controls. The `Theme` resource holds the app-wide
+/// theme; a subtree can override some of the tokens with
+/// [`provide_theme`](ProvideTheme::provide_theme), which places a `Theme` component on an
+/// ancestor of the controls. Controls look up their theme with [`UseTheme::use_theme`].
+#[derive(Component, Resource, Clone, Debug, PartialEq)]
+pub struct Theme {
+    /// Color of panel and window backgrounds.
+    pub background: Srgba,
+
+    /// Color of recessed surfaces, such as text fields and unchecked checkboxes.
+    pub surface: Srgba,
+
+    /// Background color of buttons and similar controls.
+    pub control: Srgba,
+
+    /// Background color of selected buttons.
+    pub control_selected: Srgba,
+
+    /// Default text color.
+    pub foreground: Srgba,
+
+    /// Color of secondary text.
+    pub dim: Srgba,
+
+    /// Highlight color, for checked checkboxes and headings.
+    pub accent: Srgba,
+
+    /// Background color of primary buttons.
+    pub primary: Srgba,
+
+    /// Background color of buttons which perform destructive actions.
+    pub destructive: Srgba,
+
+    /// Color of the focus outline.
+    pub focus: Srgba,
+
+    /// Background color of selected text.
+    pub text_select: Srgba,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            background: colors::BACKGROUND,
+            surface: colors::U1,
+            control: colors::U3,
+            control_selected: colors::U4,
+            foreground: colors::FOREGROUND,
+            dim: colors::DIM,
+            accent: colors::ACCENT,
+            primary: colors::PRIMARY,
+            destructive: colors::DESTRUCTIVE,
+            focus: colors::FOCUS,
+            text_select: colors::TEXT_SELECT,
+        }
+    }
+}
+
+impl Theme {
+    /// A light theme, with dark text on pale surfaces.
+    pub fn light() -> Self {
+        Self {
+            background: Srgba::new(0.945, 0.945, 0.953, 1.0),
+            surface: Srgba::new(1.0, 1.0, 1.0, 1.0),
+            control: Srgba::new(0.839, 0.839, 0.859, 1.0),
+            control_selected: Srgba::new(0.698, 0.698, 0.737, 1.0),
+            foreground: Srgba::new(0.102, 0.102, 0.118, 1.0),
+            dim: Srgba::new(0.35, 0.35, 0.38, 1.0),
+            ..Self::default()
+        }
+        .with_accent_palette(AccentPalette::Standard)
+    }
+
+    /// Whether this theme has a dark background.
+    pub fn is_dark(&self) -> bool {
+        relative_luminance(self.background) < 0.5
+    }
+
+    /// Replace the accent, button and selection colors with those of `palette`, using the
+    /// variant which suits the brightness of this theme.
+    pub fn with_accent_palette(mut self, palette: AccentPalette) -> Self {
+        let (accent, primary, destructive) = palette.colors(self.is_dark());
+        self.accent = accent;
+        self.primary = primary;
+        self.destructive = destructive;
+        self.focus = accent.with_alpha(0.15);
+        self.text_select = accent.with_alpha(0.5);
+        self
+    }
+
+    /// Find pairs of tokens whose contrast is below the WCAG minimum. Text is checked against
+    /// every color it is drawn on; the accent only needs to stand out from the surface of
+    /// the controls it fills.
+    pub fn contrast_issues(&self) -> Vec<ContrastIssue> {
+        let pairs = [
+            (
+                "foreground",
+                self.foreground,
+                "background",
+                self.background,
+                CONTRAST_TEXT,
+            ),
+            (
+                "foreground",
+                self.foreground,
+                "surface",
+                self.surface,
+                CONTRAST_TEXT,
+            ),
+            (
+                "foreground",
+                self.foreground,
+                "control",
+                self.control,
+                CONTRAST_TEXT,
+            ),
+            (
+                "dim",
+                self.dim,
+                "background",
+                self.background,
+                CONTRAST_TEXT,
+            ),
+            (
+                "foreground",
+                self.foreground,
+                "primary",
+                self.primary,
+                CONTRAST_LARGE,
+            ),
+            (
+                "foreground",
+                self.foreground,
+                "destructive",
+                self.destructive,
+                CONTRAST_LARGE,
+            ),
+            (
+                "accent",
+                self.accent,
+                "surface",
+                self.surface,
+                CONTRAST_LARGE,
+            ),
+        ];
+        pairs
+            .into_iter()
+            .filter_map(|(fg_name, fg, bg_name, bg, required)| {
+                let ratio = contrast_ratio(fg, bg);
+                (ratio < required).then_some(ContrastIssue {
+                    foreground: fg_name,
+                    background: bg_name,
+                    ratio,
+                    required,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Alternative sets of accent colors. The color-blind safe palettes are drawn from the
+/// Okabe-Ito palette, and avoid pairs of hues which are confused with the named kind of
+/// color blindness.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AccentPalette {
+    /// The standard Obsidian colors.
+    #[default]
+    Standard,
+    /// Blues and orange, for deuteranopia and protanopia.
+    RedGreenSafe,
+    /// Purple, green and vermillion, for tritanopia.
+    BlueYellowSafe,
+}
+
+impl AccentPalette {
+    /// The accent, primary and destructive colors of this palette, for a dark or light theme.
+    pub fn colors(self, dark: bool) -> (Srgba, Srgba, Srgba) {
+        match (self, dark) {
+            (AccentPalette::Standard, true) => {
+                (colors::ACCENT, colors::PRIMARY, colors::DESTRUCTIVE)
+            }
+            (AccentPalette::Standard, false) => (
+                Srgba::new(0.0, 0.447, 0.698, 1.0),
+                colors::PRIMARY_ACC,
+                colors::DESTRUCTIVE_ACC,
+            ),
+            (AccentPalette::RedGreenSafe, true) => (
+                Srgba::new(0.337, 0.706, 0.914, 1.0),
+                Srgba::new(0.0, 0.447, 0.698, 1.0),
+                Srgba::new(0.835, 0.369, 0.0, 1.0),
+            ),
+            (AccentPalette::RedGreenSafe, false) => (
+                Srgba::new(0.0, 0.447, 0.698, 1.0),
+                Srgba::new(0.337, 0.706, 0.914, 1.0),
+                Srgba::new(0.902, 0.624, 0.0, 1.0),
+            ),
+            (AccentPalette::BlueYellowSafe, true) => (
+                Srgba::new(0.8, 0.475, 0.655, 1.0),
+                Srgba::new(0.0, 0.5, 0.365, 1.0),
+                Srgba::new(0.835, 0.369, 0.0, 1.0),
+            ),
+            (AccentPalette::BlueYellowSafe, false) => (
+                Srgba::new(0.6, 0.25, 0.45, 1.0),
+                Srgba::new(0.0, 0.62, 0.451, 1.0),
+                Srgba::new(0.902, 0.5, 0.3, 1.0),
+            ),
+        }
+    }
+}
+
+/// A pair of theme tokens whose contrast is below the required ratio.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContrastIssue {
+    /// Name of the token drawn in front.
+    pub foreground: &'static str,
+    /// Name of the token drawn behind.
+    pub background: &'static str,
+    /// The actual contrast ratio.
+    pub ratio: f32,
+    /// The minimum contrast ratio for this pair.
+    pub required: f32,
+}
+
+impl fmt::Display for ContrastIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "contrast of {} on {} is {:.2}:1, below the minimum of {:.1}:1",
+            self.foreground, self.background, self.ratio, self.required
+        )
+    }
+}
+
+/// Warn about low contrast in the `Theme` resource, and in themes provided to parts of the UI,
+/// whenever they change. Only runs in debug builds.
+#[cfg(debug_assertions)]
+pub(crate) fn warn_low_contrast(
+    theme: Res<Theme>,
+    q_provided: Query<(Entity, &Theme), Changed<Theme>>,
+) {
+    if theme.is_changed() {
+        for issue in theme.contrast_issues() {
+            warn!("Theme: {}", issue);
+        }
+    }
+    for (entity, theme) in q_provided.iter() {
+        for issue in theme.contrast_issues() {
+            warn!("Theme provided by {}: {}", entity, issue);
+        }
+    }
+}
+
+/// A partial [`Theme`]: only the tokens which are set replace those of the enclosing theme.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ThemePatch {
+    /// Accent palette to apply before the individual color overrides.
+    pub accent_palette: Option<AccentPalette>,
+    /// Override for [`Theme::background`].
+    pub background: Option<Srgba>,
+    /// Override for [`Theme::surface`].
+    pub surface: Option<Srgba>,
+    /// Override for [`Theme::control`].
+    pub control: Option<Srgba>,
+    /// Override for [`Theme::control_selected`].
+    pub control_selected: Option<Srgba>,
+    /// Override for [`Theme::foreground`].
+    pub foreground: Option<Srgba>,
+    /// Override for [`Theme::dim`].
+    pub dim: Option<Srgba>,
+    /// Override for [`Theme::accent`].
+    pub accent: Option<Srgba>,
+    /// Override for [`Theme::primary`].
+    pub primary: Option<Srgba>,
+    /// Override for [`Theme::destructive`].
+    pub destructive: Option<Srgba>,
+    /// Override for [`Theme::focus`].
+    pub focus: Option<Srgba>,
+    /// Override for [`Theme::text_select`].
+    pub text_select: Option<Srgba>,
+}
+
+impl ThemePatch {
+    /// Create an empty patch, which leaves the enclosing theme unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A patch which replaces every token with those of `theme`.
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            accent_palette: None,
+            background: Some(theme.background),
+            surface: Some(theme.surface),
+            control: Some(theme.control),
+            control_selected: Some(theme.control_selected),
+            foreground: Some(theme.foreground),
+            dim: Some(theme.dim),
+            accent: Some(theme.accent),
+            primary: Some(theme.primary),
+            destructive: Some(theme.destructive),
+            focus: Some(theme.focus),
+            text_select: Some(theme.text_select),
+        }
+    }
+
+    /// Switch to a different accent palette. Colors set individually take precedence.
+    pub fn accent_palette(mut self, palette: AccentPalette) -> Self {
+        self.accent_palette = Some(palette);
+        self
+    }
+
+    /// Override the background color.
+    pub fn background(mut self, color: impl Into<Srgba>) -> Self {
+        self.background = Some(color.into());
+        self
+    }
+
+    /// Override the surface color.
+    pub fn surface(mut self, color: impl Into<Srgba>) -> Self {
+        self.surface = Some(color.into());
+        self
+    }
+
+    /// Override the control background color.
+    pub fn control(mut self, color: impl Into<Srgba>) -> Self {
+        self.control = Some(color.into());
+        self
+    }
+
+    /// Override the selected control background color.
+    pub fn control_selected(mut self, color: impl Into<Srgba>) -> Self {
+        self.control_selected = Some(color.into());
+        self
+    }
+
+    /// Override the text color.
+    pub fn foreground(mut self, color: impl Into<Srgba>) -> Self {
+        self.foreground = Some(color.into());
+        self
+    }
+
+    /// Override the secondary text color.
+    pub fn dim(mut self, color: impl Into<Srgba>) -> Self {
+        self.dim = Some(color.into());
+        self
+    }
+
+    /// Override the accent color.
+    pub fn accent(mut self, color: impl Into<Srgba>) -> Self {
+        self.accent = Some(color.into());
+        self
+    }
+
+    /// Override the primary button color.
+    pub fn primary(mut self, color: impl Into<Srgba>) -> Self {
+        self.primary = Some(color.into());
+        self
+    }
+
+    /// Override the destructive button color.
+    pub fn destructive(mut self, color: impl Into<Srgba>) -> Self {
+        self.destructive = Some(color.into());
+        self
+    }
+
+    /// Override the focus outline color.
+    pub fn focus(mut self, color: impl Into<Srgba>) -> Self {
+        self.focus = Some(color.into());
+        self
+    }
+
+    /// Override the text selection color.
+    pub fn text_select(mut self, color: impl Into<Srgba>) -> Self {
+        self.text_select = Some(color.into());
+        self
+    }
+
+    /// Return a copy of `theme` with the tokens of this patch applied.
+    pub fn apply(&self, theme: &Theme) -> Theme {
+        let theme = match self.accent_palette {
+            Some(palette) => theme.clone().with_accent_palette(palette),
+            None => theme.clone(),
+        };
+        Theme {
+            background: self.background.unwrap_or(theme.background),
+            surface: self.surface.unwrap_or(theme.surface),
+            control: self.control.unwrap_or(theme.control),
+            control_selected: self.control_selected.unwrap_or(theme.control_selected),
+            foreground: self.foreground.unwrap_or(theme.foreground),
+            dim: self.dim.unwrap_or(theme.dim),
+            accent: self.accent.unwrap_or(theme.accent),
+            primary: self.primary.unwrap_or(theme.primary),
+            destructive: self.destructive.unwrap_or(theme.destructive),
+            focus: self.focus.unwrap_or(theme.focus),
+            text_select: self.text_select.unwrap_or(theme.text_select),
+        }
+    }
+}
+
+/// Extension trait which overrides theme tokens for part of the UI.
+pub trait ProvideTheme {
+    /// Build `children` with the tokens of `patch` overriding those of the enclosing theme.
+    /// The patch may be a signal; controls in the subtree restyle when either the patch or
+    /// the enclosing theme changes. Theme providers can be nested.
+    fn provide_theme<F: FnOnce(&mut UiBuilder)>(
+        &mut self,
+        patch: impl IntoSignal<ThemePatch>,
+        children: F,
+    ) -> &mut Self;
+}
+
+impl<'w> ProvideTheme for UiBuilder<'w> {
+    fn provide_theme<F: FnOnce(&mut UiBuilder)>(
+        &mut self,
+        patch: impl IntoSignal<ThemePatch>,
+        children: F,
+    ) -> &mut Self {
+        let patch: Signal<ThemePatch> = patch.into_signal();
+        let mut provider = self.spawn((Name::new("ThemeProvider"), GhostNode::default()));
+        let provider_id = provider.id();
+        provider.create_children(|builder| {
+            // The theme must be in place before the children are built, so that their styles
+            // see it on the first run.
+            builder.create_effect(move |ecx| {
+                // Start the search above the provider, so that we don't find our own theme.
+                let mut outer = None;
+                let mut entity = ecx.world().get::<Parent>(provider_id).map(|p| p.get());
+                while let Some(e) = entity {
+                    outer = ecx.use_component_map(e, |theme: &Theme| theme.clone());
+                    if outer.is_some() {
+                        break;
+                    }
+                    entity = ecx.world().get::<Parent>(e).map(|p| p.get());
+                }
+                let outer = outer.unwrap_or_else(|| ecx.use_resource_map(Theme::clone));
+                let theme = patch.get_clone(ecx).apply(&outer);
+                let mut provider = ecx.world_mut().entity_mut(provider_id);
+                if provider.get::<Theme>() != Some(&theme) {
+                    provider.insert(theme);
+                }
+            });
+            children(builder);
+        });
+        self
+    }
+}
+
+/// Extension trait for reading the theme within a reactive context.
+pub trait UseTheme {
+    /// Return the theme in effect for the owner of this context: the nearest theme provided
+    /// by an ancestor, or the `Theme` resource if there is none.
+    fn use_theme(&self) -> Theme;
+}
+
+impl<'p, 'w> UseTheme for Rcx<'p, 'w> {
+    fn use_theme(&self) -> Theme {
+        match self.use_inherited_component::<Theme>() {
+            Some(theme) => theme.clone(),
+            None => self.read_resource::<Theme>().clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contrast_issues() {
+        let theme = Theme {
+            background: Srgba::BLACK,
+            surface: Srgba::BLACK,
+            control: Srgba::BLACK,
+            foreground: Srgba::WHITE,
+            dim: Srgba::WHITE,
+            accent: Srgba::WHITE,
+            primary: Srgba::BLACK,
+            destructive: Srgba::BLACK,
+            ..Theme::default()
+        };
+        assert_eq!(theme.contrast_issues(), Vec::new());
+
+        let theme = Theme {
+            dim: Srgba::BLACK,
+            ..theme
+        };
+        assert_eq!(
+            theme.contrast_issues(),
+            vec![ContrastIssue {
+                foreground: "dim",
+                background: "background",
+                ratio: 1.,
+                required: CONTRAST_TEXT,
+            }]
+        );
+    }
+}
@@ -0,0 +1,265 @@
+use std::collections::VecDeque;
+
+use bevy::{prelude::*, render::view::RenderLayers};
+use bevy_reactor_builder::UiBuilder;
+use bevy_reactor_signals::Signal;
+
+use crate::{
+    colors,
+    controls::mesh_preview::{create_render_target, spawn_preview_rig, PreviewRig},
+};
+
+/// Number of frames to keep a preview camera rendering once its assets are ready. The render
+/// world lags the main world by a frame, so one frame is not enough.
+const RENDER_FRAMES: u32 = 3;
+
+/// Number of frames to wait for a job's assets to load before giving up on it.
+const MAX_WAIT_FRAMES: u32 = 600;
+
+/// An asset to generate a thumbnail for.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ThumbnailSource {
+    /// A mesh, rendered with the given material.
+    Mesh(Handle<Mesh>, Handle<StandardMaterial>),
+    /// A scene, such as a glTF model.
+    Scene(Handle<Scene>),
+    /// An image, which is used as its own thumbnail.
+    Image(Handle<Image>),
+}
+
+/// Identifies a thumbnail in the cache.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum ThumbnailKey {
+    Mesh(AssetId<Mesh>, AssetId<StandardMaterial>),
+    Scene(AssetId<Scene>),
+    Image(AssetId<Image>),
+}
+
+impl ThumbnailSource {
+    fn key(&self) -> ThumbnailKey {
+        match self {
+            ThumbnailSource::Mesh(mesh, material) => ThumbnailKey::Mesh(mesh.id(), material.id()),
+            ThumbnailSource::Scene(scene) => ThumbnailKey::Scene(scene.id()),
+            ThumbnailSource::Image(image) => ThumbnailKey::Image(image.id()),
+        }
+    }
+}
+
+/// Generates thumbnails for assets by rendering them offscreen, and keeps the most recently
+/// used ones in a cache. Use [`CreateThumbnailSignal::create_thumbnail_signal`] to display a
+/// thumbnail, rather than calling this directly.
+#[derive(Resource)]
+pub struct ThumbnailService {
+    /// Size of generated thumbnails, in pixels.
+    pub size: UVec2,
+
+    /// Maximum number of thumbnails to keep. This should be larger than the number of
+    /// thumbnails which are visible at once, otherwise they will be regenerated continually.
+    pub capacity: usize,
+
+    /// Maximum number of thumbnails to render at the same time.
+    pub max_active: usize,
+
+    /// Generated thumbnails, most recently used first.
+    cache: VecDeque<(ThumbnailKey, Handle<Image>)>,
+
+    /// Requests which have not been started yet.
+    pending: VecDeque<ThumbnailSource>,
+
+    /// Requests which are currently rendering.
+    active: Vec<ThumbnailKey>,
+}
+
+impl Default for ThumbnailService {
+    fn default() -> Self {
+        Self {
+            size: UVec2::splat(96),
+            capacity: 256,
+            max_active: 2,
+            cache: VecDeque::new(),
+            pending: VecDeque::new(),
+            active: Vec::new(),
+        }
+    }
+}
+
+impl ThumbnailService {
+    /// Returns the thumbnail for a source, if it has been generated.
+    pub fn get(&self, source: &ThumbnailSource) -> Option<Handle<Image>> {
+        let key = source.key();
+        self.cache
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, image)| image.clone())
+    }
+
+    /// Request a thumbnail for a source. If it is already cached it is marked as recently used,
+    /// otherwise it is queued for rendering.
+    pub fn request(&mut self, source: ThumbnailSource) {
+        let key = source.key();
+        if let Some(index) = self.cache.iter().position(|(k, _)| *k == key) {
+            let entry = self.cache.remove(index).unwrap();
+            self.cache.push_front(entry);
+            return;
+        }
+        if let ThumbnailSource::Image(image) = source {
+            self.insert(key, image);
+            return;
+        }
+        if !self.active.contains(&key) && !self.pending.iter().any(|s| s.key() == key) {
+            self.pending.push_back(source);
+        }
+    }
+
+    /// Remove all cached thumbnails, for example after assets have been reloaded.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    fn insert(&mut self, key: ThumbnailKey, image: Handle<Image>) {
+        self.cache.push_front((key, image));
+        self.cache.truncate(self.capacity);
+    }
+}
+
+/// A thumbnail which is being rendered.
+#[derive(Component)]
+pub(crate) struct ThumbnailJob {
+    key: ThumbnailKey,
+    target: Handle<Image>,
+    /// Frames left to render once the assets are ready.
+    frames: u32,
+    /// Frames spent waiting for the assets to load.
+    waited: u32,
+}
+
+/// Start pending thumbnail jobs, and finish the ones which have rendered.
+pub(crate) fn run_thumbnail_jobs(world: &mut World) {
+    // Finish or advance the active jobs.
+    let mut q_jobs = world.query::<(Entity, &ThumbnailJob, &PreviewRig)>();
+    let jobs: Vec<(Entity, ThumbnailKey, Handle<Image>, u32, u32, Entity)> = q_jobs
+        .iter(world)
+        .map(|(e, job, rig)| {
+            let target = job.target.clone();
+            (e, job.key, target, job.frames, job.waited, rig.model)
+        })
+        .collect();
+    for (entity, key, target, frames, waited, model) in jobs {
+        if frames == 0 || waited >= MAX_WAIT_FRAMES {
+            // Despawning the job removes the rig, which stops the camera.
+            world.entity_mut(entity).despawn();
+            let mut service = world.resource_mut::<ThumbnailService>();
+            service.active.retain(|k| *k != key);
+            if frames == 0 {
+                service.insert(key, target);
+            } else {
+                warn!("Timed out generating thumbnail for {:?}", key);
+            }
+        } else if assets_ready(world, key, model) {
+            world.get_mut::<ThumbnailJob>(entity).unwrap().frames -= 1;
+        } else {
+            world.get_mut::<ThumbnailJob>(entity).unwrap().waited += 1;
+        }
+    }
+
+    // Start new jobs.
+    loop {
+        let mut service = world.resource_mut::<ThumbnailService>();
+        if service.active.len() >= service.max_active {
+            break;
+        }
+        let Some(source) = service.pending.pop_front() else {
+            break;
+        };
+        let key = source.key();
+        service.active.push(key);
+        let size = service.size;
+
+        let target = create_render_target(world, size);
+        let rig = spawn_preview_rig(world, target.clone(), colors::U1.into());
+        let model = rig.model;
+        match source {
+            ThumbnailSource::Mesh(mesh, material) => {
+                world
+                    .entity_mut(model)
+                    .insert((Mesh3d(mesh), MeshMaterial3d(material)));
+            }
+            ThumbnailSource::Scene(scene) => {
+                world.entity_mut(model).insert(SceneRoot(scene));
+            }
+            ThumbnailSource::Image(_) => unreachable!("images are not rendered"),
+        }
+        world.spawn((
+            ThumbnailJob {
+                key,
+                target,
+                frames: RENDER_FRAMES,
+                waited: 0,
+            },
+            rig,
+            Name::new("ThumbnailJob"),
+        ));
+    }
+}
+
+/// Returns true when the assets for a job have loaded. For scenes, this also moves the spawned
+/// scene entities onto the rig's render layer.
+fn assets_ready(world: &mut World, key: ThumbnailKey, model: Entity) -> bool {
+    match key {
+        ThumbnailKey::Mesh(mesh, material) => {
+            world.resource::<Assets<Mesh>>().contains(mesh)
+                && world
+                    .resource::<Assets<StandardMaterial>>()
+                    .contains(material)
+        }
+        ThumbnailKey::Scene(_) => {
+            let Some(layers) = world.get::<RenderLayers>(model).cloned() else {
+                return false;
+            };
+            let mut descendants = Vec::new();
+            let mut stack = vec![model];
+            while let Some(entity) = stack.pop() {
+                if let Some(children) = world.get::<Children>(entity) {
+                    stack.extend(children.iter().copied());
+                    descendants.extend(children.iter().copied());
+                }
+            }
+            if descendants.is_empty() {
+                return false;
+            }
+            for entity in descendants {
+                world.entity_mut(entity).insert(layers.clone());
+            }
+            true
+        }
+        ThumbnailKey::Image(_) => true,
+    }
+}
+
+/// Method to create a signal containing the thumbnail for an asset.
+pub trait CreateThumbnailSignal {
+    /// Signal which contains the thumbnail for `source` once it has been generated. The
+    /// thumbnail is requested again if it is evicted from the cache while the signal is alive.
+    fn create_thumbnail_signal(&mut self, source: ThumbnailSource)
+        -> Signal<Option<Handle<Image>>>;
+}
+
+impl<'w> CreateThumbnailSignal for UiBuilder<'w> {
+    fn create_thumbnail_signal(
+        &mut self,
+        source: ThumbnailSource,
+    ) -> Signal<Option<Handle<Image>>> {
+        let thumbnail = self.create_mutable::<Option<Handle<Image>>>(None);
+        self.create_effect(move |ecx| {
+            let key = source.clone();
+            let image = ecx.use_resource_map(move |service: &ThumbnailService| service.get(&key));
+            if image.is_none() {
+                ecx.world_mut()
+                    .resource_mut::<ThumbnailService>()
+                    .request(source.clone());
+            }
+            thumbnail.set_clone(ecx.world_mut(), image);
+        });
+        thumbnail.signal()
+    }
+}
@@ -1,13 +1,138 @@
-use bevy_mod_stylebuilder::{StyleBuilder, StyleBuilderFont};
+use bevy::prelude::*;
+use bevy_mod_stylebuilder::{StyleBuilder, StyleBuilderFont, UseInheritedTextStyles};
+use bevy_reactor_builder::{EntityStyleBuilder, UiBuilder};
+
+use crate::{colors, size::UiSizing};
+
+const FONT_MEDIUM: &str =
+    "embedded://bevy_reactor_obsidian/assets/fonts/Fira_Sans/FiraSans-Medium.ttf";
+const FONT_BOLD: &str = "embedded://bevy_reactor_obsidian/assets/fonts/Fira_Sans/FiraSans-Bold.ttf";
+const FONT_MONO: &str =
+    "embedded://bevy_reactor_obsidian/assets/fonts/DejaVu_Sans_Mono/DejaVuSansMono.ttf";
 
 /// Default text style for UI.
 pub fn text_default(ss: &mut StyleBuilder) {
-    ss.font("embedded://bevy_reactor_obsidian/assets/fonts/Fira_Sans/FiraSans-Medium.ttf")
-        .font_size(14);
+    ss.font(FONT_MEDIUM).font_size(14);
 }
 
 /// When we need to emphasize a label
 pub fn text_strong(ss: &mut StyleBuilder) {
-    ss.font("embedded://bevy_reactor_obsidian/assets/fonts/Fira_Sans/FiraSans-Bold.ttf")
-        .font_size(14);
+    ss.font(FONT_BOLD).font_size(14);
+}
+
+/// Emphasis for a run of characters within a line of text, such as a search match. Does not
+/// change the font size, so that it can be applied to text spans.
+pub fn text_highlight(ss: &mut StyleBuilder) {
+    ss.font(FONT_BOLD).color(colors::ACCENT);
+}
+
+// The named text styles below use unscaled font sizes; `text_styled` also applies
+// `UiSizing::font_scale`.
+
+/// Top-level heading, such as the title of a panel.
+pub fn heading1(ss: &mut StyleBuilder) {
+    ss.font(FONT_BOLD)
+        .font_size(TextVariant::Heading1.font_size())
+        .color(colors::FOREGROUND);
+}
+
+/// Section heading.
+pub fn heading2(ss: &mut StyleBuilder) {
+    ss.font(FONT_BOLD)
+        .font_size(TextVariant::Heading2.font_size())
+        .color(colors::FOREGROUND);
+}
+
+/// Sub-section heading.
+pub fn heading3(ss: &mut StyleBuilder) {
+    ss.font(FONT_BOLD)
+        .font_size(TextVariant::Heading3.font_size())
+        .color(colors::FOREGROUND);
+}
+
+/// Body text.
+pub fn body(ss: &mut StyleBuilder) {
+    ss.font(FONT_MEDIUM)
+        .font_size(TextVariant::Body.font_size())
+        .color(colors::FOREGROUND);
+}
+
+/// Small, de-emphasized text, such as hints and annotations.
+pub fn caption(ss: &mut StyleBuilder) {
+    ss.font(FONT_MEDIUM)
+        .font_size(TextVariant::Caption.font_size())
+        .color(colors::DIM);
+}
+
+/// Monospaced text, for code and for values in consoles and inspectors.
+pub fn code(ss: &mut StyleBuilder) {
+    ss.font(FONT_MONO)
+        .font_size(TextVariant::Code.font_size())
+        .color(colors::FOREGROUND);
+}
+
+/// Identifies one of the named text styles, for use with `text_styled`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[allow(missing_docs)]
+pub enum TextVariant {
+    Heading1,
+    Heading2,
+    Heading3,
+    #[default]
+    Body,
+    Caption,
+    Code,
+}
+
+impl TextVariant {
+    /// Returns the style function for this text style.
+    pub fn style(&self) -> fn(&mut StyleBuilder) {
+        match self {
+            TextVariant::Heading1 => heading1,
+            TextVariant::Heading2 => heading2,
+            TextVariant::Heading3 => heading3,
+            TextVariant::Body => body,
+            TextVariant::Caption => caption,
+            TextVariant::Code => code,
+        }
+    }
+
+    /// Returns the font size of this text style, before applying [`UiSizing::font_scale`].
+    pub fn font_size(&self) -> f32 {
+        match self {
+            TextVariant::Heading1 => 22.,
+            TextVariant::Heading2 => 18.,
+            TextVariant::Heading3 => 15.,
+            TextVariant::Body => 14.,
+            TextVariant::Caption => 12.,
+            TextVariant::Code => 13.,
+        }
+    }
+}
+
+/// Extension for creating text in one of the named text styles.
+pub trait StyledTextBuilder {
+    /// Create a static text entity using the given text style. The font size follows
+    /// [`UiSizing::font_scale`].
+    fn text_styled(&mut self, style: TextVariant, s: impl Into<String>) -> &mut Self;
+}
+
+impl<'w> StyledTextBuilder for UiBuilder<'w> {
+    fn text_styled(&mut self, style: TextVariant, s: impl Into<String>) -> &mut Self {
+        self.spawn((
+            Name::new("TextStyled"),
+            TextLayout::default(),
+            Text(s.into()),
+        ))
+        .style(style.style())
+        .style_dyn(
+            |rcx| rcx.read_resource::<UiSizing>().font_scale,
+            move |font_scale, sb| {
+                sb.font_size(style.font_size() * font_scale);
+            },
+        )
+        // Inserted after the style so that the initial computed style includes it.
+        .insert(UseInheritedTextStyles);
+        self
+    }
 }
@@ -1,10 +1,20 @@
+mod measure;
 mod mesh_builder;
 mod overlay;
 mod overlay_material;
+mod picking;
 mod shape_builder;
 
-use bevy::{app::Plugin, asset::embedded_asset, pbr::MaterialPlugin};
+use bevy::{
+    app::{Plugin, PreUpdate, Update},
+    asset::embedded_asset,
+    pbr::MaterialPlugin,
+    prelude::IntoSystemConfigs,
+};
+use bevy_mod_picking::picking_core::PickSet;
+pub use measure::{MeasureLabel, MeasureMode, MeasureTool, RulerOverlay};
 pub use overlay::Overlay;
+pub use picking::OverlayPickable;
 pub use shape_builder::{PolygonOptions, ShapeBuilder, StrokeMarker};
 
 use crate::overlay_material::OverlayMaterial;
@@ -20,7 +30,19 @@ impl Plugin for OverlaysPlugin {
         app.add_plugins((
             MaterialPlugin::<OverlayMaterial>::default(),
             MaterialPlugin::<UnderlayMaterial>::default(),
-        ));
+        ))
+        .init_resource::<MeasureTool>()
+        .init_resource::<RulerOverlay>()
+        .add_systems(PreUpdate, picking::update_hits.in_set(PickSet::Backend))
+        .add_systems(
+            Update,
+            (
+                (measure::pick_measure_points, measure::draw_measurements).chain(),
+                measure::update_measure_label,
+                measure::draw_ruler_overlay,
+            ),
+        )
+        .register_type::<OverlayPickable>();
     }
 }
 
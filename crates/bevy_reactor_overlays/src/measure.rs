@@ -0,0 +1,304 @@
+use bevy::{color::palettes::css, prelude::*};
+use bevy_mod_picking::{
+    events::{Click, Pointer},
+    pointer::PointerButton,
+};
+
+/// Which measurement tool is active.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MeasureMode {
+    /// No measurement; clicks are ignored.
+    #[default]
+    Off,
+    /// Measure the distance between two picked points.
+    Distance,
+    /// Measure the angle at the second of three picked points.
+    Angle,
+}
+
+impl MeasureMode {
+    /// The number of points needed to make a measurement in this mode.
+    pub fn point_count(self) -> usize {
+        match self {
+            MeasureMode::Off => 0,
+            MeasureMode::Distance => 2,
+            MeasureMode::Angle => 3,
+        }
+    }
+}
+
+/// State of the viewport measurement tools. While a mode other than [`MeasureMode::Off`] is
+/// active, clicking on a pickable object adds the hit position as a measurement point; once
+/// enough points have been picked, the measurement is drawn with a label showing its value,
+/// and the next click starts a new measurement.
+#[derive(Resource, Clone, Debug)]
+pub struct MeasureTool {
+    /// The active measurement tool.
+    pub mode: MeasureMode,
+
+    /// The points picked so far, in world space.
+    pub points: Vec<Vec3>,
+
+    /// Color of the measurement lines.
+    pub color: Color,
+
+    /// Number of decimal places shown in distance labels.
+    pub precision: usize,
+}
+
+impl Default for MeasureTool {
+    fn default() -> Self {
+        Self {
+            mode: MeasureMode::Off,
+            points: Vec::new(),
+            color: css::YELLOW.into(),
+            precision: 2,
+        }
+    }
+}
+
+impl MeasureTool {
+    /// Switch to a different tool, discarding any points picked so far.
+    pub fn set_mode(&mut self, mode: MeasureMode) {
+        self.mode = mode;
+        self.points.clear();
+    }
+
+    /// Add a picked point, starting a new measurement if the current one is complete.
+    pub fn add_point(&mut self, point: Vec3) {
+        let count = self.mode.point_count();
+        if count == 0 {
+            return;
+        }
+        if self.points.len() >= count {
+            self.points.clear();
+        }
+        self.points.push(point);
+    }
+
+    /// The measured distance, if a distance measurement is complete.
+    pub fn distance(&self) -> Option<f32> {
+        match (self.mode, self.points.as_slice()) {
+            (MeasureMode::Distance, [a, b]) => Some(a.distance(*b)),
+            _ => None,
+        }
+    }
+
+    /// The measured angle, in radians, if an angle measurement is complete.
+    pub fn angle(&self) -> Option<f32> {
+        match (self.mode, self.points.as_slice()) {
+            (MeasureMode::Angle, [a, vertex, b]) => {
+                Some((*a - *vertex).angle_between(*b - *vertex))
+            }
+            _ => None,
+        }
+    }
+
+    /// The text of the measurement label, and the world position to show it at.
+    fn label(&self) -> Option<(String, Vec3)> {
+        if let Some(distance) = self.distance() {
+            let midpoint = self.points[0].lerp(self.points[1], 0.5);
+            return Some((format!("{:.*}", self.precision, distance), midpoint));
+        }
+        self.angle()
+            .map(|angle| (format!("{:.1}°", angle.to_degrees()), self.points[1]))
+    }
+}
+
+/// Marks the UI text node which shows the value of the current measurement.
+#[derive(Component)]
+pub struct MeasureLabel;
+
+/// Add the hit positions of clicks to the active measurement.
+pub(crate) fn pick_measure_points(
+    mut events: EventReader<Pointer<Click>>,
+    mut tool: ResMut<MeasureTool>,
+) {
+    if tool.mode == MeasureMode::Off {
+        events.clear();
+        return;
+    }
+    for event in events.read() {
+        if event.button != PointerButton::Primary {
+            continue;
+        }
+        if let Some(position) = event.hit.position {
+            tool.add_point(position);
+        }
+    }
+}
+
+/// Draw the points and lines of the current measurement.
+pub(crate) fn draw_measurements(tool: Res<MeasureTool>, mut gizmos: Gizmos) {
+    if tool.mode == MeasureMode::Off {
+        return;
+    }
+    for point in tool.points.iter() {
+        gizmos.sphere(Isometry3d::from_translation(*point), 0.03, tool.color);
+    }
+    match (tool.mode, tool.points.as_slice()) {
+        (MeasureMode::Distance, [a, b]) => {
+            gizmos.line(*a, *b, tool.color);
+        }
+        (MeasureMode::Angle, [a, vertex]) => {
+            gizmos.line(*a, *vertex, tool.color);
+        }
+        (MeasureMode::Angle, [a, vertex, b]) => {
+            gizmos.line(*a, *vertex, tool.color);
+            gizmos.line(*vertex, *b, tool.color);
+            // Draw the arc at a fraction of the shorter arm, so that it stays inside the angle.
+            let radius = 0.3 * a.distance(*vertex).min(b.distance(*vertex));
+            let from = *vertex + (*a - *vertex).normalize_or_zero() * radius;
+            let to = *vertex + (*b - *vertex).normalize_or_zero() * radius;
+            gizmos.short_arc_3d_between(*vertex, from, to, tool.color);
+        }
+        _ => {}
+    }
+}
+
+/// Keep the measurement label positioned over the measurement, spawning it if needed.
+pub(crate) fn update_measure_label(
+    mut commands: Commands,
+    tool: Res<MeasureTool>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    mut q_label: Query<(&mut Node, &mut Text, &mut Visibility), With<MeasureLabel>>,
+) {
+    let label = tool.label().and_then(|(text, position)| {
+        cameras
+            .iter()
+            .filter(|(camera, _)| camera.is_active)
+            .find_map(|(camera, transform)| camera.world_to_viewport(transform, position).ok())
+            .map(|screen| (text, screen))
+    });
+
+    let Ok((mut node, mut text, mut visibility)) = q_label.get_single_mut() else {
+        if label.is_some() {
+            commands.spawn((
+                Name::new("MeasureLabel"),
+                MeasureLabel,
+                Text::default(),
+                Node {
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                BackgroundColor(Color::BLACK.with_alpha(0.6)),
+                Visibility::Hidden,
+            ));
+        }
+        return;
+    };
+
+    match label {
+        Some((value, screen)) => {
+            if text.0 != value {
+                text.0 = value;
+            }
+            node.left = Val::Px(screen.x + 8.);
+            node.top = Val::Px(screen.y - 8.);
+            visibility.set_if_neq(Visibility::Inherited);
+        }
+        None => {
+            visibility.set_if_neq(Visibility::Hidden);
+        }
+    }
+}
+
+/// A reference grid on the ground plane with ruler ticks along the X and Z axes. The spacing
+/// of the grid adapts to the height of the camera: it is always a power of ten, and as the
+/// camera moves away the finer lines fade out and the next coarser spacing takes over.
+#[derive(Resource, Clone, Debug)]
+pub struct RulerOverlay {
+    /// Whether the grid and rulers are drawn.
+    pub enabled: bool,
+
+    /// Number of grid cells on each side of the center, at the current spacing.
+    pub cells: u32,
+
+    /// Color of the grid lines.
+    pub color: Color,
+}
+
+impl Default for RulerOverlay {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cells: 20,
+            color: Color::srgba(0.5, 0.5, 0.5, 0.5),
+        }
+    }
+}
+
+/// The grid spacing for a camera at the given height, and how far the spacing is towards the
+/// next coarser one, from 0 to 1.
+fn ruler_spacing(height: f32) -> (f32, f32) {
+    let exponent = (height.max(0.01) / 10.).log10();
+    (10f32.powf(exponent.floor()), exponent - exponent.floor())
+}
+
+/// Draw the adaptive grid and axis rulers.
+pub(crate) fn draw_ruler_overlay(
+    ruler: Res<RulerOverlay>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    mut gizmos: Gizmos,
+) {
+    if !ruler.enabled {
+        return;
+    }
+    let Some((_, camera_transform)) = cameras.iter().find(|(camera, _)| camera.is_active) else {
+        return;
+    };
+    let eye = camera_transform.translation();
+    let (spacing, fade) = ruler_spacing(eye.y.abs());
+    let major = spacing * 10.;
+    let alpha = ruler.color.alpha();
+    let rotation = Quat::from_rotation_x(std::f32::consts::FRAC_PI_2);
+
+    // Center the grids under the camera, snapped so that the lines stay fixed in the world.
+    let center = |step: f32| {
+        Vec3::new(
+            (eye.x / step).round() * step,
+            0.,
+            (eye.z / step).round() * step,
+        )
+    };
+    let cells = ruler.cells * 2;
+    gizmos.grid(
+        Isometry3d::new(center(spacing), rotation),
+        UVec2::splat(cells),
+        Vec2::splat(spacing),
+        ruler.color.with_alpha(alpha * (1. - fade)),
+    );
+    gizmos.grid(
+        Isometry3d::new(center(major), rotation),
+        UVec2::splat(cells),
+        Vec2::splat(major),
+        ruler.color,
+    );
+
+    // Rulers along the X and Z axes, with a longer tick every ten.
+    let extent = major * ruler.cells as f32;
+    for (axis, across, color) in [
+        (Vec3::X, Vec3::Z, Color::from(css::RED)),
+        (Vec3::Z, Vec3::X, Color::from(css::BLUE)),
+    ] {
+        let along = eye.dot(axis);
+        gizmos.line(axis * (along - extent), axis * (along + extent), color);
+        let first = ((along - extent) / spacing).ceil() as i64;
+        let last = ((along + extent) / spacing).floor() as i64;
+        for index in first..=last {
+            let is_major = index % 10 == 0;
+            let length = if is_major {
+                spacing * 0.5
+            } else {
+                spacing * 0.2
+            };
+            let tick_color = if is_major {
+                color
+            } else {
+                color.with_alpha(1. - fade)
+            };
+            let at = axis * (index as f32 * spacing);
+            gizmos.line(at - across * length, at + across * length, tick_color);
+        }
+    }
+}
@@ -1,14 +1,19 @@
+use std::{marker::PhantomData, sync::Arc};
+
 use bevy::{
     color::LinearRgba,
     pbr::{NotShadowCaster, NotShadowReceiver},
     prelude::*,
     render::render_asset::RenderAssetUsages,
 };
-use bevy_mod_picking::{backends::raycast::RaycastPickable, picking_core::Pickable};
+use bevy_mod_picking::picking_core::Pickable;
 use bevy_reactor::*;
-use bevy_reactor_signals::{Rcx, Reaction, Signal, TrackingScope};
+use bevy_reactor_signals::{Rcx, Reaction, ReactionCell, Signal, TrackingScope};
 
-use crate::overlay_material::{OverlayMaterial, UnderlayMaterial};
+use crate::{
+    overlay_material::{OverlayMaterial, UnderlayMaterial},
+    picking::OverlayPickable,
+};
 
 use super::mesh_builder::MeshBuilder;
 
@@ -53,8 +58,11 @@ where
     /// Whether the overlay is pickable.
     pickable: bool,
 
+    /// Extra tolerance around the overlay's geometry when picking.
+    pick_width: f32,
+
     /// Reactive drawing function
-    draw: Box<dyn Fn(&Rcx, &mut SB) + Send + Sync>,
+    draw: Arc<dyn Fn(&Rcx, &mut SB) + Send + Sync>,
     // - blend_mode (signal)
     // - sides
 }
@@ -77,7 +85,8 @@ where
             transform: Signal::Constant(Transform::default()),
             underlay: 0.3,
             pickable: false,
-            draw: Box::new(draw),
+            pick_width: 0.,
+            draw: Arc::new(draw),
         }
     }
 
@@ -95,7 +104,8 @@ where
             transform: Signal::Constant(Transform::default()),
             underlay: 0.3,
             pickable: false,
-            draw: Box::new(draw),
+            pick_width: 0.,
+            draw: Arc::new(draw),
         }
     }
 
@@ -122,12 +132,20 @@ where
         self
     }
 
-    /// Whether this overlay shape should be pickable with `bevy_mod_picking`.
+    /// Whether this overlay shape should be pickable with `bevy_mod_picking`. Picking is
+    /// performed by the overlay picking backend, which tests against the shape's geometry.
     pub fn with_pickable(mut self, pickable: bool) -> Self {
         self.pickable = pickable;
         self
     }
 
+    /// Set the extra distance around the shape's geometry within which it can still be
+    /// picked, in local units. Useful for making thin strokes easier to click on.
+    pub fn with_pick_width(mut self, pick_width: f32) -> Self {
+        self.pick_width = pick_width;
+        self
+    }
+
     /// Set the color for this overlay.
     pub fn with_color(mut self, color: impl Into<LinearRgba>) -> Self {
         self.color = Signal::Constant(color.into());
@@ -232,7 +250,9 @@ where
 
         if self.pickable {
             world.entity_mut(display).insert((
-                RaycastPickable,
+                OverlayPickable {
+                    pick_width: self.pick_width,
+                },
                 Pickable {
                     should_block_lower: true,
                     is_hoverable: true,
@@ -240,33 +260,43 @@ where
             ));
         }
 
-        // Build the overlay mesh the first time.
-        let mut tracking = TrackingScope::new(world.change_tick());
-        self.react(view_entity, world, &mut tracking);
-
-        // Start reactions
-        self.start_reaction(
+        // The geometry, color and transform each have their own reaction, with its own
+        // tracking scope, so that changing the color or transform only updates the material
+        // or the entity, and the mesh is only re-tessellated when a dependency of the drawing
+        // function changes. Each reaction runs at most once per frame, no matter how many of
+        // its dependencies change.
+        spawn_reaction(
+            world,
+            view_entity,
+            "Overlay::Mesh",
+            RebuildMeshReaction {
+                draw: self.draw.clone(),
+                mesh: self.mesh.clone(),
+                marker: PhantomData,
+            },
+        );
+        spawn_reaction(
+            world,
+            view_entity,
+            "Overlay::Color",
             ChangeColorReaction {
                 color: self.color,
                 underlay: self.underlay,
                 material: self.material.clone(),
                 underlay_material: self.underlay_material.clone(),
             },
-            view_entity,
-            display,
-            world,
-            &mut tracking,
         );
-        self.start_reaction(
+        spawn_reaction(
+            world,
+            view_entity,
+            "Overlay::Transform",
             ChangeTransformReaction {
                 mesh: display,
                 transform: self.transform,
             },
-            view_entity,
-            display,
-            world,
-            &mut tracking,
         );
+
+        let mut tracking = TrackingScope::new(world.change_tick());
         for effect in self.effects.iter_mut() {
             effect.start(view_entity, display, world, &mut tracking);
         }
@@ -290,7 +320,7 @@ where
         self.display = None;
 
         // Delete all reactions.
-        world.entity_mut(view_entity).despawn();
+        world.entity_mut(view_entity).despawn_recursive();
     }
 
     fn children_changed(&mut self, _view_entity: Entity, world: &mut World) -> bool {
@@ -299,13 +329,44 @@ where
     }
 }
 
-impl<SB> Reaction for Overlay<SB>
+impl<SB> IntoView for Overlay<SB>
+where
+    SB: MeshBuilder + Default + 'static,
+{
+    fn into_view(self) -> ViewRef {
+        ViewRef::new(self)
+    }
+}
+
+/// Run a reaction for the first time, then spawn an entity, owned by the overlay's view
+/// entity, to hold it and its tracking scope.
+fn spawn_reaction<R: Reaction + Send + Sync + 'static>(
+    world: &mut World,
+    owner: Entity,
+    name: &'static str,
+    mut reaction: R,
+) {
+    let mut tracking = TrackingScope::new(world.change_tick());
+    let reaction_owner = world.spawn(Name::new(name)).set_parent(owner).id();
+    reaction.react(reaction_owner, world, &mut tracking);
+    world
+        .entity_mut(reaction_owner)
+        .insert((tracking, ReactionCell::new(reaction)));
+}
+
+/// Reactive effect which rebuilds the overlay mesh.
+pub struct RebuildMeshReaction<SB> {
+    draw: Arc<dyn Fn(&Rcx, &mut SB) + Send + Sync>,
+    mesh: Handle<Mesh>,
+    marker: PhantomData<fn() -> SB>,
+}
+
+impl<SB> Reaction for RebuildMeshReaction<SB>
 where
     SB: MeshBuilder + Default,
 {
-    fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
-        // Rebuild the overlay mesh.
-        let re = Rcx::new(world, view_entity, tracking);
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let re = Rcx::new(world, owner, tracking);
         let mut builder = SB::default();
         (self.draw)(&re, &mut builder);
         let mut meshes = world.get_resource_mut::<Assets<Mesh>>().unwrap();
@@ -314,15 +375,6 @@ where
     }
 }
 
-impl<SB> IntoView for Overlay<SB>
-where
-    SB: MeshBuilder + Default + 'static,
-{
-    fn into_view(self) -> ViewRef {
-        ViewRef::new(self)
-    }
-}
-
 /// Reactive effect which changes the color of the overlay.
 pub struct ChangeColorReaction {
     color: Signal<LinearRgba>,
@@ -337,6 +389,10 @@ impl Reaction for ChangeColorReaction {
         let mut color = self.color.get(&re);
 
         let mut materials = world.get_resource_mut::<Assets<OverlayMaterial>>().unwrap();
+        // Avoid marking the material as modified, which re-uploads it, if nothing changed.
+        if materials.get(self.material.id()).unwrap().color == color {
+            return;
+        }
         let material = materials.get_mut(self.material.id()).unwrap();
         material.color = color;
 
@@ -362,6 +418,6 @@ impl Reaction for ChangeTransformReaction {
         let next_transform = self.transform.get(&re);
         let mut entt = world.entity_mut(self.mesh);
         let mut transform = entt.get_mut::<Transform>().unwrap();
-        *transform = next_transform;
+        transform.set_if_neq(next_transform);
     }
 }
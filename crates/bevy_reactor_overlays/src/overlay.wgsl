@@ -10,10 +10,16 @@ var<uniform> color: vec4<f32>;
 
 struct Vertex {
     @location(0) position: vec3<f32>,
+#ifdef OVERLAY_EDGE
+    @location(1) edge: f32,
+#endif
 };
 
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
+#ifdef OVERLAY_EDGE
+    @location(0) edge: f32,
+#endif
 };
 
 @vertex
@@ -23,6 +29,9 @@ fn vertex(vertex: Vertex, @builtin(instance_index) instance_index: u32) -> Verte
         mfns::get_model_matrix(instance_index),
         vec4<f32>(vertex.position, 1.0)
     );
+#ifdef OVERLAY_EDGE
+    out.edge = vertex.edge;
+#endif
 
     return out;
 }
@@ -32,5 +41,13 @@ fn fragment(
     @builtin(front_facing) is_front: bool,
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
+#ifdef OVERLAY_EDGE
+    // Fade out the stroke over the last pixel on either side. Strokes narrower than a pixel
+    // never reach full opacity, so their coverage is approximated by their alpha.
+    let d = abs(mesh.edge);
+    let a = clamp((1.0 - d) / max(fwidth(mesh.edge), 0.00001), 0.0, 1.0);
+    return tone_mapping(vec4<f32>(color.rgb, color.a * a), view.color_grading);
+#else
     return tone_mapping(color, view.color_grading);
+#endif
 }
@@ -5,14 +5,38 @@ use bevy::{
     reflect::TypePath,
     render::{
         alpha::AlphaMode,
-        mesh::MeshVertexBufferLayoutRef,
+        mesh::{Mesh, MeshVertexAttribute, MeshVertexBufferLayoutRef},
         render_resource::{
             AsBindGroup, CompareFunction, RenderPipelineDescriptor, ShaderRef,
-            SpecializedMeshPipelineError,
+            SpecializedMeshPipelineError, VertexFormat,
         },
     },
 };
 
+/// Vertex attribute holding the position of each vertex across a stroke, from -1 to 1. Used
+/// by the overlay shader to anti-alias the edges of strokes in hairline mode.
+pub(crate) const ATTRIBUTE_EDGE: MeshVertexAttribute =
+    MeshVertexAttribute::new("OverlayEdge", 988_540_917, VertexFormat::Float32);
+
+/// If the mesh has an edge attribute, enable the shader's anti-aliased edges.
+fn specialize_edge(
+    descriptor: &mut RenderPipelineDescriptor,
+    layout: &MeshVertexBufferLayoutRef,
+) -> Result<(), SpecializedMeshPipelineError> {
+    if layout.0.contains(ATTRIBUTE_EDGE) {
+        let vertex_layout = layout.0.get_layout(&[
+            Mesh::ATTRIBUTE_POSITION.at_shader_location(0),
+            ATTRIBUTE_EDGE.at_shader_location(1),
+        ])?;
+        descriptor.vertex.buffers = vec![vertex_layout];
+        descriptor.vertex.shader_defs.push("OVERLAY_EDGE".into());
+        if let Some(ref mut fragment) = descriptor.fragment {
+            fragment.shader_defs.push("OVERLAY_EDGE".into());
+        }
+    }
+    Ok(())
+}
+
 /// Material for overlays
 #[derive(Debug, Clone, AsBindGroup, Asset, TypePath, Default)]
 pub struct OverlayMaterial {
@@ -44,7 +68,7 @@ impl Material for OverlayMaterial {
             depth_stencil.depth_write_enabled = true;
             depth_stencil.depth_compare = CompareFunction::GreaterEqual;
         }
-        Ok(())
+        specialize_edge(descriptor, layout)
     }
 }
 
@@ -79,6 +103,6 @@ impl Material for UnderlayMaterial {
             depth_stencil.depth_write_enabled = true;
             depth_stencil.depth_compare = CompareFunction::Less;
         }
-        Ok(())
+        specialize_edge(descriptor, layout)
     }
 }
@@ -0,0 +1,148 @@
+use bevy::{
+    prelude::*,
+    render::mesh::{Indices, PrimitiveTopology, VertexAttributeValues},
+};
+use bevy_mod_picking::backend::{ray::RayMap, HitData, PointerHits};
+
+/// Marks an overlay as pickable by the overlay picking backend. Hits are tested against the
+/// actual triangles of the overlay's mesh, rather than its bounding box.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct OverlayPickable {
+    /// Extra distance around the shape, in the overlay's local units, within which a ray still
+    /// counts as a hit. This makes thin strokes easier to pick.
+    pub pick_width: f32,
+}
+
+/// Picking backend system which ray-tests pickable overlays against their mesh geometry.
+pub fn update_hits(
+    ray_map: Res<RayMap>,
+    cameras: Query<&Camera>,
+    overlays: Query<(
+        Entity,
+        &Handle<Mesh>,
+        &GlobalTransform,
+        &OverlayPickable,
+        &ViewVisibility,
+    )>,
+    meshes: Res<Assets<Mesh>>,
+    mut output_events: EventWriter<PointerHits>,
+) {
+    for (&ray_id, ray) in ray_map.map().iter() {
+        let Ok(camera) = cameras.get(ray_id.camera) else {
+            continue;
+        };
+        if !camera.is_active {
+            continue;
+        }
+
+        let mut picks: Vec<(Entity, HitData)> = overlays
+            .iter()
+            .filter(|(.., visibility)| visibility.get())
+            .filter_map(|(entity, mesh, transform, pickable, _)| {
+                let mesh = meshes.get(mesh)?;
+                let (depth, position, normal) =
+                    ray_mesh_hit(*ray, mesh, transform, pickable.pick_width)?;
+                Some((
+                    entity,
+                    HitData::new(ray_id.camera, depth, Some(position), Some(normal)),
+                ))
+            })
+            .collect();
+
+        if !picks.is_empty() {
+            picks.sort_by(|a, b| a.1.depth.total_cmp(&b.1.depth));
+            let order = camera.order as f32;
+            output_events.send(PointerHits::new(ray_id.pointer, picks, order));
+        }
+    }
+}
+
+/// Find the nearest intersection of the ray with the mesh triangles, expanded by half of
+/// `pick_width` in every direction within the plane of each triangle. Returns the distance
+/// along the ray, and the world-space position and normal of the hit.
+fn ray_mesh_hit(
+    ray: Ray3d,
+    mesh: &Mesh,
+    transform: &GlobalTransform,
+    pick_width: f32,
+) -> Option<(f32, Vec3, Vec3)> {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return None;
+    }
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        return None;
+    };
+    let indices: Vec<usize> = match mesh.indices() {
+        Some(Indices::U32(indices)) => indices.iter().map(|i| *i as usize).collect(),
+        Some(Indices::U16(indices)) => indices.iter().map(|i| *i as usize).collect(),
+        None => (0..positions.len()).collect(),
+    };
+
+    // Work in the overlay's local space, so that the pick width is in local units.
+    let world_from_local = transform.compute_matrix();
+    let local_from_world = world_from_local.inverse();
+    let origin = local_from_world.transform_point3(ray.origin);
+    let direction = local_from_world.transform_vector3(*ray.direction);
+    let tolerance = pick_width * 0.5;
+
+    let mut nearest: Option<(f32, Vec3, Vec3)> = None;
+    for tri in indices.chunks_exact(3) {
+        let (Some(a), Some(b), Some(c)) = (
+            positions.get(tri[0]),
+            positions.get(tri[1]),
+            positions.get(tri[2]),
+        ) else {
+            continue;
+        };
+        let (a, b, c) = (Vec3::from(*a), Vec3::from(*b), Vec3::from(*c));
+        let normal = (b - a).cross(c - a);
+        let denom = normal.dot(direction);
+        if normal.length_squared() == 0. || denom.abs() < f32::EPSILON {
+            continue;
+        }
+        let t = normal.dot(a - origin) / denom;
+        if t < 0. || nearest.is_some_and(|(nearest_t, ..)| t >= nearest_t) {
+            continue;
+        }
+        let point = origin + direction * t;
+        if distance_to_triangle(point, a, b, c) <= tolerance {
+            nearest = Some((t, point, normal));
+        }
+    }
+
+    nearest.map(|(_, point, normal)| {
+        let position = world_from_local.transform_point3(point);
+        let normal = world_from_local
+            .transform_vector3(normal)
+            .normalize_or_zero();
+        (ray.origin.distance(position), position, normal)
+    })
+}
+
+/// Distance from a point lying in the plane of triangle `abc` to the triangle; zero if the
+/// point is inside it.
+fn distance_to_triangle(p: Vec3, a: Vec3, b: Vec3, c: Vec3) -> f32 {
+    let normal = (b - a).cross(c - a);
+    let inside = normal.dot((b - a).cross(p - a)) >= 0.
+        && normal.dot((c - b).cross(p - b)) >= 0.
+        && normal.dot((a - c).cross(p - c)) >= 0.;
+    if inside {
+        return 0.;
+    }
+    distance_to_segment(p, a, b)
+        .min(distance_to_segment(p, b, c))
+        .min(distance_to_segment(p, c, a))
+}
+
+fn distance_to_segment(p: Vec3, a: Vec3, b: Vec3) -> f32 {
+    let ab = b - a;
+    let len_sq = ab.length_squared();
+    if len_sq == 0. {
+        return p.distance(a);
+    }
+    let t = ((p - a).dot(ab) / len_sq).clamp(0., 1.);
+    p.distance(a + ab * t)
+}
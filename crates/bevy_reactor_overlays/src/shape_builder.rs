@@ -3,7 +3,7 @@ use bevy::{
     render::mesh::{Indices, Mesh, PrimitiveTopology},
 };
 
-use super::mesh_builder::MeshBuilder;
+use super::{mesh_builder::MeshBuilder, overlay_material::ATTRIBUTE_EDGE};
 
 /// A marker for the start or end of a shape.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
@@ -21,8 +21,12 @@ pub enum StrokeMarker {
 #[derive(Clone, Debug, Default)]
 pub struct ShapeBuilder {
     vertices: Vec<Vec3>,
+    /// Position of each vertex across the stroke, from -1 to 1. Zero for fills.
+    edges: Vec<f32>,
     indices: Vec<u32>,
     stroke_width: f32,
+    /// Width of the anti-aliased border in hairline mode, or zero if disabled.
+    feather: f32,
 }
 
 /// Options for drawing a polygon or polyline stroke.
@@ -61,8 +65,10 @@ impl ShapeBuilder {
     pub fn new() -> Self {
         Self {
             vertices: Vec::new(),
+            edges: Vec::new(),
             indices: Vec::new(),
             stroke_width: 1.0,
+            feather: 0.0,
         }
     }
 
@@ -73,9 +79,20 @@ impl ShapeBuilder {
         self
     }
 
+    /// Enable anti-aliased hairline mode. Strokes are widened by `feather` on each side, and
+    /// the overlay shader fades out their edges over one pixel, so that thin lines stay crisp
+    /// regardless of the MSAA setting. `feather` should be roughly the size of a pixel at the
+    /// distance the overlay is viewed from.
+    #[inline]
+    pub fn with_hairline(&mut self, feather: f32) -> &mut Self {
+        self.feather = feather;
+        self
+    }
+
     /// Reserve space for vertices and indices.
     pub fn reserve(&mut self, vertices: usize, indices: usize) -> &mut Self {
         self.vertices.reserve(vertices);
+        self.edges.reserve(vertices);
         self.indices.reserve(indices);
         self
     }
@@ -84,6 +101,7 @@ impl ShapeBuilder {
     #[inline]
     pub fn push_vertex(&mut self, x: f32, y: f32, z: f32) -> &mut Self {
         self.vertices.push(Vec3::new(x, y, z));
+        self.edges.push(0.);
         self
     }
 
@@ -109,19 +127,20 @@ impl ShapeBuilder {
         self.reserve(8, 24);
 
         let start = self.vertices.len() as u32;
-        let lw = self.stroke_width;
+        let inner = rect.inflate(-self.stroke_width - self.feather);
+        let outer = rect.inflate(self.feather);
 
-        self.push_vertex(rect.min.x + lw, rect.min.y + lw, 0.);
-        self.push_vertex(rect.min.x, rect.min.y, 0.);
+        self.push_vec2_index(inner.min, -1.);
+        self.push_vec2_index(outer.min, 1.);
 
-        self.push_vertex(rect.max.x - lw, rect.min.y + lw, 0.);
-        self.push_vertex(rect.max.x, rect.min.y, 0.);
+        self.push_vec2_index(Vec2::new(inner.max.x, inner.min.y), -1.);
+        self.push_vec2_index(Vec2::new(outer.max.x, outer.min.y), 1.);
 
-        self.push_vertex(rect.max.x - lw, rect.max.y - lw, 0.);
-        self.push_vertex(rect.max.x, rect.max.y, 0.);
+        self.push_vec2_index(inner.max, -1.);
+        self.push_vec2_index(outer.max, 1.);
 
-        self.push_vertex(rect.min.x + lw, rect.max.y - lw, 0.);
-        self.push_vertex(rect.min.x, rect.max.y, 0.);
+        self.push_vec2_index(Vec2::new(inner.min.x, inner.max.y), -1.);
+        self.push_vec2_index(Vec2::new(outer.min.x, outer.max.y), 1.);
 
         self.push_index(start);
         self.push_index(start + 1);
@@ -180,8 +199,8 @@ impl ShapeBuilder {
         self.reserve((segments * 2) as usize, (segments * 6) as usize);
         let start = self.vertices.len() as u32;
         let step = 2.0 * std::f32::consts::PI / segments as f32;
-        let radius_inner = (radius - self.stroke_width).max(0.0);
-        let radius_outer = radius_inner + self.stroke_width;
+        let radius_inner = (radius - self.stroke_width - self.feather).max(0.0);
+        let radius_outer = radius + self.feather;
         for i in 0..segments {
             let angle = i as f32 * step;
             let c = angle.cos();
@@ -191,8 +210,8 @@ impl ShapeBuilder {
             let x_outer = center.x + radius_outer * c;
             let y_outer = center.y + radius_outer * s;
             let next_index = (i + 1).rem_euclid(segments);
-            self.push_vertex(x_inner, y_inner, 0.);
-            self.push_vertex(x_outer, y_outer, 0.);
+            self.push_vec2_index(Vec2::new(x_inner, y_inner), -1.);
+            self.push_vec2_index(Vec2::new(x_outer, y_outer), 1.);
 
             self.push_index(start + i * 2);
             self.push_index(start + i * 2 + 1);
@@ -259,7 +278,7 @@ impl ShapeBuilder {
             return self;
         }
         let closed = options.closed && vertices.len() > 2;
-        let lw = self.stroke_width * 0.5;
+        let lw = self.stroke_width * 0.5 + self.feather;
         let count = vertices.len();
 
         let mut dash_end = options.dash_length;
@@ -287,8 +306,8 @@ impl ShapeBuilder {
                     let v_miter =
                         Vec2::new(v_dir_prev.y + v_dir.y, -v_dir_prev.x - v_dir.x).normalize() * lw
                             / dot;
-                    let v2_index = self.push_vec2_index(vtx + v_miter);
-                    let v3_index = self.push_vec2_index(vtx - v_miter);
+                    let v2_index = self.push_vec2_index(vtx + v_miter, 1.);
+                    let v3_index = self.push_vec2_index(vtx - v_miter, -1.);
                     self.push_indices(&[
                         v0_index, v2_index, v1_index, v1_index, v2_index, v3_index,
                     ]);
@@ -304,8 +323,8 @@ impl ShapeBuilder {
                         -v_dir,
                         marker_length,
                     );
-                    v0_index = self.push_vec2_index(vtx + v_perp + v_dir * marker_length);
-                    v1_index = self.push_vec2_index(vtx - v_perp + v_dir * marker_length);
+                    v0_index = self.push_vec2_index(vtx + v_perp + v_dir * marker_length, 1.);
+                    v1_index = self.push_vec2_index(vtx - v_perp + v_dir * marker_length, -1.);
                     dash_end += marker_length;
                 }
             }
@@ -319,15 +338,15 @@ impl ShapeBuilder {
             while dash_end < length {
                 // Finish the previous dash.
                 let v_dash_end = vtx + v_dir * dash_end.min(length);
-                let v2_index = self.push_vec2_index(v_dash_end + v_perp);
-                let v3_index = self.push_vec2_index(v_dash_end - v_perp);
+                let v2_index = self.push_vec2_index(v_dash_end + v_perp, 1.);
+                let v3_index = self.push_vec2_index(v_dash_end - v_perp, -1.);
                 self.push_indices(&[v0_index, v2_index, v1_index, v1_index, v2_index, v3_index]);
 
                 // Start a new dash if there's room
                 if dash_end + options.gap_length < length {
                     let v_dash_start = vtx + v_dir * (dash_end + options.gap_length);
-                    v0_index = self.push_vec2_index(v_dash_start + v_perp);
-                    v1_index = self.push_vec2_index(v_dash_start - v_perp);
+                    v0_index = self.push_vec2_index(v_dash_start + v_perp, 1.);
+                    v1_index = self.push_vec2_index(v_dash_start - v_perp, -1.);
                 }
 
                 // Prep for next dash
@@ -344,8 +363,8 @@ impl ShapeBuilder {
                     let v_miter =
                         Vec2::new(v_dir.y + v_dir_next.y, -v_dir.x - v_dir_next.x).normalize() * lw
                             / dot;
-                    let v2_index = self.push_vec2_index(vtx_next + v_miter);
-                    let v3_index = self.push_vec2_index(vtx_next - v_miter);
+                    let v2_index = self.push_vec2_index(vtx_next + v_miter, 1.);
+                    let v3_index = self.push_vec2_index(vtx_next - v_miter, -1.);
                     self.push_indices(&[
                         v0_index, v2_index, v1_index, v1_index, v2_index, v3_index,
                     ]);
@@ -356,8 +375,8 @@ impl ShapeBuilder {
                     let v_seg_end = vtx + v_dir * length;
                     let v2 = v_seg_end + v_perp;
                     let v3 = v_seg_end - v_perp;
-                    let v2_index = self.push_vec2_index(v2);
-                    let v3_index = self.push_vec2_index(v3);
+                    let v2_index = self.push_vec2_index(v2, 1.);
+                    let v3_index = self.push_vec2_index(v3, -1.);
                     self.push_indices(&[
                         v0_index, v2_index, v1_index, v1_index, v2_index, v3_index,
                     ]);
@@ -371,11 +390,13 @@ impl ShapeBuilder {
         self
     }
 
-    /// Add a vertex to the shape, and return the index of that vertex.
+    /// Add a stroke vertex to the shape, and return the index of that vertex. `edge` is the
+    /// position of the vertex across the stroke, from -1 to 1.
     #[inline]
-    fn push_vec2_index(&mut self, v: Vec2) -> u32 {
+    fn push_vec2_index(&mut self, v: Vec2, edge: f32) -> u32 {
         let index = self.vertices.len() as u32;
         self.vertices.push(Vec3::new(v.x, v.y, 0.));
+        self.edges.push(edge);
         index
     }
 
@@ -410,6 +431,9 @@ impl MeshBuilder for ShapeBuilder {
     /// Copy the shape into a [`Mesh`]. This will consume the builder and return a mesh.
     fn build(self, mesh: &mut Mesh) {
         mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, self.vertices);
+        if self.feather > 0. {
+            mesh.insert_attribute(ATTRIBUTE_EDGE, self.edges);
+        }
         mesh.insert_indices(Indices::U32(self.indices));
         mesh.compute_aabb();
     }
@@ -0,0 +1,41 @@
+//! Measures the cost of writing to mutables, both when the value changes and when it doesn't,
+//! inside and outside of a batch. Run with `cargo bench -p bevy_reactor_signals`.
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+use bevy_reactor_signals::{BatchMutations, CreateMutable, Mutable};
+
+const ITERATIONS: u32 = 100_000;
+
+fn report(name: &str, elapsed: Duration) {
+    println!(
+        "{:<32} {:>8.1} ns/write",
+        name,
+        elapsed.as_nanos() as f64 / ITERATIONS as f64
+    );
+}
+
+fn bench(name: &str, mut f: impl FnMut(&mut World, Mutable<i32>, i32)) {
+    let mut world = World::default();
+    let mutable = world.create_mutable::<i32>(0);
+    // Warm up.
+    for i in 0..1000 {
+        f(&mut world, mutable, i);
+    }
+    let start = Instant::now();
+    for i in 0..ITERATIONS as i32 {
+        f(&mut world, mutable, i);
+    }
+    report(name, start.elapsed());
+}
+
+fn main() {
+    bench("unchanged", |world, mutable, _| mutable.set(world, 0));
+    bench("changed", |world, mutable, i| mutable.set(world, i));
+    bench("unchanged, batched", |world, mutable, _| {
+        world.batch(|world| mutable.set(world, 0));
+    });
+    bench("changed, batched", |world, mutable, i| {
+        world.batch(|world| mutable.set(world, i));
+    });
+}
@@ -21,6 +21,17 @@ impl<P> Callback<P> {
     pub fn new(id: SystemId<In<P>, ()>) -> Self {
         Self { id }
     }
+
+    /// Returns true if the callback is still registered, that is, the entity which owns it
+    /// has not been despawned.
+    pub fn is_alive(&self, world: &World) -> bool {
+        world.get_entity(self.id.entity()).is_ok()
+    }
+
+    /// Return a [`WeakCallback`] which refers to the same callback system.
+    pub fn downgrade(&self) -> WeakCallback<P> {
+        WeakCallback { id: self.id }
+    }
 }
 
 impl<P> Copy for Callback<P> {}
@@ -30,6 +41,43 @@ impl<P> Clone for Callback<P> {
     }
 }
 
+/// A weak reference to a callback. Unlike a [`Callback`], running a `WeakCallback` after its
+/// owner has been despawned is not an error: the call is silently ignored. This is useful for
+/// long-lived components which hold on to callbacks whose lifetime they don't control.
+#[derive(PartialEq, Debug)]
+pub struct WeakCallback<P: 'static = ()> {
+    pub(crate) id: SystemId<In<P>, ()>,
+}
+
+impl<P> WeakCallback<P> {
+    /// Returns true if the callback is still registered.
+    pub fn is_alive(&self, world: &World) -> bool {
+        world.get_entity(self.id.entity()).is_ok()
+    }
+
+    /// Return the strong [`Callback`], if it is still registered.
+    pub fn upgrade(&self, world: &World) -> Option<Callback<P>> {
+        if self.is_alive(world) {
+            Some(Callback { id: self.id })
+        } else {
+            None
+        }
+    }
+}
+
+impl<P> Copy for WeakCallback<P> {}
+impl<P> Clone for WeakCallback<P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P> From<Callback<P>> for WeakCallback<P> {
+    fn from(callback: Callback<P>) -> Self {
+        callback.downgrade()
+    }
+}
+
 pub trait AnyCallback: 'static {
     fn remove(&self, world: &mut World);
 }
@@ -55,6 +103,16 @@ impl CallbackOwner {
     pub fn add<P: 'static>(&mut self, callback: Callback<P>) {
         self.0.push(Arc::new(callback));
     }
+
+    /// Returns the number of owned callbacks.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if there are no owned callbacks.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
 }
 
 pub(crate) fn cleanup_callbacks(world: &mut World) {
@@ -73,6 +131,10 @@ pub(crate) fn cleanup_callbacks(world: &mut World) {
 pub trait RunCallback {
     /// Invoke a callback with the given props.
     fn run_callback<P: Send>(&mut self, callback: Callback<P>, props: P);
+
+    /// Invoke a weak callback with the given props. Does nothing if the callback's owner
+    /// has been despawned.
+    fn run_weak_callback<P: Send>(&mut self, callback: WeakCallback<P>, props: P);
 }
 
 /// A mutable reactive context. This allows write access to reactive data sources.
@@ -83,8 +145,19 @@ impl RunCallback for World {
     /// * `callback` - The callback to invoke.
     /// * `props` - The props to pass to the callback.
     fn run_callback<P>(&mut self, callback: Callback<P>, props: P) {
+        debug_assert!(
+            callback.is_alive(self),
+            "Attempt to run a callback whose owner has been despawned: {:?}",
+            callback.id
+        );
         self.run_system_with_input(callback.id, props).unwrap();
     }
+
+    fn run_weak_callback<P>(&mut self, callback: WeakCallback<P>, props: P) {
+        if callback.is_alive(self) {
+            self.run_system_with_input(callback.id, props).unwrap();
+        }
+    }
 }
 
 /// A mutable reactive context. This allows write access to reactive data sources.
@@ -95,20 +168,39 @@ impl<'w> RunCallback for DeferredWorld<'w> {
     /// * `callback` - The callback to invoke.
     /// * `props` - The props to pass to the callback.
     fn run_callback<P: Send>(&mut self, callback: Callback<P>, props: P) {
+        debug_assert!(
+            callback.is_alive(self),
+            "Attempt to run a callback whose owner has been despawned: {:?}",
+            callback.id
+        );
         self.commands().run_system_with_input(callback.id, props);
     }
+
+    fn run_weak_callback<P: Send>(&mut self, callback: WeakCallback<P>, props: P) {
+        // The owner may be despawned before the command is applied, so check liveness then.
+        self.commands()
+            .queue(move |world: &mut World| world.run_weak_callback(callback, props));
+    }
 }
 
 impl<'p, 'w> RunCallback for Ecx<'p, 'w> {
     fn run_callback<P: Send>(&mut self, callback: Callback<P>, props: P) {
         self.world_mut().run_callback(callback, props);
     }
+
+    fn run_weak_callback<P: Send>(&mut self, callback: WeakCallback<P>, props: P) {
+        self.world_mut().run_weak_callback(callback, props);
+    }
 }
 
 impl<'w, 's> RunCallback for Commands<'w, 's> {
     fn run_callback<P: Send>(&mut self, callback: Callback<P>, props: P) {
         self.run_system_with_input(callback.id, props)
     }
+
+    fn run_weak_callback<P: Send>(&mut self, callback: WeakCallback<P>, props: P) {
+        self.queue(move |world: &mut World| world.run_weak_callback(callback, props));
+    }
 }
 
 pub(crate) struct UnregisterCallbackCmd(pub(crate) Arc<dyn AnyCallback + Send + Sync>);
@@ -118,3 +210,34 @@ impl Command for UnregisterCallbackCmd {
         self.0.remove(world)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct Counter(i32);
+
+    #[test]
+    fn test_weak_callback() {
+        let mut world = World::default();
+        world.init_resource::<Counter>();
+        let id = world.register_system(|value: In<i32>, mut counter: ResMut<Counter>| {
+            counter.0 += *value;
+        });
+        let callback = Callback::new(id);
+        let weak = callback.downgrade();
+        assert!(callback.is_alive(&world));
+        assert_eq!(weak.upgrade(&world), Some(callback));
+
+        world.run_weak_callback(weak, 2);
+        assert_eq!(world.resource::<Counter>().0, 2);
+
+        // Once the callback is unregistered, the weak callback is a no-op.
+        world.unregister_system(id).unwrap();
+        assert!(!callback.is_alive(&world));
+        assert!(weak.upgrade(&world).is_none());
+        world.run_weak_callback(weak, 2);
+        assert_eq!(world.resource::<Counter>().0, 2);
+    }
+}
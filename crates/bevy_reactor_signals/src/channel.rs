@@ -0,0 +1,264 @@
+use std::sync::{
+    mpsc::{Receiver, Sender, SyncSender, TryRecvError, TrySendError},
+    Mutex,
+};
+
+use bevy::prelude::*;
+
+use crate::{mutable::create_mutable, Mutable, ReadMutable, Signal, WriteMutable};
+
+/// The receiving half of a channel which can feed a [`Signal`]. This is implemented for
+/// [`std::sync::mpsc::Receiver`]; other channel types (such as crossbeam) can be supported by
+/// implementing this trait on a wrapper.
+pub trait ChannelReceiver<T>: Send + 'static {
+    /// Return the next pending value without blocking, or `Ok(None)` if there is none.
+    fn try_receive(&mut self) -> Result<Option<T>, ChannelClosed>;
+}
+
+/// Error returned by [`ChannelReceiver::try_receive`] once all senders have gone away, and by
+/// [`ChannelSender::try_send_value`] once the receiver has gone away.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelClosed;
+
+impl<T: Send + 'static> ChannelReceiver<T> for Receiver<T> {
+    fn try_receive(&mut self) -> Result<Option<T>, ChannelClosed> {
+        match self.try_recv() {
+            Ok(value) => Ok(Some(value)),
+            Err(TryRecvError::Empty) => Ok(None),
+            Err(TryRecvError::Disconnected) => Err(ChannelClosed),
+        }
+    }
+}
+
+/// The sending half of a channel which can be subscribed to a [`Mutable`]. This is implemented
+/// for [`std::sync::mpsc::Sender`] and [`std::sync::mpsc::SyncSender`].
+pub trait ChannelSender<T>: Send + Sync + 'static {
+    /// Send a value without blocking. Returns `Ok(false)` if the value could not be sent
+    /// right now, for example because the channel is full, in which case it is retried on a
+    /// later frame. Returns [`ChannelClosed`] if the receiver has gone away, in which case the
+    /// subscription is dropped.
+    fn try_send_value(&self, value: T) -> Result<bool, ChannelClosed>;
+}
+
+impl<T: Send + 'static> ChannelSender<T> for Sender<T> {
+    fn try_send_value(&self, value: T) -> Result<bool, ChannelClosed> {
+        self.send(value).map(|_| true).map_err(|_| ChannelClosed)
+    }
+}
+
+impl<T: Send + 'static> ChannelSender<T> for SyncSender<T> {
+    fn try_send_value(&self, value: T) -> Result<bool, ChannelClosed> {
+        // A full channel means the consumer is falling behind; skip the value rather than
+        // stalling the frame.
+        match self.try_send(value) {
+            Ok(()) => Ok(true),
+            Err(TrySendError::Full(_)) => Ok(false),
+            Err(TrySendError::Disconnected(_)) => Err(ChannelClosed),
+        }
+    }
+}
+
+type PollFn = Box<dyn FnMut(&mut World) -> bool + Send + Sync>;
+
+/// Bridges between a mutable cell and external channels, stored on the cell entity so that
+/// they are dropped along with the cell.
+#[derive(Component, Default)]
+pub(crate) struct ChannelBridges(Vec<PollFn>);
+
+/// Create a [`Signal`] whose value is fed from a channel. This allows non-ECS subsystems, such
+/// as background threads or network clients, to drive the reactive UI. Pending values are
+/// drained once per frame, before reactions are run; only the most recent value is kept.
+///
+/// Arguments:
+/// * `world`: The Bevy world.
+/// * `parent`: The owner of the signal; the signal is despawned along with it.
+/// * `receiver`: The receiving end of the channel.
+/// * `init`: The value of the signal until the first message arrives.
+pub fn signal_from_channel<T, R>(
+    world: &mut World,
+    parent: Entity,
+    receiver: R,
+    init: T,
+) -> Signal<T>
+where
+    T: PartialEq + Send + Sync + 'static,
+    R: ChannelReceiver<T>,
+{
+    let mutable = create_mutable(world, parent, init);
+    let receiver = Mutex::new(receiver);
+    add_bridge(
+        world,
+        mutable.id(),
+        Box::new(move |world: &mut World| {
+            let mut receiver = receiver.lock().unwrap();
+            let mut latest = None;
+            let connected = loop {
+                match receiver.try_receive() {
+                    Ok(Some(value)) => latest = Some(value),
+                    Ok(None) => break true,
+                    Err(ChannelClosed) => break false,
+                }
+            };
+            if let Some(value) = latest {
+                world.write_mutable(mutable.id(), value);
+            }
+            connected
+        }),
+    );
+    mutable.signal()
+}
+
+impl<T> Mutable<T>
+where
+    T: PartialEq + Clone + Send + Sync + 'static,
+{
+    /// Push the value of this [`Mutable`] out to a channel whenever it changes, so that
+    /// non-ECS subsystems can observe it. The current value is sent on the next frame, and
+    /// every change after that; if a bounded channel is full, the latest value is sent once
+    /// there is room. The subscription ends when the receiver is dropped or the
+    /// mutable is despawned.
+    pub fn subscribe<S: ChannelSender<T>>(&self, world: &mut World, sender: S) {
+        let mutable = *self;
+        let mut last: Option<T> = None;
+        add_bridge(
+            world,
+            self.id(),
+            Box::new(move |world: &mut World| {
+                let value = world.read_mutable_clone(&mutable);
+                if last.as_ref() == Some(&value) {
+                    return true;
+                }
+                // Only remember values which were actually sent, so that a value skipped
+                // because the channel was full is sent again on a later frame.
+                match sender.try_send_value(value.clone()) {
+                    Ok(sent) => {
+                        if sent {
+                            last = Some(value);
+                        }
+                        true
+                    }
+                    Err(ChannelClosed) => false,
+                }
+            }),
+        );
+    }
+}
+
+fn add_bridge(world: &mut World, cell: Entity, bridge: PollFn) {
+    let mut entt = world.entity_mut(cell);
+    match entt.get_mut::<ChannelBridges>() {
+        Some(mut bridges) => bridges.0.push(bridge),
+        None => {
+            entt.insert(ChannelBridges(vec![bridge]));
+        }
+    }
+}
+
+/// Exchange values between mutable cells and their channels.
+pub(crate) fn poll_channels(world: &mut World) {
+    // Nothing to do until a channel has been connected.
+    if world.component_id::<ChannelBridges>().is_none() {
+        return;
+    }
+    let mut query = world.query_filtered::<Entity, With<ChannelBridges>>();
+    let cells: Vec<Entity> = query.iter(world).collect();
+    for cell in cells {
+        // Move the bridges out of the component so that they can write to the world. The
+        // component itself stays in place, to avoid moving the cell between archetypes.
+        let Some(mut bridges) = world
+            .get_mut::<ChannelBridges>(cell)
+            .map(|mut bridges| std::mem::take(&mut bridges.0))
+        else {
+            continue;
+        };
+        bridges.retain_mut(|bridge| bridge(world));
+        let Ok(mut entt) = world.get_entity_mut(cell) else {
+            continue;
+        };
+        let Some(mut current) = entt.get_mut::<ChannelBridges>() else {
+            continue;
+        };
+        // Keep any bridges which were added while polling, after the existing ones.
+        bridges.append(&mut current.0);
+        if bridges.is_empty() {
+            entt.remove::<ChannelBridges>();
+        } else {
+            current.0 = bridges;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::{channel, sync_channel};
+
+    use super::*;
+
+    #[test]
+    fn test_signal_from_channel() {
+        let mut world = World::default();
+        let owner = world.spawn_empty().id();
+        let (tx, rx) = channel::<i32>();
+        let signal = signal_from_channel(&mut world, owner, rx, 0);
+        let Signal::Mutable(mutable) = signal else {
+            panic!("expected a mutable signal");
+        };
+
+        poll_channels(&mut world);
+        assert_eq!(world.read_mutable(&mutable), 0);
+
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+        poll_channels(&mut world);
+        assert_eq!(world.read_mutable(&mutable), 2);
+
+        // Bridge is removed once the sender is gone.
+        drop(tx);
+        poll_channels(&mut world);
+        assert!(world.get::<ChannelBridges>(mutable.id()).is_none());
+    }
+
+    #[test]
+    fn test_mutable_subscribe() {
+        let mut world = World::default();
+        let owner = world.spawn_empty().id();
+        let mutable = create_mutable(&mut world, owner, 1);
+        let (tx, rx) = channel::<i32>();
+        mutable.subscribe(&mut world, tx);
+
+        poll_channels(&mut world);
+        assert_eq!(rx.try_recv(), Ok(1));
+        poll_channels(&mut world);
+        assert!(rx.try_recv().is_err());
+
+        mutable.set(&mut world, 5);
+        poll_channels(&mut world);
+        assert_eq!(rx.try_recv(), Ok(5));
+
+        drop(rx);
+        mutable.set(&mut world, 6);
+        poll_channels(&mut world);
+        assert!(world.get::<ChannelBridges>(mutable.id()).is_none());
+    }
+
+    #[test]
+    fn test_mutable_subscribe_full_channel() {
+        let mut world = World::default();
+        let owner = world.spawn_empty().id();
+        let mutable = create_mutable(&mut world, owner, 1);
+        let (tx, rx) = sync_channel::<i32>(1);
+        mutable.subscribe(&mut world, tx);
+
+        poll_channels(&mut world);
+        mutable.set(&mut world, 2);
+        poll_channels(&mut world);
+        assert_eq!(rx.try_recv(), Ok(1));
+        assert!(rx.try_recv().is_err());
+
+        // The value which didn't fit is sent once the channel has room.
+        poll_channels(&mut world);
+        assert_eq!(rx.try_recv(), Ok(2));
+        poll_channels(&mut world);
+        assert!(rx.try_recv().is_err());
+    }
+}
@@ -16,8 +16,14 @@ impl<R, F: Fn(&mut Rcx) -> R> DerivedFnRef<R> for F {
 
 /// Contains a boxed, type-erased function which returns a reactive result.
 #[derive(Component)]
+#[require(DerivedMarker)]
 pub struct DerivedCell<R>(pub(crate) Arc<dyn DerivedFnRef<R> + Send + Sync>);
 
+/// Marker which accompanies every [`DerivedCell`], so that deriveds of any type can be found
+/// by a single component id.
+#[derive(Component, Default)]
+pub(crate) struct DerivedMarker;
+
 impl<R> DerivedCell<R> {
     /// Construct a new `DerivedCell` from a function.
     pub fn new<F: Send + Sync + 'static + Fn(&mut Rcx) -> R>(f: F) -> Self {
@@ -25,6 +31,15 @@ impl<R> DerivedCell<R> {
     }
 }
 
+/// Equality test for a derived created by [`create_derived_eq`] or [`create_derived_with_eq`].
+/// Given the value seen by a reader, returns a predicate which tests whether a recomputed
+/// value is equal to it.
+#[derive(Component)]
+#[allow(clippy::type_complexity)]
+pub(crate) struct DerivedEq<R>(
+    Arc<dyn Fn(&R) -> Box<dyn Fn(&R) -> bool + Send + Sync> + Send + Sync>,
+);
+
 /// A [`Derived`] is a readonly value that is computed from other signals.
 #[derive(PartialEq)]
 pub struct Derived<R> {
@@ -127,30 +142,14 @@ impl ReadDerivedInternal for World {
     where
         R: Send + Sync + Copy + 'static,
     {
-        let derived_entity = self.entity(derived);
-        match derived_entity.get::<DerivedCell<R>>() {
-            Some(cell) => {
-                let derived_fn = cell.0.clone();
-                let mut rcx = Rcx::new(self, derived, scope);
-                derived_fn.call(&mut rcx)
-            }
-            _ => panic!("No derived found for {:?}", derived),
-        }
+        call_derived(self, derived, scope, |value: &R| *value)
     }
 
     fn read_derived_clone_with_scope<R>(&self, derived: Entity, scope: &mut TrackingScope) -> R
     where
         R: Send + Sync + Clone + 'static,
     {
-        let derived_entity = self.entity(derived);
-        match derived_entity.get::<DerivedCell<R>>() {
-            Some(cell) => {
-                let derived_fn = cell.0.clone();
-                let mut rcx = Rcx::new(self, derived, scope);
-                derived_fn.call(&mut rcx).clone()
-            }
-            _ => panic!("No derived found for {:?}", derived),
-        }
+        call_derived(self, derived, scope, R::clone)
     }
 
     fn read_derived_map_with_scope<R, U, F: Fn(&R) -> U>(
@@ -162,15 +161,7 @@ impl ReadDerivedInternal for World {
     where
         R: Send + Sync + 'static,
     {
-        let derived_entity = self.entity(derived);
-        match derived_entity.get::<DerivedCell<R>>() {
-            Some(cell) => {
-                let derived_fn = cell.0.clone();
-                let mut rcx = Rcx::new(self, derived, scope);
-                f(&derived_fn.call(&mut rcx))
-            }
-            _ => panic!("No derived found for {:?}", derived),
-        }
+        call_derived(self, derived, scope, f)
     }
 }
 
@@ -208,30 +199,14 @@ impl<'w> ReadDerivedInternal for DeferredWorld<'w> {
     where
         R: Send + Sync + Copy + 'static,
     {
-        let derived_entity = self.entity(derived);
-        match derived_entity.get::<DerivedCell<R>>() {
-            Some(cell) => {
-                let derived_fn = cell.0.clone();
-                let mut rcx = Rcx::new(self, derived, scope);
-                derived_fn.call(&mut rcx)
-            }
-            _ => panic!("No derived found for {:?}", derived),
-        }
+        call_derived(self, derived, scope, |value: &R| *value)
     }
 
     fn read_derived_clone_with_scope<R>(&self, derived: Entity, scope: &mut TrackingScope) -> R
     where
         R: Send + Sync + Clone + 'static,
     {
-        let derived_entity = self.entity(derived);
-        match derived_entity.get::<DerivedCell<R>>() {
-            Some(cell) => {
-                let derived_fn = cell.0.clone();
-                let mut rcx = Rcx::new(self, derived, scope);
-                derived_fn.call(&mut rcx).clone()
-            }
-            _ => panic!("No derived found for {:?}", derived),
-        }
+        call_derived(self, derived, scope, R::clone)
     }
 
     fn read_derived_map_with_scope<R, U, F: Fn(&R) -> U>(
@@ -243,18 +218,38 @@ impl<'w> ReadDerivedInternal for DeferredWorld<'w> {
     where
         R: Send + Sync + 'static,
     {
-        let derived_entity = self.entity(derived);
-        match derived_entity.get::<DerivedCell<R>>() {
-            Some(cell) => {
-                let derived_fn = cell.0.clone();
-                let mut rcx = Rcx::new(self, derived, scope);
-                f(&derived_fn.call(&mut rcx))
-            }
-            _ => panic!("No derived found for {:?}", derived),
-        }
+        call_derived(self, derived, scope, f)
     }
 }
 
+/// Evaluate a derived signal and pass the result to `f`. If the derived has an equality test,
+/// its dependencies are tracked as a unit, and only count as changed if the derived's value
+/// changes; otherwise they are added directly to `scope`.
+fn call_derived<R: Send + Sync + 'static, U>(
+    world: &World,
+    derived: Entity,
+    scope: &mut TrackingScope,
+    f: impl FnOnce(&R) -> U,
+) -> U {
+    let derived_entity = world.entity(derived);
+    let Some(cell) = derived_entity.get::<DerivedCell<R>>() else {
+        panic!("No derived found for {:?}", derived);
+    };
+    let derived_fn = cell.0.clone();
+    let Some(eq) = derived_entity.get::<DerivedEq<R>>() else {
+        let mut rcx = Rcx::new(world, derived, scope);
+        return f(&derived_fn.call(&mut rcx));
+    };
+
+    let mut deps = TrackingScope::new(scope.tick);
+    let value = derived_fn.call(&mut Rcx::new(world, derived, &mut deps));
+    let unchanged = (eq.0)(&value);
+    scope.track_derived(deps, move |world, next| {
+        !unchanged(&derived_fn.call(&mut Rcx::new(world, derived, next)))
+    });
+    f(&value)
+}
+
 /// Helper function for creating deriveds.
 pub fn create_derived<R: 'static, F: Send + Sync + 'static + Fn(&mut Rcx) -> R>(
     world: &mut World,
@@ -268,3 +263,164 @@ pub fn create_derived<R: 'static, F: Send + Sync + 'static + Fn(&mut Rcx) -> R>(
         marker: PhantomData,
     }
 }
+
+/// Helper function for creating deriveds whose readers are only notified when the computed
+/// value changes, as determined by `PartialEq`. Use this for deriveds which often produce the
+/// same output for different inputs, such as formatting or thresholding a value.
+pub fn create_derived_eq<
+    R: PartialEq + Clone + Send + Sync + 'static,
+    F: Send + Sync + 'static + Fn(&mut Rcx) -> R,
+>(
+    world: &mut World,
+    compute: F,
+) -> Derived<R> {
+    create_derived_with_eq(world, compute, |a: &R, b: &R| a == b)
+}
+
+/// Like [`create_derived_eq`], but with a custom comparison function, which should return
+/// true if the two values are equivalent.
+pub fn create_derived_with_eq<
+    R: Clone + Send + Sync + 'static,
+    F: Send + Sync + 'static + Fn(&mut Rcx) -> R,
+    C: Send + Sync + 'static + Fn(&R, &R) -> bool,
+>(
+    world: &mut World,
+    compute: F,
+    eq: C,
+) -> Derived<R> {
+    let eq = Arc::new(eq);
+    let derived = world
+        .spawn((
+            DerivedCell::new(compute),
+            DerivedEq::<R>(Arc::new(move |prev: &R| {
+                let prev = prev.clone();
+                let eq = eq.clone();
+                Box::new(move |next: &R| eq(&prev, next))
+            })),
+            GhostNode::default(),
+        ))
+        .id();
+    Derived {
+        id: derived,
+        marker: PhantomData,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::{CreateMutable, Signal};
+
+    #[test]
+    fn test_derived_eq_suppresses_unchanged() {
+        let mut world = World::default();
+        let value = world.create_mutable::<i32>(1);
+        let parity = create_derived_eq(&mut world, move |rcx| value.get(rcx) % 2);
+
+        let tick = world.change_tick();
+        let mut scope = TrackingScope::new(tick);
+        assert_eq!(
+            world.read_derived_with_scope::<i32>(parity.id(), &mut scope),
+            1
+        );
+
+        // The input changes, but the output doesn't.
+        world.increment_change_tick();
+        value.set(&mut world, 3);
+        let tick = world.change_tick();
+        assert!(!scope.dependencies_changed(&world, tick));
+
+        // The output changes.
+        world.increment_change_tick();
+        value.set(&mut world, 4);
+        let tick = world.change_tick();
+        assert!(scope.dependencies_changed(&world, tick));
+    }
+
+    #[test]
+    fn test_derived_eq_not_recomputed_after_unchanged() {
+        let mut world = World::default();
+        let value = world.create_mutable::<i32>(1);
+        let calls = Arc::new(AtomicUsize::new(0));
+        let parity = create_derived_eq(&mut world, {
+            let calls = calls.clone();
+            move |rcx| {
+                calls.fetch_add(1, Ordering::Relaxed);
+                value.get(rcx) % 2
+            }
+        });
+
+        let tick = world.change_tick();
+        let mut scope = TrackingScope::new(tick);
+        world.read_derived_with_scope::<i32>(parity.id(), &mut scope);
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+        world.increment_change_tick();
+        value.set(&mut world, 3);
+        let tick = world.change_tick();
+        assert!(!scope.dependencies_changed(&world, tick));
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+
+        // Nothing has changed since, so the derived isn't recomputed.
+        world.increment_change_tick();
+        let tick = world.change_tick();
+        assert!(!scope.dependencies_changed(&world, tick));
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+
+        // But a later change to the input is still seen.
+        world.increment_change_tick();
+        value.set(&mut world, 4);
+        let tick = world.change_tick();
+        assert!(scope.dependencies_changed(&world, tick));
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn test_derived_with_eq_comparator() {
+        let mut world = World::default();
+        let value = world.create_mutable::<f32>(1.0);
+        // Treat values within 0.5 of each other as equal.
+        let derived = create_derived_with_eq(
+            &mut world,
+            move |rcx| value.get(rcx),
+            |a: &f32, b: &f32| (a - b).abs() < 0.5,
+        );
+        let signal = Signal::Derived(derived);
+
+        let tick = world.change_tick();
+        let mut scope = TrackingScope::new(tick);
+        let owner = world.spawn_empty().id();
+        assert_eq!(signal.get(&Rcx::new(&world, owner, &mut scope)), 1.0);
+
+        world.increment_change_tick();
+        value.set(&mut world, 1.2);
+        let tick = world.change_tick();
+        assert!(!scope.dependencies_changed(&world, tick));
+
+        world.increment_change_tick();
+        value.set(&mut world, 2.0);
+        let tick = world.change_tick();
+        assert!(scope.dependencies_changed(&world, tick));
+    }
+
+    #[test]
+    fn test_plain_derived_always_notifies() {
+        let mut world = World::default();
+        let value = world.create_mutable::<i32>(1);
+        let parity = create_derived(&mut world, move |rcx| value.get(rcx) % 2);
+
+        let tick = world.change_tick();
+        let mut scope = TrackingScope::new(tick);
+        assert_eq!(
+            world.read_derived_with_scope::<i32>(parity.id(), &mut scope),
+            1
+        );
+
+        world.increment_change_tick();
+        value.set(&mut world, 3);
+        let tick = world.change_tick();
+        assert!(scope.dependencies_changed(&world, tick));
+    }
+}
@@ -1,7 +1,10 @@
 use std::cell::RefCell;
 
 use bevy::{
-    ecs::world::DeferredWorld,
+    ecs::{
+        query::{QueryFilter, ROQueryItem, ReadOnlyQueryData},
+        world::DeferredWorld,
+    },
     prelude::{Component, Entity, Parent, Resource, World},
 };
 
@@ -48,6 +51,23 @@ impl<'p, 'w> Ecx<'p, 'w> {
         self.world.resource::<T>()
     }
 
+    /// Return a value projected from the resource `T`. Unlike [`read_resource`], the current
+    /// tracking scope is only considered out of date when the projected value changes, not
+    /// when any other part of the resource changes.
+    ///
+    /// [`read_resource`]: Self::read_resource
+    pub fn use_resource_map<T: Resource, U, F>(&self, project: F) -> U
+    where
+        U: PartialEq + Clone + Send + Sync + 'static,
+        F: Fn(&T) -> U + Send + Sync + 'static,
+    {
+        let value = project(self.world.resource::<T>());
+        self.tracking
+            .borrow_mut()
+            .track_resource_map(self.world, project, value.clone());
+        value
+    }
+
     /// Return a reference to the Component `C` on the given entity. Calling this function
     /// adds the component as a dependency of the current tracking scope.
     fn read_component<C: Component>(&self, entity: Entity) -> Option<&C> {
@@ -57,6 +77,43 @@ impl<'p, 'w> Ecx<'p, 'w> {
         self.world.entity(entity).get::<C>()
     }
 
+    /// Return a value extracted from the Component `C` on the given entity. Unlike
+    /// [`read_component`], the current tracking scope is only considered out of date when the
+    /// extracted value changes, not when any other part of the component changes.
+    ///
+    /// [`read_component`]: Self::read_component
+    pub fn use_component_map<C: Component, U, F>(&self, entity: Entity, extract: F) -> Option<U>
+    where
+        U: PartialEq + Clone + Send + Sync + 'static,
+        F: Fn(&C) -> U + Send + Sync + 'static,
+    {
+        let value = self.world.entity(entity).get::<C>().map(&extract);
+        self.tracking
+            .borrow_mut()
+            .track_component_map(entity, self.world, extract, value.clone());
+        value
+    }
+
+    /// Return a snapshot of the results of a query. Calling this function adds the query as a
+    /// dependency of the current tracking scope: it is considered out of date when an entity
+    /// starts or stops matching the query, or when a component read by the query changes on
+    /// one of the matching entities.
+    pub fn use_query<D, F>(&self) -> Vec<ROQueryItem<'_, D>>
+    where
+        D: ReadOnlyQueryData + 'static,
+        F: QueryFilter + 'static,
+    {
+        let world: &World = &*self.world;
+        let results: Vec<_> = match world.try_query_filtered::<(Entity, D), F>() {
+            Some(mut state) => state.iter(world).collect(),
+            None => Vec::new(),
+        };
+        self.tracking
+            .borrow_mut()
+            .track_query::<D, F>(world, results.iter().map(|(entity, _)| *entity).collect());
+        results.into_iter().map(|(_, item)| item).collect()
+    }
+
     /// Return a reference to the Component `C` on the owner entity of the current
     /// context, or one of it's ancestors. This searches up the entity tree until it finds
     /// a component of the given type.
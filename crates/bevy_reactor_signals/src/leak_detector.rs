@@ -0,0 +1,195 @@
+use std::time::Duration;
+
+use bevy::{
+    ecs::component::ComponentId,
+    prelude::*,
+    utils::{HashMap, Instant},
+};
+
+use crate::{derived::DerivedMarker, mutable::MutableMarker, CallbackOwner, ReactionCell};
+
+/// Number of reactive cells of each kind.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CellCounts {
+    /// Number of mutable variables, including lenses.
+    pub mutables: usize,
+    /// Number of derived signals.
+    pub deriveds: usize,
+    /// Number of reactions, such as effects, memos and reactive views.
+    pub reactions: usize,
+    /// Number of registered callbacks.
+    pub callbacks: usize,
+}
+
+impl CellCounts {
+    /// Total number of cells.
+    pub fn total(&self) -> usize {
+        self.mutables + self.deriveds + self.reactions + self.callbacks
+    }
+
+    fn add(&mut self, other: &CellCounts) {
+        self.mutables += other.mutables;
+        self.deriveds += other.deriveds;
+        self.reactions += other.reactions;
+        self.callbacks += other.callbacks;
+    }
+}
+
+/// Snapshot of the reactive cells in the world, grouped by owner.
+#[derive(Clone, Debug, Default)]
+pub struct CellStats {
+    /// Cells grouped by the entity which owns them.
+    pub owners: HashMap<Entity, CellCounts>,
+    /// Cells which were created without an owner, such as mutables created directly on the
+    /// world. These are not necessarily leaks, but are never cleaned up automatically.
+    pub unowned: CellCounts,
+    /// Cells whose owner has been despawned without them. These are leaks.
+    pub orphans: Vec<Entity>,
+    /// Total number of cells.
+    pub total: CellCounts,
+}
+
+/// Resource which, if inserted, enables the leak detector. Periodically counts the reactive
+/// cells (mutables, deriveds, reactions and callbacks) in the world, and logs a warning for
+/// any cells whose owner no longer exists. The counts are kept in [`LeakDetector::stats`], for
+/// tools which want to display them.
+#[derive(Resource)]
+pub struct LeakDetector {
+    /// How often to check for leaks.
+    pub interval: Duration,
+    /// Results of the most recent check.
+    pub stats: CellStats,
+    last_check: Option<Instant>,
+}
+
+impl Default for LeakDetector {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(5),
+            stats: CellStats::default(),
+            last_check: None,
+        }
+    }
+}
+
+impl LeakDetector {
+    /// Create a new leak detector which checks at the given interval.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            ..default()
+        }
+    }
+}
+
+/// Count the reactive cells in the world, grouped by owner.
+pub fn collect_cell_stats(world: &World) -> CellStats {
+    let mutable_id = world.component_id::<MutableMarker>();
+    let derived_id = world.component_id::<DerivedMarker>();
+    let reaction_id = world.component_id::<ReactionCell>();
+
+    let mut stats = CellStats::default();
+    for archetype in world.archetypes().iter() {
+        let count = |id: Option<ComponentId>| id.is_some_and(|id| archetype.contains(id)) as usize;
+        let counts = CellCounts {
+            mutables: count(mutable_id),
+            deriveds: count(derived_id),
+            reactions: count(reaction_id),
+            callbacks: 0,
+        };
+        if counts.total() == 0 {
+            continue;
+        }
+        for entity in archetype.entities() {
+            let entity = entity.id();
+            match world.get::<Parent>(entity).map(|parent| parent.get()) {
+                Some(owner) if world.get_entity(owner).is_ok() => {
+                    stats.owners.entry(owner).or_default().add(&counts);
+                }
+                Some(_) => stats.orphans.push(entity),
+                None => stats.unowned.add(&counts),
+            }
+            stats.total.add(&counts);
+        }
+    }
+
+    // Callbacks are owned by the entity holding the `CallbackOwner`.
+    if let Some(callback_owner_id) = world.component_id::<CallbackOwner>() {
+        for archetype in world.archetypes().iter() {
+            if !archetype.contains(callback_owner_id) {
+                continue;
+            }
+            for entity in archetype.entities() {
+                let owner = entity.id();
+                let Some(callbacks) = world.get::<CallbackOwner>(owner) else {
+                    continue;
+                };
+                stats.owners.entry(owner).or_default().callbacks += callbacks.len();
+                stats.total.callbacks += callbacks.len();
+            }
+        }
+    }
+
+    stats
+}
+
+/// Periodically check for leaked cells.
+pub(crate) fn detect_leaks(world: &mut World) {
+    let Some(detector) = world.get_resource::<LeakDetector>() else {
+        return;
+    };
+    if detector
+        .last_check
+        .is_some_and(|last_check| last_check.elapsed() < detector.interval)
+    {
+        return;
+    }
+
+    let stats = collect_cell_stats(world);
+    if !stats.orphans.is_empty() {
+        warn!(
+            "Found {} reactive cells whose owner has been despawned: {:?}",
+            stats.orphans.len(),
+            stats.orphans
+        );
+    }
+    debug!(
+        "Reactive cells: {} mutables, {} deriveds, {} reactions, {} callbacks, {} owners",
+        stats.total.mutables,
+        stats.total.deriveds,
+        stats.total.reactions,
+        stats.total.callbacks,
+        stats.owners.len()
+    );
+
+    let mut detector = world.resource_mut::<LeakDetector>();
+    detector.stats = stats;
+    detector.last_check = Some(Instant::now());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{create_mutable, CreateMutable};
+
+    #[test]
+    fn test_cell_stats() {
+        let mut world = World::default();
+        let owner = world.spawn_empty().id();
+        create_mutable(&mut world, owner, 0);
+        create_mutable(&mut world, owner, "a");
+        world.create_mutable(1.0f32);
+
+        let stats = collect_cell_stats(&world);
+        assert_eq!(stats.owners[&owner].mutables, 2);
+        assert_eq!(stats.unowned.mutables, 1);
+        assert_eq!(stats.total.mutables, 3);
+        assert!(stats.orphans.is_empty());
+
+        // Despawning the owner without its children leaks them.
+        world.despawn(owner);
+        let stats = collect_cell_stats(&world);
+        assert!(stats.owners.is_empty());
+        assert_eq!(stats.orphans.len(), 2);
+    }
+}
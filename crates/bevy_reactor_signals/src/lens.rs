@@ -0,0 +1,128 @@
+use std::marker::PhantomData;
+
+use bevy::{prelude::*, ui::experimental::GhostNode};
+
+use crate::{mutable::MutableCell, Mutable, Reaction, ReactionCell, TrackingScope};
+
+/// Reaction which keeps a lens mutable in sync with a field of its source mutable.
+struct LensReaction<S, T, G, M> {
+    source: Mutable<S>,
+    get: G,
+    get_mut: M,
+
+    /// The value of the projected field as of the last synchronization.
+    last: T,
+}
+
+impl<S, T, G, M> Reaction for LensReaction<S, T, G, M>
+where
+    S: Send + Sync + 'static,
+    T: PartialEq + Clone + Send + Sync + 'static,
+    G: Fn(&S) -> &T + Send + Sync + 'static,
+    M: Fn(&mut S) -> &mut T + Send + Sync + 'static,
+{
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        tracking.track_component_id(self.source.cell, self.source.component);
+        tracking.track_component::<MutableCell<T>>(owner, world);
+
+        let lens_value = world.get::<MutableCell<T>>(owner).unwrap().0.clone();
+        if lens_value != self.last {
+            // The lens was written to, so propagate the value back to the source.
+            let mut source = world.get_mut::<MutableCell<S>>(self.source.cell).unwrap();
+            if *(self.get)(&source.0) != lens_value {
+                *(self.get_mut)(&mut source.0) = lens_value.clone();
+            }
+            self.last = lens_value;
+        } else {
+            // Otherwise, copy the projected field from the source, but only if it changed.
+            let source = world.get::<MutableCell<S>>(self.source.cell).unwrap();
+            let source_value = (self.get)(&source.0);
+            if *source_value != self.last {
+                let source_value = source_value.clone();
+                world.get_mut::<MutableCell<T>>(owner).unwrap().0 = source_value.clone();
+                self.last = source_value;
+            }
+        }
+    }
+}
+
+impl<S> Mutable<S>
+where
+    S: Send + Sync + 'static,
+{
+    /// Create a new [`Mutable`] which projects a single field of this one. Subscribers to the
+    /// lens are only notified when the projected field changes, rather than whenever any part
+    /// of the source value changes. Writes to the lens are propagated back to the source.
+    ///
+    /// The lens is owned by the source mutable, and will be despawned along with it.
+    ///
+    /// Arguments:
+    /// * `world`: The Bevy world.
+    /// * `get`: Function which returns a reference to the projected field.
+    /// * `get_mut`: Function which returns a mutable reference to the projected field.
+    pub fn lens<T, G, M>(&self, world: &mut World, get: G, get_mut: M) -> Mutable<T>
+    where
+        T: PartialEq + Clone + Send + Sync + 'static,
+        G: Fn(&S) -> &T + Send + Sync + 'static,
+        M: Fn(&mut S) -> &mut T + Send + Sync + 'static,
+    {
+        let init = get(&world.get::<MutableCell<S>>(self.cell).unwrap().0).clone();
+        let lens = world
+            .spawn((MutableCell::<T>(init.clone()), GhostNode::default()))
+            .set_parent(self.cell)
+            .id();
+        let component = world.register_component::<MutableCell<T>>();
+
+        let mut reaction = LensReaction {
+            source: *self,
+            get,
+            get_mut,
+            last: init,
+        };
+        let mut scope = TrackingScope::new(world.last_change_tick());
+        reaction.react(lens, world, &mut scope);
+        world
+            .entity_mut(lens)
+            .insert((scope, ReactionCell::new(reaction)));
+
+        Mutable {
+            cell: lens,
+            component,
+            marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tracking_scope::run_reactions, CreateMutable};
+
+    use super::*;
+
+    #[derive(Clone, PartialEq)]
+    struct TestState {
+        a: i32,
+        b: i32,
+    }
+
+    #[test]
+    fn test_lens() {
+        let mut world = World::default();
+        let source = world.create_mutable(TestState { a: 0, b: 0 });
+        let lens = source.lens(&mut world, |s| &s.a, |s| &mut s.a);
+        assert_eq!(lens.get(&world), 0);
+
+        // Changes to the source are propagated to the lens.
+        world.increment_change_tick();
+        source.set_clone(&mut world, TestState { a: 1, b: 2 });
+        run_reactions(&mut world);
+        assert_eq!(lens.get(&world), 1);
+
+        // Changes to the lens are propagated to the source.
+        world.increment_change_tick();
+        lens.set(&mut world, 3);
+        run_reactions(&mut world);
+        assert_eq!(source.as_ref(&mut world).a, 3);
+        assert_eq!(source.as_ref(&mut world).b, 2);
+    }
+}
@@ -1,11 +1,19 @@
 //! Implementation of the reactive signals pattern for Bevy.
 #![warn(missing_docs)]
 
-use bevy::app::{App, Plugin, Update};
+use bevy::{
+    app::{App, Plugin, SubApp, Update},
+    ecs::schedule::ScheduleLabel,
+    log::warn,
+    prelude::{IntoSystemConfigs, Resource, World},
+};
 
 mod callback;
+mod channel;
 mod derived;
 mod ecx;
+mod leak_detector;
+mod lens;
 mod mutable;
 mod rcx;
 mod reaction;
@@ -13,25 +21,115 @@ mod signal;
 mod tracking_scope;
 
 use callback::cleanup_callbacks;
-pub use callback::{Callback, CallbackOwner, RunCallback};
-pub use derived::{create_derived, Derived, ReadDerived};
+pub use callback::{Callback, CallbackOwner, RunCallback, WeakCallback};
+use channel::poll_channels;
+pub use channel::{signal_from_channel, ChannelClosed, ChannelReceiver, ChannelSender};
+pub use derived::{
+    create_derived, create_derived_eq, create_derived_with_eq, Derived, ReadDerived,
+};
 pub use ecx::Ecx;
-pub use mutable::{create_mutable, CreateMutable, Mutable, ReadMutable, WriteMutable};
+use leak_detector::detect_leaks;
+pub use leak_detector::{collect_cell_stats, CellCounts, CellStats, LeakDetector};
+use mutable::MutableTransaction;
+pub use mutable::{
+    create_mutable, BatchMutations, CreateMutable, Mutable, ReadMutable, WriteMutable,
+};
 pub use rcx::Rcx;
 pub use reaction::*;
 pub use signal::IntoSignal;
 pub use signal::Signal;
+pub use tracking_scope::ReactionBudget;
+pub use tracking_scope::ReactionPriority;
 pub use tracking_scope::TrackingScope;
 pub use tracking_scope::TrackingScopeTracing;
 use tracking_scope::{cleanup_tracking_scopes, run_reactions};
 
-/// Plugin that adds the reactive UI system to the app.
+/// Plugin that adds the reactive UI system to the app. This installs the reaction runner in
+/// the main app's [`Update`] schedule; use [`InstallSignals`] to install it in a sub-app, or in
+/// a different schedule.
 pub struct SignalsPlugin;
 
 impl Plugin for SignalsPlugin {
     fn build(&self, app: &mut App) {
-        cleanup_tracking_scopes(app.world_mut());
-        cleanup_callbacks(app.world_mut());
-        app.add_systems(Update, run_reactions);
+        app.install_signals(Update);
+    }
+}
+
+/// Resource which records that the reaction runner has been installed in a world.
+#[derive(Resource, Default)]
+struct SignalsInstalled;
+
+/// Extension trait for installing the reaction runner in an [`App`] or [`SubApp`].
+///
+/// Each world has its own independent set of signals, reactions and callbacks: the handles
+/// returned by `create_mutable`, `create_derived` and so on are entity ids in the world that
+/// created them, and must not be used with another world. Reactions created in a sub-app's
+/// world only run if the runner has been installed in that sub-app.
+pub trait InstallSignals {
+    /// Install the reaction runner, running it in `schedule`. Installing it more than once in
+    /// the same world does nothing, other than log a warning.
+    fn install_signals(&mut self, schedule: impl ScheduleLabel) -> &mut Self;
+}
+
+impl InstallSignals for SubApp {
+    fn install_signals(&mut self, schedule: impl ScheduleLabel) -> &mut Self {
+        if init_signals_world(self.world_mut()) {
+            self.add_systems(
+                schedule,
+                (poll_channels, run_reactions, detect_leaks).chain(),
+            );
+        }
+        self
+    }
+}
+
+impl InstallSignals for App {
+    fn install_signals(&mut self, schedule: impl ScheduleLabel) -> &mut Self {
+        self.main_mut().install_signals(schedule);
+        self
+    }
+}
+
+/// Set up the hooks and resources needed by signals in `world`. Returns false if they were
+/// already set up; registering the component hooks a second time would panic.
+fn init_signals_world(world: &mut World) -> bool {
+    if world.contains_resource::<SignalsInstalled>() {
+        warn!("Signals are already installed in this world");
+        return false;
+    }
+    world.init_resource::<SignalsInstalled>();
+    cleanup_tracking_scopes(world);
+    cleanup_callbacks(world);
+    world.init_resource::<MutableTransaction>();
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::app::{AppLabel, PostUpdate};
+
+    #[derive(AppLabel, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    struct EditorApp;
+
+    #[test]
+    fn test_install_in_sub_app() {
+        let mut app = App::new();
+        app.add_plugins(SignalsPlugin);
+        app.insert_sub_app(EditorApp, SubApp::new());
+        app.sub_app_mut(EditorApp).install_signals(PostUpdate);
+        assert!(app.world().contains_resource::<MutableTransaction>());
+        assert!(app
+            .sub_app(EditorApp)
+            .world()
+            .contains_resource::<MutableTransaction>());
+    }
+
+    #[test]
+    fn test_install_twice() {
+        let mut app = App::new();
+        app.add_plugins(SignalsPlugin);
+        // Must not panic by registering the component hooks again.
+        app.install_signals(PostUpdate);
     }
 }
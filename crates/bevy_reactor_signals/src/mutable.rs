@@ -1,16 +1,42 @@
-use std::marker::PhantomData;
+use std::{
+    marker::PhantomData,
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+};
 
-use crate::signal::Signal;
+use crate::{signal::Signal, Ecx};
 use bevy::{
     ecs::{component::ComponentId, world::DeferredWorld},
     prelude::*,
     ui::experimental::GhostNode,
+    utils::HashSet,
 };
 
 /// Contains a mutable reactive value.
 #[derive(Component)]
+#[require(MutableMarker)]
 pub(crate) struct MutableCell<T>(pub(crate) T);
 
+/// Marker which accompanies every [`MutableCell`], so that mutables of any type can be found
+/// by a single component id.
+#[derive(Component, Default)]
+pub(crate) struct MutableMarker;
+
+type DeferredWrite = Box<dyn FnOnce(&mut DeferredWorld) + Send + Sync>;
+
+/// Resource which holds the writes that are pending during a [`BatchMutations::batch`] call.
+#[derive(Resource, Default)]
+pub(crate) struct MutableTransaction {
+    /// Nesting depth of batches; writes are only applied when the outermost batch ends.
+    depth: usize,
+
+    /// Writes which have been deferred until the end of the batch.
+    writes: Vec<DeferredWrite>,
+
+    /// Cells which have a deferred write. Writes to other cells can be discarded immediately
+    /// if they don't change the value.
+    pending: HashSet<Entity>,
+}
+
 /// Contains a reference to a reactive mutable variable.
 #[derive(PartialEq, Debug)]
 pub struct Mutable<T> {
@@ -43,9 +69,30 @@ where
 {
     /// Update a mutable value in place using a callback. The callback is passed a
     /// `Mut<T>` which can be used to modify the value.
-    pub fn update<W: WriteMutable, F: FnOnce(Mut<T>)>(&self, w: &mut W, updater: F) {
+    pub fn update<W: WriteMutable, F: FnOnce(Mut<T>) + Send + Sync + 'static>(
+        &self,
+        w: &mut W,
+        updater: F,
+    ) {
         w.update_mutable(self.id(), updater);
     }
+
+    /// Modify the value in place, without cloning it, and mark it as changed. This is the
+    /// most efficient way to edit large values such as collections. Unlike `set`, dependents
+    /// are always notified, even if the value ends up the same.
+    pub fn update_mut<W: WriteMutable, F: FnOnce(&mut T) + Send + Sync + 'static>(
+        &self,
+        w: &mut W,
+        updater: F,
+    ) {
+        w.update_mutable(self.id(), |mut value: Mut<T>| updater(&mut value));
+    }
+
+    /// Mark the value as changed, notifying any dependents, without modifying it. This is an
+    /// escape hatch for values which have been changed through interior mutability.
+    pub fn mark_changed<W: WriteMutable>(&self, w: &mut W) {
+        w.update_mutable(self.id(), |mut value: Mut<T>| value.set_changed());
+    }
 }
 
 impl<T> Mutable<T>
@@ -128,6 +175,108 @@ pub fn create_mutable<T: Send + Sync + 'static>(
     }
 }
 
+/// Write the value of a mutable cell, or defer the write if a batch is in progress.
+fn write_or_defer<T>(world: &mut DeferredWorld, mutable: Entity, value: T)
+where
+    T: Send + Sync + PartialEq + 'static,
+{
+    let Some(transaction) = world.get_resource::<MutableTransaction>() else {
+        write_cell(world, mutable, value);
+        return;
+    };
+    if transaction.depth == 0 {
+        write_cell(world, mutable, value);
+        return;
+    }
+
+    // Skip writes which don't change the value, unless an earlier write in the batch has
+    // already changed it, in which case the order of writes matters.
+    if !transaction.pending.contains(&mutable)
+        && world
+            .get::<MutableCell<T>>(mutable)
+            .is_some_and(|cell| cell.0 == value)
+    {
+        return;
+    }
+    let mut transaction = world.resource_mut::<MutableTransaction>();
+    transaction.pending.insert(mutable);
+    transaction
+        .writes
+        .push(Box::new(move |world| write_cell(world, mutable, value)));
+}
+
+/// Update a mutable cell in place, or defer the update if a batch is in progress.
+fn update_or_defer<T, F>(world: &mut DeferredWorld, mutable: Entity, updater: F)
+where
+    T: Send + Sync + 'static,
+    F: FnOnce(Mut<T>) + Send + Sync + 'static,
+{
+    let in_batch = world
+        .get_resource::<MutableTransaction>()
+        .is_some_and(|transaction| transaction.depth > 0);
+    if !in_batch {
+        update_cell(world, mutable, updater);
+        return;
+    }
+    let mut transaction = world.resource_mut::<MutableTransaction>();
+    transaction.pending.insert(mutable);
+    transaction
+        .writes
+        .push(Box::new(move |world| update_cell(world, mutable, updater)));
+}
+
+fn update_cell<T, F>(world: &mut DeferredWorld, mutable: Entity, updater: F)
+where
+    T: Send + Sync + 'static,
+    F: FnOnce(Mut<T>),
+{
+    let value = world.get_mut::<MutableCell<T>>(mutable).unwrap();
+    updater(value.map_unchanged(|v| &mut v.0));
+}
+
+fn write_cell<T>(world: &mut DeferredWorld, mutable: Entity, value: T)
+where
+    T: Send + Sync + PartialEq + 'static,
+{
+    let mut entt = world.entity_mut(mutable);
+    let mut cell = entt.get_mut::<MutableCell<T>>().unwrap();
+    if cell.0 != value {
+        cell.0 = value;
+    }
+}
+
+/// Begin a batch. Returns the number of writes deferred before the batch began, or `None` if
+/// batching is not available.
+fn begin_batch(world: &mut DeferredWorld) -> Option<usize> {
+    let mut transaction = world.get_resource_mut::<MutableTransaction>()?;
+    transaction.depth += 1;
+    Some(transaction.writes.len())
+}
+
+/// End a batch, applying all of the deferred writes if this was the outermost batch.
+fn end_batch(world: &mut DeferredWorld) {
+    let mut transaction = world.resource_mut::<MutableTransaction>();
+    transaction.depth -= 1;
+    if transaction.depth == 0 {
+        transaction.pending.clear();
+        let writes = std::mem::take(&mut transaction.writes);
+        for write in writes {
+            write(world);
+        }
+    }
+}
+
+/// End a batch whose closure panicked, discarding the writes it deferred. Writes deferred by
+/// enclosing batches are kept.
+fn abort_batch(world: &mut DeferredWorld, start: usize) {
+    let mut transaction = world.resource_mut::<MutableTransaction>();
+    transaction.depth -= 1;
+    transaction.writes.truncate(start);
+    if transaction.depth == 0 {
+        transaction.pending.clear();
+    }
+}
+
 /// Trait for low-level read-access to mutables given an entity id.
 pub trait ReadMutable {
     /// Read the value of a mutable variable using Copy semantics. Calling this function adds the
@@ -162,12 +311,30 @@ pub trait WriteMutable {
         T: Send + Sync + PartialEq + 'static;
 
     /// Update a mutable value in place using a callback. The callback is passed a
-    /// `Mut<T>` which can be used to modify the value.
-    fn update_mutable<T, F: FnOnce(Mut<T>)>(&mut self, mutable: Entity, updater: F)
-    where
+    /// `Mut<T>` which can be used to modify the value. Within a batch, the callback is
+    /// deferred along with the other writes.
+    fn update_mutable<T, F: FnOnce(Mut<T>) + Send + Sync + 'static>(
+        &mut self,
+        mutable: Entity,
+        updater: F,
+    ) where
         T: Send + Sync + 'static;
 }
 
+/// Trait for grouping multiple mutable writes into a single transaction.
+pub trait BatchMutations {
+    /// Run the closure, deferring all mutable writes made within it until it returns. The
+    /// writes are then applied together, so that each dependent tracking scope is notified
+    /// only once, and never observes a partially-updated state. Batches may be nested, in which
+    /// case the writes are applied when the outermost batch completes.
+    ///
+    /// Note that reads within the batch will see the values from before the batch started.
+    /// In-place updates made with [`WriteMutable::update_mutable`] are deferred too, and are
+    /// applied in order with the other writes. If `f` panics, the writes it made are discarded
+    /// and the batch is ended before the panic continues.
+    fn batch<F: FnOnce(&mut Self)>(&mut self, f: F);
+}
+
 /// Trait for creating new mutable variables.
 pub trait CreateMutable {
     /// Create a new [`Mutable`].
@@ -234,20 +401,55 @@ impl WriteMutable for World {
     where
         T: Send + Sync + PartialEq + 'static,
     {
-        let mut entt = self.entity_mut(mutable);
-        let mut cell = entt.get_mut::<MutableCell<T>>().unwrap();
-        if cell.0 != value {
-            cell.0 = value;
-        }
+        write_or_defer(&mut DeferredWorld::from(self), mutable, value);
     }
 
-    fn update_mutable<T, F: FnOnce(Mut<T>)>(&mut self, mutable: Entity, updater: F)
-    where
+    fn update_mutable<T, F: FnOnce(Mut<T>) + Send + Sync + 'static>(
+        &mut self,
+        mutable: Entity,
+        updater: F,
+    ) where
         T: Send + Sync + 'static,
     {
-        let value = self.get_mut::<MutableCell<T>>(mutable).unwrap();
-        let inner = value.map_unchanged(|v| &mut v.0);
-        (updater)(inner);
+        update_or_defer(&mut DeferredWorld::from(self), mutable, updater);
+    }
+}
+
+impl BatchMutations for World {
+    fn batch<F: FnOnce(&mut Self)>(&mut self, f: F) {
+        self.init_resource::<MutableTransaction>();
+        let start = begin_batch(&mut DeferredWorld::from(&mut *self)).unwrap();
+        if let Err(payload) = catch_unwind(AssertUnwindSafe(|| f(self))) {
+            abort_batch(&mut DeferredWorld::from(self), start);
+            resume_unwind(payload);
+        }
+        end_batch(&mut DeferredWorld::from(self));
+    }
+}
+
+impl<'w> BatchMutations for DeferredWorld<'w> {
+    fn batch<F: FnOnce(&mut Self)>(&mut self, f: F) {
+        let Some(start) = begin_batch(self) else {
+            f(self);
+            return;
+        };
+        if let Err(payload) = catch_unwind(AssertUnwindSafe(|| f(self))) {
+            abort_batch(self, start);
+            resume_unwind(payload);
+        }
+        end_batch(self);
+    }
+}
+
+impl<'p, 'w> BatchMutations for Ecx<'p, 'w> {
+    fn batch<F: FnOnce(&mut Self)>(&mut self, f: F) {
+        self.world.init_resource::<MutableTransaction>();
+        let start = begin_batch(&mut DeferredWorld::from(&mut *self.world)).unwrap();
+        if let Err(payload) = catch_unwind(AssertUnwindSafe(|| f(self))) {
+            abort_batch(&mut DeferredWorld::from(&mut *self.world), start);
+            resume_unwind(payload);
+        }
+        end_batch(&mut DeferredWorld::from(&mut *self.world));
     }
 }
 
@@ -307,20 +509,17 @@ impl<'w> WriteMutable for DeferredWorld<'w> {
     where
         T: Send + Sync + PartialEq + 'static,
     {
-        let mut entt = self.entity_mut(mutable);
-        let mut cell = entt.get_mut::<MutableCell<T>>().unwrap();
-        if cell.0 != value {
-            cell.0 = value;
-        }
+        write_or_defer(self, mutable, value);
     }
 
-    fn update_mutable<T, F: FnOnce(Mut<T>)>(&mut self, mutable: Entity, updater: F)
-    where
+    fn update_mutable<T, F: FnOnce(Mut<T>) + Send + Sync + 'static>(
+        &mut self,
+        mutable: Entity,
+        updater: F,
+    ) where
         T: Send + Sync + 'static,
     {
-        let value = self.get_mut::<MutableCell<T>>(mutable).unwrap();
-        let inner = value.map_unchanged(|v| &mut v.0);
-        (updater)(inner);
+        update_or_defer(self, mutable, updater);
     }
 }
 
@@ -362,6 +561,132 @@ mod tests {
         assert_eq!(reader2.get(&rcx), 0);
     }
 
+    #[test]
+    fn test_mutable_batch() {
+        let mut world = World::default();
+        let a = world.create_mutable::<i32>(0);
+        let b = world.create_mutable::<i32>(0);
+
+        world.batch(|world| {
+            a.set(world, 1);
+            world.batch(|world| {
+                b.set(world, 2);
+            });
+
+            // Writes are deferred until the outermost batch completes.
+            assert_eq!(a.get(world), 0);
+            assert_eq!(b.get(world), 0);
+        });
+
+        assert_eq!(a.get(&world), 1);
+        assert_eq!(b.get(&world), 2);
+    }
+
+    #[test]
+    fn test_mutable_batch_panic() {
+        let mut world = World::default();
+        let a = world.create_mutable::<i32>(0);
+        let b = world.create_mutable::<i32>(0);
+
+        world.batch(|world| {
+            a.set(world, 1);
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                world.batch(|world| {
+                    b.set(world, 2);
+                    panic!("inside batch");
+                })
+            }));
+            assert!(result.is_err());
+        });
+
+        // The write from the panicking batch is discarded, the enclosing one is applied.
+        assert_eq!(a.get(&world), 1);
+        assert_eq!(b.get(&world), 0);
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            world.batch(|world| {
+                a.set(world, 3);
+                panic!("inside batch");
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(world.resource::<MutableTransaction>().depth, 0);
+
+        // Writes outside of a batch are applied immediately again.
+        b.set(&mut world, 4);
+        assert_eq!(a.get(&world), 1);
+        assert_eq!(b.get(&world), 4);
+    }
+
+    #[test]
+    fn test_mutable_update_mut() {
+        let mut world = World::default();
+        let mutable = world.create_mutable::<Vec<String>>(vec!["a".to_string()]);
+        let changed = |world: &World, tick| {
+            world
+                .entity(mutable.id())
+                .get_change_ticks::<MutableCell<Vec<String>>>()
+                .unwrap()
+                .is_changed(tick, world.change_tick())
+        };
+
+        let tick = world.change_tick();
+        world.increment_change_tick();
+        mutable.update_mut(&mut world, |v| v.push("b".to_string()));
+        assert_eq!(mutable.get_clone(&mut world), vec!["a", "b"]);
+        assert!(changed(&world, tick));
+
+        let tick = world.change_tick();
+        world.increment_change_tick();
+        assert!(!changed(&world, tick));
+        mutable.mark_changed(&mut world);
+        assert!(changed(&world, tick));
+        assert_eq!(mutable.get_clone(&mut world).len(), 2);
+    }
+
+    #[test]
+    fn test_mutable_batch_update_mut() {
+        let mut world = World::default();
+        let list = world.create_mutable::<Vec<i32>>(vec![1]);
+        let count = world.create_mutable::<i32>(0);
+
+        world.batch(|world| {
+            list.update_mut(world, |v| v.push(2));
+            count.set(world, 2);
+            // In-place updates are deferred like other writes.
+            assert_eq!(list.get_clone(world), vec![1]);
+            list.update_mut(world, |v| v.push(3));
+            list.set_clone(world, vec![0]);
+            list.update_mut(world, |v| v.push(4));
+        });
+        // All writes are applied in the order they were made.
+        assert_eq!(list.get_clone(&mut world), vec![0, 4]);
+        assert_eq!(count.get(&world), 2);
+    }
+
+    #[test]
+    fn test_mutable_batch_unchanged_writes() {
+        let mut world = World::default();
+        let a = world.create_mutable::<i32>(0);
+        let b = world.create_mutable::<i32>(0);
+
+        // Writes which don't change the value are not queued.
+        world.batch(|world| {
+            a.set(world, 0);
+            b.set(world, 0);
+            assert!(world.resource::<MutableTransaction>().writes.is_empty());
+        });
+
+        // But once a cell has a pending write, later writes must be kept in order.
+        world.batch(|world| {
+            a.set(world, 1);
+            a.set(world, 0);
+            b.set(world, 1);
+        });
+        assert_eq!(a.get(&world), 0);
+        assert_eq!(b.get(&world), 1);
+    }
+
     #[test]
     fn test_mutable_clone() {
         let mut world = World::default();
@@ -1,4 +1,7 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    any::Any,
+    sync::{Arc, Mutex},
+};
 
 use bevy::ecs::{component::Component, entity::Entity, world::World};
 
@@ -33,6 +36,32 @@ impl ReactionCell {
     }
 }
 
+/// Component which catches panics in the reactions of its descendants. If a reaction panics,
+/// the handler on the nearest ancestor is called with the ancestor entity and the panic
+/// message, rather than the panic taking down the app. The handler will typically despawn
+/// the subtree containing the failed reaction. If there is no handler, the panic is resumed.
+#[derive(Component, Clone)]
+#[allow(clippy::type_complexity)]
+pub struct ReactionPanicHandler(pub Arc<dyn Fn(&mut World, Entity, String) + Send + Sync>);
+
+impl ReactionPanicHandler {
+    /// Construct a new [`ReactionPanicHandler`].
+    pub fn new<F: Fn(&mut World, Entity, String) + Send + Sync + 'static>(handler: F) -> Self {
+        Self(Arc::new(handler))
+    }
+}
+
+/// Extract the message from a panic payload, as returned by `catch_unwind`.
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Unknown panic".to_string()
+    }
+}
+
 // /// Command which performs the initial (startup) reaction.
 // pub struct InitialReactionCommand(Entity);
 
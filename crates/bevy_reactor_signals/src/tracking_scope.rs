@@ -1,15 +1,25 @@
-use std::sync::atomic::AtomicBool;
+use std::{
+    any::Any,
+    panic::AssertUnwindSafe,
+    sync::{
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
 
 use bevy::{
     ecs::{
+        archetype::ArchetypeId,
         component::{ComponentId, Tick},
+        query::{QueryData, QueryFilter, QueryState, ReadOnlyQueryData},
         world::DeferredWorld,
     },
     prelude::*,
-    utils::HashSet,
+    utils::{HashSet, Instant},
 };
 
-use crate::ReactionCell;
+use crate::{panic_message, ReactionCell, ReactionPanicHandler};
 
 /// A component that tracks the dependencies of a reactive task.
 #[derive(Component)]
@@ -20,6 +30,21 @@ pub struct TrackingScope {
     /// Set of resources that we are currently subscribed to.
     resource_deps: HashSet<ComponentId>,
 
+    /// List of fine-grained component dependencies, which are only considered changed if the
+    /// extracted value differs from the value seen during the previous reaction.
+    mapped_deps: Vec<MappedComponentDep>,
+
+    /// List of fine-grained resource dependencies, which are only considered changed if the
+    /// projected value differs from the value seen during the previous reaction.
+    mapped_resource_deps: Vec<MappedResourceDep>,
+
+    /// Dependencies on equality-gated deriveds, which are only considered changed if the
+    /// derived's value differs from the value seen during the previous reaction.
+    derived_deps: Vec<DerivedDep>,
+
+    /// Dependencies on the set of entities matched by a query.
+    query_deps: Vec<QueryDep>,
+
     /// Allows a tracking scope to be explictly marked as changed for reasons other than
     /// a component or resource dependency mutation.
     changed: AtomicBool,
@@ -40,6 +65,88 @@ pub struct TrackingScope {
     pub(crate) cleanups: Vec<Box<dyn FnOnce(&mut DeferredWorld) + 'static + Sync + Send>>,
 }
 
+/// A component dependency which compares an extracted value rather than relying on the
+/// component's change tick alone.
+struct MappedComponentDep {
+    entity: Entity,
+    component: ComponentId,
+
+    /// Tick at which the extracted value was last found to be unchanged. Component changes
+    /// before this tick have already been compared, so they don't need to be compared again.
+    checked: AtomicU32,
+
+    /// Returns true if the extracted value differs from the cached value.
+    #[allow(clippy::type_complexity)]
+    differs: Box<dyn Fn(&World) -> bool + Send + Sync>,
+}
+
+/// A resource dependency which compares a projected value rather than relying on the
+/// resource's change tick alone.
+struct MappedResourceDep {
+    resource: ComponentId,
+
+    /// Tick at which the projected value was last found to be unchanged.
+    checked: AtomicU32,
+
+    /// Returns true if the projected value differs from the cached value.
+    #[allow(clippy::type_complexity)]
+    differs: Box<dyn Fn(&World) -> bool + Send + Sync>,
+}
+
+/// A dependency on an equality-gated derived signal. The derived's own dependencies are kept
+/// separately, so that they can be checked as a group. When they change but the derived's value
+/// doesn't, they are replaced by the dependencies of the recomputation, so that the derived
+/// isn't recomputed again until one of its inputs changes.
+struct DerivedDep {
+    deps: Mutex<TrackingScope>,
+
+    /// Recomputes the derived, recording its dependencies in the given scope, and returns true
+    /// if its value differs from the cached value.
+    #[allow(clippy::type_complexity)]
+    differs: Box<dyn Fn(&World, &mut TrackingScope) -> bool + Send + Sync>,
+}
+
+/// A dependency on the set of entities matched by a query.
+struct QueryDep {
+    /// Re-runs the query, and returns true if the matching entities differ from the cached list.
+    #[allow(clippy::type_complexity)]
+    differs: Box<dyn Fn(&World) -> bool + Send + Sync>,
+}
+
+/// A summary of the entities which may match a query. An entity can only start or stop matching
+/// by being spawned, despawned, or moved between archetypes, and each of these changes the set
+/// of entities in a matched archetype, even when an entity moving out and another moving in
+/// leave its length the same. Each matched archetype is summarized by its length and a checksum
+/// of its entity ids, which is much cheaper than fetching the query's data; the query is only
+/// re-run when the fingerprint changes.
+#[derive(PartialEq)]
+struct QueryFingerprint {
+    /// The archetypes matched by the query, with the number of entities in each and the
+    /// checksum of their ids.
+    archetypes: Vec<(ArchetypeId, usize, u64)>,
+}
+
+impl QueryFingerprint {
+    fn new<D: QueryData, F: QueryFilter>(world: &World, state: &QueryState<D, F>) -> Self {
+        Self {
+            archetypes: state
+                .matched_archetypes()
+                .map(|id| {
+                    let entities = world.archetypes()[id].entities();
+                    let checksum = entities.iter().fold(0u64, |sum, entity| {
+                        sum.wrapping_add(entity.id().to_bits().wrapping_mul(CHECKSUM_MIX))
+                    });
+                    (id, entities.len(), checksum)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Odd multiplier which spreads entity ids over the whole checksum, so that different sets of
+/// entities are unlikely to have the same sum.
+const CHECKSUM_MIX: u64 = 0x9e37_79b9_7f4a_7c15;
+
 /// A resource which, if inserted, displays the view entities that have reacted this frame.
 #[derive(Resource)]
 pub struct TrackingScopeTracing(pub Vec<Entity>);
@@ -56,6 +163,10 @@ impl TrackingScope {
         Self {
             component_deps: HashSet::default(),
             resource_deps: HashSet::default(),
+            mapped_deps: Vec::new(),
+            mapped_resource_deps: Vec::new(),
+            derived_deps: Vec::new(),
+            query_deps: Vec::new(),
             changed: AtomicBool::new(false),
             deferred_change: false,
             tick,
@@ -97,6 +208,127 @@ impl TrackingScope {
         self.component_deps.insert((entity, component));
     }
 
+    /// Add a fine-grained component dependency. The scope will only be considered changed
+    /// if the component changed *and* the value returned by `extract` differs from `prev`.
+    pub(crate) fn track_component_map<C: Component, U, F>(
+        &mut self,
+        entity: Entity,
+        world: &World,
+        extract: F,
+        prev: Option<U>,
+    ) where
+        U: PartialEq + Send + Sync + 'static,
+        F: Fn(&C) -> U + Send + Sync + 'static,
+    {
+        self.mapped_deps.push(MappedComponentDep {
+            entity,
+            checked: AtomicU32::new(self.tick.get()),
+            component: world
+                .components()
+                .component_id::<C>()
+                .expect("Unknown component type"),
+            differs: Box::new(move |world| {
+                world
+                    .get_entity(entity)
+                    .ok()
+                    .and_then(|e| e.get::<C>())
+                    .map(&extract)
+                    != prev
+            }),
+        });
+    }
+
+    /// Add a fine-grained resource dependency. The scope will only be considered changed
+    /// if the resource changed *and* the value returned by `project` differs from `prev`.
+    pub(crate) fn track_resource_map<T: Resource, U, F>(
+        &mut self,
+        world: &World,
+        project: F,
+        prev: U,
+    ) where
+        U: PartialEq + Send + Sync + 'static,
+        F: Fn(&T) -> U + Send + Sync + 'static,
+    {
+        self.mapped_resource_deps.push(MappedResourceDep {
+            checked: AtomicU32::new(self.tick.get()),
+            resource: world
+                .components()
+                .resource_id::<T>()
+                .expect("Unknown resource type"),
+            differs: Box::new(move |world| {
+                world
+                    .get_resource::<T>()
+                    .map_or(true, |res| project(res) != prev)
+            }),
+        });
+    }
+
+    /// Add a dependency on an equality-gated derived. `deps` holds the dependencies of the
+    /// derived; the scope will only be considered changed if one of them changed *and*
+    /// `differs` returns true.
+    pub(crate) fn track_derived(
+        &mut self,
+        mut deps: TrackingScope,
+        differs: impl Fn(&World, &mut TrackingScope) -> bool + Send + Sync + 'static,
+    ) {
+        self.cleanups.append(&mut deps.cleanups);
+        self.derived_deps.push(DerivedDep {
+            deps: Mutex::new(deps),
+            differs: Box::new(differs),
+        });
+    }
+
+    /// Add a dependency on a query. The scope will be considered changed if the set of
+    /// entities matching the query differs from `matched`, or if any of the components read by
+    /// the query changes on one of those entities. Queries which read every component, such as
+    /// `EntityRef`, only track membership.
+    pub(crate) fn track_query<D, F>(&mut self, world: &World, matched: Vec<Entity>)
+    where
+        D: ReadOnlyQueryData + 'static,
+        F: QueryFilter + 'static,
+    {
+        if let Some(state) = world.try_query_filtered::<D, F>() {
+            let (components, all) = state
+                .component_access()
+                .access()
+                .component_reads_and_writes();
+            if !all {
+                let components: Vec<ComponentId> = components.collect();
+                for entity in matched.iter() {
+                    for component in components.iter() {
+                        self.track_component_id(*entity, *component);
+                    }
+                }
+            }
+        }
+        let state = world.try_query_filtered::<(Entity, D), F>();
+        let fingerprint = state
+            .as_ref()
+            .map(|state| QueryFingerprint::new(world, state));
+        let cache = Mutex::new((state, fingerprint));
+        self.query_deps.push(QueryDep {
+            differs: Box::new(move |world| {
+                let mut cache = cache.lock().unwrap();
+                let (state, fingerprint) = &mut *cache;
+                if state.is_none() {
+                    // The query's components may have been registered since.
+                    *state = world.try_query_filtered::<(Entity, D), F>();
+                }
+                let Some(state) = state else {
+                    return !matched.is_empty();
+                };
+                state.update_archetypes(world);
+                let next = QueryFingerprint::new(world, state);
+                if fingerprint.as_ref() == Some(&next) {
+                    return false;
+                }
+                *fingerprint = Some(next);
+                let entities: Vec<Entity> = state.iter(world).map(|(entity, _)| entity).collect();
+                entities != matched
+            }),
+        });
+    }
+
     /// Mark the scope as changed for reasons other than a component or resource dependency.
     pub fn set_changed(&self) {
         self.changed
@@ -117,6 +349,10 @@ impl TrackingScope {
     pub fn dependencies_changed(&self, world: &World, tick: Tick) -> bool {
         self.components_changed(world, tick)
             || self.resources_changed(world, tick)
+            || self.mapped_components_changed(world, tick)
+            || self.mapped_resources_changed(world, tick)
+            || self.deriveds_changed(world, tick)
+            || self.queries_changed(world)
             || self.changed.load(std::sync::atomic::Ordering::Relaxed)
     }
 
@@ -130,6 +366,64 @@ impl TrackingScope {
         })
     }
 
+    fn mapped_components_changed(&self, world: &World, tick: Tick) -> bool {
+        self.mapped_deps.iter().any(|dep| {
+            let checked = Tick::new(dep.checked.load(Ordering::Relaxed));
+            let component_changed = world.get_entity(dep.entity).map_or(false, |e| {
+                e.get_change_ticks_by_id(dep.component)
+                    .map(|ct| ct.is_changed(checked, tick))
+                    .unwrap_or(false)
+            });
+            if !component_changed {
+                return false;
+            }
+            if (dep.differs)(world) {
+                return true;
+            }
+            dep.checked.store(tick.get(), Ordering::Relaxed);
+            false
+        })
+    }
+
+    fn mapped_resources_changed(&self, world: &World, tick: Tick) -> bool {
+        self.mapped_resource_deps.iter().any(|dep| {
+            let checked = Tick::new(dep.checked.load(Ordering::Relaxed));
+            let resource_changed = world
+                .get_resource_change_ticks_by_id(dep.resource)
+                .map(|ct| ct.is_changed(checked, tick))
+                .unwrap_or(false);
+            if !resource_changed {
+                return false;
+            }
+            if (dep.differs)(world) {
+                return true;
+            }
+            dep.checked.store(tick.get(), Ordering::Relaxed);
+            false
+        })
+    }
+
+    fn deriveds_changed(&self, world: &World, tick: Tick) -> bool {
+        self.derived_deps.iter().any(|dep| {
+            let mut deps = dep.deps.lock().unwrap();
+            if !deps.dependencies_changed(world, tick) {
+                return false;
+            }
+            let mut next = TrackingScope::new(tick);
+            if (dep.differs)(world, &mut next) {
+                return true;
+            }
+            // The value is unchanged, so move the baseline forward.
+            deps.take_deps(&mut next);
+            deps.tick = tick;
+            false
+        })
+    }
+
+    fn queries_changed(&self, world: &World) -> bool {
+        self.query_deps.iter().any(|dep| (dep.differs)(world))
+    }
+
     fn resources_changed(&self, world: &World, tick: Tick) -> bool {
         self.resource_deps.iter().any(|c| {
             world
@@ -144,7 +438,12 @@ impl TrackingScope {
     pub fn take_deps(&mut self, other: &mut Self) {
         self.component_deps = std::mem::take(&mut other.component_deps);
         self.resource_deps = std::mem::take(&mut other.resource_deps);
+        self.mapped_deps = std::mem::take(&mut other.mapped_deps);
+        self.mapped_resource_deps = std::mem::take(&mut other.mapped_resource_deps);
+        self.derived_deps = std::mem::take(&mut other.derived_deps);
+        self.query_deps = std::mem::take(&mut other.query_deps);
         self.cleanups = std::mem::take(&mut other.cleanups);
+        self.deferred_change = std::mem::take(&mut other.deferred_change);
     }
 }
 
@@ -176,14 +475,47 @@ fn run_cleanups(world: &mut World, changed: &[Entity]) {
 
 const MAX_DIVERGENCE_CT: usize = 32;
 
+/// Determines the order in which reactions are run when several are triggered at once.
+/// Reactions with a higher priority run first. Low-priority reactions, such as rebuilding a
+/// large list, may also be postponed to a later frame if the [`ReactionBudget`] is exceeded.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ReactionPriority {
+    /// Cheap reactions which should be applied as soon as possible.
+    High,
+    /// The priority of reactions with no [`ReactionPriority`] component.
+    #[default]
+    Normal,
+    /// Expensive reactions which can be delayed when the frame is busy.
+    Low,
+}
+
+/// Resource which limits how much time is spent running reactions each frame. The budget is
+/// checked before each cycle of reactions; once it is used up, the [`ReactionPriority::Low`]
+/// reactions of later cycles are postponed until the next frame, while other reactions always
+/// run. At least one low-priority reaction runs each frame, so that they make progress even when
+/// the budget is very small.
+#[derive(Resource, Clone, Copy, Debug, Default)]
+pub struct ReactionBudget {
+    /// Time after which low-priority reactions are postponed, or `None` for no limit.
+    pub low_priority: Option<Duration>,
+}
+
 /// Run reactions whose dependencies have changed. This uses a "run to convergence" strategy:
 /// running a reaction may trigger other reactions, so we loop until there are no more reactions
 /// left to run. However, to avoid an infinite loop we require that the reactions eventually
 /// reach a quiescent state. We count the number of "divergences" (cycles where the number
 /// of reactions didn't decrease) and impose a strict limit on the number of such cycles.
+///
+/// Within each cycle, reactions are run in order of [`ReactionPriority`].
 pub(crate) fn run_reactions(world: &mut World) {
     let is_tracing = world.get_resource_mut::<TrackingScopeTracing>().is_some();
+    let budget = world
+        .get_resource::<ReactionBudget>()
+        .and_then(|budget| budget.low_priority);
+    let start = Instant::now();
     let mut all_reactions: Vec<Entity> = Vec::new();
+    let mut postponed: HashSet<Entity> = HashSet::default();
+    let mut ran_low_priority = false;
     let mut iteration_ct: usize = 0;
     let mut divergence_ct: usize = 0;
     let mut prev_change_ct: usize = 0;
@@ -198,54 +530,93 @@ pub(crate) fn run_reactions(world: &mut World) {
         };
 
         // Find all tracking scopes that have changes.
-        let mut scopes = world.query::<(Entity, &mut TrackingScope, &ReactionCell)>();
-        let mut changed: Vec<Entity> = Vec::with_capacity(64);
-        for (entity, scope, _) in scopes.iter(world) {
+        let mut scopes = world.query::<(
+            Entity,
+            &mut TrackingScope,
+            &ReactionCell,
+            Option<&ReactionPriority>,
+        )>();
+        let mut changed: Vec<(Entity, ReactionPriority)> = Vec::with_capacity(64);
+        for (entity, scope, _, priority) in scopes.iter(world) {
+            if postponed.contains(&entity) {
+                continue;
+            }
             // We only test the 'always changed' flag the first time through the loop; otherwise
             // we would never get to convergence.
             if scope.dependencies_changed(world, this_run)
                 || (iteration_ct == 0 && scope.deferred_change)
             {
-                changed.push(entity);
+                changed.push((entity, priority.copied().unwrap_or_default()));
             }
         }
+        changed.sort_by_key(|(_, priority)| *priority);
 
-        // Quit if there are no changes.
-        if changed.is_empty() {
-            break;
-        }
-
-        // In debug mode, record the changed reactions in a resource.
-        if is_tracing {
-            all_reactions.extend(changed.clone());
+        // Decide which low-priority reactions to postpone. Postponed reactions keep their old
+        // tick, so they will still be out of date next frame.
+        let over_budget = budget.is_some_and(|budget| start.elapsed() >= budget);
+        let mut batch: Vec<Entity> = Vec::with_capacity(changed.len());
+        for (scope_entity, priority) in changed {
+            if priority == ReactionPriority::Low {
+                if ran_low_priority && over_budget {
+                    postponed.insert(scope_entity);
+                    continue;
+                }
+                ran_low_priority = true;
+            }
+            batch.push(scope_entity);
         }
 
-        // Run any registered cleanup functions.
-        run_cleanups(world, &changed);
+        // Run the cleanups of every reaction in the batch before running any of the reactions,
+        // so that no reaction observes a half-cleaned-up state.
+        run_cleanups(world, &batch);
 
-        // Run reactions
-        for scope_entity in changed.iter() {
-            // Run the reaction
-            if world.get_entity(*scope_entity).is_err() {
+        // Run reactions. Reactions which panicked are counted as having run, since they may
+        // have written to other reactions' dependencies before unwinding.
+        let mut ran: Vec<Entity> = Vec::with_capacity(batch.len());
+        let mut run_ct: usize = 0;
+        for scope_entity in batch {
+            if world.get_entity(scope_entity).is_err() {
                 continue;
             }
-            let Some(cell) = world.entity(*scope_entity).get::<ReactionCell>() else {
+            let Some(cell) = world.entity(scope_entity).get::<ReactionCell>() else {
                 continue;
             };
             let mut next_scope = TrackingScope::new(this_run);
             let inner = cell.0.clone();
             let mut lock = inner.lock().unwrap();
-            lock.react(*scope_entity, world, &mut next_scope);
+            let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                lock.react(scope_entity, world, &mut next_scope);
+            }));
+            drop(lock);
+            run_ct += 1;
 
-            // Replace deps and cleanups in the current scope with the next scope.
-            let (_, mut scope, _) = scopes.get_mut(world, *scope_entity).unwrap();
-            scope.take_deps(&mut next_scope);
-            scope.tick = this_run;
+            // Replace deps and cleanups in the current scope with the next scope. This is done
+            // even if the reaction panicked, using the deps it recorded before the panic, so
+            // that it doesn't run again until one of those changes.
+            if let Ok((_, mut scope, _, _)) = scopes.get_mut(world, scope_entity) {
+                scope.take_deps(&mut next_scope);
+                scope.tick = this_run;
+            }
+            if let Err(payload) = result {
+                handle_reaction_panic(world, scope_entity, payload);
+                continue;
+            }
+            ran.push(scope_entity);
+        }
+
+        // Quit if there are no changes.
+        if run_ct == 0 {
+            break;
+        }
+
+        // In debug mode, record the changed reactions in a resource.
+        if is_tracing {
+            all_reactions.extend(ran.iter().copied());
         }
 
         // Check for divergence.
         iteration_ct += 1;
-        let change_ct = changed.len();
+        let change_ct = run_ct;
         if change_ct >= prev_change_ct {
             divergence_ct += 1;
             if divergence_ct > MAX_DIVERGENCE_CT {
@@ -261,13 +632,35 @@ pub(crate) fn run_reactions(world: &mut World) {
     }
 }
 
+/// Pass a panic from a reaction to the nearest [`ReactionPanicHandler`], or resume it if
+/// there is none.
+fn handle_reaction_panic(world: &mut World, scope_entity: Entity, payload: Box<dyn Any + Send>) {
+    let mut entity = Some(scope_entity);
+    while let Some(e) = entity {
+        if let Some(handler) = world.get::<ReactionPanicHandler>(e) {
+            let handler = handler.0.clone();
+            handler(world, e, panic_message(payload.as_ref()));
+            return;
+        }
+        entity = world.get::<Parent>(e).map(|parent| parent.get());
+    }
+    std::panic::resume_unwind(payload);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Reaction;
 
     #[derive(Resource, Default)]
     struct TestResource(bool);
 
+    #[derive(Component, Default)]
+    struct TestComponent {
+        a: i32,
+        b: i32,
+    }
+
     #[test]
     fn test_resource_deps_changed() {
         let mut world = World::default();
@@ -296,4 +689,484 @@ mod tests {
         let tick = world.change_tick();
         assert!(scope.dependencies_changed(&world, tick));
     }
+
+    #[test]
+    fn test_mapped_component_deps_changed() {
+        let mut world = World::default();
+        let entity = world.spawn(TestComponent::default()).id();
+        let tick = world.change_tick();
+        let mut scope = TrackingScope::new(tick);
+        scope.track_component_map::<TestComponent, _, _>(entity, &world, |c| c.a, Some(0));
+        assert!(!scope.dependencies_changed(&world, tick));
+
+        // Mutate a field which is not extracted
+        world.increment_change_tick();
+        world.get_mut::<TestComponent>(entity).unwrap().b = 1;
+        let tick = world.change_tick();
+        assert!(!scope.dependencies_changed(&world, tick));
+
+        // Mutate the extracted field
+        world.increment_change_tick();
+        world.get_mut::<TestComponent>(entity).unwrap().a = 1;
+        let tick = world.change_tick();
+        assert!(scope.dependencies_changed(&world, tick));
+    }
+
+    #[test]
+    fn test_mapped_component_baseline_advances() {
+        let mut world = World::default();
+        let entity = world.spawn(TestComponent::default()).id();
+        let tick = world.change_tick();
+        let mut scope = TrackingScope::new(tick);
+        let calls = std::sync::Arc::new(AtomicU32::new(0));
+        let extract_calls = calls.clone();
+        scope.track_component_map::<TestComponent, _, _>(
+            entity,
+            &world,
+            move |c| {
+                extract_calls.fetch_add(1, Ordering::Relaxed);
+                c.a
+            },
+            Some(0),
+        );
+
+        // A change which leaves the extracted value equal is compared once.
+        world.increment_change_tick();
+        world.get_mut::<TestComponent>(entity).unwrap().b = 1;
+        let tick = world.change_tick();
+        assert!(!scope.dependencies_changed(&world, tick));
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+        // And not again on later checks.
+        world.increment_change_tick();
+        let tick = world.change_tick();
+        assert!(!scope.dependencies_changed(&world, tick));
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+        // A later change to the extracted value is still seen.
+        world.increment_change_tick();
+        world.get_mut::<TestComponent>(entity).unwrap().a = 1;
+        let tick = world.change_tick();
+        assert!(scope.dependencies_changed(&world, tick));
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+    }
+
+    #[derive(Resource, Default)]
+    struct TestResourceFields {
+        a: i32,
+        b: i32,
+    }
+
+    #[test]
+    fn test_mapped_resource_deps_changed() {
+        let mut world = World::default();
+        world.init_resource::<TestResourceFields>();
+        let tick = world.change_tick();
+        let mut scope = TrackingScope::new(tick);
+        scope.track_resource_map::<TestResourceFields, _, _>(&world, |r| r.a, 0);
+        assert!(!scope.dependencies_changed(&world, tick));
+
+        // Mutate a field which is not projected
+        world.increment_change_tick();
+        world.resource_mut::<TestResourceFields>().b = 1;
+        let tick = world.change_tick();
+        assert!(!scope.dependencies_changed(&world, tick));
+
+        // Mutate the projected field
+        world.increment_change_tick();
+        world.resource_mut::<TestResourceFields>().a = 1;
+        let tick = world.change_tick();
+        assert!(scope.dependencies_changed(&world, tick));
+    }
+
+    #[test]
+    fn test_mapped_resource_baseline_advances() {
+        let mut world = World::default();
+        world.init_resource::<TestResourceFields>();
+        let tick = world.change_tick();
+        let mut scope = TrackingScope::new(tick);
+        let calls = std::sync::Arc::new(AtomicU32::new(0));
+        let project_calls = calls.clone();
+        scope.track_resource_map::<TestResourceFields, _, _>(
+            &world,
+            move |r| {
+                project_calls.fetch_add(1, Ordering::Relaxed);
+                r.a
+            },
+            0,
+        );
+
+        // A write which leaves the projected value equal is compared once.
+        world.increment_change_tick();
+        world.resource_mut::<TestResourceFields>().b = 1;
+        let tick = world.change_tick();
+        assert!(!scope.dependencies_changed(&world, tick));
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+        // And not again on later checks.
+        world.increment_change_tick();
+        let tick = world.change_tick();
+        assert!(!scope.dependencies_changed(&world, tick));
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+        // A later change to the projected value is still seen.
+        world.increment_change_tick();
+        world.resource_mut::<TestResourceFields>().a = 1;
+        let tick = world.change_tick();
+        assert!(scope.dependencies_changed(&world, tick));
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+    }
+
+    #[derive(Component, Default)]
+    struct OtherComponent;
+
+    /// Returns the entities matched by a query, in iteration order. Returns an empty list if any of
+    /// the query's components have not been registered yet.
+    fn query_entities<D, F>(world: &World) -> Vec<Entity>
+    where
+        D: ReadOnlyQueryData + 'static,
+        F: QueryFilter + 'static,
+    {
+        world
+            .try_query_filtered::<(Entity, D), F>()
+            .map_or_else(Vec::new, |mut state| {
+                state.iter(world).map(|(entity, _)| entity).collect()
+            })
+    }
+
+    #[test]
+    fn test_query_deps_changed() {
+        let mut world = World::default();
+        let entity = world.spawn(TestComponent::default()).id();
+        world.spawn(OtherComponent);
+        let tick = world.change_tick();
+        let mut scope = TrackingScope::new(tick);
+        let matched = query_entities::<&TestComponent, ()>(&world);
+        assert_eq!(matched, vec![entity]);
+        scope.track_query::<&TestComponent, ()>(&world, matched);
+        assert!(!scope.dependencies_changed(&world, tick));
+
+        // Spawn an entity which doesn't match
+        world.increment_change_tick();
+        world.spawn(OtherComponent);
+        let tick = world.change_tick();
+        assert!(!scope.dependencies_changed(&world, tick));
+
+        // Mutate a matched component
+        world.increment_change_tick();
+        world.get_mut::<TestComponent>(entity).unwrap().a = 1;
+        let tick = world.change_tick();
+        assert!(scope.dependencies_changed(&world, tick));
+        scope.tick = tick;
+        assert!(!scope.dependencies_changed(&world, tick));
+
+        // Spawn an entity which matches
+        world.increment_change_tick();
+        world.spawn(TestComponent::default());
+        let tick = world.change_tick();
+        assert!(scope.dependencies_changed(&world, tick));
+
+        // Replace the matched entity with another one, leaving the number of matches the same.
+        let mut scope = TrackingScope::new(tick);
+        let matched = query_entities::<&TestComponent, ()>(&world);
+        scope.track_query::<&TestComponent, ()>(&world, matched);
+        assert!(!scope.dependencies_changed(&world, tick));
+        world.increment_change_tick();
+        world.despawn(entity);
+        world.spawn(TestComponent::default());
+        let tick = world.change_tick();
+        assert!(scope.dependencies_changed(&world, tick));
+    }
+
+    #[test]
+    fn test_query_deps_insert_out_and_spawn_in() {
+        let mut world = World::default();
+        let entity = world.spawn(TestComponent::default()).id();
+        world.spawn(OtherComponent);
+        let tick = world.change_tick();
+        let mut scope = TrackingScope::new(tick);
+        let matched = query_entities::<&TestComponent, Without<OtherComponent>>(&world);
+        assert_eq!(matched, vec![entity]);
+        scope.track_query::<&TestComponent, Without<OtherComponent>>(&world, matched);
+        assert!(!scope.dependencies_changed(&world, tick));
+
+        // Inserting a component moves the matched entity out of the archetype, and spawning
+        // moves another into it, so the archetype's length doesn't change and nothing is removed.
+        world.increment_change_tick();
+        world.entity_mut(entity).insert(OtherComponent);
+        let other = world.spawn(TestComponent::default()).id();
+        assert_eq!(
+            query_entities::<&TestComponent, Without<OtherComponent>>(&world),
+            vec![other]
+        );
+        let tick = world.change_tick();
+        assert!(scope.dependencies_changed(&world, tick));
+    }
+
+    #[derive(Resource, Default)]
+    struct ReactionOrder(Vec<&'static str>);
+
+    struct RecordReaction(&'static str);
+
+    impl Reaction for RecordReaction {
+        fn react(&mut self, _owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+            tracking.track_resource::<TestResource>(world);
+            world.resource_mut::<ReactionOrder>().0.push(self.0);
+        }
+    }
+
+    fn spawn_reaction(world: &mut World, name: &'static str, priority: ReactionPriority) {
+        let mut scope = TrackingScope::new(world.change_tick());
+        scope.track_resource::<TestResource>(world);
+        world.spawn((scope, ReactionCell::new(RecordReaction(name)), priority));
+    }
+
+    fn trigger(world: &mut World) {
+        world.increment_change_tick();
+        world.resource_mut::<TestResource>().0 ^= true;
+        world.resource_mut::<ReactionOrder>().0.clear();
+    }
+
+    #[test]
+    fn test_reaction_priority_order() {
+        let mut world = World::default();
+        world.init_resource::<TestResource>();
+        world.init_resource::<ReactionOrder>();
+        spawn_reaction(&mut world, "low", ReactionPriority::Low);
+        spawn_reaction(&mut world, "normal", ReactionPriority::Normal);
+        spawn_reaction(&mut world, "high", ReactionPriority::High);
+
+        trigger(&mut world);
+        run_reactions(&mut world);
+        assert_eq!(
+            world.resource::<ReactionOrder>().0,
+            vec!["high", "normal", "low"]
+        );
+    }
+
+    #[test]
+    fn test_reaction_budget_postpones_low_priority() {
+        let mut world = World::default();
+        world.init_resource::<TestResource>();
+        world.init_resource::<ReactionOrder>();
+        world.insert_resource(ReactionBudget {
+            low_priority: Some(Duration::ZERO),
+        });
+        spawn_reaction(&mut world, "low1", ReactionPriority::Low);
+        spawn_reaction(&mut world, "low2", ReactionPriority::Low);
+        spawn_reaction(&mut world, "normal", ReactionPriority::Normal);
+
+        // Only one low-priority reaction fits in the budget.
+        trigger(&mut world);
+        run_reactions(&mut world);
+        let order = &world.resource::<ReactionOrder>().0;
+        assert_eq!(order.len(), 2);
+        assert_eq!(order[0], "normal");
+
+        // The other one runs on the next frame.
+        world.resource_mut::<ReactionOrder>().0.clear();
+        world.increment_change_tick();
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<ReactionOrder>().0.len(), 1);
+
+        // Nothing left to do.
+        world.resource_mut::<ReactionOrder>().0.clear();
+        world.increment_change_tick();
+        run_reactions(&mut world);
+        assert!(world.resource::<ReactionOrder>().0.is_empty());
+
+        // Without a budget, everything runs at once.
+        world.insert_resource(ReactionBudget::default());
+        trigger(&mut world);
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<ReactionOrder>().0.len(), 3);
+    }
+
+    struct CleanupReaction(&'static str);
+
+    impl Reaction for CleanupReaction {
+        fn react(&mut self, _owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+            tracking.track_resource::<TestResource>(world);
+            world.resource_mut::<ReactionOrder>().0.push(self.0);
+            let name = self.0;
+            tracking.add_cleanup(move |world| {
+                world.resource_mut::<ReactionOrder>().0.push(name);
+            });
+        }
+    }
+
+    #[test]
+    fn test_cleanups_run_before_reactions() {
+        let mut world = World::default();
+        world.init_resource::<TestResource>();
+        world.init_resource::<ReactionOrder>();
+        for (name, priority) in [
+            ("high", ReactionPriority::High),
+            ("low", ReactionPriority::Low),
+        ] {
+            let mut scope = TrackingScope::new(world.change_tick());
+            scope.track_resource::<TestResource>(&world);
+            world.spawn((scope, ReactionCell::new(CleanupReaction(name)), priority));
+        }
+
+        // The first run has no cleanups yet.
+        trigger(&mut world);
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<ReactionOrder>().0, vec!["high", "low"]);
+
+        // All of the cleanups from the previous run come before any of the reactions.
+        trigger(&mut world);
+        run_reactions(&mut world);
+        assert_eq!(
+            world.resource::<ReactionOrder>().0,
+            vec!["high", "low", "high", "low"]
+        );
+    }
+
+    #[derive(Resource, Default)]
+    struct PollCount(usize);
+
+    struct PollReaction;
+
+    impl Reaction for PollReaction {
+        fn react(&mut self, _owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+            let mut count = world.resource_mut::<PollCount>();
+            count.0 += 1;
+            if count.0 < 3 {
+                tracking.set_deferred_change();
+            }
+        }
+    }
+
+    #[test]
+    fn test_deferred_change_is_reset() {
+        let mut world = World::default();
+        world.init_resource::<PollCount>();
+        let mut scope = TrackingScope::new(world.change_tick());
+        scope.set_deferred_change();
+        world.spawn((scope, ReactionCell::new(PollReaction)));
+
+        // The reaction keeps polling until it stops requesting another run.
+        for _ in 0..5 {
+            world.increment_change_tick();
+            run_reactions(&mut world);
+        }
+        assert_eq!(world.resource::<PollCount>().0, 3);
+    }
+
+    struct PanicReaction;
+
+    impl Reaction for PanicReaction {
+        fn react(&mut self, _owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+            tracking.track_resource::<TestResource>(world);
+            panic!("reaction failed");
+        }
+    }
+
+    #[test]
+    fn test_reaction_panic_handler() {
+        let mut world = World::default();
+        world.init_resource::<TestResource>();
+        world.init_resource::<ReactionOrder>();
+        let boundary = world
+            .spawn(ReactionPanicHandler::new(|world, boundary, message| {
+                world.resource_mut::<ReactionOrder>().0.push("handled");
+                assert_eq!(message, "reaction failed");
+                world.entity_mut(boundary).despawn_descendants();
+            }))
+            .id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        scope.track_resource::<TestResource>(&world);
+        world
+            .spawn((scope, ReactionCell::new(PanicReaction)))
+            .set_parent(boundary);
+
+        trigger(&mut world);
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<ReactionOrder>().0, vec!["handled"]);
+        assert!(world.get::<Children>(boundary).is_none());
+    }
+
+    #[derive(Resource, Default)]
+    struct FollowOn(bool);
+
+    struct WriteThenPanicReaction;
+
+    impl Reaction for WriteThenPanicReaction {
+        fn react(&mut self, _owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+            tracking.track_resource::<TestResource>(world);
+            world.resource_mut::<FollowOn>().0 = true;
+            panic!("reaction failed");
+        }
+    }
+
+    struct FollowOnReaction;
+
+    impl Reaction for FollowOnReaction {
+        fn react(&mut self, _owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+            tracking.track_resource::<FollowOn>(world);
+            world.resource_mut::<ReactionOrder>().0.push("follow-on");
+        }
+    }
+
+    #[test]
+    fn test_reaction_panic_runs_follow_on() {
+        let mut world = World::default();
+        world.init_resource::<TestResource>();
+        world.init_resource::<FollowOn>();
+        world.init_resource::<ReactionOrder>();
+        let boundary = world
+            .spawn(ReactionPanicHandler::new(|world, _, _| {
+                world.resource_mut::<ReactionOrder>().0.push("handled");
+            }))
+            .id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        scope.track_resource::<TestResource>(&world);
+        world
+            .spawn((scope, ReactionCell::new(WriteThenPanicReaction)))
+            .set_parent(boundary);
+        let mut scope = TrackingScope::new(world.change_tick());
+        scope.track_resource::<FollowOn>(&world);
+        world.spawn((scope, ReactionCell::new(FollowOnReaction)));
+
+        // Every reaction in the first pass panics, but the write made before the panic still
+        // triggers the follow-on reaction in the same run.
+        trigger(&mut world);
+        run_reactions(&mut world);
+        assert_eq!(
+            world.resource::<ReactionOrder>().0,
+            vec!["handled", "follow-on"]
+        );
+    }
+
+    #[test]
+    fn test_reaction_panic_does_not_refire() {
+        let mut world = World::default();
+        world.init_resource::<TestResource>();
+        world.init_resource::<ReactionOrder>();
+        let boundary = world
+            .spawn(ReactionPanicHandler::new(|world, _, _| {
+                world.resource_mut::<ReactionOrder>().0.push("handled");
+            }))
+            .id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        scope.track_resource::<TestResource>(&world);
+        world
+            .spawn((scope, ReactionCell::new(PanicReaction)))
+            .set_parent(boundary);
+
+        trigger(&mut world);
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<ReactionOrder>().0, vec!["handled"]);
+
+        // The failed reaction isn't run again until its dependencies change.
+        world.resource_mut::<ReactionOrder>().0.clear();
+        world.increment_change_tick();
+        run_reactions(&mut world);
+        assert!(world.resource::<ReactionOrder>().0.is_empty());
+
+        trigger(&mut world);
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<ReactionOrder>().0, vec!["handled"]);
+    }
 }
@@ -19,10 +19,21 @@ var<uniform> width: f32;
 @group(1) @binding(2)
 var<storage> commands: array<PathCommand>;
 
+@group(1) @binding(3)
+var<uniform> hairline: u32;
+
 @fragment
 fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
     let pt = vec2<f32>(in.size.x, in.size.y) * in.uv;
     let d = distance_to_path(pt);
+    if (hairline != 0u) {
+        // Feather the edge over one pixel, and approximate the coverage of strokes narrower
+        // than a pixel by reducing their opacity.
+        let aa = max(fwidth(d), 0.00001);
+        let w = max(width, aa);
+        let a = clamp((w * 0.5 - d) / aa + 0.5, 0.0, 1.0) * min(width / aa, 1.0);
+        return vec4<f32>(color.rgb, color.a * a);
+    }
     let a = 1.0 - smoothstep(width * 0.5 - 0.3, width * 0.5 + 0.3, d);
     return vec4<f32>(color.rgb, color.a * a);
 }
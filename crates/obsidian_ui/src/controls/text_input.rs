@@ -46,6 +46,9 @@ pub struct TextInputProps {
     /// If true, set focus to this widget when it's added to the UI.
     pub autofocus: bool,
 
+    /// If true, the text wraps and the Enter key inserts a newline.
+    pub multiline: bool,
+
     /// Adornments to be placed before the input field.
     pub adornments_prefix: ViewRef,
 
@@ -170,6 +173,7 @@ impl ViewTemplate for TextInput {
         let disabled = self.0.disabled;
 
         let size = self.0.size;
+        let multiline = self.0.multiline;
 
         let server = cx.world_mut().get_resource::<AssetServer>().unwrap();
         let font = server.load("obsidian_ui://fonts/Open_Sans/static/OpenSans-Medium.ttf");
@@ -207,6 +211,11 @@ impl ViewTemplate for TextInput {
                 style_text_input,
                 move |ss: &mut StyleBuilder| {
                     ss.min_height(size.height());
+                    if multiline {
+                        ss.min_height(size.height() * 3.)
+                            .align_items(ui::AlignItems::FlexStart)
+                            .padding((4, 2));
+                    }
                 },
                 self.0.style.clone(),
             ))
@@ -347,6 +356,16 @@ impl ViewTemplate for TextInput {
                                     handled = true;
                                 }
 
+                                KeyCode::Enter if multiline => {
+                                    let mut new_text = value.get_clone(world);
+                                    new_text.replace_range(sel.range(), "\n");
+                                    if let Some(on_change) = on_change {
+                                        world.run_callback(on_change, new_text);
+                                    }
+                                    selection.set(world, Selection::single(sel.start() + 1));
+                                    handled = true;
+                                }
+
                                 KeyCode::Delete => {
                                     let mut new_text = value.get_clone(world);
                                     if sel.is_empty() {
@@ -485,7 +504,11 @@ impl ViewTemplate for TextInput {
                                 });
                                 let mut entt = cx.world_mut().entity_mut(elem);
                                 if let Some(mut text) = entt.get_mut::<Text>() {
-                                    text.linebreak_behavior = BreakLineOn::NoWrap;
+                                    text.linebreak_behavior = if multiline {
+                                        BreakLineOn::WordBoundary
+                                    } else {
+                                        BreakLineOn::NoWrap
+                                    };
                                     text.sections = sections;
                                 }
                             },
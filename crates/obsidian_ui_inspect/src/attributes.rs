@@ -1,3 +1,23 @@
+//! Attributes which can be attached to reflected types and fields to control how they are
+//! displayed in the inspector. Attributes are declared using the custom attribute syntax of
+//! `bevy_reflect`:
+//!
+//! ```ignore
+//! #[derive(Reflect)]
+//! struct Vehicle {
+//!     #[reflect(@ValueRange::<f32>(0.0..50.0), @Step(0.5f32), @Units("m/s"))]
+//!     speed: f32,
+//!
+//!     #[reflect(@Multiline)]
+//!     notes: String,
+//!
+//!     #[reflect(@ColorPicker)]
+//!     tint: Vec4,
+//! }
+//! ```
+//!
+//! Custom inspectors can read these attributes with [`crate::Inspectable::attribute`].
+
 use std::ops::Range;
 
 use bevy::reflect::Reflect;
@@ -32,3 +52,40 @@ pub struct Step<T>(pub T);
 /// This also means that newlines can be inserted into the text.
 #[derive(Debug, Clone, Reflect)]
 pub struct Multiline;
+
+/// An attribute that specifies the units of a numeric field, such as `"m/s"` or `"°"`. The
+/// units are displayed as a suffix after the field's editor.
+///
+/// This attribute can be applied to numeric and vector fields.
+#[derive(Debug, Clone, Reflect)]
+pub struct Units(pub &'static str);
+
+/// An attribute that specifies that a field should be edited with a color picker.
+///
+/// This attribute can be applied to `Vec3` fields (interpreted as sRGB), `Vec4` fields
+/// (interpreted as sRGB plus alpha) and `Color` fields. `Srgba` and `LinearRgba` fields always
+/// use the color picker.
+#[derive(Debug, Clone, Reflect)]
+pub struct ColorPicker;
+
+/// An attribute that specifies that an integer should be edited as a set of bit flags, displayed
+/// as a list of checkboxes. Each entry is the name of the flag for the corresponding bit,
+/// starting with the least-significant bit; empty names are skipped.
+///
+/// This attribute can be applied to unsigned integer fields, or to a tuple struct that wraps
+/// a single unsigned integer:
+///
+/// ```ignore
+/// #[derive(Reflect)]
+/// #[reflect(@BitFlags::new(&["Visible", "Pickable", "Casts Shadows"]))]
+/// struct RenderFlags(u32);
+/// ```
+#[derive(Debug, Clone, Reflect)]
+pub struct BitFlags(pub Vec<&'static str>);
+
+impl BitFlags {
+    /// Construct a new bit flags attribute from a list of flag names.
+    pub fn new(names: &[&'static str]) -> Self {
+        Self(names.to_vec())
+    }
+}
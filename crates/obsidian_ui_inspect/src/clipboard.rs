@@ -0,0 +1,89 @@
+use bevy::{
+    ecs::reflect::AppTypeRegistry,
+    prelude::*,
+    reflect::serde::{TypedReflectDeserializer, TypedReflectSerializer},
+};
+use bevy_reactor_signals::{Cx, RunContextRead};
+use serde::de::DeserializeSeed;
+
+use crate::Inspectable;
+
+/// Clipboard used by the inspector to copy and paste field values. Values are stored as RON
+/// text along with the type path of the copied value, so that a paste can be checked for type
+/// compatibility before it is applied. Apps that want to exchange values with the system
+/// clipboard can read and write this resource.
+#[derive(Resource, Default, Clone, Debug)]
+pub struct InspectorClipboard {
+    /// Type path of the copied value.
+    pub type_path: String,
+    /// The copied value, serialized as RON.
+    pub ron: String,
+}
+
+/// Copy the value of a field to the [`InspectorClipboard`].
+pub(crate) fn copy_field(cx: &mut Cx, field: &Inspectable) {
+    let Some(reflect) = field.reflect(cx) else {
+        return;
+    };
+    let type_path = reflect.reflect_type_path().to_string();
+    let registry = cx.world().resource::<AppTypeRegistry>().0.clone();
+    let registry = registry.read();
+    let serializer = TypedReflectSerializer::new(reflect, &registry);
+    match ron::ser::to_string_pretty(&serializer, ron::ser::PrettyConfig::default()) {
+        Ok(ron) => {
+            drop(registry);
+            cx.world_mut()
+                .insert_resource(InspectorClipboard { type_path, ron });
+        }
+        Err(err) => warn!("Can't copy field '{}': {}", field.name(), err),
+    }
+}
+
+/// Whether the contents of the clipboard can be pasted into the field. This reads the
+/// clipboard reactively, so it can be used in a memo.
+pub(crate) fn can_paste(cx: &Cx, field: &Inspectable) -> bool {
+    let clipboard = cx.read_resource::<InspectorClipboard>();
+    !clipboard.ron.is_empty()
+        && field
+            .reflect(cx)
+            .is_some_and(|reflect| reflect.reflect_type_path() == clipboard.type_path)
+}
+
+/// Deserialize the contents of the clipboard and apply them to the field.
+pub(crate) fn paste_field(cx: &mut Cx, field: &Inspectable) {
+    let clipboard = cx.world().resource::<InspectorClipboard>().clone();
+    let Some(reflect) = field.reflect(cx) else {
+        return;
+    };
+    if reflect.reflect_type_path() != clipboard.type_path {
+        warn!(
+            "Can't paste {} into field '{}' of type {}",
+            clipboard.type_path,
+            field.name(),
+            reflect.reflect_type_path()
+        );
+        return;
+    }
+    let Some(type_info) = reflect.get_represented_type_info() else {
+        return;
+    };
+
+    let registry = cx.world().resource::<AppTypeRegistry>().0.clone();
+    let registry = registry.read();
+    let Some(registration) = registry.get(type_info.type_id()) else {
+        warn!("Type {} is not registered", clipboard.type_path);
+        return;
+    };
+    let value = ron::Deserializer::from_str(&clipboard.ron)
+        .map_err(|err| err.to_string())
+        .and_then(|mut de| {
+            TypedReflectDeserializer::new(registration, &registry)
+                .deserialize(&mut de)
+                .map_err(|err| err.to_string())
+        });
+    drop(registry);
+    match value {
+        Ok(value) => field.set_value(cx, value.as_ref()),
+        Err(err) => warn!("Can't paste into field '{}': {}", field.name(), err),
+    }
+}
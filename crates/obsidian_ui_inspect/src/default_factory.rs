@@ -2,12 +2,20 @@ use std::sync::Arc;
 
 use crate::{
     inspectors::{
-        bool::BooleanFieldInspector, color::SrgbaInspector, f32::F32FieldInspector,
-        fallback::FallbackInspector, list::ListInspector, r#enum::EnumInspector,
-        r#struct::NestedStruct, tuple_struct::NestedTupleStruct, vec3::Vec3FieldInspector,
+        bitflags::BitFlagsInspector,
+        bool::BooleanFieldInspector,
+        color::{ColorFormat, ColorInspector},
+        f32::F32FieldInspector,
+        fallback::FallbackInspector,
+        list::ListInspector,
+        r#enum::EnumInspector,
+        r#struct::NestedStruct,
+        string::StringFieldInspector,
+        tuple_struct::NestedTupleStruct,
+        vec3::Vec3FieldInspector,
     },
     templates::{field_label::FieldLabel, field_readonly_value::FieldReadonlyValue},
-    Inspectable, InspectorFactory,
+    ColorPicker, Inspectable, InspectorFactory,
 };
 use bevy::reflect::ReflectRef;
 use bevy_reactor::*;
@@ -19,9 +27,18 @@ pub struct DefaultInspectorFactory;
 impl InspectorFactory for DefaultInspectorFactory {
     fn create_inspector(&self, cx: &Cx, field: Arc<Inspectable>) -> Option<ViewRef> {
         let reflect = field.reflect(cx)?;
+        if let Some(inspector) = BitFlagsInspector::for_field(&field, reflect) {
+            return Some(inspector.into_view());
+        }
+        if let Some(format) = ColorFormat::of(reflect) {
+            if field.attribute::<ColorPicker>().is_some()
+                || matches!(format, ColorFormat::Srgba | ColorFormat::LinearRgba)
+            {
+                return Some(ColorInspector { field, format }.into_view());
+            }
+        }
         match reflect.reflect_ref() {
             ReflectRef::Struct(s) => match s.reflect_type_path() {
-                "bevy_color::srgba::Srgba" => Some(SrgbaInspector(field.clone()).into_view()),
                 "glam::Vec3" => Some(Vec3FieldInspector(field.clone()).into_view()),
                 _ => Some(NestedStruct(field.clone()).into_view()),
             },
@@ -58,6 +75,7 @@ impl InspectorFactory for DefaultInspectorFactory {
             ReflectRef::Value(v) => match v.reflect_type_path() {
                 "bool" => Some(BooleanFieldInspector(field.clone()).into_view()),
                 "f32" => Some(F32FieldInspector(field.clone()).into_view()),
+                "alloc::string::String" => Some(StringFieldInspector(field.clone()).into_view()),
                 _ => Some(FallbackInspector(field.clone()).into_view()),
             },
         }
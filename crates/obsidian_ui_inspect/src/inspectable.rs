@@ -2,9 +2,11 @@ use core::panic;
 use std::sync::Arc;
 
 use bevy::{
+    ecs::reflect::AppTypeRegistry,
     prelude::*,
     reflect::{
-        attributes::CustomAttributes, DynamicEnum, DynamicVariant, ParsedPath, ReflectPathError,
+        attributes::CustomAttributes, std_traits::ReflectDefault, DynamicEnum, DynamicVariant,
+        ParsedPath, ReflectPathError,
     },
 };
 use bevy_reactor_signals::{Cx, RunContextRead};
@@ -23,6 +25,24 @@ pub trait InspectableRoot: Send + Sync {
 
     /// Apply a closure to a field within the item
     fn update_path(&self, cx: &mut Cx, path: &ParsedPath, f: &dyn Fn(&mut dyn Reflect));
+
+    /// Whether the field at the given path has differing values across the inspected items.
+    /// This is only ever true for roots which represent more than one item.
+    fn is_mixed(&self, cx: &Cx, path: &ParsedPath) -> bool {
+        false
+    }
+
+    /// The baseline value of the whole item, which fields are compared against to show
+    /// modification indicators and which "Reset to Default" restores. Roots that are instances
+    /// of a scene or prefab can override this to return the source value; the default is the
+    /// `Default` value of the item's type, if it registers `ReflectDefault`.
+    fn baseline(&self, cx: &Cx) -> Option<Box<dyn Reflect>> {
+        let value = self.reflect_path(cx, &ParsedPath(vec![]))?;
+        let type_id = value.get_represented_type_info()?.type_id();
+        let registry = cx.world().resource::<AppTypeRegistry>().read();
+        let default = registry.get_type_data::<ReflectDefault>(type_id)?;
+        Some(default.default())
+    }
 }
 
 /// A resource that can be inspected
@@ -107,6 +127,79 @@ impl<T: Component + Reflect> InspectableRoot for InspectableComponent<T> {
     }
 }
 
+/// A set of items being inspected together, such as the same component on several selected
+/// entities. Values are read from the first item, and edits are applied to all of them.
+pub struct InspectableSelection {
+    roots: Vec<Arc<dyn InspectableRoot>>,
+}
+
+impl InspectableSelection {
+    /// Construct a selection from a non-empty list of roots.
+    pub fn new(roots: Vec<Arc<dyn InspectableRoot>>) -> Self {
+        assert!(
+            !roots.is_empty(),
+            "InspectableSelection requires at least one root"
+        );
+        Self { roots }
+    }
+
+    /// Construct a selection that inspects component `T` on each of the given entities. The
+    /// caller is responsible for only passing entities which have the component.
+    pub fn components<T: Component + Reflect>(entities: &[Entity]) -> Self {
+        Self::new(
+            entities
+                .iter()
+                .map(|entity| {
+                    Arc::new(InspectableComponent::<T>::new(*entity)) as Arc<dyn InspectableRoot>
+                })
+                .collect(),
+        )
+    }
+}
+
+impl InspectableRoot for InspectableSelection {
+    fn name(&self, cx: &Cx) -> String {
+        let name = self.roots[0].name(cx);
+        match self.roots.len() {
+            1 => name,
+            n => format!("{} ({})", name, n),
+        }
+    }
+
+    fn reflect_path<'a>(&self, cx: &'a Cx, path: &ParsedPath) -> Option<&'a dyn Reflect> {
+        self.roots[0].reflect_path(cx, path)
+    }
+
+    fn set_path(&self, cx: &mut Cx, path: &ParsedPath, value: &dyn Reflect) {
+        for root in self.roots.iter() {
+            root.set_path(cx, path, value);
+        }
+    }
+
+    fn update_path(&self, cx: &mut Cx, path: &ParsedPath, f: &dyn Fn(&mut dyn Reflect)) {
+        for root in self.roots.iter() {
+            root.update_path(cx, path, f);
+        }
+    }
+
+    fn baseline(&self, cx: &Cx) -> Option<Box<dyn Reflect>> {
+        self.roots[0].baseline(cx)
+    }
+
+    fn is_mixed(&self, cx: &Cx, path: &ParsedPath) -> bool {
+        let Some(first) = self.roots[0].reflect_path(cx, path) else {
+            return false;
+        };
+        self.roots[1..].iter().any(|root| {
+            match root.reflect_path(cx, path) {
+                // Types which don't support comparison are treated as the same.
+                Some(value) => first.reflect_partial_eq(value) == Some(false),
+                None => true,
+            }
+        })
+    }
+}
+
 /// A reference to a field within an `Inspectable`. This contains information needed to
 /// get and set the field as well as query it's type.
 #[derive(Clone)]
@@ -133,11 +226,49 @@ impl Inspectable {
         &self.name
     }
 
+    /// Look up a custom attribute, such as [`crate::ValueRange`], attached to this field.
+    pub fn attribute<T: Reflect>(&self) -> Option<&'static T> {
+        self.attributes.and_then(|attrs| attrs.get::<T>())
+    }
+
     /// Get the reflected value of the field.
     pub fn reflect<'a>(&self, cx: &'a Cx) -> Option<&'a dyn Reflect> {
         self.root.reflect_path(cx, &self.value_path)
     }
 
+    /// Whether the field has differing values across the inspected items.
+    pub fn is_mixed(&self, cx: &Cx) -> bool {
+        self.root.is_mixed(cx, &self.value_path)
+    }
+
+    /// The value of this field within the root's baseline (see [`InspectableRoot::baseline`]).
+    pub fn baseline_value(&self, cx: &Cx) -> Option<Box<dyn Reflect>> {
+        let baseline = self.root.baseline(cx)?;
+        baseline
+            .as_ref()
+            .reflect_path(&self.value_path)
+            .ok()
+            .map(|value| value.clone_value())
+    }
+
+    /// Whether the field differs from its baseline value. Fields whose type doesn't support
+    /// comparison are never considered modified.
+    pub fn is_modified(&self, cx: &Cx) -> bool {
+        match (self.reflect(cx), self.baseline_value(cx)) {
+            (Some(value), Some(baseline)) => {
+                value.reflect_partial_eq(baseline.as_ref()) == Some(false)
+            }
+            _ => false,
+        }
+    }
+
+    /// Restore the field to its baseline value.
+    pub fn reset(&self, cx: &mut Cx) {
+        if let Some(baseline) = self.baseline_value(cx) {
+            self.set_value(cx, baseline.as_ref());
+        }
+    }
+
     /// Update the value of the field
     pub fn set_value(&self, cx: &mut Cx, value: &dyn Reflect) {
         self.root.set_path(cx, &self.value_path, value);
@@ -10,7 +10,7 @@ use crate::{
         r#struct::{StructFieldList, StructInspectorHeaderControls},
         tuple_struct::TupleStructElements,
     },
-    templates::inspector_panel::InspectorPanel,
+    templates::{field_menu::FieldMenu, inspector_panel::InspectorPanel},
     Inspectable, InspectableRoot,
 };
 
@@ -63,6 +63,9 @@ impl ViewTemplate for Inspector {
                 StructInspectorHeaderControls {
                     target: inspectable.clone(),
                 },
+                FieldMenu {
+                    field: inspectable.clone(),
+                },
             ))
             .body(self.create_fields(cx, inspectable))
             .expanded(true)
@@ -0,0 +1,143 @@
+use std::sync::Arc;
+
+use bevy::{
+    prelude::*,
+    reflect::{OffsetAccess, ReflectRef, TypeInfo},
+    ui,
+};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor::*;
+use bevy_reactor_signals::{Cx, RunContextSetup};
+use obsidian_ui::controls::Checkbox;
+
+use crate::{templates::field_label::FieldLabel, BitFlags, Inspectable};
+
+fn style_flag_list(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::FlexStart)
+        .row_gap(2);
+}
+
+/// Inspector for an unsigned integer that is annotated with the [`BitFlags`] attribute.
+pub struct BitFlagsInspector {
+    /// The integer field containing the flag bits.
+    pub(crate) field: Arc<Inspectable>,
+    /// Names of the individual flags.
+    pub(crate) flags: BitFlags,
+}
+
+impl BitFlagsInspector {
+    /// Returns a bit flags inspector if the field, or the type of the field, has a [`BitFlags`]
+    /// attribute. If the field is a tuple struct wrapping an integer, the inspector edits the
+    /// inner integer.
+    pub(crate) fn for_field(field: &Arc<Inspectable>, reflect: &dyn Reflect) -> Option<Self> {
+        if let Some(flags) = field.attributes.and_then(|attrs| attrs.get::<BitFlags>()) {
+            if read_bits(reflect).is_some() {
+                return Some(Self {
+                    field: field.clone(),
+                    flags: flags.clone(),
+                });
+            }
+        }
+
+        let Some(TypeInfo::TupleStruct(info)) = reflect.get_represented_type_info() else {
+            return None;
+        };
+        let flags = info.custom_attributes().get::<BitFlags>()?;
+        let ReflectRef::TupleStruct(ts) = reflect.reflect_ref() else {
+            return None;
+        };
+        if ts.field_len() != 1 || read_bits(ts.field(0)?).is_none() {
+            return None;
+        }
+
+        let mut path = field.value_path.clone();
+        path.0.push(OffsetAccess {
+            access: bevy::reflect::Access::TupleIndex(0),
+            offset: None,
+        });
+        Some(Self {
+            field: Arc::new(Inspectable {
+                root: field.root.clone(),
+                name: field.name.clone(),
+                field_path: field.field_path.clone(),
+                value_path: path,
+                can_remove: field.can_remove,
+                attributes: field.attributes,
+            }),
+            flags: flags.clone(),
+        })
+    }
+}
+
+impl ViewTemplate for BitFlagsInspector {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let field = self.field.clone();
+        let bits = cx.create_memo(move |cx| field.reflect(cx).and_then(read_bits).unwrap_or(0));
+
+        let mut items: Vec<ViewRef> = Vec::new();
+        for (index, name) in self.flags.0.iter().enumerate().take(64) {
+            if name.is_empty() {
+                continue;
+            }
+            let mask = 1u64 << index;
+            let field = self.field.clone();
+            items.push(
+                Checkbox {
+                    checked: cx.create_memo(move |cx| bits.get(cx) & mask != 0),
+                    label: name.to_string().into_view(),
+                    on_change: Some(cx.create_callback(move |cx: &mut Cx, value: bool| {
+                        field.update(cx, &|reflect| {
+                            let Some(bits) = read_bits(reflect) else {
+                                return;
+                            };
+                            write_bits(reflect, if value { bits | mask } else { bits & !mask });
+                        });
+                    })),
+                    ..default()
+                }
+                .into_view(),
+            );
+        }
+
+        Fragment::new((
+            FieldLabel {
+                field: self.field.clone(),
+            },
+            Element::<Node>::new()
+                .style(style_flag_list)
+                .children(items),
+        ))
+    }
+}
+
+/// Read an unsigned integer of any width as a `u64`.
+fn read_bits(value: &dyn Reflect) -> Option<u64> {
+    if let Some(v) = value.downcast_ref::<u8>() {
+        Some(*v as u64)
+    } else if let Some(v) = value.downcast_ref::<u16>() {
+        Some(*v as u64)
+    } else if let Some(v) = value.downcast_ref::<u32>() {
+        Some(*v as u64)
+    } else if let Some(v) = value.downcast_ref::<u64>() {
+        Some(*v)
+    } else {
+        value.downcast_ref::<usize>().map(|v| *v as u64)
+    }
+}
+
+/// Write a `u64` back into an unsigned integer, truncating to the integer's width.
+fn write_bits(value: &mut dyn Reflect, bits: u64) {
+    if let Some(v) = value.downcast_mut::<u8>() {
+        *v = bits as u8;
+    } else if let Some(v) = value.downcast_mut::<u16>() {
+        *v = bits as u16;
+    } else if let Some(v) = value.downcast_mut::<u32>() {
+        *v = bits as u32;
+    } else if let Some(v) = value.downcast_mut::<u64>() {
+        *v = bits;
+    } else if let Some(v) = value.downcast_mut::<usize>() {
+        *v = bits as usize;
+    }
+}
@@ -34,18 +34,78 @@ use crate::{
     Inspectable,
 };
 
-pub struct SrgbaInspector(pub(crate) Arc<Inspectable>);
+/// The reflected type being edited by a [`ColorInspector`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum ColorFormat {
+    Srgba,
+    LinearRgba,
+    Color,
+    /// A `Vec3` interpreted as sRGB components.
+    Vec3,
+    /// A `Vec4` interpreted as sRGB + alpha components.
+    Vec4,
+}
+
+impl ColorFormat {
+    /// Determine the color format for a reflected value, if it is one of the supported types.
+    pub(crate) fn of(reflect: &dyn Reflect) -> Option<Self> {
+        if reflect.is::<Srgba>() {
+            Some(Self::Srgba)
+        } else if reflect.is::<LinearRgba>() {
+            Some(Self::LinearRgba)
+        } else if reflect.is::<Color>() {
+            Some(Self::Color)
+        } else if reflect.is::<Vec3>() {
+            Some(Self::Vec3)
+        } else if reflect.is::<Vec4>() {
+            Some(Self::Vec4)
+        } else {
+            None
+        }
+    }
+
+    fn to_srgba(self, reflect: &dyn Reflect) -> Option<Srgba> {
+        match self {
+            Self::Srgba => reflect.downcast_ref::<Srgba>().copied(),
+            Self::LinearRgba => reflect
+                .downcast_ref::<LinearRgba>()
+                .map(|c| Srgba::from(*c)),
+            Self::Color => reflect.downcast_ref::<Color>().map(|c| Srgba::from(*c)),
+            Self::Vec3 => reflect
+                .downcast_ref::<Vec3>()
+                .map(|v| Srgba::rgb(v.x, v.y, v.z)),
+            Self::Vec4 => reflect
+                .downcast_ref::<Vec4>()
+                .map(|v| Srgba::new(v.x, v.y, v.z, v.w)),
+        }
+    }
+
+    fn from_srgba(self, color: Srgba) -> Box<dyn Reflect> {
+        match self {
+            Self::Srgba => Box::new(color),
+            Self::LinearRgba => Box::new(LinearRgba::from(color)),
+            Self::Color => Box::new(Color::from(color)),
+            Self::Vec3 => Box::new(Vec3::new(color.red, color.green, color.blue)),
+            Self::Vec4 => Box::new(Vec4::new(color.red, color.green, color.blue, color.alpha)),
+        }
+    }
+}
+
+/// Inspector that edits a color value with a swatch and a color picker popup.
+pub struct ColorInspector {
+    pub(crate) field: Arc<Inspectable>,
+    pub(crate) format: ColorFormat,
+}
 
-impl ViewTemplate for SrgbaInspector {
+impl ViewTemplate for ColorInspector {
     fn create(&self, cx: &mut Cx) -> impl IntoView {
-        let field = self.0.clone();
+        let field = self.field.clone();
+        let format = self.format;
         let value = cx.create_memo(move |cx| {
-            if let Some(value) = field.reflect(cx) {
-                if value.is::<Srgba>() {
-                    return *value.downcast_ref::<Srgba>().unwrap();
-                }
-            }
-            Srgba::NONE
+            field
+                .reflect(cx)
+                .and_then(|value| format.to_srgba(value))
+                .unwrap_or(Srgba::NONE)
         });
 
         let state = cx.create_mutable(ColorEditState {
@@ -54,20 +114,19 @@ impl ViewTemplate for SrgbaInspector {
             hsl: Hsla::default(),
         });
 
-        let field = self.0.clone();
+        let field = self.field.clone();
         cx.create_effect(move |cx| {
             let next_state = state.get(cx);
             if let Some(reflect) = field.reflect(cx) {
-                let value = *reflect.downcast_ref::<Srgba>().unwrap();
-                if value != next_state.rgb {
-                    field.set_value(cx, &next_state.rgb);
+                if format.to_srgba(reflect) != Some(next_state.rgb) {
+                    field.set_value(cx, format.from_srgba(next_state.rgb).as_ref());
                 }
             }
         });
 
         Fragment::new((
             FieldLabel {
-                field: self.0.clone(),
+                field: self.field.clone(),
             },
             Element::<Node>::new().style(style_field).children((
                 Swatch::new(value).style(style_swatch),
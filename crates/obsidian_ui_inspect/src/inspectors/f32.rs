@@ -5,7 +5,10 @@ use bevy_reactor::*;
 use bevy_reactor_signals::{Cx, RunContextSetup};
 use obsidian_ui::controls::{Slider, SpinBox};
 
-use crate::{templates::field_label::FieldLabel, Inspectable, Precision, Step, ValueRange};
+use crate::{
+    templates::{field_label::FieldLabel, field_units::FieldUnits},
+    Inspectable, Precision, Step, Units, ValueRange,
+};
 
 #[derive(Clone, Debug)]
 struct F32Attrs {
@@ -25,6 +28,7 @@ impl ViewTemplate for F32FieldInspector {
         });
 
         let field = self.0.clone();
+        let units = field.attribute::<Units>().map(|units| units.0);
         let mut slider_params = F32Attrs {
             range: None,
             precision: 0,
@@ -54,29 +58,32 @@ impl ViewTemplate for F32FieldInspector {
             },
             // Don't need `Cond` here because condition is not reactive; reflection data
             // is constant.
-            match slider_params.range {
-                Some(range) => Slider::new()
-                    .min(range.start)
-                    .max(range.end)
-                    .precision(slider_params.precision)
-                    .step(slider_params.step)
-                    .value(value)
-                    .on_change(cx.create_callback(move |cx, value: f32| {
-                        field.update(cx, &|reflect| {
-                            reflect.apply(value.as_reflect());
-                        });
-                    }))
-                    .into_view(),
-                None => SpinBox::new()
-                    .precision(slider_params.precision)
-                    .step(slider_params.step)
-                    .value(value)
-                    .on_change(cx.create_callback(move |cx, value: f32| {
-                        field.update(cx, &|reflect| {
-                            reflect.apply(value.as_reflect());
-                        });
-                    }))
-                    .into_view(),
+            FieldUnits {
+                units,
+                editor: match slider_params.range {
+                    Some(range) => Slider::new()
+                        .min(range.start)
+                        .max(range.end)
+                        .precision(slider_params.precision)
+                        .step(slider_params.step)
+                        .value(value)
+                        .on_change(cx.create_callback(move |cx, value: f32| {
+                            field.update(cx, &|reflect| {
+                                reflect.apply(value.as_reflect());
+                            });
+                        }))
+                        .into_view(),
+                    None => SpinBox::new()
+                        .precision(slider_params.precision)
+                        .step(slider_params.step)
+                        .value(value)
+                        .on_change(cx.create_callback(move |cx, value: f32| {
+                            field.update(cx, &|reflect| {
+                                reflect.apply(value.as_reflect());
+                            });
+                        }))
+                        .into_view(),
+                },
             },
         ))
         .into_view()
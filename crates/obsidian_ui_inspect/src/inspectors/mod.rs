@@ -1,9 +1,11 @@
+pub mod bitflags;
 pub mod bool;
 pub mod color;
 pub mod r#enum;
 pub mod r#f32;
 pub mod fallback;
 pub mod list;
+pub mod string;
 pub mod r#struct;
 pub mod tuple_struct;
 pub mod vec3;
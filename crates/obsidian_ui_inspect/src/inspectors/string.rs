@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use bevy::reflect::Reflect;
+use bevy_reactor::*;
+use bevy_reactor_signals::{Cx, RunContextSetup};
+use obsidian_ui::controls::{TextInput, TextInputProps};
+
+use crate::{templates::field_label::FieldLabel, Inspectable, Multiline};
+
+/// Inspector for `String` fields. Fields with the [`Multiline`] attribute get a wrapping text
+/// area which accepts newlines.
+pub struct StringFieldInspector(pub(crate) Arc<Inspectable>);
+
+impl ViewTemplate for StringFieldInspector {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let field = self.0.clone();
+        let value = cx.create_memo(move |cx| match field.reflect(cx) {
+            Some(value) if value.is::<String>() => value.downcast_ref::<String>().unwrap().clone(),
+            _ => String::new(),
+        });
+
+        let field = self.0.clone();
+        Fragment::new((
+            FieldLabel {
+                field: field.clone(),
+            },
+            TextInput::new(TextInputProps {
+                value,
+                multiline: field.attribute::<Multiline>().is_some(),
+                on_change: Some(cx.create_callback(move |cx, value: String| {
+                    field.set_value(cx, value.as_reflect());
+                })),
+                ..Default::default()
+            }),
+        ))
+    }
+}
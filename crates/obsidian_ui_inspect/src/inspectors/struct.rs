@@ -143,8 +143,8 @@ impl ViewTemplate for StructFieldList {
     }
 }
 
-struct NamedFieldInspector {
-    field: Arc<Inspectable>,
+pub(crate) struct NamedFieldInspector {
+    pub(crate) field: Arc<Inspectable>,
 }
 
 impl ViewTemplate for NamedFieldInspector {
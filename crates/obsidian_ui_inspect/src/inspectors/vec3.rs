@@ -10,7 +10,10 @@ use bevy_reactor::*;
 use bevy_reactor_signals::{Cx, RunContextSetup};
 use obsidian_ui::controls::SpinBox;
 
-use crate::{templates::field_label::FieldLabel, Inspectable, Precision, Step};
+use crate::{
+    templates::{field_label::FieldLabel, field_units::FieldUnits},
+    Inspectable, Precision, Step, Units,
+};
 
 pub struct Vec3FieldInspector(pub(crate) Arc<Inspectable>);
 
@@ -41,6 +44,7 @@ impl ViewTemplate for Vec3FieldInspector {
         });
 
         let field = self.0.clone();
+        let units = field.attribute::<Units>().map(|units| units.0);
         let mut slider_params = Vec3Attrs {
             precision: 2,
             step: 0.1,
@@ -63,53 +67,59 @@ impl ViewTemplate for Vec3FieldInspector {
             },
             // Don't need `Cond` here because condition is not reactive; reflection data
             // is constant.
-            Element::<Node>::new().style(style_spinbox_group).children((
-                // "x",
-                SpinBox::new()
-                    .style(style_spinbox)
-                    .precision(slider_params.precision)
-                    .step(slider_params.step)
-                    .value(cx.create_derived(move |cx| value.get(cx).x))
-                    .on_change(cx.create_callback({
-                        let field = self.0.clone();
-                        move |cx, x: f32| {
-                            let value = value.get(cx).with_x(x);
-                            field.update(cx, &|reflect| {
-                                reflect.apply(value.as_reflect());
-                            });
-                        }
-                    })),
-                // "y",
-                SpinBox::new()
-                    .style(style_spinbox)
-                    .precision(slider_params.precision)
-                    .step(slider_params.step)
-                    .value(cx.create_derived(move |cx| value.get(cx).y))
-                    .on_change(cx.create_callback({
-                        let field = self.0.clone();
-                        move |cx, y: f32| {
-                            let value = value.get(cx).with_y(y);
-                            field.update(cx, &|reflect| {
-                                reflect.apply(value.as_reflect());
-                            });
-                        }
-                    })),
-                // "z",
-                SpinBox::new()
-                    .style(style_spinbox)
-                    .precision(slider_params.precision)
-                    .step(slider_params.step)
-                    .value(cx.create_derived(move |cx| value.get(cx).z))
-                    .on_change(cx.create_callback({
-                        let field = self.0.clone();
-                        move |cx, z: f32| {
-                            let value = value.get(cx).with_z(z);
-                            field.update(cx, &|reflect| {
-                                reflect.apply(value.as_reflect());
-                            });
-                        }
-                    })),
-            )),
+            FieldUnits {
+                units,
+                editor: Element::<Node>::new()
+                    .style(style_spinbox_group)
+                    .children((
+                        // "x",
+                        SpinBox::new()
+                            .style(style_spinbox)
+                            .precision(slider_params.precision)
+                            .step(slider_params.step)
+                            .value(cx.create_derived(move |cx| value.get(cx).x))
+                            .on_change(cx.create_callback({
+                                let field = self.0.clone();
+                                move |cx, x: f32| {
+                                    let value = value.get(cx).with_x(x);
+                                    field.update(cx, &|reflect| {
+                                        reflect.apply(value.as_reflect());
+                                    });
+                                }
+                            })),
+                        // "y",
+                        SpinBox::new()
+                            .style(style_spinbox)
+                            .precision(slider_params.precision)
+                            .step(slider_params.step)
+                            .value(cx.create_derived(move |cx| value.get(cx).y))
+                            .on_change(cx.create_callback({
+                                let field = self.0.clone();
+                                move |cx, y: f32| {
+                                    let value = value.get(cx).with_y(y);
+                                    field.update(cx, &|reflect| {
+                                        reflect.apply(value.as_reflect());
+                                    });
+                                }
+                            })),
+                        // "z",
+                        SpinBox::new()
+                            .style(style_spinbox)
+                            .precision(slider_params.precision)
+                            .step(slider_params.step)
+                            .value(cx.create_derived(move |cx| value.get(cx).z))
+                            .on_change(cx.create_callback({
+                                let field = self.0.clone();
+                                move |cx, z: f32| {
+                                    let value = value.get(cx).with_z(z);
+                                    field.update(cx, &|reflect| {
+                                        reflect.apply(value.as_reflect());
+                                    });
+                                }
+                            })),
+                    ))
+                    .into_view(),
+            },
         ))
     }
 }
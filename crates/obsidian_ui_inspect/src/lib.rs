@@ -1,18 +1,22 @@
 mod attributes;
+mod clipboard;
 mod default_factory;
 mod inspectable;
 mod inspector;
 mod inspector_factory;
 mod inspectors;
+mod settings_panel;
 mod templates;
 
 use bevy::app::{App, Plugin};
 use default_factory::DefaultInspectorFactory;
 
 pub use attributes::*;
+pub use clipboard::InspectorClipboard;
 pub use inspectable::*;
 pub use inspector::*;
 pub use inspector_factory::*;
+pub use settings_panel::{SettingsDraft, SettingsPanel};
 use templates::color_edit::RecentColors;
 
 pub struct InspectorPlugin;
@@ -20,6 +24,7 @@ pub struct InspectorPlugin;
 impl Plugin for InspectorPlugin {
     fn build(&self, app: &mut App) {
         app.register_inspector::<DefaultInspectorFactory>()
-            .init_resource::<RecentColors>();
+            .init_resource::<RecentColors>()
+            .init_resource::<InspectorClipboard>();
     }
 }
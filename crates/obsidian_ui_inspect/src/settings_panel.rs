@@ -0,0 +1,227 @@
+use std::sync::Arc;
+
+use bevy::{
+    prelude::*,
+    reflect::{OffsetAccess, ParsedPath, ReflectPathError, ReflectRef, TypeInfo},
+    ui,
+};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor::*;
+use bevy_reactor_signals::{Cx, RunContextRead, RunContextSetup};
+use obsidian_ui::controls::{Button, ButtonVariant};
+
+use crate::{
+    inspectors::{
+        color::ColorFormat,
+        r#struct::{NamedFieldInspector, StructFieldList},
+    },
+    templates::inspector_panel::InspectorPanel,
+    Inspectable, InspectableRoot,
+};
+
+fn style_settings_panel(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch)
+        .row_gap(6);
+}
+
+fn style_settings_footer(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .justify_content(ui::JustifyContent::FlexEnd)
+        .column_gap(4);
+}
+
+/// The working copy of a settings resource which is edited by a [`SettingsPanel`]. Edits are
+/// made to the draft, and only copied to the resource when they are applied.
+#[derive(Resource)]
+pub struct SettingsDraft<T: Resource + Reflect + Clone>(pub T);
+
+/// Inspectable root for the draft of a settings resource. The baseline is the live resource,
+/// so the modified indicators show which fields have unapplied changes, and "Reset" on a field
+/// reverts just that field.
+struct InspectableSettingsDraft<T: Resource + Reflect + Clone> {
+    marker: std::marker::PhantomData<T>,
+}
+
+impl<T: Resource + Reflect + Clone> InspectableRoot for InspectableSettingsDraft<T> {
+    fn name(&self, cx: &Cx) -> String {
+        let res = cx.read_resource::<T>();
+        res.reflect_short_type_path().to_string()
+    }
+
+    fn reflect_path<'a>(&self, cx: &'a Cx, path: &ParsedPath) -> Option<&'a dyn Reflect> {
+        let draft = cx.read_resource::<SettingsDraft<T>>();
+        match draft.0.reflect_path(path) {
+            Ok(result) => Some(result),
+            Err(ReflectPathError::InvalidAccess(_)) => None,
+            Err(err) => panic!("{:?}", err),
+        }
+    }
+
+    fn set_path(&self, cx: &mut Cx, path: &ParsedPath, value: &dyn Reflect) {
+        let mut draft = cx.world_mut().resource_mut::<SettingsDraft<T>>();
+        draft.0.reflect_path_mut(path).unwrap().apply(value);
+    }
+
+    fn update_path(&self, cx: &mut Cx, path: &ParsedPath, f: &dyn Fn(&mut dyn Reflect)) {
+        let mut draft = cx.world_mut().resource_mut::<SettingsDraft<T>>();
+        f(draft.0.reflect_path_mut(path).unwrap());
+    }
+
+    fn baseline(&self, cx: &Cx) -> Option<Box<dyn Reflect>> {
+        Some(Box::new(cx.read_resource::<T>().clone()))
+    }
+}
+
+/// Whether a field should be shown as a section of its own, rather than as a row. Structs
+/// which have a dedicated editor, such as vectors and colors, are shown as rows.
+fn is_section(value: &dyn Reflect) -> bool {
+    matches!(value.reflect_ref(), ReflectRef::Struct(_))
+        && ColorFormat::of(value).is_none()
+        && !value.reflect_type_path().starts_with("glam::")
+}
+
+/// Create the inspectable for a top-level field of the settings.
+fn settings_field(root: &Arc<dyn InspectableRoot>, info: &TypeInfo, name: &str) -> Inspectable {
+    let path = ParsedPath(vec![OffsetAccess {
+        access: bevy::reflect::Access::Field(name.to_string().into()),
+        offset: None,
+    }]);
+    let TypeInfo::Struct(st_info) = info else {
+        panic!("Expected StructInfo");
+    };
+    Inspectable {
+        root: root.clone(),
+        name: name.to_string(),
+        value_path: path.clone(),
+        field_path: path,
+        can_remove: false,
+        attributes: st_info
+            .field(name)
+            .map(|field_info| field_info.custom_attributes()),
+    }
+}
+
+/// A complete settings panel for a reflected settings resource, such as graphics options,
+/// generated from its type. Each field gets the editor that the inspector would use for it,
+/// chosen by the registered [`crate::InspectorFactory`]s and the field's attributes. Fields
+/// which are nested structs become sections of their own; the remaining fields are grouped in
+/// a "General" section.
+///
+/// Edits are made to a [`SettingsDraft`] copy of the resource. "Apply" copies the draft to the
+/// resource, and "Revert" discards the changes. The resource must be registered with the type
+/// registry, and be a struct.
+///
+/// Unlike [`crate::Inspector`], this doesn't depend on the inspector panel, so it can be used
+/// in a game's own options screen.
+pub struct SettingsPanel<T: Resource + Reflect + Clone> {
+    /// Title of the section holding the fields which aren't nested structs.
+    pub general_title: String,
+    marker: std::marker::PhantomData<T>,
+}
+
+impl<T: Resource + Reflect + Clone> Default for SettingsPanel<T> {
+    fn default() -> Self {
+        Self {
+            general_title: "General".to_string(),
+            marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T: Resource + Reflect + Clone> SettingsPanel<T> {
+    /// Create a new settings panel for the resource `T`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the title of the section holding the fields which aren't nested structs.
+    pub fn general_title(mut self, title: impl Into<String>) -> Self {
+        self.general_title = title.into();
+        self
+    }
+}
+
+impl<T: Resource + Reflect + Clone> ViewTemplate for SettingsPanel<T> {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let initial = cx.world().resource::<T>().clone();
+        cx.world_mut().insert_resource(SettingsDraft(initial));
+        cx.on_cleanup(|world| {
+            world.commands().remove_resource::<SettingsDraft<T>>();
+        });
+
+        let root: Arc<dyn InspectableRoot> = Arc::new(InspectableSettingsDraft::<T> {
+            marker: std::marker::PhantomData,
+        });
+        let draft = cx.read_resource::<SettingsDraft<T>>();
+        let info = draft.0.get_represented_type_info().unwrap();
+        let ReflectRef::Struct(st) = draft.0.reflect_ref() else {
+            panic!("SettingsPanel requires a struct resource");
+        };
+
+        // Split the fields into rows and sections. The set of fields of a struct never
+        // changes, so this only needs to be done once.
+        let mut rows: Vec<ViewRef> = Vec::new();
+        let mut sections: Vec<ViewRef> = Vec::new();
+        for findex in 0..st.field_len() {
+            let name = st.name_at(findex).unwrap();
+            let field = Arc::new(settings_field(&root, info, name));
+            if is_section(st.field_at(findex).unwrap()) {
+                sections.push(
+                    InspectorPanel::new()
+                        .title(name.to_string())
+                        .body(StructFieldList(field))
+                        .expanded(true)
+                        .into_view(),
+                );
+            } else {
+                rows.push(NamedFieldInspector { field }.into_view());
+            }
+        }
+        if !rows.is_empty() {
+            sections.insert(
+                0,
+                InspectorPanel::new()
+                    .title(self.general_title.clone())
+                    .body(Fragment::from_slice(&rows))
+                    .expanded(true)
+                    .into_view(),
+            );
+        }
+
+        let modified = cx.create_memo(|cx| {
+            let draft = cx.read_resource::<SettingsDraft<T>>();
+            let live = cx.read_resource::<T>();
+            draft.0.reflect_partial_eq(live) == Some(false)
+        });
+        let on_apply = cx.create_callback(|cx: &mut Cx, ()| {
+            let draft = cx.read_resource::<SettingsDraft<T>>().0.clone();
+            cx.world_mut().insert_resource(draft);
+        });
+        let on_revert = cx.create_callback(|cx: &mut Cx, ()| {
+            let live = cx.read_resource::<T>().clone();
+            cx.world_mut().insert_resource(SettingsDraft(live));
+        });
+
+        Element::<Node>::new()
+            .style(style_settings_panel)
+            .children((
+                Fragment::from_slice(&sections),
+                Element::<Node>::new()
+                    .style(style_settings_footer)
+                    .children((
+                        Button::new()
+                            .children("Revert")
+                            .disabled(cx.create_derived(move |cx| !modified.get(cx)))
+                            .on_click(on_revert),
+                        Button::new()
+                            .children("Apply")
+                            .variant(ButtonVariant::Primary)
+                            .disabled(cx.create_derived(move |cx| !modified.get(cx)))
+                            .on_click(on_apply),
+                    )),
+            ))
+    }
+}
@@ -11,7 +11,7 @@ use obsidian_ui::{
     typography,
 };
 
-use crate::Inspectable;
+use crate::{templates::field_menu::FieldMenu, Inspectable};
 
 fn style_field_label(ss: &mut StyleBuilder) {
     ss.display(ui::Display::Flex)
@@ -23,6 +23,19 @@ fn style_field_label(ss: &mut StyleBuilder) {
         .padding_left(16);
 }
 
+fn style_field_modified(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .left(6)
+        .width(4)
+        .height(4)
+        .border_radius(2.0)
+        .background_color(colors::ACCENT);
+}
+
+fn style_field_mixed(ss: &mut StyleBuilder) {
+    ss.font_size(14).color(colors::DIM).margin_left(4);
+}
+
 /// Label for editable struct field in an inspector.
 pub struct FieldLabel {
     /// The content of the label.
@@ -36,10 +49,28 @@ impl ViewTemplate for FieldLabel {
         let remove = cx.create_callback(move |cx, _| {
             field.remove(cx);
         });
+        let field = self.field.clone();
+        let is_mixed = cx.create_memo(move |cx| field.is_mixed(cx));
+        let field = self.field.clone();
+        let is_modified = cx.create_memo(move |cx| field.is_modified(cx));
         Element::<Node>::new()
             .style((typography::text_default, style_field_label))
             .children((
+                Cond::new(
+                    is_modified,
+                    || Element::<Node>::new().style(style_field_modified),
+                    || (),
+                ),
                 self.field.name(),
+                Cond::new(
+                    is_mixed,
+                    || {
+                        Element::<Node>::new()
+                            .style(style_field_mixed)
+                            .children("(mixed)")
+                    },
+                    || (),
+                ),
                 Cond::new(
                     can_remove,
                     move || {
@@ -51,6 +82,9 @@ impl ViewTemplate for FieldLabel {
                     || (),
                 ),
                 Spacer,
+                FieldMenu {
+                    field: self.field.clone(),
+                },
             ))
     }
 }
@@ -112,6 +146,9 @@ impl ViewTemplate for FieldLabelWide {
                 self.name.clone(),
                 Spacer,
                 self.buttons.clone(),
+                FieldMenu {
+                    field: self.field.clone(),
+                },
                 Cond::new(
                     can_remove,
                     move || {
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use bevy::prelude::*;
+use bevy_mod_stylebuilder::*;
+use bevy_reactor::*;
+use bevy_reactor_signals::{Cx, RunContextSetup};
+use obsidian_ui::{
+    colors,
+    controls::{Icon, MenuButton, MenuDivider, MenuItem, MenuPopup},
+    floating::FloatAlign,
+    size::Size,
+};
+
+use crate::{
+    clipboard::{can_paste, copy_field, paste_field},
+    Inspectable,
+};
+
+fn style_menu_icon(ss: &mut StyleBuilder) {
+    ss.margin((2, 0));
+}
+
+/// Menu attached to a field label with actions for the field value: copy, paste and reset.
+pub struct FieldMenu {
+    /// The field to copy from or paste into.
+    pub field: Arc<Inspectable>,
+}
+
+impl ViewTemplate for FieldMenu {
+    fn create(&self, cx: &mut Cx) -> impl IntoView {
+        let field = self.field.clone();
+        let copy = cx.create_callback(move |cx, _| copy_field(cx, &field));
+        let field = self.field.clone();
+        let paste = cx.create_callback(move |cx, _| paste_field(cx, &field));
+        let field = self.field.clone();
+        let paste_disabled = cx.create_memo(move |cx| !can_paste(cx, &field));
+        let field = self.field.clone();
+        let reset = cx.create_callback(move |cx, _| field.reset(cx));
+        let field = self.field.clone();
+        let reset_disabled = cx.create_memo(move |cx| !field.is_modified(cx));
+
+        MenuButton::new()
+            .children(
+                Icon::new("obsidian_ui://icons/tune.png")
+                    .size(Vec2::splat(12.0))
+                    .style(style_menu_icon)
+                    .color(Color::from(colors::DIM)),
+            )
+            .popup(
+                MenuPopup::new().align(FloatAlign::End).children((
+                    MenuItem::new().label("Copy").on_click(copy),
+                    MenuItem::new()
+                        .label("Paste")
+                        .disabled(paste_disabled)
+                        .on_click(paste),
+                    MenuDivider,
+                    MenuItem::new()
+                        .label("Reset to Default")
+                        .disabled(reset_disabled)
+                        .on_click(reset),
+                )),
+            )
+            .size(Size::Xxs)
+            .minimal(true)
+            .no_caret(true)
+    }
+}
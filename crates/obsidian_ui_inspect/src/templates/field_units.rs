@@ -0,0 +1,50 @@
+use bevy::{prelude::*, ui};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor::*;
+use bevy_reactor_signals::Cx;
+use obsidian_ui::{colors, typography};
+
+fn style_field_units(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .column_gap(4);
+}
+
+fn style_editor(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch)
+        .flex_grow(1.)
+        .min_width(0);
+}
+
+fn style_units(ss: &mut StyleBuilder) {
+    ss.font_size(14).color(colors::DIM);
+}
+
+/// Field editor followed by a units suffix, as specified by the [`crate::Units`] attribute.
+/// If there are no units, the editor is displayed as-is.
+pub struct FieldUnits {
+    /// The editor for the field value.
+    pub editor: ViewRef,
+    /// The units label, if any.
+    pub units: Option<&'static str>,
+}
+
+impl ViewTemplate for FieldUnits {
+    fn create(&self, _cx: &mut Cx) -> impl IntoView {
+        match self.units {
+            Some(units) => Element::<Node>::new()
+                .style((typography::text_default, style_field_units))
+                .children((
+                    Element::<Node>::new()
+                        .style(style_editor)
+                        .children(self.editor.clone()),
+                    Element::<Node>::new().style(style_units).children(units),
+                ))
+                .into_view(),
+            None => self.editor.clone(),
+        }
+    }
+}
@@ -1,4 +1,6 @@
 pub mod color_edit;
 pub mod field_label;
+pub mod field_menu;
 pub mod field_readonly_value;
+pub mod field_units;
 pub mod inspector_panel;
@@ -2,7 +2,9 @@
 
 use bevy::{color::palettes, ecs::world::DeferredWorld, prelude::*, ui};
 use bevy_mod_stylebuilder::*;
-use bevy_reactor_builder::{CreateChilden, EntityStyleBuilder, InvokeUiTemplate, TextBuilder};
+use bevy_reactor_builder::{
+    CreateChilden, EntityStyleBuilder, InvokeUiTemplate, SlottedTemplate, TextBuilder,
+};
 use bevy_reactor_obsidian::{
     animation::{BistableTransitionState, CreateBistableTransition},
     controls::Button,
@@ -103,16 +105,14 @@ fn setup_view_root(world: &mut World) {
                     Dialog::new()
                         .open(open.signal())
                         .on_close(on_close)
-                        .children(move |builder| {
-                            builder.invoke(DialogHeader::new().children(|builder| {
-                                builder.text("Dialog Header");
-                            }));
-                            builder.invoke(DialogBody::new().children(|builder| {
-                                builder.text("Dialog Body");
-                            }));
-                            builder.invoke(DialogFooter::new().children(move |builder| {
-                                builder.invoke(Button::new().labeled("Close").on_click(on_close));
-                            }));
+                        .slot("header", |builder| {
+                            builder.text("Dialog Header");
+                        })
+                        .slot("body", |builder| {
+                            builder.text("Dialog Body");
+                        })
+                        .slot("footer", move |builder| {
+                            builder.invoke(Button::new().labeled("Close").on_click(on_close));
                         }),
                 );
             });
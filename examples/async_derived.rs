@@ -0,0 +1,77 @@
+//! Example of a signal computed by a future. This also runs on wasm:
+//!
+//! ```sh
+//! cargo run --example async_derived --target wasm32-unknown-unknown
+//! ```
+
+use bevy::{color::palettes, prelude::*};
+use bevy_mod_stylebuilder::*;
+use bevy_reactor_builder::{
+    AsyncDerivedBuilder, CreateChilden, EntityStyleBuilder, InvokeUiTemplate, SuspenseBuilder,
+    TextBuilder, UiBuilder, UiTemplate,
+};
+use bevy_reactor_signals::SignalsPlugin;
+
+fn style_test(ss: &mut StyleBuilder) {
+    ss.display(Display::Flex)
+        .flex_direction(FlexDirection::Column)
+        .border(3)
+        .padding(3)
+        .row_gap(4);
+}
+
+#[derive(Resource, Default)]
+struct Counter {
+    count: u64,
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins((SignalsPlugin, StyleBuilderPlugin))
+        .init_resource::<Counter>()
+        .add_systems(Startup, setup_view_root)
+        .add_systems(Update, handle_key_input)
+        .run();
+}
+
+fn setup_view_root(world: &mut World) {
+    let camera = world.spawn((Camera::default(), Camera2d)).id();
+
+    world
+        .spawn(Node::default())
+        .style(style_test)
+        .insert(TargetCamera(camera))
+        .insert(BorderColor(palettes::css::LIME.into()))
+        .create_children(|builder| {
+            builder.text("Press space to increment the counter.");
+            builder.invoke(SumOfCounter);
+        });
+}
+
+struct SumOfCounter;
+
+impl UiTemplate for SumOfCounter {
+    fn build(&self, builder: &mut UiBuilder) {
+        let sum = builder.create_async_derived(|ecx| {
+            // Read the inputs synchronously; the future itself can't access the world.
+            let n = ecx.read_resource::<Counter>().count * 1_000_000;
+            async move { (0..=n).sum::<u64>() }
+        });
+        builder.suspense(
+            sum,
+            |builder| {
+                builder.text("Computing...");
+            },
+            |builder, value| {
+                builder.text(format!("Sum: {}", value));
+            },
+        );
+    }
+}
+
+fn handle_key_input(mut counter: ResMut<Counter>, key: Res<ButtonInput<KeyCode>>) {
+    if key.just_pressed(KeyCode::Space) {
+        counter.count += 1;
+    }
+}
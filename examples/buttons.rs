@@ -2,6 +2,7 @@
 
 use bevy::{
     asset::io::{file::FileAssetReader, AssetSource},
+    ecs::world::DeferredWorld,
     prelude::*,
     ui,
 };
@@ -216,52 +217,27 @@ fn setup_view_root(world: &mut World) {
                 .spawn(Node::default())
                 .style(style_row)
                 .create_children(|builder| {
-                    builder.invoke(ToolPalette::new().columns(3).children(|builder| {
-                        builder
-                            .invoke(
-                                ToolButton::new()
-                                    .children(|builder| {
+                    let selected = builder.create_mutable::<Option<usize>>(Some(0));
+                    let on_change = builder.create_callback(
+                        move |index: In<usize>, mut world: DeferredWorld| {
+                            selected.set(&mut world, Some(*index));
+                        },
+                    );
+                    builder.invoke(
+                        ToolPalette::new()
+                            .columns(3)
+                            .selected(selected)
+                            .on_change(on_change)
+                            .children(|builder| {
+                                for _ in 0..5 {
+                                    builder.invoke(ToolButton::new().children(|builder| {
                                         builder.invoke(Icon::new(
                                             "obsidian_ui://icons/chevron_left.png",
                                         ));
-                                    })
-                                    .selected(true)
-                                    .corners(RoundedCorners::TopLeft),
-                            )
-                            .invoke(ToolButton::new().children(|builder| {
-                                builder.invoke(Icon::new("obsidian_ui://icons/chevron_left.png"));
-                            }))
-                            .invoke(
-                                ToolButton::new()
-                                    .children(|builder| {
-                                        builder.invoke(Icon::new(
-                                            "obsidian_ui://icons/chevron_left.png",
-                                        ));
-                                    })
-                                    .corners(RoundedCorners::TopRight),
-                            )
-                            .invoke(
-                                ToolButton::new()
-                                    .children(|builder| {
-                                        builder.invoke(Icon::new(
-                                            "obsidian_ui://icons/chevron_left.png",
-                                        ));
-                                    })
-                                    .corners(RoundedCorners::BottomLeft),
-                            )
-                            .invoke(ToolButton::new().children(|builder| {
-                                builder.invoke(Icon::new("obsidian_ui://icons/chevron_left.png"));
-                            }))
-                            .invoke(
-                                ToolButton::new()
-                                    .children(|builder| {
-                                        builder.invoke(Icon::new(
-                                            "obsidian_ui://icons/chevron_left.png",
-                                        ));
-                                    })
-                                    .corners(RoundedCorners::BottomRight),
-                            );
-                    }));
+                                    }));
+                                }
+                            }),
+                    );
                 });
         });
 }
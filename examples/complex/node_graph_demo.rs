@@ -259,15 +259,15 @@ impl ViewTemplate for NodeGraphDemo {
             .children((
                 For::each(
                     |cx| {
-                        let graph = cx.read_resource::<DemoGraphRoot>();
-                        graph.edges.clone().into_iter()
+                        cx.use_resource_map(|graph: &DemoGraphRoot| graph.edges.clone())
+                            .into_iter()
                     },
                     |id| EdgeTemplate { id: *id },
                 ),
                 For::each(
                     |cx| {
-                        let graph = cx.read_resource::<DemoGraphRoot>();
-                        graph.nodes.clone().into_iter()
+                        cx.use_resource_map(|graph: &DemoGraphRoot| graph.nodes.clone())
+                            .into_iter()
                     },
                     |id| NodeTemplate { id: *id },
                 ),
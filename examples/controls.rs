@@ -43,6 +43,12 @@ fn main() {
             StyleBuilderPlugin,
             ObsidianUiPlugin,
         ))
+        .register_palette_command("Say Hello", |_: In<()>| {
+            info!("Hello from the command palette!");
+        })
+        .register_palette_command("Quit", |_: In<()>, mut exit: EventWriter<AppExit>| {
+            exit.send(AppExit::Success);
+        })
         .add_systems(Startup, setup_view_root)
         .add_systems(Update, close_on_esc)
         .run();
@@ -76,21 +82,32 @@ fn setup_view_root(world: &mut World) {
                 .style(style_row)
                 .create_children(|builder| {
                     let selected = builder.create_mutable::<Srgba>(palettes::css::BLUE);
+                    let swatches = builder.create_mutable::<Vec<Srgba>>(vec![
+                        palettes::css::BLUE,
+                        palettes::css::RED,
+                        palettes::css::GREEN,
+                        palettes::css::REBECCA_PURPLE,
+                    ]);
                     let on_change = builder.create_callback(
                         move |color: In<Srgba>, mut world: DeferredWorld| {
                             selected.set(&mut world, *color);
                         },
                     );
+                    let on_reorder = builder.create_callback(
+                        move |indices: In<(usize, usize)>, mut world: DeferredWorld| {
+                            let (from, to) = *indices;
+                            let mut colors = swatches.get_clone(&mut world);
+                            let color = colors.remove(from);
+                            colors.insert(to, color);
+                            swatches.set_clone(&mut world, colors);
+                        },
+                    );
                     builder.invoke(
-                        SwatchGrid::new(vec![
-                            palettes::css::BLUE,
-                            palettes::css::RED,
-                            palettes::css::GREEN,
-                            palettes::css::REBECCA_PURPLE,
-                        ])
-                        .grid_size(UVec2::new(12, 4))
-                        .selected(selected.signal())
-                        .on_change(on_change),
+                        SwatchGrid::new(swatches.signal())
+                            .grid_size(UVec2::new(12, 4))
+                            .selected(selected.signal())
+                            .on_change(on_change)
+                            .on_reorder(on_reorder),
                     );
                 });
 
@@ -236,11 +253,112 @@ fn setup_view_root(world: &mut World) {
                         .on_change(on_change),
                 );
             });
+
+            builder.text("Accordion");
+            builder.invoke(
+                Accordion::new()
+                    .initially_expanded([0])
+                    .section("First", |builder| {
+                        builder.text("Contents of the first section");
+                    })
+                    .section("Second", |builder| {
+                        builder.text("Contents of the second section");
+                    })
+                    .section("Third", |builder| {
+                        builder.text("Contents of the third section");
+                    })
+                    .style(|ss: &mut StyleBuilder| {
+                        ss.width(200);
+                    }),
+            );
+
+            builder.text("NumberInput");
+            builder
+                .spawn(Node::default())
+                .style(style_column)
+                .create_children(|builder| {
+                    let value = builder.create_mutable::<f32>(14.);
+                    let on_change = builder.create_callback(
+                        move |new_value: In<f32>, mut world: DeferredWorld| {
+                            value.set(&mut world, *new_value);
+                        },
+                    );
+                    let position = builder.create_mutable(Vec3::new(1., 2., 3.));
+                    let on_position_change = builder.create_callback(
+                        move |new_value: In<Vec3>, mut world: DeferredWorld| {
+                            position.set(&mut world, *new_value);
+                        },
+                    );
+                    builder
+                        .invoke(
+                            NumberInput::new()
+                                .value(value)
+                                .min(0.)
+                                .max(100.)
+                                .on_change(on_change),
+                        )
+                        .invoke(
+                            Vec3Input::new()
+                                .value(position)
+                                .on_change(on_position_change),
+                        );
+                });
+
+            builder.text("Form");
+            builder
+                .spawn(Node::default())
+                .style(style_column)
+                .create_children(|builder| {
+                    let on_submit = builder.create_callback(|_: In<()>| info!("Form submitted"));
+                    let form = builder.create_form(Some(on_submit));
+                    let name = form.field(builder, String::new(), |name: &String| {
+                        if name.trim().is_empty() {
+                            Err("Name is required".to_string())
+                        } else {
+                            Ok(())
+                        }
+                    });
+                    let on_name_change = name.create_setter(builder);
+                    let name_error = name.create_error_signal(builder);
+                    let is_pristine = builder.create_derived(move |rcx| !form.is_dirty(rcx));
+                    let on_save = form.create_submit_callback(builder);
+                    let on_revert = form.create_reset_callback(builder);
+                    builder
+                        .invoke(
+                            TextInput::new()
+                                .value(name.value())
+                                .placeholder("Name")
+                                .on_change(on_name_change),
+                        )
+                        .invoke(FieldError::new(name_error));
+                    builder
+                        .spawn(Node::default())
+                        .style(style_row)
+                        .create_children(|builder| {
+                            builder
+                                .invoke(Button::new().labeled("Save").on_click(on_save))
+                                .invoke(
+                                    Button::new()
+                                        .labeled("Revert")
+                                        .disabled(is_pristine)
+                                        .on_click(on_revert),
+                                );
+                        });
+                });
+
+            builder.text("Press Ctrl+P to open the command palette, or ? to list shortcuts");
+            builder.invoke(CommandPalette::new());
+            builder.invoke(ShortcutHelp);
         });
 }
 
-pub fn close_on_esc(input: Res<ButtonInput<KeyCode>>, mut exit: EventWriter<AppExit>) {
-    if input.just_pressed(KeyCode::Escape) {
+pub fn close_on_esc(
+    input: Res<ButtonInput<KeyCode>>,
+    palette: Res<CommandPaletteState>,
+    mut exit: EventWriter<AppExit>,
+) {
+    // Escape dismisses the command palette, so don't also quit if it was open this frame.
+    if input.just_pressed(KeyCode::Escape) && !palette.open && !palette.is_changed() {
         exit.send(AppExit::Success);
     }
 }
@@ -2,7 +2,9 @@
 
 use bevy::{ecs::world::DeferredWorld, prelude::*, ui};
 use bevy_mod_stylebuilder::*;
-use bevy_reactor_builder::{CreateChilden, EntityStyleBuilder, InvokeUiTemplate, TextBuilder};
+use bevy_reactor_builder::{
+    CreateChilden, EntityStyleBuilder, InvokeUiTemplate, TextBuilder, UiBuilder,
+};
 use bevy_reactor_obsidian::{
     input_dispatch::DefaultKeyHandler, prelude::*, tab_navigation::TabGroup,
 };
@@ -28,6 +30,10 @@ fn style_panel(ss: &mut StyleBuilder) {
         .column_gap(4);
 }
 
+fn style_section_header(ss: &mut StyleBuilder) {
+    ss.padding((4, 2)).background_color(colors::U2);
+}
+
 #[derive(Resource)]
 pub struct LeftPanelWidth(f32);
 
@@ -85,7 +91,7 @@ fn setup_view_root(world: &mut World) {
                     builder.invoke(
                         ScrollView::new()
                             .style(|sb: &mut StyleBuilder| {
-                                sb.flex_grow(1.);
+                                sb.flex_grow(1.).clip_children(6.);
                             })
                             .scroll_enable_x(true)
                             .scroll_enable_y(true)
@@ -104,7 +110,18 @@ fn setup_view_root(world: &mut World) {
                     sb.flex_grow(1.);
                 })
                 .create_children(|builder| {
-                    builder.text("Middle");
+                    builder.invoke(
+                        ScrollView::new()
+                            .style(|sb: &mut StyleBuilder| {
+                                sb.flex_grow(1.).clip_children(6.);
+                            })
+                            .scroll_enable_y(true)
+                            .children(|builder| {
+                                for section in ["Alpha", "Beta", "Gamma", "Delta"] {
+                                    build_section(builder, section);
+                                }
+                            }),
+                    );
                 });
 
             builder.invoke(
@@ -129,6 +146,24 @@ fn setup_view_root(world: &mut World) {
         });
 }
 
+/// A list section whose header sticks to the top until the next section pushes it out.
+fn build_section(builder: &mut UiBuilder, section: &'static str) {
+    builder
+        .spawn(Node::default())
+        .style(style_panel)
+        .create_children(|builder| {
+            builder
+                .spawn((Node::default(), Sticky::Top))
+                .style(style_section_header)
+                .create_children(|builder| {
+                    builder.text(section);
+                });
+            for item in 1..=12 {
+                builder.text(format!("{} item {}", section, item));
+            }
+        });
+}
+
 pub fn close_on_esc(input: Res<ButtonInput<KeyCode>>, mut exit: EventWriter<AppExit>) {
     if input.just_pressed(KeyCode::Escape) {
         exit.send(AppExit::Success);